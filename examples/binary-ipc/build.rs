@@ -0,0 +1,3 @@
+fn main() {
+	millennium_build::build()
+}
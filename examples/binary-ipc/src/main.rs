@@ -0,0 +1,16 @@
+#![cfg_attr(all(not(debug_assertions), target_os = "windows"), windows_subsystem = "windows")]
+
+#[millennium::command]
+fn receive_base64(data: String) -> Result<usize, String> {
+	base64::decode(data).map(|bytes| bytes.len()).map_err(|e| e.to_string())
+}
+
+fn main() {
+	millennium::Builder::default()
+		.invoke_handler(millennium::generate_handler![receive_base64])
+		.on_binary_message(|window, data| {
+			window.emit("binary-received", data.len()).expect("failed to emit event");
+		})
+		.run(millennium::generate_context!())
+		.expect("error while running application");
+}
@@ -3,7 +3,7 @@
 use std::sync::Mutex;
 
 use millennium::Manager;
-use millennium_plugin_vibrancy::{apply_acrylic, apply_mica, clear_acrylic, clear_mica};
+use millennium_plugin_vibrancy::{apply_acrylic, apply_mica, clear_acrylic, clear_mica, Error as VibrancyError};
 
 struct AcrylicState {
 	mica: Mutex<bool>,
@@ -53,7 +53,21 @@ fn main() {
 		.invoke_handler(millennium::generate_handler![toggle_mica, toggle_acrylic])
 		.setup(|app| {
 			let main = app.get_window("main").unwrap();
-			apply_mica(&main)?;
+			// respect "reduce transparency" in the system's accessibility settings
+			if main.accessibility_preferences()?.reduce_transparency {
+				*app.state::<AcrylicState>().mica.lock().unwrap() = false;
+			} else {
+				match apply_mica(&main) {
+					// Windows 10 doesn't have Mica, fall back to Acrylic
+					Err(VibrancyError::UnsupportedPlatformVersion { .. }) => {
+						let state = app.state::<AcrylicState>();
+						*state.mica.lock().unwrap() = false;
+						*state.acrylic.lock().unwrap() = true;
+						apply_acrylic(&main, None)?;
+					}
+					result => result?
+				}
+			}
 
 			Ok(())
 		})
@@ -46,7 +46,9 @@ export type EventName = LiteralUnion<
 	| 'millennium://file-drop'
 	| 'millennium://file-drop-hover'
 	| 'millennium://file-drop-cancelled'
-	| 'millennium://theme-changed',
+	| 'millennium://theme-changed'
+	| 'millennium://suspend'
+	| 'millennium://resume',
 	string
 >;
 
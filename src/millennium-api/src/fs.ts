@@ -35,7 +35,8 @@
  * 				"createDir": true,
  * 				"removeDir": true,
  * 				"removeFile": true,
- * 				"renameFile": true
+ * 				"renameFile": true,
+ * 				"watch": true
  * 			}
  * 		}
  * 	}
@@ -84,6 +85,7 @@
  */
 
 import { invokeMillenniumCommand } from './helpers/millennium';
+import { transformCallback } from './millennium';
 
 export enum BaseDirectory {
 	AUDIO = 1,
@@ -131,6 +133,21 @@ interface FsBinaryFileOptions {
 	contents: BinaryFileContents;
 }
 
+interface WatchOptions {
+	dir?: BaseDirectory;
+	/** Whether changes in subdirectories should be reported as well. */
+	recursive?: boolean;
+	/** Time in milliseconds to wait for further changes before emitting a batch of events. */
+	debounce?: number;
+}
+
+/** A change reported by {@link watch}. */
+interface WatchEvent {
+	kind: 'created' | 'modified' | 'removed' | 'renamed';
+	/** The affected paths. Paths outside of the filesystem scope are not reported. */
+	paths: string[];
+}
+
 interface FileEntry {
 	path: string;
 	/** Name of the directory/file. Can be null if the path terminates with `..`. */
@@ -368,6 +385,35 @@ export async function writeFile(path: string, contents: string | TypedArray | Bu
 		throw new Error(`Unsupported contents type: '${{}.toString.call(contents)}'.`);
 }
 
+/**
+ * Writes a UTF-8 string or a raw byte array to a file atomically, replacing the file if it exists.
+ *
+ * The contents are first written to a temporary file next to the target, which then replaces it, so if the app exits
+ * midway the file keeps either its previous or its new contents. Requires the `writeFile` allowlist.
+ *
+ * @param path The file path.
+ * @param contents The file contents.
+ * @param options Additional file writing options.
+ * @returns A promise that resolves when the file has been written.
+ */
+export async function writeFileAtomic(path: string, contents: string | BinaryFileContents, options?: FsOptions): Promise<void> {
+	return await invokeMillenniumCommand<void>({
+		__millenniumModule: 'Fs',
+		message: {
+			cmd: 'writeFileAtomic',
+			path,
+			contents: Array.from(
+				typeof contents === 'string'
+					? new TextEncoder().encode(contents)
+					: contents instanceof ArrayBuffer || contents instanceof SharedArrayBuffer
+						? new Uint8Array(contents)
+						: contents
+			),
+			options
+		}
+	});
+}
+
 const invokeBase = <T>(cmd: string, options: Record<string, any>): Promise<T> =>
 	invokeMillenniumCommand<T>({
 		__millenniumModule: 'Fs',
@@ -416,6 +462,16 @@ export async function removeFile(file: string, options: FsOptions = {}): Promise
 	return await invokeBase<void>('removeFile', { path: file, options });
 }
 
+/**
+ * Moves a file or directory to the trash (the recycle bin on Windows). Requires the `removeFile` allowlist.
+ *
+ * @returns A promise resolving to the location of the item inside the trash, or `null` if the platform doesn't report it.
+ * Only macOS reports the location.
+ */
+export async function moveToTrash(path: string, options: FsOptions = {}): Promise<string | null> {
+	return await invokeBase<string | null>('moveToTrash', { path, options });
+}
+
 /**
  * Renames/moves a file or directory.
  */
@@ -429,3 +485,18 @@ export async function rename(oldPath: string, newPath: string, options: FsOption
 export async function exists(path: string, options: FsOptions = {}): Promise<boolean> {
 	return await invokeBase<boolean>('exists', { path, options });
 }
+
+/**
+ * Watches a file or directory for changes. Requires the `watch` allowlist.
+ *
+ * The watcher is stopped when the returned function is called or when the window is closed.
+ *
+ * @param path The file or directory path.
+ * @param cb Called with the changes that happened since the last call.
+ * @param options Additional watch options.
+ * @returns A promise resolving to a function that stops the watcher.
+ */
+export async function watch(path: string, cb: (events: WatchEvent[]) => void, options: WatchOptions = {}): Promise<() => Promise<void>> {
+	const id = await invokeBase<number>('watch', { path, options, onEventFn: transformCallback(cb) });
+	return async () => await invokeBase<void>('unwatch', { id });
+}
@@ -30,6 +30,7 @@ declare global {
 		__MILLENNIUM_IPC__: (message: any) => void;
 		ipc: {
 			postMessage: (args: string) => void;
+			postBinaryMessage?: (data: ArrayBuffer | ArrayBufferView) => Promise<void>;
 		}
 	}
 }
@@ -92,6 +93,29 @@ export async function invoke<T>(cmd: string, args: InvokeArgs = {}): Promise<T>
 	});
 }
 
+/**
+ * Sends binary data to the backend without serializing it to JSON.
+ * The data is received by the handler registered with `Builder::on_binary_message`.
+ *
+ * Binary messages are not ordered relative to {@link invoke} calls, and are unavailable when using the isolation pattern.
+ *
+ * @param data The bytes to send.
+ * @example
+ * ```typescript
+ * import { postBinaryMessage } from '@pyke/millennium-api/millennium'
+ * const file = document.querySelector('input[type=file]').files[0]
+ * await postBinaryMessage(await file.arrayBuffer())
+ * ```
+ *
+ * @returns A Promise resolving once the message has been sent.
+ */
+export async function postBinaryMessage(data: ArrayBuffer | ArrayBufferView): Promise<void> {
+	if (!window.ipc.postBinaryMessage)
+		throw new Error('Binary messages are not available. Register a handler with `Builder::on_binary_message`.');
+
+	return window.ipc.postBinaryMessage(data);
+}
+
 /**
  * Convert a device file path to an URL that can be loaded by the webview.
  * Note that `asset:` and `https://asset.localhost` must be allowed on the `csp` value configured on `.millenniumrc > millennium > security`.
@@ -45,8 +45,8 @@ export async function appDir(): Promise<string> { return resolveBaseDirectory(Ba
 
 /**
  * Returns the path to the user's audio directory.
- * - **Linux**: resolves to `XDG_MUSIC_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/))
- * - **macOS**: resolves to `$HOME/Music`
+ * - **Linux**: resolves to `XDG_MUSIC_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/)) or `$HOME/Music`
+ * - **macOS**: resolves to `NSMusicDirectory` (`$HOME/Music`)
  * - **Windows**: resolves to `{FOLDERID_Music}` (`C:\Users\{username}\Music`)
  */
 export async function audioDir(): Promise<string> { return resolveBaseDirectory(BaseDirectory.AUDIO) }
@@ -77,24 +77,24 @@ export async function dataDir(): Promise<string> { return resolveBaseDirectory(B
 
 /**
  * Returns the path to the user's desktop directory.
- * - **Linux**: resolves to `XDG_DESKTOP_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/))
- * - **macOS**: resolves to `$HOME/Library/Desktop`
+ * - **Linux**: resolves to `XDG_DESKTOP_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/)) or `$HOME/Desktop`
+ * - **macOS**: resolves to `NSDesktopDirectory` (`$HOME/Desktop`)
  * - **Windows**: resolves to `{FOLDERID_Desktop}` (`C:\Users\{username}\Desktop`)
  */
 export async function desktopDir(): Promise<string> { return resolveBaseDirectory(BaseDirectory.DESKTOP) }
 
 /**
  * Returns the path to the user's documents directory.
- * - **Linux**: resolves to `XDG_DOCUMENTS_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/))
- * - **macOS**: resolves to `$HOME/Documents`
+ * - **Linux**: resolves to `XDG_DOCUMENTS_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/)) or `$HOME/Documents`
+ * - **macOS**: resolves to `NSDocumentDirectory` (`$HOME/Documents`)
  * - **Windows**: resolves to `{FOLDERID_Documents}` (`C:\Users\{username}\Documents`)
  */
 export async function documentsDir(): Promise<string> { return resolveBaseDirectory(BaseDirectory.DOCUMENT) }
 
 /**
  * Returns the path to the user's downloads directory.
- * - **Linux**: resolves to `XDG_DOWNLOAD_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/))
- * - **macOS**: resolves to `$HOME/Downloads`
+ * - **Linux**: resolves to `XDG_DOWNLOAD_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/)) or `$HOME/Downloads`
+ * - **macOS**: resolves to `NSDownloadsDirectory` (`$HOME/Downloads`)
  * - **Windows**: resolves to `{FOLDERID_Downloads}` (`C:\Users\{username}\Downloads`)
  */
 export async function downloadsDir(): Promise<string> { return resolveBaseDirectory(BaseDirectory.DOWNLOAD) }
@@ -133,16 +133,16 @@ export async function localDataDir(): Promise<string> { return resolveBaseDirect
 
 /**
  * Returns the path to the user's picture directory.
- * - **Linux**: resolves to `XDG_PICTURES_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/))
- * - **macOS**: resolves to `$HOME/Pictures`
+ * - **Linux**: resolves to `XDG_PICTURES_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/)) or `$HOME/Pictures`
+ * - **macOS**: resolves to `NSPicturesDirectory` (`$HOME/Pictures`)
  * - **Windows**: resolves to `{FOLDERID_Pictures}` (`C:\Users\{username}\Pictures`)
  */
 export async function pictureDir(): Promise<string> { return resolveBaseDirectory(BaseDirectory.PICTURES) }
 
 /**
  * Returns the path to the user's public directory.
- * - **Linux**: resolves to `XDG_PUBLICSHARE_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/))
- * - **macOS**: resolves to `$HOME/Public`
+ * - **Linux**: resolves to `XDG_PUBLICSHARE_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/)) or `$HOME/Public`
+ * - **macOS**: resolves to `NSSharedPublicDirectory` (`$HOME/Public`)
  * - **Windows**: resolves to `{FOLDERID_Public}` (`C:\Users\{username}\Public`)
  */
 export async function publicDir(): Promise<string> { return resolveBaseDirectory(BaseDirectory.PUBLIC) }
@@ -162,7 +162,7 @@ export async function runtimeDir(): Promise<string> { return resolveBaseDirector
 
 /**
  * Returns the path to the user's template directory.
- * - **Linux**: resolves to `XDG_TEMPLATES_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/))
+ * - **Linux**: resolves to `XDG_TEMPLATES_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/)) or `$HOME/Templates`
  * - **macOS**: Not supported.
  * - **Windows**: resolves to `{FOLDERID_Templates}` (`C:\Users\{username}\Templates`)
  */
@@ -170,8 +170,8 @@ export async function templateDir(): Promise<string> { return resolveBaseDirecto
 
 /**
  * Returns the path to the user's videos directory.
- * - **Linux**: resolves to `XDG_VIDEOS_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/))
- * - **macOS**: resolves to `$HOME/Movies`
+ * - **Linux**: resolves to `XDG_VIDEOS_DIR` ([`xdg-user-dirs`](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/)) or `$HOME/Videos`
+ * - **macOS**: resolves to `NSMoviesDirectory` (`$HOME/Movies`)
  * - **Windows**: resolves to `{FOLDERID_Videos}` (`C:\Users\{username}\Videos`)
  */
 export async function videosDir(): Promise<string> { return resolveBaseDirectory(BaseDirectory.VIDEOS) }
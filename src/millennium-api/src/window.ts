@@ -55,6 +55,7 @@
  * 				"setCursorIcon": true,
  * 				"setCursorPosition": true,
  * 				"startDragging": true,
+ * 				"startDrag": true,
  * 				"print": true
  * 			}
  * 		}
@@ -99,6 +100,12 @@
  * #### 'millennium://blur'
  * Emitted when the window loses focus.
  *
+ * #### 'millennium://suspend'
+ * Emitted when the window's webview is suspended.
+ *
+ * #### 'millennium://resume'
+ * Emitted when the window's webview is resumed.
+ *
  * #### 'millennium://scale-change'
  * Emitted when the window's scale factor has changed.
  * The following user actions can cause DPI changes:
@@ -127,6 +134,7 @@ import { invokeMillenniumCommand } from './helpers/millennium';
 import { emit, listen, once, EventName, EventCallback, Unlistener } from './event';
 
 export type Theme = 'light' | 'dark';
+export type LayoutDirection = 'ltr' | 'rtl';
 
 export interface Monitor {
 	name: string | null;
@@ -138,6 +146,16 @@ export interface Monitor {
 	scaleFactor: number;
 }
 
+/** The display preferences the user set in the system's accessibility settings. */
+export interface AccessibilityPreferences {
+	/** Animations and motion effects should be minimized. */
+	reduceMotion: boolean;
+	/** Content should be drawn with higher contrast. */
+	increaseContrast: boolean;
+	/** Translucent effects such as blur and vibrancy should be replaced with opaque backgrounds. */
+	reduceTransparency: boolean;
+}
+
 export class LogicalSize {
 	public readonly TYPE = 'Logical';
 
@@ -245,6 +263,8 @@ export type CursorIcon =
 	| 'colResize'
 	| 'rowResize';
 
+export type ResizeDirection = 'east' | 'north' | 'northEast' | 'northWest' | 'south' | 'southEast' | 'southWest' | 'west';
+
 /**
  * Get an instance of `WebviewWindow` for the current webview window.
  */
@@ -395,13 +415,45 @@ class WindowManager extends WebviewWindowHandle {
 	 * Gets the window's current theme.
 	 *
 	 * ## Platform-specific
-	 * - **Linux**: Not implemented; always returns `light`.
+	 * - **Linux**: Reads the `color-scheme` setting of the XDG desktop portal, falling back to the GTK theme.
 	 * - **macOS**: Window themes were introduced in macOS 10.14. Returns `light` on macOS 10.13 and below.
 	 */
 	public async theme(): Promise<Theme | null> {
 		return await this._manage('theme');
 	}
 
+	/**
+	 * Gets the accent color the user picked for the system as `[red, green, blue, alpha]`, each in `0..=255`. Returns `null` if the platform doesn't expose one.
+	 *
+	 * ## Platform-specific
+	 * - **macOS**: Supported on macOS 10.14+.
+	 * - **Linux**: Reads the `accent-color` setting of the XDG desktop portal, falling back to the GTK theme's selection color.
+	 */
+	public async accentColor(): Promise<[number, number, number, number] | null> {
+		return await this._manage('accentColor');
+	}
+
+	/**
+	 * Gets the display preferences set in the system's accessibility settings.
+	 *
+	 * ## Platform-specific
+	 * - **Linux**: `reduceTransparency` is always `false`.
+	 */
+	public async accessibilityPreferences(): Promise<AccessibilityPreferences> {
+		return await this._manage('accessibilityPreferences');
+	}
+
+	/**
+	 * The resident memory of the process rendering the window's webview in bytes, useful for diagnostics. Returns `null` if it can't be determined.
+	 *
+	 * ## Platform-specific
+	 * - **Windows**: Reports the WebView2 browser process, which is shared by all windows.
+	 * - **Linux**: Reports the combined memory of all WebKit web processes of the application.
+	 */
+	public async memoryUsage(): Promise<number | null> {
+		return await this._manage('memoryUsage');
+	}
+
 	/** Centers the window on the display the window is currently on. */
 	public async center(): Promise<void> {
 		return await this._manage('center');
@@ -630,6 +682,36 @@ class WindowManager extends WebviewWindowHandle {
 	async startDragging(): Promise<void> {
 		return await this._manage('startDragging');
 	}
+
+	/**
+	 * Starts resizing the window from the given border or corner, for custom resize handles on undecorated windows.
+	 * Call this from a `mousedown` event handler.
+	 *
+	 * @param direction The border or corner to resize from.
+	 */
+	async startResizeDragging(direction: ResizeDirection): Promise<void> {
+		return await this._manage('startResizeDragging', direction);
+	}
+
+	/**
+	 * Starts dragging files out of the window, so they can be dropped into other applications like the file manager.
+	 * Call this from a `mousedown` event handler.
+	 *
+	 * The paths must be allowed by the `fs` scope.
+	 *
+	 * ### Platform-specific
+	 *
+	 * - **Windows**: The `icon` is ignored; the shell provides the drag image.
+	 *
+	 * @param paths Absolute paths of the files to drag.
+	 * @param icon Raw icon RGBA bytes or path to an icon file to show under the cursor. Defaults to the file's icon.
+	 */
+	async startDrag(paths: string[], icon?: string | Uint8Array): Promise<void> {
+		return await this._manage('startDrag', {
+			paths,
+			icon: icon === undefined || typeof icon === 'string' ? icon : Array.from(icon)
+		});
+	}
 }
 
 /**
@@ -763,6 +845,10 @@ export interface WindowOptions {
 	 * Currently only implemented on Windows and macOS 10.14+.
 	 */
 	theme?: Theme;
+	/**
+	 * The layout direction of the window's menu bar and controls. Defaults to the direction of the system locale.
+	 */
+	layoutDirection?: LayoutDirection;
 }
 
 /**
@@ -277,6 +277,8 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
 	#[cfg(windows)]
 	if let Some(fixed_webview2_runtime_path) = &config.millennium.bundle.windows.webview_fixed_runtime_path {
 		resources.push(fixed_webview2_runtime_path.display().to_string());
+	} else if let millennium_utils::config::WebviewInstallMode::FixedRuntime { path } = &config.millennium.bundle.windows.webview_install_mode {
+		resources.push(path.display().to_string());
 	}
 
 	copy_resources(ResourcePaths::new(resources.as_slice(), true), target_dir)?;
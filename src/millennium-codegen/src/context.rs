@@ -55,6 +55,7 @@ fn map_core_assets(options: &AssetOptions) -> impl Fn(&AssetKey, &Path, &mut Vec
 				::millennium_utils::html::inject_csp_token(&mut document);
 
 				inject_nonce_token(&mut document, &dangerous_disable_asset_csp_modification);
+				::millennium_utils::html::inject_csp_nonce_token(&mut document);
 
 				if dangerous_disable_asset_csp_modification.can_modify("script-src") {
 					if let Ok(inline_script_elements) = document.select("script:not(empty)") {
@@ -57,6 +57,7 @@
 use std::path::PathBuf;
 
 use instant::Instant;
+use url::Url;
 
 use crate::{
 	accelerator::AcceleratorId,
@@ -64,7 +65,7 @@ use crate::{
 	keyboard::{self, ModifiersState},
 	menu::{MenuId, MenuType},
 	platform_impl,
-	window::{Theme, WindowId}
+	window::{AccessibilityPreferences, Theme, WindowId}
 };
 
 /// Describes a generic event.
@@ -124,6 +125,56 @@ pub enum Event<'a, T: 'static> {
 	/// - **iOS / Android:** Unsupported.
 	GlobalShortcutEvent(AcceleratorId),
 
+	/// Emitted when the OS asks the application to open files or URLs, e.g.
+	/// when the user opens a file associated with the application or follows a
+	/// link using one of its custom URL schemes. Files are given as `file://`
+	/// URLs.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux / iOS / Android:** Unsupported. On Windows and Linux,
+	///   the files or URLs are passed as command line arguments instead.
+	Opened { urls: Vec<Url> },
+
+	/// Emitted when the system theme changes, e.g. when the user switches the
+	/// OS to dark mode. Unlike [`WindowEvent::ThemeChanged`], this is emitted
+	/// once for the whole application, even if it has no windows.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Follows the `color-scheme` setting of the XDG desktop
+	///   portal, falling back to the GTK theme.
+	/// - **iOS / Android:** Unsupported.
+	ThemeChanged(Theme),
+
+	/// Emitted when the system accent color changes. Carries the new color as
+	/// RGBA, see [`Window::accent_color`](crate::window::Window::accent_color).
+	///
+	/// ## Platform-specific
+	///
+	/// - **iOS / Android:** Unsupported.
+	AccentColorChanged((u8, u8, u8, u8)),
+
+	/// Emitted when the display preferences in the system's accessibility
+	/// settings change. See
+	/// [`Window::accessibility_preferences`](crate::window::Window::accessibility_preferences).
+	///
+	/// ## Platform-specific
+	///
+	/// - **iOS / Android:** Unsupported.
+	AccessibilityPreferencesChanged(AccessibilityPreferences),
+
+	/// Emitted when an item set with
+	/// [`EventLoopWindowTarget::set_jump_list`](crate::event_loop::EventLoopWindowTarget::set_jump_list)
+	/// is clicked. Carries the item's id.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Clicking an item launches a new instance of the app,
+	///   which emits this event once its event loop starts.
+	/// - **Linux / iOS / Android:** Unsupported.
+	JumpListItemClicked { id: String },
+
 	/// Emitted when the application has been suspended.
 	Suspended,
 
@@ -216,7 +267,12 @@ impl<T: Clone> Clone for Event<'static, T> {
 				event: *event,
 				position: *position
 			},
-			GlobalShortcutEvent(accelerator_id) => GlobalShortcutEvent(*accelerator_id)
+			GlobalShortcutEvent(accelerator_id) => GlobalShortcutEvent(*accelerator_id),
+			Opened { urls } => Opened { urls: urls.clone() },
+			ThemeChanged(theme) => ThemeChanged(*theme),
+			AccentColorChanged(color) => AccentColorChanged(*color),
+			AccessibilityPreferencesChanged(preferences) => AccessibilityPreferencesChanged(*preferences),
+			JumpListItemClicked { id } => JumpListItemClicked { id: id.clone() }
 		}
 	}
 }
@@ -237,7 +293,12 @@ impl<'a, T> Event<'a, T> {
 			Resumed => Ok(Resumed),
 			MenuEvent { window_id, menu_id, origin } => Ok(MenuEvent { window_id, menu_id, origin }),
 			TrayEvent { bounds, event, position } => Ok(TrayEvent { bounds, event, position }),
-			GlobalShortcutEvent(accelerator_id) => Ok(GlobalShortcutEvent(accelerator_id))
+			GlobalShortcutEvent(accelerator_id) => Ok(GlobalShortcutEvent(accelerator_id)),
+			Opened { urls } => Ok(Opened { urls }),
+			ThemeChanged(theme) => Ok(ThemeChanged(theme)),
+			AccentColorChanged(color) => Ok(AccentColorChanged(color)),
+			AccessibilityPreferencesChanged(preferences) => Ok(AccessibilityPreferencesChanged(preferences)),
+			JumpListItemClicked { id } => Ok(JumpListItemClicked { id })
 		}
 	}
 
@@ -258,7 +319,12 @@ impl<'a, T> Event<'a, T> {
 			Resumed => Some(Resumed),
 			MenuEvent { window_id, menu_id, origin } => Some(MenuEvent { window_id, menu_id, origin }),
 			TrayEvent { bounds, event, position } => Some(TrayEvent { bounds, event, position }),
-			GlobalShortcutEvent(accelerator_id) => Some(GlobalShortcutEvent(accelerator_id))
+			GlobalShortcutEvent(accelerator_id) => Some(GlobalShortcutEvent(accelerator_id)),
+			Opened { urls } => Some(Opened { urls }),
+			ThemeChanged(theme) => Some(ThemeChanged(theme)),
+			AccentColorChanged(color) => Some(AccentColorChanged(color)),
+			AccessibilityPreferencesChanged(preferences) => Some(AccessibilityPreferencesChanged(preferences)),
+			JumpListItemClicked { id } => Some(JumpListItemClicked { id })
 		}
 	}
 }
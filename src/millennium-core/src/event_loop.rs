@@ -32,7 +32,7 @@ use std::{error, fmt, ops::Deref};
 
 use instant::Instant;
 
-use crate::{event::Event, monitor::MonitorHandle, platform_impl};
+use crate::{event::Event, jump_list::JumpListItem, monitor::MonitorHandle, platform_impl};
 
 /// Provides a way to retrieve events from the system and from the windows that
 /// were registered to the events loop.
@@ -60,12 +60,21 @@ pub struct EventLoop<T: 'static> {
 /// executes your callback. `EventLoop` will coerce into this type (`impl<T>
 /// Deref for EventLoop<T>`), so functions that take this as a parameter can
 /// also take `&EventLoop`.
-#[derive(Clone)]
 pub struct EventLoopWindowTarget<T: 'static> {
 	pub(crate) p: platform_impl::EventLoopWindowTarget<T>,
 	pub(crate) _marker: ::std::marker::PhantomData<*mut ()> // Not Send nor Sync
 }
 
+// implemented manually so user events don't need to be `Clone`
+impl<T: 'static> Clone for EventLoopWindowTarget<T> {
+	fn clone(&self) -> Self {
+		Self {
+			p: self.p.clone(),
+			_marker: ::std::marker::PhantomData
+		}
+	}
+}
+
 impl<T> fmt::Debug for EventLoop<T> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		f.pad("EventLoop { .. }")
@@ -231,6 +240,22 @@ impl<T> EventLoopWindowTarget<T> {
 	pub fn primary_monitor(&self) -> Option<MonitorHandle> {
 		self.p.primary_monitor()
 	}
+
+	/// Sets the items of the app's jump list, replacing the previous ones. An
+	/// empty list removes it.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** The items are shown as tasks in the taskbar button's jump
+	///   list. Clicking one launches a new instance of the app with a
+	///   `--jump-list-item=<id>` argument, so apps that keep a single instance
+	///   need to forward the launch to the running one.
+	/// - **macOS:** The items are shown in the dock menu.
+	/// - **Linux / iOS / Android:** Unsupported.
+	#[inline]
+	pub fn set_jump_list(&self, items: Vec<JumpListItem>) {
+		self.p.set_jump_list(items)
+	}
 }
 
 /// Used to send custom events to `EventLoop`.
@@ -0,0 +1,40 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Quick actions shown in the app's taskbar jump list on Windows and in its
+//! dock menu on macOS, e.g. to open recent documents.
+//!
+//! The items are set with
+//! [`EventLoopWindowTarget::set_jump_list`](crate::event_loop::EventLoopWindowTarget::set_jump_list),
+//! and clicking one emits
+//! [`Event::JumpListItemClicked`](crate::event::Event::JumpListItemClicked).
+
+/// An item of the jump list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JumpListItem {
+	/// Identifies the item in
+	/// [`Event::JumpListItemClicked`](crate::event::Event::JumpListItemClicked).
+	pub id: String,
+	/// The text shown for the item.
+	pub title: String
+}
+
+impl JumpListItem {
+	/// Creates a new jump list item.
+	pub fn new<I: Into<String>, T: Into<String>>(id: I, title: T) -> Self {
+		Self { id: id.into(), title: title.into() }
+	}
+}
@@ -193,6 +193,7 @@ pub mod event;
 pub mod event_loop;
 pub mod global_shortcut;
 mod icon;
+pub mod jump_list;
 pub mod keyboard;
 pub mod menu;
 pub mod monitor;
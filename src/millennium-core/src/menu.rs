@@ -61,7 +61,8 @@ pub struct MenuItemAttributes<'a> {
 	title: &'a str,
 	keyboard_accelerator: Option<Accelerator>,
 	enabled: bool,
-	selected: bool
+	selected: bool,
+	checkable: bool
 }
 
 impl<'a> MenuItemAttributes<'a> {
@@ -76,7 +77,8 @@ impl<'a> MenuItemAttributes<'a> {
 			title,
 			keyboard_accelerator: None,
 			enabled: true,
-			selected: false
+			selected: false,
+			checkable: false
 		}
 	}
 
@@ -107,6 +109,18 @@ impl<'a> MenuItemAttributes<'a> {
 		self.selected = selected;
 		self
 	}
+
+	/// Render the item so that its check mark can be toggled later with
+	/// [`CustomMenuItem::set_selected`], even if it isn't initially selected.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / macOS:** Any item can show a check mark, so this has no effect.
+	/// - **Android / iOS:** Unsupported
+	pub fn with_checkable(mut self, checkable: bool) -> Self {
+		self.checkable = checkable;
+		self
+	}
 }
 
 /// Base `Menu` functions.
@@ -136,7 +150,7 @@ impl ContextMenu {
 	pub fn add_item(&mut self, item: MenuItemAttributes<'_>) -> CustomMenuItem {
 		self.0
 			.menu_platform
-			.add_item(item.id, item.title, item.keyboard_accelerator, item.enabled, item.selected, MenuType::ContextMenu)
+			.add_item(item.id, item.title, item.keyboard_accelerator, item.enabled, item.selected, item.checkable, MenuType::ContextMenu)
 	}
 
 	/// Add new item to this menu.
@@ -170,7 +184,7 @@ impl MenuBar {
 	pub fn add_item(&mut self, item: MenuItemAttributes<'_>) -> CustomMenuItem {
 		self.0
 			.menu_platform
-			.add_item(item.id, item.title, item.keyboard_accelerator, item.enabled, item.selected, MenuType::MenuBar)
+			.add_item(item.id, item.title, item.keyboard_accelerator, item.enabled, item.selected, item.checkable, MenuType::MenuBar)
 	}
 
 	/// Add new item to this menu.
@@ -385,6 +399,41 @@ impl CustomMenuItem {
 	pub fn set_icon(&mut self, icon: Icon) {
 		self.0.set_icon(icon)
 	}
+
+	/// Inserts a new item into the menu of this item, right before it.
+	///
+	/// Has no effect if this item isn't part of a menu yet, but still returns the
+	/// new item.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Android / iOS:** Unsupported
+	pub fn insert_before(&mut self, item: MenuItemAttributes<'_>) -> CustomMenuItem {
+		self.0
+			.insert_item(item.id, item.title, item.keyboard_accelerator, item.enabled, item.selected, item.checkable, false)
+	}
+
+	/// Inserts a new item into the menu of this item, right after it.
+	///
+	/// Has no effect if this item isn't part of a menu yet, but still returns the
+	/// new item.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Android / iOS:** Unsupported
+	pub fn insert_after(&mut self, item: MenuItemAttributes<'_>) -> CustomMenuItem {
+		self.0
+			.insert_item(item.id, item.title, item.keyboard_accelerator, item.enabled, item.selected, item.checkable, true)
+	}
+
+	/// Removes the menu item from its menu.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Android / iOS:** Unsupported
+	pub fn remove(&mut self) {
+		self.0.remove()
+	}
 }
 
 /// Identifier of a custom menu item.
@@ -16,7 +16,7 @@
 
 #![cfg(target_os = "macos")]
 
-use std::os::raw::c_void;
+use std::{os::raw::c_void, path::Path};
 
 use cocoa::{
 	appkit::{
@@ -69,6 +69,12 @@ pub trait WindowExtMacOS {
 
 	/// Returns the current window theme.
 	fn theme(&self) -> Theme;
+
+	/// Sets the file the window represents, showing its icon in the title bar.
+	///
+	/// The icon can be dragged to move the file and Cmd-clicked to reveal its
+	/// path. An empty path removes the icon.
+	fn set_represented_filename(&self, path: &Path);
 }
 
 impl WindowExtMacOS for Window {
@@ -106,6 +112,11 @@ impl WindowExtMacOS for Window {
 	fn theme(&self) -> Theme {
 		self.window.theme()
 	}
+
+	#[inline]
+	fn set_represented_filename(&self, path: &Path) {
+		self.window.set_represented_filename(path)
+	}
 }
 
 /// Corresponds to `NSApplicationActivationPolicy`.
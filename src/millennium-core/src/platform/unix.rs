@@ -16,10 +16,10 @@
 
 #![cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
 
-pub use crate::platform_impl::{hit_test, EventLoop as UnixEventLoop};
+pub use crate::platform_impl::{drag_region_hit_test, hit_test, EventLoop as UnixEventLoop};
 use crate::{
 	event_loop::EventLoop,
-	window::{Window, WindowBuilder}
+	window::{Theme, Window, WindowBuilder}
 };
 
 /// Additional methods on `Window` that are specific to Unix.
@@ -30,6 +30,10 @@ pub trait WindowExtUnix {
 
 	/// Whether to show the window icon in the taskbar or not.
 	fn set_skip_taskbar(&self, skip: bool);
+
+	/// Returns the current system theme, read from the XDG desktop portal's
+	/// `color-scheme` setting or, failing that, the GTK theme.
+	fn theme(&self) -> Theme;
 }
 
 impl WindowExtUnix for Window {
@@ -40,6 +44,10 @@ impl WindowExtUnix for Window {
 	fn set_skip_taskbar(&self, skip: bool) {
 		self.window.set_skip_taskbar(skip);
 	}
+
+	fn theme(&self) -> Theme {
+		crate::platform_impl::system_theme()
+	}
 }
 
 pub trait WindowBuilderExtUnix {
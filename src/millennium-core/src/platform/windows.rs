@@ -34,6 +34,12 @@ use crate::{
 	window::{BadIcon, Icon, Theme, Window, WindowBuilder}
 };
 
+/// Returns the build number of Windows 10 or 11, e.g. `22000` for the first release of Windows 11, or `None` on older
+/// versions of Windows.
+pub fn windows_build_number() -> Option<u32> {
+	crate::platform_impl::windows_build_number()
+}
+
 /// Additional methods on `EventLoop` that are specific to Windows.
 pub trait EventLoopExtWindows {
 	/// Creates an event loop off of the main thread.
@@ -134,6 +140,13 @@ pub trait WindowExtWindows {
 
 	/// Whether to show the window icon in the taskbar or not.
 	fn set_skip_taskbar(&self, skip: bool);
+
+	/// Sets the colors of the title bar background, the title text, and the
+	/// window border as RGBA. `None` restores the system's color. The alpha
+	/// channel is ignored.
+	///
+	/// This requires Windows 11 and does nothing on older versions.
+	fn set_title_bar_colors(&self, caption: Option<(u8, u8, u8, u8)>, text: Option<(u8, u8, u8, u8)>, border: Option<(u8, u8, u8, u8)>);
 }
 
 impl WindowExtWindows for Window {
@@ -178,6 +191,11 @@ impl WindowExtWindows for Window {
 	fn set_skip_taskbar(&self, skip: bool) {
 		self.window.set_skip_taskbar(skip);
 	}
+
+	#[inline]
+	fn set_title_bar_colors(&self, caption: Option<(u8, u8, u8, u8)>, text: Option<(u8, u8, u8, u8)>, border: Option<(u8, u8, u8, u8)>) {
+		self.window.set_title_bar_colors(caption, text, border);
+	}
 }
 
 /// Additional methods on `WindowBuilder` that are specific to Windows.
@@ -36,6 +36,7 @@ use crate::{
 	error, event,
 	event_loop::{self, ControlFlow},
 	icon::Icon,
+	jump_list::JumpListItem,
 	keyboard::{Key, KeyCode, KeyLocation, NativeKeyCode},
 	menu::{CustomMenuItem, MenuId, MenuItem, MenuType},
 	monitor, window
@@ -92,6 +93,7 @@ impl Menu {
 	pub fn new_popup_menu() -> Self {
 		Self::new()
 	}
+	#[allow(clippy::too_many_arguments)]
 	pub fn add_item(
 		&mut self,
 		_menu_id: MenuId,
@@ -99,6 +101,7 @@ impl Menu {
 		_accelerator: Option<Accelerator>,
 		_enabled: bool,
 		_selected: bool,
+		_checkable: bool,
 		_menu_type: MenuType
 	) -> CustomMenuItem {
 		CustomMenuItem(MenuItemAttributes {})
@@ -120,6 +123,20 @@ impl MenuItemAttributes {
 	pub fn set_title(&mut self, _title: &str) {}
 	pub fn set_selected(&mut self, _is_selected: bool) {}
 	pub fn set_icon(&mut self, _icon: Icon) {}
+	#[allow(clippy::too_many_arguments)]
+	pub fn insert_item(
+		&mut self,
+		_menu_id: MenuId,
+		_title: &str,
+		_accelerator: Option<Accelerator>,
+		_enabled: bool,
+		_selected: bool,
+		_checkable: bool,
+		_after: bool
+	) -> CustomMenuItem {
+		CustomMenuItem(MenuItemAttributes {})
+	}
+	pub fn remove(&mut self) {}
 }
 
 pub struct EventLoop<T: 'static> {
@@ -414,11 +431,16 @@ impl<T> Clone for EventLoopProxy<T> {
 	}
 }
 
-#[derive(Clone)]
 pub struct EventLoopWindowTarget<T: 'static> {
 	_marker: std::marker::PhantomData<T>
 }
 
+impl<T> Clone for EventLoopWindowTarget<T> {
+	fn clone(&self) -> Self {
+		EventLoopWindowTarget { _marker: std::marker::PhantomData }
+	}
+}
+
 impl<T: 'static> EventLoopWindowTarget<T> {
 	pub fn primary_monitor(&self) -> Option<monitor::MonitorHandle> {
 		Some(monitor::MonitorHandle { inner: MonitorHandle });
@@ -429,6 +451,8 @@ impl<T: 'static> EventLoopWindowTarget<T> {
 		v.push_back(MonitorHandle);
 		v
 	}
+
+	pub fn set_jump_list(&self, _items: Vec<JumpListItem>) {}
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -518,6 +542,10 @@ impl Window {
 
 	pub fn set_max_inner_size(&self, _: Option<Size>) {}
 
+	pub fn set_aspect_ratio(&self, _: Option<f64>) {}
+
+	pub fn set_opacity(&self, _: f64) {}
+
 	pub fn set_title(&self, _title: &str) {}
 
 	pub fn set_menu(&self, _menu: Option<Menu>) {}
@@ -531,6 +559,12 @@ impl Window {
 
 	pub fn set_resizable(&self, _resizeable: bool) {}
 
+	pub fn set_minimizable(&self, _minimizable: bool) {}
+
+	pub fn set_maximizable(&self, _maximizable: bool) {}
+
+	pub fn set_closable(&self, _closable: bool) {}
+
 	pub fn set_minimized(&self, _minimized: bool) {}
 
 	pub fn set_maximized(&self, _maximized: bool) {}
@@ -539,6 +573,10 @@ impl Window {
 		false
 	}
 
+	pub fn is_minimized(&self) -> bool {
+		false
+	}
+
 	pub fn is_visible(&self) -> bool {
 		log::warn!("`Window::is_visible` is ignored on android");
 		false
@@ -549,11 +587,30 @@ impl Window {
 		false
 	}
 
+	pub fn is_minimizable(&self) -> bool {
+		warn!("`Window::is_minimizable` is ignored on Android");
+		false
+	}
+
+	pub fn is_maximizable(&self) -> bool {
+		warn!("`Window::is_maximizable` is ignored on Android");
+		false
+	}
+
+	pub fn is_closable(&self) -> bool {
+		warn!("`Window::is_closable` is ignored on Android");
+		false
+	}
+
 	pub fn is_decorated(&self) -> bool {
 		warn!("`Window::is_decorated` is ignored on Android");
 		false
 	}
 
+	pub fn is_visible_on_all_workspaces(&self) -> bool {
+		false
+	}
+
 	pub fn set_fullscreen(&self, _monitor: Option<window::Fullscreen>) {
 		warn!("Cannot set fullscreen on Android");
 	}
@@ -566,6 +623,8 @@ impl Window {
 
 	pub fn set_always_on_top(&self, _always_on_top: bool) {}
 
+	pub fn set_visible_on_all_workspaces(&self, _visible_on_all_workspaces: bool) {}
+
 	pub fn set_window_icon(&self, _window_icon: Option<crate::icon::Icon>) {}
 
 	pub fn set_ime_position(&self, _position: Position) {}
@@ -581,6 +640,14 @@ impl Window {
 		false
 	}
 
+	pub fn accent_color(&self) -> Option<(u8, u8, u8, u8)> {
+		None
+	}
+
+	pub fn accessibility_preferences(&self) -> window::AccessibilityPreferences {
+		window::AccessibilityPreferences::default()
+	}
+
 	pub fn set_cursor_icon(&self, _: window::CursorIcon) {}
 
 	pub fn set_cursor_position(&self, _: Position) -> Result<(), error::ExternalError> {
@@ -597,6 +664,22 @@ impl Window {
 		Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
 	}
 
+	pub fn start_drag(&self, _paths: Vec<std::path::PathBuf>, _icon: Option<Icon>) -> Result<(), error::ExternalError> {
+		Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+	}
+
+	pub fn start_resize_drag(&self, _direction: window::ResizeDirection) -> Result<(), error::ExternalError> {
+		Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+	}
+
+	pub fn titlebar_double_click(&self) {}
+
+	pub fn set_drag_regions(&self, _drag_regions: Vec<window::Rect>) {}
+
+	pub fn drag_regions(&self) -> Vec<window::Rect> {
+		Vec::new()
+	}
+
 	pub fn raw_window_handle(&self) -> RawWindowHandle {
 		// TODO: use main activity instead?
 		let mut handle = AndroidNdkHandle::empty();
@@ -160,6 +160,45 @@ impl MainPipe<'_> {
 						}
 					}
 				}
+				WebViewMessage::EvaluateJavascript(script) => {
+					if let Some(webview) = &self.webview {
+						let s = env.new_string(script)?;
+						env.call_method(
+							webview.as_obj(),
+							"evaluateJavascript",
+							"(Ljava/lang/String;Landroid/webkit/ValueCallback;)V",
+							&[s.into(), JObject::null().into()]
+						)?;
+					}
+				}
+				WebViewMessage::Print => {
+					if let Some(webview) = &self.webview {
+						let service = env.new_string("print")?;
+						let print_manager = env
+							.call_method(activity, "getSystemService", "(Ljava/lang/String;)Ljava/lang/Object;", &[service.into()])?
+							.l()?;
+						let title = env.call_method(webview.as_obj(), "getTitle", "()Ljava/lang/String;", &[])?.l()?;
+						let job_name = if title.is_null() { env.new_string("Document")?.into() } else { title };
+						let adapter = env
+							.call_method(
+								webview.as_obj(),
+								"createPrintDocumentAdapter",
+								"(Ljava/lang/String;)Landroid/print/PrintDocumentAdapter;",
+								&[job_name.into()]
+							)?
+							.l()?;
+						env.call_method(
+							print_manager,
+							"print",
+							"(Ljava/lang/String;Landroid/print/PrintDocumentAdapter;Landroid/print/PrintAttributes;)Landroid/print/PrintJob;",
+							&[job_name.into(), adapter.into(), JObject::null().into()]
+						)?;
+					}
+				}
+				WebViewMessage::SetDevtools(enabled) => {
+					let class = env.find_class("android/webkit/WebView")?;
+					env.call_static_method(class, "setWebContentsDebuggingEnabled", "(Z)V", &[enabled.into()])?;
+				}
 			}
 		}
 		Ok(())
@@ -169,7 +208,14 @@ impl MainPipe<'_> {
 #[derive(Debug)]
 pub enum WebViewMessage {
 	CreateWebView(String, Vec<String>, bool),
-	Eval
+	/// Evaluates the pending initialization scripts.
+	Eval,
+	/// Evaluates the given script in the webview.
+	EvaluateJavascript(String),
+	/// Opens the system print dialog for the webview contents.
+	Print,
+	/// Toggles remote debugging of web contents (`chrome://inspect`).
+	SetDevtools(bool)
 }
 
 pub static IPC: OnceCell<UnsafeIpc> = OnceCell::new();
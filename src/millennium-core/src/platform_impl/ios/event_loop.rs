@@ -38,6 +38,7 @@ use crate::{
 	dpi::LogicalSize,
 	event::Event,
 	event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootEventLoopWindowTarget},
+	jump_list::JumpListItem,
 	monitor::MonitorHandle as RootMonitorHandle,
 	platform::ios::Idiom
 };
@@ -59,12 +60,20 @@ pub enum EventProxy {
 	}
 }
 
-#[derive(Clone)]
 pub struct EventLoopWindowTarget<T: 'static> {
 	receiver: Receiver<T>,
 	sender_to_clone: Sender<T>
 }
 
+impl<T> Clone for EventLoopWindowTarget<T> {
+	fn clone(&self) -> Self {
+		EventLoopWindowTarget {
+			receiver: self.receiver.clone(),
+			sender_to_clone: self.sender_to_clone.clone()
+		}
+	}
+}
+
 impl<T: 'static> EventLoopWindowTarget<T> {
 	pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
 		// guaranteed to be on main thread
@@ -76,6 +85,8 @@ impl<T: 'static> EventLoopWindowTarget<T> {
 		let monitor = unsafe { monitor::main_uiscreen() };
 		return Some(RootMonitorHandle { inner: monitor });
 	}
+
+	pub fn set_jump_list(&self, _items: Vec<JumpListItem>) {}
 }
 
 pub struct EventLoop<T: 'static> {
@@ -135,6 +135,7 @@ impl Menu {
 	pub fn new_popup_menu() -> Self {
 		Self::new()
 	}
+	#[allow(clippy::too_many_arguments)]
 	pub fn add_item(
 		&mut self,
 		_menu_id: MenuId,
@@ -142,6 +143,7 @@ impl Menu {
 		_accelerator: Option<Accelerator>,
 		_enabled: bool,
 		_selected: bool,
+		_checkable: bool,
 		_menu_type: MenuType
 	) -> CustomMenuItem {
 		CustomMenuItem(MenuItemAttributes {})
@@ -163,6 +165,20 @@ impl MenuItemAttributes {
 	pub fn set_title(&mut self, _title: &str) {}
 	pub fn set_selected(&mut self, _is_selected: bool) {}
 	pub fn set_icon(&mut self, _icon: Icon) {}
+	#[allow(clippy::too_many_arguments)]
+	pub fn insert_item(
+		&mut self,
+		_menu_id: MenuId,
+		_title: &str,
+		_accelerator: Option<Accelerator>,
+		_enabled: bool,
+		_selected: bool,
+		_checkable: bool,
+		_after: bool
+	) -> CustomMenuItem {
+		CustomMenuItem(MenuItemAttributes {})
+	}
+	pub fn remove(&mut self) {}
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -35,7 +35,7 @@ use crate::{
 		ffi::{id, CGFloat, CGPoint, CGRect, CGSize, UIEdgeInsets, UIInterfaceOrientationMask, UIRectEdge, UIScreenOverscanCompensation},
 		monitor, view, EventLoopWindowTarget, Menu, MonitorHandle
 	},
-	window::{CursorIcon, Fullscreen, UserAttentionType, WindowAttributes, WindowId as RootWindowId}
+	window::{AccessibilityPreferences, CursorIcon, Fullscreen, Rect, ResizeDirection, UserAttentionType, WindowAttributes, WindowId as RootWindowId}
 };
 
 pub struct Inner {
@@ -176,10 +176,30 @@ impl Inner {
 		warn!("`Window::set_max_inner_size` is ignored on iOS");
 	}
 
+	pub fn set_aspect_ratio(&self, _aspect_ratio: Option<f64>) {
+		warn!("`Window::set_aspect_ratio` is ignored on iOS");
+	}
+
+	pub fn set_opacity(&self, _opacity: f64) {
+		warn!("`Window::set_opacity` is ignored on iOS");
+	}
+
 	pub fn set_resizable(&self, _resizable: bool) {
 		warn!("`Window::set_resizable` is ignored on iOS");
 	}
 
+	pub fn set_minimizable(&self, _minimizable: bool) {
+		warn!("`Window::set_minimizable` is ignored on iOS");
+	}
+
+	pub fn set_maximizable(&self, _maximizable: bool) {
+		warn!("`Window::set_maximizable` is ignored on iOS");
+	}
+
+	pub fn set_closable(&self, _closable: bool) {
+		warn!("`Window::set_closable` is ignored on iOS");
+	}
+
 	pub fn scale_factor(&self) -> f64 {
 		unsafe {
 			let hidpi: CGFloat = msg_send![self.view, contentScaleFactor];
@@ -207,6 +227,26 @@ impl Inner {
 		return Err(ExternalError::NotSupported(NotSupportedError::new()));
 	}
 
+	pub fn start_drag(&self, _paths: Vec<std::path::PathBuf>, _icon: Option<Icon>) -> Result<(), ExternalError> {
+		Err(ExternalError::NotSupported(NotSupportedError::new()))
+	}
+
+	pub fn start_resize_drag(&self, _direction: ResizeDirection) -> Result<(), ExternalError> {
+		Err(ExternalError::NotSupported(NotSupportedError::new()))
+	}
+
+	pub fn titlebar_double_click(&self) {
+		warn!("`Window::titlebar_double_click` is ignored on iOS");
+	}
+
+	pub fn set_drag_regions(&self, _drag_regions: Vec<Rect>) {
+		warn!("`Window::set_drag_regions` is ignored on iOS");
+	}
+
+	pub fn drag_regions(&self) -> Vec<Rect> {
+		Vec::new()
+	}
+
 	pub fn set_minimized(&self, _minimized: bool) {
 		warn!("`Window::set_minimized` is ignored on iOS");
 	}
@@ -220,6 +260,11 @@ impl Inner {
 		return false;
 	}
 
+	pub fn is_minimized(&self) -> bool {
+		warn!("`Window::is_minimized` is ignored on iOS");
+		return false;
+	}
+
 	pub fn is_visible(&self) -> bool {
 		log::warn!("`Window::is_visible` is ignored on iOS");
 		return false;
@@ -230,11 +275,31 @@ impl Inner {
 		return false;
 	}
 
+	pub fn is_minimizable(&self) -> bool {
+		warn!("`Window::is_minimizable` is ignored on iOS");
+		false
+	}
+
+	pub fn is_maximizable(&self) -> bool {
+		warn!("`Window::is_maximizable` is ignored on iOS");
+		false
+	}
+
+	pub fn is_closable(&self) -> bool {
+		warn!("`Window::is_closable` is ignored on iOS");
+		false
+	}
+
 	pub fn is_decorated(&self) -> bool {
 		warn!("`Window::is_decorated` is ignored on iOS");
 		return false;
 	}
 
+	pub fn is_visible_on_all_workspaces(&self) -> bool {
+		warn!("`Window::is_visible_on_all_workspaces` is ignored on iOS");
+		false
+	}
+
 	pub fn set_fullscreen(&self, monitor: Option<Fullscreen>) {
 		unsafe {
 			let uiscreen = match monitor {
@@ -294,6 +359,10 @@ impl Inner {
 		warn!("`Window::set_always_on_top` is ignored on iOS");
 	}
 
+	pub fn set_visible_on_all_workspaces(&self, _visible_on_all_workspaces: bool) {
+		warn!("`Window::set_visible_on_all_workspaces` is ignored on iOS");
+	}
+
 	pub fn set_window_icon(&self, _icon: Option<Icon>) {
 		warn!("`Window::set_window_icon` is ignored on iOS");
 	}
@@ -319,6 +388,14 @@ impl Inner {
 		return false;
 	}
 
+	pub fn accent_color(&self) -> Option<(u8, u8, u8, u8)> {
+		None
+	}
+
+	pub fn accessibility_preferences(&self) -> AccessibilityPreferences {
+		AccessibilityPreferences::default()
+	}
+
 	// Allow directly accessing the current monitor internally without unwrapping.
 	fn current_monitor_inner(&self) -> RootMonitorHandle {
 		unsafe {
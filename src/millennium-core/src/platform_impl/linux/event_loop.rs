@@ -26,7 +26,7 @@ use std::{
 	time::Instant
 };
 
-use gdk::{Cursor, CursorType, EventKey, EventMask, ScrollDirection, WindowEdge, WindowState};
+use gdk::{Cursor, CursorType, EventKey, EventMask, ScrollDirection, WMFunction, WindowEdge, WindowState};
 use gio::{prelude::*, Cancellable};
 use glib::{source::Priority, Continue, MainContext};
 use gtk::{builders::AboutDialogBuilder, prelude::*, Inhibit};
@@ -41,14 +41,17 @@ use crate::{
 	dpi::{LogicalPosition, LogicalSize},
 	event::{ElementState, Event, MouseButton, MouseScrollDelta, StartCause, TouchPhase, WindowEvent},
 	event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootELW},
+	jump_list::JumpListItem,
 	keyboard::ModifiersState,
 	menu::{MenuItem, MenuType},
 	monitor::MonitorHandle as RootMonitorHandle,
-	platform_impl::platform::{window::hit_test, DEVICE_ID},
-	window::{CursorIcon, Fullscreen, WindowId as RootWindowId}
+	platform_impl::platform::{
+		window::{drag_region_hit_test, hit_test},
+		DEVICE_ID
+	},
+	window::{CursorIcon, Fullscreen, ResizeDirection, WindowId as RootWindowId}
 };
 
-#[derive(Clone)]
 pub struct EventLoopWindowTarget<T> {
 	/// Gdk display
 	pub(crate) display: gdk::Display,
@@ -61,6 +64,18 @@ pub struct EventLoopWindowTarget<T> {
 	_marker: std::marker::PhantomData<T>
 }
 
+impl<T> Clone for EventLoopWindowTarget<T> {
+	fn clone(&self) -> Self {
+		Self {
+			display: self.display.clone(),
+			app: self.app.clone(),
+			windows: self.windows.clone(),
+			window_requests_tx: self.window_requests_tx.clone(),
+			_marker: std::marker::PhantomData
+		}
+	}
+}
+
 impl<T> EventLoopWindowTarget<T> {
 	#[inline]
 	pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
@@ -84,6 +99,9 @@ impl<T> EventLoopWindowTarget<T> {
 		let handle = MonitorHandle::new(&self.display, number);
 		Some(RootMonitorHandle { inner: handle })
 	}
+
+	#[inline]
+	pub fn set_jump_list(&self, _items: Vec<JumpListItem>) {}
 }
 
 pub struct EventLoop<T: 'static> {
@@ -139,6 +157,30 @@ impl<T: 'static> EventLoop<T> {
 			_marker: std::marker::PhantomData
 		};
 
+		// Forward system theme changes
+		let event_tx_ = event_tx.clone();
+		super::theme::watch_system_theme(move |theme| {
+			if let Err(e) = event_tx_.send(Event::ThemeChanged(theme)) {
+				log::warn!("Failed to send theme changed event to event channel: {}", e);
+			}
+		});
+
+		// Forward system accent color changes
+		let event_tx_ = event_tx.clone();
+		super::theme::watch_accent_color(move |color| {
+			if let Err(e) = event_tx_.send(Event::AccentColorChanged(color)) {
+				log::warn!("Failed to send accent color changed event to event channel: {}", e);
+			}
+		});
+
+		// Forward accessibility preference changes
+		let event_tx_ = event_tx.clone();
+		super::theme::watch_accessibility_preferences(move |preferences| {
+			if let Err(e) = event_tx_.send(Event::AccessibilityPreferencesChanged(preferences)) {
+				log::warn!("Failed to send accessibility preferences changed event to event channel: {}", e);
+			}
+		});
+
 		// Create user event channel
 		let (user_event_tx, user_event_rx) = glib::MainContext::channel(Priority::default());
 		let event_tx_ = event_tx.clone();
@@ -172,6 +214,16 @@ impl<T: 'static> EventLoop<T> {
 							gdk::WindowHints::MAX_SIZE
 						)
 					}
+					WindowRequest::AspectRatio(aspect_ratio) => {
+						let picky_none: Option<&gtk::Window> = None;
+						let ratio = aspect_ratio.unwrap_or_default();
+						window.set_geometry_hints(
+							picky_none,
+							Some(&gdk::Geometry::new(0, 0, 0, 0, 0, 0, 0, 0, ratio, ratio, gdk::Gravity::Center)),
+							if aspect_ratio.is_some() { gdk::WindowHints::ASPECT } else { gdk::WindowHints::empty() }
+						)
+					}
+					WindowRequest::Opacity(opacity) => window.set_opacity(opacity),
 					WindowRequest::Visible(visible) => {
 						if visible {
 							window.show_all();
@@ -187,6 +239,13 @@ impl<T: 'static> EventLoop<T> {
 						window.set_size_request(alloc.width(), alloc.height());
 						window.set_resizable(resizable)
 					}
+					WindowRequest::WmFunctions(functions) => {
+						// `set_deletable` resets the functions of a realized window, so it has to come first
+						window.set_deletable(functions.contains(WMFunction::CLOSE));
+						if let Some(gdk_window) = window.window() {
+							gdk_window.set_functions(functions);
+						}
+					}
 					WindowRequest::Minimized(minimized) => {
 						if minimized {
 							window.iconify();
@@ -207,6 +266,83 @@ impl<T: 'static> EventLoop<T> {
 							window.begin_move_drag(1, x, y, 0);
 						}
 					}
+					WindowRequest::ResizeDrag(direction) => {
+						if let Some(cursor) = window.display().default_seat().and_then(|seat| seat.pointer()) {
+							let (_, x, y) = cursor.position();
+							let edge = match direction {
+								ResizeDirection::East => WindowEdge::East,
+								ResizeDirection::North => WindowEdge::North,
+								ResizeDirection::NorthEast => WindowEdge::NorthEast,
+								ResizeDirection::NorthWest => WindowEdge::NorthWest,
+								ResizeDirection::South => WindowEdge::South,
+								ResizeDirection::SouthEast => WindowEdge::SouthEast,
+								ResizeDirection::SouthWest => WindowEdge::SouthWest,
+								ResizeDirection::West => WindowEdge::West
+							};
+							window.begin_resize_drag(edge, 1, x, y, 0);
+						}
+					}
+					WindowRequest::TitlebarDoubleClick => {
+						// `gio::prelude` also has a `SettingsExt`, so the glob imports cancel each other out
+						let action = gtk::Settings::default().and_then(|settings| gtk::traits::SettingsExt::gtk_titlebar_double_click(&settings));
+						match action.as_deref() {
+							Some("minimize") => window.iconify(),
+							Some("lower") => {
+								if let Some(window) = window.window() {
+									window.lower();
+								}
+							}
+							// "menu" needs the triggering button event, which we don't have here
+							Some("none") | Some("menu") => {}
+							// "toggle-maximize", the horizontal/vertical variants and the default
+							_ => {
+								if window.is_resizable() {
+									if window.is_maximized() {
+										window.unmaximize();
+									} else {
+										window.maximize();
+									}
+								}
+							}
+						}
+					}
+					WindowRequest::StartDrag(paths, icon) => {
+						let uris: Vec<String> = paths
+							.iter()
+							.filter_map(|path| glib::filename_to_uri(path, None).ok())
+							.map(|uri| uri.to_string())
+							.collect();
+
+						// the handlers only live for the duration of this drag
+						let handlers = Rc::new(RefCell::new(Vec::new()));
+						handlers.borrow_mut().push(window.connect_drag_data_get(move |_, _, data, _, _| {
+							let uris: Vec<&str> = uris.iter().map(String::as_str).collect();
+							data.set_uris(&uris);
+						}));
+						let handlers_ = handlers.clone();
+						handlers.borrow_mut().push(window.connect_drag_end(move |window, _| {
+							for handler in handlers_.borrow_mut().drain(..) {
+								window.disconnect(handler);
+							}
+						}));
+
+						let targets = gtk::TargetList::new(&[]);
+						targets.add_uri_targets(0);
+						// -1 makes gtk start the drag from the current pointer position
+						match window.drag_begin_with_coordinates(&targets, gdk::DragAction::COPY | gdk::DragAction::MOVE, 1, None, -1, -1) {
+							Some(context) => {
+								if let Some(icon) = icon {
+									context.drag_set_icon_pixbuf(&icon.inner.into(), 0, 0);
+								}
+							}
+							// the drag didn't start, so `drag-end` will never be emitted to disconnect the handlers
+							None => {
+								for handler in handlers.borrow_mut().drain(..) {
+									window.disconnect(handler);
+								}
+							}
+						}
+					}
 					WindowRequest::Fullscreen(fullscreen) => match fullscreen {
 						Some(f) => {
 							if let Fullscreen::Borderless(m) = f {
@@ -223,6 +359,13 @@ impl<T: 'static> EventLoop<T> {
 					},
 					WindowRequest::Decorations(decorations) => window.set_decorated(decorations),
 					WindowRequest::AlwaysOnTop(always_on_top) => window.set_keep_above(always_on_top),
+					WindowRequest::VisibleOnAllWorkspaces(visible) => {
+						if visible {
+							window.stick();
+						} else {
+							window.unstick();
+						}
+					}
 					WindowRequest::WindowIcon(window_icon) => {
 						if let Some(icon) = window_icon {
 							window.set_icon(Some(&icon.inner.into()));
@@ -327,19 +470,26 @@ impl<T: 'static> EventLoop<T> {
 							Inhibit(false)
 						});
 						window.connect_button_press_event(|window, event| {
-							if !window.is_decorated() && window.is_resizable() && event.button() == 1 {
-								if let Some(window) = window.window() {
-									let (cx, cy) = event.root();
-									let result = hit_test(&window, cx, cy);
-
-									// Ignore the `__Unknown` variant so the window receives the click correctly if
-									// it is not on the edges.
-									match result {
-										WindowEdge::__Unknown(_) => (),
-										_ => {
-											// FIXME: calling `window.begin_resize_drag` uses the default cursor, it should
-											// show a resizing cursor instead
-											window.begin_resize_drag(result, 1, cx as i32, cy as i32, event.time())
+							if event.button() == 1 {
+								let (cx, cy) = event.root();
+								let edge = match window.window() {
+									Some(gdk_window) if !window.is_decorated() && window.is_resizable() => hit_test(&gdk_window, cx, cy),
+									_ => WindowEdge::__Unknown(8)
+								};
+
+								// Ignore the `__Unknown` variant so the window receives the click correctly if
+								// it is not on the edges.
+								match edge {
+									WindowEdge::__Unknown(_) => {
+										if drag_region_hit_test(window, cx, cy) {
+											window.begin_move_drag(1, cx as i32, cy as i32, event.time());
+										}
+									}
+									_ => {
+										// FIXME: calling `window.begin_resize_drag` uses the default cursor, it should
+										// show a resizing cursor instead
+										if let Some(window) = window.window() {
+											window.begin_resize_drag(edge, 1, cx as i32, cy as i32, event.time())
 										}
 									}
 								}
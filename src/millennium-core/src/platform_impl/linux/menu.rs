@@ -14,8 +14,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::{cell::RefCell, rc::Rc};
+
 use glib::{Cast, Sender};
-use gtk::{prelude::*, AccelFlags, AccelGroup, CheckMenuItem, Menu as GtkMenu, MenuItem as GtkMenuItem, SeparatorMenuItem};
+use gtk::{prelude::*, AccelFlags, AccelGroup, CheckMenuItem, Menu as GtkMenu, MenuItem as GtkMenuItem, MenuShell, SeparatorMenuItem};
 
 use super::{
 	keyboard::key_to_raw_key,
@@ -82,7 +84,16 @@ pub struct MenuItemAttributes {
 	selected: bool,
 	enabled: bool,
 	menu_type: MenuType,
-	gtk_item: GtkMenuItem
+	gtk_item: GtkMenuItem,
+	// set once the item is added to a GTK menu, so items inserted next to it can be activated too
+	context: Rc<RefCell<Option<MenuContext>>>
+}
+
+#[derive(Debug, Clone)]
+struct MenuContext {
+	tx: Sender<(WindowId, WindowRequest)>,
+	accel_group: AccelGroup,
+	window_id: WindowId
 }
 
 impl MenuItemAttributes {
@@ -110,6 +121,65 @@ impl MenuItemAttributes {
 
 	// TODO
 	pub fn set_icon(&mut self, _icon: Icon) {}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn insert_item(
+		&mut self,
+		menu_id: MenuId,
+		title: &str,
+		accelerators: Option<Accelerator>,
+		enabled: bool,
+		selected: bool,
+		checkable: bool,
+		after: bool
+	) -> CustomMenuItem {
+		let item = MenuItemAttributes {
+			id: menu_id,
+			key: accelerators,
+			enabled,
+			selected,
+			menu_type: self.menu_type,
+			gtk_item: new_gtk_item(title, selected, checkable),
+			context: self.context.clone()
+		};
+		if let (Some(context), Some(shell)) = (&*self.context.borrow(), self.gtk_item.parent().and_then(|parent| parent.downcast::<MenuShell>().ok())) {
+			item.connect(context);
+			let position = shell.children().iter().position(|child| child == self.gtk_item.upcast_ref::<gtk::Widget>()).unwrap_or(0);
+			shell.insert(&item.gtk_item, (position + after as usize) as i32);
+			item.gtk_item.show();
+		}
+		CustomMenuItem(item)
+	}
+
+	pub fn remove(&mut self) {
+		if let Some(shell) = self.gtk_item.parent().and_then(|parent| parent.downcast::<MenuShell>().ok()) {
+			shell.remove(&self.gtk_item);
+		}
+	}
+
+	fn connect(&self, context: &MenuContext) {
+		if let Some(key) = self.key.clone() {
+			register_accelerator(&self.gtk_item, &context.accel_group, key);
+		}
+		self.gtk_item.set_sensitive(self.enabled);
+
+		let (tx, window_id, id) = (context.tx.clone(), context.window_id, self.id);
+		self.gtk_item.connect_activate(move |_| {
+			if let Err(e) = tx.send((window_id, WindowRequest::Menu((None, Some(id))))) {
+				log::warn!("Fail to send menu request: {}", e);
+			}
+		});
+	}
+}
+
+fn new_gtk_item(title: &str, selected: bool, checkable: bool) -> GtkMenuItem {
+	if selected || checkable {
+		let item = CheckMenuItem::with_label(title);
+		item.set_active(selected);
+		item.upcast::<GtkMenuItem>()
+	} else {
+		GtkMenuItem::with_label(title)
+	}
 }
 
 impl Default for Menu {
@@ -126,6 +196,7 @@ impl Menu {
 		Self::new()
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub fn add_item(
 		&mut self,
 		menu_id: MenuId,
@@ -133,22 +204,17 @@ impl Menu {
 		accelerators: Option<Accelerator>,
 		enabled: bool,
 		selected: bool,
+		checkable: bool,
 		menu_type: MenuType
 	) -> CustomMenuItem {
-		let gtk_item = if selected {
-			let item = CheckMenuItem::with_label(title);
-			item.set_active(true);
-			item.upcast::<GtkMenuItem>()
-		} else {
-			GtkMenuItem::with_label(title)
-		};
 		let custom_menu = MenuItemAttributes {
 			id: menu_id,
 			key: accelerators,
 			enabled,
 			selected,
 			menu_type,
-			gtk_item
+			gtk_item: new_gtk_item(title, selected, checkable),
+			context: Default::default()
 		};
 
 		self.gtk_items.push(GtkMenuInfo {
@@ -211,23 +277,18 @@ impl Menu {
 				}
 				GtkMenuInfo {
 					menu_type: GtkMenuType::Custom,
-					custom_menu_item: Some(MenuItemAttributes { enabled, gtk_item, id, key, .. }),
+					custom_menu_item: Some(item),
 					..
 				} => {
-					if let Some(key) = key {
-						register_accelerator(&gtk_item, accel_group, key);
-					}
-
-					gtk_item.set_sensitive(enabled);
-
-					let tx_ = tx.clone();
-					gtk_item.connect_activate(move |_| {
-						if let Err(e) = tx_.send((window_id, WindowRequest::Menu((None, Some(id))))) {
-							log::warn!("Fail to send menu request: {}", e);
-						}
-					});
+					let context = MenuContext {
+						tx: tx.clone(),
+						accel_group: accel_group.clone(),
+						window_id
+					};
+					item.connect(&context);
+					item.context.replace(Some(context));
 
-					Some(gtk_item)
+					Some(item.gtk_item)
 				}
 				GtkMenuInfo {
 					menu_type: GtkMenuType::Native,
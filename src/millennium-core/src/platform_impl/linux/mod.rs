@@ -26,12 +26,14 @@ mod menu;
 mod monitor;
 #[cfg(feature = "tray")]
 mod system_tray;
+mod theme;
 mod window;
 
 pub use event_loop::{EventLoop, EventLoopProxy, EventLoopWindowTarget};
 pub use icon::PlatformIcon;
 pub use monitor::{MonitorHandle, VideoMode};
-pub use window::{hit_test, Window, WindowId};
+pub use theme::system_theme;
+pub use window::{drag_region_hit_test, hit_test, Window, WindowId};
 
 #[cfg(feature = "tray")]
 pub use self::system_tray::{SystemTray, SystemTrayBuilder};
@@ -0,0 +1,264 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+	cell::{Cell, RefCell},
+	collections::HashMap,
+	iter,
+	rc::Rc
+};
+
+use gio::{BusType, Cancellable, DBusCallFlags, DBusConnection, DBusSignalFlags};
+use glib::{ObjectExt, StaticType, ToVariant, Variant};
+use gtk::traits::{SettingsExt, StyleContextExt};
+
+use crate::window::{AccessibilityPreferences, Theme};
+
+const PORTAL_NAME: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const PORTAL_SETTINGS: &str = "org.freedesktop.portal.Settings";
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+const COLOR_SCHEME_KEY: &str = "color-scheme";
+const ACCENT_COLOR_KEY: &str = "accent-color";
+const REDUCED_MOTION_KEY: &str = "reduced-motion";
+const CONTRAST_KEY: &str = "contrast";
+
+thread_local! {
+	static PORTAL: RefCell<Portal> = RefCell::new(Portal::default());
+}
+
+/// The appearance settings of the XDG desktop portal. They're read in the background so that a missing or slow portal
+/// doesn't block the event loop, and are empty until the portal replies.
+#[derive(Default)]
+struct Portal {
+	started: bool,
+	settings: HashMap<String, Variant>,
+	/// Called whenever a setting changes.
+	listeners: Vec<Rc<dyn Fn()>>
+}
+
+/// Returns the theme the user prefers, read from the `color-scheme` setting of
+/// the XDG desktop portal. Falls back to the GTK theme when the portal is
+/// unavailable, hasn't replied yet or has no preference.
+pub fn system_theme() -> Theme {
+	portal_color_scheme().unwrap_or_else(gtk_theme)
+}
+
+/// Returns the accent color the user prefers as RGBA, read from the
+/// `accent-color` setting of the XDG desktop portal. Falls back to the
+/// selection color of the GTK theme.
+pub fn accent_color() -> Option<(u8, u8, u8, u8)> {
+	portal_accent_color().or_else(gtk_accent_color)
+}
+
+/// Returns the accessibility display preferences, read from the
+/// `reduced-motion` and `contrast` settings of the XDG desktop portal. Each
+/// falls back to the GTK settings when the portal has no preference. There is
+/// no setting to reduce transparency.
+pub fn accessibility_preferences() -> AccessibilityPreferences {
+	let portal = |key| portal_setting(key)?.get::<u32>();
+	let settings = gtk::Settings::default();
+
+	// 0: no preference, 1: reduced motion / higher contrast
+	let reduce_motion = match portal(REDUCED_MOTION_KEY) {
+		Some(value) if value != 0 => true,
+		_ => settings.as_ref().map(|settings| !settings.is_gtk_enable_animations()).unwrap_or(false)
+	};
+	let increase_contrast = match portal(CONTRAST_KEY) {
+		Some(value) if value != 0 => true,
+		_ => settings
+			.as_ref()
+			.and_then(|settings| settings.gtk_theme_name())
+			.map(|name| name.to_lowercase().starts_with("highcontrast"))
+			.unwrap_or(false)
+	};
+	AccessibilityPreferences {
+		reduce_motion,
+		increase_contrast,
+		reduce_transparency: false
+	}
+}
+
+/// Calls `callback` whenever the system theme changes, either through the
+/// portal or the GTK settings.
+pub fn watch_system_theme<F: Fn(Theme) + 'static>(callback: F) {
+	watch(system_theme, callback);
+}
+
+/// Calls `callback` whenever the system accent color changes, either through
+/// the portal or the GTK theme.
+pub fn watch_accent_color<F: Fn((u8, u8, u8, u8)) + 'static>(callback: F) {
+	watch(accent_color, move |color| {
+		if let Some(color) = color {
+			callback(color);
+		}
+	});
+}
+
+/// Calls `callback` whenever the accessibility display preferences change,
+/// either through the portal or the GTK settings.
+pub fn watch_accessibility_preferences<F: Fn(AccessibilityPreferences) + 'static>(callback: F) {
+	watch(accessibility_preferences, callback);
+}
+
+fn watch<T: Copy + PartialEq + 'static, F: Fn(T) + 'static>(read: fn() -> T, callback: F) {
+	let last = Rc::new(Cell::new(read()));
+	let changed = Rc::new(move || {
+		let value = read();
+		if last.replace(value) != value {
+			callback(value);
+		}
+	});
+
+	PORTAL.with(|portal| portal.borrow_mut().listeners.push(changed.clone()));
+
+	if let Some(settings) = gtk::Settings::default() {
+		for property in ["gtk-theme-name", "gtk-application-prefer-dark-theme", "gtk-enable-animations"] {
+			let changed = changed.clone();
+			settings.connect_notify_local(Some(property), move |_, _| changed());
+		}
+	}
+}
+
+/// Starts reading the appearance settings from the portal, and listening for their changes, unless already started.
+fn start_portal() {
+	if PORTAL.with(|portal| std::mem::replace(&mut portal.borrow_mut().started, true)) {
+		return;
+	}
+
+	gio::bus_get(BusType::Session, None::<&Cancellable>, |connection| {
+		let connection = match connection {
+			Ok(connection) => connection,
+			Err(_) => return
+		};
+
+		// the signal carries the namespace, key and new value of the setting
+		connection.signal_subscribe(
+			Some(PORTAL_NAME),
+			Some(PORTAL_SETTINGS),
+			Some("SettingChanged"),
+			Some(PORTAL_PATH),
+			Some(APPEARANCE_NAMESPACE),
+			DBusSignalFlags::NONE,
+			|_, _, _, _, _, parameters| {
+				if let Some(key) = parameters.child_value(1).get::<String>() {
+					update_settings(iter::once((key, unwrap_variant(parameters.child_value(2)))));
+				}
+			}
+		);
+
+		read_all_settings(&connection);
+	});
+}
+
+fn read_all_settings(connection: &DBusConnection) {
+	connection.call(
+		Some(PORTAL_NAME),
+		PORTAL_PATH,
+		PORTAL_SETTINGS,
+		"ReadAll",
+		Some(&(vec![APPEARANCE_NAMESPACE],).to_variant()),
+		None,
+		DBusCallFlags::NONE,
+		-1,
+		None::<&Cancellable>,
+		|reply| {
+			// the reply maps each namespace to its settings
+			if let Ok(reply) = reply {
+				for namespace in reply.child_value(0).iter() {
+					if namespace.child_value(0).get::<String>().as_deref() == Some(APPEARANCE_NAMESPACE) {
+						update_settings(
+							namespace
+								.child_value(1)
+								.iter()
+								.filter_map(|setting| Some((setting.child_value(0).get::<String>()?, unwrap_variant(setting.child_value(1)))))
+						);
+					}
+				}
+			}
+		}
+	);
+}
+
+fn update_settings(settings: impl Iterator<Item = (String, Variant)>) {
+	let listeners = PORTAL.with(|portal| {
+		let mut portal = portal.borrow_mut();
+		portal.settings.extend(settings);
+		portal.listeners.clone()
+	});
+	for listener in listeners {
+		listener();
+	}
+}
+
+/// Some portal versions wrap the values in more than one variant.
+fn unwrap_variant(mut value: Variant) -> Variant {
+	while let Some(inner) = value.as_variant() {
+		value = inner;
+	}
+	value
+}
+
+fn portal_setting(key: &str) -> Option<Variant> {
+	start_portal();
+	PORTAL.with(|portal| portal.borrow().settings.get(key).cloned())
+}
+
+fn portal_color_scheme() -> Option<Theme> {
+	// 0: no preference, 1: prefer dark, 2: prefer light
+	match portal_setting(COLOR_SCHEME_KEY)?.get::<u32>()? {
+		1 => Some(Theme::Dark),
+		2 => Some(Theme::Light),
+		_ => None
+	}
+}
+
+fn portal_accent_color() -> Option<(u8, u8, u8, u8)> {
+	// the color is given as sRGB components in 0..=1, anything outside that range means unset
+	let (red, green, blue) = portal_setting(ACCENT_COLOR_KEY)?.get::<(f64, f64, f64)>()?;
+	if [red, green, blue].iter().all(|c| (0.0..=1.0).contains(c)) {
+		Some(to_rgba(red, green, blue, 1.0))
+	} else {
+		None
+	}
+}
+
+fn gtk_theme() -> Theme {
+	let settings = match gtk::Settings::default() {
+		Some(settings) => settings,
+		None => return Theme::Light
+	};
+	let theme_name = settings.gtk_theme_name().map(|name| name.to_lowercase()).unwrap_or_default();
+	if settings.is_gtk_application_prefer_dark_theme() || theme_name.ends_with("-dark") || theme_name.ends_with(":dark") {
+		Theme::Dark
+	} else {
+		Theme::Light
+	}
+}
+
+fn gtk_accent_color() -> Option<(u8, u8, u8, u8)> {
+	let path = gtk::WidgetPath::new();
+	path.append_type(gtk::Window::static_type());
+	let context = gtk::StyleContext::new();
+	context.set_path(&path);
+	let color = context.lookup_color("theme_selected_bg_color")?;
+	Some(to_rgba(color.red(), color.green(), color.blue(), color.alpha()))
+}
+
+fn to_rgba(red: f64, green: f64, blue: f64, alpha: f64) -> (u8, u8, u8, u8) {
+	let channel = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+	(channel(red), channel(green), channel(blue), channel(alpha))
+}
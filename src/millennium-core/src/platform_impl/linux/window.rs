@@ -17,11 +17,12 @@
 use std::{
 	cell::RefCell,
 	collections::VecDeque,
+	path::PathBuf,
 	rc::Rc,
 	sync::atomic::{AtomicBool, AtomicI32, Ordering}
 };
 
-use gdk::{WindowEdge, WindowState};
+use gdk::{WMFunction, WindowEdge, WindowState};
 use gtk::{prelude::*, AccelGroup, Orientation};
 use raw_window_handle::{RawWindowHandle, XlibHandle};
 
@@ -32,7 +33,9 @@ use crate::{
 	icon::Icon,
 	menu::{MenuId, MenuItem},
 	monitor::MonitorHandle as RootMonitorHandle,
-	window::{CursorIcon, Fullscreen, UserAttentionType, WindowAttributes, BORDERLESS_RESIZE_INSET}
+	window::{
+		AccessibilityPreferences, CursorIcon, Fullscreen, LayoutDirection, Rect, ResizeDirection, UserAttentionType, WindowAttributes, BORDERLESS_RESIZE_INSET
+	}
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -60,6 +63,10 @@ pub struct Window {
 	size: Rc<(AtomicI32, AtomicI32)>,
 	maximized: Rc<AtomicBool>,
 	minimized: Rc<AtomicBool>,
+	sticky: Rc<AtomicBool>,
+	minimizable: Rc<AtomicBool>,
+	maximizable: Rc<AtomicBool>,
+	closable: Rc<AtomicBool>,
 	fullscreen: RefCell<Option<Fullscreen>>
 }
 
@@ -164,6 +171,41 @@ impl Window {
 			window_menu.generate_menu(&mut menu_bar, &window_requests_tx, &accel_group, window_id);
 		}
 
+		// Set Layout Direction; GTK already follows the locale's direction otherwise
+		if let Some(direction) = attributes.layout_direction {
+			let direction = match direction {
+				LayoutDirection::LeftToRight => gtk::TextDirection::Ltr,
+				LayoutDirection::RightToLeft => gtk::TextDirection::Rtl
+			};
+			window.set_direction(direction);
+			window_box.set_direction(direction);
+			menu_bar.set_direction(direction);
+		}
+
+		// Draw our own header bar so its color doesn't depend on the theme or the window manager.
+		// GTK keeps the header bar's title in sync with the window title.
+		if let Some((r, g, b, a)) = attributes.title_bar_color {
+			let header_bar = gtk::HeaderBar::new();
+			header_bar.set_show_close_button(true);
+			let css_provider = gtk::CssProvider::new();
+			let theme = format!(
+				r#"
+				headerbar {{
+					background-image: none;
+					background-color: rgba({}, {}, {}, {});
+				}}
+			"#,
+				r,
+				g,
+				b,
+				a as f64 / 255.
+			);
+			let _ = css_provider.load_from_data(theme.as_bytes());
+			header_bar.style_context().add_provider(&css_provider, 600);
+			header_bar.show_all();
+			window.set_titlebar(Some(&header_bar));
+		}
+
 		// Rest attributes
 		window.set_title(&attributes.title);
 		if let Some(Fullscreen::Borderless(m)) = &attributes.fullscreen {
@@ -186,6 +228,24 @@ impl Window {
 			window.set_icon(Some(&icon.inner.into()));
 		}
 
+		// Set Window Controls; GTK resets the WM functions when the window is realized, so apply them afterwards
+		let minimizable = Rc::new(AtomicBool::new(attributes.minimizable));
+		let maximizable = Rc::new(AtomicBool::new(attributes.maximizable));
+		let closable = Rc::new(AtomicBool::new(attributes.closable));
+		window.set_deletable(attributes.closable);
+		{
+			let (minimizable, maximizable, closable) = (minimizable.clone(), maximizable.clone(), closable.clone());
+			window.connect_realize(move |window| {
+				if let Some(gdk_window) = window.window() {
+					gdk_window.set_functions(wm_functions(
+						minimizable.load(Ordering::Acquire),
+						maximizable.load(Ordering::Acquire),
+						closable.load(Ordering::Acquire)
+					));
+				}
+			});
+		}
+
 		if attributes.visible {
 			window.show_all();
 		} else {
@@ -217,11 +277,14 @@ impl Window {
 		let max_clone = maximized.clone();
 		let minimized = Rc::new(AtomicBool::new(false));
 		let min_clone = minimized.clone();
+		let sticky = Rc::new(AtomicBool::new(false));
+		let sticky_clone = sticky.clone();
 
 		window.connect_window_state_event(move |_, event| {
 			let state = event.new_window_state();
 			max_clone.store(state.contains(WindowState::MAXIMIZED), Ordering::Release);
 			min_clone.store(state.contains(WindowState::ICONIFIED), Ordering::Release);
+			sticky_clone.store(state.contains(WindowState::STICKY), Ordering::Release);
 			Inhibit(false)
 		});
 
@@ -250,6 +313,10 @@ impl Window {
 			size,
 			maximized,
 			minimized,
+			sticky,
+			minimizable,
+			maximizable,
+			closable,
 			fullscreen: RefCell::new(attributes.fullscreen)
 		};
 
@@ -337,6 +404,18 @@ impl Window {
 		}
 	}
 
+	pub fn set_aspect_ratio(&self, aspect_ratio: Option<f64>) {
+		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::AspectRatio(aspect_ratio))) {
+			log::warn!("Fail to send aspect ratio request: {}", e);
+		}
+	}
+
+	pub fn set_opacity(&self, opacity: f64) {
+		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::Opacity(opacity))) {
+			log::warn!("Fail to send opacity request: {}", e);
+		}
+	}
+
 	pub fn set_title(&self, title: &str) {
 		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::Title(title.to_string()))) {
 			log::warn!("Fail to send title request: {}", e);
@@ -372,6 +451,32 @@ impl Window {
 		}
 	}
 
+	pub fn set_minimizable(&self, minimizable: bool) {
+		self.minimizable.store(minimizable, Ordering::Release);
+		self.send_wm_functions();
+	}
+
+	pub fn set_maximizable(&self, maximizable: bool) {
+		self.maximizable.store(maximizable, Ordering::Release);
+		self.send_wm_functions();
+	}
+
+	pub fn set_closable(&self, closable: bool) {
+		self.closable.store(closable, Ordering::Release);
+		self.send_wm_functions();
+	}
+
+	fn send_wm_functions(&self) {
+		let functions = wm_functions(
+			self.minimizable.load(Ordering::Acquire),
+			self.maximizable.load(Ordering::Acquire),
+			self.closable.load(Ordering::Acquire)
+		);
+		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::WmFunctions(functions))) {
+			log::warn!("Fail to send window controls request: {}", e);
+		}
+	}
+
 	pub fn set_minimized(&self, minimized: bool) {
 		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::Minimized(minimized))) {
 			log::warn!("Fail to send minimized request: {}", e);
@@ -388,14 +493,34 @@ impl Window {
 		self.maximized.load(Ordering::Acquire)
 	}
 
+	pub fn is_minimized(&self) -> bool {
+		self.minimized.load(Ordering::Acquire)
+	}
+
 	pub fn is_resizable(&self) -> bool {
 		self.window.is_resizable()
 	}
 
+	pub fn is_minimizable(&self) -> bool {
+		self.minimizable.load(Ordering::Acquire)
+	}
+
+	pub fn is_maximizable(&self) -> bool {
+		self.maximizable.load(Ordering::Acquire)
+	}
+
+	pub fn is_closable(&self) -> bool {
+		self.closable.load(Ordering::Acquire)
+	}
+
 	pub fn is_decorated(&self) -> bool {
 		self.window.is_decorated()
 	}
 
+	pub fn is_visible_on_all_workspaces(&self) -> bool {
+		self.sticky.load(Ordering::Acquire)
+	}
+
 	#[inline]
 	pub fn is_visible(&self) -> bool {
 		self.window.is_visible()
@@ -408,6 +533,36 @@ impl Window {
 		Ok(())
 	}
 
+	pub fn start_resize_drag(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
+		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::ResizeDrag(direction))) {
+			log::warn!("Fail to send resize drag request: {}", e);
+		}
+		Ok(())
+	}
+
+	pub fn titlebar_double_click(&self) {
+		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::TitlebarDoubleClick)) {
+			log::warn!("Fail to send titlebar double click request: {}", e);
+		}
+	}
+
+	pub fn set_drag_regions(&self, drag_regions: Vec<Rect>) {
+		// stored on the GTK window so the button press handlers of both the window and the webview inside it can
+		// read them
+		unsafe { self.window.set_data(DRAG_REGIONS_KEY, drag_regions) };
+	}
+
+	pub fn drag_regions(&self) -> Vec<Rect> {
+		unsafe { self.window.data::<Vec<Rect>>(DRAG_REGIONS_KEY).map(|regions| regions.as_ref().clone()) }.unwrap_or_default()
+	}
+
+	pub fn start_drag(&self, paths: Vec<PathBuf>, icon: Option<Icon>) -> Result<(), ExternalError> {
+		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::StartDrag(paths, icon))) {
+			log::warn!("Fail to send start drag request: {}", e);
+		}
+		Ok(())
+	}
+
 	pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
 		self.fullscreen.replace(fullscreen.clone());
 		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::Fullscreen(fullscreen))) {
@@ -431,6 +586,15 @@ impl Window {
 		}
 	}
 
+	pub fn set_visible_on_all_workspaces(&self, visible_on_all_workspaces: bool) {
+		if let Err(e) = self
+			.window_requests_tx
+			.send((self.window_id, WindowRequest::VisibleOnAllWorkspaces(visible_on_all_workspaces)))
+		{
+			log::warn!("Fail to send visible on all workspaces request: {}", e);
+		}
+	}
+
 	pub fn set_window_icon(&self, window_icon: Option<Icon>) {
 		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::WindowIcon(window_icon))) {
 			log::warn!("Fail to send window icon request: {}", e);
@@ -459,6 +623,14 @@ impl Window {
 		self.menu_bar.get_visible()
 	}
 
+	pub fn accent_color(&self) -> Option<(u8, u8, u8, u8)> {
+		super::theme::accent_color()
+	}
+
+	pub fn accessibility_preferences(&self) -> AccessibilityPreferences {
+		super::theme::accessibility_preferences()
+	}
+
 	pub fn set_cursor_icon(&self, cursor: CursorIcon) {
 		if let Err(e) = self.window_requests_tx.send((self.window_id, WindowRequest::CursorIcon(Some(cursor)))) {
 			log::warn!("Fail to send cursor icon request: {}", e);
@@ -563,15 +735,22 @@ pub enum WindowRequest {
 	Size((i32, i32)),
 	MinSize((i32, i32)),
 	MaxSize((i32, i32)),
+	AspectRatio(Option<f64>),
+	Opacity(f64),
 	Visible(bool),
 	Focus,
 	Resizable(bool),
+	WmFunctions(WMFunction),
 	Minimized(bool),
 	Maximized(bool),
 	DragWindow,
+	ResizeDrag(ResizeDirection),
+	TitlebarDoubleClick,
+	StartDrag(Vec<PathBuf>, Option<Icon>),
 	Fullscreen(Option<Fullscreen>),
 	Decorations(bool),
 	AlwaysOnTop(bool),
+	VisibleOnAllWorkspaces(bool),
 	WindowIcon(Option<Icon>),
 	UserAttention(Option<UserAttentionType>),
 	SetSkipTaskbar(bool),
@@ -584,6 +763,34 @@ pub enum WindowRequest {
 	GlobalHotKey(u16)
 }
 
+/// The window manager functions, i.e. the title bar buttons and window menu
+/// entries, that are enabled for the given window controls.
+fn wm_functions(minimizable: bool, maximizable: bool, closable: bool) -> WMFunction {
+	let mut functions = WMFunction::MOVE | WMFunction::RESIZE;
+	functions.set(WMFunction::MINIMIZE, minimizable);
+	functions.set(WMFunction::MAXIMIZE, maximizable);
+	functions.set(WMFunction::CLOSE, closable);
+	functions
+}
+
+const DRAG_REGIONS_KEY: &str = "millennium-drag-regions";
+
+/// Returns whether the root coordinates `(cx, cy)` are inside one of the drag regions of `window`.
+pub fn drag_region_hit_test<W: IsA<gtk::Window> + IsA<gtk::Widget>>(window: &W, cx: f64, cy: f64) -> bool {
+	let regions = match unsafe { window.data::<Vec<Rect>>(DRAG_REGIONS_KEY) } {
+		Some(regions) => regions,
+		None => return false
+	};
+	let (left, top) = match window.window() {
+		Some(window) => window.position(),
+		None => return false
+	};
+	// GTK coordinates are logical
+	let scale_factor = window.scale_factor() as f64;
+	let position: PhysicalPosition<f64> = LogicalPosition::new(cx - left as f64, cy - top as f64).to_physical(scale_factor);
+	unsafe { regions.as_ref() }.iter().any(|r| r.contains(position, scale_factor))
+}
+
 pub fn hit_test(window: &gdk::Window, cx: f64, cy: f64) -> WindowEdge {
 	let (left, top) = window.position();
 	let (w, h) = (window.width(), window.height());
@@ -19,13 +19,22 @@ use std::{
 	os::raw::c_void
 };
 
-use cocoa::base::id;
+use cocoa::{
+	appkit::CGFloat,
+	base::{id, nil},
+	foundation::{NSArray, NSAutoreleasePool, NSString}
+};
 use objc::{
 	declare::ClassDecl,
-	runtime::{Class, Object, Sel}
+	runtime::{Class, Object, Sel, BOOL, NO}
 };
 
-use crate::{platform::macos::ActivationPolicy, platform_impl::platform::app_state::AppState};
+use crate::{
+	jump_list::JumpListItem,
+	platform::macos::ActivationPolicy,
+	platform_impl::platform::{app_state::AppState, util::ns_string_to_rust},
+	window::{AccessibilityPreferences, Theme}
+};
 
 static AUX_DELEGATE_STATE_NAME: &str = "auxState";
 
@@ -36,7 +45,22 @@ pub struct AuxDelegateState {
 	/// 10.15 for example.
 	pub activation_policy: ActivationPolicy,
 
-	pub create_default_menu: bool
+	pub create_default_menu: bool,
+
+	/// The last system theme, so `Event::ThemeChanged` is only emitted when it
+	/// actually changes. Read once the app has finished launching.
+	pub system_theme: Option<Theme>,
+
+	/// The last system accent color, for the same reason as `system_theme`.
+	pub accent_color: Option<(u8, u8, u8, u8)>,
+
+	/// The last accessibility display preferences, for the same reason as
+	/// `system_theme`.
+	pub accessibility_preferences: Option<AccessibilityPreferences>,
+
+	/// The items shown in the dock menu. AppKit asks for the menu each time
+	/// it is opened, so it is built from this list on demand.
+	pub jump_list: Vec<JumpListItem>
 }
 
 pub struct AppDelegateClass(pub *const Class);
@@ -53,6 +77,17 @@ lazy_static! {
 
 		decl.add_method(sel!(applicationDidFinishLaunching:), did_finish_launching as extern "C" fn(&Object, Sel, id));
 		decl.add_method(sel!(applicationWillTerminate:), application_will_terminate as extern "C" fn(&Object, Sel, id));
+		// when this is implemented, AppKit doesn't call `application:openFiles:`, and passes opened files as file URLs
+		decl.add_method(sel!(application:openURLs:), application_open_urls as extern "C" fn(&Object, Sel, id, id));
+		decl.add_method(sel!(systemThemeDidChange:), system_theme_did_change as extern "C" fn(&Object, Sel, id));
+		decl.add_method(sel!(systemThemeDidChangeOnMainThread:), system_theme_did_change_on_main_thread as extern "C" fn(&Object, Sel, id));
+		decl.add_method(sel!(systemColorsDidChange:), system_colors_did_change as extern "C" fn(&Object, Sel, id));
+		decl.add_method(
+			sel!(accessibilityDisplayOptionsDidChange:),
+			accessibility_display_options_did_change as extern "C" fn(&Object, Sel, id)
+		);
+		decl.add_method(sel!(applicationDockMenu:), application_dock_menu as extern "C" fn(&Object, Sel, id) -> id);
+		decl.add_method(sel!(jumpListItemClicked:), jump_list_item_clicked as extern "C" fn(&Object, Sel, id));
 		decl.add_ivar::<*mut c_void>(AUX_DELEGATE_STATE_NAME);
 
 		AppDelegateClass(decl.register())
@@ -74,7 +109,11 @@ extern "C" fn new(class: &Class, _: Sel) -> id {
 			AUX_DELEGATE_STATE_NAME,
 			Box::into_raw(Box::new(RefCell::new(AuxDelegateState {
 				activation_policy: ActivationPolicy::Regular,
-				create_default_menu: true
+				create_default_menu: true,
+				system_theme: None,
+				accent_color: None,
+				accessibility_preferences: None,
+				jump_list: Vec::new()
 			}))) as *mut c_void
 		);
 		this
@@ -83,6 +122,14 @@ extern "C" fn new(class: &Class, _: Sel) -> id {
 
 extern "C" fn dealloc(this: &Object, _: Sel) {
 	unsafe {
+		let notification_center: id = msg_send![class!(NSDistributedNotificationCenter), defaultCenter];
+		let _: () = msg_send![notification_center, removeObserver: this];
+		let notification_center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+		let _: () = msg_send![notification_center, removeObserver: this];
+		let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+		let notification_center: id = msg_send![workspace, notificationCenter];
+		let _: () = msg_send![notification_center, removeObserver: this];
+
 		let state_ptr: *mut c_void = *(this.get_ivar(AUX_DELEGATE_STATE_NAME));
 		// As soon as the box is constructed it is immediately dropped, releasing the
 		// underlying memory
@@ -92,6 +139,48 @@ extern "C" fn dealloc(this: &Object, _: Sel) {
 
 extern "C" fn did_finish_launching(this: &Object, _: Sel, _: id) {
 	trace!("Triggered `applicationDidFinishLaunching`");
+	unsafe {
+		let mut aux_state = get_aux_state_mut(this);
+		aux_state.system_theme = Some(system_theme());
+		aux_state.accent_color = accent_color();
+		aux_state.accessibility_preferences = Some(accessibility_preferences());
+		drop(aux_state);
+
+		let notification_center: id = msg_send![class!(NSDistributedNotificationCenter), defaultCenter];
+		let notification_name = NSString::alloc(nil).init_str("AppleInterfaceThemeChangedNotification");
+		let _: () = msg_send![
+			notification_center,
+			addObserver: this
+			selector: sel!(systemThemeDidChange:)
+			name: notification_name
+			object: nil
+		];
+
+		// posted on the main thread when the accent or highlight color changes
+		let notification_center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+		let notification_name = NSString::alloc(nil).init_str("NSSystemColorsDidChangeNotification").autorelease();
+		let _: () = msg_send![
+			notification_center,
+			addObserver: this
+			selector: sel!(systemColorsDidChange:)
+			name: notification_name
+			object: nil
+		];
+
+		// accessibility display options are only posted to the workspace's own notification center
+		let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+		let notification_center: id = msg_send![workspace, notificationCenter];
+		let notification_name = NSString::alloc(nil)
+			.init_str("NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification")
+			.autorelease();
+		let _: () = msg_send![
+			notification_center,
+			addObserver: this
+			selector: sel!(accessibilityDisplayOptionsDidChange:)
+			name: notification_name
+			object: nil
+		];
+	}
 	AppState::launched(this);
 	trace!("Completed `applicationDidFinishLaunching`");
 }
@@ -101,3 +190,145 @@ extern "C" fn application_will_terminate(_: &Object, _: Sel, _: id) {
 	AppState::exit();
 	trace!("Completed `applicationWillTerminate`");
 }
+
+extern "C" fn application_open_urls(_: &Object, _: Sel, _: id, urls: id) {
+	trace!("Triggered `application:openURLs:`");
+	let urls = unsafe {
+		(0..urls.count())
+			.filter_map(|i| {
+				let url = urls.objectAtIndex(i);
+				let absolute_string: id = msg_send![url, absoluteString];
+				url::Url::parse(&ns_string_to_rust(absolute_string)).ok()
+			})
+			.collect::<Vec<_>>()
+	};
+	AppState::open_urls(urls);
+	trace!("Completed `application:openURLs:`");
+}
+
+extern "C" fn application_dock_menu(this: &Object, _: Sel, _: id) -> id {
+	unsafe {
+		let aux_state = get_aux_state_mut(this);
+		if aux_state.jump_list.is_empty() {
+			return nil;
+		}
+
+		let menu: id = msg_send![class!(NSMenu), alloc];
+		let menu: id = msg_send![menu, initWithTitle: NSString::alloc(nil).init_str("").autorelease()];
+		for item in &aux_state.jump_list {
+			let title = NSString::alloc(nil).init_str(&item.title).autorelease();
+			let key_equivalent = NSString::alloc(nil).init_str("").autorelease();
+			let menu_item: id = msg_send![class!(NSMenuItem), alloc];
+			let menu_item: id = msg_send![menu_item, initWithTitle: title action: sel!(jumpListItemClicked:) keyEquivalent: key_equivalent];
+			let _: () = msg_send![menu_item, setTarget: this];
+			let _: () = msg_send![menu_item, setRepresentedObject: NSString::alloc(nil).init_str(&item.id).autorelease()];
+			let _: () = msg_send![menu, addItem: menu_item];
+			let _: () = msg_send![menu_item, release];
+		}
+		menu.autorelease()
+	}
+}
+
+extern "C" fn jump_list_item_clicked(_: &Object, _: Sel, menu_item: id) {
+	trace!("Triggered `jumpListItemClicked:`");
+	let id = unsafe {
+		let represented_object: id = msg_send![menu_item, representedObject];
+		ns_string_to_rust(represented_object)
+	};
+	AppState::jump_list_item_clicked(id);
+	trace!("Completed `jumpListItemClicked:`");
+}
+
+extern "C" fn system_theme_did_change(this: &Object, _: Sel, _: id) {
+	// distributed notifications may be delivered on another thread
+	unsafe {
+		let _: () = msg_send![this, performSelectorOnMainThread: sel!(systemThemeDidChangeOnMainThread:) withObject: nil waitUntilDone: false];
+	}
+}
+
+extern "C" fn system_theme_did_change_on_main_thread(this: &Object, _: Sel, _: id) {
+	trace!("Triggered `systemThemeDidChange:`");
+	let theme = system_theme();
+	let changed = unsafe { get_aux_state_mut(this).system_theme.replace(theme) } != Some(theme);
+	if changed {
+		AppState::system_theme_changed(theme);
+	}
+	trace!("Completed `systemThemeDidChange:`");
+}
+
+extern "C" fn system_colors_did_change(this: &Object, _: Sel, _: id) {
+	trace!("Triggered `systemColorsDidChange:`");
+	if let Some(color) = accent_color() {
+		let changed = unsafe { get_aux_state_mut(this).accent_color.replace(color) } != Some(color);
+		if changed {
+			AppState::accent_color_changed(color);
+		}
+	}
+	trace!("Completed `systemColorsDidChange:`");
+}
+
+extern "C" fn accessibility_display_options_did_change(this: &Object, _: Sel, _: id) {
+	trace!("Triggered `accessibilityDisplayOptionsDidChange:`");
+	let preferences = accessibility_preferences();
+	let changed = unsafe { get_aux_state_mut(this).accessibility_preferences.replace(preferences) } != Some(preferences);
+	if changed {
+		AppState::accessibility_preferences_changed(preferences);
+	}
+	trace!("Completed `accessibilityDisplayOptionsDidChange:`");
+}
+
+/// Reads the system appearance from the user defaults, since the app's
+/// `effectiveAppearance` reflects any theme the app forced on itself.
+pub fn system_theme() -> Theme {
+	unsafe {
+		let defaults: id = msg_send![class!(NSUserDefaults), standardUserDefaults];
+		let style: id = msg_send![defaults, stringForKey: NSString::alloc(nil).init_str("AppleInterfaceStyle").autorelease()];
+		if style != nil && ns_string_to_rust(style) == "Dark" {
+			Theme::Dark
+		} else {
+			Theme::Light
+		}
+	}
+}
+
+/// Reads `NSColor.controlAccentColor` as RGBA. Returns `None` before macOS
+/// 10.14, where there is no accent color.
+pub fn accent_color() -> Option<(u8, u8, u8, u8)> {
+	unsafe {
+		let supported: BOOL = msg_send![class!(NSColor), respondsToSelector: sel!(controlAccentColor)];
+		if supported == NO {
+			return None;
+		}
+		// the accent color is a catalog color, which has no components until converted
+		let color: id = msg_send![class!(NSColor), controlAccentColor];
+		let color_space: id = msg_send![class!(NSColorSpace), sRGBColorSpace];
+		let color: id = msg_send![color, colorUsingColorSpace: color_space];
+		if color == nil {
+			return None;
+		}
+		let (mut red, mut green, mut blue, mut alpha): (CGFloat, CGFloat, CGFloat, CGFloat) = (0.0, 0.0, 0.0, 0.0);
+		let _: () = msg_send![color, getRed: &mut red green: &mut green blue: &mut blue alpha: &mut alpha];
+		let channel = |c: CGFloat| (c.max(0.0).min(1.0) * 255.0).round() as u8;
+		Some((channel(red), channel(green), channel(blue), channel(alpha)))
+	}
+}
+
+/// Reads the display options from the accessibility settings.
+pub fn accessibility_preferences() -> AccessibilityPreferences {
+	unsafe {
+		let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+		let reduce_motion_supported: BOOL = msg_send![workspace, respondsToSelector: sel!(accessibilityDisplayShouldReduceMotion)];
+		let reduce_motion: BOOL = if reduce_motion_supported == NO {
+			NO
+		} else {
+			msg_send![workspace, accessibilityDisplayShouldReduceMotion]
+		};
+		let increase_contrast: BOOL = msg_send![workspace, accessibilityDisplayShouldIncreaseContrast];
+		let reduce_transparency: BOOL = msg_send![workspace, accessibilityDisplayShouldReduceTransparency];
+		AccessibilityPreferences {
+			reduce_motion: reduce_motion != NO,
+			increase_contrast: increase_contrast != NO,
+			reduce_transparency: reduce_transparency != NO
+		}
+	}
+}
@@ -50,7 +50,7 @@ use crate::{
 			window::get_window_id
 		}
 	},
-	window::WindowId
+	window::{AccessibilityPreferences, Theme, WindowId}
 };
 
 lazy_static! {
@@ -339,6 +339,43 @@ impl AppState {
 		HANDLER.events().push_back(wrapper);
 	}
 
+	pub fn open_urls(urls: Vec<url::Url>) {
+		// this may be called before `applicationDidFinishLaunching:` when the app is launched to open a file, so queue the
+		// event to be delivered after `StartCause::Init`
+		Self::queue_event(EventWrapper::StaticEvent(Event::Opened { urls }));
+		unsafe {
+			CFRunLoopWakeUp(CFRunLoopGetMain());
+		}
+	}
+
+	pub fn system_theme_changed(theme: Theme) {
+		Self::queue_event(EventWrapper::StaticEvent(Event::ThemeChanged(theme)));
+		unsafe {
+			CFRunLoopWakeUp(CFRunLoopGetMain());
+		}
+	}
+
+	pub fn accent_color_changed(color: (u8, u8, u8, u8)) {
+		Self::queue_event(EventWrapper::StaticEvent(Event::AccentColorChanged(color)));
+		unsafe {
+			CFRunLoopWakeUp(CFRunLoopGetMain());
+		}
+	}
+
+	pub fn accessibility_preferences_changed(preferences: AccessibilityPreferences) {
+		Self::queue_event(EventWrapper::StaticEvent(Event::AccessibilityPreferencesChanged(preferences)));
+		unsafe {
+			CFRunLoopWakeUp(CFRunLoopGetMain());
+		}
+	}
+
+	pub fn jump_list_item_clicked(id: String) {
+		Self::queue_event(EventWrapper::StaticEvent(Event::JumpListItemClicked { id }));
+		unsafe {
+			CFRunLoopWakeUp(CFRunLoopGetMain());
+		}
+	}
+
 	pub fn queue_events(mut wrappers: VecDeque<EventWrapper>) {
 		let is_main_thread: BOOL = unsafe { msg_send!(class!(NSThread), isMainThread) };
 		if is_main_thread == NO {
@@ -37,10 +37,11 @@ use scopeguard::defer;
 use crate::{
 	event::Event,
 	event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootWindowTarget},
+	jump_list::JumpListItem,
 	monitor::MonitorHandle as RootMonitorHandle,
 	platform_impl::platform::{
 		app::APP_CLASS,
-		app_delegate::APP_DELEGATE_CLASS,
+		app_delegate::{get_aux_state_mut, APP_DELEGATE_CLASS},
 		app_state::AppState,
 		monitor::{self, MonitorHandle},
 		observer::*,
@@ -76,12 +77,20 @@ impl PanicInfo {
 	}
 }
 
-#[derive(Clone)]
 pub struct EventLoopWindowTarget<T: 'static> {
 	pub sender: Sender<T>, // this is only here to be cloned elsewhere
 	pub receiver: Receiver<T>
 }
 
+impl<T> Clone for EventLoopWindowTarget<T> {
+	fn clone(&self) -> Self {
+		EventLoopWindowTarget {
+			sender: self.sender.clone(),
+			receiver: self.receiver.clone()
+		}
+	}
+}
+
 impl<T> Default for EventLoopWindowTarget<T> {
 	fn default() -> Self {
 		let (sender, receiver) = channel::unbounded();
@@ -100,6 +109,14 @@ impl<T: 'static> EventLoopWindowTarget<T> {
 		let monitor = monitor::primary_monitor();
 		Some(RootMonitorHandle { inner: monitor })
 	}
+
+	#[inline]
+	pub fn set_jump_list(&self, items: Vec<JumpListItem>) {
+		unsafe {
+			let delegate: id = msg_send![NSApp(), delegate];
+			get_aux_state_mut(&*delegate).jump_list = items;
+		}
+	}
 }
 
 pub struct EventLoop<T: 'static> {
@@ -110,6 +110,46 @@ impl MenuItemAttributes {
 			let () = msg_send![self.1, setImage: image_ref];
 		}
 	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn insert_item(
+		&mut self,
+		menu_id: MenuId,
+		title: &str,
+		accelerators: Option<Accelerator>,
+		enabled: bool,
+		selected: bool,
+		_checkable: bool,
+		after: bool
+	) -> CustomMenuItem {
+		unsafe {
+			// reuse the action of this item, so the new item reports the same menu type
+			let action: Sel = msg_send![self.1, action];
+			let menu_item = make_custom_menu_item(menu_id, title, Some(action), accelerators, MenuType::MenuBar);
+			if selected {
+				let () = msg_send![menu_item, setState: 1_isize];
+			}
+			if !enabled {
+				let () = msg_send![menu_item, setEnabled: NO];
+			}
+
+			let menu: id = msg_send![self.1, menu];
+			if menu != nil {
+				let index: isize = msg_send![menu, indexOfItem: self.1];
+				let () = msg_send![menu, insertItem: menu_item atIndex: index + after as isize];
+			}
+			CustomMenuItem(MenuItemAttributes(Some(menu_id), menu_item))
+		}
+	}
+
+	pub fn remove(&mut self) {
+		unsafe {
+			let menu: id = msg_send![self.1, menu];
+			if menu != nil {
+				let () = msg_send![menu, removeItem: self.1];
+			}
+		}
+	}
 }
 
 impl Default for Menu {
@@ -130,6 +170,7 @@ impl Menu {
 		Self::new()
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub fn add_item(
 		&mut self,
 		menu_id: MenuId,
@@ -137,6 +178,7 @@ impl Menu {
 		accelerators: Option<Accelerator>,
 		enabled: bool,
 		selected: bool,
+		_checkable: bool,
 		menu_type: MenuType
 	) -> CustomMenuItem {
 		let menu_item = make_custom_menu_item(menu_id, title, None, accelerators, menu_type);
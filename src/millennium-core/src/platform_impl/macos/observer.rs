@@ -32,6 +32,7 @@ use crate::platform_impl::platform::{
 #[link(name = "CoreFoundation", kind = "framework")]
 extern "C" {
 	pub static kCFRunLoopCommonModes: CFRunLoopMode;
+	pub static kCFRunLoopDefaultMode: CFRunLoopMode;
 
 	pub fn CFRunLoopGetMain() -> CFRunLoopRef;
 	pub fn CFRunLoopWakeUp(rl: CFRunLoopRef);
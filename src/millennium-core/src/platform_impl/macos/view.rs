@@ -189,6 +189,10 @@ lazy_static! {
 		decl.add_method(sel!(cancelOperation:), cancel_operation as extern "C" fn(&Object, Sel, id));
 		decl.add_method(sel!(frameDidChange:), frame_did_change as extern "C" fn(&Object, Sel, id));
 		decl.add_method(sel!(acceptsFirstMouse:), accepts_first_mouse as extern "C" fn(&Object, Sel, id) -> BOOL);
+		decl.add_method(
+			sel!(draggingSession:sourceOperationMaskForDraggingContext:),
+			dragging_source_operation_mask as extern "C" fn(&Object, Sel, id, NSInteger) -> NSUInteger
+		);
 		decl.add_ivar::<*mut c_void>("millenniumState");
 		decl.add_ivar::<id>("markedText");
 		let protocol = Protocol::get("NSTextInputClient").unwrap();
@@ -946,3 +950,9 @@ extern "C" fn wants_key_down_for_event(_this: &Object, _sel: Sel, _event: id) ->
 extern "C" fn accepts_first_mouse(_this: &Object, _sel: Sel, _event: id) -> BOOL {
 	YES
 }
+
+// `NSDraggingSource` conformance for sessions started by `Window::start_drag`.
+extern "C" fn dragging_source_operation_mask(_this: &Object, _sel: Sel, _session: id, _context: NSInteger) -> NSUInteger {
+	// NSDragOperationCopy
+	1
+}
@@ -15,10 +15,11 @@
 // limitations under the License.
 
 use std::{
-	collections::VecDeque,
+	collections::{HashMap, VecDeque},
 	convert::TryInto,
 	f64,
 	os::raw::c_void,
+	path::{Path, PathBuf},
 	sync::{
 		atomic::{AtomicBool, Ordering},
 		Arc, Mutex, Weak
@@ -27,11 +28,11 @@ use std::{
 
 use cocoa::{
 	appkit::{
-		self, CGFloat, NSApp, NSApplication, NSApplicationPresentationOptions, NSColor, NSEvent, NSRequestUserAttentionType, NSScreen, NSView, NSWindow,
-		NSWindowButton, NSWindowOrderingMode, NSWindowStyleMask
+		self, CGFloat, NSApp, NSApplication, NSApplicationPresentationOptions, NSColor, NSEvent, NSImage, NSRequestUserAttentionType, NSScreen, NSView,
+		NSWindow, NSWindowButton, NSWindowCollectionBehavior, NSWindowOrderingMode, NSWindowStyleMask
 	},
 	base::{id, nil},
-	foundation::{NSArray, NSAutoreleasePool, NSDictionary, NSPoint, NSRect, NSSize, NSString, NSUInteger}
+	foundation::{NSArray, NSAutoreleasePool, NSData, NSDictionary, NSInteger, NSPoint, NSRect, NSSize, NSString, NSUInteger}
 };
 use core_graphics::display::{CGDisplay, CGDisplayMode};
 use objc::{
@@ -48,15 +49,20 @@ use crate::{
 	monitor::{MonitorHandle as RootMonitorHandle, VideoMode as RootVideoMode},
 	platform::macos::WindowExtMacOS,
 	platform_impl::platform::{
+		app_delegate,
 		app_state::AppState,
 		ffi, menu,
 		monitor::{self, MonitorHandle, VideoMode},
+		observer,
 		util::{self, IdRef},
 		view::{self, new_view, CursorState},
 		window_delegate::new_delegate,
 		OsError
 	},
-	window::{CursorIcon, Fullscreen, Theme, UserAttentionType, WindowAttributes, WindowId as RootWindowId}
+	window::{
+		AccessibilityPreferences, CursorIcon, Fullscreen, LayoutDirection, Rect, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
+		WindowId as RootWindowId
+	}
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -220,6 +226,9 @@ fn create_window(attrs: &WindowAttributes, pl_attrs: &PlatformSpecificWindowBuil
 					let _: () = msg_send![button, setHidden: YES];
 				}
 			}
+			set_window_button_enabled(*ns_window, NSWindowButton::NSWindowMiniaturizeButton, attrs.minimizable);
+			set_window_button_enabled(*ns_window, NSWindowButton::NSWindowZoomButton, attrs.maximizable);
+			set_window_button_enabled(*ns_window, NSWindowButton::NSWindowCloseButton, attrs.closable);
 			if pl_attrs.movable_by_window_background {
 				ns_window.setMovableByWindowBackground_(YES);
 			}
@@ -257,6 +266,19 @@ fn create_window(attrs: &WindowAttributes, pl_attrs: &PlatformSpecificWindowBuil
 	}
 }
 
+// Equivalent Obj-C:
+// [[window standardWindowButton:button] setEnabled:enabled];
+unsafe fn set_window_button_enabled(ns_window: id, button: NSWindowButton, enabled: bool) {
+	let button = ns_window.standardWindowButton_(button);
+	let _: () = msg_send![button, setEnabled: if enabled { YES } else { NO }];
+}
+
+unsafe fn is_window_button_enabled(ns_window: id, button: NSWindowButton) -> bool {
+	let button = ns_window.standardWindowButton_(button);
+	let enabled: BOOL = msg_send![button, isEnabled];
+	enabled == YES
+}
+
 pub(super) fn get_ns_theme() -> Theme {
 	unsafe {
 		let mut appearances: Vec<id> = Vec::new();
@@ -308,6 +330,8 @@ lazy_static! {
 		decl.add_method(sel!(sendEvent:), send_event as extern "C" fn(&Object, Sel, id));
 		WindowClass(decl.register())
 	};
+	// keyed by the address of the `NSWindow`, since `sendEvent:` only gets the window itself
+	static ref DRAG_REGIONS: Mutex<HashMap<usize, Vec<Rect>>> = Default::default();
 }
 
 extern "C" fn send_event(this: &Object, _sel: Sel, event: id) {
@@ -319,7 +343,7 @@ extern "C" fn send_event(this: &Object, _sel: Sel, event: id) {
 				// Because of this, we need to invoke `[NSWindow performWindowDragWithEvent]` in the NSLeftMouseDown
 				// event manually.
 				let is_movable_window: BOOL = msg_send![this, isMovableByWindowBackground];
-				if is_movable_window == YES {
+				if is_movable_window == YES || in_drag_region(this, event) {
 					let _: () = msg_send![this, performWindowDragWithEvent: event];
 				}
 			}
@@ -331,6 +355,25 @@ extern "C" fn send_event(this: &Object, _sel: Sel, event: id) {
 	}
 }
 
+unsafe fn in_drag_region(window: &Object, event: id) -> bool {
+	let drag_regions = DRAG_REGIONS.lock().unwrap();
+	let regions = match drag_regions.get(&(window as *const Object as usize)) {
+		Some(regions) => regions,
+		None => return false
+	};
+
+	let content_view: id = msg_send![window, contentView];
+	let location: NSPoint = msg_send![event, locationInWindow];
+	let location: NSPoint = msg_send![content_view, convertPoint: location fromView: nil];
+	// the regions are relative to the top-left corner, while AppKit views usually start at the bottom-left
+	let is_flipped: BOOL = msg_send![content_view, isFlipped];
+	let y = if is_flipped == YES { location.y } else { NSView::frame(content_view).size.height - location.y };
+
+	let scale_factor = NSWindow::backingScaleFactor(window as *const Object as id) as f64;
+	let position = LogicalPosition::new(location.x as f64, y as f64).to_physical(scale_factor);
+	regions.iter().any(|r| r.contains(position, scale_factor))
+}
+
 #[derive(Default)]
 pub struct SharedState {
 	pub resizable: bool,
@@ -425,9 +468,31 @@ impl UnownedWindow {
 		let scale_factor = unsafe { NSWindow::backingScaleFactor(*ns_window) as f64 };
 
 		unsafe {
+			// AppKit follows the locale's direction unless told otherwise
+			if let Some(direction) = win_attribs.layout_direction {
+				// NSUserInterfaceLayoutDirectionLeftToRight / NSUserInterfaceLayoutDirectionRightToLeft
+				let direction: NSInteger = match direction {
+					LayoutDirection::LeftToRight => 0,
+					LayoutDirection::RightToLeft => 1
+				};
+				let () = msg_send![*ns_view, setUserInterfaceLayoutDirection: direction];
+				let () = msg_send![*ns_window, setWindowTitlebarLayoutDirection: direction];
+			}
+
 			if win_attribs.transparent {
 				ns_window.setOpaque_(NO);
 				ns_window.setBackgroundColor_(NSColor::clearColor(nil));
+			} else if let Some((r, g, b, a)) = win_attribs.title_bar_color {
+				// the title bar shows the window's background through it once it's transparent
+				ns_window.setTitlebarAppearsTransparent_(YES);
+				let color: id = msg_send![
+					class!(NSColor),
+					colorWithSRGBRed: r as CGFloat / 255.
+					green: g as CGFloat / 255.
+					blue: b as CGFloat / 255.
+					alpha: a as CGFloat / 255.
+				];
+				ns_window.setBackgroundColor_(color);
 			}
 
 			win_attribs.min_inner_size.map(|dim| {
@@ -611,6 +676,26 @@ impl UnownedWindow {
 		}
 	}
 
+	pub fn set_aspect_ratio(&self, aspect_ratio: Option<f64>) {
+		unsafe {
+			match aspect_ratio {
+				Some(ratio) => {
+					let () = msg_send![*self.ns_window, setContentAspectRatio: NSSize::new(ratio as CGFloat, 1.0)];
+				}
+				// setting resize increments is the documented way to clear an aspect ratio
+				None => {
+					let () = msg_send![*self.ns_window, setContentResizeIncrements: NSSize::new(1.0, 1.0)];
+				}
+			}
+		}
+	}
+
+	pub fn set_opacity(&self, opacity: f64) {
+		unsafe {
+			let () = msg_send![*self.ns_window, setAlphaValue: opacity as CGFloat];
+		}
+	}
+
 	#[inline]
 	pub fn set_resizable(&self, resizable: bool) {
 		let fullscreen = {
@@ -631,6 +716,21 @@ impl UnownedWindow {
 		} // Otherwise, we don't change the mask until we exit fullscreen.
 	}
 
+	#[inline]
+	pub fn set_minimizable(&self, minimizable: bool) {
+		unsafe { set_window_button_enabled(*self.ns_window, NSWindowButton::NSWindowMiniaturizeButton, minimizable) }
+	}
+
+	#[inline]
+	pub fn set_maximizable(&self, maximizable: bool) {
+		unsafe { set_window_button_enabled(*self.ns_window, NSWindowButton::NSWindowZoomButton, maximizable) }
+	}
+
+	#[inline]
+	pub fn set_closable(&self, closable: bool) {
+		unsafe { set_window_button_enabled(*self.ns_window, NSWindowButton::NSWindowCloseButton, closable) }
+	}
+
 	pub fn set_cursor_icon(&self, cursor: CursorIcon) {
 		let cursor = util::Cursor::from(cursor);
 		if let Some(cursor_access) = self.cursor_state.upgrade() {
@@ -696,6 +796,147 @@ impl UnownedWindow {
 		Ok(())
 	}
 
+	#[inline]
+	pub fn start_resize_drag(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
+		use ResizeDirection::*;
+
+		unsafe {
+			let event: id = msg_send![NSApp(), currentEvent];
+			if event == nil {
+				return Err(ExternalError::NotSupported(NotSupportedError::new()));
+			}
+
+			// AppKit has no public API to start an edge resize, so track the drag ourselves
+			// until the button is released. Frames are in screen coordinates with the origin
+			// at the bottom left, so the north edge grows upwards.
+			let ns_window = *self.ns_window;
+			let start_frame = NSWindow::frame(ns_window);
+			let start_mouse: NSPoint = msg_send![class!(NSEvent), mouseLocation];
+			let min_size: NSSize = msg_send![ns_window, minSize];
+			let max_size: NSSize = msg_send![ns_window, maxSize];
+			let mask = appkit::NSEventMask::NSLeftMouseDraggedMask | appkit::NSEventMask::NSLeftMouseUpMask;
+			let until: id = msg_send![class!(NSDate), distantFuture];
+
+			loop {
+				let event: id = msg_send![NSApp(), nextEventMatchingMask: mask.bits() untilDate: until inMode: observer::kCFRunLoopDefaultMode as id dequeue: YES];
+				if event == nil || event.eventType() == appkit::NSLeftMouseUp {
+					break;
+				}
+
+				let mouse: NSPoint = msg_send![class!(NSEvent), mouseLocation];
+				let (dx, dy) = (mouse.x - start_mouse.x, mouse.y - start_mouse.y);
+				let mut frame = start_frame;
+				match direction {
+					East | NorthEast | SouthEast => {
+						frame.size.width = (start_frame.size.width + dx).max(min_size.width).min(max_size.width);
+					}
+					West | NorthWest | SouthWest => {
+						frame.size.width = (start_frame.size.width - dx).max(min_size.width).min(max_size.width);
+						frame.origin.x = start_frame.origin.x + start_frame.size.width - frame.size.width;
+					}
+					_ => {}
+				}
+				match direction {
+					North | NorthEast | NorthWest => {
+						frame.size.height = (start_frame.size.height + dy).max(min_size.height).min(max_size.height);
+					}
+					South | SouthEast | SouthWest => {
+						frame.size.height = (start_frame.size.height - dy).max(min_size.height).min(max_size.height);
+						frame.origin.y = start_frame.origin.y + start_frame.size.height - frame.size.height;
+					}
+					_ => {}
+				}
+				NSWindow::setFrame_display_(ns_window, frame, YES);
+			}
+		}
+
+		Ok(())
+	}
+
+	#[inline]
+	pub fn titlebar_double_click(&self) {
+		let action = unsafe {
+			let defaults: id = msg_send![class!(NSUserDefaults), standardUserDefaults];
+			let action: id = msg_send![defaults, stringForKey: NSString::alloc(nil).init_str("AppleActionOnDoubleClick").autorelease()];
+			if action != nil {
+				util::ns_string_to_rust(action)
+			} else {
+				// releases before 10.11 only have the "minimize on double click" checkbox
+				let minimize: BOOL = msg_send![defaults, boolForKey: NSString::alloc(nil).init_str("AppleMiniaturizeOnDoubleClick").autorelease()];
+				if minimize == YES { "Minimize" } else { "Maximize" }.to_owned()
+			}
+		};
+
+		match action.as_str() {
+			"Minimize" => self.set_minimized(true),
+			"None" => {}
+			// "Maximize" zooms the window, and is the default
+			_ => {
+				if self.is_resizable() {
+					self.set_maximized(!self.is_zoomed());
+				}
+			}
+		}
+	}
+
+	#[inline]
+	pub fn set_drag_regions(&self, drag_regions: Vec<Rect>) {
+		let mut regions = DRAG_REGIONS.lock().unwrap();
+		if drag_regions.is_empty() {
+			regions.remove(&(*self.ns_window as usize));
+		} else {
+			regions.insert(*self.ns_window as usize, drag_regions);
+		}
+	}
+
+	#[inline]
+	pub fn drag_regions(&self) -> Vec<Rect> {
+		DRAG_REGIONS.lock().unwrap().get(&(*self.ns_window as usize)).cloned().unwrap_or_default()
+	}
+
+	#[inline]
+	pub fn start_drag(&self, paths: Vec<PathBuf>, icon: Option<Icon>) -> Result<(), ExternalError> {
+		unsafe {
+			let event: id = msg_send![NSApp(), currentEvent];
+			if event == nil {
+				return Err(ExternalError::NotSupported(NotSupportedError::new()));
+			}
+
+			let icon: id = match icon {
+				Some(icon) => {
+					let icon = icon.inner.to_png();
+					let nsdata = NSData::dataWithBytes_length_(nil, icon.as_ptr() as *const c_void, icon.len() as u64);
+					NSImage::initWithData_(NSImage::alloc(nil), nsdata).autorelease()
+				}
+				None => nil
+			};
+
+			let location: NSPoint = msg_send![*self.ns_view, convertPoint: NSEvent::locationInWindow(event) fromView: nil];
+			let items: id = msg_send![class!(NSMutableArray), array];
+			for path in &paths {
+				let path = NSString::alloc(nil).init_str(&path.to_string_lossy()).autorelease();
+				let url: id = msg_send![class!(NSURL), fileURLWithPath: path];
+				let item: id = msg_send![class!(NSDraggingItem), alloc];
+				let item: id = msg_send![item, initWithPasteboardWriter: url];
+				let image: id = if icon != nil {
+					icon
+				} else {
+					let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+					msg_send![workspace, iconForFile: path]
+				};
+				let size: NSSize = msg_send![image, size];
+				let frame = NSRect::new(NSPoint::new(location.x - size.width / 2., location.y - size.height / 2.), size);
+				let _: () = msg_send![item, setDraggingFrame: frame contents: image];
+				let _: () = msg_send![items, addObject: item];
+				let _: () = msg_send![item, release];
+			}
+
+			let _: id = msg_send![*self.ns_view, beginDraggingSessionWithItems: items event: event source: *self.ns_view];
+		}
+
+		Ok(())
+	}
+
 	pub(crate) fn is_zoomed(&self) -> bool {
 		// because `isZoomed` doesn't work if the window's borderless,
 		// we make it resizable temporalily.
@@ -787,12 +1028,33 @@ impl UnownedWindow {
 		is_visible == YES
 	}
 
+	#[inline]
+	pub fn is_minimized(&self) -> bool {
+		let is_minimized: BOOL = unsafe { msg_send![*self.ns_window, isMiniaturized] };
+		is_minimized == YES
+	}
+
 	#[inline]
 	pub fn is_resizable(&self) -> bool {
 		let is_resizable: BOOL = unsafe { msg_send![*self.ns_window, isResizable] };
 		is_resizable == YES
 	}
 
+	#[inline]
+	pub fn is_minimizable(&self) -> bool {
+		unsafe { is_window_button_enabled(*self.ns_window, NSWindowButton::NSWindowMiniaturizeButton) }
+	}
+
+	#[inline]
+	pub fn is_maximizable(&self) -> bool {
+		unsafe { is_window_button_enabled(*self.ns_window, NSWindowButton::NSWindowZoomButton) }
+	}
+
+	#[inline]
+	pub fn is_closable(&self) -> bool {
+		unsafe { is_window_button_enabled(*self.ns_window, NSWindowButton::NSWindowCloseButton) }
+	}
+
 	#[inline]
 	pub fn is_decorated(&self) -> bool {
 		let current_mask = unsafe { self.ns_window.styleMask() };
@@ -802,6 +1064,12 @@ impl UnownedWindow {
 		true
 	}
 
+	#[inline]
+	pub fn is_visible_on_all_workspaces(&self) -> bool {
+		let behavior = unsafe { self.ns_window.collectionBehavior() };
+		behavior.contains(NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces)
+	}
+
 	#[inline]
 	pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
 		trace!("Locked shared state in `set_fullscreen`");
@@ -1005,6 +1273,15 @@ impl UnownedWindow {
 		unsafe { util::set_level_async(*self.ns_window, level) };
 	}
 
+	#[inline]
+	pub fn set_visible_on_all_workspaces(&self, visible_on_all_workspaces: bool) {
+		unsafe {
+			let mut behavior = self.ns_window.collectionBehavior();
+			behavior.set(NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces, visible_on_all_workspaces);
+			self.ns_window.setCollectionBehavior_(behavior);
+		}
+	}
+
 	#[inline]
 	pub fn set_window_icon(&self, _icon: Option<Icon>) {
 		// macOS doesn't have window icons. Though, there is
@@ -1051,6 +1328,16 @@ impl UnownedWindow {
 		true
 	}
 
+	#[inline]
+	pub fn accent_color(&self) -> Option<(u8, u8, u8, u8)> {
+		app_delegate::accent_color()
+	}
+
+	#[inline]
+	pub fn accessibility_preferences(&self) -> AccessibilityPreferences {
+		app_delegate::accessibility_preferences()
+	}
+
 	#[inline]
 	// Allow directly accessing the current monitor internally without unwrapping.
 	pub(crate) fn current_monitor_inner(&self) -> RootMonitorHandle {
@@ -1184,11 +1471,20 @@ impl WindowExtMacOS for UnownedWindow {
 		let state = self.shared_state.lock().unwrap();
 		state.current_theme
 	}
+
+	#[inline]
+	fn set_represented_filename(&self, path: &Path) {
+		unsafe {
+			let filename = NSString::alloc(nil).init_str(&path.to_string_lossy()).autorelease();
+			let _: () = msg_send![*self.ns_window, setRepresentedFilename: filename];
+		}
+	}
 }
 
 impl Drop for UnownedWindow {
 	fn drop(&mut self) {
 		trace!("Dropping `UnownedWindow` ({:?})", self as *mut _);
+		DRAG_REGIONS.lock().unwrap().remove(&(*self.ns_window as usize));
 		// Close the window if it has not yet been closed.
 		if *self.ns_window != nil {
 			unsafe { util::close_async(self.ns_window.clone()) };
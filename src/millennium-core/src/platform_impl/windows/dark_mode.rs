@@ -29,6 +29,11 @@ use windows::{
 
 use crate::{platform_impl::platform::util, window::Theme};
 
+/// Returns the build number of Windows 10 or 11, or `None` on older versions.
+pub fn windows_build_number() -> Option<u32> {
+	*WIN10_BUILD_VERSION
+}
+
 lazy_static! {
 	static ref WIN10_BUILD_VERSION: Option<u32> = {
 		// FIXME: RtlGetVersion is a documented windows API,
@@ -152,6 +157,15 @@ fn set_dark_mode_for_window(hwnd: HWND, is_dark_mode: bool) -> bool {
 	}
 }
 
+/// Returns the theme the system asks applications to use.
+pub fn system_theme() -> Theme {
+	if *DARK_MODE_SUPPORTED && should_use_dark_mode() {
+		Theme::Dark
+	} else {
+		Theme::Light
+	}
+}
+
 fn should_use_dark_mode() -> bool {
 	should_apps_use_dark_mode() && !is_high_contrast()
 }
@@ -181,7 +195,7 @@ fn should_apps_use_dark_mode() -> bool {
 
 const HCF_HIGHCONTRASTON: u32 = 1;
 
-fn is_high_contrast() -> bool {
+pub fn is_high_contrast() -> bool {
 	let mut hc = HIGHCONTRASTA {
 		cbSize: 0,
 		dwFlags: Default::default(),
@@ -0,0 +1,84 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{path::PathBuf, ptr};
+
+use windows::{
+	core::PCWSTR,
+	Win32::{
+		Foundation::{BOOL, DRAGDROP_S_CANCEL, DRAGDROP_S_DROP, DRAGDROP_S_USEDEFAULTCURSORS, HRESULT, S_OK},
+		System::{
+			Com::IDataObject,
+			Ole::{DoDragDrop, IDropSource, IDropSource_Impl, DROPEFFECT_COPY, DROPEFFECT_LINK, DROPEFFECT_MOVE}
+		},
+		UI::Shell::{Common::ITEMIDLIST, IShellItemArray, ILFree, SHCreateShellItemArrayFromIDLists, SHParseDisplayName, BHID_DataObject}
+	}
+};
+use windows_implement::implement;
+
+use super::{util, OsError};
+use crate::error::ExternalError;
+
+// `MK_LBUTTON` from `WinUser.h`.
+const MK_LBUTTON: u32 = 0x0001;
+
+#[implement(IDropSource)]
+struct DragSource;
+
+#[allow(non_snake_case)]
+impl IDropSource_Impl for DragSource {
+	fn QueryContinueDrag(&self, fEscapePressed: BOOL, grfKeyState: u32) -> HRESULT {
+		if fEscapePressed.as_bool() {
+			DRAGDROP_S_CANCEL
+		} else if grfKeyState & MK_LBUTTON == 0 {
+			DRAGDROP_S_DROP
+		} else {
+			S_OK
+		}
+	}
+
+	fn GiveFeedback(&self, _dwEffect: u32) -> HRESULT {
+		DRAGDROP_S_USEDEFAULTCURSORS
+	}
+}
+
+/// Starts an OLE drag operation carrying the given files. This blocks until the drop completes or is cancelled.
+pub fn start_drag(paths: &[PathBuf]) -> Result<(), ExternalError> {
+	unsafe {
+		let mut pidls: Vec<*const ITEMIDLIST> = Vec::with_capacity(paths.len());
+		for path in paths {
+			let path = util::encode_wide(path);
+			let mut pidl = ptr::null_mut();
+			if SHParseDisplayName(PCWSTR(path.as_ptr()), None, &mut pidl, 0, ptr::null_mut()).is_ok() {
+				pidls.push(pidl);
+			}
+		}
+
+		let data_object = SHCreateShellItemArrayFromIDLists(pidls.len() as u32, pidls.as_ptr())
+			.and_then(|items: IShellItemArray| items.BindToHandler::<_, IDataObject>(None, &BHID_DataObject));
+		for pidl in pidls {
+			ILFree(pidl);
+		}
+		let data_object = data_object.map_err(|e| ExternalError::Os(os_error!(OsError::IoError(e.into()))))?;
+
+		let drop_source: IDropSource = DragSource.into();
+		let mut effect = 0;
+		DoDragDrop(&data_object, &drop_source, DROPEFFECT_COPY | DROPEFFECT_MOVE | DROPEFFECT_LINK, &mut effect)
+			.ok()
+			.map_err(|e| ExternalError::Os(os_error!(OsError::IoError(e.into()))))?;
+	}
+	Ok(())
+}
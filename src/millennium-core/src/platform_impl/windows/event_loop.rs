@@ -59,12 +59,14 @@ use crate::{
 	dpi::{PhysicalPosition, PhysicalSize},
 	event::{DeviceEvent, Event, Force, RawKeyEvent, Touch, TouchPhase, WindowEvent},
 	event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootELW},
+	jump_list::JumpListItem,
 	keyboard::{KeyCode, ModifiersState},
 	monitor::MonitorHandle as RootMonitorHandle,
 	platform_impl::platform::{
 		accelerator,
-		dark_mode::try_theme,
+		dark_mode::{system_theme, try_theme},
 		dpi::{become_dpi_aware, dpi_to_scale_factor, enable_non_client_dpi_scaling},
+		jump_list,
 		keyboard::is_msg_keyboard_related,
 		keyboard_layout::LAYOUT_CACHE,
 		minimal_ime::is_msg_ime_related,
@@ -73,7 +75,7 @@ use crate::{
 		window_state::{CursorFlags, WindowFlags, WindowState},
 		wrap_device_id, WindowId, DEVICE_ID
 	},
-	window::{Fullscreen, WindowId as RootWindowId}
+	window::{AccessibilityPreferences, Fullscreen, Theme, WindowId as RootWindowId}
 };
 
 type GetPointerFrameInfoHistory =
@@ -111,7 +113,10 @@ impl<T> SubclassInput<T> {
 
 struct ThreadMsgTargetSubclassInput<T: 'static> {
 	event_loop_runner: EventLoopRunnerShared<T>,
-	user_event_receiver: Receiver<T>
+	user_event_receiver: Receiver<T>,
+	system_theme: Cell<Theme>,
+	accent_color: Cell<Option<(u8, u8, u8, u8)>>,
+	accessibility_preferences: Cell<AccessibilityPreferences>
 }
 
 impl<T> ThreadMsgTargetSubclassInput<T> {
@@ -132,13 +137,22 @@ pub struct EventLoop<T: 'static> {
 	window_target: RootELW<T>
 }
 
-#[derive(Clone)]
 pub struct EventLoopWindowTarget<T: 'static> {
 	thread_id: u32,
 	thread_msg_target: HWND,
 	pub(crate) runner_shared: EventLoopRunnerShared<T>
 }
 
+impl<T: 'static> Clone for EventLoopWindowTarget<T> {
+	fn clone(&self) -> Self {
+		Self {
+			thread_id: self.thread_id,
+			thread_msg_target: self.thread_msg_target,
+			runner_shared: self.runner_shared.clone()
+		}
+	}
+}
+
 macro_rules! main_thread_check {
 	($fn_name:literal) => {{
 		let thread_id = unsafe { GetCurrentThreadId() };
@@ -229,6 +243,9 @@ impl<T: 'static> EventLoop<T> {
 			let mut msg = MSG::default();
 
 			runner.poll();
+			if let Some(id) = jump_list::take_launch_item() {
+				runner.send_event(Event::JumpListItemClicked { id });
+			}
 			'main: loop {
 				if !GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
 					break 'main 0;
@@ -294,6 +311,12 @@ impl<T> EventLoopWindowTarget<T> {
 		let monitor = monitor::primary_monitor();
 		Some(RootMonitorHandle { inner: monitor })
 	}
+
+	pub fn set_jump_list(&self, items: Vec<JumpListItem>) {
+		if let Err(error) = jump_list::set_jump_list(&items) {
+			warn!("Failed to set the jump list: {}", error);
+		}
+	}
 }
 
 fn main_thread_id() -> u32 {
@@ -589,7 +612,10 @@ fn subclass_event_target_window<T>(window: HWND, event_loop_runner: EventLoopRun
 
 		let subclass_input = ThreadMsgTargetSubclassInput {
 			event_loop_runner,
-			user_event_receiver: rx
+			user_event_receiver: rx,
+			system_theme: Cell::new(system_theme()),
+			accent_color: Cell::new(util::accent_color()),
+			accessibility_preferences: Cell::new(util::accessibility_preferences())
 		};
 		let input_ptr = Box::into_raw(Box::new(subclass_input));
 		let subclass_result = SetWindowSubclass(window, Some(thread_event_target_callback::<T>), THREAD_EVENT_TARGET_SUBCLASS_ID, input_ptr as usize);
@@ -1565,6 +1591,32 @@ unsafe fn public_window_callback_inner<T: 'static>(
 			result = ProcResult::Value(LRESULT(0));
 		}
 
+		win32wm::WM_SIZING => {
+			let aspect_ratio = subclass_input.window_state.lock().aspect_ratio;
+
+			match (aspect_ratio, util::get_window_rect(window), util::get_client_rect(window)) {
+				(Some(aspect_ratio), Some(window_rect), Ok(client_rect)) => {
+					let rect = &mut *(lparam.0 as *mut RECT);
+					// the ratio applies to the client area, so leave the frame out of the calculation
+					let frame_width = (window_rect.right - window_rect.left) - (client_rect.right - client_rect.left);
+					let frame_height = (window_rect.bottom - window_rect.top) - (client_rect.bottom - client_rect.top);
+					let width = (rect.right - rect.left - frame_width).max(1);
+					let height = (rect.bottom - rect.top - frame_height).max(1);
+
+					match wparam.0 as u32 {
+						// dragging the top or bottom edge drives the width...
+						WMSZ_TOP | WMSZ_BOTTOM => rect.right = rect.left + (height as f64 * aspect_ratio).round() as i32 + frame_width,
+						// ...and every other edge or corner drives the height
+						WMSZ_TOPLEFT | WMSZ_TOPRIGHT => rect.top = rect.bottom - (width as f64 / aspect_ratio).round() as i32 - frame_height,
+						_ => rect.bottom = rect.top + (width as f64 / aspect_ratio).round() as i32 + frame_height
+					}
+
+					result = ProcResult::Value(LRESULT(1));
+				}
+				_ => result = ProcResult::DefWindowProc
+			}
+		}
+
 		// Only sent on Windows 8.1 or newer. On Windows 7 and older user has to log out to change
 		// DPI, therefore all applications are closed while DPI is changing.
 		win32wm::WM_DPICHANGED => {
@@ -1822,15 +1874,15 @@ unsafe fn public_window_callback_inner<T: 'static>(
 		}
 
 		win32wm::WM_NCHITTEST => {
+			// cursor location
+			let (cx, cy) = (i32::from(util::GET_X_LPARAM(lparam)), i32::from(util::GET_Y_LPARAM(lparam)));
+
 			if !util::is_maximized(window) {
 				if let Some(state) = subclass_input.window_state.try_lock() {
 					let win_flags = state.window_flags();
 
 					// Only apply this hit test for borderless windows that wants to be resizable
 					if !win_flags.contains(WindowFlags::DECORATIONS) {
-						// cursor location
-						let (cx, cy) = (i32::from(util::GET_X_LPARAM(lparam)), i32::from(util::GET_Y_LPARAM(lparam)));
-
 						result = ProcResult::Value(crate::platform_impl::hit_test(window.0 as _, cx, cy));
 					} else if !win_flags.contains(WindowFlags::RESIZABLE) {
 						// we need WS_THICKFRAME for borders when decorations are enabled, but this
@@ -1847,6 +1899,27 @@ unsafe fn public_window_callback_inner<T: 'static>(
 					}
 				}
 			}
+
+			// the client area under a drag region acts as the caption, so the system moves the window
+			if let Some(state) = subclass_input.window_state.try_lock() {
+				if !state.drag_regions.is_empty() {
+					let hit = match result {
+						ProcResult::Value(hit) => hit,
+						_ => DefSubclassProc(window, msg, wparam, lparam)
+					};
+					let mut point = POINT { x: cx, y: cy };
+					if hit.0 as u32 == HTCLIENT && ScreenToClient(window, &mut point as *mut _).as_bool() {
+						let position = PhysicalPosition::new(point.x as f64, point.y as f64);
+						if state.drag_regions.iter().any(|r| r.contains(position, state.scale_factor)) {
+							result = ProcResult::Value(LRESULT(HTCAPTION as _));
+						} else {
+							result = ProcResult::Value(hit);
+						}
+					} else {
+						result = ProcResult::Value(hit);
+					}
+				}
+			}
 		}
 
 		_ => {
@@ -1948,6 +2021,33 @@ unsafe extern "system" fn thread_event_target_callback<T: 'static>(
 			DefSubclassProc(window, msg, wparam, lparam)
 		}
 
+		// The thread event target is a top-level window, so it receives the `WM_SETTINGCHANGE`
+		// broadcast sent when the user switches between light and dark mode or changes an
+		// accessibility setting.
+		win32wm::WM_WININICHANGE => {
+			let theme = system_theme();
+			if subclass_input.system_theme.replace(theme) != theme {
+				subclass_input.send_event(Event::ThemeChanged(theme));
+			}
+
+			let preferences = util::accessibility_preferences();
+			if subclass_input.accessibility_preferences.replace(preferences) != preferences {
+				subclass_input.send_event(Event::AccessibilityPreferencesChanged(preferences));
+			}
+
+			DefSubclassProc(window, msg, wparam, lparam)
+		}
+
+		win32wm::WM_DWMCOLORIZATIONCOLORCHANGED => {
+			if let Some(color) = util::accent_color() {
+				if subclass_input.accent_color.replace(Some(color)) != Some(color) {
+					subclass_input.send_event(Event::AccentColorChanged(color));
+				}
+			}
+
+			DefSubclassProc(window, msg, wparam, lparam)
+		}
+
 		_ if msg == *USER_EVENT_MSG_ID => {
 			if let Ok(event) = subclass_input.user_event_receiver.recv() {
 				subclass_input.send_event(Event::UserEvent(event));
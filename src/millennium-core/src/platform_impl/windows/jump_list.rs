@@ -0,0 +1,135 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+	env, iter,
+	sync::atomic::{AtomicBool, Ordering}
+};
+
+use windows::{
+	core::{Interface, PCWSTR, PWSTR},
+	Win32::{
+		Foundation::E_FAIL,
+		Storage::EnhancedStorage::PKEY_Title,
+		System::{
+			Com::{CoCreateInstance, StructuredStorage::PROPVARIANT, CLSCTX_INPROC_SERVER},
+			Ole::VT_LPWSTR
+		},
+		UI::Shell::{
+			Common::{IObjectArray, IObjectCollection},
+			DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW,
+			PropertiesSystem::IPropertyStore,
+			ShellLink
+		}
+	}
+};
+
+use super::{util, window::com_initialized};
+use crate::jump_list::JumpListItem;
+
+/// The argument a jump list task launches the app with, followed by the item's id.
+const ITEM_ARG_PREFIX: &str = "--jump-list-item=";
+
+pub fn set_jump_list(items: &[JumpListItem]) -> windows::core::Result<()> {
+	com_initialized();
+
+	unsafe {
+		let list: ICustomDestinationList = CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+		if items.is_empty() {
+			return list.DeleteList(PCWSTR::default());
+		}
+
+		let exe = env::current_exe().map_err(|error| windows::core::Error::new(E_FAIL, error.to_string().into()))?;
+		let exe = util::encode_wide(exe);
+
+		let mut min_slots = 0;
+		let _removed: IObjectArray = list.BeginList(&mut min_slots)?;
+
+		let tasks: IObjectCollection = CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+		for item in items {
+			let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+			link.SetPath(PCWSTR(exe.as_ptr()))?;
+			link.SetIconLocation(PCWSTR(exe.as_ptr()), 0)?;
+			let arguments = util::encode_wide(quote_argument(&format!("{}{}", ITEM_ARG_PREFIX, item.id)));
+			link.SetArguments(PCWSTR(arguments.as_ptr()))?;
+
+			// tasks show the link's title rather than its description
+			let mut title = util::encode_wide(&item.title);
+			let mut value = PROPVARIANT::default();
+			(*value.Anonymous.Anonymous).vt = VT_LPWSTR.0 as u16;
+			(*value.Anonymous.Anonymous).Anonymous.pwszVal = PWSTR(title.as_mut_ptr());
+			let store: IPropertyStore = link.cast()?;
+			store.SetValue(&PKEY_Title, &value)?;
+			store.Commit()?;
+
+			tasks.AddObject(&link)?;
+		}
+
+		list.AddUserTasks(&tasks)?;
+		list.CommitList()
+	}
+}
+
+/// Quotes `arg` so that `CommandLineToArgvW` parses it back as a single argument, whatever spaces, quotes or
+/// backslashes it contains.
+fn quote_argument(arg: &str) -> String {
+	let mut quoted = String::from('"');
+	let mut backslashes = 0;
+	for c in arg.chars() {
+		match c {
+			'\\' => backslashes += 1,
+			// backslashes are only special before a quote, where each of them and the quote must be escaped
+			'"' => {
+				quoted.extend(iter::repeat('\\').take(backslashes * 2 + 1));
+				quoted.push('"');
+				backslashes = 0;
+			}
+			c => {
+				quoted.extend(iter::repeat('\\').take(backslashes));
+				quoted.push(c);
+				backslashes = 0;
+			}
+		}
+	}
+	// the closing quote follows the trailing backslashes, so they must be escaped too
+	quoted.extend(iter::repeat('\\').take(backslashes * 2));
+	quoted.push('"');
+	quoted
+}
+
+/// Returns the id of the jump list item the app was launched from, if any.
+/// Only the first call can return an item.
+pub fn take_launch_item() -> Option<String> {
+	static TAKEN: AtomicBool = AtomicBool::new(false);
+	if TAKEN.swap(true, Ordering::SeqCst) {
+		return None;
+	}
+
+	env::args().find_map(|arg| arg.strip_prefix(ITEM_ARG_PREFIX).map(String::from))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::quote_argument;
+
+	#[test]
+	fn quotes_arguments() {
+		assert_eq!(quote_argument("--jump-list-item=open"), r#""--jump-list-item=open""#);
+		assert_eq!(quote_argument("--jump-list-item=open recent"), r#""--jump-list-item=open recent""#);
+		assert_eq!(quote_argument(r#"--jump-list-item=say "hi""#), r#""--jump-list-item=say \"hi\"""#);
+		assert_eq!(quote_argument(r#"--jump-list-item=a\b\"c\"#), r#""--jump-list-item=a\b\\\"c\\""#);
+	}
+}
@@ -151,6 +151,52 @@ impl MenuItemAttributes {
 
 	// todo: set custom icon to the menu item
 	pub fn set_icon(&mut self, _icon: Icon) {}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn insert_item(
+		&mut self,
+		menu_id: MenuId,
+		title: &str,
+		accelerator: Option<Accelerator>,
+		enabled: bool,
+		selected: bool,
+		_checkable: bool,
+		after: bool
+	) -> CustomMenuItem {
+		unsafe {
+			let (flags, title) = item_flags_and_title(title, accelerator.as_ref(), enabled, selected);
+			// menu ids are unique, so this finds the position of this item
+			let position = (0..GetMenuItemCount(self.1)).find(|&position| GetMenuItemID(self.1, position) == self.0 as u32);
+			if let Some(position) = position {
+				InsertMenuW(self.1, (position + after as i32) as u32, flags | MF_BYPOSITION, menu_id.0 as _, PCWSTR(util::encode_wide(title).as_ptr()));
+				MENU_IDS.lock().unwrap().push(menu_id.0 as _);
+			}
+			CustomMenuItem(MenuItemAttributes(menu_id.0, self.1, accelerator))
+		}
+	}
+
+	pub fn remove(&mut self) {
+		unsafe {
+			RemoveMenu(self.1, self.0 as u32, MF_BYCOMMAND);
+		}
+	}
+}
+
+fn item_flags_and_title(title: &str, accelerator: Option<&Accelerator>, enabled: bool, selected: bool) -> (MENU_ITEM_FLAGS, String) {
+	let mut flags = MF_STRING;
+	if !enabled {
+		flags |= MF_GRAYED;
+	}
+	if selected {
+		flags |= MF_CHECKED;
+	}
+
+	let mut title = title.to_string();
+	if let Some(accelerator) = accelerator {
+		title.push('\t');
+		title.push_str(accelerator.to_string().as_str());
+	}
+	(flags, title)
 }
 
 #[derive(Debug, Clone)]
@@ -195,6 +241,7 @@ impl Menu {
 		Some(self.accels.values().cloned().map(|d| d.0).collect())
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub fn add_item(
 		&mut self,
 		menu_id: MenuId,
@@ -202,23 +249,11 @@ impl Menu {
 		accelerator: Option<Accelerator>,
 		enabled: bool,
 		selected: bool,
+		_checkable: bool,
 		_menu_type: MenuType
 	) -> CustomMenuItem {
 		unsafe {
-			let mut flags = MF_STRING;
-			if !enabled {
-				flags |= MF_GRAYED;
-			}
-			if selected {
-				flags |= MF_CHECKED;
-			}
-
-			let mut title = title.to_string();
-			if let Some(accelerator) = &accelerator {
-				title.push('\t');
-				title.push_str(accelerator.to_string().as_str());
-			}
-
+			let (flags, title) = item_flags_and_title(title, accelerator.as_ref(), enabled, selected);
 			AppendMenuW(self.hmenu, flags, menu_id.0 as _, PCWSTR(util::encode_wide(title).as_ptr()));
 
 			// add our accels
@@ -24,6 +24,7 @@ use windows::Win32::{
 pub use self::icon::WinIcon as PlatformIcon;
 pub use self::{
 	clipboard::Clipboard,
+	dark_mode::windows_build_number,
 	event_loop::{EventLoop, EventLoopProxy, EventLoopWindowTarget},
 	global_shortcut::{GlobalShortcut, ShortcutManager},
 	icon::WinIcon,
@@ -150,9 +151,11 @@ mod util;
 mod clipboard;
 mod dark_mode;
 mod dpi;
+mod drag_source;
 mod drop_handler;
 mod event_loop;
 mod icon;
+mod jump_list;
 mod keyboard;
 mod keyboard_layout;
 mod minimal_ime;
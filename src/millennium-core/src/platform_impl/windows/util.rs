@@ -27,10 +27,16 @@ use std::{
 use windows::{
 	core::{HRESULT, PCWSTR},
 	Win32::{
-		Foundation::{BOOL, FARPROC, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
-		Globalization::lstrlenW,
-		Graphics::Gdi::{ClientToScreen, InvalidateRgn, HMONITOR, HRGN},
-		System::LibraryLoader::*,
+		Foundation::{BOOL, ERROR_SUCCESS, FARPROC, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
+		Globalization::{lstrlenW, GetUserDefaultUILanguage},
+		Graphics::{
+			Dwm::DwmGetColorizationColor,
+			Gdi::{ClientToScreen, InvalidateRgn, HMONITOR, HRGN}
+		},
+		System::{
+			LibraryLoader::*,
+			Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD}
+		},
 		UI::{
 			HiDpi::*,
 			Input::KeyboardAndMouse::*,
@@ -40,7 +46,11 @@ use windows::{
 	}
 };
 
-use crate::{dpi::PhysicalSize, window::CursorIcon};
+use super::dark_mode;
+use crate::{
+	dpi::PhysicalSize,
+	window::{AccessibilityPreferences, CursorIcon}
+};
 
 pub fn has_flag<T>(bitset: T, flag: T) -> bool
 where
@@ -49,6 +59,60 @@ where
 	bitset & flag == flag
 }
 
+/// Whether the user's UI language is written right-to-left, in which case
+/// windows should be mirrored with `WS_EX_LAYOUTRTL`.
+pub fn is_rtl_locale() -> bool {
+	// primary language IDs from `winnt.h`: Arabic, Hebrew, Urdu, Yiddish, Farsi,
+	// Sindhi, Syriac, Pashto, Divehi, Uyghur & Central Kurdish
+	const RTL_LANGUAGES: [u16; 11] = [0x01, 0x0d, 0x20, 0x3d, 0x29, 0x59, 0x5a, 0x63, 0x65, 0x80, 0x92];
+	let primary_language = unsafe { GetUserDefaultUILanguage() } & 0x3ff;
+	RTL_LANGUAGES.contains(&primary_language)
+}
+
+/// The DWM colorization color, which follows the accent color picked in the
+/// personalization settings. Returned as RGBA.
+pub fn accent_color() -> Option<(u8, u8, u8, u8)> {
+	let mut color = 0u32;
+	let mut opaque_blend = BOOL::default();
+	unsafe { DwmGetColorizationColor(&mut color, &mut opaque_blend) }.ok()?;
+	// 0xAARRGGBB
+	let [alpha, red, green, blue] = color.to_be_bytes();
+	Some((red, green, blue, alpha))
+}
+
+/// Reads the display preferences from the system's accessibility settings.
+pub fn accessibility_preferences() -> AccessibilityPreferences {
+	let mut animations = BOOL(1);
+	let ok = unsafe { SystemParametersInfoW(SPI_GETCLIENTAREAANIMATION, 0, &mut animations as *mut _ as _, Default::default()) };
+	AccessibilityPreferences {
+		reduce_motion: ok.as_bool() && !animations.as_bool(),
+		increase_contrast: dark_mode::is_high_contrast(),
+		reduce_transparency: !transparency_effects_enabled()
+	}
+}
+
+/// "Transparency effects" in the personalization settings, which has no
+/// `SystemParametersInfo` counterpart.
+fn transparency_effects_enabled() -> bool {
+	let subkey = encode_wide("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize");
+	let value = encode_wide("EnableTransparency");
+	let mut enabled = 1u32;
+	let mut size = mem::size_of::<u32>() as u32;
+	let status = unsafe {
+		RegGetValueW(
+			HKEY_CURRENT_USER,
+			PCWSTR(subkey.as_ptr()),
+			PCWSTR(value.as_ptr()),
+			RRF_RT_REG_DWORD,
+			ptr::null_mut(),
+			&mut enabled as *mut _ as _,
+			&mut size
+		)
+	};
+	// the value is missing on systems that never changed it, where transparency is on
+	status != ERROR_SUCCESS || enabled != 0
+}
+
 pub fn wchar_to_string(wchar: &[u16]) -> String {
 	String::from_utf16_lossy(wchar)
 }
@@ -21,6 +21,7 @@ use std::{
 	ffi::OsStr,
 	io, mem,
 	os::windows::ffi::OsStrExt,
+	path::PathBuf,
 	ptr,
 	sync::Arc
 };
@@ -34,7 +35,10 @@ use windows::{
 	Win32::{
 		Foundation::{self as win32f, HINSTANCE, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
 		Graphics::{
-			Dwm::{DwmEnableBlurBehindWindow, DWM_BB_BLURREGION, DWM_BB_ENABLE, DWM_BLURBEHIND},
+			Dwm::{
+				DwmEnableBlurBehindWindow, DwmSetWindowAttribute, DWMWA_BORDER_COLOR, DWMWA_CAPTION_COLOR, DWMWA_COLOR_DEFAULT, DWMWA_TEXT_COLOR,
+				DWMWINDOWATTRIBUTE, DWM_BB_BLURREGION, DWM_BB_ENABLE, DWM_BLURBEHIND
+			},
 			Gdi::*
 		},
 		System::{Com::*, LibraryLoader::*, Ole::*},
@@ -56,6 +60,7 @@ use crate::{
 	platform_impl::platform::{
 		dark_mode::try_theme,
 		dpi::{dpi_to_scale_factor, hwnd_dpi},
+		drag_source,
 		drop_handler::FileDropHandler,
 		event_loop::{self, EventLoopWindowTarget, DESTROY_MSG_ID},
 		icon::{self, IconType},
@@ -63,7 +68,10 @@ use crate::{
 		window_state::{CursorFlags, SavedWindow, WindowFlags, WindowState},
 		OsError, Parent, PlatformSpecificWindowBuilderAttributes, WindowId
 	},
-	window::{CursorIcon, Fullscreen, Theme, UserAttentionType, WindowAttributes, WindowId as RootWindowId, BORDERLESS_RESIZE_INSET}
+	window::{
+		CursorIcon, Fullscreen, LayoutDirection, Rect, ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowId as RootWindowId,
+		BORDERLESS_RESIZE_INSET
+	}
 };
 
 struct HMenuWrapper(HMENU);
@@ -268,6 +276,29 @@ impl Window {
 		self.set_inner_size(size.into());
 	}
 
+	#[inline]
+	pub fn set_aspect_ratio(&self, aspect_ratio: Option<f64>) {
+		self.window_state.lock().aspect_ratio = aspect_ratio;
+	}
+
+	#[inline]
+	pub fn set_opacity(&self, opacity: f64) {
+		let window = self.window.clone();
+		let window_state = Arc::clone(&self.window_state);
+
+		self.thread_executor.execute_in_thread(move || {
+			// a layered window with a constant alpha of 255 still goes through the slower
+			// layered composition path, so drop the style entirely when fully opaque
+			let layered = opacity < 1.0;
+			WindowState::set_window_flags(window_state.lock(), window.0, |f| f.set(WindowFlags::LAYERED, layered));
+			if layered {
+				unsafe {
+					SetLayeredWindowAttributes(window.0, 0, (opacity * 255.0).round() as u8, LWA_ALPHA);
+				}
+			}
+		});
+	}
+
 	#[inline]
 	pub fn set_resizable(&self, resizable: bool) {
 		let window = self.window.clone();
@@ -278,6 +309,36 @@ impl Window {
 		});
 	}
 
+	#[inline]
+	pub fn set_minimizable(&self, minimizable: bool) {
+		let window = self.window.clone();
+		let window_state = Arc::clone(&self.window_state);
+
+		self.thread_executor.execute_in_thread(move || {
+			WindowState::set_window_flags(window_state.lock(), window.0, |f| f.set(WindowFlags::MINIMIZABLE, minimizable));
+		});
+	}
+
+	#[inline]
+	pub fn set_maximizable(&self, maximizable: bool) {
+		let window = self.window.clone();
+		let window_state = Arc::clone(&self.window_state);
+
+		self.thread_executor.execute_in_thread(move || {
+			WindowState::set_window_flags(window_state.lock(), window.0, |f| f.set(WindowFlags::MAXIMIZABLE, maximizable));
+		});
+	}
+
+	#[inline]
+	pub fn set_closable(&self, closable: bool) {
+		let window = self.window.clone();
+		let window_state = Arc::clone(&self.window_state);
+
+		self.thread_executor.execute_in_thread(move || {
+			WindowState::set_window_flags(window_state.lock(), window.0, |f| f.set(WindowFlags::CLOSABLE, closable));
+		});
+	}
+
 	/// Returns the `hwnd` of this window.
 	#[inline]
 	pub fn hwnd(&self) -> HWND {
@@ -374,6 +435,54 @@ impl Window {
 		Ok(())
 	}
 
+	#[inline]
+	pub fn start_resize_drag(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
+		let hit_test = match direction {
+			ResizeDirection::East => HTRIGHT,
+			ResizeDirection::North => HTTOP,
+			ResizeDirection::NorthEast => HTTOPRIGHT,
+			ResizeDirection::NorthWest => HTTOPLEFT,
+			ResizeDirection::South => HTBOTTOM,
+			ResizeDirection::SouthEast => HTBOTTOMRIGHT,
+			ResizeDirection::SouthWest => HTBOTTOMLEFT,
+			ResizeDirection::West => HTLEFT
+		};
+		let mut pos = POINT::default();
+		unsafe {
+			GetCursorPos(&mut pos);
+			ReleaseCapture();
+			PostMessageW(self.window.0, WM_NCLBUTTONDOWN, WPARAM(hit_test as _), util::MAKELPARAM(pos.x as i16, pos.y as i16));
+		}
+
+		Ok(())
+	}
+
+	#[inline]
+	pub fn titlebar_double_click(&self) {
+		let mut pos = POINT::default();
+		unsafe {
+			GetCursorPos(&mut pos);
+			// the default window procedure maximizes or restores for a double click on the
+			// caption, as long as the window has a maximize box
+			PostMessageW(self.window.0, WM_NCLBUTTONDBLCLK, WPARAM(HTCAPTION as _), util::MAKELPARAM(pos.x as i16, pos.y as i16));
+		}
+	}
+
+	#[inline]
+	pub fn set_drag_regions(&self, drag_regions: Vec<Rect>) {
+		self.window_state.lock().drag_regions = drag_regions;
+	}
+
+	#[inline]
+	pub fn drag_regions(&self) -> Vec<Rect> {
+		self.window_state.lock().drag_regions.clone()
+	}
+
+	#[inline]
+	pub fn start_drag(&self, paths: Vec<PathBuf>, _icon: Option<Icon>) -> Result<(), ExternalError> {
+		drag_source::start_drag(&paths)
+	}
+
 	#[inline]
 	pub fn id(&self) -> WindowId {
 		WindowId(self.window.0.0)
@@ -405,18 +514,47 @@ impl Window {
 		window_state.window_flags.contains(WindowFlags::MAXIMIZED)
 	}
 
+	#[inline]
+	pub fn is_minimized(&self) -> bool {
+		let window_state = self.window_state.lock();
+		window_state.window_flags.contains(WindowFlags::MINIMIZED)
+	}
+
 	#[inline]
 	pub fn is_resizable(&self) -> bool {
 		let window_state = self.window_state.lock();
 		window_state.window_flags.contains(WindowFlags::RESIZABLE)
 	}
 
+	#[inline]
+	pub fn is_minimizable(&self) -> bool {
+		let window_state = self.window_state.lock();
+		window_state.window_flags.contains(WindowFlags::MINIMIZABLE)
+	}
+
+	#[inline]
+	pub fn is_maximizable(&self) -> bool {
+		let window_state = self.window_state.lock();
+		window_state.window_flags.contains(WindowFlags::MAXIMIZABLE)
+	}
+
+	#[inline]
+	pub fn is_closable(&self) -> bool {
+		let window_state = self.window_state.lock();
+		window_state.window_flags.contains(WindowFlags::CLOSABLE)
+	}
+
 	#[inline]
 	pub fn is_decorated(&self) -> bool {
 		let window_state = self.window_state.lock();
 		window_state.window_flags.contains(WindowFlags::DECORATIONS)
 	}
 
+	#[inline]
+	pub fn is_visible_on_all_workspaces(&self) -> bool {
+		false
+	}
+
 	#[inline]
 	pub fn is_visible(&self) -> bool {
 		util::is_visible(self.window.0)
@@ -566,6 +704,11 @@ impl Window {
 		});
 	}
 
+	#[inline]
+	pub fn set_visible_on_all_workspaces(&self, _visible_on_all_workspaces: bool) {
+		// virtual desktops have no public API to pin a window to every desktop
+	}
+
 	#[inline]
 	pub fn current_monitor(&self) -> Option<RootMonitorHandle> {
 		Some(RootMonitorHandle {
@@ -667,6 +810,16 @@ impl Window {
 		unsafe { !GetMenu(self.hwnd()).is_invalid() }
 	}
 
+	#[inline]
+	pub fn accent_color(&self) -> Option<(u8, u8, u8, u8)> {
+		util::accent_color()
+	}
+
+	#[inline]
+	pub fn accessibility_preferences(&self) -> AccessibilityPreferences {
+		util::accessibility_preferences()
+	}
+
 	#[inline]
 	pub fn reset_dead_keys(&self) {
 		// `ToUnicode` consumes the dead-key by default, so we are constructing a fake
@@ -703,6 +856,25 @@ impl Window {
 			}
 		}
 	}
+
+	#[inline]
+	pub(crate) fn set_title_bar_colors(&self, caption: Option<(u8, u8, u8, u8)>, text: Option<(u8, u8, u8, u8)>, border: Option<(u8, u8, u8, u8)>) {
+		unsafe {
+			set_dwm_color(self.hwnd(), DWMWA_CAPTION_COLOR, caption);
+			set_dwm_color(self.hwnd(), DWMWA_TEXT_COLOR, text);
+			set_dwm_color(self.hwnd(), DWMWA_BORDER_COLOR, border);
+		}
+	}
+}
+
+// Only Windows 11 knows the color attributes; older versions return an error which we ignore.
+unsafe fn set_dwm_color(hwnd: HWND, attribute: DWMWINDOWATTRIBUTE, color: Option<(u8, u8, u8, u8)>) {
+	let color = match color {
+		// COLORREF is 0x00BBGGRR
+		Some((r, g, b, _)) => r as u32 | (g as u32) << 8 | (b as u32) << 16,
+		None => DWMWA_COLOR_DEFAULT
+	};
+	let _ = DwmSetWindowAttribute(hwnd, attribute, &color as *const u32 as *const _, std::mem::size_of::<u32>() as u32);
 }
 
 impl Drop for Window {
@@ -745,7 +917,17 @@ unsafe fn init<T: 'static>(
 	// WindowFlags::VISIBLE and MAXIMIZED are set down below after the window has
 	// been configured.
 	window_flags.set(WindowFlags::RESIZABLE, attributes.resizable);
+	window_flags.set(WindowFlags::MINIMIZABLE, attributes.minimizable);
+	window_flags.set(WindowFlags::MAXIMIZABLE, attributes.maximizable);
+	window_flags.set(WindowFlags::CLOSABLE, attributes.closable);
 	window_flags.set(WindowFlags::HIDDEN_TITLEBAR, pl_attribs.titlebar_hidden);
+	window_flags.set(
+		WindowFlags::RIGHT_TO_LEFT,
+		match attributes.layout_direction {
+			Some(direction) => direction == LayoutDirection::RightToLeft,
+			None => util::is_rtl_locale()
+		}
+	);
 
 	let parent = match pl_attribs.parent {
 		Parent::ChildOf(parent) => {
@@ -818,6 +1000,10 @@ unsafe fn init<T: 'static>(
 		DeleteObject(region);
 	}
 
+	if attributes.title_bar_color.is_some() {
+		set_dwm_color(real_window.0, DWMWA_CAPTION_COLOR, attributes.title_bar_color);
+	}
+
 	// If the system theme is dark, we need to set the window theme now
 	// before we update the window flags (and possibly show the
 	// window for the first time).
@@ -28,7 +28,7 @@ use crate::{
 	icon::Icon,
 	keyboard::ModifiersState,
 	platform_impl::platform::{event_loop, minimal_ime::MinimalIme, util},
-	window::{CursorIcon, Fullscreen, Theme, WindowAttributes}
+	window::{CursorIcon, Fullscreen, Rect, Theme, WindowAttributes}
 };
 
 /// Contains information about states and the window that the callback is going
@@ -39,6 +39,8 @@ pub struct WindowState {
 	/// Used by `WM_GETMINMAXINFO`.
 	pub min_size: Option<Size>,
 	pub max_size: Option<Size>,
+	/// Used by `WM_SIZING`.
+	pub aspect_ratio: Option<f64>,
 
 	pub window_icon: Option<Icon>,
 	pub taskbar_icon: Option<Icon>,
@@ -54,6 +56,9 @@ pub struct WindowState {
 
 	pub ime_handler: MinimalIme,
 
+	/// Used by `WM_NCHITTEST`.
+	pub drag_regions: Vec<Rect>,
+
 	pub window_flags: WindowFlags
 }
 
@@ -90,6 +95,11 @@ bitflags! {
 		const MAXIMIZED       = 1 << 8;
 		const POPUP           = 1 << 14;
 		const HIDDEN_TITLEBAR = 1 << 15;
+		const RIGHT_TO_LEFT   = 1 << 16;
+		const LAYERED         = 1 << 17;
+		const MINIMIZABLE     = 1 << 18;
+		const MAXIMIZABLE     = 1 << 19;
+		const CLOSABLE        = 1 << 20;
 
 		/// Marker flag for fullscreen. Should always match `WindowState::fullscreen`, but is
 		/// included here to make masking easier.
@@ -112,6 +122,10 @@ bitflags! {
 	}
 }
 
+/// The flags of a window before its attributes are applied. A new window's close button is enabled, so `CLOSABLE` is
+/// set for a window created with the close button disabled to see it change.
+const INITIAL_WINDOW_FLAGS: WindowFlags = WindowFlags::CLOSABLE;
+
 impl WindowState {
 	pub fn new(
 		attributes: &WindowAttributes,
@@ -130,6 +144,7 @@ impl WindowState {
 
 			min_size: attributes.min_inner_size,
 			max_size: attributes.max_inner_size,
+			aspect_ratio: None,
 
 			window_icon: attributes.window_icon.clone(),
 			taskbar_icon,
@@ -143,7 +158,8 @@ impl WindowState {
 			preferred_theme,
 			high_surrogate: None,
 			ime_handler: MinimalIme::default(),
-			window_flags: WindowFlags::empty()
+			drag_regions: Vec::new(),
+			window_flags: INITIAL_WINDOW_FLAGS
 		}
 	}
 
@@ -209,11 +225,17 @@ impl WindowFlags {
 
 	pub fn to_window_styles(self) -> (WINDOW_STYLE, WINDOW_EX_STYLE) {
 		let (mut style, mut style_ex) = (Default::default(), Default::default());
-		style |= WS_CLIPSIBLINGS | WS_CLIPCHILDREN | WS_SYSMENU | WS_CAPTION | WS_MINIMIZEBOX;
+		style |= WS_CLIPSIBLINGS | WS_CLIPCHILDREN | WS_SYSMENU | WS_CAPTION;
 		style_ex |= WS_EX_ACCEPTFILES;
 
 		if self.contains(WindowFlags::RESIZABLE) {
-			style |= WS_SIZEBOX | WS_MAXIMIZEBOX;
+			style |= WS_SIZEBOX;
+			if self.contains(WindowFlags::MAXIMIZABLE) {
+				style |= WS_MAXIMIZEBOX;
+			}
+		}
+		if self.contains(WindowFlags::MINIMIZABLE) {
+			style |= WS_MINIMIZEBOX;
 		}
 		if self.contains(WindowFlags::DECORATIONS) {
 			if !self.contains(WindowFlags::RESIZABLE) {
@@ -245,6 +267,12 @@ impl WindowFlags {
 		if self.contains(WindowFlags::MAXIMIZED) {
 			style |= WS_MAXIMIZE;
 		}
+		if self.contains(WindowFlags::RIGHT_TO_LEFT) {
+			style_ex |= WS_EX_LAYOUTRTL;
+		}
+		if self.contains(WindowFlags::LAYERED) {
+			style_ex |= WS_EX_LAYERED;
+		}
 		if self.contains(WindowFlags::HIDDEN_TITLEBAR) {
 			style_ex |= WS_EX_WINDOWEDGE;
 			style &= !(WS_CAPTION);
@@ -256,12 +284,10 @@ impl WindowFlags {
 		(style, style_ex)
 	}
 
-	/// Adjust the window client rectangle to the return value, if present.
-	fn apply_diff(mut self, window: HWND, mut new: WindowFlags) {
-		self = self.mask();
-		new = new.mask();
-
-		let mut diff = self ^ new;
+	/// The flags that must be applied to the window to go from `self` to `new`.
+	fn diff(self, new: WindowFlags) -> WindowFlags {
+		let new = new.mask();
+		let mut diff = self.mask() ^ new;
 
 		// when hiding a maximized window, `self` contains `WindowFlags::MAXIMIZED`
 		// but `new` won't have it as it is removed in `new.mask()` call by applying `WindowFlags::INVISIBLE_AND_MASK`
@@ -271,6 +297,14 @@ impl WindowFlags {
 			diff &= WindowFlags::INVISIBLE_AND_MASK;
 		}
 
+		diff
+	}
+
+	/// Adjust the window client rectangle to the return value, if present.
+	fn apply_diff(self, window: HWND, mut new: WindowFlags) {
+		let diff = self.diff(new);
+		new = new.mask();
+
 		if diff == WindowFlags::empty() {
 			return;
 		}
@@ -316,6 +350,21 @@ impl WindowFlags {
 			}
 		}
 
+		// There is no style for the close button; it follows the close item of the window menu, which also
+		// disables Alt+F4
+		if diff.contains(WindowFlags::CLOSABLE) {
+			unsafe {
+				EnableMenuItem(
+					GetSystemMenu(window, false),
+					SC_CLOSE,
+					match new.contains(WindowFlags::CLOSABLE) {
+						true => MF_BYCOMMAND | MF_ENABLED,
+						false => MF_BYCOMMAND | MF_DISABLED | MF_GRAYED
+					}
+				);
+			}
+		}
+
 		// Minimize operations should execute after maximize for proper window
 		// animations
 		if diff.contains(WindowFlags::MINIMIZED) {
@@ -397,3 +446,17 @@ impl CursorFlags {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{WindowFlags, INITIAL_WINDOW_FLAGS};
+
+	#[test]
+	fn initial_diff_applies_disabled_close_button() {
+		let diff = INITIAL_WINDOW_FLAGS.diff(WindowFlags::VISIBLE);
+		assert!(diff.contains(WindowFlags::CLOSABLE));
+
+		let diff = INITIAL_WINDOW_FLAGS.diff(WindowFlags::VISIBLE | WindowFlags::CLOSABLE);
+		assert!(!diff.contains(WindowFlags::CLOSABLE));
+	}
+}
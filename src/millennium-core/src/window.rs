@@ -18,7 +18,7 @@
 #![allow(clippy::tabs_in_doc_comments)]
 
 //! The `Window` struct and associated types.
-use std::fmt;
+use std::{fmt, path::PathBuf};
 
 pub use crate::icon::{BadIcon, Icon};
 use crate::{
@@ -169,6 +169,21 @@ pub struct WindowAttributes {
 	/// The default is `true`.
 	pub resizable: bool,
 
+	/// Whether the window's minimize button is enabled.
+	///
+	/// The default is `true`.
+	pub minimizable: bool,
+
+	/// Whether the window's maximize button is enabled.
+	///
+	/// The default is `true`.
+	pub maximizable: bool,
+
+	/// Whether the window's close button is enabled.
+	///
+	/// The default is `true`.
+	pub closable: bool,
+
 	/// Whether the window should be set as fullscreen upon creation.
 	///
 	/// The default is `None`.
@@ -214,7 +229,33 @@ pub struct WindowAttributes {
 	/// The window menu.
 	///
 	/// The default is `None`.
-	pub window_menu: Option<platform_impl::Menu>
+	pub window_menu: Option<platform_impl::Menu>,
+
+	/// The layout direction of the native window chrome, i.e. the menu bar and
+	/// window controls. If this is `None`, the direction of the system locale
+	/// will be used.
+	///
+	/// The default is `None`.
+	///
+	/// ## Platform-specific
+	///
+	/// - **iOS / Android:** Unsupported.
+	pub layout_direction: Option<LayoutDirection>,
+
+	/// The background color of the window's title bar as RGBA, regardless of
+	/// the system theme. If this is `None`, the title bar follows the theme.
+	///
+	/// The default is `None`.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS:** The title bar is made transparent and the color is used as
+	///   the window's background color.
+	/// - **Windows:** Requires Windows 11. The alpha channel is ignored.
+	/// - **Linux:** The window uses a client-side header bar drawn with the
+	///   color.
+	/// - **iOS / Android:** Unsupported.
+	pub title_bar_color: Option<(u8, u8, u8, u8)>
 }
 
 impl Default for WindowAttributes {
@@ -226,6 +267,9 @@ impl Default for WindowAttributes {
 			max_inner_size: None,
 			position: None,
 			resizable: true,
+			minimizable: true,
+			maximizable: true,
+			closable: true,
 			title: "Millennium Core Window".to_owned(),
 			maximized: false,
 			fullscreen: None,
@@ -234,7 +278,9 @@ impl Default for WindowAttributes {
 			decorations: true,
 			always_on_top: false,
 			window_icon: None,
-			window_menu: None
+			window_menu: None,
+			layout_direction: None,
+			title_bar_color: None
 		}
 	}
 }
@@ -301,6 +347,39 @@ impl WindowBuilder {
 		self
 	}
 
+	/// Sets whether the window's minimize button is enabled.
+	///
+	/// See [`Window::set_minimizable`] for details.
+	///
+	/// [`Window::set_minimizable`]: crate::window::Window::set_minimizable
+	#[inline]
+	pub fn with_minimizable(mut self, minimizable: bool) -> Self {
+		self.window.minimizable = minimizable;
+		self
+	}
+
+	/// Sets whether the window's maximize button is enabled.
+	///
+	/// See [`Window::set_maximizable`] for details.
+	///
+	/// [`Window::set_maximizable`]: crate::window::Window::set_maximizable
+	#[inline]
+	pub fn with_maximizable(mut self, maximizable: bool) -> Self {
+		self.window.maximizable = maximizable;
+		self
+	}
+
+	/// Sets whether the window's close button is enabled.
+	///
+	/// See [`Window::set_closable`] for details.
+	///
+	/// [`Window::set_closable`]: crate::window::Window::set_closable
+	#[inline]
+	pub fn with_closable(mut self, closable: bool) -> Self {
+		self.window.closable = closable;
+		self
+	}
+
 	/// Requests a specific title for the window.
 	///
 	/// See [`Window::set_title`] for details.
@@ -396,6 +475,28 @@ impl WindowBuilder {
 		self
 	}
 
+	/// Sets the layout direction of the window's menu bar and controls.
+	///
+	/// See [`WindowAttributes::layout_direction`] for details.
+	///
+	/// [`WindowAttributes::layout_direction`]: crate::window::WindowAttributes::layout_direction
+	#[inline]
+	pub fn with_layout_direction(mut self, layout_direction: Option<LayoutDirection>) -> Self {
+		self.window.layout_direction = layout_direction;
+		self
+	}
+
+	/// Sets the background color of the window's title bar.
+	///
+	/// See [`WindowAttributes::title_bar_color`] for details.
+	///
+	/// [`WindowAttributes::title_bar_color`]: crate::window::WindowAttributes::title_bar_color
+	#[inline]
+	pub fn with_title_bar_color(mut self, color: Option<(u8, u8, u8, u8)>) -> Self {
+		self.window.title_bar_color = color;
+		self
+	}
+
 	/// Builds the window.
 	///
 	/// Possible causes of error include denied permission, incompatible system,
@@ -595,6 +696,19 @@ impl Window {
 	pub fn set_max_inner_size<S: Into<Size>>(&self, max_size: Option<S>) {
 		self.window.set_max_inner_size(max_size.map(|s| s.into()))
 	}
+
+	/// Locks the ratio of the inner width to the inner height while the user
+	/// resizes the window. Pass `None` to allow free resizing again.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Replaces any min/max size constraints previously set with
+	///   [`Window::set_min_inner_size`]/[`Window::set_max_inner_size`].
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn set_aspect_ratio(&self, aspect_ratio: Option<f64>) {
+		self.window.set_aspect_ratio(aspect_ratio)
+	}
 }
 
 /// Misc. attribute functions.
@@ -666,6 +780,47 @@ impl Window {
 		self.window.set_resizable(resizable)
 	}
 
+	/// Sets whether the window's minimize button is enabled.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Hides the button if the maximize button is disabled too.
+	/// - **Linux:** Sets the window manager functions hint, which the window
+	///   manager may ignore.
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn set_minimizable(&self, minimizable: bool) {
+		self.window.set_minimizable(minimizable)
+	}
+
+	/// Sets whether the window's maximize button is enabled.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** The button is always disabled while the window isn't
+	///   resizable, and is hidden if the minimize button is disabled too.
+	/// - **Linux:** Sets the window manager functions hint, which the window
+	///   manager may ignore.
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn set_maximizable(&self, maximizable: bool) {
+		self.window.set_maximizable(maximizable)
+	}
+
+	/// Sets whether the window's close button is enabled.
+	///
+	/// This only disables the button, closing the window from code still
+	/// works.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Also disables the close item of the window menu and Alt+F4.
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn set_closable(&self, closable: bool) {
+		self.window.set_closable(closable)
+	}
+
 	/// Sets the window to minimized or back
 	///
 	/// ## Platform-specific
@@ -696,6 +851,18 @@ impl Window {
 		self.window.is_maximized()
 	}
 
+	/// Gets the window's current minimized state.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Reflects the state reported by the window manager, so it only
+	///   changes once the window manager has minimized or restored the window.
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn is_minimized(&self) -> bool {
+		self.window.is_minimized()
+	}
+
 	/// Gets the window's current vibility state.
 	///
 	/// ## Platform-specific
@@ -716,6 +883,36 @@ impl Window {
 		self.window.is_resizable()
 	}
 
+	/// Gets whether the window's minimize button is enabled.
+	///
+	/// ## Platform-specific
+	///
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn is_minimizable(&self) -> bool {
+		self.window.is_minimizable()
+	}
+
+	/// Gets whether the window's maximize button is enabled.
+	///
+	/// ## Platform-specific
+	///
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn is_maximizable(&self) -> bool {
+		self.window.is_maximizable()
+	}
+
+	/// Gets whether the window's close button is enabled.
+	///
+	/// ## Platform-specific
+	///
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn is_closable(&self) -> bool {
+		self.window.is_closable()
+	}
+
 	/// Gets the window's current decoration state.
 	///
 	/// ## Platform-specific
@@ -725,6 +922,17 @@ impl Window {
 		self.window.is_decorated()
 	}
 
+	/// Gets whether the window is shown on every virtual desktop.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Reflects the state reported by the window manager, so it only
+	///   changes once the window manager has applied the hint.
+	/// - **Windows / iOS / Android:** Unsupported, always returns `false`.
+	pub fn is_visible_on_all_workspaces(&self) -> bool {
+		self.window.is_visible_on_all_workspaces()
+	}
+
 	/// Sets the window to fullscreen or back.
 	///
 	/// ## Platform-specific
@@ -781,6 +989,36 @@ impl Window {
 		self.window.set_always_on_top(always_on_top)
 	}
 
+	/// Change whether or not the window is shown on every virtual desktop
+	/// (workspace, or space on macOS) instead of only the one it was opened on.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Sets the `_NET_WM_STATE_STICKY` hint, which the window
+	///   manager may ignore.
+	/// - **Windows / iOS / Android:** Unsupported.
+	#[inline]
+	pub fn set_visible_on_all_workspaces(&self, visible_on_all_workspaces: bool) {
+		self.window.set_visible_on_all_workspaces(visible_on_all_workspaces)
+	}
+
+	/// Sets the opacity of the whole window, including its decorations, from
+	/// `0.0` (fully transparent) to `1.0` (fully opaque). Values outside that
+	/// range are clamped.
+	///
+	/// This is unrelated to [`WindowBuilder::with_transparent`], which only
+	/// makes the window background transparent so its contents can draw with
+	/// per-pixel alpha.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Requires a compositing window manager.
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn set_opacity(&self, opacity: f64) {
+		self.window.set_opacity(opacity.clamp(0.0, 1.0))
+	}
+
 	/// Sets the window icon. On Windows and Linux, this is typically the small
 	/// icon in the top-left corner of the title bar.
 	///
@@ -854,6 +1092,35 @@ impl Window {
 	pub fn is_menu_visible(&self) -> bool {
 		self.window.is_menu_visible()
 	}
+
+	/// Returns the accent color the user picked for the system, as RGBA.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** The DWM colorization color.
+	/// - **macOS:** `NSColor.controlAccentColor`. Requires macOS 10.14+.
+	/// - **Linux:** The `accent-color` setting of the XDG desktop portal, falling back
+	///   to the selection color of the GTK theme.
+	/// - **iOS / Android:** Unsupported, always returns `None`.
+	pub fn accent_color(&self) -> Option<(u8, u8, u8, u8)> {
+		self.window.accent_color()
+	}
+
+	/// Returns the display preferences set in the system's accessibility
+	/// settings.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** `reduce_motion` follows "Show animations in Windows", and
+	///   `reduce_transparency` follows "Transparency effects".
+	/// - **macOS:** `reduce_motion` requires macOS 10.12+.
+	/// - **Linux:** Reads the `reduced-motion` and `contrast` settings of the XDG
+	///   desktop portal, falling back to the GTK settings. `reduce_transparency`
+	///   is always `false`.
+	/// - **iOS / Android:** Unsupported, always returns the default preferences.
+	pub fn accessibility_preferences(&self) -> AccessibilityPreferences {
+		self.window.accessibility_preferences()
+	}
 }
 
 /// Cursor functions.
@@ -922,6 +1189,94 @@ impl Window {
 	pub fn drag_window(&self) -> Result<(), ExternalError> {
 		self.window.drag_window()
 	}
+
+	/// Starts a drag and drop operation that moves the files at `paths` out of
+	/// the window, so they can be dropped into other applications such as the
+	/// file manager. `icon` is shown under the cursor during the drag.
+	///
+	/// Like [`Window::drag_window`], this should be called right after the left
+	/// mouse button was pressed.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Blocks until the drag ends. `icon` is unsupported; the shell
+	///   provides the drag image.
+	/// - **iOS / Android:** Always returns an [`ExternalError::NotSupported`].
+	#[inline]
+	pub fn start_drag(&self, paths: Vec<PathBuf>, icon: Option<Icon>) -> Result<(), ExternalError> {
+		self.window.start_drag(paths, icon)
+	}
+
+	/// Resizes the window with the left mouse button until the button is
+	/// released, as if the user had grabbed the border or corner in
+	/// `direction`. Useful for custom resize handles on undecorated windows.
+	///
+	/// There's no guarantee that this will work unless the left mouse button
+	/// was pressed immediately before this function is called.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS:** Blocks until the button is released.
+	/// - **iOS / Android:** Always returns an [`ExternalError::NotSupported`].
+	#[inline]
+	pub fn start_resize_drag(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
+		self.window.start_resize_drag(direction)
+	}
+
+	/// Performs the action the system runs when the user double-clicks a
+	/// native title bar, so custom title bars built around
+	/// [`Window::drag_window`] behave the same way.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS:** Zooms, minimizes or does nothing depending on the "Double-click a
+	///   window's title bar to" preference.
+	/// - **Windows:** Maximizes or restores the window if it is resizable.
+	/// - **Linux:** Follows the `gtk-titlebar-double-click` setting. Defaults to
+	///   maximizing or restoring the window if it is resizable.
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn titlebar_double_click(&self) {
+		self.window.titlebar_double_click()
+	}
+
+	/// Sets the regions of the window that move it when dragged, e.g. for a
+	/// custom title bar: pressing the left mouse button inside one of them
+	/// starts moving the window like [`Window::drag_window`], while the rest of
+	/// the window keeps receiving mouse input. Replaces the regions set by a
+	/// previous call; pass an empty list to remove them.
+	///
+	/// The regions only move the window; they don't get the other title bar
+	/// behaviors, e.g. double-clicking one doesn't maximize the window (see
+	/// [`Window::titlebar_double_click`]).
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** The regions are hit-tested as the caption, but only
+	///   where nothing covers the client area. Webviews do, so Millennium
+	///   Webview checks the left mouse button presses on the webview with
+	///   [`Window::is_drag_region`] and calls [`Window::drag_window`] itself.
+	/// - **Linux:** Left mouse button presses inside the regions call
+	///   `gtk_window_begin_move_drag`, on the window and on the webview.
+	/// - **macOS:** Left mouse button presses inside the regions call
+	///   `performWindowDragWithEvent:`.
+	/// - **Windows / Linux:** Resize borders of undecorated windows take
+	///   precedence over the regions.
+	/// - **iOS / Android:** Unsupported.
+	#[inline]
+	pub fn set_drag_regions(&self, drag_regions: Vec<Rect>) {
+		self.window.set_drag_regions(drag_regions)
+	}
+
+	/// Returns whether `position`, relative to the top-left corner of the
+	/// client area, is inside one of the regions set with
+	/// [`Window::set_drag_regions`].
+	#[inline]
+	pub fn is_drag_region<P: Into<Position>>(&self, position: P) -> bool {
+		let scale_factor = self.scale_factor();
+		let position = position.into().to_physical(scale_factor);
+		self.window.drag_regions().iter().any(|r| r.contains(position, scale_factor))
+	}
 }
 
 /// Monitor info functions.
@@ -1072,6 +1427,29 @@ impl Default for Theme {
 	}
 }
 
+/// The display preferences the user set in the system's accessibility
+/// settings.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AccessibilityPreferences {
+	/// Animations and motion effects should be minimized.
+	pub reduce_motion: bool,
+	/// Content should be drawn with higher contrast, e.g. a high contrast theme
+	/// is active.
+	pub increase_contrast: bool,
+	/// Translucent effects such as blur and vibrancy should be replaced with
+	/// opaque backgrounds.
+	pub reduce_transparency: bool
+}
+
+/// The direction in which the native window chrome is laid out.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutDirection {
+	LeftToRight,
+	RightToLeft
+}
+
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UserAttentionType {
@@ -1091,6 +1469,45 @@ impl Default for UserAttentionType {
 	}
 }
 
+/// The border or corner of a window to resize from in
+/// [`Window::start_resize_drag`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResizeDirection {
+	East,
+	North,
+	NorthEast,
+	NorthWest,
+	South,
+	SouthEast,
+	SouthWest,
+	West
+}
+
+/// A rectangle relative to the top-left corner of the window's client area,
+/// used to describe the drag regions of [`Window::set_drag_regions`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+	pub position: Position,
+	pub size: Size
+}
+
+impl Rect {
+	pub fn new<P: Into<Position>, S: Into<Size>>(position: P, size: S) -> Self {
+		Self {
+			position: position.into(),
+			size: size.into()
+		}
+	}
+
+	/// Returns whether the rectangle contains `position`.
+	pub fn contains(&self, position: PhysicalPosition<f64>, scale_factor: f64) -> bool {
+		let origin: PhysicalPosition<f64> = self.position.to_physical(scale_factor);
+		let size: PhysicalSize<f64> = self.size.to_physical(scale_factor);
+		position.x >= origin.x && position.y >= origin.y && position.x < origin.x + size.width && position.y < origin.y + size.height
+	}
+}
+
 /// A constant used to determine how much inside the window, the resize handler
 /// should appear (only used in Linux(gtk) and Windows).
 pub const BORDERLESS_RESIZE_INSET: i32 = 5;
@@ -31,12 +31,20 @@
 //!
 //! let window = WindowBuilder::new().with_decorations(false).build(&event_loop).unwrap();
 //!
-//! #[cfg(target_os = "windows")]
-//! apply_blur(&window, Some((18, 18, 18, 125))).unwrap();
+//! // skip the effects if the user asked the system to reduce transparency
+//! if !window.accessibility_preferences().reduce_transparency {
+//!     #[cfg(target_os = "windows")]
+//!     apply_blur(&window, Some((18, 18, 18, 125))).unwrap();
 //!
-//! #[cfg(target_os = "macos")]
-//! apply_vibrancy(&window, NSVisualEffectMaterial::AppearanceBased).unwrap();
+//!     #[cfg(target_os = "macos")]
+//!     apply_vibrancy(&window, NSVisualEffectMaterial::AppearanceBased).unwrap();
+//! }
 //! ```
+//!
+//! Effects are never skipped automatically. Check `reduce_transparency` before applying them, and clear them on
+//! `RunEvent::AccessibilityPreferencesChanged` (or `Event::AccessibilityPreferencesChanged` in Millennium Core).
+
+use std::fmt;
 
 use thiserror::Error;
 
@@ -60,7 +68,10 @@ pub fn apply_blur(window: impl raw_window_handle::HasRawWindowHandle, #[allow(un
 	match window.raw_window_handle() {
 		#[cfg(target_os = "windows")]
 		raw_window_handle::RawWindowHandle::Win32(handle) => windows::apply_blur(handle.hwnd as _, color),
-		_ => Err(Error::UnsupportedPlatform("\"apply_blur()\" is only supported on Windows."))
+		_ => Err(Error::UnsupportedPlatform {
+			effect: Effect::Blur,
+			supported_platforms: "Windows"
+		})
 	}
 }
 
@@ -70,7 +81,10 @@ pub fn clear_blur(window: impl raw_window_handle::HasRawWindowHandle) -> Result<
 	match window.raw_window_handle() {
 		#[cfg(target_os = "windows")]
 		raw_window_handle::RawWindowHandle::Win32(handle) => windows::clear_blur(handle.hwnd as _),
-		_ => Err(Error::UnsupportedPlatform("\"clear_blur()\" is only supported on Windows."))
+		_ => Err(Error::UnsupportedPlatform {
+			effect: Effect::Blur,
+			supported_platforms: "Windows"
+		})
 	}
 }
 
@@ -85,7 +99,10 @@ pub fn apply_acrylic(window: impl raw_window_handle::HasRawWindowHandle, #[allow
 	match window.raw_window_handle() {
 		#[cfg(target_os = "windows")]
 		raw_window_handle::RawWindowHandle::Win32(handle) => windows::apply_acrylic(handle.hwnd as _, color),
-		_ => Err(Error::UnsupportedPlatform("\"apply_acrylic()\" is only supported on Windows."))
+		_ => Err(Error::UnsupportedPlatform {
+			effect: Effect::Acrylic,
+			supported_platforms: "Windows"
+		})
 	}
 }
 
@@ -95,7 +112,10 @@ pub fn clear_acrylic(window: impl raw_window_handle::HasRawWindowHandle) -> Resu
 	match window.raw_window_handle() {
 		#[cfg(target_os = "windows")]
 		raw_window_handle::RawWindowHandle::Win32(handle) => windows::clear_acrylic(handle.hwnd as _),
-		_ => Err(Error::UnsupportedPlatform("\"clear_acrylic()\" is only supported on Windows."))
+		_ => Err(Error::UnsupportedPlatform {
+			effect: Effect::Acrylic,
+			supported_platforms: "Windows"
+		})
 	}
 }
 
@@ -107,7 +127,10 @@ pub fn apply_mica(window: impl raw_window_handle::HasRawWindowHandle) -> Result<
 	match window.raw_window_handle() {
 		#[cfg(target_os = "windows")]
 		raw_window_handle::RawWindowHandle::Win32(handle) => windows::apply_mica(handle.hwnd as _),
-		_ => Err(Error::UnsupportedPlatform("\"apply_mica()\" is only supported on Windows."))
+		_ => Err(Error::UnsupportedPlatform {
+			effect: Effect::Mica,
+			supported_platforms: "Windows"
+		})
 	}
 }
 
@@ -117,26 +140,128 @@ pub fn clear_mica(window: impl raw_window_handle::HasRawWindowHandle) -> Result<
 	match window.raw_window_handle() {
 		#[cfg(target_os = "windows")]
 		raw_window_handle::RawWindowHandle::Win32(handle) => windows::clear_mica(handle.hwnd as _),
-		_ => Err(Error::UnsupportedPlatform("\"clear_mica()\" is only supported on Windows."))
+		_ => Err(Error::UnsupportedPlatform {
+			effect: Effect::Mica,
+			supported_platforms: "Windows"
+		})
 	}
 }
 
 /// Applies macOS Vibrancy effect to the window. Works only on macOS 10.10 or
 /// newer.
+///
+/// - Returns [`Error::UnsupportedPlatformVersion`] if the `effect` material isn't available on the running version of
+///   macOS, e.g. [`NSVisualEffectMaterial::HudWindow`] before macOS 10.14.
 pub fn apply_vibrancy(window: impl raw_window_handle::HasRawWindowHandle, #[allow(unused)] effect: NSVisualEffectMaterial) -> Result<(), Error> {
 	match window.raw_window_handle() {
 		#[cfg(target_os = "macos")]
 		raw_window_handle::RawWindowHandle::AppKit(handle) => macos::apply_vibrancy(handle.ns_window as _, effect),
-		_ => Err(Error::UnsupportedPlatform("\"apply_vibrancy()\" is only supported on macOS."))
+		_ => Err(Error::UnsupportedPlatform {
+			effect: Effect::Vibrancy,
+			supported_platforms: "macOS"
+		})
+	}
+}
+
+/// Applies the effect to the window and keeps it applied while the window is alive. Windows drops Mica and Acrylic
+/// when the system theme changes, when desktop composition restarts, or when the window moves to a monitor with a
+/// different DPI; the effect is reapplied automatically after each of these. Works only on Windows.
+///
+/// - *`color`* is passed on to [`apply_blur`] and [`apply_acrylic`], and ignored for Mica.
+/// - Managing another effect replaces the previous one. The window stops being managed when it's destroyed or when
+///   [`unmanage_effect`] is called.
+/// - Must be called on the thread that created the window, i.e. the main thread.
+pub fn manage_effect(window: impl raw_window_handle::HasRawWindowHandle, effect: Effect, #[allow(unused)] color: Option<Color>) -> Result<(), Error> {
+	match window.raw_window_handle() {
+		#[cfg(target_os = "windows")]
+		raw_window_handle::RawWindowHandle::Win32(handle) => windows::manage_effect(handle.hwnd as _, effect, color),
+		_ => Err(Error::UnsupportedPlatform {
+			effect,
+			supported_platforms: "Windows"
+		})
+	}
+}
+
+/// Stops reapplying the effect managed with [`manage_effect`] and removes the window hook. The effect itself stays
+/// applied until it's cleared, e.g. with [`clear_mica`]. Does nothing if the window isn't managed.
+///
+/// Must be called on the thread that created the window, i.e. the main thread.
+pub fn unmanage_effect(window: impl raw_window_handle::HasRawWindowHandle) {
+	#[cfg(target_os = "windows")]
+	if let raw_window_handle::RawWindowHandle::Win32(handle) = window.raw_window_handle() {
+		windows::unmanage_effect(handle.hwnd as _);
+	}
+	#[cfg(not(target_os = "windows"))]
+	let _ = window;
+}
+
+/// A window effect provided by this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Effect {
+	/// The blur effect of [`apply_blur`].
+	Blur,
+	/// The Acrylic effect of [`apply_acrylic`].
+	Acrylic,
+	/// The Mica effect of [`apply_mica`].
+	Mica,
+	/// The macOS vibrancy effect of [`apply_vibrancy`].
+	Vibrancy
+}
+
+impl fmt::Display for Effect {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Blur => "blur",
+			Self::Acrylic => "acrylic",
+			Self::Mica => "mica",
+			Self::Vibrancy => "vibrancy"
+		})
 	}
 }
 
-#[derive(Debug, Error)]
+/// A version of the operating system, e.g. `10.0.22000` for the first release of Windows 11. On macOS, `build` is the
+/// patch version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OsVersion {
+	pub major: u32,
+	pub minor: u32,
+	pub build: u32
+}
+
+impl fmt::Display for OsVersion {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}.{}.{}", self.major, self.minor, self.build)
+	}
+}
+
+/// An error applying or clearing a window effect.
+///
+/// Match on it to fall back to another effect, e.g. from [`Effect::Mica`] to [`Effect::Acrylic`] when the system is
+/// too old for Mica.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
 pub enum Error {
-	#[error("Unsupported platform: {0}")]
-	UnsupportedPlatform(&'static str),
-	#[error("Unsupported platform version: {0}")]
-	UnsupportedPlatformVersion(&'static str),
-	#[error("{0}")]
-	NotMainThread(&'static str)
+	/// The effect isn't available on this platform at all.
+	#[error("Unsupported platform: {effect} is only supported on {supported_platforms}.")]
+	UnsupportedPlatform {
+		effect: Effect,
+		supported_platforms: &'static str
+	},
+	/// The effect, or the vibrancy `material` that was requested, needs a newer version of the operating system.
+	/// `os_version` is the version the app is running on, if it could be detected.
+	#[error(
+		"Unsupported platform version: {effect}{} is only available on {required_version}{}",
+		.material.map(|material| format!(" material {:?}", material)).unwrap_or_default(),
+		.os_version.map(|version| format!(" (running {})", version)).unwrap_or_default()
+	)]
+	UnsupportedPlatformVersion {
+		effect: Effect,
+		material: Option<NSVisualEffectMaterial>,
+		required_version: &'static str,
+		os_version: Option<OsVersion>
+	},
+	/// The effect has to be applied on the main thread.
+	#[error("{effect} must be applied on the main thread.")]
+	NotMainThread { effect: Effect }
 }
@@ -67,30 +67,46 @@ mod internal {
 			NSViewWidthSizable, NSWindow, NSWindowOrderingMode
 		},
 		base::{id, nil, BOOL},
-		foundation::{NSAutoreleasePool, NSPoint, NSRect, NSSize}
+		foundation::{NSAutoreleasePool, NSInteger, NSPoint, NSRect, NSSize}
 	};
 	use objc::{class, msg_send, sel, sel_impl};
 
 	use super::NSVisualEffectMaterial;
-	use crate::Error;
+	use crate::{Effect, Error, OsVersion};
 
 	#[allow(deprecated)]
 	pub fn apply_vibrancy(window: id, appearance: NSVisualEffectMaterial) -> Result<(), Error> {
 		unsafe {
 			if NSAppKitVersionNumber < NSAppKitVersionNumber10_10 {
 				eprintln!("\"NSVisualEffectView\" is only available on macOS 10.10 or newer.");
-				return Err(Error::UnsupportedPlatformVersion("\"apply_vibrancy\" is only available on macOS 10.10 or newer."));
+				// the version can only be queried since 10.10 itself
+				return Err(Error::UnsupportedPlatformVersion {
+					effect: Effect::Vibrancy,
+					material: None,
+					required_version: "macOS 10.10 or newer",
+					os_version: None
+				});
 			}
 
 			if !msg_send![class!(NSThread), isMainThread] {
-				return Err(Error::NotMainThread("\"apply_vibrancy\" must be called on the main thread."));
+				return Err(Error::NotMainThread { effect: Effect::Vibrancy });
 			}
 
-			let mut m = appearance;
-			if appearance as u32 > 9 && NSAppKitVersionNumber < NSAppKitVersionNumber10_14 {
-				m = NSVisualEffectMaterial::AppearanceBased;
+			// older versions of AppKit don't know the material, and would give the view an undefined look
+			let required_version = if appearance as u32 > 9 && NSAppKitVersionNumber < NSAppKitVersionNumber10_14 {
+				Some("macOS 10.14 or newer")
 			} else if appearance as u32 > 4 && NSAppKitVersionNumber < NSAppKitVersionNumber10_11 {
-				m = NSVisualEffectMaterial::AppearanceBased;
+				Some("macOS 10.11 or newer")
+			} else {
+				None
+			};
+			if let Some(required_version) = required_version {
+				return Err(Error::UnsupportedPlatformVersion {
+					effect: Effect::Vibrancy,
+					material: Some(appearance),
+					required_version,
+					os_version: Some(os_version())
+				});
 			}
 
 			let ns_view: id = window.contentView();
@@ -99,7 +115,7 @@ mod internal {
 			let blurred_view = NSVisualEffectView::initWithFrame_(NSVisualEffectView::alloc(nil), bounds);
 			blurred_view.autorelease();
 
-			blurred_view.setMaterial_(m);
+			blurred_view.setMaterial_(appearance);
 			blurred_view.setBlendingMode_(NSVisualEffectBlendingMode::BehindWindow);
 			blurred_view.setState_(NSVisualEffectState::FollowsWindowActiveState);
 			NSVisualEffectView::setAutoresizingMask_(blurred_view, NSViewWidthSizable | NSViewHeightSizable);
@@ -112,6 +128,24 @@ mod internal {
 	#[allow(non_upper_case_globals)]
 	const NSAppKitVersionNumber10_14: f64 = 1671.0;
 
+	#[repr(C)]
+	struct NSOperatingSystemVersion {
+		major: NSInteger,
+		minor: NSInteger,
+		patch: NSInteger
+	}
+
+	// -[NSProcessInfo operatingSystemVersion] is available since macOS 10.10, which vibrancy requires anyway
+	unsafe fn os_version() -> OsVersion {
+		let process_info: id = msg_send![class!(NSProcessInfo), processInfo];
+		let version: NSOperatingSystemVersion = msg_send![process_info, operatingSystemVersion];
+		OsVersion {
+			major: version.major as u32,
+			minor: version.minor as u32,
+			build: version.patch as u32
+		}
+	}
+
 	// https://developer.apple.com/documentation/appkit/nsvisualeffectview/blendingmode
 	#[allow(dead_code)]
 	#[repr(u64)]
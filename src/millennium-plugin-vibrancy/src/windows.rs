@@ -28,7 +28,12 @@ pub use windows_sys::Win32::{
 	System::{LibraryLoader::*, SystemInformation::*}
 };
 
-use crate::{Color, Error};
+use windows_sys::Win32::UI::{
+	Shell::{DefSubclassProc, GetWindowSubclass, RemoveWindowSubclass, SetWindowSubclass},
+	WindowsAndMessaging::{WM_DPICHANGED, WM_DWMCOMPOSITIONCHANGED, WM_NCDESTROY, WM_SETTINGCHANGE, WM_THEMECHANGED}
+};
+
+use crate::{Color, Effect, Error, OsVersion};
 
 pub fn apply_blur(hwnd: HWND, color: Option<Color>) -> Result<(), Error> {
 	if is_win7() {
@@ -46,7 +51,7 @@ pub fn apply_blur(hwnd: HWND, color: Option<Color>) -> Result<(), Error> {
 			SetWindowCompositionAttribute(hwnd, ACCENT_STATE::ACCENT_ENABLE_BLURBEHIND, color);
 		}
 	} else {
-		return Err(Error::UnsupportedPlatformVersion("\"apply_blur()\" is only available on Windows 7, Windows 10 v1809+, or Windows 11"));
+		return Err(unsupported_version(Effect::Blur, "Windows 7, Windows 10 v1809+, or Windows 11"));
 	}
 	Ok(())
 }
@@ -67,7 +72,7 @@ pub fn clear_blur(hwnd: HWND) -> Result<(), Error> {
 			SetWindowCompositionAttribute(hwnd, ACCENT_STATE::ACCENT_DISABLED, None);
 		}
 	} else {
-		return Err(Error::UnsupportedPlatformVersion("\"clear_blur()\" is only available on Windows 7, Windows 10 v1809+, or Windows 11"));
+		return Err(unsupported_version(Effect::Blur, "Windows 7, Windows 10 v1809+, or Windows 11"));
 	}
 	Ok(())
 }
@@ -82,7 +87,7 @@ pub fn apply_acrylic(hwnd: HWND, color: Option<Color>) -> Result<(), Error> {
 			SetWindowCompositionAttribute(hwnd, ACCENT_STATE::ACCENT_ENABLE_ACRYLICBLURBEHIND, color);
 		}
 	} else {
-		return Err(Error::UnsupportedPlatformVersion("\"apply_acrylic()\" is only available on Windows 10 v1809+ or Windows 11"));
+		return Err(unsupported_version(Effect::Acrylic, "Windows 10 v1809+ or Windows 11"));
 	}
 	Ok(())
 }
@@ -97,7 +102,7 @@ pub fn clear_acrylic(hwnd: HWND) -> Result<(), Error> {
 			SetWindowCompositionAttribute(hwnd, ACCENT_STATE::ACCENT_DISABLED, None);
 		}
 	} else {
-		return Err(Error::UnsupportedPlatformVersion("\"clear_acrylic()\" is only available on Windows 10 v1809+ or Windows 11"));
+		return Err(unsupported_version(Effect::Acrylic, "Windows 10 v1809+ or Windows 11"));
 	}
 	Ok(())
 }
@@ -112,7 +117,7 @@ pub fn apply_mica(hwnd: HWND) -> Result<(), Error> {
 			DwmSetWindowAttribute(hwnd, DWMWA_MICA_EFFECT, &1 as *const _ as _, 4);
 		}
 	} else {
-		return Err(Error::UnsupportedPlatformVersion("\"apply_mica()\" is only available on Windows 11"));
+		return Err(unsupported_version(Effect::Mica, "Windows 11"));
 	}
 	Ok(())
 }
@@ -127,11 +132,83 @@ pub fn clear_mica(hwnd: HWND) -> Result<(), Error> {
 			DwmSetWindowAttribute(hwnd, DWMWA_MICA_EFFECT, &0 as *const _ as _, 4);
 		}
 	} else {
-		return Err(Error::UnsupportedPlatformVersion("\"clear_mica()\" is only available on Windows 11"));
+		return Err(unsupported_version(Effect::Mica, "Windows 11"));
 	}
 	Ok(())
 }
 
+// identifies our subclass among the others of the window
+const MANAGED_EFFECT_SUBCLASS_ID: usize = 0x7662;
+
+struct ManagedEffect {
+	effect: Effect,
+	color: Option<Color>
+}
+
+pub fn manage_effect(hwnd: HWND, effect: Effect, color: Option<Color>) -> Result<(), Error> {
+	unmanage_effect(hwnd);
+	let managed = Box::into_raw(Box::new(ManagedEffect { effect, color }));
+	// fails if the window belongs to another thread
+	if unsafe { SetWindowSubclass(hwnd, Some(managed_effect_proc), MANAGED_EFFECT_SUBCLASS_ID, managed as usize) } == 0 {
+		drop(unsafe { Box::from_raw(managed) });
+		return Err(Error::NotMainThread { effect });
+	}
+
+	let result = apply_effect(hwnd, effect, color);
+	if result.is_err() {
+		unmanage_effect(hwnd);
+	}
+	result
+}
+
+pub fn unmanage_effect(hwnd: HWND) {
+	unsafe {
+		let mut managed = 0;
+		if GetWindowSubclass(hwnd, Some(managed_effect_proc), MANAGED_EFFECT_SUBCLASS_ID, &mut managed) != 0 {
+			RemoveWindowSubclass(hwnd, Some(managed_effect_proc), MANAGED_EFFECT_SUBCLASS_ID);
+			drop(Box::from_raw(managed as *mut ManagedEffect));
+		}
+	}
+}
+
+fn apply_effect(hwnd: HWND, effect: Effect, color: Option<Color>) -> Result<(), Error> {
+	match effect {
+		Effect::Blur => apply_blur(hwnd, color),
+		Effect::Acrylic => apply_acrylic(hwnd, color),
+		Effect::Mica => apply_mica(hwnd),
+		Effect::Vibrancy => Err(Error::UnsupportedPlatform {
+			effect,
+			supported_platforms: "macOS"
+		})
+	}
+}
+
+unsafe extern "system" fn managed_effect_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM, _: usize, managed: usize) -> LRESULT {
+	match msg {
+		// the theme, composition or DPI changed; let the window handle it before reapplying the effect on top
+		WM_DWMCOMPOSITIONCHANGED | WM_SETTINGCHANGE | WM_THEMECHANGED | WM_DPICHANGED => {
+			let result = DefSubclassProc(hwnd, msg, wparam, lparam);
+			let managed = &*(managed as *const ManagedEffect);
+			let _ = apply_effect(hwnd, managed.effect, managed.color);
+			result
+		}
+		WM_NCDESTROY => {
+			unmanage_effect(hwnd);
+			DefSubclassProc(hwnd, msg, wparam, lparam)
+		}
+		_ => DefSubclassProc(hwnd, msg, wparam, lparam)
+	}
+}
+
+fn unsupported_version(effect: Effect, required_version: &'static str) -> Error {
+	Error::UnsupportedPlatformVersion {
+		effect,
+		material: None,
+		required_version,
+		os_version: get_windows_ver().map(|(major, minor, build)| OsVersion { major, minor, build })
+	}
+}
+
 fn get_function_impl(library: &str, function: &str) -> Option<FARPROC> {
 	assert_eq!(library.chars().last(), Some('\0'));
 	assert_eq!(function.chars().last(), Some('\0'));
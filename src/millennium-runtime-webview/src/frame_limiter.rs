@@ -0,0 +1,133 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+	collections::HashSet,
+	time::{Duration, Instant}
+};
+
+use crate::WebviewId;
+
+/// Caps how often windows are redrawn through [`WindowMessage::RequestRedraw`](crate::WindowMessage::RequestRedraw).
+///
+/// Redraws requested sooner than one frame after the previous one are deferred until the next frame; the event loop
+/// waits with [`ControlFlow::WaitUntil`](millennium_webview::application::event_loop::ControlFlow::WaitUntil) and
+/// then redraws every deferred window at once, so continuously redrawing windows share a single frame clock.
+#[derive(Debug, Default)]
+pub struct FrameLimiter {
+	frame_interval: Option<Duration>,
+	last_frame: Option<Instant>,
+	deferred: HashSet<WebviewId>
+}
+
+impl FrameLimiter {
+	/// Sets the maximum number of frames per second, or removes the cap if `None` or `0` is provided.
+	pub fn set_max_frame_rate(&mut self, max_frame_rate: Option<u32>) {
+		self.frame_interval = max_frame_rate.filter(|fps| *fps > 0).map(|fps| Duration::from_secs(1) / fps);
+	}
+
+	/// Returns whether the window can be redrawn right away. Otherwise, the redraw is deferred until
+	/// [`Self::take_due`] returns it.
+	pub fn request_redraw(&mut self, id: WebviewId) -> bool {
+		self.request_redraw_at(id, Instant::now())
+	}
+
+	/// The time the event loop has to wake up at to redraw the deferred windows, if any.
+	pub fn next_frame(&self) -> Option<Instant> {
+		if self.deferred.is_empty() {
+			return None;
+		}
+		match (self.last_frame, self.frame_interval) {
+			(Some(last_frame), Some(frame_interval)) => Some(last_frame + frame_interval),
+			_ => Some(Instant::now())
+		}
+	}
+
+	/// Takes the deferred windows if their frame is due.
+	pub fn take_due(&mut self) -> Vec<WebviewId> {
+		self.take_due_at(Instant::now())
+	}
+
+	fn request_redraw_at(&mut self, id: WebviewId, now: Instant) -> bool {
+		match (self.last_frame, self.frame_interval) {
+			(Some(last_frame), Some(frame_interval)) if now < last_frame + frame_interval => {
+				self.deferred.insert(id);
+				false
+			}
+			_ => {
+				self.last_frame = Some(now);
+				true
+			}
+		}
+	}
+
+	fn take_due_at(&mut self, now: Instant) -> Vec<WebviewId> {
+		match self.next_frame() {
+			Some(next_frame) if now >= next_frame || self.frame_interval.is_none() => {
+				self.last_frame = Some(now);
+				self.deferred.drain().collect()
+			}
+			_ => Vec::new()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::{Duration, Instant};
+
+	use super::FrameLimiter;
+
+	#[test]
+	fn uncapped_redraws_immediately() {
+		let mut limiter = FrameLimiter::default();
+		let now = Instant::now();
+		assert!(limiter.request_redraw_at(1, now));
+		assert!(limiter.request_redraw_at(1, now));
+		assert_eq!(limiter.next_frame(), None);
+	}
+
+	#[test]
+	fn defers_redraws_within_a_frame() {
+		let mut limiter = FrameLimiter::default();
+		limiter.set_max_frame_rate(Some(50));
+		let start = Instant::now();
+		assert!(limiter.request_redraw_at(1, start));
+		assert!(!limiter.request_redraw_at(1, start + Duration::from_millis(5)));
+		assert!(!limiter.request_redraw_at(2, start + Duration::from_millis(10)));
+		assert_eq!(limiter.next_frame(), Some(start + Duration::from_millis(20)));
+
+		assert!(limiter.take_due_at(start + Duration::from_millis(15)).is_empty());
+		let mut due = limiter.take_due_at(start + Duration::from_millis(20));
+		due.sort_unstable();
+		assert_eq!(due, vec![1, 2]);
+		assert_eq!(limiter.next_frame(), None);
+
+		// the flushed frame starts the next interval
+		assert!(!limiter.request_redraw_at(1, start + Duration::from_millis(30)));
+		assert!(limiter.request_redraw_at(2, start + Duration::from_millis(40)));
+	}
+
+	#[test]
+	fn zero_removes_the_cap() {
+		let mut limiter = FrameLimiter::default();
+		limiter.set_max_frame_rate(Some(60));
+		limiter.set_max_frame_rate(Some(0));
+		let now = Instant::now();
+		assert!(limiter.request_redraw_at(1, now));
+		assert!(limiter.request_redraw_at(1, now));
+	}
+}
@@ -25,14 +25,14 @@ use std::{
 	}
 };
 
-use millennium_runtime::{Error, GlobalShortcutManager, Result, UserEvent};
+use millennium_runtime::{accelerator::Accelerator as RuntimeAccelerator, Error, GlobalShortcutManager, Result, UserEvent};
 pub use millennium_webview::application::global_shortcut::ShortcutManager as MillenniumShortcutManager;
 use millennium_webview::application::{
 	accelerator::{Accelerator, AcceleratorId},
 	global_shortcut::GlobalShortcut
 };
 
-use crate::{getter, Context, Message};
+use crate::{getter, AcceleratorWrapper, Context, Message};
 
 pub type GlobalShortcutListeners = Arc<Mutex<HashMap<AcceleratorId, Box<dyn Fn() + Send>>>>;
 
@@ -74,12 +74,13 @@ impl<T: UserEvent> fmt::Debug for GlobalShortcutManagerHandle<T> {
 
 impl<T: UserEvent> GlobalShortcutManager for GlobalShortcutManagerHandle<T> {
 	fn is_registered(&self, accelerator: &str) -> Result<bool> {
+		let accelerator = AcceleratorWrapper::from(&accelerator.parse::<RuntimeAccelerator>()?).0;
 		let (tx, rx) = channel();
-		getter!(self, rx, Message::GlobalShortcut(GlobalShortcutMessage::IsRegistered(accelerator.parse().expect("invalid accelerator"), tx)))
+		getter!(self, rx, Message::GlobalShortcut(GlobalShortcutMessage::IsRegistered(accelerator, tx)))
 	}
 
 	fn register<F: Fn() + Send + 'static>(&mut self, accelerator: &str, handler: F) -> Result<()> {
-		let wry_accelerator: Accelerator = accelerator.parse().expect("invalid accelerator");
+		let wry_accelerator = AcceleratorWrapper::from(&accelerator.parse::<RuntimeAccelerator>()?).0;
 		let id = wry_accelerator.clone().id();
 		let (tx, rx) = channel();
 		let shortcut = getter!(self, rx, Message::GlobalShortcut(GlobalShortcutMessage::Register(wry_accelerator, tx)))??;
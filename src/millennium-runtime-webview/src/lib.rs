@@ -32,27 +32,34 @@ use std::{
 
 use millennium_runtime::window::MenuEvent;
 use millennium_runtime::{
+	accelerator::Accelerator,
 	http::{Request as HttpRequest, RequestParts as HttpRequestParts, Response as HttpResponse, ResponseParts as HttpResponseParts},
-	menu::{AboutMetadata, CustomMenuItem, Menu, MenuEntry, MenuHash, MenuId, MenuItem, MenuUpdate},
+	menu::{AboutMetadata, CustomMenuItem, Menu, MenuEntry, MenuHash, MenuId, MenuItem, MenuItemKind, MenuUpdate},
 	monitor::Monitor,
-	webview::{WebviewIpcHandler, WindowBuilder, WindowBuilderBase},
+	webview::{
+		CookiePersistence, CssId, DevtoolsPosition, InitScriptId, PdfOrientation, PdfSettings, PermissionKind, PermissionResponse, ScrollBarStyle,
+		WebviewIpcHandler, WindowBuilder, WindowBuilderBase
+	},
 	window::{
 		dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size},
-		CursorIcon, DetachedWindow, FileDropEvent, JsEventListenerKey, PendingWindow, WindowEvent
+		AccessibilityPreferences, CursorIcon, DetachedWindow, FileDropEvent, JsEventListenerKey, PendingWindow, Rect, ResizeDirection, WindowCreatedHandler,
+		WindowEvent
 	},
-	Dispatch, Error, EventLoopProxy, ExitRequestedEventAction, Icon, Result, RunEvent, RunIteration, Runtime, RuntimeHandle, UserAttentionType, UserEvent
+	ActivationPolicy, Dispatch, Error, EventLoopProxy, ExitRequestedEventAction, Icon, JumpListItem, Result, RunEvent, RunIteration, Runtime,
+	RuntimeCapabilities, RuntimeHandle, UserAttentionType, UserEvent
 };
 #[cfg(target_os = "macos")]
-use millennium_runtime::{menu::NativeImage, ActivationPolicy};
+use millennium_runtime::menu::NativeImage;
 #[cfg(feature = "system-tray")]
 use millennium_runtime::{SystemTray, SystemTrayEvent};
-use millennium_utils::{config::WindowConfig, Theme};
+use millennium_utils::{config::WindowConfig, LayoutDirection, Theme};
 pub use millennium_webview;
 #[cfg(target_os = "macos")]
 use millennium_webview::application::platform::macos::WindowBuilderExtMacOS;
 #[cfg(target_os = "macos")]
 pub use millennium_webview::application::platform::macos::{
-	ActivationPolicy as MillenniumActivationPolicy, CustomMenuItemExtMacOS, EventLoopExtMacOS, NativeImage as MillenniumNativeImage, WindowExtMacOS
+	ActivationPolicy as MillenniumActivationPolicy, CustomMenuItemExtMacOS, EventLoopExtMacOS, EventLoopWindowTargetExtMacOS,
+	NativeImage as MillenniumNativeImage, WindowExtMacOS
 };
 #[cfg(all(feature = "system-tray", target_os = "macos"))]
 use millennium_webview::application::platform::macos::{SystemTrayBuilderExtMacOS, SystemTrayExtMacOS};
@@ -68,27 +75,37 @@ pub use millennium_webview::application::window::{Window, WindowBuilder as Mille
 use millennium_webview::webview::WebviewExtWindows;
 use millennium_webview::{
 	application::{
+		accelerator::{Accelerator as MillenniumAccelerator, AcceleratorId as MillenniumAcceleratorId},
 		dpi::{
 			LogicalPosition as MillenniumLogicalPosition, LogicalSize as MillenniumLogicalSize, PhysicalPosition as MillenniumPhysicalPosition,
 			PhysicalSize as MillenniumPhysicalSize, Position as MillenniumPosition, Size as MillenniumSize
 		},
 		event::{Event, StartCause, WindowEvent as MillenniumWindowEvent},
 		event_loop::{ControlFlow, EventLoop, EventLoopProxy as MillenniumEventLoopProxy, EventLoopWindowTarget},
+		jump_list::JumpListItem as MillenniumJumpListItem,
+		keyboard::{KeyCode, ModifiersState, NativeKeyCode},
 		menu::{
 			AboutMetadata as MillenniumAboutMetadata, CustomMenuItem as MillenniumCustomMenuItem, MenuBar, MenuId as MillenniumMenuId,
 			MenuItem as MillenniumMenuItem, MenuItemAttributes as MillenniumMenuItemAttributes, MenuType
 		},
 		monitor::MonitorHandle,
 		window::{
-			CursorIcon as MillenniumCursorIcon, Fullscreen, Icon as MillenniumWindowIcon, Theme as MillenniumTheme,
+			AccessibilityPreferences as MillenniumAccessibilityPreferences, CursorIcon as MillenniumCursorIcon, Fullscreen, Icon as MillenniumWindowIcon,
+			LayoutDirection as MillenniumLayoutDirection, Rect as MillenniumRect, ResizeDirection as MillenniumResizeDirection, Theme as MillenniumTheme,
 			UserAttentionType as MillenniumUserAttentionType
 		}
 	},
 	http::{
 		Request as MillenniumHttpRequest, RequestParts as MillenniumRequestParts, Response as MillenniumHttpResponse, ResponseParts as MillenniumResponseParts
 	},
-	webview::{FileDropEvent as MillenniumFileDropEvent, WebContext, WebView, WebViewBuilder}
+	webview::{
+		CookiePersistence as MillenniumCookiePersistence, CssId as MillenniumCssId, DevtoolsPosition as MillenniumDevtoolsPosition,
+		FileDropEvent as MillenniumFileDropEvent, InitScriptId as MillenniumInitScriptId, PdfOrientation as MillenniumPdfOrientation,
+		PdfSettings as MillenniumPdfSettings, PermissionKind as MillenniumPermissionKind, PermissionResponse as MillenniumPermissionResponse,
+		ScrollBarStyle as MillenniumScrollBarStyle, WebContext, WebView, WebViewBuilder
+	}
 };
+use once_cell::sync::OnceCell;
 pub use raw_window_handle::HasRawWindowHandle;
 use uuid::Uuid;
 #[cfg(windows)]
@@ -98,9 +115,12 @@ use webview2_com::FocusChangedEventHandler;
 use windows::Win32::{Foundation::HWND, System::WinRT::EventRegistrationToken};
 
 pub type WebviewId = u64;
-type IpcHandler = dyn Fn(&Window, String) + 'static;
+type IpcHandler<M = String> = dyn Fn(&Window, M) + 'static;
 type FileDropHandler = dyn Fn(&Window, MillenniumFileDropEvent) -> bool + 'static;
 
+mod frame_limiter;
+pub use frame_limiter::FrameLimiter;
+mod protocol;
 mod webview;
 pub use webview::Webview;
 
@@ -123,6 +143,10 @@ pub type WebContextStore = Arc<Mutex<HashMap<Option<PathBuf>, WebContext>>>;
 // window
 pub type WindowEventHandler = Box<dyn Fn(&WindowEvent) + Send>;
 pub type WindowEventListeners = Arc<Mutex<HashMap<Uuid, WindowEventHandler>>>;
+pub type AnyWindowEventHandler = Box<dyn Fn(&str, &WindowEvent) + Send>;
+pub type AnyWindowEventListeners = Arc<Mutex<HashMap<Uuid, AnyWindowEventHandler>>>;
+pub type AppShortcutHandler = Arc<dyn Fn() + Send + Sync>;
+pub type AppShortcuts = Arc<Mutex<HashMap<String, (MillenniumAccelerator, AppShortcutHandler)>>>;
 // menu
 pub type MenuEventHandler = Box<dyn Fn(&MenuEvent) + Send>;
 pub type WindowMenuEventListeners = Arc<Mutex<HashMap<Uuid, MenuEventHandler>>>;
@@ -172,6 +196,8 @@ fn send_user_message<T: UserEvent>(context: &Context<T>, message: Message<T>) ->
 				#[cfg(feature = "clipboard")]
 				clipboard_manager: context.main_thread.clipboard_manager.clone(),
 				windows: context.main_thread.windows.clone(),
+				any_window_event_listeners: context.any_window_event_listeners.clone(),
+				frame_limiter: context.frame_limiter.clone(),
 				#[cfg(feature = "system-tray")]
 				tray_context: &context.main_thread.tray_context
 			},
@@ -188,6 +214,10 @@ pub struct Context<T: UserEvent> {
 	pub webview_id_map: WebviewIdStore,
 	main_thread_id: ThreadId,
 	pub proxy: MillenniumEventLoopProxy<Message<T>>,
+	default_window_icon: Arc<Mutex<Option<Icon>>>,
+	any_window_event_listeners: AnyWindowEventListeners,
+	app_shortcuts: AppShortcuts,
+	frame_limiter: Arc<Mutex<FrameLimiter>>,
 	main_thread: DispatcherMainThreadContext<T>
 }
 
@@ -323,14 +353,30 @@ impl<'a> From<&'a CustomMenuItem> for MenuItemAttributesWrapper<'a> {
 		let mut attributes = MillenniumMenuItemAttributes::new(&item.title)
 			.with_enabled(item.enabled)
 			.with_selected(item.selected)
+			.with_checkable(item.kind != MenuItemKind::Normal)
 			.with_id(MillenniumMenuId(item.id));
 		if let Some(accelerator) = item.keyboard_accelerator.as_ref() {
-			attributes = attributes.with_accelerators(&accelerator.parse().expect("invalid accelerator"));
+			attributes = attributes.with_accelerators(&AcceleratorWrapper::from(accelerator).0);
 		}
 		Self(attributes)
 	}
 }
 
+pub struct AcceleratorWrapper(pub MillenniumAccelerator);
+
+impl From<&Accelerator> for AcceleratorWrapper {
+	fn from(accelerator: &Accelerator) -> Self {
+		let mut mods = ModifiersState::empty();
+		mods.set(ModifiersState::SHIFT, accelerator.mods.shift);
+		mods.set(ModifiersState::CONTROL, accelerator.mods.ctrl);
+		mods.set(ModifiersState::ALT, accelerator.mods.alt);
+		mods.set(ModifiersState::SUPER, accelerator.mods.super_key);
+		// the runtime parser only accepts key names that `KeyCode` knows about
+		let key = accelerator.key.parse().unwrap_or(KeyCode::Unidentified(NativeKeyCode::Unidentified));
+		Self(MillenniumAccelerator::new(mods, key).with_id(MillenniumAcceleratorId::new(&accelerator.to_string())))
+	}
+}
+
 pub struct AboutMetadataWrapper(pub MillenniumAboutMetadata);
 
 impl From<AboutMetadata> for AboutMetadataWrapper {
@@ -611,6 +657,14 @@ impl From<Position> for PositionWrapper {
 	}
 }
 
+pub struct RectWrapper(pub MillenniumRect);
+
+impl From<Rect> for RectWrapper {
+	fn from(rect: Rect) -> Self {
+		Self(MillenniumRect::new(PositionWrapper::from(rect.position).0, SizeWrapper::from(rect.size).0))
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct UserAttentionTypeWrapper(pub MillenniumUserAttentionType);
 
@@ -624,6 +678,142 @@ impl From<UserAttentionType> for UserAttentionTypeWrapper {
 	}
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct ResizeDirectionWrapper(pub MillenniumResizeDirection);
+
+impl From<ResizeDirection> for ResizeDirectionWrapper {
+	fn from(direction: ResizeDirection) -> Self {
+		let o = match direction {
+			ResizeDirection::East => MillenniumResizeDirection::East,
+			ResizeDirection::North => MillenniumResizeDirection::North,
+			ResizeDirection::NorthEast => MillenniumResizeDirection::NorthEast,
+			ResizeDirection::NorthWest => MillenniumResizeDirection::NorthWest,
+			ResizeDirection::South => MillenniumResizeDirection::South,
+			ResizeDirection::SouthEast => MillenniumResizeDirection::SouthEast,
+			ResizeDirection::SouthWest => MillenniumResizeDirection::SouthWest,
+			ResizeDirection::West => MillenniumResizeDirection::West
+		};
+		Self(o)
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AccessibilityPreferencesWrapper(pub MillenniumAccessibilityPreferences);
+
+impl From<AccessibilityPreferencesWrapper> for AccessibilityPreferences {
+	fn from(preferences: AccessibilityPreferencesWrapper) -> Self {
+		Self {
+			reduce_motion: preferences.0.reduce_motion,
+			increase_contrast: preferences.0.increase_contrast,
+			reduce_transparency: preferences.0.reduce_transparency
+		}
+	}
+}
+
+pub struct JumpListItemWrapper(pub MillenniumJumpListItem);
+
+impl From<JumpListItem> for JumpListItemWrapper {
+	fn from(item: JumpListItem) -> Self {
+		Self(MillenniumJumpListItem::new(item.id, item.title))
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PdfSettingsWrapper(pub MillenniumPdfSettings);
+
+impl From<PdfSettings> for PdfSettingsWrapper {
+	fn from(settings: PdfSettings) -> Self {
+		Self(MillenniumPdfSettings {
+			page_width: settings.page_width,
+			page_height: settings.page_height,
+			margin_top: settings.margin_top,
+			margin_bottom: settings.margin_bottom,
+			margin_left: settings.margin_left,
+			margin_right: settings.margin_right,
+			orientation: match settings.orientation {
+				PdfOrientation::Portrait => MillenniumPdfOrientation::Portrait,
+				PdfOrientation::Landscape => MillenniumPdfOrientation::Landscape
+			}
+		})
+	}
+}
+
+pub struct PermissionKindWrapper(pub PermissionKind);
+
+impl From<MillenniumPermissionKind> for PermissionKindWrapper {
+	fn from(kind: MillenniumPermissionKind) -> Self {
+		Self(match kind {
+			MillenniumPermissionKind::Camera => PermissionKind::Camera,
+			MillenniumPermissionKind::Microphone => PermissionKind::Microphone,
+			MillenniumPermissionKind::Geolocation => PermissionKind::Geolocation,
+			MillenniumPermissionKind::Notifications => PermissionKind::Notifications,
+			_ => PermissionKind::Other
+		})
+	}
+}
+
+pub struct PermissionResponseWrapper(pub MillenniumPermissionResponse);
+
+impl From<PermissionResponse> for PermissionResponseWrapper {
+	fn from(response: PermissionResponse) -> Self {
+		Self(match response {
+			PermissionResponse::Allow => MillenniumPermissionResponse::Allow,
+			PermissionResponse::Deny => MillenniumPermissionResponse::Deny,
+			PermissionResponse::Prompt => MillenniumPermissionResponse::Prompt
+		})
+	}
+}
+
+pub struct DevtoolsPositionWrapper(pub MillenniumDevtoolsPosition);
+
+impl From<DevtoolsPosition> for DevtoolsPositionWrapper {
+	fn from(position: DevtoolsPosition) -> Self {
+		Self(match position {
+			DevtoolsPosition::Detached => MillenniumDevtoolsPosition::Detached,
+			_ => MillenniumDevtoolsPosition::Docked
+		})
+	}
+}
+
+/// `None` for policies Millennium Core doesn't know, which are ignored.
+#[cfg(target_os = "macos")]
+pub struct ActivationPolicyWrapper(pub Option<MillenniumActivationPolicy>);
+
+#[cfg(target_os = "macos")]
+impl From<ActivationPolicy> for ActivationPolicyWrapper {
+	fn from(activation_policy: ActivationPolicy) -> Self {
+		Self(match activation_policy {
+			ActivationPolicy::Regular => Some(MillenniumActivationPolicy::Regular),
+			ActivationPolicy::Accessory => Some(MillenniumActivationPolicy::Accessory),
+			ActivationPolicy::Prohibited => Some(MillenniumActivationPolicy::Prohibited),
+			_ => None
+		})
+	}
+}
+
+pub struct CookiePersistenceWrapper(pub MillenniumCookiePersistence);
+
+impl From<CookiePersistence> for CookiePersistenceWrapper {
+	fn from(persistence: CookiePersistence) -> Self {
+		Self(match persistence {
+			CookiePersistence::Session => MillenniumCookiePersistence::Session,
+			_ => MillenniumCookiePersistence::Persistent
+		})
+	}
+}
+
+pub struct ScrollBarStyleWrapper(pub MillenniumScrollBarStyle);
+
+impl From<ScrollBarStyle> for ScrollBarStyleWrapper {
+	fn from(style: ScrollBarStyle) -> Self {
+		Self(match style {
+			ScrollBarStyle::Overlay => MillenniumScrollBarStyle::Overlay,
+			ScrollBarStyle::AlwaysVisible => MillenniumScrollBarStyle::AlwaysVisible,
+			_ => MillenniumScrollBarStyle::Default
+		})
+	}
+}
+
 #[derive(Debug)]
 pub struct CursorIconWrapper(pub MillenniumCursorIcon);
 
@@ -676,6 +866,7 @@ impl From<CursorIcon> for CursorIconWrapper {
 pub struct WindowBuilderWrapper {
 	inner: MillenniumWindowBuilder,
 	center: bool,
+	center_on_monitor: Option<Monitor>,
 	menu: Option<Menu>
 }
 
@@ -700,15 +891,16 @@ impl WindowBuilder for WindowBuilderWrapper {
 			.maximized(config.maximized)
 			.always_on_top(config.always_on_top)
 			.skip_taskbar(config.skip_taskbar)
-			.theme(config.theme);
+			.theme(config.theme)
+			.layout_direction(config.layout_direction);
 
 		#[cfg(any(not(target_os = "macos"), feature = "macos-private-api"))]
 		{
 			window = window.transparent(config.transparent);
 		}
-		#[cfg(all(target_os = "macos", not(feature = "macos-private-api"), debug_assertions))]
+		#[cfg(all(target_os = "macos", not(feature = "macos-private-api")))]
 		if config.transparent {
-			eprintln!(
+			log::warn!(
 				"The window is set to be transparent but the `macos-private-api` is not enabled. This can be enabled via the `millennium.macOSPrivateApi` configuration property."
 			);
 		}
@@ -744,6 +936,11 @@ impl WindowBuilder for WindowBuilderWrapper {
 		self
 	}
 
+	fn center_on_monitor(mut self, monitor: Monitor) -> Self {
+		self.center_on_monitor.replace(monitor);
+		self
+	}
+
 	fn position(mut self, x: f64, y: f64) -> Self {
 		self.inner = self.inner.with_position(MillenniumLogicalPosition::new(x, y));
 		self
@@ -769,6 +966,21 @@ impl WindowBuilder for WindowBuilderWrapper {
 		self
 	}
 
+	fn minimizable(mut self, minimizable: bool) -> Self {
+		self.inner = self.inner.with_minimizable(minimizable);
+		self
+	}
+
+	fn maximizable(mut self, maximizable: bool) -> Self {
+		self.inner = self.inner.with_maximizable(maximizable);
+		self
+	}
+
+	fn closable(mut self, closable: bool) -> Self {
+		self.inner = self.inner.with_closable(closable);
+		self
+	}
+
 	fn title<S: Into<String>>(mut self, title: S) -> Self {
 		self.inner = self.inner.with_title(title.into());
 		self
@@ -844,6 +1056,16 @@ impl WindowBuilder for WindowBuilderWrapper {
 		Ok(self)
 	}
 
+	#[allow(unused_mut, unused_variables)]
+	fn taskbar_icon(mut self, icon: Icon) -> Result<Self> {
+		let icon = MillenniumIcon::try_from(icon)?.0;
+		#[cfg(windows)]
+		{
+			self.inner = self.inner.with_taskbar_icon(Some(icon));
+		}
+		Ok(self)
+	}
+
 	#[cfg(any(windows, target_os = "linux"))]
 	fn skip_taskbar(mut self, skip: bool) -> Self {
 		self.inner = self.inner.with_skip_taskbar(skip);
@@ -871,6 +1093,19 @@ impl WindowBuilder for WindowBuilderWrapper {
 		self
 	}
 
+	fn layout_direction(mut self, direction: Option<LayoutDirection>) -> Self {
+		self.inner = self.inner.with_layout_direction(direction.map(|direction| match direction {
+			LayoutDirection::RightToLeft => MillenniumLayoutDirection::RightToLeft,
+			_ => MillenniumLayoutDirection::LeftToRight
+		}));
+		self
+	}
+
+	fn title_bar_color(mut self, color: Option<(u8, u8, u8, u8)>) -> Self {
+		self.inner = self.inner.with_title_bar_color(color);
+		self
+	}
+
 	fn has_icon(&self) -> bool {
 		self.inner.window.window_icon.is_some()
 	}
@@ -922,9 +1157,13 @@ pub enum WindowMessage {
 	WithWebview(Box<dyn FnOnce(Webview) + Send>),
 	AddEventListener(Uuid, Box<dyn Fn(&WindowEvent) + Send>),
 	AddMenuEventListener(Uuid, Box<dyn Fn(&MenuEvent) + Send>),
+	RemoveEventListener(Uuid),
+	RemoveMenuEventListener(Uuid),
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	OpenDevTools,
 	#[cfg(any(debug_assertions, feature = "devtools"))]
+	OpenDevToolsDetached,
+	#[cfg(any(debug_assertions, feature = "devtools"))]
 	CloseDevTools,
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	IsDevToolsOpen(Sender<bool>),
@@ -934,10 +1173,16 @@ pub enum WindowMessage {
 	OuterPosition(Sender<Result<PhysicalPosition<i32>>>),
 	InnerSize(Sender<PhysicalSize<u32>>),
 	OuterSize(Sender<PhysicalSize<u32>>),
+	MinSize(Sender<Option<PhysicalSize<u32>>>),
+	MaxSize(Sender<Option<PhysicalSize<u32>>>),
 	IsFullscreen(Sender<bool>),
 	IsMaximized(Sender<bool>),
 	IsDecorated(Sender<bool>),
 	IsResizable(Sender<bool>),
+	IsMinimizable(Sender<bool>),
+	IsMaximizable(Sender<bool>),
+	IsClosable(Sender<bool>),
+	IsVisibleOnAllWorkspaces(Sender<bool>),
 	IsVisible(Sender<bool>),
 	IsMenuVisible(Sender<bool>),
 	CurrentMonitor(Sender<Option<MonitorHandle>>),
@@ -947,10 +1192,16 @@ pub enum WindowMessage {
 	GtkWindow(Sender<GtkWindow>),
 	RawWindowHandle(Sender<RawWindowHandle>),
 	Theme(Sender<Theme>),
+	AccentColor(Sender<Option<(u8, u8, u8, u8)>>),
+	AccessibilityPreferences(Sender<AccessibilityPreferences>),
 	// Setters
 	Center,
+	CenterOnMonitor(Monitor),
 	RequestUserAttention(Option<UserAttentionTypeWrapper>),
 	SetResizable(bool),
+	SetMinimizable(bool),
+	SetMaximizable(bool),
+	SetClosable(bool),
 	SetTitle(String),
 	Maximize,
 	Unmaximize,
@@ -963,20 +1214,36 @@ pub enum WindowMessage {
 	Close,
 	SetDecorations(bool),
 	SetAlwaysOnTop(bool),
+	SetVisibleOnAllWorkspaces(bool),
+	SetOpacity(f64),
 	SetSize(Size),
 	SetMinSize(Option<Size>),
 	SetMaxSize(Option<Size>),
+	SetAspectRatio(Option<f64>),
 	SetPosition(Position),
 	SetFullscreen(bool),
 	SetFocus,
 	SetIcon(MillenniumWindowIcon),
+	SetTaskbarIcon(MillenniumWindowIcon),
 	SetSkipTaskbar(bool),
+	SetRepresentedFilename(PathBuf),
+	SetTitleBarColors {
+		caption: Option<(u8, u8, u8, u8)>,
+		text: Option<(u8, u8, u8, u8)>,
+		border: Option<(u8, u8, u8, u8)>
+	},
 	SetCursorGrab(bool),
 	SetCursorVisible(bool),
 	SetCursorIcon(CursorIcon),
 	SetCursorPosition(Position),
 	DragWindow,
+	StartResizeDrag(ResizeDirectionWrapper),
+	TitlebarDoubleClick,
+	SetDragRegions(Vec<Rect>),
+	StartDrag(Vec<PathBuf>, Option<MillenniumWindowIcon>),
 	UpdateMenuItem(u16, MenuUpdate),
+	InsertMenuItem(Option<MenuId>, Option<usize>, CustomMenuItem),
+	RemoveMenuItem(MenuId),
 	RequestRedraw
 }
 
@@ -985,7 +1252,21 @@ pub enum WebviewMessage {
 	EvaluateScript(String),
 	#[allow(dead_code)]
 	WebviewEvent(WebviewEvent),
-	Print
+	Print,
+	PrintToPdf {
+		path: PathBuf,
+		settings: PdfSettingsWrapper,
+		tx: Sender<Result<PathBuf>>
+	},
+	TakeSnapshot(Sender<Result<Vec<u8>>>),
+	SetClipboardAccess(bool),
+	InsertCss(String, Sender<Result<CssId>>),
+	RemoveCss(CssId),
+	AddInitScript(String, Sender<Result<InitScriptId>>),
+	RemoveInitScript(InitScriptId),
+	GetMemoryUsage(Sender<Option<u64>>),
+	Suspend,
+	Resume
 }
 
 #[allow(dead_code)]
@@ -998,6 +1279,8 @@ pub enum WebviewEvent {
 #[derive(Debug, Clone)]
 pub enum TrayMessage {
 	UpdateItem(u16, MenuUpdate),
+	InsertItem(Option<MenuId>, Option<usize>, CustomMenuItem),
+	RemoveItem(MenuId),
 	UpdateMenu(SystemTrayMenu),
 	UpdateIcon(Icon),
 	#[cfg(target_os = "macos")]
@@ -1006,10 +1289,18 @@ pub enum TrayMessage {
 }
 
 pub type CreateWebviewClosure<T> = Box<dyn FnOnce(&EventLoopWindowTarget<Message<T>>, &WebContextStore) -> Result<WindowWrapper> + Send>;
+/// A message to the event loop.
+///
+/// This isn't `Clone`, as tasks and window creation closures can only run once.
 pub enum Message<T: 'static> {
 	Task(Box<dyn FnOnce() + Send>),
 	Window(WebviewId, WindowMessage),
 	Webview(WebviewId, WebviewMessage),
+	/// Evaluates the script on every webview.
+	EvaluateScriptAll(String),
+	#[cfg(target_os = "macos")]
+	SetActivationPolicy(ActivationPolicy),
+	SetJumpList(Vec<JumpListItem>),
 	#[cfg(feature = "system-tray")]
 	Tray(TrayMessage),
 	CreateWebview(WebviewId, CreateWebviewClosure<T>),
@@ -1021,22 +1312,6 @@ pub enum Message<T: 'static> {
 	UserEvent(T)
 }
 
-impl<T: UserEvent> Clone for Message<T> {
-	fn clone(&self) -> Self {
-		match self {
-			Self::Webview(i, m) => Self::Webview(*i, m.clone()),
-			#[cfg(feature = "system-tray")]
-			Self::Tray(m) => Self::Tray(m.clone()),
-			#[cfg(feature = "global-shortcut")]
-			Self::GlobalShortcut(m) => Self::GlobalShortcut(m.clone()),
-			#[cfg(feature = "clipboard")]
-			Self::Clipboard(m) => Self::Clipboard(m.clone()),
-			Self::UserEvent(t) => Self::UserEvent(t.clone()),
-			_ => unimplemented!()
-		}
-	}
-}
-
 #[derive(Debug, Clone)]
 pub struct MillenniumDispatcher<T: UserEvent> {
 	window_id: WebviewId,
@@ -1052,6 +1327,19 @@ impl<T: UserEvent> MillenniumDispatcher<T> {
 	pub fn with_webview<F: FnOnce(Webview) + Send + 'static>(&self, f: F) -> Result<()> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::WithWebview(Box::new(f))))
 	}
+
+	/// Runs the closure on the main thread and returns its result. When called from another thread, this blocks until
+	/// the event loop has run the closure.
+	pub fn run_on_main_thread_returning<R: Send + 'static, F: FnOnce() -> R + Send + 'static>(&self, f: F) -> Result<R> {
+		let (tx, rx) = channel();
+		getter!(
+			self,
+			rx,
+			Message::Task(Box::new(move || {
+				let _ = tx.send(f());
+			}))
+		)
+	}
 }
 
 impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
@@ -1080,11 +1368,24 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 		id
 	}
 
+	fn remove_window_event_listener(&self, id: Uuid) {
+		let _ = self.context.proxy.send_event(Message::Window(self.window_id, WindowMessage::RemoveEventListener(id)));
+	}
+
+	fn remove_menu_event_listener(&self, id: Uuid) {
+		let _ = self.context.proxy.send_event(Message::Window(self.window_id, WindowMessage::RemoveMenuEventListener(id)));
+	}
+
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	fn open_devtools(&self) {
 		let _ = send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::OpenDevTools));
 	}
 
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	fn open_devtools_detached(&self) {
+		let _ = send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::OpenDevToolsDetached));
+	}
+
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	fn close_devtools(&self) {
 		let _ = send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::CloseDevTools));
@@ -1117,6 +1418,14 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 		window_getter!(self, WindowMessage::OuterSize)
 	}
 
+	fn min_size(&self) -> Result<Option<PhysicalSize<u32>>> {
+		window_getter!(self, WindowMessage::MinSize)
+	}
+
+	fn max_size(&self) -> Result<Option<PhysicalSize<u32>>> {
+		window_getter!(self, WindowMessage::MaxSize)
+	}
+
 	fn is_fullscreen(&self) -> Result<bool> {
 		window_getter!(self, WindowMessage::IsFullscreen)
 	}
@@ -1135,6 +1444,22 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 		window_getter!(self, WindowMessage::IsResizable)
 	}
 
+	fn is_minimizable(&self) -> Result<bool> {
+		window_getter!(self, WindowMessage::IsMinimizable)
+	}
+
+	fn is_maximizable(&self) -> Result<bool> {
+		window_getter!(self, WindowMessage::IsMaximizable)
+	}
+
+	fn is_closable(&self) -> Result<bool> {
+		window_getter!(self, WindowMessage::IsClosable)
+	}
+
+	fn is_visible_on_all_workspaces(&self) -> Result<bool> {
+		window_getter!(self, WindowMessage::IsVisibleOnAllWorkspaces)
+	}
+
 	fn is_visible(&self) -> Result<bool> {
 		window_getter!(self, WindowMessage::IsVisible)
 	}
@@ -1162,6 +1487,14 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 		window_getter!(self, WindowMessage::Theme)
 	}
 
+	fn accent_color(&self) -> Result<Option<(u8, u8, u8, u8)>> {
+		window_getter!(self, WindowMessage::AccentColor)
+	}
+
+	fn accessibility_preferences(&self) -> Result<AccessibilityPreferences> {
+		window_getter!(self, WindowMessage::AccessibilityPreferences)
+	}
+
 	/// Returns the `ApplicatonWindow` from gtk crate that is used by this
 	/// window.
 	#[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
@@ -1179,17 +1512,65 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::Center))
 	}
 
+	fn center_on_monitor(&self, monitor: Monitor) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::CenterOnMonitor(monitor)))
+	}
+
 	fn print(&self) -> Result<()> {
 		send_user_message(&self.context, Message::Webview(self.window_id, WebviewMessage::Print))
 	}
 
+	fn print_to_pdf(&self, path: PathBuf, settings: PdfSettings) -> Result<PathBuf> {
+		let (tx, rx) = channel();
+		getter!(self, rx, Message::Webview(self.window_id, WebviewMessage::PrintToPdf { path, settings: settings.into(), tx }))?
+	}
+
+	fn take_snapshot(&self) -> Result<Vec<u8>> {
+		let (tx, rx) = channel();
+		getter!(self, rx, Message::Webview(self.window_id, WebviewMessage::TakeSnapshot(tx)))?
+	}
+
+	fn set_clipboard_access(&self, enabled: bool) -> Result<()> {
+		send_user_message(&self.context, Message::Webview(self.window_id, WebviewMessage::SetClipboardAccess(enabled)))
+	}
+
+	fn insert_css(&self, css: &str) -> Result<CssId> {
+		let (tx, rx) = channel();
+		getter!(self, rx, Message::Webview(self.window_id, WebviewMessage::InsertCss(css.to_string(), tx)))?
+	}
+
+	fn remove_css(&self, id: CssId) -> Result<()> {
+		send_user_message(&self.context, Message::Webview(self.window_id, WebviewMessage::RemoveCss(id)))
+	}
+
+	fn add_init_script(&self, js: &str) -> Result<InitScriptId> {
+		let (tx, rx) = channel();
+		getter!(self, rx, Message::Webview(self.window_id, WebviewMessage::AddInitScript(js.to_string(), tx)))?
+	}
+
+	fn remove_init_script(&self, id: InitScriptId) -> Result<()> {
+		send_user_message(&self.context, Message::Webview(self.window_id, WebviewMessage::RemoveInitScript(id)))
+	}
+
+	fn memory_usage(&self) -> Result<Option<u64>> {
+		let (tx, rx) = channel();
+		getter!(self, rx, Message::Webview(self.window_id, WebviewMessage::GetMemoryUsage(tx)))
+	}
+
+	fn suspend(&self) -> Result<()> {
+		send_user_message(&self.context, Message::Webview(self.window_id, WebviewMessage::Suspend))
+	}
+
+	fn resume(&self) -> Result<()> {
+		send_user_message(&self.context, Message::Webview(self.window_id, WebviewMessage::Resume))
+	}
+
 	fn request_user_attention(&self, request_type: Option<UserAttentionType>) -> Result<()> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::RequestUserAttention(request_type.map(Into::into))))
 	}
 
-	// Creates a window by dispatching a message to the event loop.
-	// Note that this must be called from a separate thread, otherwise the channel
-	// will introduce a deadlock.
+	// Creates a window by dispatching a message to the event loop, or immediately
+	// when called from the main thread. This never waits on the event loop.
 	fn create_window(&mut self, pending: PendingWindow<T, Self::Runtime>) -> Result<DetachedWindow<T, Self::Runtime>> {
 		self.context.create_webview(pending)
 	}
@@ -1198,6 +1579,18 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetResizable(resizable)))
 	}
 
+	fn set_minimizable(&self, minimizable: bool) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetMinimizable(minimizable)))
+	}
+
+	fn set_maximizable(&self, maximizable: bool) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetMaximizable(maximizable)))
+	}
+
+	fn set_closable(&self, closable: bool) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetClosable(closable)))
+	}
+
 	fn set_title<S: Into<String>>(&self, title: S) -> Result<()> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetTitle(title.into())))
 	}
@@ -1251,6 +1644,14 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetAlwaysOnTop(always_on_top)))
 	}
 
+	fn set_visible_on_all_workspaces(&self, visible_on_all_workspaces: bool) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetVisibleOnAllWorkspaces(visible_on_all_workspaces)))
+	}
+
+	fn set_opacity(&self, opacity: f64) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetOpacity(opacity)))
+	}
+
 	fn set_size(&self, size: Size) -> Result<()> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetSize(size)))
 	}
@@ -1263,6 +1664,10 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetMaxSize(size)))
 	}
 
+	fn set_aspect_ratio(&self, aspect_ratio: Option<f64>) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetAspectRatio(aspect_ratio)))
+	}
+
 	fn set_position(&self, position: Position) -> Result<()> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetPosition(position)))
 	}
@@ -1279,10 +1684,22 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetIcon(MillenniumIcon::try_from(icon)?.0)))
 	}
 
+	fn set_taskbar_icon(&self, icon: Icon) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetTaskbarIcon(MillenniumIcon::try_from(icon)?.0)))
+	}
+
 	fn set_skip_taskbar(&self, skip: bool) -> Result<()> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetSkipTaskbar(skip)))
 	}
 
+	fn set_represented_filename(&self, path: PathBuf) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetRepresentedFilename(path)))
+	}
+
+	fn set_title_bar_colors(&self, caption: Option<(u8, u8, u8, u8)>, text: Option<(u8, u8, u8, u8)>, border: Option<(u8, u8, u8, u8)>) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetTitleBarColors { caption, text, border }))
+	}
+
 	fn set_cursor_grab(&self, grab: bool) -> crate::Result<()> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetCursorGrab(grab)))
 	}
@@ -1303,6 +1720,23 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::DragWindow))
 	}
 
+	fn start_resize_dragging(&self, direction: ResizeDirection) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::StartResizeDrag(direction.into())))
+	}
+
+	fn titlebar_double_click(&self) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::TitlebarDoubleClick))
+	}
+
+	fn set_drag_regions(&self, drag_regions: Vec<Rect>) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::SetDragRegions(drag_regions)))
+	}
+
+	fn start_drag(&self, paths: Vec<PathBuf>, icon: Option<Icon>) -> Result<()> {
+		let icon = icon.map(MillenniumIcon::try_from).transpose()?.map(|icon| icon.0);
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::StartDrag(paths, icon)))
+	}
+
 	fn eval_script<S: Into<String>>(&self, script: S) -> Result<()> {
 		send_user_message(&self.context, Message::Webview(self.window_id, WebviewMessage::EvaluateScript(script.into())))
 	}
@@ -1310,6 +1744,14 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 	fn update_menu_item(&self, id: u16, update: MenuUpdate) -> Result<()> {
 		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::UpdateMenuItem(id, update)))
 	}
+
+	fn insert_menu_item(&self, parent: Option<MenuId>, index: Option<usize>, item: CustomMenuItem) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::InsertMenuItem(parent, index, item)))
+	}
+
+	fn remove_menu_item(&self, id: MenuId) -> Result<()> {
+		send_user_message(&self.context, Message::Window(self.window_id, WindowMessage::RemoveMenuItem(id)))
+	}
 }
 
 #[cfg(feature = "system-tray")]
@@ -1317,7 +1759,8 @@ impl<T: UserEvent> Dispatch<T> for MillenniumDispatcher<T> {
 pub struct TrayContext {
 	tray: Arc<Mutex<Option<Arc<Mutex<MillenniumSystemTray>>>>>,
 	listeners: SystemTrayEventListeners,
-	items: SystemTrayItems
+	items: SystemTrayItems,
+	menu: Arc<Mutex<Option<SystemTrayMenu>>>
 }
 
 #[cfg(feature = "system-tray")]
@@ -1363,9 +1806,19 @@ impl WindowHandle {
 pub struct WindowWrapper {
 	label: String,
 	inner: Option<WindowHandle>,
+	menu: Option<Menu>,
 	menu_items: Option<HashMap<u16, MillenniumCustomMenuItem>>,
 	window_event_listeners: WindowEventListeners,
-	menu_event_listeners: WindowMenuEventListeners
+	menu_event_listeners: WindowMenuEventListeners,
+	headless: bool,
+	// not every platform can report these back, so keep track of the last constraints we set
+	min_size: Option<MillenniumSize>,
+	max_size: Option<MillenniumSize>,
+	suspend_when_unfocused: bool,
+	suspended: bool,
+	scale_change_scripts: Vec<String>,
+	// asked to be centered before a monitor was known; retried once the window is resized or moved
+	center_pending: bool
 }
 
 impl fmt::Debug for WindowWrapper {
@@ -1374,10 +1827,38 @@ impl fmt::Debug for WindowWrapper {
 			.field("label", &self.label)
 			.field("inner", &self.inner)
 			.field("menu_items", &self.menu_items)
+			.field("headless", &self.headless)
 			.finish()
 	}
 }
 
+impl WindowWrapper {
+	/// Rebuilds the native menu from the menu model, for an inserted item that has no native neighbour to be placed
+	/// next to.
+	fn refresh_menu(&mut self) {
+		if let (Some(window), Some(menu)) = (&self.inner, &self.menu) {
+			let mut menu_items = HashMap::new();
+			window.set_menu(Some(to_millennium_menu(&mut menu_items, menu.clone())));
+			self.menu_items.replace(menu_items);
+		}
+	}
+
+	/// Updates the check marks of the menu after a checkbox or radio item was clicked.
+	fn activate_menu_item(&mut self, id: MenuHash) {
+		if let (Some(menu), Some(menu_items)) = (self.menu.as_mut(), self.menu_items.as_mut()) {
+			let item_id = match find_menu_item_mut(menu, id) {
+				Some(item) if item.kind != MenuItemKind::Normal => item.id_str.clone(),
+				_ => return
+			};
+			set_native_selected(menu_items, menu.activate_item(&item_id));
+			// GTK toggles check menu items on its own, so make sure the clicked item matches the model
+			if let (Some(item), Some(native_item)) = (menu.find_item(&item_id), menu_items.get_mut(&id)) {
+				native_item.set_selected(item.selected);
+			}
+		}
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct EventProxy<T: UserEvent>(MillenniumEventLoopProxy<Message<T>>);
 
@@ -1407,6 +1888,8 @@ pub trait Plugin<T: UserEvent> {
 
 pub struct MillenniumWebview<T: UserEvent> {
 	context: Context<T>,
+	// the only strong reference to the context that `WeakMillenniumHandle`s upgrade from
+	root_context: Arc<Mutex<Context<T>>>,
 
 	plugins: Vec<Box<dyn Plugin<T>>>,
 
@@ -1441,7 +1924,28 @@ impl<T: UserEvent> fmt::Debug for MillenniumWebview<T> {
 /// A handle to the Millennium Webview runtime.
 #[derive(Debug, Clone)]
 pub struct MillenniumHandle<T: UserEvent> {
-	context: Context<T>
+	context: Context<T>,
+	root_context: Weak<Mutex<Context<T>>>
+}
+
+/// A handle to the Millennium Webview runtime that doesn't keep its resources alive, created with
+/// [`MillenniumHandle::downgrade`].
+///
+/// Background tasks can hold on to it and [upgrade](Self::upgrade) it whenever they need to reach the runtime; once
+/// the runtime is gone, upgrading fails instead of keeping the windows and webview contexts pinned.
+#[derive(Debug, Clone)]
+pub struct WeakMillenniumHandle<T: UserEvent> {
+	context: Weak<Mutex<Context<T>>>
+}
+
+impl<T: UserEvent> WeakMillenniumHandle<T> {
+	/// Upgrades to a full [`MillenniumHandle`], or returns `None` if the runtime has already been dropped.
+	pub fn upgrade(&self) -> Option<MillenniumHandle<T>> {
+		self.context.upgrade().map(|context| MillenniumHandle {
+			context: context.lock().unwrap().clone(),
+			root_context: self.context.clone()
+		})
+	}
 }
 
 // SAFETY: this is safe since the `Context` usage is guarded on
@@ -1450,6 +1954,11 @@ pub struct MillenniumHandle<T: UserEvent> {
 unsafe impl<T: UserEvent> Sync for MillenniumHandle<T> {}
 
 impl<T: UserEvent> MillenniumHandle<T> {
+	/// Creates a [`WeakMillenniumHandle`] that only upgrades to a handle while the runtime is alive.
+	pub fn downgrade(&self) -> WeakMillenniumHandle<T> {
+		WeakMillenniumHandle { context: self.root_context.clone() }
+	}
+
 	/// Creates a new Millennium Core window using a callback, and returns its
 	/// window id.
 	pub fn create_core_window<F: FnOnce() -> (String, MillenniumWindowBuilder) + Send + 'static>(&self, f: F) -> Result<Weak<Window>> {
@@ -1463,11 +1972,85 @@ impl<T: UserEvent> MillenniumHandle<T> {
 		*self.context.webview_id_map.0.lock().unwrap().get(&window_id).unwrap()
 	}
 
+	/// Registers a listener for the events of every window, including windows created later. The listener receives
+	/// the label of the window the event belongs to.
+	///
+	/// Returns an id that can be passed to [`Self::remove_any_window_event_listener`].
+	pub fn on_any_window_event<F: Fn(&str, &WindowEvent) + Send + 'static>(&self, f: F) -> Uuid {
+		let id = Uuid::new_v4();
+		self.context.any_window_event_listeners.lock().unwrap().insert(id, Box::new(f));
+		id
+	}
+
+	/// Removes a listener registered with [`Self::on_any_window_event`].
+	pub fn remove_any_window_event_listener(&self, id: Uuid) {
+		self.context.any_window_event_listeners.lock().unwrap().remove(&id);
+	}
+
 	/// Send a message to the event loop.
 	pub fn send_event(&self, message: Message<T>) -> Result<()> {
 		self.context.proxy.send_event(message).map_err(|_| Error::FailedToSendMessage)?;
 		Ok(())
 	}
+
+	/// Runs the closure on the main thread and returns its result. When called from another thread, this blocks until
+	/// the event loop has run the closure.
+	pub fn run_on_main_thread_returning<R: Send + 'static, F: FnOnce() -> R + Send + 'static>(&self, f: F) -> Result<R> {
+		let (tx, rx) = channel();
+		getter!(
+			self,
+			rx,
+			Message::Task(Box::new(move || {
+				let _ = tx.send(f());
+			}))
+		)
+	}
+
+	/// Evaluates the script on every webview with a single message to the event loop, rather than one per window.
+	pub fn eval_script_all<S: Into<String>>(&self, script: S) -> Result<()> {
+		send_user_message(&self.context, Message::EvaluateScriptAll(script.into()))
+	}
+
+	/// Changes the activation policy of the application while the event loop is running, e.g. for an agent app to show
+	/// its dock icon only while one of its windows is open.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux / iOS / Android:** Unsupported; does nothing.
+	pub fn set_activation_policy(&self, activation_policy: ActivationPolicy) -> Result<()> {
+		#[cfg(target_os = "macos")]
+		send_user_message(&self.context, Message::SetActivationPolicy(activation_policy))?;
+		#[cfg(not(target_os = "macos"))]
+		let _ = activation_policy;
+		Ok(())
+	}
+
+	/// Returns the features this runtime supports on the current platform.
+	pub fn capabilities(&self) -> RuntimeCapabilities {
+		RuntimeCapabilities {
+			transparency: cfg!(any(not(target_os = "macos"), feature = "macos-private-api")),
+			skip_taskbar: cfg!(not(target_os = "macos")),
+			#[cfg(windows)]
+			mica: millennium_webview::application::platform::windows::windows_build_number().map_or(false, |build| build >= 22000),
+			#[cfg(not(windows))]
+			mica: false,
+			global_shortcuts: cfg!(feature = "global-shortcut"),
+			system_tray: cfg!(feature = "system-tray"),
+			clipboard: cfg!(feature = "clipboard"),
+			jump_list: cfg!(any(windows, target_os = "macos"))
+		}
+	}
+
+	/// Returns the version of the platform webview: WebView2 on Windows, WebKit on macOS and Linux.
+	///
+	/// The version is only read once; later calls return the cached value.
+	pub fn webview_version(&self) -> Result<String> {
+		static VERSION: OnceCell<String> = OnceCell::new();
+		VERSION
+			.get_or_try_init(millennium_webview::webview::webview_version)
+			.map(ToString::to_string)
+			.map_err(|e| Error::WebviewVersion(e.to_string()))
+	}
 }
 
 impl<T: UserEvent> RuntimeHandle<T> for MillenniumHandle<T> {
@@ -1477,9 +2060,8 @@ impl<T: UserEvent> RuntimeHandle<T> for MillenniumHandle<T> {
 		EventProxy(self.context.proxy.clone())
 	}
 
-	// Creates a window by dispatching a message to the event loop.
-	// Note that this must be called from a separate thread, otherwise the channel
-	// will introduce a deadlock.
+	// Creates a window by dispatching a message to the event loop, or immediately
+	// when called from the main thread. This never waits on the event loop.
 	fn create_window(&self, pending: PendingWindow<T, Self::Runtime>) -> Result<DetachedWindow<T, Self::Runtime>> {
 		self.context.create_webview(pending)
 	}
@@ -1488,6 +2070,25 @@ impl<T: UserEvent> RuntimeHandle<T> for MillenniumHandle<T> {
 		send_user_message(&self.context, Message::Task(Box::new(f)))
 	}
 
+	fn register_app_shortcut<F: Fn() + Send + Sync + 'static>(&self, accelerator: &str, handler: F) -> Result<()> {
+		let parsed = AcceleratorWrapper::from(&accelerator.parse::<Accelerator>()?).0;
+		self.context
+			.app_shortcuts
+			.lock()
+			.unwrap()
+			.insert(accelerator.into(), (parsed, Arc::new(handler)));
+		Ok(())
+	}
+
+	fn unregister_app_shortcut(&self, accelerator: &str) -> Result<()> {
+		self.context.app_shortcuts.lock().unwrap().remove(accelerator);
+		Ok(())
+	}
+
+	fn set_jump_list(&self, items: Vec<JumpListItem>) -> Result<()> {
+		send_user_message(&self.context, Message::SetJumpList(items))
+	}
+
 	#[cfg(all(windows, feature = "system-tray"))]
 	fn remove_system_tray(&self) -> Result<()> {
 		send_user_message(&self.context, Message::Tray(TrayMessage::Close))
@@ -1512,6 +2113,10 @@ impl<T: UserEvent> MillenniumWebview<T> {
 			webview_id_map,
 			main_thread_id,
 			proxy: event_loop.create_proxy(),
+			default_window_icon: Default::default(),
+			any_window_event_listeners: Default::default(),
+			app_shortcuts: Default::default(),
+			frame_limiter: Default::default(),
 			main_thread: DispatcherMainThreadContext {
 				window_target: event_loop.deref().clone(),
 				web_context,
@@ -1537,6 +2142,7 @@ impl<T: UserEvent> MillenniumWebview<T> {
 		let clipboard_manager_handle = ClipboardManagerWrapper { context: context.clone() };
 
 		Ok(Self {
+			root_context: Arc::new(Mutex::new(context.clone())),
 			context,
 
 			plugins: Default::default(),
@@ -1587,7 +2193,10 @@ impl<T: UserEvent> Runtime<T> for MillenniumWebview<T> {
 	}
 
 	fn handle(&self) -> Self::Handle {
-		MillenniumHandle { context: self.context.clone() }
+		MillenniumHandle {
+			context: self.context.clone(),
+			root_context: Arc::downgrade(&self.root_context)
+		}
 	}
 
 	#[cfg(feature = "global-shortcut")]
@@ -1628,6 +2237,8 @@ impl<T: UserEvent> Runtime<T> for MillenniumWebview<T> {
 
 		let mut items = HashMap::new();
 
+		*self.context.main_thread.tray_context.menu.lock().unwrap() = system_tray.menu.clone();
+
 		#[allow(unused_mut)]
 		let mut tray_builder = SystemTrayBuilder::new(icon.0, system_tray.menu.map(|menu| to_millennium_context_menu(&mut items, menu)));
 
@@ -1661,20 +2272,29 @@ impl<T: UserEvent> Runtime<T> for MillenniumWebview<T> {
 		id
 	}
 
-	#[cfg(target_os = "macos")]
+	fn set_default_window_icon(&mut self, icon: Option<Icon>) {
+		*self.context.default_window_icon.lock().unwrap() = icon;
+	}
+
+	fn set_max_frame_rate(&mut self, max_frame_rate: Option<u32>) {
+		self.context.frame_limiter.lock().unwrap().set_max_frame_rate(max_frame_rate);
+	}
+
 	fn set_activation_policy(&mut self, activation_policy: ActivationPolicy) {
-		self.event_loop.set_activation_policy(match activation_policy {
-			ActivationPolicy::Regular => MillenniumActivationPolicy::Regular,
-			ActivationPolicy::Accessory => MillenniumActivationPolicy::Accessory,
-			ActivationPolicy::Prohibited => MillenniumActivationPolicy::Prohibited,
-			_ => unimplemented!()
-		});
+		#[cfg(target_os = "macos")]
+		if let Some(activation_policy) = ActivationPolicyWrapper::from(activation_policy).0 {
+			self.event_loop.set_activation_policy(activation_policy);
+		}
+		#[cfg(not(target_os = "macos"))]
+		let _ = activation_policy;
 	}
 
 	fn run_iteration<F: FnMut(RunEvent<T>) + 'static>(&mut self, mut callback: F) -> RunIteration {
 		use millennium_webview::application::platform::run_return::EventLoopExtRunReturn;
 		let windows = self.context.main_thread.windows.clone();
 		let webview_id_map = self.context.webview_id_map.clone();
+		let any_window_event_listeners = self.context.any_window_event_listeners.clone();
+		let frame_limiter = self.context.frame_limiter.clone();
 		let web_context = &self.context.main_thread.web_context;
 		let plugins = &mut self.plugins;
 		#[cfg(feature = "system-tray")]
@@ -1707,6 +2327,8 @@ impl<T: UserEvent> Runtime<T> for MillenniumWebview<T> {
 						callback: &mut callback,
 						windows: windows.clone(),
 						webview_id_map: webview_id_map.clone(),
+						any_window_event_listeners: any_window_event_listeners.clone(),
+						frame_limiter: frame_limiter.clone(),
 						#[cfg(feature = "global-shortcut")]
 						global_shortcut_manager: global_shortcut_manager.clone(),
 						#[cfg(feature = "global-shortcut")]
@@ -1731,6 +2353,8 @@ impl<T: UserEvent> Runtime<T> for MillenniumWebview<T> {
 					callback: &mut callback,
 					windows: windows.clone(),
 					webview_id_map: webview_id_map.clone(),
+					any_window_event_listeners: any_window_event_listeners.clone(),
+					frame_limiter: frame_limiter.clone(),
 					#[cfg(feature = "global-shortcut")]
 					global_shortcut_manager: global_shortcut_manager.clone(),
 					#[cfg(feature = "global-shortcut")]
@@ -1750,6 +2374,8 @@ impl<T: UserEvent> Runtime<T> for MillenniumWebview<T> {
 	fn run<F: FnMut(RunEvent<T>) + 'static>(self, mut callback: F) {
 		let windows = self.context.main_thread.windows.clone();
 		let webview_id_map = self.context.webview_id_map.clone();
+		let any_window_event_listeners = self.context.any_window_event_listeners.clone();
+		let frame_limiter = self.context.frame_limiter.clone();
 		let web_context = self.context.main_thread.web_context;
 		let mut plugins = self.plugins;
 
@@ -1775,6 +2401,8 @@ impl<T: UserEvent> Runtime<T> for MillenniumWebview<T> {
 						callback: &mut callback,
 						webview_id_map: webview_id_map.clone(),
 						windows: windows.clone(),
+						any_window_event_listeners: any_window_event_listeners.clone(),
+						frame_limiter: frame_limiter.clone(),
 						#[cfg(feature = "global-shortcut")]
 						global_shortcut_manager: global_shortcut_manager.clone(),
 						#[cfg(feature = "global-shortcut")]
@@ -1798,6 +2426,8 @@ impl<T: UserEvent> Runtime<T> for MillenniumWebview<T> {
 					callback: &mut callback,
 					webview_id_map: webview_id_map.clone(),
 					windows: windows.clone(),
+					any_window_event_listeners: any_window_event_listeners.clone(),
+					frame_limiter: frame_limiter.clone(),
 					#[cfg(feature = "global-shortcut")]
 					global_shortcut_manager: global_shortcut_manager.clone(),
 					#[cfg(feature = "global-shortcut")]
@@ -1817,6 +2447,8 @@ pub struct EventLoopIterationContext<'a, T: UserEvent> {
 	pub callback: &'a mut (dyn FnMut(RunEvent<T>) + 'static),
 	pub webview_id_map: WebviewIdStore,
 	pub windows: Arc<Mutex<HashMap<WebviewId, WindowWrapper>>>,
+	pub any_window_event_listeners: AnyWindowEventListeners,
+	pub frame_limiter: Arc<Mutex<FrameLimiter>>,
 	#[cfg(feature = "global-shortcut")]
 	pub global_shortcut_manager: Arc<Mutex<MillenniumShortcutManager>>,
 	#[cfg(feature = "global-shortcut")]
@@ -1836,6 +2468,8 @@ struct UserMessageContext<'a> {
 	#[cfg(feature = "clipboard")]
 	clipboard_manager: Arc<Mutex<Clipboard>>,
 	windows: Arc<Mutex<HashMap<WebviewId, WindowWrapper>>>,
+	any_window_event_listeners: AnyWindowEventListeners,
+	frame_limiter: Arc<Mutex<FrameLimiter>>,
 	#[cfg(feature = "system-tray")]
 	tray_context: &'a TrayContext
 }
@@ -1854,20 +2488,25 @@ fn handle_user_message<T: UserEvent>(
 		#[cfg(feature = "clipboard")]
 		clipboard_manager,
 		windows,
+		any_window_event_listeners,
+		frame_limiter,
 		#[cfg(feature = "system-tray")]
 		tray_context
 	} = context;
 	match message {
 		Message::Task(task) => task(),
-		Message::Window(id, window_message) => {
-			if let WindowMessage::UpdateMenuItem(item_id, update) = window_message {
-				if let Some(menu_items) = windows
-					.lock()
-					.expect("poisoned webview collection")
-					.get_mut(&id)
-					.map(|w| &mut w.menu_items)
-				{
-					if let Some(menu_items) = menu_items.as_mut() {
+		Message::Window(id, window_message) => match window_message {
+			WindowMessage::UpdateMenuItem(item_id, update) => {
+				if let Some(window) = windows.lock().expect("poisoned webview collection").get_mut(&id) {
+					let model_id = window.menu.as_mut().and_then(|menu| find_menu_item_mut(menu, item_id)).map(|item| {
+						apply_menu_update(item, &update);
+						item.id_str.clone()
+					});
+					if let Some(menu_items) = window.menu_items.as_mut() {
+						if let (MenuUpdate::SetSelected(selected), Some(model_id), Some(menu)) = (&update, model_id, window.menu.as_mut()) {
+							// selecting a radio item deselects the rest of its group
+							set_native_selected(menu_items, menu.set_item_selected(&model_id, *selected));
+						}
 						let item = menu_items.get_mut(&item_id).expect("menu item not found");
 						match update {
 							MenuUpdate::SetEnabled(enabled) => item.set_enabled(enabled),
@@ -1878,11 +2517,77 @@ fn handle_user_message<T: UserEvent>(
 						}
 					}
 				}
-			} else {
+			}
+			WindowMessage::InsertMenuItem(parent, index, item) => {
+				if let Some(window) = windows.lock().expect("poisoned webview collection").get_mut(&id) {
+					let item_id = item.id_str.clone();
+					let menu = window.menu.get_or_insert_with(Menu::new);
+					if menu.insert_item(parent.as_deref(), index, item.clone()) {
+						let anchor = menu_item_anchor(menu, item.id);
+						if !window
+							.menu_items
+							.as_mut()
+							.map_or(false, |menu_items| insert_native_menu_item(menu_items, anchor, &item))
+						{
+							window.refresh_menu();
+						}
+					} else {
+						log::warn!("failed to insert menu item \"{}\": submenu \"{}\" not found", item_id, parent.unwrap_or_default());
+					}
+				}
+			}
+			WindowMessage::RemoveMenuItem(item_id) => {
+				if let Some(window) = windows.lock().expect("poisoned webview collection").get_mut(&id) {
+					if let Some(item) = window.menu.as_mut().and_then(|menu| menu.remove_item(&item_id)) {
+						if let Some(mut native_item) = window.menu_items.as_mut().and_then(|menu_items| menu_items.remove(&item.id)) {
+							native_item.remove();
+						}
+					}
+				}
+			}
+			WindowMessage::SetMinSize(size) => {
+				let size = size.map(|s| SizeWrapper::from(s).0);
+				let window = windows.lock().expect("poisoned webview collection").get_mut(&id).and_then(|window| {
+					window.min_size = size;
+					window.inner.clone()
+				});
+				if let Some(window) = window {
+					window.set_min_inner_size(size);
+				}
+			}
+			WindowMessage::SetMaxSize(size) => {
+				let size = size.map(|s| SizeWrapper::from(s).0);
+				let window = windows.lock().expect("poisoned webview collection").get_mut(&id).and_then(|window| {
+					window.max_size = size;
+					window.inner.clone()
+				});
+				if let Some(window) = window {
+					window.set_max_inner_size(size);
+				}
+			}
+			WindowMessage::MinSize(tx) => {
+				let min_size = windows.lock().expect("poisoned webview collection").get(&id).map(|w| (w.inner.clone(), w.min_size));
+				if let Some((Some(window), size)) = min_size {
+					tx.send(size.map(|s| PhysicalSizeWrapper(s.to_physical(window.scale_factor())).into())).unwrap();
+				}
+			}
+			WindowMessage::MaxSize(tx) => {
+				let max_size = windows.lock().expect("poisoned webview collection").get(&id).map(|w| (w.inner.clone(), w.max_size));
+				if let Some((Some(window), size)) = max_size {
+					tx.send(size.map(|s| PhysicalSizeWrapper(s.to_physical(window.scale_factor())).into())).unwrap();
+				}
+			}
+			WindowMessage::Center => {
+				if let Some(window) = windows.lock().expect("poisoned webview collection").get_mut(&id) {
+					window.center_pending = true;
+				}
+				center_pending_window(&windows, id);
+			}
+			window_message => {
 				let windows_lock = windows.lock().expect("poisoned webview collection");
-				if let Some((Some(window), window_event_listeners, menu_event_listeners)) = windows_lock
+				if let Some((Some(window), window_event_listeners, menu_event_listeners, headless)) = windows_lock
 					.get(&id)
-					.map(|w| (w.inner.clone(), w.window_event_listeners.clone(), w.menu_event_listeners.clone()))
+					.map(|w| (w.inner.clone(), w.window_event_listeners.clone(), w.menu_event_listeners.clone(), w.headless))
 				{
 					drop(windows_lock);
 					match window_message {
@@ -1915,6 +2620,12 @@ fn handle_user_message<T: UserEvent>(
 						WindowMessage::AddMenuEventListener(id, listener) => {
 							menu_event_listeners.lock().unwrap().insert(id, listener);
 						}
+						WindowMessage::RemoveEventListener(id) => {
+							window_event_listeners.lock().unwrap().remove(&id);
+						}
+						WindowMessage::RemoveMenuEventListener(id) => {
+							menu_event_listeners.lock().unwrap().remove(&id);
+						}
 
 						#[cfg(any(debug_assertions, feature = "devtools"))]
 						WindowMessage::OpenDevTools => {
@@ -1923,6 +2634,12 @@ fn handle_user_message<T: UserEvent>(
 							}
 						}
 						#[cfg(any(debug_assertions, feature = "devtools"))]
+						WindowMessage::OpenDevToolsDetached => {
+							if let WindowHandle::Webview(w) = &window {
+								w.open_devtools_detached();
+							}
+						}
+						#[cfg(any(debug_assertions, feature = "devtools"))]
 						WindowMessage::CloseDevTools => {
 							if let WindowHandle::Webview(w) = &window {
 								w.close_devtools();
@@ -1959,7 +2676,11 @@ fn handle_user_message<T: UserEvent>(
 						WindowMessage::IsFullscreen(tx) => tx.send(window.fullscreen().is_some()).unwrap(),
 						WindowMessage::IsMaximized(tx) => tx.send(window.is_maximized()).unwrap(),
 						WindowMessage::IsDecorated(tx) => tx.send(window.is_decorated()).unwrap(),
+						WindowMessage::IsVisibleOnAllWorkspaces(tx) => tx.send(window.is_visible_on_all_workspaces()).unwrap(),
 						WindowMessage::IsResizable(tx) => tx.send(window.is_resizable()).unwrap(),
+						WindowMessage::IsMinimizable(tx) => tx.send(window.is_minimizable()).unwrap(),
+						WindowMessage::IsMaximizable(tx) => tx.send(window.is_maximizable()).unwrap(),
+						WindowMessage::IsClosable(tx) => tx.send(window.is_closable()).unwrap(),
 						WindowMessage::IsVisible(tx) => tx.send(window.is_visible()).unwrap(),
 						WindowMessage::IsMenuVisible(tx) => tx.send(window.is_menu_visible()).unwrap(),
 						WindowMessage::CurrentMonitor(tx) => tx.send(window.current_monitor()).unwrap(),
@@ -1971,17 +2692,26 @@ fn handle_user_message<T: UserEvent>(
 						WindowMessage::Theme(tx) => {
 							#[cfg(any(windows, target_os = "macos"))]
 							tx.send(map_theme(&window.theme())).unwrap();
-							#[cfg(not(any(windows, target_os = "macos")))]
+							#[cfg(target_os = "linux")]
+							tx.send(map_theme(&WindowExtUnix::theme(&*window))).unwrap();
+							#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
 							tx.send(Theme::Light).unwrap();
 						}
+						WindowMessage::AccentColor(tx) => tx.send(window.accent_color()).unwrap(),
+						WindowMessage::AccessibilityPreferences(tx) => tx
+							.send(AccessibilityPreferencesWrapper(window.accessibility_preferences()).into())
+							.unwrap(),
 						// Setters
-						WindowMessage::Center => {
-							let _ = center_window(&window, window.inner_size());
+						WindowMessage::CenterOnMonitor(monitor) => {
+							let _ = center_window_on_monitor(&window, &monitor);
 						}
 						WindowMessage::RequestUserAttention(request_type) => {
 							window.request_user_attention(request_type.map(|r| r.0));
 						}
 						WindowMessage::SetResizable(resizable) => window.set_resizable(resizable),
+						WindowMessage::SetMinimizable(minimizable) => window.set_minimizable(minimizable),
+						WindowMessage::SetMaximizable(maximizable) => window.set_maximizable(maximizable),
+						WindowMessage::SetClosable(closable) => window.set_closable(closable),
 						WindowMessage::SetTitle(title) => window.set_title(&title),
 						WindowMessage::Maximize => window.set_maximized(true),
 						WindowMessage::Unmaximize => window.set_maximized(false),
@@ -1989,20 +2719,22 @@ fn handle_user_message<T: UserEvent>(
 						WindowMessage::Unminimize => window.set_minimized(false),
 						WindowMessage::ShowMenu => window.show_menu(),
 						WindowMessage::HideMenu => window.hide_menu(),
-						WindowMessage::Show => window.set_visible(true),
+						WindowMessage::Show => {
+							// headless windows are never shown
+							if !headless {
+								window.set_visible(true);
+							}
+						}
 						WindowMessage::Hide => window.set_visible(false),
 						WindowMessage::Close => panic!("cannot handle `WindowMessage::Close` on the main thread"),
 						WindowMessage::SetDecorations(decorations) => window.set_decorations(decorations),
 						WindowMessage::SetAlwaysOnTop(always_on_top) => window.set_always_on_top(always_on_top),
+						WindowMessage::SetVisibleOnAllWorkspaces(visible_on_all_workspaces) => window.set_visible_on_all_workspaces(visible_on_all_workspaces),
+						WindowMessage::SetOpacity(opacity) => window.set_opacity(opacity),
 						WindowMessage::SetSize(size) => {
 							window.set_inner_size(SizeWrapper::from(size).0);
 						}
-						WindowMessage::SetMinSize(size) => {
-							window.set_min_inner_size(size.map(|s| SizeWrapper::from(s).0));
-						}
-						WindowMessage::SetMaxSize(size) => {
-							window.set_max_inner_size(size.map(|s| SizeWrapper::from(s).0));
-						}
+						WindowMessage::SetAspectRatio(aspect_ratio) => window.set_aspect_ratio(aspect_ratio),
 						WindowMessage::SetPosition(position) => window.set_outer_position(PositionWrapper::from(position).0),
 						WindowMessage::SetFullscreen(fullscreen) => {
 							if fullscreen {
@@ -2018,10 +2750,25 @@ fn handle_user_message<T: UserEvent>(
 							window.set_window_icon(Some(icon));
 						}
 						#[allow(unused_variables)]
+						WindowMessage::SetTaskbarIcon(icon) => {
+							#[cfg(windows)]
+							window.set_taskbar_icon(Some(icon));
+						}
+						#[allow(unused_variables)]
 						WindowMessage::SetSkipTaskbar(skip) => {
 							#[cfg(any(windows, target_os = "linux"))]
 							window.set_skip_taskbar(skip);
 						}
+						#[allow(unused_variables)]
+						WindowMessage::SetRepresentedFilename(path) => {
+							#[cfg(target_os = "macos")]
+							window.set_represented_filename(&path);
+						}
+						#[allow(unused_variables)]
+						WindowMessage::SetTitleBarColors { caption, text, border } => {
+							#[cfg(windows)]
+							window.set_title_bar_colors(caption, text, border);
+						}
 						WindowMessage::SetCursorGrab(grab) => {
 							let _ = window.set_cursor_grab(grab);
 						}
@@ -2037,16 +2784,35 @@ fn handle_user_message<T: UserEvent>(
 						WindowMessage::DragWindow => {
 							let _ = window.drag_window();
 						}
-						WindowMessage::UpdateMenuItem(_id, _update) => {
+						WindowMessage::StartResizeDrag(direction) => {
+							let _ = window.start_resize_drag(direction.0);
+						}
+						WindowMessage::TitlebarDoubleClick => window.titlebar_double_click(),
+						WindowMessage::SetDragRegions(drag_regions) => {
+							window.set_drag_regions(drag_regions.into_iter().map(|r| RectWrapper::from(r).0).collect());
+						}
+						WindowMessage::StartDrag(paths, icon) => {
+							let _ = window.start_drag(paths, icon);
+						}
+						WindowMessage::UpdateMenuItem(..)
+						| WindowMessage::InsertMenuItem(..)
+						| WindowMessage::RemoveMenuItem(_)
+						| WindowMessage::SetMinSize(_)
+						| WindowMessage::SetMaxSize(_)
+						| WindowMessage::MinSize(_)
+						| WindowMessage::MaxSize(_)
+						| WindowMessage::Center => {
 							// already handled
 						}
 						WindowMessage::RequestRedraw => {
-							window.request_redraw();
+							if frame_limiter.lock().unwrap().request_redraw(id) {
+								window.request_redraw();
+							}
 						}
 					}
 				}
 			}
-		}
+		},
 		Message::Webview(id, webview_message) => match webview_message {
 			WebviewMessage::EvaluateScript(script) => {
 				if let Some(WindowHandle::Webview(webview)) = windows
@@ -2055,10 +2821,8 @@ fn handle_user_message<T: UserEvent>(
 					.get(&id)
 					.and_then(|w| w.inner.as_ref())
 				{
-					#[cfg_attr(not(debug_assertions), allow(unused_variables))]
 					if let Err(e) = webview.evaluate_script(&script) {
-						#[cfg(debug_assertions)]
-						eprintln!("{}", e);
+						log::error!("{}", e);
 					}
 				}
 			}
@@ -2072,35 +2836,162 @@ fn handle_user_message<T: UserEvent>(
 					let _ = webview.print();
 				}
 			}
+			WebviewMessage::PrintToPdf { path, settings, tx } => {
+				if let Some(WindowHandle::Webview(webview)) = windows
+					.lock()
+					.expect("poisoned webview collection")
+					.get(&id)
+					.and_then(|w| w.inner.as_ref())
+				{
+					let tx_ = tx.clone();
+					if let Err(e) = webview.print_to_pdf(path, settings.0, move |result| {
+						let _ = tx_.send(result.map_err(|e| Error::PrintToPdf(e.to_string())));
+					}) {
+						let _ = tx.send(Err(Error::PrintToPdf(e.to_string())));
+					}
+				}
+			}
+			WebviewMessage::TakeSnapshot(tx) => {
+				if let Some(WindowHandle::Webview(webview)) = windows
+					.lock()
+					.expect("poisoned webview collection")
+					.get(&id)
+					.and_then(|w| w.inner.as_ref())
+				{
+					let tx_ = tx.clone();
+					if let Err(e) = webview.take_snapshot(move |result| {
+						let _ = tx_.send(result.map_err(|e| Error::Snapshot(e.to_string())));
+					}) {
+						let _ = tx.send(Err(Error::Snapshot(e.to_string())));
+					}
+				}
+			}
+			WebviewMessage::SetClipboardAccess(enabled) => {
+				if let Some(WindowHandle::Webview(webview)) = windows
+					.lock()
+					.expect("poisoned webview collection")
+					.get(&id)
+					.and_then(|w| w.inner.as_ref())
+				{
+					webview.set_clipboard(enabled);
+				}
+			}
+			WebviewMessage::InsertCss(css, tx) => {
+				if let Some(WindowHandle::Webview(webview)) = windows
+					.lock()
+					.expect("poisoned webview collection")
+					.get(&id)
+					.and_then(|w| w.inner.as_ref())
+				{
+					let _ = tx.send(
+						webview
+							.insert_css(&css)
+							.map(|id| CssId(id.0))
+							.map_err(|e| Error::InsertCss(e.to_string()))
+					);
+				}
+			}
+			WebviewMessage::RemoveCss(css_id) => {
+				if let Some(WindowHandle::Webview(webview)) = windows
+					.lock()
+					.expect("poisoned webview collection")
+					.get(&id)
+					.and_then(|w| w.inner.as_ref())
+				{
+					if let Err(e) = webview.remove_css(MillenniumCssId(css_id.0)) {
+						log::error!("{}", e);
+					}
+				}
+			}
+			WebviewMessage::AddInitScript(js, tx) => {
+				if let Some(WindowHandle::Webview(webview)) = windows
+					.lock()
+					.expect("poisoned webview collection")
+					.get(&id)
+					.and_then(|w| w.inner.as_ref())
+				{
+					let _ = tx.send(
+						webview
+							.add_init_script(&js)
+							.map(|id| InitScriptId(id.0))
+							.map_err(|e| Error::InitScript(e.to_string()))
+					);
+				}
+			}
+			WebviewMessage::RemoveInitScript(script_id) => {
+				if let Some(WindowHandle::Webview(webview)) = windows
+					.lock()
+					.expect("poisoned webview collection")
+					.get(&id)
+					.and_then(|w| w.inner.as_ref())
+				{
+					if let Err(e) = webview.remove_init_script(MillenniumInitScriptId(script_id.0)) {
+						log::error!("{}", e);
+					}
+				}
+			}
+			WebviewMessage::GetMemoryUsage(tx) => {
+				if let Some(WindowHandle::Webview(webview)) = windows
+					.lock()
+					.expect("poisoned webview collection")
+					.get(&id)
+					.and_then(|w| w.inner.as_ref())
+				{
+					let _ = tx.send(webview.memory_usage());
+				}
+			}
+			WebviewMessage::Suspend => set_webview_suspended(&windows, &any_window_event_listeners, id, true),
+			WebviewMessage::Resume => set_webview_suspended(&windows, &any_window_event_listeners, id, false),
 			WebviewMessage::WebviewEvent(event) => {
-				let window_event_listeners = windows
+				let WebviewEvent::Focused(focused) = event;
+				suspend_if_unfocused(&windows, &any_window_event_listeners, id, focused);
+				let window = windows
 					.lock()
 					.expect("poisoned webview collection")
 					.get(&id)
-					.map(|w| w.window_event_listeners.clone());
-				if let Some(window_event_listeners) = window_event_listeners {
+					.map(|w| (w.label.clone(), w.window_event_listeners.clone()));
+				if let Some((label, window_event_listeners)) = window {
 					if let Some(event) = WindowEventWrapper::from(&event).0 {
 						let listeners = window_event_listeners.lock().unwrap();
 						let handlers = listeners.values();
 						for handler in handlers {
 							handler(&event);
 						}
+						emit_any_window_event(&any_window_event_listeners, &label, &event);
 					}
 				}
 			}
 		},
+		Message::EvaluateScriptAll(script) => {
+			for window in windows.lock().expect("poisoned webview collection").values() {
+				// skips plain windows and webviews that have already been torn down
+				if let Some(WindowHandle::Webview(webview)) = &window.inner {
+					if let Err(e) = webview.evaluate_script(&script) {
+						log::error!("{}", e);
+					}
+				}
+			}
+		}
+		#[cfg(target_os = "macos")]
+		Message::SetActivationPolicy(activation_policy) => {
+			if let Some(activation_policy) = ActivationPolicyWrapper::from(activation_policy).0 {
+				event_loop.set_activation_policy_at_runtime(activation_policy);
+			}
+		}
+		Message::SetJumpList(items) => {
+			event_loop.set_jump_list(items.into_iter().map(|item| JumpListItemWrapper::from(item).0).collect());
+		}
 		Message::CreateWebview(window_id, handler) => match handler(event_loop, web_context) {
 			Ok(webview) => {
 				windows.lock().expect("poisoned webview collection").insert(window_id, webview);
 			}
-			#[cfg_attr(not(debug_assertions), allow(unused_variables))]
 			Err(e) => {
-				#[cfg(debug_assertions)]
-				eprintln!("{}", e);
+				log::error!("{}", e);
 			}
 		},
 		Message::CreateWindow(window_id, handler, sender) => {
 			let (label, builder) = handler();
+			let (min_size, max_size) = (builder.window.min_inner_size, builder.window.max_inner_size);
 			if let Ok(window) = builder.build(event_loop) {
 				webview_id_map.insert(window.id(), window_id);
 
@@ -2111,9 +3002,17 @@ fn handle_user_message<T: UserEvent>(
 					WindowWrapper {
 						label,
 						inner: Some(WindowHandle::Window(w.clone())),
+						menu: Default::default(),
 						menu_items: Default::default(),
 						window_event_listeners: Default::default(),
-						menu_event_listeners: Default::default()
+						menu_event_listeners: Default::default(),
+						headless: false,
+						min_size,
+						max_size,
+						suspend_when_unfocused: false,
+						suspended: false,
+						scale_change_scripts: Vec::new(),
+						center_pending: false
 					}
 				);
 				sender.send(Ok(Arc::downgrade(&w))).unwrap();
@@ -2125,7 +3024,18 @@ fn handle_user_message<T: UserEvent>(
 		#[cfg(feature = "system-tray")]
 		Message::Tray(tray_message) => match tray_message {
 			TrayMessage::UpdateItem(menu_id, update) => {
+				let mut menu = tray_context.menu.lock().unwrap();
 				let mut tray = tray_context.items.as_ref().lock().unwrap();
+				if let Some(menu) = menu.as_mut() {
+					if let Some(item) = find_tray_menu_item_mut(menu, menu_id) {
+						apply_menu_update(item, &update);
+						let model_id = item.id_str.clone();
+						if let MenuUpdate::SetSelected(selected) = update {
+							// selecting a radio item deselects the rest of its group
+							set_native_selected(&mut tray, menu.set_item_selected(&model_id, selected));
+						}
+					}
+				}
 				let item = tray.get_mut(&menu_id).expect("menu item not found");
 				match update {
 					MenuUpdate::SetEnabled(enabled) => item.set_enabled(enabled),
@@ -2135,13 +3045,31 @@ fn handle_user_message<T: UserEvent>(
 					MenuUpdate::SetNativeImage(image) => item.set_native_image(NativeImageWrapper::from(image).0)
 				}
 			}
-			TrayMessage::UpdateMenu(menu) => {
-				if let Some(tray) = &*tray_context.tray.lock().unwrap() {
-					let mut items = HashMap::new();
-					tray.lock().unwrap().set_menu(&to_millennium_context_menu(&mut items, menu));
-					*tray_context.items.lock().unwrap() = items;
+			TrayMessage::InsertItem(parent, index, item) => {
+				let mut menu = tray_context.menu.lock().unwrap();
+				let item_id = item.id_str.clone();
+				let menu = menu.get_or_insert_with(SystemTrayMenu::new);
+				if menu.insert_item(parent.as_deref(), index, item.clone()) {
+					let anchor = tray_menu_item_anchor(menu, item.id);
+					if !insert_native_menu_item(&mut tray_context.items.lock().unwrap(), anchor, &item) {
+						refresh_tray_menu(tray_context, menu);
+					}
+				} else {
+					log::warn!("failed to insert tray menu item \"{}\": submenu \"{}\" not found", item_id, parent.unwrap_or_default());
+				}
+			}
+			TrayMessage::RemoveItem(item_id) => {
+				let mut menu = tray_context.menu.lock().unwrap();
+				if let Some(item) = menu.as_mut().and_then(|menu| menu.remove_item(&item_id)) {
+					if let Some(mut native_item) = tray_context.items.lock().unwrap().remove(&item.id) {
+						native_item.remove();
+					}
 				}
 			}
+			TrayMessage::UpdateMenu(menu) => {
+				refresh_tray_menu(tray_context, &menu);
+				tray_context.menu.lock().unwrap().replace(menu);
+			}
 			TrayMessage::UpdateIcon(icon) => {
 				if let Some(tray) = &*tray_context.tray.lock().unwrap() {
 					if let Ok(icon) = TrayIcon::try_from(icon) {
@@ -2159,6 +3087,7 @@ fn handle_user_message<T: UserEvent>(
 				*tray_context.tray.lock().unwrap() = None;
 				tray_context.listeners.lock().unwrap().clear();
 				tray_context.items.lock().unwrap().clear();
+				tray_context.menu.lock().unwrap().take();
 			}
 		},
 		#[cfg(feature = "global-shortcut")]
@@ -2185,6 +3114,8 @@ fn handle_event_loop<T: UserEvent>(
 		callback,
 		webview_id_map,
 		windows,
+		any_window_event_listeners,
+		frame_limiter,
 		#[cfg(feature = "global-shortcut")]
 		global_shortcut_manager,
 		#[cfg(feature = "global-shortcut")]
@@ -2194,8 +3125,15 @@ fn handle_event_loop<T: UserEvent>(
 		#[cfg(feature = "system-tray")]
 		tray_context
 	} = context;
+	if let Event::NewEvents(_) = &event {
+		redraw_deferred_windows(&frame_limiter, &windows);
+	}
 	if *control_flow != ControlFlow::Exit {
-		*control_flow = ControlFlow::Wait;
+		// wake up for the next frame if the frame limiter deferred any redraws
+		*control_flow = match frame_limiter.lock().unwrap().next_frame() {
+			Some(next_frame) => ControlFlow::WaitUntil(next_frame),
+			None => ControlFlow::Wait
+		};
 	}
 
 	match event {
@@ -2215,6 +3153,26 @@ fn handle_event_loop<T: UserEvent>(
 			callback(RunEvent::Exit);
 		}
 
+		Event::Opened { urls } => {
+			callback(RunEvent::Opened { urls });
+		}
+
+		Event::ThemeChanged(theme) => {
+			callback(RunEvent::ThemeChanged(map_theme(&theme)));
+		}
+
+		Event::AccentColorChanged(color) => {
+			callback(RunEvent::AccentColorChanged(color));
+		}
+
+		Event::AccessibilityPreferencesChanged(preferences) => {
+			callback(RunEvent::AccessibilityPreferencesChanged(AccessibilityPreferencesWrapper(preferences).into()));
+		}
+
+		Event::JumpListItemClicked { id } => {
+			callback(RunEvent::JumpListItemClicked { id });
+		}
+
 		#[cfg(feature = "global-shortcut")]
 		Event::GlobalShortcutEvent(accelerator_id) => {
 			for (id, handler) in &*global_shortcut_manager_handle.listeners.lock().unwrap() {
@@ -2248,7 +3206,10 @@ fn handle_event_loop<T: UserEvent>(
 				} else {
 					*webview_id_map.0.lock().unwrap().values().next().unwrap()
 				};
-				windows.lock().unwrap().get(&window_id).unwrap().menu_event_listeners.clone()
+				let mut windows = windows.lock().unwrap();
+				let window = windows.get_mut(&window_id).unwrap();
+				window.activate_menu_item(menu_id.0);
+				window.menu_event_listeners.clone()
 			};
 			let listeners = window_menu_event_listeners.lock().unwrap();
 			let handlers = listeners.values();
@@ -2263,6 +3224,7 @@ fn handle_event_loop<T: UserEvent>(
 			origin: MenuType::ContextMenu,
 			..
 		} => {
+			activate_tray_menu_item(tray_context, menu_id.0);
 			let event = SystemTrayEvent::MenuItemClick(menu_id.0);
 			let listeners = tray_context.listeners.lock().unwrap().clone();
 			for handler in listeners.values() {
@@ -2291,6 +3253,13 @@ fn handle_event_loop<T: UserEvent>(
 		}
 		Event::WindowEvent { event, window_id, .. } => {
 			let window_id = webview_id_map.get(&window_id);
+			// resume before focusing, a suspended webview can't take focus
+			if let MillenniumWindowEvent::Focused(focused) = event {
+				// on Windows the window loses focus to its own webview, so only the webview's focus events may suspend it
+				if cfg!(any(target_os = "linux", target_os = "macos")) || focused {
+					suspend_if_unfocused(&windows, &any_window_event_listeners, window_id, focused);
+				}
+			}
 			// NOTE(amrbashir): we handle this event here instead of `match` statement below
 			// because we want to focus the webview as soon as possible, especially on
 			// windows.
@@ -2314,19 +3283,26 @@ fn handle_event_loop<T: UserEvent>(
 						let label = window.label.clone();
 						let window_event_listeners = window.window_event_listeners.clone();
 						drop(windows_lock);
-						callback(RunEvent::WindowEvent { label, event: event.clone() });
+						callback(RunEvent::WindowEvent { label: label.clone(), event: event.clone() });
 						let listeners = window_event_listeners.lock().unwrap();
 						let handlers = listeners.values();
 						for handler in handlers {
 							handler(&event);
 						}
+						emit_any_window_event(&any_window_event_listeners, &label, &event);
 					}
 				}
 			}
 
 			match event {
 				MillenniumWindowEvent::CloseRequested => {
-					on_close_requested(callback, window_id, windows.clone());
+					on_close_requested(callback, window_id, windows.clone(), &any_window_event_listeners);
+				}
+				MillenniumWindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+					run_scale_change_scripts(&windows, window_id, scale_factor);
+				}
+				MillenniumWindowEvent::Resized(_) | MillenniumWindowEvent::Moved(_) => {
+					center_pending_window(&windows, window_id);
 				}
 				MillenniumWindowEvent::Destroyed => {
 					if windows.lock().unwrap().remove(&window_id).is_some() {
@@ -2363,6 +3339,8 @@ fn handle_event_loop<T: UserEvent>(
 						#[cfg(feature = "clipboard")]
 						clipboard_manager,
 						windows,
+						any_window_event_listeners,
+						frame_limiter,
 						#[cfg(feature = "system-tray")]
 						tray_context
 					},
@@ -2379,10 +3357,89 @@ fn handle_event_loop<T: UserEvent>(
 	it
 }
 
+/// Redraws the windows whose redraws the frame limiter deferred, once their frame is due.
+fn redraw_deferred_windows(frame_limiter: &Arc<Mutex<FrameLimiter>>, windows: &Arc<Mutex<HashMap<WebviewId, WindowWrapper>>>) {
+	let due = frame_limiter.lock().unwrap().take_due();
+	if due.is_empty() {
+		return;
+	}
+	let windows = windows.lock().expect("poisoned webview collection");
+	for id in due {
+		if let Some(window) = windows.get(&id).and_then(|w| w.inner.as_ref()) {
+			window.request_redraw();
+		}
+	}
+}
+
+/// Suspends or resumes the webview of a window, notifying the window's listeners if its state changed.
+fn set_webview_suspended(
+	windows: &Arc<Mutex<HashMap<WebviewId, WindowWrapper>>>,
+	any_window_event_listeners: &AnyWindowEventListeners,
+	window_id: WebviewId,
+	suspended: bool
+) {
+	let mut windows_lock = windows.lock().expect("poisoned webview collection");
+	let window = match windows_lock.get_mut(&window_id) {
+		Some(window) if window.suspended != suspended => window,
+		_ => return
+	};
+	if let Some(WindowHandle::Webview(webview)) = &window.inner {
+		let result = if suspended { webview.suspend() } else { webview.resume() };
+		if let Err(e) = result {
+			log::error!("{}", e);
+			return;
+		}
+		window.suspended = suspended;
+		let label = window.label.clone();
+		let window_event_listeners = window.window_event_listeners.clone();
+		drop(windows_lock);
+		let listeners = window_event_listeners.lock().unwrap();
+		for handler in listeners.values() {
+			handler(&WindowEvent::Suspended(suspended));
+		}
+		emit_any_window_event(any_window_event_listeners, &label, &WindowEvent::Suspended(suspended));
+	}
+}
+
+/// Suspends the webview of a window built with `suspend_when_unfocused` when it loses focus while hidden or minimized,
+/// and resumes it when it regains focus. Visible windows aren't suspended, since suspending hides the webview.
+fn suspend_if_unfocused(
+	windows: &Arc<Mutex<HashMap<WebviewId, WindowWrapper>>>,
+	any_window_event_listeners: &AnyWindowEventListeners,
+	window_id: WebviewId,
+	focused: bool
+) {
+	let suspend = windows
+		.lock()
+		.expect("poisoned webview collection")
+		.get(&window_id)
+		.filter(|w| w.suspend_when_unfocused)
+		.and_then(|w| w.inner.as_ref())
+		.map(|window| !focused && (!window.is_visible() || window.is_minimized()));
+	if let Some(suspend) = suspend {
+		set_webview_suspended(windows, any_window_event_listeners, window_id, suspend);
+	}
+}
+
+/// Evaluates the scale change scripts of a window with the new scale factor in scope as `scaleFactor`.
+fn run_scale_change_scripts(windows: &Arc<Mutex<HashMap<WebviewId, WindowWrapper>>>, window_id: WebviewId, scale_factor: f64) {
+	let windows_lock = windows.lock().expect("poisoned webview collection");
+	if let Some(window) = windows_lock.get(&window_id) {
+		if let Some(WindowHandle::Webview(webview)) = &window.inner {
+			for script in &window.scale_change_scripts {
+				if let Err(e) = webview.evaluate_script(&format!("(function (scaleFactor) {{\n{}\n}})({:?});", script, scale_factor)) {
+					log::error!("{}", e);
+				}
+			}
+		}
+	}
+}
+
 fn on_close_requested<'a, T: UserEvent>(
 	callback: &'a mut (dyn FnMut(RunEvent<T>) + 'static),
 	window_id: WebviewId,
-	windows: Arc<Mutex<HashMap<WebviewId, WindowWrapper>>>
+	windows: Arc<Mutex<HashMap<WebviewId, WindowWrapper>>>,
+	any_window_event_listeners: &AnyWindowEventListeners
 ) {
 	let (tx, rx) = channel();
 	let windows_guard = windows.lock().expect("poisoned webview collection");
@@ -2395,6 +3452,7 @@ fn on_close_requested<'a, T: UserEvent>(
 		for handler in handlers {
 			handler(&WindowEvent::CloseRequested { signal_tx: tx.clone() });
 		}
+		emit_any_window_event(any_window_event_listeners, &label, &WindowEvent::CloseRequested { signal_tx: tx.clone() });
 		callback(RunEvent::WindowEvent {
 			label,
 			event: WindowEvent::CloseRequested { signal_tx: tx }
@@ -2406,22 +3464,135 @@ fn on_close_requested<'a, T: UserEvent>(
 	}
 }
 
+/// Calls the listeners registered with [`MillenniumHandle::on_any_window_event`].
+fn emit_any_window_event(any_window_event_listeners: &AnyWindowEventListeners, label: &str, event: &WindowEvent) {
+	for handler in any_window_event_listeners.lock().unwrap().values() {
+		handler(label, event);
+	}
+}
+
 fn on_window_close(window_id: WebviewId, mut windows: MutexGuard<'_, HashMap<WebviewId, WindowWrapper>>) {
 	if let Some(mut window_wrapper) = windows.get_mut(&window_id) {
 		window_wrapper.inner = None;
 	}
 }
 
+/// Centers the window on its current monitor, or on the primary monitor if the current one isn't known yet.
 pub fn center_window(window: &Window, window_size: MillenniumPhysicalSize<u32>) -> Result<()> {
-	if let Some(monitor) = window.current_monitor() {
-		let screen_size = monitor.size();
-		let monitor_pos = monitor.position();
-		let x = (screen_size.width as i32 - window_size.width as i32) / 2;
-		let y = (screen_size.height as i32 - window_size.height as i32) / 2;
-		window.set_outer_position(MillenniumPhysicalPosition::new(monitor_pos.x + x, monitor_pos.y + y));
-		Ok(())
-	} else {
-		Err(Error::FailedToGetMonitor)
+	let monitor = window.current_monitor().or_else(|| window.primary_monitor());
+	let position = centered_position(monitor.map(|m| (m.position(), m.size())), window_size)?;
+	window.set_outer_position(position);
+	Ok(())
+}
+
+/// Centers the window on the given monitor. The window is sized for the monitor's scale factor, since that's the size it
+/// ends up with once it's moved there.
+fn center_window_on_monitor(window: &Window, monitor: &Monitor) -> Result<()> {
+	let window_size = window.inner_size().to_logical::<f64>(window.scale_factor()).to_physical(monitor.scale_factor);
+	let monitor = (
+		MillenniumPhysicalPosition::new(monitor.position.x, monitor.position.y),
+		MillenniumPhysicalSize::new(monitor.size.width, monitor.size.height)
+	);
+	window.set_outer_position(centered_position(Some(monitor), window_size)?);
+	Ok(())
+}
+
+/// Computes the position that centers a window on a monitor, given as its position and size.
+fn centered_position(
+	monitor: Option<(MillenniumPhysicalPosition<i32>, MillenniumPhysicalSize<u32>)>,
+	window_size: MillenniumPhysicalSize<u32>
+) -> Result<MillenniumPhysicalPosition<i32>> {
+	let (monitor_pos, screen_size) = monitor.ok_or(Error::FailedToGetMonitor)?;
+	let x = (screen_size.width as i32 - window_size.width as i32) / 2;
+	let y = (screen_size.height as i32 - window_size.height as i32) / 2;
+	Ok(MillenniumPhysicalPosition::new(monitor_pos.x + x, monitor_pos.y + y))
+}
+
+/// Centers a window that asked to be centered, if any monitor is known yet. Otherwise, it stays pending until the
+/// next time the window is resized or moved; e.g. on Wayland, there's no monitor until the window is mapped.
+fn center_pending_window(windows: &Arc<Mutex<HashMap<WebviewId, WindowWrapper>>>, window_id: WebviewId) {
+	retry_pending_center(windows, window_id, || {
+		let window = windows.lock().expect("poisoned webview collection").get(&window_id).and_then(|w| w.inner.clone());
+		match window {
+			Some(window) => center_window(&window, window.inner_size()),
+			None => Ok(())
+		}
+	});
+}
+
+/// Calls `center` if the window asked to be centered, keeping the request pending if it fails.
+fn retry_pending_center(windows: &Mutex<HashMap<WebviewId, WindowWrapper>>, window_id: WebviewId, center: impl FnOnce() -> Result<()>) {
+	// clear the flag before centering; moving the window may emit `Moved` before `set_outer_position` returns
+	match windows.lock().expect("poisoned webview collection").get_mut(&window_id) {
+		Some(window) if window.center_pending => window.center_pending = false,
+		_ => return
+	}
+	if center().is_err() {
+		if let Some(window) = windows.lock().expect("poisoned webview collection").get_mut(&window_id) {
+			window.center_pending = true;
+		}
+	}
+}
+
+fn find_menu_item_mut(menu: &mut Menu, id: MenuHash) -> Option<&mut CustomMenuItem> {
+	menu.items.iter_mut().find_map(|item| match item {
+		MenuEntry::CustomItem(c) if c.id == id => Some(c),
+		MenuEntry::Submenu(s) => find_menu_item_mut(&mut s.inner, id),
+		_ => None
+	})
+}
+
+/// Finds a custom item right next to the item `id` in its menu, returning its id and whether `id` comes after it.
+fn menu_item_anchor(menu: &Menu, id: MenuHash) -> Option<(MenuHash, bool)> {
+	let custom_id = |entry: Option<&MenuEntry>| match entry {
+		Some(MenuEntry::CustomItem(c)) => Some(c.id),
+		_ => None
+	};
+	match menu.items.iter().position(|item| matches!(item, MenuEntry::CustomItem(c) if c.id == id)) {
+		Some(index) => custom_id(menu.items.get(index + 1))
+			.map(|next| (next, false))
+			.or_else(|| custom_id(index.checked_sub(1).and_then(|index| menu.items.get(index))).map(|previous| (previous, true))),
+		None => menu.items.iter().find_map(|item| match item {
+			MenuEntry::Submenu(s) => menu_item_anchor(&s.inner, id),
+			_ => None
+		})
+	}
+}
+
+/// Inserts the native item for `item` next to its `anchor` (see [`menu_item_anchor`]), so the rest of the native menu
+/// keeps its state. Returns `false` if there is no native anchor and the menu has to be rebuilt instead.
+fn insert_native_menu_item(menu_items: &mut HashMap<MenuHash, MillenniumCustomMenuItem>, anchor: Option<(MenuHash, bool)>, item: &CustomMenuItem) -> bool {
+	let (anchor, after) = match anchor.and_then(|(id, after)| menu_items.get_mut(&id).map(|anchor| (anchor, after))) {
+		Some(anchor) => anchor,
+		None => return false
+	};
+	let attributes = MenuItemAttributesWrapper::from(item).0;
+	#[allow(unused_mut)]
+	let mut native_item = if after { anchor.insert_after(attributes) } else { anchor.insert_before(attributes) };
+	#[cfg(target_os = "macos")]
+	if let Some(native_image) = item.native_image.clone() {
+		native_item.set_native_image(NativeImageWrapper::from(native_image).0);
+	}
+	menu_items.insert(item.id, native_item);
+	true
+}
+
+fn set_native_selected(menu_items: &mut HashMap<MenuHash, MillenniumCustomMenuItem>, changes: Vec<(MenuHash, bool)>) {
+	for (id, selected) in changes {
+		if let Some(item) = menu_items.get_mut(&id) {
+			item.set_selected(selected);
+		}
+	}
+}
+
+/// Mirrors a menu update on the menu model so the change survives when the native menu is rebuilt.
+fn apply_menu_update(item: &mut CustomMenuItem, update: &MenuUpdate) {
+	match update {
+		MenuUpdate::SetEnabled(enabled) => item.enabled = *enabled,
+		MenuUpdate::SetTitle(title) => item.title = title.clone(),
+		MenuUpdate::SetSelected(selected) => item.selected = *selected,
+		#[cfg(target_os = "macos")]
+		MenuUpdate::SetNativeImage(image) => item.native_image = Some(image.clone())
 	}
 }
 
@@ -2438,7 +3609,9 @@ fn to_millennium_menu(custom_menu_items: &mut HashMap<MenuHash, MillenniumCustom
 				if let Some(native_image) = c.native_image {
 					item.set_native_image(NativeImageWrapper::from(native_image).0);
 				}
-				custom_menu_items.insert(c.id, item);
+				if custom_menu_items.insert(c.id, item).is_some() {
+					log::warn!("menu item \"{}\" has the same id hash as another item; updates will only reach one of them", c.id_str);
+				}
 			}
 			MenuEntry::NativeItem(i) => {
 				millennium_menu.add_native_item(MenuItemWrapper::from(i).0);
@@ -2452,6 +3625,28 @@ fn to_millennium_menu(custom_menu_items: &mut HashMap<MenuHash, MillenniumCustom
 }
 
 fn create_webview<T: UserEvent>(
+	window_id: WebviewId,
+	event_loop: &EventLoopWindowTarget<Message<T>>,
+	web_context: &WebContextStore,
+	context: Context<T>,
+	mut pending: PendingWindow<T, MillenniumWebview<T>>
+) -> Result<WindowWrapper> {
+	let on_created = pending.on_created.take();
+	notify_created(on_created, build_webview(window_id, event_loop, web_context, context, pending))
+}
+
+/// Reports whether the window was created to its [`PendingWindow::on_created`] handler.
+fn notify_created<W>(on_created: Option<WindowCreatedHandler>, result: Result<W>) -> Result<W> {
+	if let Some(on_created) = on_created {
+		on_created(match &result {
+			Ok(_) => Ok(()),
+			Err(e) => Err(Error::CreateWebview(e.to_string().into()))
+		});
+	}
+	result
+}
+
+fn build_webview<T: UserEvent>(
 	window_id: WebviewId,
 	event_loop: &EventLoopWindowTarget<Message<T>>,
 	web_context: &WebContextStore,
@@ -2464,6 +3659,9 @@ fn create_webview<T: UserEvent>(
 		uri_scheme_protocols,
 		mut window_builder,
 		ipc_handler,
+		binary_ipc_handler,
+		permission_handler,
+		request_interceptor,
 		label,
 		url,
 		menu_ids,
@@ -2474,6 +3672,12 @@ fn create_webview<T: UserEvent>(
 	#[cfg(windows)]
 	let proxy = context.proxy.clone();
 
+	if !window_builder.has_icon() {
+		if let Some(icon) = context.default_window_icon.lock().unwrap().clone() {
+			window_builder = window_builder.icon(icon)?;
+		}
+	}
+
 	#[cfg(target_os = "macos")]
 	{
 		window_builder.inner = window_builder.inner.with_fullsize_content_view(true);
@@ -2482,9 +3686,17 @@ fn create_webview<T: UserEvent>(
 	{
 		window_builder.inner = window_builder.inner.with_drag_and_drop(webview_attributes.file_drop_handler_enabled);
 	}
+	let headless = webview_attributes.headless;
+	let suspend_when_unfocused = webview_attributes.suspend_when_unfocused;
+	let scale_change_scripts = webview_attributes.scale_change_scripts.clone();
+	if headless {
+		window_builder.inner = window_builder.inner.with_visible(false);
+	}
 
 	let is_window_transparent = window_builder.inner.window.transparent;
-	let menu_items = if let Some(menu) = window_builder.menu {
+	let (min_size, max_size) = (window_builder.inner.window.min_inner_size, window_builder.inner.window.max_inner_size);
+	let menu = window_builder.menu;
+	let menu_items = if let Some(menu) = menu.clone() {
 		let mut menu_items = HashMap::new();
 		let menu = to_millennium_menu(&mut menu_items, menu);
 		window_builder.inner = window_builder.inner.with_menu(menu);
@@ -2492,13 +3704,14 @@ fn create_webview<T: UserEvent>(
 	} else {
 		None
 	};
-	let window = window_builder.inner.build(event_loop).unwrap();
+	let window = window_builder.inner.build(event_loop).map_err(|_| Error::CreateWindow)?;
 
 	webview_id_map.insert(window.id(), window_id);
 
-	if window_builder.center {
-		let _ = center_window(&window, window.inner_size());
-	}
+	let center_pending = match &window_builder.center_on_monitor {
+		Some(monitor) => center_window_on_monitor(&window, monitor).is_err(),
+		None => window_builder.center && center_window(&window, window.inner_size()).is_err()
+	};
 	let mut webview_builder = WebViewBuilder::new(window)
 		.map_err(|e| Error::CreateWebview(Box::new(e)))?
 		.with_url(&url)
@@ -2507,14 +3720,67 @@ fn create_webview<T: UserEvent>(
 	if webview_attributes.file_drop_handler_enabled {
 		webview_builder = webview_builder.with_file_drop_handler(create_file_drop_handler(&context));
 	}
+	let app_shortcuts = context.app_shortcuts.clone();
+	webview_builder = webview_builder.with_key_press_handler(move |modifiers, key| {
+		let handler = app_shortcuts
+			.lock()
+			.unwrap()
+			.values()
+			.find(|(accelerator, _)| accelerator.matches(modifiers, key))
+			.map(|(_, handler)| handler.clone());
+		// the lock is released before calling the handler so it can (un)register shortcuts
+		match handler {
+			Some(handler) => {
+				handler();
+				true
+			}
+			None => false
+		}
+	});
 	if let Some(handler) = ipc_handler {
-		webview_builder = webview_builder.with_ipc_handler(create_ipc_handler(context, label.clone(), menu_ids, js_event_listeners, handler));
+		webview_builder = webview_builder.with_ipc_handler(create_ipc_handler(context.clone(), label.clone(), menu_ids.clone(), js_event_listeners.clone(), handler));
+	}
+	if let Some(handler) = binary_ipc_handler {
+		webview_builder = webview_builder.with_binary_ipc_handler(create_ipc_handler(context, label.clone(), menu_ids, js_event_listeners, handler));
+	}
+	if let Some(handler) = permission_handler {
+		webview_builder = webview_builder.with_permission_handler(move |origin, kind| PermissionResponseWrapper::from(handler(origin, PermissionKindWrapper::from(kind).0)).0);
+	}
+	if let Some((filters, interceptor)) = request_interceptor {
+		webview_builder = webview_builder.with_request_interceptor(filters, interceptor);
 	}
+	let protocol_timeout = webview_attributes.custom_protocol_timeout;
+	// one cache for all of the window's schemes, the URI keys include the scheme
+	let protocol_cache = webview_attributes
+		.custom_protocol_cache_size
+		.map(|max_size| Arc::new(Mutex::new(protocol::ResponseCache::new(max_size))));
 	for (scheme, protocol) in uri_scheme_protocols {
-		webview_builder = webview_builder.with_custom_protocol(scheme, move |millennium_request| {
-			protocol(&HttpRequestWrapper::from(millennium_request).0)
-				.map(|millennium_response| HttpResponseWrapper::from(millennium_response).0)
-				.map_err(|_| millennium_webview::Error::InitScriptError)
+		let protocol = Arc::new(protocol);
+		let protocol_cache = protocol_cache.clone();
+		webview_builder = webview_builder.with_asynchronous_custom_protocol(scheme, move |millennium_request, responder| {
+			let request = HttpRequestWrapper::from(&millennium_request).0;
+			let (request, miss) = match &protocol_cache {
+				Some(cache) => match protocol::lookup(cache, request) {
+					protocol::CacheLookup::Hit(response) => return responder.respond(Ok(HttpResponseWrapper::from(response).0)),
+					protocol::CacheLookup::Miss(request, miss) => (request, Some((cache.clone(), miss)))
+				},
+				None => (request, None)
+			};
+			let respond: protocol::Respond = Box::new(move |response| {
+				let response = response.map(|response| match miss {
+					Some((cache, miss)) => miss.complete(&cache, response),
+					None => response
+				});
+				responder.respond(
+					response
+						.map(|millennium_response| HttpResponseWrapper::from(millennium_response).0)
+						.map_err(|_| millennium_webview::Error::InitScriptError)
+				);
+			});
+			match protocol_timeout {
+				Some(timeout) => protocol::spawn_with_timeout(&protocol, request, timeout, respond),
+				None => respond(protocol(&request).map_err(|e| e.to_string()))
+			}
 		});
 	}
 
@@ -2547,6 +3813,27 @@ fn create_webview<T: UserEvent>(
 	if webview_attributes.clipboard {
 		webview_builder.webview.clipboard = true;
 	}
+	webview_builder = webview_builder
+		.with_autoplay(webview_attributes.autoplay)
+		.with_default_context_menu(webview_attributes.default_context_menu_enabled)
+		.with_devtools_shortcuts(webview_attributes.devtools_shortcuts_enabled)
+		.with_devtools_position(DevtoolsPositionWrapper::from(webview_attributes.devtools_position).0)
+		.with_headless(headless)
+		.with_scroll_bar_style(ScrollBarStyleWrapper::from(webview_attributes.scroll_bar_style).0)
+		.with_hardware_acceleration(webview_attributes.hardware_acceleration)
+		.with_scroll_bounce(webview_attributes.scroll_bounce)
+		.with_accept_first_mouse(webview_attributes.accept_first_mouse)
+		.with_cookie_persistence(CookiePersistenceWrapper::from(webview_attributes.cookie_persistence).0)
+		.with_block_third_party_cookies(webview_attributes.block_third_party_cookies);
+	if let Some(pinch_zoom) = webview_attributes.pinch_zoom {
+		webview_builder = webview_builder.with_pinch_zoom(pinch_zoom);
+	}
+	if let Some(swipe_navigation) = webview_attributes.swipe_navigation {
+		webview_builder = webview_builder.with_swipe_navigation(swipe_navigation);
+	}
+	for accelerator in &webview_attributes.passthrough_shortcuts {
+		webview_builder = webview_builder.with_passthrough_accelerator(AcceleratorWrapper::from(&accelerator.parse::<Accelerator>()?).0);
+	}
 
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	{
@@ -2588,20 +3875,28 @@ fn create_webview<T: UserEvent>(
 	Ok(WindowWrapper {
 		label,
 		inner: Some(WindowHandle::Webview(Arc::new(webview))),
+		menu,
 		menu_items,
 		window_event_listeners: Default::default(),
-		menu_event_listeners: Default::default()
+		menu_event_listeners: Default::default(),
+		headless,
+		min_size,
+		max_size,
+		suspend_when_unfocused,
+		suspended: false,
+		scale_change_scripts,
+		center_pending
 	})
 }
 
 /// Create a Millennium Webview ipc handler from a Millennium ipc handler.
-fn create_ipc_handler<T: UserEvent>(
+fn create_ipc_handler<T: UserEvent, M: 'static>(
 	context: Context<T>,
 	label: String,
 	menu_ids: Arc<Mutex<HashMap<MenuHash, MenuId>>>,
 	js_event_listeners: Arc<Mutex<HashMap<JsEventListenerKey, HashSet<u64>>>>,
-	handler: WebviewIpcHandler<T, MillenniumWebview<T>>
-) -> Box<IpcHandler> {
+	handler: WebviewIpcHandler<T, MillenniumWebview<T>, M>
+) -> Box<IpcHandler<M>> {
 	Box::new(move |window, request| {
 		let window_id = context.webview_id_map.get(&window.id());
 		handler(
@@ -2642,3 +3937,91 @@ fn create_file_drop_handler<T: UserEvent>(context: &Context<T>) -> Box<FileDropH
 		}
 	})
 }
+
+#[cfg(test)]
+mod tests {
+	use millennium_runtime::menu::Submenu;
+
+	use super::*;
+
+	#[test]
+	fn centers_on_monitor() {
+		let monitor = (MillenniumPhysicalPosition::new(1920, 0), MillenniumPhysicalSize::new(1920, 1080));
+		let position = centered_position(Some(monitor), MillenniumPhysicalSize::new(800, 600)).unwrap();
+		assert_eq!(position, MillenniumPhysicalPosition::new(2480, 240));
+	}
+
+	#[test]
+	fn centering_without_monitor_fails() {
+		assert!(matches!(centered_position(None, MillenniumPhysicalSize::new(800, 600)), Err(Error::FailedToGetMonitor)));
+	}
+
+	#[test]
+	fn defers_centering_until_a_monitor_is_known() {
+		let window = WindowWrapper {
+			label: "main".into(),
+			inner: None,
+			menu: None,
+			menu_items: None,
+			window_event_listeners: Default::default(),
+			menu_event_listeners: Default::default(),
+			headless: false,
+			min_size: None,
+			max_size: None,
+			suspend_when_unfocused: false,
+			suspended: false,
+			scale_change_scripts: Vec::new(),
+			center_pending: true
+		};
+		let windows = Mutex::new(HashMap::from([(0, window)]));
+		let is_pending = || windows.lock().unwrap()[&0].center_pending;
+
+		// no monitor yet, e.g. on Wayland before the window is mapped
+		retry_pending_center(&windows, 0, || centered_position(None, MillenniumPhysicalSize::new(800, 600)).map(|_| ()));
+		assert!(is_pending());
+
+		// the next `Resized` or `Moved` event centers the window
+		let centered = std::cell::Cell::new(0);
+		retry_pending_center(&windows, 0, || {
+			centered.set(centered.get() + 1);
+			Ok(())
+		});
+		assert_eq!(centered.get(), 1);
+		assert!(!is_pending());
+
+		// later events leave the window where it is
+		retry_pending_center(&windows, 0, || {
+			centered.set(centered.get() + 1);
+			Ok(())
+		});
+		assert_eq!(centered.get(), 1);
+	}
+
+	#[test]
+	fn reports_window_build_failure() {
+		let (tx, rx) = std::sync::mpsc::channel();
+		let on_created: WindowCreatedHandler = Box::new(move |result| tx.send(result.map_err(|e| e.to_string())).unwrap());
+		// what `build_webview` returns when the native window fails to build
+		let result = notify_created::<()>(Some(on_created), Err(Error::CreateWindow));
+		assert!(matches!(result, Err(Error::CreateWindow)));
+		assert_eq!(rx.recv().unwrap(), Err("failed to create webview: failed to create window".to_string()));
+	}
+
+	#[test]
+	fn anchors_menu_items_on_adjacent_custom_items() {
+		let item = |id: &str| CustomMenuItem::new(id, id);
+		let hash = |id: &str| item(id).id;
+		let menu = Menu::new()
+			.add_item(item("open"))
+			.add_item(item("save"))
+			.add_native_item(MenuItem::Separator)
+			.add_item(item("quit"))
+			.add_submenu(Submenu::new("Edit", Menu::new().add_native_item(MenuItem::Copy).add_item(item("find"))));
+
+		assert_eq!(menu_item_anchor(&menu, hash("open")), Some((hash("save"), false)));
+		assert_eq!(menu_item_anchor(&menu, hash("save")), Some((hash("open"), true)));
+		assert_eq!(menu_item_anchor(&menu, hash("quit")), None);
+		assert_eq!(menu_item_anchor(&menu, hash("find")), None);
+		assert_eq!(menu_item_anchor(&menu, hash("missing")), None);
+	}
+}
@@ -0,0 +1,443 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Custom protocol handlers executed on their own thread with a timeout, and
+//! an in-memory cache for their responses.
+
+use std::{
+	collections::HashMap,
+	error::Error,
+	sync::{
+		mpsc::{sync_channel, RecvTimeoutError},
+		Arc, Mutex
+	},
+	thread,
+	time::{Duration, Instant}
+};
+
+use millennium_runtime::http::{
+	header::{HeaderMap, HeaderValue, CACHE_CONTROL, ETAG, IF_NONE_MATCH},
+	method::Method,
+	status::StatusCode,
+	Request as HttpRequest, Response as HttpResponse, ResponseBuilder as HttpResponseBuilder
+};
+
+/// Completes a custom protocol request with the handler's response, or the
+/// message of its error.
+pub(crate) type Respond = Box<dyn FnOnce(Result<HttpResponse, String>) + Send>;
+
+/// Runs the protocol handler on a new thread and completes the request with
+/// `respond` once it returns, or after `timeout` with a `504 Gateway Timeout`
+/// response, whichever comes first.
+///
+/// The handler keeps running in the background after a timeout and its
+/// response is discarded once it completes. Each request gets its own threads,
+/// so a handler that never returns doesn't hold up any other request.
+pub(crate) fn spawn_with_timeout<P>(protocol: &Arc<P>, request: HttpRequest, timeout: Duration, respond: Respond)
+where
+	P: Fn(&HttpRequest) -> Result<HttpResponse, Box<dyn Error>> + Send + Sync + 'static
+{
+	let (tx, rx) = sync_channel(1);
+	let protocol = protocol.clone();
+	let handler = thread::Builder::new().name("millennium-protocol".into()).spawn(move || {
+		// `Box<dyn Error>` is not `Send`, so only the error message makes it back
+		let _ = tx.send(protocol(&request).map_err(|e| e.to_string()));
+	});
+	if let Err(e) = handler {
+		return respond(Err(e.to_string()));
+	}
+
+	// waits on its own thread so the webview thread is free as soon as the request is dispatched
+	let watchdog = thread::Builder::new().name("millennium-protocol-timeout".into()).spawn(move || {
+		respond(match rx.recv_timeout(timeout) {
+			Ok(response) => response,
+			Err(RecvTimeoutError::Timeout) => HttpResponseBuilder::new()
+				.status(504)
+				.mimetype("text/plain")
+				.body(b"Gateway Timeout".to_vec())
+				.map_err(|e| e.to_string()),
+			Err(RecvTimeoutError::Disconnected) => Err("custom protocol handler panicked".into())
+		})
+	});
+	if let Err(e) = watchdog {
+		log::error!("failed to spawn custom protocol timeout thread: {}", e);
+	}
+}
+
+/// An in-memory LRU cache of custom protocol responses, keyed by URI and
+/// bounded by the total size of the cached bodies.
+///
+/// Only successful `GET` responses are cached, and only if the handler opts in
+/// with a `Cache-Control: max-age` or an `ETag` header:
+///
+/// - `Cache-Control: no-store` responses are never cached.
+/// - Until its `max-age` expires, a response is served without running the
+///   handler at all.
+/// - Afterwards, or for `Cache-Control: no-cache`, the handler runs with
+///   `If-None-Match` set to the cached `ETag` and may answer
+///   `304 Not Modified` to have the cached response served instead.
+pub(crate) struct ResponseCache {
+	max_size: usize,
+	size: usize,
+	// incremented on every access, so the entry with the lowest `last_used` is the least recently used
+	clock: u64,
+	entries: HashMap<String, CacheEntry>
+}
+
+#[derive(Clone)]
+struct CacheEntry {
+	status: StatusCode,
+	mimetype: Option<String>,
+	headers: HeaderMap<HeaderValue>,
+	body: Vec<u8>,
+	expires: Option<Instant>,
+	last_used: u64
+}
+
+impl CacheEntry {
+	fn is_fresh(&self) -> bool {
+		self.expires.map_or(false, |expires| Instant::now() < expires)
+	}
+
+	fn into_response(self) -> HttpResponse {
+		let mut response = HttpResponse::new(self.body);
+		response.set_status(self.status);
+		response.set_mimetype(self.mimetype);
+		*response.headers_mut() = self.headers;
+		response
+	}
+}
+
+#[derive(Default)]
+struct CacheControl {
+	no_store: bool,
+	no_cache: bool,
+	max_age: Option<u64>
+}
+
+impl CacheControl {
+	fn parse(headers: &HeaderMap<HeaderValue>) -> Self {
+		let mut cache_control = Self::default();
+		let directives = headers
+			.get_all(CACHE_CONTROL)
+			.iter()
+			.filter_map(|value| value.to_str().ok())
+			.flat_map(|value| value.split(','));
+		for directive in directives {
+			let directive = directive.trim().to_ascii_lowercase();
+			match directive.split_once('=') {
+				Some(("max-age", seconds)) => cache_control.max_age = seconds.trim_matches('"').parse().ok(),
+				_ if directive == "no-store" => cache_control.no_store = true,
+				_ if directive == "no-cache" => cache_control.no_cache = true,
+				_ => {}
+			}
+		}
+		cache_control
+	}
+}
+
+impl ResponseCache {
+	pub(crate) fn new(max_size: usize) -> Self {
+		Self {
+			max_size,
+			size: 0,
+			clock: 0,
+			entries: HashMap::new()
+		}
+	}
+
+	fn get(&mut self, uri: &str) -> Option<CacheEntry> {
+		self.clock += 1;
+		let clock = self.clock;
+		self.entries.get_mut(uri).map(|entry| {
+			entry.last_used = clock;
+			entry.clone()
+		})
+	}
+
+	fn remove(&mut self, uri: &str) {
+		if let Some(entry) = self.entries.remove(uri) {
+			self.size -= entry.body.len();
+		}
+	}
+
+	fn insert(&mut self, uri: String, mut entry: CacheEntry) {
+		self.remove(&uri);
+		if entry.body.len() > self.max_size {
+			return;
+		}
+		while self.size + entry.body.len() > self.max_size {
+			let oldest = match self.entries.iter().min_by_key(|(_, entry)| entry.last_used) {
+				Some((uri, _)) => uri.clone(),
+				None => break
+			};
+			self.remove(&oldest);
+		}
+		self.clock += 1;
+		entry.last_used = self.clock;
+		self.size += entry.body.len();
+		self.entries.insert(uri, entry);
+	}
+
+	/// Caches `response` if its headers allow it, replacing any previous response
+	/// for `uri`.
+	fn store(&mut self, uri: String, response: &HttpResponse) {
+		let cache_control = CacheControl::parse(response.headers());
+		let expires = cache_control
+			.max_age
+			.filter(|_| !cache_control.no_cache)
+			.map(|max_age| Instant::now() + Duration::from_secs(max_age));
+		if response.status() != StatusCode::OK || cache_control.no_store || (expires.is_none() && !response.headers().contains_key(ETAG)) {
+			self.remove(&uri);
+			return;
+		}
+		self.insert(
+			uri,
+			CacheEntry {
+				status: response.status(),
+				mimetype: response.mimetype().cloned(),
+				headers: response.headers().clone(),
+				body: response.body().clone(),
+				expires,
+				last_used: 0
+			}
+		);
+	}
+}
+
+/// The result of looking up a request in the [`ResponseCache`].
+pub(crate) enum CacheLookup {
+	/// A fresh response that can be served as is.
+	Hit(HttpResponse),
+	/// The handler needs to run for the request, which carries `If-None-Match`
+	/// if a stale response is cached. Its response is passed to
+	/// [`CacheMiss::complete`].
+	Miss(HttpRequest, Box<CacheMiss>)
+}
+
+/// A request that wasn't answered from the cache, see [`CacheLookup::Miss`].
+pub(crate) struct CacheMiss {
+	uri: Option<String>,
+	cached: Option<CacheEntry>
+}
+
+/// Answers `request` from `cache` if possible, otherwise returns the request
+/// the handler should run for. See [`ResponseCache`].
+///
+/// The cache is not locked while the handler runs, so a slow handler doesn't
+/// hold up requests that can be answered from the cache.
+pub(crate) fn lookup(cache: &Mutex<ResponseCache>, request: HttpRequest) -> CacheLookup {
+	if request.method() != Method::GET {
+		return CacheLookup::Miss(request, Box::new(CacheMiss { uri: None, cached: None }));
+	}
+
+	let uri = request.uri().to_string();
+	let cached = cache.lock().unwrap().get(&uri);
+	let (mut head, body) = request.into_parts();
+	if let Some(cached) = &cached {
+		if cached.is_fresh() {
+			return CacheLookup::Hit(cached.clone().into_response());
+		}
+		if let Some(etag) = cached.headers.get(ETAG) {
+			head.headers.insert(IF_NONE_MATCH, etag.clone());
+		}
+	}
+	CacheLookup::Miss(HttpRequest::new_internal(head, body), Box::new(CacheMiss { uri: Some(uri), cached }))
+}
+
+impl CacheMiss {
+	/// Caches the handler's `response`, returning the response to serve.
+	pub(crate) fn complete(self, cache: &Mutex<ResponseCache>, response: HttpResponse) -> HttpResponse {
+		let uri = match self.uri {
+			Some(uri) => uri,
+			None => return response
+		};
+		match self.cached {
+			Some(mut cached) if response.status() == StatusCode::NOT_MODIFIED => {
+				// the 304 may come with a new lifetime for the cached response
+				let cache_control = CacheControl::parse(response.headers());
+				if let Some(max_age) = cache_control.max_age.filter(|_| !cache_control.no_cache) {
+					cached.expires = Some(Instant::now() + Duration::from_secs(max_age));
+					cache.lock().unwrap().insert(uri, cached.clone());
+				}
+				cached.into_response()
+			}
+			_ => {
+				cache.lock().unwrap().store(uri, &response);
+				response
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		sync::{
+			atomic::{AtomicUsize, Ordering},
+			mpsc::channel,
+			Arc, Mutex
+		},
+		thread::sleep,
+		time::Duration
+	};
+
+	use millennium_runtime::http::{
+		header::IF_NONE_MATCH,
+		method::Method,
+		Request as HttpRequest, RequestParts as HttpRequestParts, Response as HttpResponse, ResponseBuilder as HttpResponseBuilder
+	};
+
+	use super::{lookup, spawn_with_timeout, CacheLookup, ResponseCache};
+
+	fn get(uri: &str) -> HttpRequest {
+		HttpRequest::new_internal(
+			HttpRequestParts {
+				method: Method::GET,
+				uri: uri.into(),
+				headers: Default::default()
+			},
+			Vec::new()
+		)
+	}
+
+	fn cached(cache: &Mutex<ResponseCache>, calls: &AtomicUsize, uri: &str, response: fn(&HttpRequest) -> HttpResponse) -> HttpResponse {
+		match lookup(cache, get(uri)) {
+			CacheLookup::Hit(response) => response,
+			CacheLookup::Miss(request, miss) => {
+				calls.fetch_add(1, Ordering::SeqCst);
+				miss.complete(cache, response(&request))
+			}
+		}
+	}
+
+	fn with_timeout<P>(protocol: P, timeout: Duration) -> Result<HttpResponse, String>
+	where
+		P: Fn(&HttpRequest) -> Result<HttpResponse, Box<dyn std::error::Error>> + Send + Sync + 'static
+	{
+		let (tx, rx) = channel();
+		spawn_with_timeout(&Arc::new(protocol), HttpRequest::default(), timeout, Box::new(move |response| tx.send(response).unwrap()));
+		rx.recv().unwrap()
+	}
+
+	#[test]
+	fn responds_before_timeout() {
+		let response = with_timeout(|_| HttpResponseBuilder::new().status(200).body(b"ok".to_vec()), Duration::from_secs(5)).unwrap();
+		assert_eq!(response.status(), 200);
+		assert_eq!(response.body(), b"ok");
+	}
+
+	#[test]
+	fn times_out_with_504() {
+		let protocol = |_: &HttpRequest| {
+			sleep(Duration::from_millis(500));
+			HttpResponseBuilder::new().status(200).body(Vec::new())
+		};
+		let response = with_timeout(protocol, Duration::from_millis(10)).unwrap();
+		assert_eq!(response.status(), 504);
+	}
+
+	#[test]
+	fn slow_handlers_do_not_block_others() {
+		let protocol = |_: &HttpRequest| {
+			sleep(Duration::from_secs(2));
+			HttpResponseBuilder::new().status(200).body(Vec::new())
+		};
+		for _ in 0..8 {
+			spawn_with_timeout(&Arc::new(protocol), HttpRequest::default(), Duration::from_secs(5), Box::new(|_| {}));
+		}
+		// the handlers above occupy eight threads, which must not delay this one
+		let response = with_timeout(|_| HttpResponseBuilder::new().status(200).body(b"ok".to_vec()), Duration::from_millis(500)).unwrap();
+		assert_eq!(response.status(), 200);
+	}
+
+	#[test]
+	fn forwards_handler_errors() {
+		let error = with_timeout(|_| Err("not found".into()), Duration::from_secs(5)).unwrap_err();
+		assert_eq!(error, "not found");
+	}
+
+	#[test]
+	fn serves_fresh_responses_from_cache() {
+		let cache = Mutex::new(ResponseCache::new(1024));
+		let calls = AtomicUsize::new(0);
+		let response = |_: &HttpRequest| {
+			HttpResponseBuilder::new()
+				.header("Cache-Control", "max-age=60")
+				.body(b"chunk".to_vec())
+				.unwrap()
+		};
+		cached(&cache, &calls, "millennium://localhost/chunk.js", response);
+		let second = cached(&cache, &calls, "millennium://localhost/chunk.js", response);
+		assert_eq!(calls.load(Ordering::SeqCst), 1);
+		assert_eq!(second.body(), b"chunk");
+	}
+
+	#[test]
+	fn revalidates_with_etag() {
+		let cache = Mutex::new(ResponseCache::new(1024));
+		let calls = AtomicUsize::new(0);
+		let response = |request: &HttpRequest| {
+			if request.headers().get(IF_NONE_MATCH).map_or(false, |etag| etag == "\"v1\"") {
+				HttpResponseBuilder::new().status(304).body(Vec::new()).unwrap()
+			} else {
+				HttpResponseBuilder::new().header("ETag", "\"v1\"").body(b"index".to_vec()).unwrap()
+			}
+		};
+		cached(&cache, &calls, "millennium://localhost/index.html", response);
+		let second = cached(&cache, &calls, "millennium://localhost/index.html", response);
+		assert_eq!(calls.load(Ordering::SeqCst), 2);
+		assert_eq!(second.status(), 200);
+		assert_eq!(second.body(), b"index");
+	}
+
+	#[test]
+	fn skips_uncacheable_responses() {
+		let cache = Mutex::new(ResponseCache::new(1024));
+		let calls = AtomicUsize::new(0);
+		let response = |_: &HttpRequest| {
+			HttpResponseBuilder::new()
+				.header("Cache-Control", "no-store, max-age=60")
+				.body(b"secret".to_vec())
+				.unwrap()
+		};
+		cached(&cache, &calls, "millennium://localhost/secret", response);
+		cached(&cache, &calls, "millennium://localhost/secret", response);
+		assert_eq!(calls.load(Ordering::SeqCst), 2);
+	}
+
+	#[test]
+	fn evicts_least_recently_used() {
+		let cache = Mutex::new(ResponseCache::new(10));
+		let calls = AtomicUsize::new(0);
+		let response = |_: &HttpRequest| {
+			HttpResponseBuilder::new()
+				.header("Cache-Control", "max-age=60")
+				.body(b"12345".to_vec())
+				.unwrap()
+		};
+		cached(&cache, &calls, "millennium://localhost/a", response);
+		cached(&cache, &calls, "millennium://localhost/b", response);
+		// touch `a` so `b` is evicted to make room for `c`
+		cached(&cache, &calls, "millennium://localhost/a", response);
+		cached(&cache, &calls, "millennium://localhost/c", response);
+		assert_eq!(calls.load(Ordering::SeqCst), 3);
+		cached(&cache, &calls, "millennium://localhost/a", response);
+		assert_eq!(calls.load(Ordering::SeqCst), 3);
+		cached(&cache, &calls, "millennium://localhost/b", response);
+		assert_eq!(calls.load(Ordering::SeqCst), 4);
+	}
+}
@@ -19,7 +19,10 @@ use std::{
 	sync::{Arc, Mutex}
 };
 
-use millennium_runtime::{menu::MenuHash, UserEvent};
+use millennium_runtime::{
+	menu::{CustomMenuItem, MenuHash, MenuId, MenuItemKind},
+	UserEvent
+};
 pub use millennium_runtime::{
 	menu::{Menu, MenuEntry, MenuItem, MenuUpdate, Submenu, SystemTrayMenu, SystemTrayMenuEntry, SystemTrayMenuItem, TrayHandle},
 	Icon, SystemTrayEvent
@@ -34,7 +37,7 @@ pub use millennium_webview::application::{
 };
 use uuid::Uuid;
 
-use crate::{Error, Message, Result, TrayMessage};
+use crate::{Error, Message, Result, TrayContext, TrayMessage};
 
 pub type SystemTrayEventHandler = Box<dyn Fn(&SystemTrayEvent) + Send>;
 pub type SystemTrayEventListeners = Arc<Mutex<HashMap<Uuid, Arc<SystemTrayEventHandler>>>>;
@@ -74,6 +77,16 @@ impl<T: UserEvent> TrayHandle for SystemTrayHandle<T> {
 			.send_event(Message::Tray(TrayMessage::UpdateItem(id, update)))
 			.map_err(|_| Error::FailedToSendMessage)
 	}
+	fn insert_item(&self, parent: Option<MenuId>, index: Option<usize>, item: CustomMenuItem) -> Result<()> {
+		self.proxy
+			.send_event(Message::Tray(TrayMessage::InsertItem(parent, index, item)))
+			.map_err(|_| Error::FailedToSendMessage)
+	}
+	fn remove_item(&self, id: MenuId) -> Result<()> {
+		self.proxy
+			.send_event(Message::Tray(TrayMessage::RemoveItem(id)))
+			.map_err(|_| Error::FailedToSendMessage)
+	}
 	#[cfg(target_os = "macos")]
 	fn set_icon_as_template(&self, is_template: bool) -> millennium_runtime::Result<()> {
 		self.proxy
@@ -102,7 +115,9 @@ pub fn to_millennium_context_menu(custom_menu_items: &mut HashMap<MenuHash, Mill
 				if let Some(native_image) = c.native_image {
 					item.set_native_image(crate::NativeImageWrapper::from(native_image).0);
 				}
-				custom_menu_items.insert(c.id, item);
+				if custom_menu_items.insert(c.id, item).is_some() {
+					log::warn!("menu item \"{}\" has the same id hash as another item; updates will only reach one of them", c.id_str);
+				}
 			}
 			SystemTrayMenuEntry::NativeItem(i) => {
 				tray_menu.add_native_item(crate::MenuItemWrapper::from(i).0);
@@ -114,3 +129,54 @@ pub fn to_millennium_context_menu(custom_menu_items: &mut HashMap<MenuHash, Mill
 	}
 	tray_menu
 }
+
+pub(crate) fn find_tray_menu_item_mut(menu: &mut SystemTrayMenu, id: MenuHash) -> Option<&mut CustomMenuItem> {
+	menu.items.iter_mut().find_map(|item| match item {
+		SystemTrayMenuEntry::CustomItem(c) if c.id == id => Some(c),
+		SystemTrayMenuEntry::Submenu(s) => find_tray_menu_item_mut(&mut s.inner, id),
+		_ => None
+	})
+}
+
+/// Updates the check marks of the tray menu after a checkbox or radio item was clicked.
+pub(crate) fn activate_tray_menu_item(tray_context: &TrayContext, id: MenuHash) {
+	let mut menu = tray_context.menu.lock().unwrap();
+	let mut items = tray_context.items.lock().unwrap();
+	if let Some(menu) = menu.as_mut() {
+		let item_id = match find_tray_menu_item_mut(menu, id) {
+			Some(item) if item.kind != MenuItemKind::Normal => item.id_str.clone(),
+			_ => return
+		};
+		crate::set_native_selected(&mut items, menu.activate_item(&item_id));
+		// GTK toggles check menu items on its own, so make sure the clicked item matches the model
+		if let (Some(item), Some(native_item)) = (menu.find_item(&item_id), items.get_mut(&id)) {
+			native_item.set_selected(item.selected);
+		}
+	}
+}
+
+/// Finds a custom item right next to the item `id` in its tray menu, returning its id and whether `id` comes after it.
+pub(crate) fn tray_menu_item_anchor(menu: &SystemTrayMenu, id: MenuHash) -> Option<(MenuHash, bool)> {
+	let custom_id = |entry: Option<&SystemTrayMenuEntry>| match entry {
+		Some(SystemTrayMenuEntry::CustomItem(c)) => Some(c.id),
+		_ => None
+	};
+	match menu.items.iter().position(|item| matches!(item, SystemTrayMenuEntry::CustomItem(c) if c.id == id)) {
+		Some(index) => custom_id(menu.items.get(index + 1))
+			.map(|next| (next, false))
+			.or_else(|| custom_id(index.checked_sub(1).and_then(|index| menu.items.get(index))).map(|previous| (previous, true))),
+		None => menu.items.iter().find_map(|item| match item {
+			SystemTrayMenuEntry::Submenu(s) => tray_menu_item_anchor(&s.inner, id),
+			_ => None
+		})
+	}
+}
+
+/// Rebuilds the native tray menu from `menu`.
+pub(crate) fn refresh_tray_menu(tray_context: &TrayContext, menu: &SystemTrayMenu) {
+	if let Some(tray) = &*tray_context.tray.lock().unwrap() {
+		let mut items = HashMap::new();
+		tray.lock().unwrap().set_menu(&to_millennium_context_menu(&mut items, menu.clone()));
+		*tray_context.items.lock().unwrap() = items;
+	}
+}
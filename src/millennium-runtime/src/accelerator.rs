@@ -0,0 +1,386 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing of keyboard accelerators, e.g. `Ctrl+Shift+K`, used by menu items
+//! and global shortcuts.
+
+use std::{fmt, str::FromStr};
+
+/// Key names accepted as the main key of an accelerator, in addition to the
+/// letters `A`-`Z`, digits `0`-`9` and function keys `F1`-`F35`.
+const NAMED_KEYS: &[&str] = &[
+	"`",
+	"BACKQUOTE",
+	"BACKSLASH",
+	"[",
+	"BRACKETLEFT",
+	"]",
+	"BRACKETRIGHT",
+	",",
+	"COMMA",
+	"=",
+	"-",
+	".",
+	"PERIOD",
+	"'",
+	"QUOTE",
+	"\\",
+	";",
+	"SEMICOLON",
+	"/",
+	"SLASH",
+	"BACKSPACE",
+	"CAPSLOCK",
+	"CONTEXTMENU",
+	"ENTER",
+	"SPACE",
+	"TAB",
+	"CONVERT",
+	"DELETE",
+	"END",
+	"HELP",
+	"HOME",
+	"PAGEDOWN",
+	"PAGEUP",
+	"DOWN",
+	"UP",
+	"LEFT",
+	"RIGHT",
+	"NUMLOCK",
+	"NUMADD",
+	"NUMPADADD",
+	"NUMBACKSPACE",
+	"NUMPADBACKSPACE",
+	"NUMCLEAR",
+	"NUMPADCLEAR",
+	"NUMCOMMA",
+	"NUMPADCOMMA",
+	"NUMDIVIDE",
+	"NUMPADDIVIDE",
+	"NUMSUBSTRACT",
+	"NUMPADSUBSTRACT",
+	"NUMENTER",
+	"NUMPADENTER",
+	"ESC",
+	"ESCAPE",
+	"FN",
+	"FNLOCK",
+	"PRINTSCREEN",
+	"SCROLLLOCK",
+	"PAUSE",
+	"VOLUMEMUTE",
+	"VOLUMEDOWN",
+	"VOLUMEUP",
+	"MEDIANEXTTRACK",
+	"MEDIAPREVIOUSTRACK",
+	"MEDIAPLAYPAUSE",
+	"LAUNCHMAIL",
+	"SUSPEND"
+];
+
+/// The modifier keys of an [`Accelerator`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+	/// The `Shift` key.
+	pub shift: bool,
+	/// The `Control` key.
+	pub ctrl: bool,
+	/// The `Alt` key, labeled `Option` on macOS.
+	pub alt: bool,
+	/// The `Super` key: `Command` on macOS, the `Windows` key on Windows.
+	pub super_key: bool
+}
+
+impl Modifiers {
+	/// The platform's primary shortcut modifier: `Command` on macOS and `Ctrl`
+	/// on other platforms. This is what the `CmdOrCtrl` token resolves to.
+	pub const CMD_OR_CTRL: Modifiers = Modifiers {
+		shift: false,
+		ctrl: !cfg!(target_os = "macos"),
+		alt: false,
+		super_key: cfg!(target_os = "macos")
+	};
+
+	/// Sets every modifier that is set in `other`.
+	pub fn insert(&mut self, other: Modifiers) {
+		self.shift |= other.shift;
+		self.ctrl |= other.ctrl;
+		self.alt |= other.alt;
+		self.super_key |= other.super_key;
+	}
+
+	/// Returns `true` if no modifier is set.
+	pub fn is_empty(&self) -> bool {
+		*self == Self::default()
+	}
+}
+
+/// A parsed keyboard accelerator.
+///
+/// # Examples
+///
+/// ```
+/// use millennium_runtime::accelerator::Accelerator;
+///
+/// let accelerator: Accelerator = "Ctrl+Shift+K".parse().unwrap();
+/// assert!(accelerator.mods.ctrl && accelerator.mods.shift);
+/// assert_eq!(accelerator.key, "K");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Accelerator {
+	/// The modifier keys that must be held.
+	pub mods: Modifiers,
+	/// The main key, normalized to uppercase, e.g. `K`, `F12` or `SPACE`.
+	pub key: String
+}
+
+impl FromStr for Accelerator {
+	type Err = AcceleratorParseError;
+
+	fn from_str(accelerator: &str) -> Result<Self, Self::Err> {
+		parse_accelerator(accelerator)
+	}
+}
+
+impl fmt::Display for Accelerator {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for (enabled, name) in [(self.mods.ctrl, "Ctrl"), (self.mods.alt, "Alt"), (self.mods.shift, "Shift"), (self.mods.super_key, "Super")] {
+			if enabled {
+				write!(f, "{}+", name)?;
+			}
+		}
+		write!(f, "{}", self.key)
+	}
+}
+
+/// An error encountered while parsing an accelerator string.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum AcceleratorParseError {
+	/// The accelerator string is empty.
+	#[error("accelerator is empty")]
+	Empty,
+	/// The accelerator contains an empty token, e.g. `Ctrl++K` or `Ctrl+`.
+	#[error("unexpected empty token in accelerator \"{0}\"")]
+	EmptyToken(String),
+	/// The accelerator only contains modifiers.
+	#[error("accelerator \"{0}\" has no main key")]
+	MissingKey(String),
+	/// A modifier or another key follows the main key, e.g. `Ctrl+C+Shift`.
+	#[error("unexpected \"{token}\" after the main key in accelerator \"{accelerator}\"; the main key must come last")]
+	TrailingToken { accelerator: String, token: String },
+	/// The main key isn't a known key name.
+	#[error("unknown key \"{token}\" in accelerator \"{accelerator}\"")]
+	UnknownKey { accelerator: String, token: String }
+}
+
+/// Parses an accelerator string such as `Ctrl+Shift+K` or `Super+F12`.
+///
+/// Tokens are separated by `+` and case-insensitive. Any number of modifiers may
+/// be followed by exactly one main key:
+///
+/// - `Shift`
+/// - `Ctrl` or `Control`
+/// - `Alt`, `Option` or `Opt`
+/// - `Super`, `Cmd`, `Command` or `Meta`
+/// - `CmdOrCtrl` (or `CommandOrControl`): `Command` on macOS and `Ctrl` on other platforms
+///
+/// `CmdOrCtrl` lets a single accelerator string follow each platform's
+/// convention, e.g. `CmdOrCtrl+S` is `⌘S` on macOS and `Ctrl+S` on Windows and
+/// Linux.
+pub fn parse_accelerator(accelerator: &str) -> Result<Accelerator, AcceleratorParseError> {
+	if accelerator.trim().is_empty() {
+		return Err(AcceleratorParseError::Empty);
+	}
+
+	let mut mods = Modifiers::default();
+	let mut key: Option<String> = None;
+	for raw in accelerator.split('+') {
+		let token = raw.trim().to_uppercase();
+		if token.is_empty() {
+			return Err(AcceleratorParseError::EmptyToken(accelerator.into()));
+		}
+		if key.is_some() {
+			return Err(AcceleratorParseError::TrailingToken {
+				accelerator: accelerator.into(),
+				token: raw.trim().into()
+			});
+		}
+
+		match token.as_str() {
+			"SHIFT" => mods.shift = true,
+			"CONTROL" | "CTRL" => mods.ctrl = true,
+			"OPTION" | "OPT" | "ALT" => mods.alt = true,
+			"COMMAND" | "CMD" | "SUPER" | "META" => mods.super_key = true,
+			"COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => mods.insert(Modifiers::CMD_OR_CTRL),
+			_ if is_key(&token) => key = Some(token),
+			_ => {
+				return Err(AcceleratorParseError::UnknownKey {
+					accelerator: accelerator.into(),
+					token: raw.trim().into()
+				});
+			}
+		}
+	}
+
+	match key {
+		Some(key) => Ok(Accelerator { mods, key }),
+		None => Err(AcceleratorParseError::MissingKey(accelerator.into()))
+	}
+}
+
+fn is_key(token: &str) -> bool {
+	let mut chars = token.chars();
+	match (chars.next(), chars.next()) {
+		(Some(c), None) if c.is_ascii_uppercase() || c.is_ascii_digit() => return true,
+		_ => {}
+	}
+	if let Some(n) = token.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+		return (1..=35).contains(&n);
+	}
+	if let Some(n) = token.strip_prefix("NUMPAD").or_else(|| token.strip_prefix("NUM")) {
+		if n.len() == 1 && n.chars().all(|c| c.is_ascii_digit()) {
+			return true;
+		}
+	}
+	NAMED_KEYS.contains(&token)
+}
+
+#[cfg(test)]
+mod tests {
+	use proptest::prelude::*;
+
+	use super::*;
+
+	fn mods(shift: bool, ctrl: bool, alt: bool, super_key: bool) -> Modifiers {
+		Modifiers { shift, ctrl, alt, super_key }
+	}
+
+	#[test]
+	fn parses_modifiers_and_key() {
+		let accelerator = parse_accelerator("Ctrl+Shift+K").unwrap();
+		assert_eq!(accelerator.mods, mods(true, true, false, false));
+		assert_eq!(accelerator.key, "K");
+
+		let accelerator = parse_accelerator("super+ctrl+SHIFT+alt+Up").unwrap();
+		assert_eq!(accelerator.mods, mods(true, true, true, true));
+		assert_eq!(accelerator.key, "UP");
+
+		let accelerator = parse_accelerator(" shift + f12 ").unwrap();
+		assert_eq!(accelerator.mods, mods(true, false, false, false));
+		assert_eq!(accelerator.key, "F12");
+
+		let accelerator = parse_accelerator("5").unwrap();
+		assert!(accelerator.mods.is_empty());
+		assert_eq!(accelerator.key, "5");
+
+		assert_eq!(parse_accelerator("Alt+Numpad5").unwrap().key, "NUMPAD5");
+		assert_eq!(parse_accelerator("Ctrl+Space").unwrap().key, "SPACE");
+		assert_eq!(parse_accelerator("Ctrl+/").unwrap().key, "/");
+	}
+
+	#[test]
+	fn parses_platform_specific_modifier_names() {
+		for name in ["Super", "Cmd", "Command", "Meta"] {
+			let accelerator = parse_accelerator(&format!("{}+Q", name)).unwrap();
+			assert_eq!(accelerator.mods, mods(false, false, false, true), "{}", name);
+		}
+		for name in ["Alt", "Option", "Opt"] {
+			let accelerator = parse_accelerator(&format!("{}+Q", name)).unwrap();
+			assert_eq!(accelerator.mods, mods(false, false, true, false), "{}", name);
+		}
+
+		for name in ["CmdOrCtrl", "CommandOrControl", "cmdorcontrol", "CommandOrCtrl"] {
+			let accelerator = parse_accelerator(&format!("{}+S", name)).unwrap();
+			#[cfg(target_os = "macos")]
+			assert_eq!(accelerator.mods, mods(false, false, false, true), "{}", name);
+			#[cfg(not(target_os = "macos"))]
+			assert_eq!(accelerator.mods, mods(false, true, false, false), "{}", name);
+			assert_eq!(accelerator.mods, Modifiers::CMD_OR_CTRL);
+		}
+
+		// combining `CmdOrCtrl` with the modifier it resolves to is harmless
+		let accelerator = parse_accelerator("CmdOrCtrl+Ctrl+Option+S").unwrap();
+		#[cfg(target_os = "macos")]
+		assert_eq!(accelerator.mods, mods(false, true, true, true));
+		#[cfg(not(target_os = "macos"))]
+		assert_eq!(accelerator.mods, mods(false, true, true, false));
+	}
+
+	#[test]
+	fn rejects_invalid_accelerators() {
+		assert_eq!(parse_accelerator(""), Err(AcceleratorParseError::Empty));
+		assert_eq!(parse_accelerator("+G"), Err(AcceleratorParseError::EmptyToken("+G".into())));
+		assert_eq!(parse_accelerator("Ctrl+"), Err(AcceleratorParseError::EmptyToken("Ctrl+".into())));
+		assert_eq!(parse_accelerator("Ctrl+Shift"), Err(AcceleratorParseError::MissingKey("Ctrl+Shift".into())));
+		assert_eq!(
+			parse_accelerator("Ctrl+C+Shift"),
+			Err(AcceleratorParseError::TrailingToken {
+				accelerator: "Ctrl+C+Shift".into(),
+				token: "Shift".into()
+			})
+		);
+		assert_eq!(
+			parse_accelerator("SHGSH+G"),
+			Err(AcceleratorParseError::UnknownKey {
+				accelerator: "SHGSH+G".into(),
+				token: "SHGSH".into()
+			})
+		);
+		assert!(parse_accelerator("F36").is_err());
+	}
+
+	#[test]
+	fn displays_canonical_form() {
+		assert_eq!(parse_accelerator("shift+super+alt+ctrl+k").unwrap().to_string(), "Ctrl+Alt+Shift+Super+K");
+		assert_eq!(parse_accelerator("F5").unwrap().to_string(), "F5");
+	}
+
+	proptest! {
+		#![proptest_config(ProptestConfig::with_cases(10000))]
+
+		#[test]
+		fn never_panics_on_arbitrary_input(accelerator in "\\PC*") {
+			let _ = parse_accelerator(&accelerator);
+		}
+
+		// Joins tokens that look like accelerators so the parser gets past the first token most of the time.
+		#[test]
+		fn never_panics_on_token_soup(
+			tokens in prop::collection::vec(
+				prop_oneof![
+					Just("Ctrl".to_string()),
+					Just("Shift".to_string()),
+					Just("CmdOrCtrl".to_string()),
+					Just("F12".to_string()),
+					Just("Numpad5".to_string()),
+					Just(String::new()),
+					Just(" ".to_string()),
+					"\\PC{0,4}"
+				],
+				0..6
+			)
+		) {
+			let _ = parse_accelerator(&tokens.join("+"));
+		}
+
+		#[test]
+		fn display_round_trips(accelerator in "((Shift|Ctrl|Alt|Super|CmdOrCtrl)\\+){0,4}([A-Z0-9]|F[1-9]|F1[0-9]|Space|Up|Numpad[0-9])") {
+			let parsed = parse_accelerator(&accelerator).unwrap();
+			prop_assert_eq!(parse_accelerator(&parsed.to_string()), Ok(parsed));
+		}
+	}
+}
@@ -0,0 +1,206 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A custom protocol handler that serves a directory from the filesystem.
+
+use std::{
+	fmt::Write,
+	fs,
+	path::{Component, Path, PathBuf}
+};
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use url::Url;
+
+use super::{MimeType, Request, Response, ResponseBuilder};
+
+type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Characters escaped in the links of a directory listing: everything but the unreserved characters of RFC 3986.
+const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// Creates a custom protocol handler that serves the files under `root`, reading them from disk on every request so
+/// edits show up on the next reload.
+///
+/// The URI path is resolved relative to `root`. Directories are served with their `index.html` if they have one, or
+/// with a listing of their contents otherwise. Missing files are answered with `404 Not Found`, and paths that would
+/// escape `root`, through `..` or a symlink, with `403 Forbidden`.
+///
+/// This is meant for development; use the embedded assets for production builds.
+pub fn directory_protocol<P: Into<PathBuf>>(root: P) -> impl Fn(&Request) -> Result<Response> + Send + Sync + 'static {
+	let root = root.into();
+	move |request| serve(&root, request.uri())
+}
+
+fn serve(root: &Path, uri: &str) -> Result<Response> {
+	let url = Url::parse(uri)?;
+	let segments = match decode_path(url.path()) {
+		Some(segments) => segments,
+		None => return status(403, "Forbidden")
+	};
+
+	let mut path = root.to_path_buf();
+	path.extend(&segments);
+	// symlinks inside `root` may still point outside of it
+	let (root, path) = match (root.canonicalize(), path.canonicalize()) {
+		(Ok(root), Ok(path)) => (root, path),
+		_ => return status(404, "Not Found")
+	};
+	if !path.starts_with(&root) {
+		return status(403, "Forbidden");
+	}
+
+	if path.is_dir() {
+		// relative links in the page must resolve inside the directory
+		if !url.path().ends_with('/') {
+			return ResponseBuilder::new()
+				.status(301)
+				.header("Location", format!("{}/", url.path()))
+				.body(Vec::new());
+		}
+		let index = path.join("index.html");
+		if index.is_file() {
+			return file(&index);
+		}
+		return listing(&path, url.path());
+	}
+	file(&path)
+}
+
+/// Splits the URI path into decoded segments, or `None` if a segment would leave the directory it's in.
+fn decode_path(path: &str) -> Option<Vec<String>> {
+	let mut segments: Vec<String> = Vec::new();
+	for segment in path.split('/') {
+		let segment = percent_decode_str(segment).decode_utf8().ok()?.into_owned();
+		// a decoded segment must be a single plain path component; this rejects `%2F`, `%5C` and drive prefixes
+		let mut components = Path::new(&segment).components();
+		match (components.next(), components.next()) {
+			(None, _) | (Some(Component::CurDir), None) => {}
+			(Some(Component::ParentDir), None) => {
+				segments.pop()?;
+			}
+			(Some(Component::Normal(name)), None) if name == segment.as_str() => segments.push(segment),
+			_ => return None
+		}
+	}
+	Some(segments)
+}
+
+fn html_escape(text: &str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn status(status: u16, message: &str) -> Result<Response> {
+	ResponseBuilder::new().status(status).mimetype("text/plain").body(message.as_bytes().to_vec())
+}
+
+fn file(path: &Path) -> Result<Response> {
+	match fs::read(path) {
+		Ok(content) => {
+			let mimetype = MimeType::parse(&content, &path.to_string_lossy());
+			ResponseBuilder::new().mimetype(&mimetype).body(content)
+		}
+		Err(_) => status(404, "Not Found")
+	}
+}
+
+fn listing(path: &Path, url_path: &str) -> Result<Response> {
+	let mut entries = fs::read_dir(path)?
+		.flatten()
+		.map(|entry| {
+			let name = entry.file_name().to_string_lossy().into_owned();
+			let is_dir = entry.file_type().map(|file_type| file_type.is_dir()).unwrap_or(false);
+			(!is_dir, name)
+		})
+		.collect::<Vec<_>>();
+	// directories first, then by name
+	entries.sort();
+
+	let title = html_escape(&percent_decode_str(url_path).decode_utf8_lossy());
+	let mut html = format!("<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{0}</title></head><body><h1>{0}</h1><ul>", title);
+	if url_path != "/" {
+		html.push_str("<li><a href=\"../\">../</a></li>");
+	}
+	for (is_file, name) in entries {
+		let suffix = if is_file { "" } else { "/" };
+		let _ = write!(html, "<li><a href=\"{}{2}\">{}{2}</a></li>", utf8_percent_encode(&name, SEGMENT), html_escape(&name), suffix);
+	}
+	html.push_str("</ul></body></html>");
+	ResponseBuilder::new().mimetype("text/html").body(html.into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use super::{decode_path, serve};
+
+	#[test]
+	fn decodes_path_segments() {
+		assert_eq!(decode_path("/a/b%20c/./d.js").unwrap(), vec!["a", "b c", "d.js"]);
+		assert_eq!(decode_path("/a/../b").unwrap(), vec!["b"]);
+		assert!(decode_path("/").unwrap().is_empty());
+	}
+
+	#[test]
+	fn rejects_path_traversal() {
+		assert!(decode_path("/../secret").is_none());
+		assert!(decode_path("/a/%2E%2E/%2E%2E/secret").is_none());
+		assert!(decode_path("/a%2F..%2F..%2Fsecret").is_none());
+	}
+
+	#[test]
+	fn serves_files_with_their_mime_type() {
+		let root = tempfile::tempdir().unwrap();
+		fs::write(root.path().join("app.js"), "console.log('hello')").unwrap();
+		fs::create_dir(root.path().join("page")).unwrap();
+		fs::write(root.path().join("page").join("index.html"), "<html></html>").unwrap();
+
+		let response = serve(root.path(), "dev://localhost/app.js?v=1").unwrap();
+		assert_eq!(response.status(), 200);
+		assert_eq!(response.mimetype().map(String::as_str), Some("text/javascript"));
+		assert_eq!(response.body(), b"console.log('hello')");
+
+		let response = serve(root.path(), "dev://localhost/page/").unwrap();
+		assert_eq!(response.status(), 200);
+		assert_eq!(response.mimetype().map(String::as_str), Some("text/html"));
+	}
+
+	#[test]
+	fn answers_missing_files_with_not_found() {
+		let root = tempfile::tempdir().unwrap();
+
+		let response = serve(root.path(), "dev://localhost/missing.js").unwrap();
+		assert_eq!(response.status(), 404);
+	}
+
+	#[test]
+	fn forbids_paths_outside_of_the_root() {
+		let parent = tempfile::tempdir().unwrap();
+		fs::write(parent.path().join("secret.txt"), "secret").unwrap();
+		let root = parent.path().join("root");
+		fs::create_dir(&root).unwrap();
+
+		// the URL parser resolves `%2E%2E` segments, so this looks for `secret.txt` inside the root
+		assert_eq!(serve(&root, "dev://localhost/%2E%2E/secret.txt").unwrap().status(), 404);
+		assert_eq!(serve(&root, "dev://localhost/..%2Fsecret.txt").unwrap().status(), 403);
+		#[cfg(unix)]
+		{
+			std::os::unix::fs::symlink(parent.path().join("secret.txt"), root.join("link.txt")).unwrap();
+			assert_eq!(serve(&root, "dev://localhost/link.txt").unwrap().status(), 403);
+		}
+	}
+}
@@ -22,15 +22,20 @@ const MIMETYPE_PLAIN: &str = "text/plain";
 pub enum MimeType {
 	Css,
 	Csv,
+	Gif,
 	Html,
 	Ico,
+	Jpeg,
 	Js,
 	Json,
 	Jsonld,
 	OctetStream,
+	Pdf,
+	Png,
 	Rtf,
 	Svg,
-	Mp4
+	Mp4,
+	Webp
 }
 
 impl std::fmt::Display for MimeType {
@@ -38,15 +43,20 @@ impl std::fmt::Display for MimeType {
 		let mime = match self {
 			MimeType::Css => "text/css",
 			MimeType::Csv => "text/csv",
+			MimeType::Gif => "image/gif",
 			MimeType::Html => "text/html",
 			MimeType::Ico => "image/vnd.microsoft.icon",
+			MimeType::Jpeg => "image/jpeg",
 			MimeType::Js => "text/javascript",
 			MimeType::Json => "application/json",
 			MimeType::Jsonld => "application/ld+json",
 			MimeType::OctetStream => "application/octet-stream",
+			MimeType::Pdf => "application/pdf",
+			MimeType::Png => "image/png",
 			MimeType::Rtf => "application/rtf",
 			MimeType::Svg => "image/svg+xml",
-			MimeType::Mp4 => "video/mp4"
+			MimeType::Mp4 => "video/mp4",
+			MimeType::Webp => "image/webp"
 		};
 		write!(f, "{}", mime)
 	}
@@ -61,15 +71,20 @@ impl MimeType {
 			Some("bin") => Self::OctetStream,
 			Some("css") => Self::Css,
 			Some("csv") => Self::Csv,
+			Some("gif") => Self::Gif,
 			Some("html") => Self::Html,
 			Some("ico") => Self::Ico,
+			Some("jpg") | Some("jpeg") => Self::Jpeg,
 			Some("js") => Self::Js,
 			Some("json") => Self::Json,
 			Some("jsonld") => Self::Jsonld,
 			Some("mjs") => Self::Js,
+			Some("pdf") => Self::Pdf,
+			Some("png") => Self::Png,
 			Some("rtf") => Self::Rtf,
 			Some("svg") => Self::Svg,
 			Some("mp4") => Self::Mp4,
+			Some("webp") => Self::Webp,
 			// Assume HTML when a TLD is found for eg. `millennium:://pyke.io` | `millennium://example.com`
 			Some(_) => Self::Html,
 			// https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types/Common_types
@@ -78,6 +93,42 @@ impl MimeType {
 		}
 	}
 
+	/// Sniffs the mimetype from the magic number or leading markup of `content`.
+	///
+	/// Recognizes PNG, JPEG, GIF, WebP, ICO, PDF, MP4, HTML, SVG and JSON.
+	/// Returns `None` if the content doesn't match any of them, in which case the
+	/// caller should fall back to [`MimeType::parse_from_uri`].
+	pub fn parse_from_bytes(content: &[u8]) -> Option<MimeType> {
+		// skip the UTF-8 BOM so text formats are recognized
+		let content = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(content);
+		let mime = match infer::get(content).map(|info| info.mime_type()) {
+			Some("image/png") => Some(Self::Png),
+			Some("image/jpeg") => Some(Self::Jpeg),
+			Some("image/gif") => Some(Self::Gif),
+			Some("image/webp") => Some(Self::Webp),
+			Some("image/vnd.microsoft.icon") => Some(Self::Ico),
+			Some("application/pdf") => Some(Self::Pdf),
+			Some("video/mp4") => Some(Self::Mp4),
+			Some("text/html") => Some(Self::Html),
+			_ => None
+		};
+		if mime.is_some() {
+			return mime;
+		}
+
+		// `infer` reports SVG as plain XML and doesn't know JSON at all
+		let text = &content[content.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(content.len())..];
+		let head = String::from_utf8_lossy(&text[..text.len().min(512)]).to_ascii_lowercase();
+		if head.starts_with("<svg") || (head.starts_with("<?xml") && head.contains("<svg")) {
+			return Some(Self::Svg);
+		}
+		if (head.starts_with('{') || head.starts_with('[')) && serde_json::from_slice::<serde::de::IgnoredAny>(text).is_ok() {
+			return Some(Self::Json);
+		}
+
+		None
+	}
+
 	/// infer mimetype from content (or) URI if needed.
 	pub fn parse(content: &[u8], uri: &str) -> String {
 		let mime = if uri.ends_with(".svg") {
@@ -137,4 +188,27 @@ mod tests {
 		let custom_scheme = MimeType::parse_from_uri("millennium://pyke.io").to_string();
 		assert_eq!(custom_scheme, String::from("text/html"));
 	}
+
+	#[test]
+	fn should_parse_mimetype_from_bytes() {
+		let parse = |content: &[u8]| MimeType::parse_from_bytes(content).map(|m| m.to_string());
+
+		assert_eq!(parse(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").as_deref(), Some("image/png"));
+		assert_eq!(parse(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10]).as_deref(), Some("image/jpeg"));
+		assert_eq!(parse(b"GIF89a\x01\0\x01\0").as_deref(), Some("image/gif"));
+		assert_eq!(parse(b"RIFF\x24\0\0\0WEBPVP8 ").as_deref(), Some("image/webp"));
+		assert_eq!(parse(b"%PDF-1.7\n").as_deref(), Some("application/pdf"));
+		assert_eq!(parse(&[0x00, 0x00, 0x01, 0x00, 0x01, 0x00]).as_deref(), Some("image/vnd.microsoft.icon"));
+		assert_eq!(parse(b"\xEF\xBB\xBF  <!DOCTYPE html><html></html>").as_deref(), Some("text/html"));
+		assert_eq!(parse(b"<html lang=\"en\"></html>").as_deref(), Some("text/html"));
+		assert_eq!(parse(b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>").as_deref(), Some("image/svg+xml"));
+		assert_eq!(
+			parse(b"<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>").as_deref(),
+			Some("image/svg+xml")
+		);
+		assert_eq!(parse(b"\n{ \"hello\": [1, 2, 3] }").as_deref(), Some("application/json"));
+		assert_eq!(parse(b"{ not json").as_deref(), None);
+		assert_eq!(parse(b"plain text").as_deref(), None);
+		assert_eq!(parse(b"").as_deref(), None);
+	}
 }
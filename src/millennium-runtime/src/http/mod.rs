@@ -15,6 +15,7 @@
 // limitations under the License.
 
 // custom Millennium Webview types
+mod directory;
 mod mime_type;
 mod request;
 mod response;
@@ -25,6 +26,7 @@ pub use http::{header, method, status, uri::InvalidUri, version, Uri};
 pub use http_range::HttpRange;
 
 pub use self::{
+	directory::directory_protocol,
 	mime_type::MimeType,
 	request::{Request, RequestParts},
 	response::{Builder as ResponseBuilder, Response, ResponseParts}
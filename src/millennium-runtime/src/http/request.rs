@@ -14,8 +14,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#![allow(clippy::tabs_in_doc_comments)]
+
 use std::fmt;
 
+use serde::de::DeserializeOwned;
+
 use super::{
 	header::{HeaderMap, HeaderValue},
 	method::Method
@@ -90,6 +94,26 @@ impl Request {
 		&self.body
 	}
 
+	/// Deserializes the HTTP body as JSON.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use millennium_runtime::http::*;
+	///
+	/// #[derive(serde::Deserialize)]
+	/// struct Payload {
+	/// 	message: String
+	/// }
+	///
+	/// let request = Request::new(br#"{ "message": "hello!" }"#.to_vec());
+	/// let payload: Payload = request.json().unwrap();
+	/// assert_eq!(payload.message, "hello!");
+	/// ```
+	pub fn json<T: DeserializeOwned>(&self) -> crate::Result<T> {
+		serde_json::from_slice(&self.body).map_err(Into::into)
+	}
+
 	/// Consumes the request returning the head and body RequestParts.
 	///
 	/// ## Stability
@@ -18,6 +18,8 @@
 
 use std::fmt;
 
+use serde::Serialize;
+
 use super::{
 	header::{HeaderMap, HeaderName, HeaderValue},
 	status::StatusCode,
@@ -285,6 +287,68 @@ impl Builder {
 		self.inner.map(move |head| Response { head, body })
 	}
 
+	/// "Consumes" this builder, serializing `value` as the JSON body of the
+	/// constructed `Response` and setting its mimetype to `application/json`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use millennium_runtime::http::*;
+	///
+	/// #[derive(serde::Serialize)]
+	/// struct Payload {
+	/// 	message: String
+	/// }
+	///
+	/// let response = ResponseBuilder::new()
+	/// 	.status(200)
+	/// 	.json(&Payload { message: "hello!".into() })
+	/// 	.unwrap();
+	/// ```
+	pub fn json<T: Serialize + ?Sized>(self, value: &T) -> Result<Response> {
+		let body = serde_json::to_vec(value)?;
+		self.mimetype("application/json").body(body)
+	}
+
+	/// "Consumes" this builder, compressing `body` with the best encoding
+	/// accepted by the request's `Accept-Encoding` header value and setting
+	/// the `Content-Encoding` header accordingly.
+	///
+	/// `gzip` is preferred over `deflate`. If the client accepts neither, the body
+	/// is sent uncompressed.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Headers cannot be changed, so the body is always sent uncompressed.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use millennium_runtime::http::*;
+	///
+	/// let request = Request::new(Vec::new());
+	/// let accept_encoding = request.headers().get(header::ACCEPT_ENCODING).and_then(|v| v.to_str().ok()).unwrap_or_default();
+	/// let response = ResponseBuilder::new()
+	/// 	.mimetype("text/html")
+	/// 	.compressed_body(b"<h1>hello!</h1>".to_vec(), accept_encoding)
+	/// 	.unwrap();
+	/// ```
+	pub fn compressed_body(self, body: Vec<u8>, accept_encoding: &str) -> Result<Response> {
+		#[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
+		{
+			let _ = accept_encoding;
+			self.body(body)
+		}
+		#[cfg(not(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd")))]
+		match ContentEncoding::negotiate(accept_encoding) {
+			Some(encoding) => {
+				let body = encoding.encode(&body)?;
+				self.header("Content-Encoding", encoding.as_str()).header("Vary", "Accept-Encoding").body(body)
+			}
+			None => self.body(body)
+		}
+	}
+
 	// private
 
 	fn and_then<F>(self, func: F) -> Self
@@ -295,9 +359,130 @@ impl Builder {
 	}
 }
 
+/// A `Content-Encoding` supported by [`Builder::compressed_body`].
+#[cfg_attr(
+	any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"),
+	allow(dead_code)
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContentEncoding {
+	Gzip,
+	Deflate
+}
+
+#[cfg_attr(
+	any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"),
+	allow(dead_code)
+)]
+impl ContentEncoding {
+	/// Picks the preferred encoding from an `Accept-Encoding` header value,
+	/// honoring quality values. Ties are broken in favor of `gzip`.
+	fn negotiate(accept_encoding: &str) -> Option<Self> {
+		let mut best: Option<(Self, f32)> = None;
+		for directive in accept_encoding.split(',') {
+			let mut params = directive.split(';').map(str::trim);
+			let name = params.next().unwrap_or_default().to_ascii_lowercase();
+			let quality = params
+				.find_map(|p| p.strip_prefix("q="))
+				.map(|q| q.parse::<f32>().unwrap_or(0.0))
+				.unwrap_or(1.0);
+			if quality <= 0.0 {
+				continue;
+			}
+
+			let encoding = match name.as_str() {
+				"gzip" | "x-gzip" | "*" => Self::Gzip,
+				"deflate" => Self::Deflate,
+				_ => continue
+			};
+			match best {
+				Some((current, q)) if q > quality || (q == quality && current == Self::Gzip) => {}
+				_ => best = Some((encoding, quality))
+			}
+		}
+		best.map(|(encoding, _)| encoding)
+	}
+
+	fn as_str(self) -> &'static str {
+		match self {
+			Self::Gzip => "gzip",
+			Self::Deflate => "deflate"
+		}
+	}
+
+	fn encode(self, body: &[u8]) -> std::io::Result<Vec<u8>> {
+		use std::io::Write;
+
+		use flate2::{
+			write::{GzEncoder, ZlibEncoder},
+			Compression
+		};
+
+		match self {
+			Self::Gzip => {
+				let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+				encoder.write_all(body)?;
+				encoder.finish()
+			}
+			// HTTP's `deflate` is the zlib format, not a raw deflate stream.
+			Self::Deflate => {
+				let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+				encoder.write_all(body)?;
+				encoder.finish()
+			}
+		}
+	}
+}
+
 impl Default for Builder {
 	#[inline]
 	fn default() -> Builder {
 		Builder { inner: Ok(ResponseParts::new()) }
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::io::Read;
+
+	use flate2::read::{GzDecoder, ZlibDecoder};
+
+	use super::ContentEncoding;
+
+	#[test]
+	fn builds_json_response() {
+		let response = super::Builder::new().status(201).json(&serde_json::json!({ "ok": true })).unwrap();
+		assert_eq!(response.status(), 201);
+		assert_eq!(response.mimetype().map(String::as_str), Some("application/json"));
+		assert_eq!(response.body(), br#"{"ok":true}"#);
+	}
+
+	#[test]
+	fn negotiates_content_encoding() {
+		assert_eq!(ContentEncoding::negotiate("gzip, deflate, br"), Some(ContentEncoding::Gzip));
+		assert_eq!(ContentEncoding::negotiate("deflate, gzip"), Some(ContentEncoding::Gzip));
+		assert_eq!(ContentEncoding::negotiate("deflate"), Some(ContentEncoding::Deflate));
+		assert_eq!(ContentEncoding::negotiate("gzip;q=0.5, deflate"), Some(ContentEncoding::Deflate));
+		assert_eq!(ContentEncoding::negotiate("gzip;q=0, deflate;q=0"), None);
+		assert_eq!(ContentEncoding::negotiate("*"), Some(ContentEncoding::Gzip));
+		assert_eq!(ContentEncoding::negotiate("br, identity"), None);
+		assert_eq!(ContentEncoding::negotiate(""), None);
+	}
+
+	#[test]
+	fn encodes_body() {
+		let body = b"hello world, hello world, hello world".to_vec();
+
+		let mut decoded = Vec::new();
+		GzDecoder::new(ContentEncoding::Gzip.encode(&body).unwrap().as_slice())
+			.read_to_end(&mut decoded)
+			.unwrap();
+		assert_eq!(decoded, body);
+
+		let mut decoded = Vec::new();
+		ZlibDecoder::new(ContentEncoding::Deflate.encode(&body).unwrap().as_slice())
+			.read_to_end(&mut decoded)
+			.unwrap();
+		assert_eq!(decoded, body);
+	}
+}
@@ -18,12 +18,13 @@
 
 #![cfg_attr(doc_cfg, feature(doc_cfg))]
 
-use std::{fmt::Debug, sync::mpsc::Sender};
+use std::{fmt::Debug, path::PathBuf, sync::mpsc::Sender};
 
 use millennium_utils::Theme;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub mod accelerator;
 pub mod http;
 /// Create window and system tray menus.
 pub mod menu;
@@ -36,7 +37,7 @@ use monitor::Monitor;
 use webview::WindowBuilder;
 use window::{
 	dpi::{PhysicalPosition, PhysicalSize, Position, Size},
-	CursorIcon, DetachedWindow, PendingWindow, WindowEvent
+	AccessibilityPreferences, CursorIcon, DetachedWindow, PendingWindow, Rect, ResizeDirection, WindowEvent
 };
 
 use crate::http::{
@@ -146,6 +147,9 @@ pub enum Error {
 	/// Failed to get monitor on window operation.
 	#[error("failed to get monitor")]
 	FailedToGetMonitor,
+	/// Failed to parse an accelerator string.
+	#[error("invalid accelerator: {0}")]
+	InvalidAccelerator(#[from] accelerator::AcceleratorParseError),
 	/// Global shortcut error.
 	#[cfg(feature = "global-shortcut")]
 	#[error(transparent)]
@@ -163,12 +167,47 @@ pub enum Error {
 	#[error("Infallible error, something went really wrong: {0}")]
 	Infallible(#[from] std::convert::Infallible),
 	#[error("The event loop has been closed")]
-	EventLoopClosed
+	EventLoopClosed,
+	/// Failed to print the webview to a PDF file.
+	#[error("failed to print to PDF: {0}")]
+	PrintToPdf(String),
+	/// Failed to take a snapshot of the webview.
+	#[error("failed to take a snapshot: {0}")]
+	Snapshot(String),
+	/// Failed to insert CSS into the webview.
+	#[error("failed to insert CSS: {0}")]
+	InsertCss(String),
+	/// Failed to add an initialization script to the webview.
+	#[error("failed to add initialization script: {0}")]
+	InitScript(String),
+	/// Failed to get the version of the platform webview.
+	#[error("failed to get the webview version: {0}")]
+	WebviewVersion(String)
 }
 
 /// Result type.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The features the runtime supports on the current platform, so apps can hide options that would be no-ops.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeCapabilities {
+	/// Windows can be transparent. On macOS, this requires the `macos-private-api` feature.
+	pub transparency: bool,
+	/// Windows can be hidden from the taskbar. Not supported on macOS.
+	pub skip_taskbar: bool,
+	/// The Mica backdrop effect is available. Requires Windows 11.
+	pub mica: bool,
+	/// Global shortcuts can be registered. Requires the `global-shortcut` feature.
+	pub global_shortcuts: bool,
+	/// A system tray icon can be created. Requires the `system-tray` feature.
+	pub system_tray: bool,
+	/// The clipboard can be read and written. Requires the `clipboard` feature.
+	pub clipboard: bool,
+	/// A jump list can be set. Only supported on Windows and macOS.
+	pub jump_list: bool
+}
+
 /// Window icon.
 #[derive(Debug, Clone)]
 pub struct Icon {
@@ -180,6 +219,15 @@ pub struct Icon {
 	pub height: u32
 }
 
+/// An item of the app's jump list on Windows, or of its dock menu on macOS.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JumpListItem {
+	/// Identifies the item in [`RunEvent::JumpListItemClicked`].
+	pub id: String,
+	/// The text shown for the item.
+	pub title: String
+}
+
 /// A type that can be used as a user event.
 pub trait UserEvent: Debug + Clone + Send + 'static {}
 
@@ -211,6 +259,24 @@ pub enum RunEvent<T: UserEvent> {
 	/// stuff (updating state, performing calculations, etc) that happens as the
 	/// “main body” of your event loop.
 	MainEventsCleared,
+	/// The OS asked the application to open the given files or URLs. Only emitted on macOS.
+	Opened {
+		/// The URLs to open. Files are given as `file://` URLs.
+		urls: Vec<url::Url>
+	},
+	/// The system theme changed.
+	ThemeChanged(Theme),
+	/// The system accent color changed. Carries the new color as RGBA.
+	AccentColorChanged((u8, u8, u8, u8)),
+	/// The display preferences in the system's accessibility settings changed.
+	AccessibilityPreferencesChanged(AccessibilityPreferences),
+	/// An item of the jump list was clicked. Only emitted on Windows and macOS.
+	///
+	/// On Windows, clicking an item launches a new instance of the app, which emits this event once it starts.
+	JumpListItemClicked {
+		/// The id of the clicked item.
+		id: String
+	},
 	/// A custom event defined by the user.
 	UserEvent(T)
 }
@@ -238,9 +304,7 @@ pub struct RunIteration {
 }
 
 /// Application's activation policy. Corresponds to
-/// NSApplicationActivationPolicy.
-#[cfg(target_os = "macos")]
-#[cfg_attr(doc_cfg, doc(cfg(target_os = "macos")))]
+/// NSApplicationActivationPolicy; only macOS has activation policies.
 #[non_exhaustive]
 pub enum ActivationPolicy {
 	/// Corresponds to NSApplicationActivationPolicyRegular.
@@ -260,11 +324,27 @@ pub trait RuntimeHandle<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'st
 	fn create_proxy(&self) -> <Self::Runtime as Runtime<T>>::EventLoopProxy;
 
 	/// Create a new webview window.
+	///
+	/// This doesn't wait for the webview to be created: it is queued on the event loop, or created immediately when
+	/// called from the main thread, so it is safe to call from any thread. Use [`PendingWindow::on_created`] to be
+	/// notified once the webview exists.
 	fn create_window(&self, pending: PendingWindow<T, Self::Runtime>) -> Result<DetachedWindow<T, Self::Runtime>>;
 
 	/// Run a task on the main thread.
 	fn run_on_main_thread<F: FnOnce() + Send + 'static>(&self, f: F) -> Result<()>;
 
+	/// Registers an app-level shortcut of `accelerator`.
+	///
+	/// The key press is checked against these shortcuts before it reaches the webview, so a matching press never gets to
+	/// the page. Unlike global shortcuts, the handler only fires while one of the app's webviews is focused.
+	fn register_app_shortcut<F: Fn() + Send + Sync + 'static>(&self, accelerator: &str, handler: F) -> Result<()>;
+
+	/// Unregisters the app-level shortcut of `accelerator`.
+	fn unregister_app_shortcut(&self, accelerator: &str) -> Result<()>;
+
+	/// Sets the items of the app's jump list on Windows, or of its dock menu on macOS. An empty list removes them.
+	fn set_jump_list(&self, items: Vec<JumpListItem>) -> Result<()>;
+
 	#[cfg(all(windows, feature = "system-tray"))]
 	#[cfg_attr(doc_cfg, doc(cfg(all(windows, feature = "system-tray"))))]
 	fn remove_system_tray(&self) -> Result<()>;
@@ -277,6 +357,9 @@ pub trait GlobalShortcutManager: Debug + Clone + Send + Sync {
 	fn is_registered(&self, accelerator: &str) -> Result<bool>;
 
 	/// Register a global shortcut of `accelerator`.
+	///
+	/// See [`accelerator::parse_accelerator`] for the accepted syntax; `CmdOrCtrl+Shift+K` registers `⌘⇧K` on
+	/// macOS and `Ctrl+Shift+K` elsewhere.
 	fn register<F: Fn() + Send + 'static>(&mut self, accelerator: &str, handler: F) -> Result<()>;
 
 	/// Unregister all accelerators registered by the manager instance.
@@ -353,10 +436,22 @@ pub trait Runtime<T: UserEvent>: Debug + Sized + 'static {
 	#[cfg_attr(doc_cfg, doc(cfg(feature = "system-tray")))]
 	fn on_system_tray_event<F: Fn(&SystemTrayEvent) + Send + 'static>(&mut self, f: F) -> Uuid;
 
+	/// Sets the icon applied to webview windows created without an icon.
+	fn set_default_window_icon(&mut self, icon: Option<Icon>);
+
+	/// Caps how many times per second a window can be redrawn, or removes the cap if `None` or `0` is provided.
+	///
+	/// Redraws requested sooner than one frame after the previous one are deferred until the next frame instead of
+	/// being dropped, and the event loop sleeps until then, so apps that request a redraw for every animation frame
+	/// don't keep a core busy. Windows redrawing continuously share a single frame clock. Uncapped by default.
+	fn set_max_frame_rate(&mut self, max_frame_rate: Option<u32>);
+
 	/// Sets the activation policy for the application. It is set to
 	/// `NSApplicationActivationPolicyRegular` by default.
-	#[cfg(target_os = "macos")]
-	#[cfg_attr(doc_cfg, doc(cfg(target_os = "macos")))]
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux / iOS / Android:** Unsupported; does nothing.
 	fn set_activation_policy(&mut self, activation_policy: ActivationPolicy);
 
 	/// Runs the one step of the webview runtime event loop and returns control
@@ -384,10 +479,24 @@ pub trait Dispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'static
 	/// Registers a window event handler.
 	fn on_menu_event<F: Fn(&window::MenuEvent) + Send + 'static>(&self, f: F) -> Uuid;
 
+	/// Removes a handler registered with [`Self::on_window_event`].
+	///
+	/// Destroying the window already drops its handlers; this is only needed for windows that stay open.
+	fn remove_window_event_listener(&self, id: Uuid);
+
+	/// Removes a handler registered with [`Self::on_menu_event`].
+	///
+	/// Destroying the window already drops its handlers; this is only needed for windows that stay open.
+	fn remove_menu_event_listener(&self, id: Uuid);
+
 	/// Open the web inspector/devtools.
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	fn open_devtools(&self);
 
+	/// Open the web inspector/devtools in a separate window.
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	fn open_devtools_detached(&self);
+
 	/// Close the web inspector/devtools.
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	fn close_devtools(&self);
@@ -422,6 +531,12 @@ pub trait Dispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'static
 	/// that (and you usually don't), use inner_size instead.
 	fn outer_size(&self) -> Result<PhysicalSize<u32>>;
 
+	/// Returns the minimum inner size currently enforced on the window, if any.
+	fn min_size(&self) -> Result<Option<PhysicalSize<u32>>>;
+
+	/// Returns the maximum inner size currently enforced on the window, if any.
+	fn max_size(&self) -> Result<Option<PhysicalSize<u32>>>;
+
 	/// Gets the window's current fullscreen state.
 	fn is_fullscreen(&self) -> Result<bool>;
 
@@ -434,6 +549,18 @@ pub trait Dispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'static
 	/// Gets the window’s current resizable state.
 	fn is_resizable(&self) -> Result<bool>;
 
+	/// Gets whether the window's minimize button is enabled.
+	fn is_minimizable(&self) -> Result<bool>;
+
+	/// Gets whether the window's maximize button is enabled.
+	fn is_maximizable(&self) -> Result<bool>;
+
+	/// Gets whether the window's close button is enabled.
+	fn is_closable(&self) -> Result<bool>;
+
+	/// Gets whether the window is shown on every virtual desktop.
+	fn is_visible_on_all_workspaces(&self) -> Result<bool>;
+
 	/// Gets the window's current vibility state.
 	fn is_visible(&self) -> Result<bool>;
 
@@ -464,25 +591,85 @@ pub trait Dispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'static
 	/// Returns the current system theme.
 	fn theme(&self) -> Result<Theme>;
 
+	/// Returns the system accent color as RGBA, if the platform exposes one.
+	fn accent_color(&self) -> Result<Option<(u8, u8, u8, u8)>>;
+
+	/// Returns the display preferences set in the system's accessibility settings.
+	fn accessibility_preferences(&self) -> Result<AccessibilityPreferences>;
+
 	// SETTERS
 
 	/// Centers the window.
 	fn center(&self) -> Result<()>;
 
+	/// Centers the window on the given monitor.
+	fn center_on_monitor(&self, monitor: Monitor) -> Result<()>;
+
 	/// Opens the dialog to prints the contents of the webview.
 	fn print(&self) -> Result<()>;
 
+	/// Prints the contents of the webview to a PDF file at `path` without opening the print dialog, blocking until
+	/// the file has been written. Returns the path of the written file.
+	///
+	/// This must not be called on the main thread, as the printing happens there.
+	fn print_to_pdf(&self, path: PathBuf, settings: webview::PdfSettings) -> Result<PathBuf>;
+
+	/// Captures the webview as a PNG image, blocking until it is ready.
+	///
+	/// This must not be called on the main thread, as the capture happens there.
+	fn take_snapshot(&self) -> Result<Vec<u8>>;
+
+	/// Enables or disables clipboard access for the page, overriding
+	/// [`WebviewAttributes::enable_clipboard_access`](webview::WebviewAttributes::enable_clipboard_access).
+	fn set_clipboard_access(&self, enabled: bool) -> Result<()>;
+
+	/// Inserts CSS into the webview that stays applied across navigations. Returns an ID that can be passed to
+	/// [`Dispatch::remove_css`].
+	fn insert_css(&self, css: &str) -> Result<webview::CssId>;
+
+	/// Removes CSS inserted with [`Dispatch::insert_css`].
+	fn remove_css(&self, id: webview::CssId) -> Result<()>;
+
+	/// Adds a script that runs before any other script on every page loaded afterwards. Returns an ID that can be
+	/// passed to [`Dispatch::remove_init_script`].
+	fn add_init_script(&self, js: &str) -> Result<webview::InitScriptId>;
+
+	/// Removes a script added with [`Dispatch::add_init_script`].
+	fn remove_init_script(&self, id: webview::InitScriptId) -> Result<()>;
+
+	/// Gets the resident memory in bytes of the process rendering the webview, if it can be determined.
+	fn memory_usage(&self) -> Result<Option<u64>>;
+
+	/// Suspends the webview to reduce its CPU usage while it isn't being used. Emits [`WindowEvent::Suspended`] if the
+	/// webview wasn't already suspended.
+	fn suspend(&self) -> Result<()>;
+
+	/// Resumes a webview suspended with [`Dispatch::suspend`]. Emits [`WindowEvent::Suspended`] if the webview was
+	/// suspended.
+	fn resume(&self) -> Result<()>;
+
 	/// Requests user attention to the window.
 	///
 	/// Providing `None` will unset the request for user attention.
 	fn request_user_attention(&self, request_type: Option<UserAttentionType>) -> Result<()>;
 
 	/// Create a new webview window.
+	///
+	/// See [`RuntimeHandle::create_window`].
 	fn create_window(&mut self, pending: PendingWindow<T, Self::Runtime>) -> Result<DetachedWindow<T, Self::Runtime>>;
 
 	/// Updates the window resizable flag.
 	fn set_resizable(&self, resizable: bool) -> Result<()>;
 
+	/// Updates whether the window's minimize button is enabled.
+	fn set_minimizable(&self, minimizable: bool) -> Result<()>;
+
+	/// Updates whether the window's maximize button is enabled.
+	fn set_maximizable(&self, maximizable: bool) -> Result<()>;
+
+	/// Updates whether the window's close button is enabled.
+	fn set_closable(&self, closable: bool) -> Result<()>;
+
 	/// Updates the window title.
 	fn set_title<S: Into<String>>(&self, title: S) -> Result<()>;
 
@@ -519,6 +706,12 @@ pub trait Dispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'static
 	/// Updates the window alwaysOnTop flag.
 	fn set_always_on_top(&self, always_on_top: bool) -> Result<()>;
 
+	/// Shows the window on every virtual desktop instead of only the one it was opened on.
+	fn set_visible_on_all_workspaces(&self, visible_on_all_workspaces: bool) -> Result<()>;
+
+	/// Sets the opacity of the whole window, from `0.0` (fully transparent) to `1.0` (fully opaque).
+	fn set_opacity(&self, opacity: f64) -> Result<()>;
+
 	/// Resizes the window.
 	fn set_size(&self, size: Size) -> Result<()>;
 
@@ -528,6 +721,10 @@ pub trait Dispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'static
 	/// Updates the window max size.
 	fn set_max_size(&self, size: Option<Size>) -> Result<()>;
 
+	/// Locks the ratio of the window's inner width to its inner height during interactive resizing, or unlocks it if
+	/// `None` was provided.
+	fn set_aspect_ratio(&self, aspect_ratio: Option<f64>) -> Result<()>;
+
 	/// Updates the window position.
 	fn set_position(&self, position: Position) -> Result<()>;
 
@@ -540,9 +737,33 @@ pub trait Dispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'static
 	/// Updates the window icon.
 	fn set_icon(&self, icon: Icon) -> Result<()>;
 
+	/// Updates the taskbar icon, separately from the window icon shown in the title bar.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Sets `ICON_BIG`; a good ceiling here is 256x256.
+	/// - **macOS / Linux:** Unsupported.
+	fn set_taskbar_icon(&self, icon: Icon) -> Result<()>;
+
 	/// Whether to show the window icon in the task bar or not.
 	fn set_skip_taskbar(&self, skip: bool) -> Result<()>;
 
+	/// Sets the file the window represents, which macOS shows as a proxy icon in the title bar.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux:** Unsupported.
+	fn set_represented_filename(&self, path: PathBuf) -> Result<()>;
+
+	/// Sets the colors of the title bar background, the title text, and the window border as RGBA. `None` restores
+	/// the system's color.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Requires Windows 11. The alpha channel is ignored.
+	/// - **macOS / Linux:** Unsupported.
+	fn set_title_bar_colors(&self, caption: Option<(u8, u8, u8, u8)>, text: Option<(u8, u8, u8, u8)>, border: Option<(u8, u8, u8, u8)>) -> Result<()>;
+
 	/// Grabs the cursor, preventing it from leaving the window.
 	///
 	/// There's no guarantee that the cursor will be hidden; you should additionally hide it yourself via CSS or
@@ -563,10 +784,31 @@ pub trait Dispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'static
 	/// Starts dragging the window.
 	fn start_dragging(&self) -> Result<()>;
 
+	/// Starts resizing the window from the given border or corner.
+	fn start_resize_dragging(&self, direction: ResizeDirection) -> Result<()>;
+
+	/// Performs the system's action for a double click on the title bar, e.g. maximizing the window.
+	fn titlebar_double_click(&self) -> Result<()>;
+
+	/// Sets the regions of the window that move it when dragged, like a title bar. The rest of the window keeps
+	/// receiving mouse input.
+	fn set_drag_regions(&self, drag_regions: Vec<Rect>) -> Result<()>;
+
+	/// Starts a native drag session carrying the files at `paths`, which can be
+	/// dropped into other applications. `icon` overrides the drag image.
+	fn start_drag(&self, paths: Vec<PathBuf>, icon: Option<Icon>) -> Result<()>;
+
 	/// Executes javascript on the window this [`Dispatch`] represents.
 	fn eval_script<S: Into<String>>(&self, script: S) -> Result<()>;
 
 	/// Applies the specified `update` to the menu item associated with the
 	/// given `id`.
 	fn update_menu_item(&self, id: u16, update: menu::MenuUpdate) -> Result<()>;
+
+	/// Inserts a custom item into the window menu. See [`menu::Menu::insert_item`] for how `parent` and `index` are
+	/// interpreted.
+	fn insert_menu_item(&self, parent: Option<menu::MenuId>, index: Option<usize>, item: menu::CustomMenuItem) -> Result<()>;
+
+	/// Removes the custom item with the given `id` from the window menu.
+	fn remove_menu_item(&self, id: menu::MenuId) -> Result<()>;
 }
@@ -22,6 +22,8 @@ use std::{
 	hash::{Hash, Hasher}
 };
 
+use crate::accelerator::{parse_accelerator, Accelerator, AcceleratorParseError};
+
 pub type MenuHash = u16;
 pub type MenuId = String;
 pub type MenuIdRef<'a> = &'a str;
@@ -164,6 +166,8 @@ pub trait TrayHandle: fmt::Debug + Clone + Send + Sync {
 	fn set_icon(&self, icon: crate::Icon) -> crate::Result<()>;
 	fn set_menu(&self, menu: crate::menu::SystemTrayMenu) -> crate::Result<()>;
 	fn update_item(&self, id: u16, update: MenuUpdate) -> crate::Result<()>;
+	fn insert_item(&self, parent: Option<MenuId>, index: Option<usize>, item: CustomMenuItem) -> crate::Result<()>;
+	fn remove_item(&self, id: MenuId) -> crate::Result<()>;
 	#[cfg(target_os = "macos")]
 	fn set_icon_as_template(&self, is_template: bool) -> crate::Result<()>;
 }
@@ -178,6 +182,7 @@ pub struct Menu {
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct Submenu {
+	pub id: Option<MenuId>,
 	pub title: String,
 	pub enabled: bool,
 	pub inner: Menu
@@ -187,11 +192,19 @@ impl Submenu {
 	/// Creates a new submenu with the given title and menu items.
 	pub fn new<S: Into<String>>(title: S, menu: Menu) -> Self {
 		Self {
+			id: None,
 			title: title.into(),
 			enabled: true,
 			inner: menu
 		}
 	}
+
+	/// Assigns an id to the submenu so items can be inserted into it with [`Menu::insert_item`].
+	#[must_use]
+	pub fn id<I: Into<String>>(mut self, id: I) -> Self {
+		self.id.replace(id.into());
+		self
+	}
 }
 
 impl Menu {
@@ -305,6 +318,70 @@ impl Menu {
 		self.items.push(MenuEntry::Submenu(submenu));
 		self
 	}
+
+	/// Finds the custom menu item with the given `id`, searching submenus recursively.
+	pub fn find_item(&self, id: MenuIdRef<'_>) -> Option<&CustomMenuItem> {
+		entries::find_item(&self.items, id)
+	}
+
+	/// Finds the custom menu item with the given `id`, searching submenus recursively.
+	pub fn find_item_mut(&mut self, id: MenuIdRef<'_>) -> Option<&mut CustomMenuItem> {
+		entries::find_item_mut(&mut self.items, id)
+	}
+
+	/// Gets the hash of the custom menu item with the given `id`, which can be used to update the item through the
+	/// dispatcher.
+	pub fn item_hash(&self, id: MenuIdRef<'_>) -> Option<MenuHash> {
+		self.find_item(id).map(|item| item.id)
+	}
+
+	/// Sets the selected state of the custom menu item with the given `id`. If it is a radio item being selected,
+	/// the other items of its group are deselected.
+	///
+	/// Returns the hash and new selected state of every item that changed, so the native menu can be updated.
+	pub fn set_item_selected(&mut self, id: MenuIdRef<'_>, selected: bool) -> Vec<(MenuHash, bool)> {
+		entries::set_item_selected(&mut self.items, id, selected)
+	}
+
+	/// Updates the selected state of the custom menu item with the given `id` as if it was clicked: checkboxes are
+	/// toggled and radio items are selected. See [`Menu::set_item_selected`] for the return value.
+	pub fn activate_item(&mut self, id: MenuIdRef<'_>) -> Vec<(MenuHash, bool)> {
+		entries::activate_item(&mut self.items, id)
+	}
+
+	/// Inserts a custom item into the submenu with the given `parent` id, or into this menu if `parent` is `None`.
+	///
+	/// `index` counts every entry of the target menu, including native items and submenus. The item is appended if
+	/// `index` is `None` or past the end of the menu. Returns `false` if no submenu has the `parent` id.
+	pub fn insert_item(&mut self, parent: Option<MenuIdRef<'_>>, index: Option<usize>, item: CustomMenuItem) -> bool {
+		entries::insert_item(&mut self.items, parent, index, item)
+	}
+
+	/// Removes the custom menu item with the given `id`, searching submenus recursively.
+	pub fn remove_item(&mut self, id: MenuIdRef<'_>) -> Option<CustomMenuItem> {
+		entries::remove_item(&mut self.items, id)
+	}
+}
+
+/// How a custom menu item shows and changes its selected state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuItemKind {
+	/// A regular item. It can still be marked as selected with [`MenuUpdate::SetSelected`].
+	Normal,
+	/// An item with a check mark that is toggled when the item is clicked.
+	Checkbox,
+	/// An item of a radio group. Selecting it, either by clicking it or with [`MenuUpdate::SetSelected`], deselects
+	/// every other item of the same `group` in the menu.
+	Radio {
+		/// The name of the radio group.
+		group: String
+	}
+}
+
+impl Default for MenuItemKind {
+	fn default() -> Self {
+		Self::Normal
+	}
 }
 
 /// A custom menu item.
@@ -314,9 +391,10 @@ pub struct CustomMenuItem {
 	pub id: MenuHash,
 	pub id_str: MenuId,
 	pub title: String,
-	pub keyboard_accelerator: Option<String>,
+	pub keyboard_accelerator: Option<Accelerator>,
 	pub enabled: bool,
 	pub selected: bool,
+	pub kind: MenuItemKind,
 	#[cfg(target_os = "macos")]
 	pub native_image: Option<NativeImage>
 }
@@ -332,16 +410,21 @@ impl CustomMenuItem {
 			keyboard_accelerator: None,
 			enabled: true,
 			selected: false,
+			kind: MenuItemKind::Normal,
 			#[cfg(target_os = "macos")]
 			native_image: None
 		}
 	}
 
 	/// Assign a keyboard shortcut to the menu action.
-	#[must_use]
-	pub fn accelerator<T: Into<String>>(mut self, accelerator: T) -> Self {
-		self.keyboard_accelerator.replace(accelerator.into());
-		self
+	///
+	/// The accelerator is parsed with [`parse_accelerator`], which returns an
+	/// error if it isn't valid. Use the `CmdOrCtrl` modifier to get `Command` on
+	/// macOS and `Ctrl` on other platforms from a single string, e.g.
+	/// `CmdOrCtrl+Shift+S`.
+	pub fn accelerator<T: AsRef<str>>(mut self, accelerator: T) -> Result<Self, AcceleratorParseError> {
+		self.keyboard_accelerator.replace(parse_accelerator(accelerator.as_ref())?);
+		Ok(self)
 	}
 
 	#[cfg(target_os = "macos")]
@@ -367,6 +450,20 @@ impl CustomMenuItem {
 		self
 	}
 
+	/// Make the item a checkbox that is toggled when clicked.
+	#[must_use]
+	pub fn checkbox(mut self) -> Self {
+		self.kind = MenuItemKind::Checkbox;
+		self
+	}
+
+	/// Make the item part of the radio group `group`.
+	#[must_use]
+	pub fn radio<G: Into<String>>(mut self, group: G) -> Self {
+		self.kind = MenuItemKind::Radio { group: group.into() };
+		self
+	}
+
 	fn hash(id: &str) -> MenuHash {
 		let mut hasher = DefaultHasher::new();
 		id.hash(&mut hasher);
@@ -384,6 +481,7 @@ pub struct SystemTrayMenu {
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct SystemTraySubmenu {
+	pub id: Option<MenuId>,
 	pub title: String,
 	pub enabled: bool,
 	pub inner: SystemTrayMenu
@@ -393,11 +491,19 @@ impl SystemTraySubmenu {
 	/// Creates a new submenu with the given title and menu items.
 	pub fn new<S: Into<String>>(title: S, menu: SystemTrayMenu) -> Self {
 		Self {
+			id: None,
 			title: title.into(),
 			enabled: true,
 			inner: menu
 		}
 	}
+
+	/// Assigns an id to the submenu so items can be inserted into it with [`SystemTrayMenu::insert_item`].
+	#[must_use]
+	pub fn id<I: Into<String>>(mut self, id: I) -> Self {
+		self.id.replace(id.into());
+		self
+	}
 }
 
 impl SystemTrayMenu {
@@ -426,6 +532,49 @@ impl SystemTrayMenu {
 		self.items.push(SystemTrayMenuEntry::Submenu(submenu));
 		self
 	}
+
+	/// Finds the custom menu item with the given `id`, searching submenus recursively.
+	pub fn find_item(&self, id: MenuIdRef<'_>) -> Option<&CustomMenuItem> {
+		entries::find_item(&self.items, id)
+	}
+
+	/// Finds the custom menu item with the given `id`, searching submenus recursively.
+	pub fn find_item_mut(&mut self, id: MenuIdRef<'_>) -> Option<&mut CustomMenuItem> {
+		entries::find_item_mut(&mut self.items, id)
+	}
+
+	/// Gets the hash of the custom menu item with the given `id`, which can be used to update the item through the
+	/// tray handle.
+	pub fn item_hash(&self, id: MenuIdRef<'_>) -> Option<MenuHash> {
+		self.find_item(id).map(|item| item.id)
+	}
+
+	/// Sets the selected state of the custom menu item with the given `id`. If it is a radio item being selected,
+	/// the other items of its group are deselected.
+	///
+	/// Returns the hash and new selected state of every item that changed, so the native menu can be updated.
+	pub fn set_item_selected(&mut self, id: MenuIdRef<'_>, selected: bool) -> Vec<(MenuHash, bool)> {
+		entries::set_item_selected(&mut self.items, id, selected)
+	}
+
+	/// Updates the selected state of the custom menu item with the given `id` as if it was clicked: checkboxes are
+	/// toggled and radio items are selected. See [`SystemTrayMenu::set_item_selected`] for the return value.
+	pub fn activate_item(&mut self, id: MenuIdRef<'_>) -> Vec<(MenuHash, bool)> {
+		entries::activate_item(&mut self.items, id)
+	}
+
+	/// Inserts a custom item into the submenu with the given `parent` id, or into this menu if `parent` is `None`.
+	///
+	/// `index` counts every entry of the target menu, including native items and submenus. The item is appended if
+	/// `index` is `None` or past the end of the menu. Returns `false` if no submenu has the `parent` id.
+	pub fn insert_item(&mut self, parent: Option<MenuIdRef<'_>>, index: Option<usize>, item: CustomMenuItem) -> bool {
+		entries::insert_item(&mut self.items, parent, index, item)
+	}
+
+	/// Removes the custom menu item with the given `id`, searching submenus recursively.
+	pub fn remove_item(&mut self, id: MenuIdRef<'_>) -> Option<CustomMenuItem> {
+		entries::remove_item(&mut self.items, id)
+	}
 }
 
 /// An entry on the system tray menu.
@@ -476,6 +625,161 @@ impl From<Submenu> for MenuEntry {
 	}
 }
 
+/// Lets [`Menu`] and [`SystemTrayMenu`] share the functions in [`entries`] that walk their items.
+trait Entry: Sized {
+	fn custom_item(item: CustomMenuItem) -> Self;
+	fn as_custom_item(&self) -> Option<&CustomMenuItem>;
+	fn as_custom_item_mut(&mut self) -> Option<&mut CustomMenuItem>;
+	fn into_custom_item(self) -> Option<CustomMenuItem>;
+	/// The entries of the submenu, if this entry is one.
+	fn submenu(&self) -> Option<&[Self]>;
+	/// The id and entries of the submenu, if this entry is one.
+	fn submenu_mut(&mut self) -> Option<(Option<MenuIdRef<'_>>, &mut Vec<Self>)>;
+}
+
+macro_rules! impl_entry {
+	($entry:ident) => {
+		impl Entry for $entry {
+			fn custom_item(item: CustomMenuItem) -> Self {
+				Self::CustomItem(item)
+			}
+
+			fn as_custom_item(&self) -> Option<&CustomMenuItem> {
+				match self {
+					Self::CustomItem(c) => Some(c),
+					_ => None
+				}
+			}
+
+			fn as_custom_item_mut(&mut self) -> Option<&mut CustomMenuItem> {
+				match self {
+					Self::CustomItem(c) => Some(c),
+					_ => None
+				}
+			}
+
+			fn into_custom_item(self) -> Option<CustomMenuItem> {
+				match self {
+					Self::CustomItem(c) => Some(c),
+					_ => None
+				}
+			}
+
+			fn submenu(&self) -> Option<&[Self]> {
+				match self {
+					Self::Submenu(s) => Some(&s.inner.items),
+					_ => None
+				}
+			}
+
+			fn submenu_mut(&mut self) -> Option<(Option<MenuIdRef<'_>>, &mut Vec<Self>)> {
+				match self {
+					Self::Submenu(s) => Some((s.id.as_deref(), &mut s.inner.items)),
+					_ => None
+				}
+			}
+		}
+	};
+}
+
+impl_entry!(MenuEntry);
+impl_entry!(SystemTrayMenuEntry);
+
+/// The item lookup and editing shared by [`Menu`] and [`SystemTrayMenu`]; see their methods of the same name.
+mod entries {
+	use super::{CustomMenuItem, Entry, MenuHash, MenuIdRef, MenuItemKind};
+
+	pub(super) fn find_item<'a, E: Entry>(entries: &'a [E], id: MenuIdRef<'_>) -> Option<&'a CustomMenuItem> {
+		entries.iter().find_map(|entry| match entry.as_custom_item() {
+			Some(item) if item.id_str == id => Some(item),
+			Some(_) => None,
+			None => entry.submenu().and_then(|entries| find_item(entries, id))
+		})
+	}
+
+	pub(super) fn find_item_mut<'a, E: Entry>(entries: &'a mut [E], id: MenuIdRef<'_>) -> Option<&'a mut CustomMenuItem> {
+		entries.iter_mut().find_map(|entry| {
+			if entry.as_custom_item().map_or(false, |item| item.id_str == id) {
+				entry.as_custom_item_mut()
+			} else {
+				entry.submenu_mut().and_then(|(_, entries)| find_item_mut(entries, id))
+			}
+		})
+	}
+
+	pub(super) fn set_item_selected<E: Entry>(entries: &mut [E], id: MenuIdRef<'_>, selected: bool) -> Vec<(MenuHash, bool)> {
+		let group = match find_item(entries, id).map(|item| &item.kind) {
+			Some(MenuItemKind::Radio { group }) if selected => Some(group.clone()),
+			Some(_) => None,
+			None => return Vec::new()
+		};
+		let mut changed = Vec::new();
+		for_each_item_mut(entries, &mut |item| {
+			let new_state = if item.id_str == id {
+				selected
+			} else if group.is_some() && matches!(&item.kind, MenuItemKind::Radio { group: g } if Some(g) == group.as_ref()) {
+				false
+			} else {
+				return;
+			};
+			if item.selected != new_state {
+				item.selected = new_state;
+				changed.push((item.id, new_state));
+			}
+		});
+		changed
+	}
+
+	pub(super) fn activate_item<E: Entry>(entries: &mut [E], id: MenuIdRef<'_>) -> Vec<(MenuHash, bool)> {
+		match find_item(entries, id).map(|item| (&item.kind, item.selected)) {
+			Some((MenuItemKind::Checkbox, selected)) => set_item_selected(entries, id, !selected),
+			Some((MenuItemKind::Radio { .. }, _)) => set_item_selected(entries, id, true),
+			_ => Vec::new()
+		}
+	}
+
+	pub(super) fn insert_item<E: Entry>(entries: &mut Vec<E>, parent: Option<MenuIdRef<'_>>, index: Option<usize>, item: CustomMenuItem) -> bool {
+		let entries = match parent {
+			Some(parent) => match find_submenu_mut(entries, parent) {
+				Some(entries) => entries,
+				None => return false
+			},
+			None => entries
+		};
+		let index = index.map_or(entries.len(), |index| index.min(entries.len()));
+		entries.insert(index, E::custom_item(item));
+		true
+	}
+
+	pub(super) fn remove_item<E: Entry>(entries: &mut Vec<E>, id: MenuIdRef<'_>) -> Option<CustomMenuItem> {
+		if let Some(index) = entries.iter().position(|entry| entry.as_custom_item().map_or(false, |item| item.id_str == id)) {
+			return entries.remove(index).into_custom_item();
+		}
+		entries.iter_mut().find_map(|entry| entry.submenu_mut().and_then(|(_, entries)| remove_item(entries, id)))
+	}
+
+	fn for_each_item_mut<E: Entry>(entries: &mut [E], f: &mut impl FnMut(&mut CustomMenuItem)) {
+		for entry in entries {
+			if let Some(item) = entry.as_custom_item_mut() {
+				f(item);
+			} else if let Some((_, entries)) = entry.submenu_mut() {
+				for_each_item_mut(entries, f);
+			}
+		}
+	}
+
+	fn find_submenu_mut<'a, E: Entry>(entries: &'a mut [E], id: MenuIdRef<'_>) -> Option<&'a mut Vec<E>> {
+		entries.iter_mut().find_map(|entry| {
+			let (submenu_id, entries) = entry.submenu_mut()?;
+			if submenu_id == Some(id) {
+				Some(entries)
+			} else {
+				find_submenu_mut(entries, id)
+			}
+		})
+	}
+}
+
 /// Application metadata for the [`MenuItem::About`] action.
 ///
 /// ## Platform-specific
@@ -680,3 +984,106 @@ pub enum MenuItem {
 	/// - **Android / iOS:** Unsupported
 	Separator
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_items_by_id() {
+		let mut menu = Menu::new()
+			.add_item(CustomMenuItem::new("open", "Open"))
+			.add_native_item(MenuItem::Separator)
+			.add_submenu(Submenu::new(
+				"Recent",
+				Menu::new().add_submenu(Submenu::new("More", Menu::new().add_item(CustomMenuItem::new("recent-1", "a.txt"))))
+			));
+
+		assert_eq!(menu.find_item("open").map(|i| i.title.as_str()), Some("Open"));
+		assert_eq!(menu.item_hash("recent-1"), Some(CustomMenuItem::new("recent-1", "").id));
+		assert!(menu.find_item("missing").is_none());
+
+		menu.find_item_mut("recent-1").unwrap().title = "b.txt".into();
+		assert_eq!(menu.find_item("recent-1").unwrap().title, "b.txt");
+
+		let tray = SystemTrayMenu::new().add_submenu(SystemTraySubmenu::new("Sub", SystemTrayMenu::new().add_item(CustomMenuItem::new("quit", "Quit"))));
+		assert_eq!(tray.item_hash("quit"), Some(CustomMenuItem::new("quit", "").id));
+		assert!(tray.find_item("open").is_none());
+	}
+
+	#[test]
+	fn inserts_and_removes_items() {
+		let titles = |menu: &Menu| {
+			menu.items
+				.iter()
+				.map(|item| match item {
+					MenuEntry::CustomItem(c) => c.title.clone(),
+					MenuEntry::NativeItem(_) => "-".into(),
+					MenuEntry::Submenu(s) => s.title.clone()
+				})
+				.collect::<Vec<_>>()
+		};
+
+		let mut menu = Menu::new()
+			.add_item(CustomMenuItem::new("open", "Open"))
+			.add_submenu(Submenu::new("Recent", Menu::new()).id("recent"));
+
+		assert!(menu.insert_item(None, Some(0), CustomMenuItem::new("new", "New")));
+		assert!(menu.insert_item(None, Some(100), CustomMenuItem::new("quit", "Quit")));
+		assert_eq!(titles(&menu), ["New", "Open", "Recent", "Quit"]);
+
+		assert!(menu.insert_item(Some("recent"), None, CustomMenuItem::new("recent-2", "b.txt")));
+		assert!(menu.insert_item(Some("recent"), Some(0), CustomMenuItem::new("recent-1", "a.txt")));
+		assert!(!menu.insert_item(Some("missing"), None, CustomMenuItem::new("recent-3", "c.txt")));
+		match &menu.items[2] {
+			MenuEntry::Submenu(s) => assert_eq!(titles(&s.inner), ["a.txt", "b.txt"]),
+			_ => panic!("expected the recent submenu")
+		}
+
+		assert_eq!(menu.remove_item("recent-1").map(|i| i.title), Some("a.txt".into()));
+		assert_eq!(menu.remove_item("quit").map(|i| i.title), Some("Quit".into()));
+		assert!(menu.remove_item("quit").is_none());
+		assert!(menu.find_item("recent-1").is_none());
+		assert!(menu.find_item("recent-2").is_some());
+
+		let mut tray = SystemTrayMenu::new().add_submenu(SystemTraySubmenu::new("Recent", SystemTrayMenu::new()).id("recent"));
+		assert!(tray.insert_item(Some("recent"), Some(3), CustomMenuItem::new("recent-1", "a.txt")));
+		assert_eq!(tray.item_hash("recent-1"), Some(CustomMenuItem::new("recent-1", "").id));
+		assert!(tray.remove_item("recent-1").is_some());
+		assert!(tray.find_item("recent-1").is_none());
+	}
+
+	#[test]
+	fn radio_groups_are_exclusive() {
+		let hash = |id: &str| CustomMenuItem::new(id, "").id;
+		let mut menu = Menu::new()
+			.add_item(CustomMenuItem::new("wrap", "Word Wrap").checkbox())
+			.add_submenu(Submenu::new(
+				"Theme",
+				Menu::new()
+					.add_item(CustomMenuItem::new("light", "Light").radio("theme").selected())
+					.add_item(CustomMenuItem::new("dark", "Dark").radio("theme"))
+					.add_item(CustomMenuItem::new("system", "System").radio("theme"))
+			))
+			.add_item(CustomMenuItem::new("compact", "Compact").radio("density").selected());
+
+		assert_eq!(menu.set_item_selected("dark", true), [(hash("light"), false), (hash("dark"), true)]);
+		assert!(!menu.find_item("light").unwrap().selected);
+		// other groups are left alone
+		assert!(menu.find_item("compact").unwrap().selected);
+		// selecting an already selected item changes nothing
+		assert!(menu.activate_item("dark").is_empty());
+		assert_eq!(menu.activate_item("system"), [(hash("dark"), false), (hash("system"), true)]);
+		// deselecting a radio item doesn't select a sibling
+		assert_eq!(menu.set_item_selected("system", false), [(hash("system"), false)]);
+
+		assert_eq!(menu.activate_item("wrap"), [(hash("wrap"), true)]);
+		assert_eq!(menu.activate_item("wrap"), [(hash("wrap"), false)]);
+		assert!(menu.activate_item("missing").is_empty());
+
+		let mut tray = SystemTrayMenu::new()
+			.add_item(CustomMenuItem::new("a", "A").radio("g").selected())
+			.add_item(CustomMenuItem::new("b", "B").radio("g"));
+		assert_eq!(tray.activate_item("b"), [(hash("a"), false), (hash("b"), true)]);
+	}
+}
@@ -16,16 +16,16 @@
 
 //! Items specific to the [`Runtime`](crate::Runtime)'s webview.
 
-use std::{fmt, path::PathBuf};
+use std::{fmt, path::PathBuf, time::Duration};
 
 use millennium_utils::{
 	config::{WindowConfig, WindowUrl},
-	Theme
+	LayoutDirection, Theme
 };
 #[cfg(windows)]
 use windows::Win32::Foundation::HWND;
 
-use crate::{menu::Menu, window::DetachedWindow, Icon};
+use crate::{http::header::HeaderMap, menu::Menu, monitor::Monitor, window::DetachedWindow, Icon};
 
 /// The attributes used to create an webview.
 #[derive(Debug, Clone)]
@@ -34,7 +34,25 @@ pub struct WebviewAttributes {
 	pub initialization_scripts: Vec<String>,
 	pub data_directory: Option<PathBuf>,
 	pub file_drop_handler_enabled: bool,
-	pub clipboard: bool
+	pub clipboard: bool,
+	pub custom_protocol_timeout: Option<Duration>,
+	pub custom_protocol_cache_size: Option<usize>,
+	pub autoplay: bool,
+	pub default_context_menu_enabled: bool,
+	pub devtools_shortcuts_enabled: bool,
+	pub devtools_position: DevtoolsPosition,
+	pub headless: bool,
+	pub scroll_bar_style: ScrollBarStyle,
+	pub suspend_when_unfocused: bool,
+	pub scale_change_scripts: Vec<String>,
+	pub hardware_acceleration: bool,
+	pub scroll_bounce: bool,
+	pub accept_first_mouse: bool,
+	pub pinch_zoom: Option<bool>,
+	pub swipe_navigation: Option<bool>,
+	pub cookie_persistence: CookiePersistence,
+	pub block_third_party_cookies: bool,
+	pub passthrough_shortcuts: Vec<String>
 }
 
 impl WebviewAttributes {
@@ -45,7 +63,25 @@ impl WebviewAttributes {
 			initialization_scripts: Vec::new(),
 			data_directory: None,
 			file_drop_handler_enabled: true,
-			clipboard: false
+			clipboard: false,
+			custom_protocol_timeout: None,
+			custom_protocol_cache_size: None,
+			autoplay: false,
+			default_context_menu_enabled: true,
+			devtools_shortcuts_enabled: true,
+			devtools_position: DevtoolsPosition::Docked,
+			headless: false,
+			scroll_bar_style: ScrollBarStyle::Default,
+			suspend_when_unfocused: false,
+			scale_change_scripts: Vec::new(),
+			hardware_acceleration: true,
+			scroll_bounce: true,
+			accept_first_mouse: false,
+			pinch_zoom: None,
+			swipe_navigation: None,
+			cookie_persistence: CookiePersistence::Persistent,
+			block_third_party_cookies: false,
+			passthrough_shortcuts: Vec::new()
 		}
 	}
 
@@ -81,6 +117,155 @@ impl WebviewAttributes {
 		self.clipboard = true;
 		self
 	}
+
+	/// Runs each custom protocol request on its own thread, responding with
+	/// `504 Gateway Timeout` if a handler takes longer than `timeout`.
+	///
+	/// By default, handlers run synchronously on the webview thread, which
+	/// freezes rendering for as long as the handler runs. With a timeout, the
+	/// request is completed asynchronously and the webview thread never waits
+	/// for the handler; the cost is a thread per request and handlers that may
+	/// keep running after their response has been discarded.
+	#[must_use]
+	pub fn custom_protocol_timeout(mut self, timeout: Duration) -> Self {
+		self.custom_protocol_timeout.replace(timeout);
+		self
+	}
+
+	/// Caches custom protocol responses in memory, keeping at most `max_size`
+	/// bytes of response bodies and evicting the least recently used ones first.
+	///
+	/// Only responses that set `Cache-Control: max-age` or an `ETag` are cached,
+	/// and `Cache-Control: no-store` is always honored.
+	#[must_use]
+	pub fn custom_protocol_cache(mut self, max_size: usize) -> Self {
+		self.custom_protocol_cache_size.replace(max_size);
+		self
+	}
+
+	/// Whether media can play automatically without a user gesture. Disabled by
+	/// default, matching browser behavior.
+	#[must_use]
+	pub fn autoplay(mut self, autoplay: bool) -> Self {
+		self.autoplay = autoplay;
+		self
+	}
+
+	/// Disables the webview's built-in context menu.
+	#[must_use]
+	pub fn disable_default_context_menu(mut self) -> Self {
+		self.default_context_menu_enabled = false;
+		self
+	}
+
+	/// Disables the keyboard shortcuts that open the devtools.
+	#[must_use]
+	pub fn disable_devtools_shortcuts(mut self) -> Self {
+		self.devtools_shortcuts_enabled = false;
+		self
+	}
+
+	/// Renders the webview without ever showing its window.
+	#[must_use]
+	pub fn headless(mut self, headless: bool) -> Self {
+		self.headless = headless;
+		self
+	}
+
+	/// Sets the style of the webview's scroll bars.
+	#[must_use]
+	pub fn scroll_bar_style(mut self, style: ScrollBarStyle) -> Self {
+		self.scroll_bar_style = style;
+		self
+	}
+
+	/// Where the devtools open when they are opened without an explicit
+	/// position. Docked by default.
+	#[must_use]
+	pub fn devtools_position(mut self, position: DevtoolsPosition) -> Self {
+		self.devtools_position = position;
+		self
+	}
+
+	/// Suspends the webview whenever its window loses focus while hidden or
+	/// minimized, and resumes it when the window regains focus.
+	#[must_use]
+	pub fn suspend_when_unfocused(mut self, suspend: bool) -> Self {
+		self.suspend_when_unfocused = suspend;
+		self
+	}
+
+	/// Adds a script that is evaluated whenever the scale factor of the window
+	/// changes, e.g. when it is moved to a monitor with a different DPI. The new
+	/// scale factor is available to the script as `scaleFactor`.
+	#[must_use]
+	pub fn scale_change_script(mut self, script: &str) -> Self {
+		self.scale_change_scripts.push(script.to_string());
+		self
+	}
+
+	/// Whether the webview renders with the GPU. Enabled by default; disable
+	/// it to force software rendering.
+	#[must_use]
+	pub fn hardware_acceleration(mut self, enabled: bool) -> Self {
+		self.hardware_acceleration = enabled;
+		self
+	}
+
+	/// Whether the webview bounces when scrolled past the edges of the page.
+	/// Follows the platform by default.
+	#[must_use]
+	pub fn scroll_bounce(mut self, enabled: bool) -> Self {
+		self.scroll_bounce = enabled;
+		self
+	}
+
+	/// Whether the click that activates an unfocused window is also delivered
+	/// to the webview. Only has an effect on macOS.
+	#[must_use]
+	pub fn accept_first_mouse(mut self, accept_first_mouse: bool) -> Self {
+		self.accept_first_mouse = accept_first_mouse;
+		self
+	}
+
+	/// Whether pinching zooms the page. Follows the platform if not set.
+	#[must_use]
+	pub fn pinch_zoom(mut self, enabled: bool) -> Self {
+		self.pinch_zoom = Some(enabled);
+		self
+	}
+
+	/// Whether swiping with two fingers navigates back and forward. Follows
+	/// the platform if not set.
+	#[must_use]
+	pub fn swipe_navigation(mut self, enabled: bool) -> Self {
+		self.swipe_navigation = Some(enabled);
+		self
+	}
+
+	/// Whether cookies are kept across runs of the app. Persistent by
+	/// default.
+	#[must_use]
+	pub fn cookie_persistence(mut self, persistence: CookiePersistence) -> Self {
+		self.cookie_persistence = persistence;
+		self
+	}
+
+	/// Whether cookies set by sites other than the page's own are rejected.
+	/// Disabled by default, which keeps the platform's own policy.
+	#[must_use]
+	pub fn block_third_party_cookies(mut self, block: bool) -> Self {
+		self.block_third_party_cookies = block;
+		self
+	}
+
+	/// Adds a shortcut that always reaches the window's menu and the OS,
+	/// even when the webview has focus.
+	#[must_use]
+	pub fn passthrough_shortcut(mut self, accelerator: &str) -> Self {
+		self.passthrough_shortcuts.push(accelerator.to_string());
+		self
+	}
 }
 
 /// Do **NOT** implement this trait except for use in a custom
@@ -109,6 +294,11 @@ pub trait WindowBuilder: WindowBuilderBase {
 	#[must_use]
 	fn center(self) -> Self;
 
+	/// Show window in the center of the given monitor, e.g. the one under the cursor, instead of the monitor it's
+	/// created on.
+	#[must_use]
+	fn center_on_monitor(self, monitor: Monitor) -> Self;
+
 	/// The initial position of the window's.
 	#[must_use]
 	fn position(self, x: f64, y: f64) -> Self;
@@ -129,6 +319,18 @@ pub trait WindowBuilder: WindowBuilderBase {
 	#[must_use]
 	fn resizable(self, resizable: bool) -> Self;
 
+	/// Whether the window's minimize button is enabled.
+	#[must_use]
+	fn minimizable(self, minimizable: bool) -> Self;
+
+	/// Whether the window's maximize button is enabled.
+	#[must_use]
+	fn maximizable(self, maximizable: bool) -> Self;
+
+	/// Whether the window's close button is enabled.
+	#[must_use]
+	fn closable(self, closable: bool) -> Self;
+
 	/// The title of the window in the title bar.
 	#[must_use]
 	fn title<S: Into<String>>(self, title: S) -> Self;
@@ -172,6 +374,14 @@ pub trait WindowBuilder: WindowBuilderBase {
 	/// Sets the window icon.
 	fn icon(self, icon: Icon) -> crate::Result<Self>;
 
+	/// Sets the taskbar icon, separately from the window icon shown in the title bar.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Sets `ICON_BIG`; a good ceiling here is 256x256.
+	/// - **macOS / Linux:** Unsupported.
+	fn taskbar_icon(self, icon: Icon) -> crate::Result<Self>;
+
 	/// Sets whether or not the window icon should be added to the taskbar.
 	#[must_use]
 	fn skip_taskbar(self, skip: bool) -> Self;
@@ -206,6 +416,16 @@ pub trait WindowBuilder: WindowBuilderBase {
 	/// Forces a theme or uses the system settings if None was provided.
 	fn theme(self, theme: Option<Theme>) -> Self;
 
+	/// Forces a layout direction for the window's menu bar and controls, or follows the system locale if None was
+	/// provided.
+	#[must_use]
+	fn layout_direction(self, direction: Option<LayoutDirection>) -> Self;
+
+	/// Forces the background color of the window's title bar as RGBA, or follows the system theme if None was
+	/// provided.
+	#[must_use]
+	fn title_bar_color(self, color: Option<(u8, u8, u8, u8)>) -> Self;
+
 	/// Whether the icon was set or not.
 	fn has_icon(&self) -> bool;
 
@@ -213,5 +433,136 @@ pub trait WindowBuilder: WindowBuilderBase {
 	fn get_menu(&self) -> Option<&Menu>;
 }
 
+/// Page layout for [`Dispatch::print_to_pdf`](crate::Dispatch::print_to_pdf). All lengths are in inches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdfSettings {
+	pub page_width: f64,
+	pub page_height: f64,
+	pub margin_top: f64,
+	pub margin_bottom: f64,
+	pub margin_left: f64,
+	pub margin_right: f64,
+	pub orientation: PdfOrientation
+}
+
+impl Default for PdfSettings {
+	/// US Letter in portrait, with 0.4 inch margins.
+	fn default() -> Self {
+		Self {
+			page_width: 8.5,
+			page_height: 11.0,
+			margin_top: 0.4,
+			margin_bottom: 0.4,
+			margin_left: 0.4,
+			margin_right: 0.4,
+			orientation: PdfOrientation::Portrait
+		}
+	}
+}
+
+impl PdfSettings {
+	/// Sets the page size.
+	#[must_use]
+	pub fn page_size(mut self, width: f64, height: f64) -> Self {
+		self.page_width = width;
+		self.page_height = height;
+		self
+	}
+
+	/// Sets all four page margins.
+	#[must_use]
+	pub fn margins(mut self, top: f64, right: f64, bottom: f64, left: f64) -> Self {
+		self.margin_top = top;
+		self.margin_right = right;
+		self.margin_bottom = bottom;
+		self.margin_left = left;
+		self
+	}
+
+	/// Sets the page orientation.
+	#[must_use]
+	pub fn orientation(mut self, orientation: PdfOrientation) -> Self {
+		self.orientation = orientation;
+		self
+	}
+}
+
+/// Page orientation of a printed PDF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfOrientation {
+	Portrait,
+	Landscape
+}
+
+/// How the webview draws its scroll bars.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollBarStyle {
+	/// Follow the system preference.
+	Default,
+	/// Thin scroll bars drawn over the content, hidden while not scrolling.
+	Overlay,
+	/// Scroll bars that are always shown next to the content.
+	AlwaysVisible
+}
+
+/// Where the devtools of a webview open.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevtoolsPosition {
+	/// Attached to the webview's window.
+	Docked,
+	/// In a separate window.
+	Detached
+}
+
+/// How long a webview keeps its cookies.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CookiePersistence {
+	/// Stored on disk and kept across runs of the app.
+	Persistent,
+	/// Kept in memory and forgotten once the app exits.
+	Session
+}
+
+/// Identifies an initialization script added to a webview, used to remove it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InitScriptId(pub u64);
+
+/// Identifies CSS inserted into a webview, used to remove it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CssId(pub u64);
+
+/// A capability a page asks permission to use.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionKind {
+	Camera,
+	Microphone,
+	Geolocation,
+	Notifications,
+	/// Any other permission, such as device sensors or pointer lock.
+	Other
+}
+
+/// The answer to a permission request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionResponse {
+	Allow,
+	Deny,
+	/// Let the webview decide, which usually means asking the user.
+	Prompt
+}
+
+/// Permission request handler, called with the origin of the requesting page.
+pub type WebviewPermissionHandler = Box<dyn Fn(&str, PermissionKind) -> PermissionResponse + Send>;
+
+/// Outgoing request interceptor, called with the URL and the headers of the request.
+pub type WebviewRequestInterceptor = Box<dyn Fn(&str, &mut HeaderMap) + Send>;
+
 /// IPC handler.
-pub type WebviewIpcHandler<T, R> = Box<dyn Fn(DetachedWindow<T, R>, String) + Send>;
+pub type WebviewIpcHandler<T, R, M = String> = Box<dyn Fn(DetachedWindow<T, R>, M) + Send>;
+
+/// Binary IPC handler.
+pub type WebviewBinaryIpcHandler<T, R> = WebviewIpcHandler<T, R, Vec<u8>>;
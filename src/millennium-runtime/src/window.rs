@@ -27,7 +27,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 use crate::{
 	http::{Request as HttpRequest, Response as HttpResponse},
 	menu::{Menu, MenuEntry, MenuHash, MenuId},
-	webview::{WebviewAttributes, WebviewIpcHandler},
+	webview::{WebviewAttributes, WebviewBinaryIpcHandler, WebviewIpcHandler, WebviewPermissionHandler, WebviewRequestInterceptor},
 	Dispatch, Runtime, UserEvent, WindowBuilder
 };
 
@@ -79,7 +79,12 @@ pub enum WindowEvent {
 	/// changes the theme.
 	///
 	/// Currently only implemented on Windows.
-	ThemeChanged(Theme)
+	ThemeChanged(Theme),
+	/// The window's webview has been suspended or resumed.
+	///
+	/// The parameter is true if the webview has been suspended, and false if it
+	/// has been resumed.
+	Suspended(bool)
 }
 
 /// The file drop event payload.
@@ -226,6 +231,52 @@ impl Default for CursorIcon {
 	}
 }
 
+/// The border or corner of a window to resize from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResizeDirection {
+	East,
+	North,
+	NorthEast,
+	NorthWest,
+	South,
+	SouthEast,
+	SouthWest,
+	West
+}
+
+/// A rectangle relative to the top-left corner of the window's client area, used for the drag regions of
+/// [`Dispatch::set_drag_regions`].
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Rect {
+	pub position: dpi::Position,
+	pub size: dpi::Size
+}
+
+impl Rect {
+	pub fn new<P: Into<dpi::Position>, S: Into<dpi::Size>>(position: P, size: S) -> Self {
+		Self {
+			position: position.into(),
+			size: size.into()
+		}
+	}
+}
+
+/// The display preferences the user set in the system's accessibility settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibilityPreferences {
+	/// Animations and motion effects should be minimized.
+	pub reduce_motion: bool,
+	/// Content should be drawn with higher contrast.
+	pub increase_contrast: bool,
+	/// Translucent effects such as blur and vibrancy should be replaced with opaque backgrounds.
+	pub reduce_transparency: bool
+}
+
+/// Called once the webview of a [`PendingWindow`] has been created, or with the error that prevented it.
+pub type WindowCreatedHandler = Box<dyn FnOnce(crate::Result<()>) + Send>;
+
 /// A webview window that has yet to be built.
 pub struct PendingWindow<T: UserEvent, R: Runtime<T>> {
 	/// The label that the window will be named.
@@ -242,6 +293,16 @@ pub struct PendingWindow<T: UserEvent, R: Runtime<T>> {
 	/// How to handle IPC calls on the webview window.
 	pub ipc_handler: Option<WebviewIpcHandler<T, R>>,
 
+	/// How to handle binary IPC messages on the webview window.
+	pub binary_ipc_handler: Option<WebviewBinaryIpcHandler<T, R>>,
+
+	/// How to answer the webview's camera, microphone, geolocation and other permission requests. Requests
+	/// are denied when this is `None`.
+	pub permission_handler: Option<WebviewPermissionHandler>,
+
+	/// Rewrites the headers of the outgoing requests whose URL matches one of the filters.
+	pub request_interceptor: Option<(Vec<String>, WebviewRequestInterceptor)>,
+
 	/// The resolved URL to load on the webview.
 	pub url: String,
 
@@ -249,7 +310,10 @@ pub struct PendingWindow<T: UserEvent, R: Runtime<T>> {
 	pub menu_ids: Arc<Mutex<HashMap<MenuHash, MenuId>>>,
 
 	/// A HashMap mapping JS event names with associated listener ids.
-	pub js_event_listeners: Arc<Mutex<HashMap<JsEventListenerKey, HashSet<u64>>>>
+	pub js_event_listeners: Arc<Mutex<HashMap<JsEventListenerKey, HashSet<u64>>>>,
+
+	/// Called once the webview has been created on the main thread.
+	pub on_created: Option<WindowCreatedHandler>
 }
 
 pub fn is_label_valid(label: &str) -> bool {
@@ -283,9 +347,13 @@ impl<T: UserEvent, R: Runtime<T>> PendingWindow<T, R> {
 				uri_scheme_protocols: Default::default(),
 				label,
 				ipc_handler: None,
+				binary_ipc_handler: None,
+				permission_handler: None,
+				request_interceptor: None,
 				url: "millennium://localhost".to_string(),
 				menu_ids: Arc::new(Mutex::new(menu_ids)),
-				js_event_listeners: Default::default()
+				js_event_listeners: Default::default(),
+				on_created: None
 			})
 		}
 	}
@@ -308,9 +376,13 @@ impl<T: UserEvent, R: Runtime<T>> PendingWindow<T, R> {
 				uri_scheme_protocols: Default::default(),
 				label,
 				ipc_handler: None,
+				binary_ipc_handler: None,
+				permission_handler: None,
+				request_interceptor: None,
 				url: "millennium://localhost".to_string(),
 				menu_ids: Arc::new(Mutex::new(menu_ids)),
-				js_event_listeners: Default::default()
+				js_event_listeners: Default::default(),
+				on_created: None
 			})
 		}
 	}
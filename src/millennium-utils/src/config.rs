@@ -151,6 +151,14 @@ pub enum BundleTarget {
 	One(BundleType)
 }
 
+/// Sets the metadata of `schema`, like `schemars` does for the fields of derived schemas.
+#[cfg(feature = "schema")]
+fn with_metadata(schema: schemars::schema::Schema, metadata: schemars::schema::Metadata) -> schemars::schema::Schema {
+	let mut schema = schema.into_object();
+	schema.metadata = Some(Box::new(metadata));
+	schema.into()
+}
+
 #[cfg(feature = "schema")]
 impl schemars::JsonSchema for BundleTarget {
 	fn schema_name() -> String {
@@ -168,14 +176,14 @@ impl schemars::JsonSchema for BundleTarget {
 				..Default::default()
 			}
 			.into(),
-			schemars::_private::apply_metadata(
+			with_metadata(
 				gen.subschema_for::<Vec<BundleType>>(),
 				schemars::schema::Metadata {
 					description: Some("A list of bundle targets.".to_owned()),
 					..Default::default()
 				}
 			),
-			schemars::_private::apply_metadata(
+			with_metadata(
 				gen.subschema_for::<BundleType>(),
 				schemars::schema::Metadata {
 					description: Some("A single bundle target.".to_owned()),
@@ -273,7 +281,21 @@ pub struct DebConfig {
 	pub depends: Option<Vec<String>>,
 	/// The files to include on the package.
 	#[serde(default)]
-	pub files: HashMap<PathBuf, PathBuf>
+	pub files: HashMap<PathBuf, PathBuf>,
+	/// Path to a maintainer script run before the package is unpacked, on install and upgrade.
+	///
+	/// Maintainer scripts must be executable and start with a `#!` line. Debian policy expects them to be idempotent
+	/// and to fail on the first error (e.g. with `set -e`); dpkg passes the action (`install`, `upgrade`, `remove`,
+	/// etc.) as the first argument. See <https://www.debian.org/doc/debian-policy/ch-maintainerscripts.html>.
+	pub preinst: Option<PathBuf>,
+	/// Path to a maintainer script run after the package is unpacked, e.g. to update icon caches or register MIME
+	/// handlers. See [`Self::preinst`] for the requirements.
+	pub postinst: Option<PathBuf>,
+	/// Path to a maintainer script run before the package is removed. See [`Self::preinst`] for the requirements.
+	pub prerm: Option<PathBuf>,
+	/// Path to a maintainer script run after the package is removed or purged. See [`Self::preinst`] for the
+	/// requirements.
+	pub postrm: Option<PathBuf>
 }
 
 fn de_minimum_system_version<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
@@ -318,7 +340,10 @@ pub struct MacConfig {
 	/// Provider short name for notarization.
 	pub provider_short_name: Option<String>,
 	/// Path to the entitlements file.
-	pub entitlements: Option<String>
+	pub entitlements: Option<String>,
+	/// Name of the keychain profile storing the notarization credentials, created with `xcrun notarytool
+	/// store-credentials`. Takes precedence over the `APPLE_ID` and `APPLE_API_KEY` environment variables.
+	pub notarization_profile: Option<String>
 }
 
 impl Default for MacConfig {
@@ -330,7 +355,8 @@ impl Default for MacConfig {
 			license: None,
 			signing_identity: None,
 			provider_short_name: None,
-			entitlements: None
+			entitlements: None,
+			notarization_profile: None
 		}
 	}
 }
@@ -428,7 +454,8 @@ pub struct WixConfig {
 #[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub enum WebviewInstallMode {
-	/// Do not install WebView2 as part of the Windows Installer.
+	/// Do not install WebView2 as part of the Windows Installer. Use this when WebView2 is known to be preinstalled, e.g.
+	/// on Windows 11 or in managed deployments, to keep the installer small.
 	Skip,
 	/// Downloads the WebView2 Evergreen bootstrapper and runs it. Requires an internet connection. Results in a smaller
 	/// installer size, but is not recommended on Windows 7.
@@ -484,6 +511,9 @@ pub struct WindowsConfig {
 	pub digest_algorithm: Option<String>,
 	/// Specifies the SHA1 hash of the signing certificate.
 	pub certificate_thumbprint: Option<String>,
+	/// Path to a PFX certificate file to sign with, used when [`Self::certificate_thumbprint`] isn't set.
+	/// Its password is read from the `WINDOWS_CERTIFICATE_PASSWORD` environment variable.
+	pub certificate_path: Option<PathBuf>,
 	/// Server to use during timestamping.
 	pub timestamp_url: Option<String>,
 	/// Whether to use Time-Stamp Protocol (TSP, RFC 3161) for the timestamp
@@ -518,6 +548,7 @@ impl Default for WindowsConfig {
 		Self {
 			digest_algorithm: None,
 			certificate_thumbprint: None,
+			certificate_path: None,
 			timestamp_url: None,
 			tsp: false,
 			webview_install_mode: Default::default(),
@@ -870,7 +901,10 @@ pub struct WindowConfig {
 	#[serde(default)]
 	pub skip_taskbar: bool,
 	/// The initial window theme. Defaults to the system theme. Currently only implemented on Windows and macOS 10.14+.
-	pub theme: Option<crate::Theme>
+	pub theme: Option<crate::Theme>,
+	/// The layout direction of the window's menu bar and controls, either `ltr` or `rtl`. Defaults to the direction of
+	/// the system locale.
+	pub layout_direction: Option<crate::LayoutDirection>
 }
 
 impl Default for WindowConfig {
@@ -899,7 +933,8 @@ impl Default for WindowConfig {
 			titlebar_hidden: default_titlebar_hidden(),
 			always_on_top: false,
 			skip_taskbar: false,
-			theme: None
+			theme: None,
+			layout_direction: None
 		}
 	}
 }
@@ -1128,6 +1163,34 @@ pub struct SecurityConfig {
 	pub dangerous_disable_asset_csp_modification: DisabledCspModificationKind
 }
 
+/// Configuration for how the embedded frontend assets are served.
+///
+/// By default, assets are served through the `millennium://` custom protocol. None of the supported webviews (WebView2,
+/// WKWebView, WebKitGTK) allow service workers to be registered from a custom protocol, so apps that rely on service
+/// workers (e.g. for offline caching or push handling shared with a web build) need to be served over HTTP instead.
+/// `http://localhost` is treated as a secure context by all of them, which is what service worker registration
+/// requires.
+///
+/// Requests must carry a token generated on every launch, which Millennium hands to the webview as a cookie when a
+/// window is created, so other processes on the machine can't read the assets. Note that
+/// `WindowBuilder::on_web_resource_request` handlers do not apply to assets served this way.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EmbeddedAssetsConfig {
+	/// Serve the embedded assets from `http://localhost:<port>` instead of the custom protocol, which makes it
+	/// possible to register service workers.
+	///
+	/// Set it to `0` to bind a free port picked by the OS on every launch, which can't fail because another app took
+	/// the port. However, the origin is the key for service worker registrations, caches and `localStorage`, so a
+	/// different port on every launch loses them; use a fixed port if the app relies on them. If a fixed port is
+	/// already in use, the app fails to start.
+	///
+	/// Only applies when the assets are embedded; `millennium dev` with a dev server URL keeps using that URL.
+	pub localhost_port: Option<u16>
+}
+
 /// Defines an allowlist type.
 pub trait Allowlist {
 	/// Returns all features associated with the allowlist struct.
@@ -1231,7 +1294,10 @@ pub struct FsAllowlistConfig {
 	pub rename: bool,
 	/// Check if file on local filesystem exists.
 	#[serde(default)]
-	pub exists: bool
+	pub exists: bool,
+	/// Watch files and directories on local filesystem for changes.
+	#[serde(default)]
+	pub watch: bool
 }
 
 impl Allowlist for FsAllowlistConfig {
@@ -1247,7 +1313,8 @@ impl Allowlist for FsAllowlistConfig {
 			remove_dir: true,
 			remove_file: true,
 			rename: true,
-			exists: true
+			exists: true,
+			watch: true
 		};
 		let mut features = allowlist.to_features();
 		features.push("fs-all");
@@ -1268,6 +1335,7 @@ impl Allowlist for FsAllowlistConfig {
 			check_feature!(self, features, remove_file, "fs-remove-file");
 			check_feature!(self, features, rename, "fs-rename");
 			check_feature!(self, features, exists, "fs-exists");
+			check_feature!(self, features, watch, "fs-watch");
 			features
 		}
 	}
@@ -1350,6 +1418,9 @@ pub struct WindowAllowlistConfig {
 	/// Allows start dragging on the window.
 	#[serde(default)]
 	pub start_dragging: bool,
+	/// Allows dragging files in the `fs` scope out of the window.
+	#[serde(default)]
+	pub start_drag: bool,
 	/// Allows opening the system dialog to print the window content.
 	#[serde(default)]
 	pub print: bool
@@ -1382,6 +1453,7 @@ impl Allowlist for WindowAllowlistConfig {
 			set_icon: true,
 			set_skip_taskbar: true,
 			start_dragging: true,
+			start_drag: true,
 			print: true
 		};
 		let mut features = allowlist.to_features();
@@ -1417,6 +1489,7 @@ impl Allowlist for WindowAllowlistConfig {
 			check_feature!(self, features, set_icon, "window-set-icon");
 			check_feature!(self, features, set_skip_taskbar, "window-set-skip-taskbar");
 			check_feature!(self, features, start_dragging, "window-start-dragging");
+			check_feature!(self, features, start_drag, "window-start-drag");
 			check_feature!(self, features, print, "window-print");
 			features
 		}
@@ -2089,7 +2162,10 @@ pub struct MillenniumConfig {
 	/// MacOS private API configuration. Enables the transparent background API
 	/// and sets the `fullScreenEnabled` preference to `true`.
 	#[serde(rename = "macOSPrivateApi", default)]
-	pub macos_private_api: bool
+	pub macos_private_api: bool,
+	/// How the embedded frontend assets are served.
+	#[serde(default)]
+	pub embedded_assets: EmbeddedAssetsConfig
 }
 
 impl MillenniumConfig {
@@ -2261,6 +2337,10 @@ pub struct UpdaterConfig {
 	///   each platform.
 	/// - `https://updates.app.dev/{{target}}?version={{current_version}}&arch={{arch}}`: a dedicated API with
 	///   positional and query string arguments.
+	///
+	/// AppImage bundles embed the first endpoint's directory as their update information for AppImageUpdate, e.g.
+	/// `zsync|https://my.cdn.com/app_amd64.AppImage.zsync` for the first example. Upload the generated `.zsync` file and
+	/// the AppImage next to it with every release.
 	pub endpoints: Option<Vec<UpdaterEndpoint>>,
 	/// Signature public key.
 	#[serde(default)] // use default just so the schema doesn't flag it as required
@@ -2764,6 +2844,17 @@ mod build {
 		}
 	}
 
+	impl ToTokens for crate::LayoutDirection {
+		fn to_tokens(&self, tokens: &mut TokenStream) {
+			let prefix = quote! { ::millennium::utils::LayoutDirection };
+
+			tokens.append_all(match self {
+				Self::LeftToRight => quote! { #prefix::LeftToRight },
+				Self::RightToLeft => quote! { #prefix::RightToLeft }
+			})
+		}
+	}
+
 	impl ToTokens for WindowConfig {
 		fn to_tokens(&self, tokens: &mut TokenStream) {
 			let label = str_lit(&self.label);
@@ -2790,6 +2881,7 @@ mod build {
 			let always_on_top = self.always_on_top;
 			let skip_taskbar = self.skip_taskbar;
 			let theme = opt_lit(self.theme.as_ref());
+			let layout_direction = opt_lit(self.layout_direction.as_ref());
 
 			literal_struct!(
 				tokens,
@@ -2817,7 +2909,8 @@ mod build {
 				titlebar_hidden,
 				always_on_top,
 				skip_taskbar,
-				theme
+				theme,
+				layout_direction
 			);
 		}
 	}
@@ -3124,6 +3217,14 @@ mod build {
 		}
 	}
 
+	impl ToTokens for EmbeddedAssetsConfig {
+		fn to_tokens(&self, tokens: &mut TokenStream) {
+			let localhost_port = opt_lit(self.localhost_port.as_ref());
+
+			literal_struct!(tokens, EmbeddedAssetsConfig, localhost_port);
+		}
+	}
+
 	impl ToTokens for SystemTrayConfig {
 		fn to_tokens(&self, tokens: &mut TokenStream) {
 			let icon_as_template = self.icon_as_template;
@@ -3269,8 +3370,22 @@ mod build {
 			let system_tray = opt_lit(self.system_tray.as_ref());
 			let allowlist = &self.allowlist;
 			let macos_private_api = self.macos_private_api;
+			let embedded_assets = &self.embedded_assets;
 
-			literal_struct!(tokens, MillenniumConfig, pattern, windows, cli, bundle, updater, security, system_tray, allowlist, macos_private_api);
+			literal_struct!(
+				tokens,
+				MillenniumConfig,
+				pattern,
+				windows,
+				cli,
+				bundle,
+				updater,
+				security,
+				system_tray,
+				allowlist,
+				macos_private_api,
+				embedded_assets
+			);
 		}
 	}
 
@@ -3305,6 +3420,8 @@ mod build {
 
 #[cfg(test)]
 mod test {
+	use proptest::prelude::*;
+
 	use super::*;
 
 	// TODO: create a test that compares a config to a json config
@@ -3361,7 +3478,8 @@ mod test {
 			},
 			allowlist: AllowlistConfig::default(),
 			system_tray: None,
-			macos_private_api: false
+			macos_private_api: false,
+			embedded_assets: EmbeddedAssetsConfig::default()
 		};
 
 		// create a build config
@@ -3383,4 +3501,47 @@ mod test {
 		assert_eq!(d_path, AppUrl::Url(WindowUrl::External(Url::parse("http://localhost:8080").unwrap())));
 		assert_eq!(d_windows, millennium.windows);
 	}
+
+	// Keys of the config, so generated objects reach past the top level instead of failing on the first unknown field.
+	const CONFIG_KEYS: &[&str] = &[
+		"$schema", "millennium", "package", "build", "plugins", "productName", "version", "pattern", "use", "options", "key", "windows", "label",
+		"url", "width", "height", "x", "y", "minWidth", "maxHeight", "theme", "fileDropEnabled", "cli", "args", "subcommands", "name", "short",
+		"takesValue", "multiple", "possibleValues", "bundle", "active", "targets", "identifier", "icon", "resources", "externalBin", "deb",
+		"depends", "files", "macos", "frameworks", "minimumSystemVersion", "windows", "wix", "webviewInstallMode", "type", "path", "allowlist",
+		"all", "fs", "scope", "shell", "open", "execute", "sidecar", "cmd", "http", "request", "protocol", "asset", "assetScope", "security",
+		"csp", "devCsp", "freezePrototype", "dangerousDisableAssetCspModification", "updater", "dialog", "endpoints", "pubkey", "installMode",
+		"systemTray", "iconPath", "iconAsTemplate", "devPath", "distDir", "runner", "beforeDevCommand", "features", "withGlobalMillennium"
+	];
+
+	fn arbitrary_json() -> impl Strategy<Value = serde_json::Value> {
+		let leaf = prop_oneof![
+			Just(serde_json::Value::Null),
+			any::<bool>().prop_map(serde_json::Value::from),
+			any::<i64>().prop_map(serde_json::Value::from),
+			any::<f64>().prop_map(serde_json::Value::from),
+			prop::sample::select(CONFIG_KEYS).prop_map(serde_json::Value::from),
+			"\\PC{0,16}".prop_map(serde_json::Value::from)
+		];
+		leaf.prop_recursive(5, 128, 8, |inner| {
+			let key = prop_oneof![prop::sample::select(CONFIG_KEYS).prop_map(String::from), "[a-zA-Z]{1,8}"];
+			prop_oneof![
+				prop::collection::vec(inner.clone(), 0..6).prop_map(serde_json::Value::Array),
+				prop::collection::btree_map(key, inner, 0..8).prop_map(|map| serde_json::Value::Object(map.into_iter().collect()))
+			]
+		})
+	}
+
+	proptest! {
+		#![proptest_config(ProptestConfig::with_cases(10000))]
+
+		#[test]
+		fn config_never_panics_on_arbitrary_json(value in arbitrary_json()) {
+			let _ = serde_json::from_value::<Config>(value);
+		}
+
+		#[test]
+		fn config_never_panics_on_arbitrary_input(input in "\\PC*") {
+			let _ = serde_json::from_str::<Config>(&input);
+		}
+	}
 }
@@ -35,6 +35,9 @@ pub const CSP_TOKEN: &str = "__MILLENNIUM_CSP__";
 pub const SCRIPT_NONCE_TOKEN: &str = "__MILLENNIUM_SCRIPT_NONCE__";
 /// The token used for style nonces.
 pub const STYLE_NONCE_TOKEN: &str = "__MILLENNIUM_STYLE_NONCE__";
+/// The token replaced with the nonce generated for each page load. It must differ from the nonce's global,
+/// `window.__MILLENNIUM_CSP_NONCE__`, which inline scripts may reference.
+pub const CSP_NONCE_TOKEN: &str = "__MILLENNIUM_CSP_NONCE_TOKEN__";
 
 /// Parses the given HTML string.
 pub fn parse(html: String) -> NodeRef {
@@ -77,6 +80,13 @@ pub fn inject_nonce_token(document: &mut NodeRef, dangerous_disable_asset_csp_mo
 	}
 }
 
+/// Inject nonce tokens to inline scripts, for HTML that doesn't have its inline script hashes computed at build time.
+pub fn inject_inline_script_nonce_token(document: &mut NodeRef, dangerous_disable_asset_csp_modification: &DisabledCspModificationKind) {
+	if dangerous_disable_asset_csp_modification.can_modify("script-src") {
+		inject_nonce(document, "script:not([src])", SCRIPT_NONCE_TOKEN);
+	}
+}
+
 /// Injects a content security policy to the HTML.
 pub fn inject_csp(document: &mut NodeRef, csp: &str) {
 	with_head(document, |head| {
@@ -89,6 +99,19 @@ pub fn inject_csp_token(document: &mut NodeRef) {
 	inject_csp(document, CSP_TOKEN)
 }
 
+/// Injects a `<meta property="csp-nonce">` tag holding the per-load nonce token, so scripts can read the nonce.
+pub fn inject_csp_nonce_token(document: &mut NodeRef) {
+	with_head(document, |head| {
+		head.append(NodeRef::new_element(
+			QualName::new(None, ns!(html), LocalName::from("meta")),
+			vec![
+				(ExpandedName::new(ns!(), LocalName::from("property")), Attribute { prefix: None, value: "csp-nonce".into() }),
+				(ExpandedName::new(ns!(), LocalName::from("nonce")), Attribute { prefix: None, value: CSP_NONCE_TOKEN.into() }),
+			]
+		));
+	});
+}
+
 fn create_csp_meta_tag(csp: &str) -> NodeRef {
 	NodeRef::new_element(
 		QualName::new(None, ns!(html), LocalName::from("meta")),
@@ -208,4 +231,14 @@ mod tests {
 			);
 		}
 	}
+
+	#[test]
+	fn csp_nonce() {
+		let mut document = kuchiki::parse_html().one("<html><head></head></html>".to_string());
+		super::inject_csp_nonce_token(&mut document);
+		assert_eq!(
+			document.to_string(),
+			format!(r#"<html><head><meta nonce="{}" property="csp-nonce"></head><body></body></html>"#, super::CSP_NONCE_TOKEN)
+		);
+	}
 }
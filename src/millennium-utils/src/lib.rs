@@ -109,6 +109,19 @@ impl Display for Theme {
 	}
 }
 
+/// Layout direction of the native window chrome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum LayoutDirection {
+	/// Left-to-right, as used by most scripts.
+	#[serde(rename = "ltr")]
+	LeftToRight,
+	/// Right-to-left, as used by e.g. Arabic and Hebrew.
+	#[serde(rename = "rtl")]
+	RightToLeft
+}
+
 /// Information about environment variables.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -35,6 +35,21 @@
 
 	const aesGcmKey = await window.crypto.subtle.importKey('raw', aesGcmKeyRaw, 'AES-GCM', true, [ 'encrypt' ]);
 
+	/**
+	 * Creates a nonce from a 48-bit big-endian millisecond timestamp followed by 48 random bits, so the backend only has
+	 * to remember recent nonces to reject replayed payloads.
+	 * @return {Uint8Array}
+	 */
+	function createNonce() {
+		const nonce = window.crypto.getRandomValues(new Uint8Array(12));
+		let timestamp = Date.now();
+		for (let i = 5; i >= 0; i--) {
+			nonce[i] = timestamp % 256;
+			timestamp = Math.floor(timestamp / 256);
+		}
+		return nonce;
+	}
+
 	/**
 	 * @param {object} data
 	 * @return {Promise<{nonce: number[], payload: number[]}>}
@@ -42,7 +57,7 @@
 	async function encrypt(data) {
 		const algorithm = Object.create(null);
 		algorithm.name = 'AES-GCM';
-		algorithm.iv = window.crypto.getRandomValues(new Uint8Array(12));
+		algorithm.iv = createNonce();
 
 		const encoder = new TextEncoder();
 		const payloadRaw = encoder.encode(JSON.stringify(data));
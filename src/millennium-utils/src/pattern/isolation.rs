@@ -16,8 +16,11 @@
 
 use std::array::TryFromSliceError;
 use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::fmt::{Debug, Formatter};
 use std::string::FromUtf8Error;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use aes_gcm::aead::Aead;
 use aes_gcm::{aead::NewAead, Aes256Gcm, Nonce};
@@ -27,6 +30,9 @@ use serialize_to_javascript::{default_template, Template};
 /// The style for the isolation iframe.
 pub const IFRAME_STYLE: &str = "#__millennium_isolation__ { display: none !important }";
 
+/// How far the timestamp of a payload's nonce may be from the current time. Nonces are only remembered for this long.
+const NONCE_WINDOW: Duration = Duration::from_secs(30);
+
 /// Errors that can occur during Isolation keys generation.
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -43,6 +49,14 @@ pub enum Error {
 	#[error("Nonce: {0}")]
 	NonceSize(#[from] TryFromSliceError),
 
+	/// Nonce was already used by a previously decrypted payload
+	#[error("Nonce was already used")]
+	ReplayedNonce,
+
+	/// Nonce timestamp was outside of the replay window
+	#[error("Nonce has expired")]
+	ExpiredNonce,
+
 	/// Payload was not valid utf8
 	#[error("{0}")]
 	Utf8(#[from] FromUtf8Error),
@@ -89,13 +103,18 @@ impl AesGcmPair {
 #[derive(Debug, Clone)]
 pub struct Keys {
 	/// AES-GCM key
-	aes_gcm: AesGcmPair
+	aes_gcm: AesGcmPair,
+	/// Nonces of the payloads decrypted within the replay window, used to reject replayed payloads. Nonces start with
+	/// a big-endian timestamp, so they are ordered by time.
+	used_nonces: Arc<Mutex<BTreeSet<[u8; 12]>>>
 }
 
 impl Keys {
 	/// Securely generate required keys for Isolation encryption.
 	pub fn new() -> Result<Self, Error> {
-		AesGcmPair::new().map(|aes_gcm| Self { aes_gcm }).map_err(Into::into)
+		AesGcmPair::new()
+			.map(|aes_gcm| Self { aes_gcm, used_nonces: Default::default() })
+			.map_err(Into::into)
 	}
 
 	/// The AES-GCM data (and raw data).
@@ -104,6 +123,9 @@ impl Keys {
 	}
 
 	/// Decrypts a message using the generated keys.
+	///
+	/// Nonces start with the 48-bit millisecond timestamp of their payload. Each nonce is only accepted once and only
+	/// within [`NONCE_WINDOW`] of its timestamp, so a payload captured from the main frame cannot be replayed.
 	pub fn decrypt(&self, raw: RawIsolationPayload<'_>) -> Result<String, Error> {
 		let RawIsolationPayload { nonce, payload } = raw;
 		let nonce: [u8; 12] = nonce.as_ref().try_into()?;
@@ -113,10 +135,33 @@ impl Keys {
 			.decrypt(Nonce::from_slice(&nonce), payload.as_ref())
 			.map_err(|_| self::Error::Aes)?;
 
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+		let window = NONCE_WINDOW.as_millis() as u64;
+		let timestamp = nonce_timestamp(&nonce);
+		if timestamp.saturating_add(window) < now || timestamp > now.saturating_add(window) {
+			return Err(self::Error::ExpiredNonce);
+		}
+
+		// only record nonces of authentic payloads so forged messages can't grow the set
+		let mut used_nonces = self.used_nonces.lock().expect("poisoned isolation nonce set");
+		// nonces from before the window would be rejected as expired anyway
+		let mut oldest = [0u8; 12];
+		oldest[..6].copy_from_slice(&now.saturating_sub(window).to_be_bytes()[2..]);
+		*used_nonces = used_nonces.split_off(&oldest);
+		if !used_nonces.insert(nonce) {
+			return Err(self::Error::ReplayedNonce);
+		}
+
 		String::from_utf8(bytes).map_err(Into::into)
 	}
 }
 
+fn nonce_timestamp(nonce: &[u8; 12]) -> u64 {
+	let mut timestamp = [0u8; 8];
+	timestamp[2..].copy_from_slice(&nonce[..6]);
+	u64::from_be_bytes(timestamp)
+}
+
 /// Raw representation of
 #[derive(Debug, serde::Deserialize)]
 pub struct RawIsolationPayload<'a> {
@@ -158,4 +203,53 @@ mod test {
 		let _ = super::Keys::new()?;
 		Ok(())
 	}
+
+	fn encrypt(keys: &super::Keys, timestamp: u64, random: u8) -> String {
+		use aes_gcm::{aead::Aead, Nonce};
+
+		let mut nonce = [random; 12];
+		nonce[..6].copy_from_slice(&timestamp.to_be_bytes()[2..]);
+		let payload = keys.aes_gcm().key().encrypt(&Nonce::from(nonce), b"{}".as_ref()).unwrap();
+		serde_json::json!({ "nonce": nonce, "payload": payload }).to_string()
+	}
+
+	fn now() -> u64 {
+		std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis() as u64
+	}
+
+	#[test]
+	fn reject_replayed_payload() -> Result<(), Box<dyn std::error::Error>> {
+		let keys = super::Keys::new()?;
+		let json = encrypt(&keys, now(), 7);
+
+		assert_eq!(keys.decrypt(json.as_str().try_into()?)?, "{}");
+		assert!(matches!(keys.decrypt(json.as_str().try_into()?), Err(super::Error::ReplayedNonce)));
+		Ok(())
+	}
+
+	#[test]
+	fn reject_expired_payload() -> Result<(), Box<dyn std::error::Error>> {
+		let keys = super::Keys::new()?;
+		let window = super::NONCE_WINDOW.as_millis() as u64;
+
+		let json = encrypt(&keys, now() - window - 1000, 7);
+		assert!(matches!(keys.decrypt(json.as_str().try_into()?), Err(super::Error::ExpiredNonce)));
+		let json = encrypt(&keys, now() + window + 1000, 7);
+		assert!(matches!(keys.decrypt(json.as_str().try_into()?), Err(super::Error::ExpiredNonce)));
+		Ok(())
+	}
+
+	#[test]
+	fn forget_nonces_outside_of_window() -> Result<(), Box<dyn std::error::Error>> {
+		let keys = super::Keys::new()?;
+		let window = super::NONCE_WINDOW.as_millis() as u64;
+		let mut old_nonce = [0u8; 12];
+		old_nonce[..6].copy_from_slice(&(now() - window - 1000).to_be_bytes()[2..]);
+		keys.used_nonces.lock().unwrap().insert(old_nonce);
+
+		keys.decrypt(encrypt(&keys, now(), 1).as_str().try_into()?)?;
+		keys.decrypt(encrypt(&keys, now(), 2).as_str().try_into()?)?;
+		assert_eq!(keys.used_nonces.lock().unwrap().len(), 2);
+		Ok(())
+	}
 }
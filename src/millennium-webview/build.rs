@@ -0,0 +1,66 @@
+// Copyright 2022 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{env, path::PathBuf};
+
+fn main() {
+	let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+	if matches!(target_os.as_str(), "linux" | "dragonfly" | "freebsd" | "openbsd" | "netbsd") {
+		build_web_extension();
+	}
+}
+
+/// Compiles the WebKitGTK web process extension implementing request interceptors. Request interceptors are
+/// unsupported if it can't be compiled, e.g. without the WebKitGTK web extension headers.
+fn build_web_extension() {
+	const SOURCE: &str = "src/webview/webkitgtk/web_extension.c";
+	println!("cargo:rerun-if-changed={}", SOURCE);
+	println!("cargo:rustc-check-cfg=cfg(web_extension)");
+
+	let library = match pkg_config::Config::new().cargo_metadata(false).probe("webkit2gtk-web-extension-4.0") {
+		Ok(library) => library,
+		Err(e) => {
+			println!("cargo:warning=request interceptors are unsupported: {}", e);
+			return;
+		}
+	};
+
+	let output = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("libmillennium_web_extension.so");
+	let mut command = cc::Build::new().get_compiler().to_command();
+	command.args(["-shared", "-fPIC", "-o"]).arg(&output).arg(SOURCE);
+	for path in &library.include_paths {
+		command.arg("-I").arg(path);
+	}
+	for (name, value) in &library.defines {
+		command.arg(match value {
+			Some(value) => format!("-D{}={}", name, value),
+			None => format!("-D{}", name)
+		});
+	}
+	for path in &library.link_paths {
+		command.arg("-L").arg(path);
+	}
+	for lib in &library.libs {
+		command.arg(format!("-l{}", lib));
+	}
+
+	match command.output() {
+		Ok(output) if output.status.success() => println!("cargo:rustc-cfg=web_extension"),
+		Ok(output) => {
+			let stderr = String::from_utf8_lossy(&output.stderr);
+			println!("cargo:warning=request interceptors are unsupported: {}", stderr.lines().next().unwrap_or_default());
+		}
+		Err(e) => println!("cargo:warning=request interceptors are unsupported: {}", e)
+	}
+}
@@ -156,6 +156,10 @@ pub enum Error {
 	#[cfg(target_os = "windows")]
 	#[error("WebView2 error: {0}")]
 	WebView2Error(webview2_com::Error),
+	#[error("Failed to print to PDF: {0}")]
+	PrintToPdf(String),
+	#[error("Failed to take a snapshot: {0}")]
+	Snapshot(String),
 	#[error("Duplicate custom protocol registered: {0}")]
 	DuplicateCustomProtocol(String),
 	#[error("Invalid header name: {0}")]
@@ -168,9 +172,14 @@ pub enum Error {
 	InvalidStatusCode(#[from] InvalidStatusCode),
 	#[error("Invalid method: {0}")]
 	InvalidMethod(#[from] InvalidMethod),
+	#[error("{0} is not supported on this platform")]
+	UnsupportedPlatform(&'static str),
 	#[error("Infallible error, something went really wrong: {0}")]
 	Infallible(#[from] std::convert::Infallible),
 	#[cfg(target_os = "android")]
 	#[error("JNI error: {0}")]
-	JNIError(#[from] jni::errors::Error)
+	JNIError(#[from] jni::errors::Error),
+	#[cfg(target_os = "android")]
+	#[error("Failed to resolve the Android System WebView provider")]
+	WebViewProviderNotFound
 }
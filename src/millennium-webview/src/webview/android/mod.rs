@@ -14,46 +14,101 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{collections::HashSet, ffi::c_void, ptr::null_mut, rc::Rc, sync::RwLock};
+use std::{collections::HashSet, ffi::c_void, path::PathBuf, ptr::null_mut, rc::Rc, sync::RwLock};
 
 use jni::{
-	objects::{JClass, JObject},
+	objects::{JClass, JObject, JString},
 	sys::jobject,
-	JNIEnv
+	JNIEnv, JavaVM
 };
-use once_Cell::sync::Lazy;
+use millennium_core::platform::android::ndk_glue::{MainPipe, WebViewMessage};
+use once_cell::sync::Lazy;
 
-use super::{WebContext, WebViewAttributes};
-use crate::{application::window::Window, Result};
+use super::{insert_css_script, remove_css_script, CssId, InitScriptId, PdfSettings, WebContext, WebViewAttributes};
+use crate::{application::window::Window, Error, Result};
 static IPC: Lazy<RwLock<UnsafeIpc>> = Lazy::new(|| RwLock::new(UnsafeIpc(null_mut())));
 
 pub struct InnerWebView {
 	pub window: Rc<Window>,
-	pub attributes: WebViewAttributes
+	pub attributes: WebViewAttributes,
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	devtools_enabled: std::cell::Cell<bool>
 }
 
 impl InnerWebView {
 	pub fn new(window: Rc<Window>, attributes: WebViewAttributes, _web_context: Option<&mut WebContext>) -> Result<Self> {
-		Ok(Self { window, attributes })
+		Ok(Self {
+			window,
+			#[cfg(any(debug_assertions, feature = "devtools"))]
+			devtools_enabled: std::cell::Cell::new(attributes.devtools),
+			attributes
+		})
 	}
 
-	pub fn print(&self) {}
+	pub fn print(&self) {
+		MainPipe::send(WebViewMessage::Print);
+	}
+
+	pub fn print_to_pdf(&self, _path: PathBuf, _settings: PdfSettings, _callback: Box<dyn FnOnce(Result<PathBuf>)>) -> Result<()> {
+		Err(Error::PrintToPdf("unsupported on Android".into()))
+	}
 
-	pub fn eval(&self, _js: &str) -> Result<()> {
+	pub fn take_snapshot(&self, _callback: Box<dyn FnOnce(Result<Vec<u8>>)>) -> Result<()> {
+		Err(Error::Snapshot("unsupported on Android".into()))
+	}
+
+	pub fn eval(&self, js: &str) -> Result<()> {
+		MainPipe::send(WebViewMessage::EvaluateJavascript(js.to_string()));
 		Ok(())
 	}
 
+	pub fn add_init_script(&self, _id: InitScriptId, _js: &str) -> Result<()> {
+		Err(Error::UnsupportedPlatform("Adding initialization scripts after the webview is built"))
+	}
+
+	pub fn remove_init_script(&self, _id: InitScriptId) -> Result<()> {
+		Err(Error::UnsupportedPlatform("Removing initialization scripts"))
+	}
+
+	pub fn insert_css(&self, id: CssId, css: &str) -> Result<()> {
+		self.eval(&insert_css_script(id, css))
+	}
+
+	pub fn remove_css(&self, id: CssId) -> Result<()> {
+		self.eval(&remove_css_script(id))
+	}
+
+	pub fn memory_usage(&self) -> Option<u64> {
+		None
+	}
+
+	pub fn suspend(&self) -> Result<()> {
+		Err(Error::UnsupportedPlatform("Suspending the webview"))
+	}
+
+	pub fn resume(&self) -> Result<()> {
+		Err(Error::UnsupportedPlatform("Resuming the webview"))
+	}
+
 	pub fn focus(&self) {}
 
+	// Android has no devtools window; instead, enabling devtools allows the webview
+	// to be inspected remotely from `chrome://inspect`.
 	#[cfg(any(debug_assertions, feature = "devtools"))]
-	pub fn open_devtools(&self) {}
+	pub fn open_devtools(&self, _position: crate::webview::DevtoolsPosition) {
+		self.devtools_enabled.set(true);
+		MainPipe::send(WebViewMessage::SetDevtools(true));
+	}
 
 	#[cfg(any(debug_assertions, feature = "devtools"))]
-	pub fn close_devtools(&self) {}
+	pub fn close_devtools(&self) {
+		self.devtools_enabled.set(false);
+		MainPipe::send(WebViewMessage::SetDevtools(false));
+	}
 
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	pub fn is_devtools_open(&self) -> bool {
-		false
+		self.devtools_enabled.get()
 	}
 
 	pub fn run(self, env: JNIEnv, _jclass: JClass, jobject: JObject) -> Result<jobject> {
@@ -112,13 +167,70 @@ impl InnerWebView {
 		}
 	}
 
-	pub fn zoom(&self, scale_factor: f64) {}
+	pub fn zoom(&self, _scale_factor: f64) {}
+
+	pub fn set_clipboard(&self, _enabled: bool) {}
 }
 
 pub struct UnsafeIpc(*mut c_void);
 unsafe impl Send for UnsafeIpc {}
 unsafe impl Sync for UnsafeIpc {}
 
+/// Package name of the Android System WebView, used when the current WebView
+/// provider can't be queried directly (API level < 26).
+const SYSTEM_WEBVIEW_PACKAGE: &str = "com.google.android.webview";
+
 pub fn platform_webview_version() -> Result<String> {
-	todo!()
+	let ctx = ndk_context::android_context();
+	if ctx.vm().is_null() || ctx.context().is_null() {
+		return Err(Error::WebViewProviderNotFound);
+	}
+
+	let vm = unsafe { JavaVM::from_raw(ctx.vm() as *mut _) }?;
+	let env = vm.attach_current_thread()?;
+	let context = JObject::from(ctx.context() as jobject);
+
+	let package_info = current_webview_package(&env).or_else(|_| {
+		let _ = env.exception_clear();
+		system_webview_package(&env, context)
+	})?;
+	if package_info.is_null() {
+		return Err(Error::WebViewProviderNotFound);
+	}
+
+	let version = env.get_field(package_info, "versionName", "Ljava/lang/String;")?.l()?;
+	if version.is_null() {
+		return Err(Error::WebViewProviderNotFound);
+	}
+	Ok(env.get_string(JString::from(version))?.into())
+}
+
+/// Gets the `PackageInfo` of the active WebView provider via
+/// `WebView.getCurrentWebViewPackage()`, which is only available on API level
+/// 26 and up.
+fn current_webview_package<'a>(env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+	let class = env.find_class("android/webkit/WebView")?;
+	Ok(env
+		.call_static_method(class, "getCurrentWebViewPackage", "()Landroid/content/pm/PackageInfo;", &[])?
+		.l()?)
+}
+
+/// Gets the `PackageInfo` of the Android System WebView from the
+/// `PackageManager`.
+fn system_webview_package<'a>(env: &JNIEnv<'a>, context: JObject<'a>) -> Result<JObject<'a>> {
+	let package_manager = env
+		.call_method(context, "getPackageManager", "()Landroid/content/pm/PackageManager;", &[])?
+		.l()?;
+	let package_name = env.new_string(SYSTEM_WEBVIEW_PACKAGE)?;
+	env.call_method(
+		package_manager,
+		"getPackageInfo",
+		"(Ljava/lang/String;I)Landroid/content/pm/PackageInfo;",
+		&[package_name.into(), 0.into()]
+	)
+	.and_then(|v| v.l())
+	.map_err(|e| {
+		let _ = env.exception_clear();
+		e.into()
+	})
 }
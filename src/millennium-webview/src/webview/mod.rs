@@ -34,7 +34,11 @@ pub(crate) mod wkwebview;
 use wkwebview::*;
 #[cfg(target_os = "windows")]
 pub(crate) mod webview2;
-use std::{path::PathBuf, rc::Rc};
+use std::{
+	path::PathBuf,
+	rc::Rc,
+	sync::atomic::{AtomicU64, Ordering}
+};
 
 #[cfg(target_os = "android")]
 use jni::{
@@ -52,8 +56,13 @@ use windows::{Win32::Foundation::HWND, Win32::UI::WindowsAndMessaging::DestroyWi
 use self::webview2::*;
 #[cfg(target_os = "windows")]
 use crate::application::platform::windows::WindowExtWindows;
-use crate::application::{dpi::PhysicalSize, window::Window};
-use crate::http::{Request as HttpRequest, Response as HttpResponse};
+use crate::application::{
+	accelerator::Accelerator,
+	dpi::PhysicalSize,
+	keyboard::{KeyCode, ModifiersState},
+	window::Window
+};
+use crate::http::{header::HeaderMap, Request as HttpRequest, Response as HttpResponse};
 use crate::Result;
 
 pub struct WebViewAttributes {
@@ -118,14 +127,33 @@ pub struct WebViewAttributes {
 	/// - iOS: Same as macOS. To get the path to your assets, you can call [`CFBundle::resources_path`](https://docs.rs/core-foundation/latest/core_foundation/bundle/struct.CFBundle.html#method.resources_path).
 	///   So, a URL like `millennium://assets/index.html` would get the HTML file in the assets directory.
 	///
+	/// Handlers registered with [`WebViewBuilder::with_custom_protocol`] respond
+	/// immediately; those registered with
+	/// [`WebViewBuilder::with_asynchronous_custom_protocol`] may respond later.
+	///
 	/// [bug]: https://bugs.webkit.org/show_bug.cgi?id=229034
-	pub custom_protocols: Vec<(String, Box<dyn Fn(&HttpRequest) -> Result<HttpResponse>>)>,
+	pub custom_protocols: Vec<(String, Box<dyn Fn(HttpRequest, RequestAsyncResponder)>)>,
 	/// Set the IPC handler to receive the message from Javascript on webview to
 	/// host Rust code. The message sent from webview should call
 	/// `window.ipc.postMessage("insert_message_here");`.
 	///
 	/// Both functions return promises but `notify()` resolves immediately.
 	pub ipc_handler: Option<Box<dyn Fn(&Window, String)>>,
+	/// Set a handler to receive binary messages from Javascript, sent with
+	/// `window.ipc.postBinaryMessage(data)` where `data` is an `ArrayBuffer` or a typed array.
+	///
+	/// Binary messages are not ordered relative to messages sent with `window.ipc.postMessage`.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / macOS / iOS:** The bytes are sent unencoded as the body of a `fetch` request to the internal
+	///   `millennium-ipc` custom protocol, so the page's CSP must allow connecting to `https://millennium-ipc.localhost`
+	///   on Windows or `millennium-ipc://localhost` on macOS and iOS.
+	/// - **Linux:** WebKitGTK doesn't give custom protocols the request body, so instead each byte is packed into one
+	///   character of a string that goes through a separate script message handler. This still avoids the size and
+	///   decoding overhead of base64.
+	/// - **Android:** Unsupported; `window.ipc.postBinaryMessage` is not defined.
+	pub binary_ipc_handler: Option<Box<dyn Fn(&Window, Vec<u8>)>>,
 	/// Set a handler closure to process incoming [`FileDropEvent`] of the
 	/// webview.
 	///
@@ -152,6 +180,48 @@ pub struct WebViewAttributes {
 	/// The closure takes the URL as a `String` parameter and returns a `bool` to determine whether to allow navigation.
 	pub new_window_handler: Option<Box<dyn Fn(String) -> bool>>,
 
+	/// Set a handler to decide whether the page may use the camera, microphone, geolocation and other
+	/// capabilities it asks for.
+	///
+	/// The closure takes the origin of the page making the request (e.g. `https://example.com`) and the kind of
+	/// permission. When no handler is set, all requests are denied.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Clipboard reads are controlled by [`WebViewAttributes::clipboard`] instead.
+	/// - **Linux:** [`PermissionResponse::Prompt`] falls back to WebKitGTK's default handling, which denies the request.
+	/// - **macOS / iOS:** Only camera and microphone requests are handled, on macOS 12+ / iOS 15+. Other requests
+	///   are decided by WebKit.
+	/// - **Android:** Unsupported.
+	pub permission_handler: Option<Box<dyn Fn(&str, PermissionKind) -> PermissionResponse>>,
+
+	/// Set a handler called with the key presses of the webview before the page receives them, e.g. for application
+	/// shortcuts that should work regardless of what the page does with the keyboard.
+	///
+	/// The closure takes the pressed modifiers and the physical key, and returns `true` to keep the key press from
+	/// reaching the page.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Only called for the key presses WebView2 considers accelerators: keys pressed while Ctrl or Alt
+	///   is held, and keys that don't produce a character such as the function keys.
+	/// - **Android / iOS:** Unsupported.
+	pub key_press_handler: Option<Box<dyn Fn(ModifiersState, KeyCode) -> bool>>,
+
+	/// Rewrites the headers of the requests the page sends, e.g. to add an `Authorization` header to the `fetch` and
+	/// XHR calls made to an API. Only requests whose URL matches one of the filters are passed to the handler, along
+	/// with their URL; in a filter, `*` matches any run of characters, e.g. `https://api.example.com/*`.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Requests to custom protocols aren't intercepted.
+	/// - **Linux:** Implemented by a web process extension, which needs the WebKitGTK web extension headers
+	///   (`webkit2gtk-web-extension-4.0`) at build time; unsupported without them.
+	/// - **macOS / iOS:** Unsupported; `WKWebView` has no API to modify outgoing requests, and `WKURLSchemeHandler`
+	///   can only answer requests to custom protocols.
+	/// - **Android:** Unsupported.
+	pub request_interceptor: Option<(Vec<String>, Box<dyn Fn(&str, &mut HeaderMap)>)>,
+
 	/// Enables clipboard access for the page rendered on **Linux** and
 	/// **Windows**.
 	///
@@ -173,7 +243,144 @@ pub struct WebViewAttributes {
 	/// - **Android**: Open `chrome://inspect/#devices` in Chrome to get the devtools window. The `WebView` devtools
 	///   APIs aren't supported on Android.
 	/// - **iOS**: Open Safari > Develop > [Device Name] > [Your WebView] to get the devtools window.
-	pub devtools: bool
+	pub devtools: bool,
+
+	/// Whether media can play automatically without a user gesture. Blocked by default, like in browsers.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Webviews sharing a data directory must use the same setting.
+	/// - **Android:** Unsupported.
+	pub autoplay: bool,
+
+	/// Whether the webview's built-in context menu is shown on right click. Enabled by default.
+	pub default_context_menu_enabled: bool,
+
+	/// Whether the keyboard shortcuts that open the devtools (e.g. F12 and Ctrl+Shift+I) work. These only have an
+	/// effect when [`Self::devtools`] is enabled. Enabled by default.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS / Android / iOS:** Unsupported; the devtools have no keyboard shortcuts.
+	pub devtools_shortcuts_enabled: bool,
+	/// Where [`WebView::open_devtools`] opens the devtools. [`DevtoolsPosition::Docked`] by default.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Unsupported; the devtools always open in a separate window.
+	/// - **Android / iOS:** Unsupported.
+	pub devtools_position: DevtoolsPosition,
+
+	/// Whether the webview is meant to render without its window ever being shown, e.g. to take snapshots with
+	/// [`WebView::take_snapshot`]. The window should be created invisible.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** The webview is laid out at the window's size without mapping the window; WebKit renders it
+	///   entirely offscreen.
+	/// - **Windows / macOS:** The webview keeps rendering into the hidden window as if it were visible.
+	/// - **Android / iOS:** Unsupported.
+	pub headless: bool,
+
+	/// The style of the webview's scroll bars. Pages can instead style their own scroll bars, even per element, with
+	/// the `::-webkit-scrollbar` CSS pseudo-elements, which every backend supports.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** [`ScrollBarStyle::Overlay`] is the default, unless the user disabled overlay scrolling.
+	///   [`ScrollBarStyle::AlwaysVisible`] adds a user stylesheet that draws classic scroll bars, which pages can
+	///   restyle. Webviews sharing a web context get the same stylesheet.
+	/// - **Windows:** [`ScrollBarStyle::AlwaysVisible`] is the default. Webviews sharing a data directory must use the
+	///   same setting.
+	/// - **macOS:** Overrides the user's "Show scroll bars" preference for the whole app while it runs, without
+	///   persisting it to the app's preferences.
+	/// - **Android / iOS:** Unsupported.
+	pub scroll_bar_style: ScrollBarStyle,
+
+	/// Whether the webview composites and draws with the GPU. Enabled by default. Disabling it forces software
+	/// rendering, which is slower but works around rendering glitches with some GPU drivers, VMs and remote desktops.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Passes `--disable-gpu` to WebView2. Webviews sharing a data directory must use the same setting.
+	/// - **Linux:** Sets the webview's hardware acceleration policy to `Never` and disables accelerated 2D canvas.
+	/// - **macOS:** Turns off accelerated drawing through private WebKit preferences, which may be ignored by some
+	///   versions of WebKit.
+	/// - **Android / iOS:** Unsupported.
+	pub hardware_acceleration: bool,
+
+	/// Whether the webview bounces ("rubber-bands") when scrolled past the edges of the page. Enabled by default,
+	/// following the platform. Fixed-layout app UIs usually look better with it disabled.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS:** Disabled through a private WebKit API, which may be ignored by some versions of WebKit.
+	/// - **Windows:** Disables the elastic overscroll of precision touchpads. Webviews sharing a data directory must use
+	///   the same setting.
+	/// - **Linux / Android:** Unsupported; WebKitGTK and Android don't bounce.
+	pub scroll_bounce: bool,
+	/// Whether clicking into an unfocused window also delivers the click to the webview, instead of only activating
+	/// the window. Disabled by default.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux / Android / iOS:** Unsupported; only macOS swallows the activating click.
+	pub accept_first_mouse: bool,
+	/// Whether pinching on a trackpad or touch screen zooms the page. `None` keeps the platform default.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Follows [`WebViewAttributes::zoom_hotkeys_enabled`] when `None`.
+	/// - **macOS:** Disabled by default.
+	/// - **Linux / Android / iOS:** Unsupported.
+	pub pinch_zoom: Option<bool>,
+	/// Whether swiping with two fingers navigates back and forward through the page history. `None` keeps the
+	/// platform default.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS / Linux:** Disabled by default.
+	/// - **Linux:** Requires WebKitGTK 2.24 or newer.
+	/// - **Android / iOS:** Unsupported.
+	pub swipe_navigation: Option<bool>,
+
+	/// Whether cookies are kept across runs of the app. [`CookiePersistence::Persistent`] by default, which keeps
+	/// users logged in; [`CookiePersistence::Session`] forgets every cookie once the app exits.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** [`CookiePersistence::Session`] makes the webview ephemeral, so local storage and caches aren't
+	///   written to disk either.
+	/// - **macOS / iOS:** [`CookiePersistence::Session`] uses a non-persistent `WKWebsiteDataStore`, so local storage
+	///   and caches aren't written to disk either.
+	/// - **Windows:** [`CookiePersistence::Session`] makes the webview InPrivate, so local storage and caches aren't
+	///   written to disk either. Requires WebView2 Runtime 101 or newer; cookies are persisted on older runtimes.
+	/// - **Android:** Unsupported.
+	pub cookie_persistence: CookiePersistence,
+
+	/// Whether cookies set by sites other than the page's own are rejected. Disabled by default, which keeps the
+	/// platform's own policy: WebKitGTK and WebKit already block most third-party cookies, while WebView2 accepts them.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Sets the accept policy of the webview's `WebKitCookieManager`, which is shared by every webview
+	///   using the same [`WebContext`] unless [`CookiePersistence::Session`] is used.
+	/// - **macOS / iOS:** Set through a private `WKHTTPCookieStore` API, which may be ignored by some versions of
+	///   WebKit.
+	/// - **Windows / Android:** Unsupported; WebView2's `CookieManager` has no third-party cookie policy.
+	pub block_third_party_cookies: bool,
+
+	/// Shortcuts that always reach the window's menu and the OS, even when the webview has focus and would otherwise
+	/// consume them, e.g. `Cmd+Q` while a text input is focused.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** The key press is posted to the window, where it triggers the menu accelerators and system
+	///   shortcuts such as `Alt+F4`.
+	/// - **macOS:** The webview declines the key equivalent, so it goes on to the main menu.
+	/// - **Linux:** Unsupported; GTK already gives the window's accelerators precedence over the webview.
+	/// - **Android / iOS:** Unsupported.
+	pub passthrough_accelerators: Vec<Accelerator>
 }
 
 impl Default for WebViewAttributes {
@@ -187,12 +394,30 @@ impl Default for WebViewAttributes {
 			initialization_scripts: vec![],
 			custom_protocols: vec![],
 			ipc_handler: None,
+			binary_ipc_handler: None,
 			file_drop_handler: None,
 			navigation_handler: None,
 			new_window_handler: None,
+			permission_handler: None,
+			key_press_handler: None,
+			request_interceptor: None,
 			clipboard: false,
 			devtools: false,
-			zoom_hotkeys_enabled: false
+			zoom_hotkeys_enabled: false,
+			autoplay: false,
+			default_context_menu_enabled: true,
+			devtools_shortcuts_enabled: true,
+			devtools_position: DevtoolsPosition::Docked,
+			headless: false,
+			scroll_bar_style: ScrollBarStyle::Default,
+			hardware_acceleration: true,
+			scroll_bounce: true,
+			accept_first_mouse: false,
+			pinch_zoom: None,
+			swipe_navigation: None,
+			cookie_persistence: CookiePersistence::Persistent,
+			block_third_party_cookies: false,
+			passthrough_accelerators: Vec::new()
 		}
 	}
 }
@@ -271,6 +496,25 @@ impl<'a> WebViewBuilder<'a> {
 	pub fn with_custom_protocol<F>(mut self, name: String, handler: F) -> Self
 	where
 		F: Fn(&HttpRequest) -> Result<HttpResponse> + 'static
+	{
+		self.webview
+			.custom_protocols
+			.push((name, Box::new(move |request, responder| responder.respond(handler(&request)))));
+		self
+	}
+
+	/// Same as [`Self::with_custom_protocol`], but the handler gets a
+	/// [`RequestAsyncResponder`] to complete the request with instead of
+	/// returning the response.
+	///
+	/// The handler is still called on the webview thread, but it can move the
+	/// request and the responder to another thread and respond from there, so a
+	/// slow handler doesn't block the webview. The response is handed back to the
+	/// webview thread to complete the request.
+	#[cfg(feature = "protocol")]
+	pub fn with_asynchronous_custom_protocol<F>(mut self, name: String, handler: F) -> Self
+	where
+		F: Fn(HttpRequest, RequestAsyncResponder) + 'static
 	{
 		self.webview.custom_protocols.push((name, Box::new(handler)));
 		self
@@ -287,6 +531,16 @@ impl<'a> WebViewBuilder<'a> {
 		self
 	}
 
+	/// Set a handler for binary messages sent with `window.ipc.postBinaryMessage(data)`. See
+	/// [`WebViewAttributes::binary_ipc_handler`].
+	pub fn with_binary_ipc_handler<F>(mut self, handler: F) -> Self
+	where
+		F: Fn(&Window, Vec<u8>) + 'static
+	{
+		self.webview.binary_ipc_handler = Some(Box::new(handler));
+		self
+	}
+
 	/// Set a handler closure to process incoming [`FileDropEvent`] of the
 	/// webview.
 	///
@@ -388,11 +642,109 @@ impl<'a> WebViewBuilder<'a> {
 	///
 	/// macOS doesn't provide such method and is always enabled by default. However, you still need to add menu
 	/// item accelerators to use shortcuts.
+	///
+	/// This can be changed after the webview is created with [`WebView::set_clipboard`].
 	pub fn with_clipboard(mut self, clipboard: bool) -> Self {
 		self.webview.clipboard = clipboard;
 		self
 	}
 
+	/// Sets whether media can play automatically without a user gesture.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Webviews sharing a data directory must use the same setting.
+	/// - **Android:** Unsupported.
+	pub fn with_autoplay(mut self, autoplay: bool) -> Self {
+		self.webview.autoplay = autoplay;
+		self
+	}
+
+	/// Sets whether the webview's built-in context menu is shown on right click.
+	pub fn with_default_context_menu(mut self, enabled: bool) -> Self {
+		self.webview.default_context_menu_enabled = enabled;
+		self
+	}
+
+	/// Sets whether the keyboard shortcuts that open the devtools work.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS / Android / iOS:** Unsupported; the devtools have no keyboard shortcuts.
+	pub fn with_devtools_shortcuts(mut self, enabled: bool) -> Self {
+		self.webview.devtools_shortcuts_enabled = enabled;
+		self
+	}
+
+	/// Lays out the webview so it can render while its window stays hidden. See [`WebViewAttributes::headless`].
+	pub fn with_headless(mut self, headless: bool) -> Self {
+		self.webview.headless = headless;
+		self
+	}
+
+	/// Sets the style of the webview's scroll bars. See [`WebViewAttributes::scroll_bar_style`].
+	pub fn with_scroll_bar_style(mut self, style: ScrollBarStyle) -> Self {
+		self.webview.scroll_bar_style = style;
+		self
+	}
+
+	/// Sets where [`WebView::open_devtools`] opens the devtools. See [`WebViewAttributes::devtools_position`].
+	pub fn with_devtools_position(mut self, position: DevtoolsPosition) -> Self {
+		self.webview.devtools_position = position;
+		self
+	}
+
+	/// Sets whether the webview renders with the GPU. See [`WebViewAttributes::hardware_acceleration`].
+	pub fn with_hardware_acceleration(mut self, enabled: bool) -> Self {
+		self.webview.hardware_acceleration = enabled;
+		self
+	}
+
+	/// Sets whether the webview bounces when scrolled past the edges of the page. See
+	/// [`WebViewAttributes::scroll_bounce`].
+	pub fn with_scroll_bounce(mut self, enabled: bool) -> Self {
+		self.webview.scroll_bounce = enabled;
+		self
+	}
+
+	/// Sets whether the click that activates an unfocused window is delivered to the webview. See
+	/// [`WebViewAttributes::accept_first_mouse`].
+	pub fn with_accept_first_mouse(mut self, accept_first_mouse: bool) -> Self {
+		self.webview.accept_first_mouse = accept_first_mouse;
+		self
+	}
+
+	/// Sets whether pinching zooms the page. See [`WebViewAttributes::pinch_zoom`].
+	pub fn with_pinch_zoom(mut self, enabled: bool) -> Self {
+		self.webview.pinch_zoom = Some(enabled);
+		self
+	}
+
+	/// Sets whether swiping navigates through the page history. See [`WebViewAttributes::swipe_navigation`].
+	pub fn with_swipe_navigation(mut self, enabled: bool) -> Self {
+		self.webview.swipe_navigation = Some(enabled);
+		self
+	}
+
+	/// Sets whether cookies are kept across runs of the app. See [`WebViewAttributes::cookie_persistence`].
+	pub fn with_cookie_persistence(mut self, persistence: CookiePersistence) -> Self {
+		self.webview.cookie_persistence = persistence;
+		self
+	}
+
+	/// Sets whether third-party cookies are rejected. See [`WebViewAttributes::block_third_party_cookies`].
+	pub fn with_block_third_party_cookies(mut self, block: bool) -> Self {
+		self.webview.block_third_party_cookies = block;
+		self
+	}
+
+	/// Adds a shortcut that is passed on to the window's menu and the OS rather than consumed by the webview. See
+	/// [`WebViewAttributes::passthrough_accelerators`].
+	pub fn with_passthrough_accelerator(mut self, accelerator: Accelerator) -> Self {
+		self.webview.passthrough_accelerators.push(accelerator);
+		self
+	}
+
 	/// Set a new window request handler to decide if an incoming URL is allowed to open in a new window.
 	///
 	/// The closure takes the URL as a `String` parameter and returns a `bool` to determine whether to allow navigation.
@@ -401,6 +753,27 @@ impl<'a> WebViewBuilder<'a> {
 		self
 	}
 
+	/// Set a handler to decide whether the page may use the camera, microphone, geolocation and other
+	/// capabilities it asks for. See [`WebViewAttributes::permission_handler`].
+	pub fn with_permission_handler(mut self, handler: impl Fn(&str, PermissionKind) -> PermissionResponse + 'static) -> Self {
+		self.webview.permission_handler = Some(Box::new(handler));
+		self
+	}
+
+	/// Set a handler called with the key presses of the webview before the page receives them. See
+	/// [`WebViewAttributes::key_press_handler`].
+	pub fn with_key_press_handler(mut self, handler: impl Fn(ModifiersState, KeyCode) -> bool + 'static) -> Self {
+		self.webview.key_press_handler = Some(Box::new(handler));
+		self
+	}
+
+	/// Set a handler to rewrite the headers of the requests the page sends to URLs matching `filters`. See
+	/// [`WebViewAttributes::request_interceptor`].
+	pub fn with_request_interceptor(mut self, filters: Vec<String>, handler: impl Fn(&str, &mut HeaderMap) + 'static) -> Self {
+		self.webview.request_interceptor = Some((filters, Box::new(handler)));
+		self
+	}
+
 	/// Consume the builder and create the [`WebView`].
 	///
 	/// Platform-specific behavior:
@@ -411,8 +784,9 @@ impl<'a> WebViewBuilder<'a> {
 	/// [`EventLoop`]: crate::application::event_loop::EventLoop
 	pub fn build(self) -> Result<WebView> {
 		let window = Rc::new(self.window);
+		let devtools_position = self.webview.devtools_position;
 		let webview = InnerWebView::new(window.clone(), self.webview, self.web_context)?;
-		Ok(WebView { window, webview })
+		Ok(WebView { window, webview, devtools_position })
 	}
 }
 
@@ -425,7 +799,8 @@ impl<'a> WebViewBuilder<'a> {
 /// it. [`WebView`] also contains the associate [`Window`] with it.
 pub struct WebView {
 	window: Rc<Window>,
-	webview: InnerWebView
+	webview: InnerWebView,
+	devtools_position: DevtoolsPosition
 }
 
 // Signal the Window to drop on Linux and Windows. On mac, we need to handle
@@ -488,6 +863,104 @@ impl WebView {
 		Ok(())
 	}
 
+	/// Print the webview content straight to a PDF file at `path`, without showing the print dialog. `callback` is
+	/// called on the main thread with the path of the written file once printing has finished.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** `path` must be absolute.
+	/// - **macOS / iOS:** The page size, margins and orientation are ignored; the content is rendered onto a single
+	///   page. Available on macOS 11+ / iOS 14+ only.
+	/// - **Android:** Unsupported.
+	pub fn print_to_pdf(&self, path: impl Into<PathBuf>, settings: PdfSettings, callback: impl FnOnce(Result<PathBuf>) + 'static) -> Result<()> {
+		self.webview.print_to_pdf(path.into(), settings, Box::new(callback))
+	}
+
+	/// Captures the visible area of the webview as a PNG image. `callback` is called on the main thread with the
+	/// encoded image once it is ready.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Android:** Unsupported.
+	pub fn take_snapshot(&self, callback: impl FnOnce(Result<Vec<u8>>) + 'static) -> Result<()> {
+		self.webview.take_snapshot(Box::new(callback))
+	}
+
+	/// Inserts `css` into the page as a user stylesheet that stays applied across navigations. Returns a [`CssId`]
+	/// that can be passed to [`WebView::remove_css`].
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** The stylesheet applies to every webview sharing the same [`WebContext`].
+	/// - **Android:** The CSS is only applied to the current page and is lost on navigation.
+	pub fn insert_css(&self, css: &str) -> Result<CssId> {
+		static NEXT_CSS_ID: AtomicU64 = AtomicU64::new(0);
+		let id = CssId(NEXT_CSS_ID.fetch_add(1, Ordering::Relaxed));
+		self.webview.insert_css(id, css)?;
+		Ok(id)
+	}
+
+	/// Removes CSS inserted with [`WebView::insert_css`]. Does nothing if it has already been removed.
+	pub fn remove_css(&self, id: CssId) -> Result<()> {
+		self.webview.remove_css(id)
+	}
+
+	/// Adds a script that runs before any other script on every page loaded after this call, like
+	/// [`WebViewBuilder::with_initialization_script`]. Returns an [`InitScriptId`] that can be passed to
+	/// [`WebView::remove_init_script`].
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** The script runs in every webview sharing the same [`WebContext`].
+	/// - **Android:** Unsupported; returns [`crate::Error::UnsupportedPlatform`].
+	pub fn add_init_script(&self, js: &str) -> Result<InitScriptId> {
+		static NEXT_INIT_SCRIPT_ID: AtomicU64 = AtomicU64::new(0);
+		let id = InitScriptId(NEXT_INIT_SCRIPT_ID.fetch_add(1, Ordering::Relaxed));
+		self.webview.add_init_script(id, js)?;
+		Ok(id)
+	}
+
+	/// Removes a script added with [`WebView::add_init_script`], so it no longer runs on pages loaded afterwards. Does
+	/// nothing if it has already been removed.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Android:** Unsupported; returns [`crate::Error::UnsupportedPlatform`].
+	pub fn remove_init_script(&self, id: InitScriptId) -> Result<()> {
+		self.webview.remove_init_script(id)
+	}
+
+	/// Gets the resident memory of the process rendering the webview in bytes, or `None` if it can't be determined.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Reports the WebView2 browser process, which is shared by webviews using the same data directory.
+	/// - **Linux:** Reports the combined memory of all WebKit web processes started by the application.
+	/// - **iOS / Android:** Unsupported.
+	pub fn memory_usage(&self) -> Option<u64> {
+		self.webview.memory_usage()
+	}
+
+	/// Suspends the webview to save CPU and power while it isn't being used. The webview is hidden until it is resumed,
+	/// so the window shows no content in the meantime. Suspending an already suspended webview does nothing.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Calls `SetIsVisible(false)` on the WebView2 controller, which blanks the window, and asks WebView2
+	///   to suspend the webview, which freezes its timers, scripts and rendering. WebView2 may refuse to suspend a page
+	///   that is playing audio or holds a lock.
+	/// - **Linux / macOS / iOS:** WebKit treats the hidden page like a background tab: it stops rendering and throttles
+	///   its timers. Media is muted on WebKitGTK 2.30+ and paused on macOS 12+ / iOS 15+.
+	/// - **Android:** Unsupported; returns [`crate::Error::UnsupportedPlatform`].
+	pub fn suspend(&self) -> Result<()> {
+		self.webview.suspend()
+	}
+
+	/// Resumes a webview suspended with [`WebView::suspend`], showing it again.
+	pub fn resume(&self) -> Result<()> {
+		self.webview.resume()
+	}
+
 	/// Moves Focus to the Webview control.
 	///
 	/// It's usually safe to call `focus` method on `Window` which would also
@@ -500,14 +973,26 @@ impl WebView {
 		self.webview.focus();
 	}
 
-	/// Open the web inspector/devtools.
+	/// Open the web inspector/devtools, docked or detached according to
+	/// [`WebViewAttributes::devtools_position`].
 	///
 	/// ## Platform-specific
 	///
 	/// - **Android / iOS**: Not supported.
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	pub fn open_devtools(&self) {
-		self.webview.open_devtools();
+		self.webview.open_devtools(self.devtools_position);
+	}
+
+	/// Open the web inspector/devtools in a separate window, regardless of
+	/// [`WebViewAttributes::devtools_position`].
+	///
+	/// ## Platform-specific
+	///
+	/// - **Android / iOS**: Not supported.
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	pub fn open_devtools_detached(&self) {
+		self.webview.open_devtools(DevtoolsPosition::Detached);
 	}
 
 	/// Close the web inspector/devtools.
@@ -551,6 +1036,17 @@ impl WebView {
 		self.webview.zoom(scale_factor);
 	}
 
+	/// Enables or disables clipboard access for the page after the webview has been created. See
+	/// [`WebViewBuilder::with_clipboard`].
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Only affects reading the clipboard; disabling denies the request instead of prompting the user.
+	/// - **macOS / iOS / Android**: Not supported. Clipboard access is always enabled on macOS and iOS.
+	pub fn set_clipboard(&self, enabled: bool) {
+		self.webview.set_clipboard(enabled);
+	}
+
 	#[cfg(target_os = "android")]
 	pub fn run(self, env: JNIEnv, jclass: JClass, jobject: JObject) -> jobject {
 		self.webview.run(env, jclass, jobject).unwrap()
@@ -575,6 +1071,253 @@ pub enum FileDropEvent {
 	Cancelled
 }
 
+/// Completes a request to a custom protocol registered with
+/// [`WebViewBuilder::with_asynchronous_custom_protocol`].
+///
+/// The responder can be sent to another thread. Dropping it without responding
+/// fails the request.
+pub struct RequestAsyncResponder {
+	responder: Option<Box<dyn FnOnce(Option<HttpResponse>) + Send>>
+}
+
+impl RequestAsyncResponder {
+	pub(crate) fn new<F: FnOnce(Option<HttpResponse>) + Send + 'static>(responder: F) -> Self {
+		Self { responder: Some(Box::new(responder)) }
+	}
+
+	/// Completes the request with `response`. An error fails the request.
+	pub fn respond(mut self, response: Result<HttpResponse>) {
+		if let Some(responder) = self.responder.take() {
+			responder(response.ok());
+		}
+	}
+}
+
+impl Drop for RequestAsyncResponder {
+	fn drop(&mut self) {
+		if let Some(responder) = self.responder.take() {
+			responder(None);
+		}
+	}
+}
+
+/// Page layout used by [`WebView::print_to_pdf`]. All lengths are in inches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdfSettings {
+	pub page_width: f64,
+	pub page_height: f64,
+	pub margin_top: f64,
+	pub margin_bottom: f64,
+	pub margin_left: f64,
+	pub margin_right: f64,
+	pub orientation: PdfOrientation
+}
+
+impl Default for PdfSettings {
+	/// US Letter in portrait, with 0.4 inch margins.
+	fn default() -> Self {
+		Self {
+			page_width: 8.5,
+			page_height: 11.0,
+			margin_top: 0.4,
+			margin_bottom: 0.4,
+			margin_left: 0.4,
+			margin_right: 0.4,
+			orientation: PdfOrientation::Portrait
+		}
+	}
+}
+
+/// Page orientation of a PDF printed with [`WebView::print_to_pdf`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfOrientation {
+	Portrait,
+	Landscape
+}
+
+/// A capability the page asks permission to use.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionKind {
+	Camera,
+	Microphone,
+	Geolocation,
+	Notifications,
+	/// Any other permission, such as device sensors or pointer lock.
+	Other
+}
+
+/// The answer to a permission request, returned by [`WebViewAttributes::permission_handler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionResponse {
+	Allow,
+	Deny,
+	/// Let the webview decide, which usually means asking the user.
+	Prompt
+}
+
+/// How the webview draws its scroll bars, set with [`WebViewAttributes::scroll_bar_style`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollBarStyle {
+	/// Follow the system preference.
+	Default,
+	/// Thin scroll bars that are drawn over the content and hidden while not scrolling.
+	Overlay,
+	/// Scroll bars that are always shown and take up space next to the content.
+	AlwaysVisible
+}
+
+/// Where the devtools open, set with [`WebViewAttributes::devtools_position`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevtoolsPosition {
+	/// Attached to the webview's window.
+	Docked,
+	/// In a separate window.
+	Detached
+}
+
+/// How long the webview keeps cookies, set with [`WebViewAttributes::cookie_persistence`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CookiePersistence {
+	/// Stored on disk and kept across runs of the app.
+	Persistent,
+	/// Kept in memory and forgotten once the app exits.
+	Session
+}
+
+/// Identifies CSS inserted with [`WebView::insert_css`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CssId(pub u64);
+
+/// Identifies a script added with [`WebView::add_init_script`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InitScriptId(pub u64);
+
+/// Builds a script that adds `css` to the page as a `<style>` element tagged with `id`. Platforms without native user
+/// stylesheets run it on every document.
+#[cfg(not(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd")))]
+pub(crate) fn insert_css_script(id: CssId, css: &str) -> String {
+	format!(
+		r#"(function () {{
+			if (document.querySelector('style[data-millennium-css="{id}"]')) return;
+			var style = document.createElement('style');
+			style.setAttribute('data-millennium-css', '{id}');
+			style.textContent = {css};
+			var insert = function () {{ (document.head || document.documentElement).appendChild(style); }};
+			if (document.documentElement) insert();
+			else document.addEventListener('DOMContentLoaded', insert);
+		}})();"#,
+		id = id.0,
+		css = serde_json::to_string(css).expect("failed to serialize CSS")
+	)
+}
+
+/// Builds a script that removes the `<style>` element added by [`insert_css_script`].
+#[cfg(not(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd")))]
+pub(crate) fn remove_css_script(id: CssId) -> String {
+	format!(r#"document.querySelectorAll('style[data-millennium-css="{}"]').forEach(function (style) {{ style.remove(); }});"#, id.0)
+}
+
+/// Answers a permission request with `handler`, denying it if there is no handler.
+#[cfg(not(target_os = "android"))]
+pub(crate) fn request_permission(handler: Option<&dyn Fn(&str, PermissionKind) -> PermissionResponse>, origin: &str, kind: PermissionKind) -> PermissionResponse {
+	handler.map_or(PermissionResponse::Deny, |handler| handler(origin, kind))
+}
+
+/// Answers a media capture request, which may be for both the camera and the microphone. The request is only
+/// allowed if every device it asks for is allowed.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd",
+	target_os = "macos",
+	target_os = "ios"
+))]
+pub(crate) fn request_media_capture_permission(
+	handler: Option<&dyn Fn(&str, PermissionKind) -> PermissionResponse>,
+	origin: &str,
+	camera: bool,
+	microphone: bool
+) -> PermissionResponse {
+	[(camera, PermissionKind::Camera), (microphone, PermissionKind::Microphone)]
+		.into_iter()
+		.filter(|(requested, _)| *requested)
+		.map(|(_, kind)| request_permission(handler, origin, kind))
+		.fold(PermissionResponse::Allow, |acc, response| match (acc, response) {
+			(PermissionResponse::Deny, _) | (_, PermissionResponse::Deny) => PermissionResponse::Deny,
+			(PermissionResponse::Prompt, _) | (_, PermissionResponse::Prompt) => PermissionResponse::Prompt,
+			_ => PermissionResponse::Allow
+		})
+}
+
+/// Gets the origin of `url` to pass to a permission handler. Unlike [`Url::origin`], custom protocols like
+/// `millennium://localhost` keep their scheme and host.
+#[cfg(not(any(target_os = "android", target_os = "macos", target_os = "ios")))]
+pub(crate) fn url_origin(url: &str) -> String {
+	match Url::parse(url) {
+		Ok(url) => match (url.host_str(), url.port()) {
+			(Some(host), Some(port)) => format!("{}://{}:{}", url.scheme(), host, port),
+			(Some(host), None) => format!("{}://{}", url.scheme(), host),
+			(None, _) => url.origin().ascii_serialization()
+		},
+		Err(_) => String::from("null")
+	}
+}
+
+/// Whether `url` matches a request interceptor filter, where `*` matches any run of characters.
+#[cfg(any(target_os = "windows", test))]
+pub(crate) fn url_matches_filter(filter: &str, url: &str) -> bool {
+	let mut parts = filter.split('*');
+	let first = parts.next().unwrap_or_default();
+	let mut rest = match url.strip_prefix(first) {
+		Some(rest) => rest,
+		None => return false
+	};
+	let parts = parts.collect::<Vec<_>>();
+	let (last, middle) = match parts.split_last() {
+		Some(split) => split,
+		// no wildcard, so the filter must match exactly
+		None => return rest.is_empty()
+	};
+	for part in middle {
+		match rest.find(part) {
+			Some(index) => rest = &rest[index + part.len()..],
+			None => return false
+		}
+	}
+	rest.ends_with(last)
+}
+
+/// The custom protocol binary IPC messages are posted to on platforms that expose request bodies.
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "ios"))]
+pub(crate) const BINARY_IPC_PROTOCOL: &str = "millennium-ipc";
+
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "ios"))]
+pub(crate) fn binary_ipc_protocol(window: Rc<Window>, handler: Box<dyn Fn(&Window, Vec<u8>)>) -> (String, Box<dyn Fn(HttpRequest, RequestAsyncResponder)>) {
+	use crate::http::{method::Method, ResponseBuilder};
+
+	(
+		BINARY_IPC_PROTOCOL.to_string(),
+		Box::new(move |request, responder| {
+			// CORS preflight requests carry no message
+			if request.method() == Method::POST {
+				handler(&window, request.body().clone());
+			}
+			responder.respond(
+				ResponseBuilder::new()
+					.header("Access-Control-Allow-Origin", "*")
+					.header("Access-Control-Allow-Headers", "*")
+					.body(Vec::new())
+			)
+		})
+	)
+}
+
 /// Get Webview/Webkit version on current platform.
 pub fn webview_version() -> Result<String> {
 	platform_webview_version()
@@ -641,4 +1384,37 @@ mod tests {
 			panic!("{}", error);
 		}
 	}
+
+	#[cfg(not(any(target_os = "android", target_os = "macos", target_os = "ios")))]
+	#[test]
+	fn url_origin_keeps_custom_protocols() {
+		assert_eq!(url_origin("millennium://localhost/index.html"), "millennium://localhost");
+		assert_eq!(url_origin("https://example.com:8080/a?b"), "https://example.com:8080");
+		assert_eq!(url_origin("https://example.com:443/"), "https://example.com");
+		assert_eq!(url_origin("data:text/html,hi"), "null");
+	}
+
+	#[test]
+	fn request_interceptor_filters_match_wildcards() {
+		assert!(url_matches_filter("https://api.example.com/*", "https://api.example.com/v1/users"));
+		assert!(url_matches_filter("https://*.example.com/*/users", "https://api.example.com/v1/users"));
+		assert!(url_matches_filter("https://example.com/", "https://example.com/"));
+		assert!(url_matches_filter("*", "https://example.com/"));
+		assert!(!url_matches_filter("https://api.example.com/*", "https://example.com/api.example.com/"));
+		assert!(!url_matches_filter("https://example.com/", "https://example.com/index.html"));
+		assert!(!url_matches_filter("https://*.example.com/*.json", "https://api.example.com/data.xml"));
+	}
+
+	#[cfg(any(target_os = "linux", target_os = "macos"))]
+	#[test]
+	fn media_capture_permission_requires_every_device() {
+		let handler = |_: &str, kind: PermissionKind| match kind {
+			PermissionKind::Camera => PermissionResponse::Allow,
+			PermissionKind::Microphone => PermissionResponse::Prompt,
+			_ => PermissionResponse::Deny
+		};
+		assert_eq!(request_media_capture_permission(Some(&handler), "", true, false), PermissionResponse::Allow);
+		assert_eq!(request_media_capture_permission(Some(&handler), "", true, true), PermissionResponse::Prompt);
+		assert_eq!(request_media_capture_permission(None, "", true, false), PermissionResponse::Deny);
+	}
 }
@@ -20,33 +20,56 @@ use std::sync::{
 	Arc
 };
 use std::{
+	cell::{Cell, RefCell},
 	collections::hash_map::DefaultHasher,
 	hash::{Hash, Hasher},
+	path::PathBuf,
 	rc::Rc
 };
 
-use gdk::{Cursor, EventMask, WindowEdge, RGBA};
+use gdk::{keys::constants as keys, Cursor, EventMask, ModifierType, WindowEdge, RGBA};
 use gio::Cancellable;
 use glib::signal::Inhibit;
 use gtk::prelude::*;
 use web_context::WebContextExt;
 pub use web_context::WebContextImpl;
 use webkit2gtk::{
-	traits::*, NavigationPolicyDecision, PolicyDecisionType, UserContentInjectedFrames, UserScript, UserScriptInjectionTime, WebView, WebViewBuilder
+	traits::*, CookieAcceptPolicy, GeolocationPermissionRequest, HardwareAccelerationPolicy, NavigationPolicyDecision, NotificationPermissionRequest,
+	PolicyDecisionType, PrintOperation, PrintOperationExt, SnapshotOptions, SnapshotRegion, UserContentInjectedFrames, UserMediaPermissionRequest, UserScript,
+	UserScriptInjectionTime, UserStyleLevel, UserStyleSheet, WebView, WebViewBuilder
 };
 use webkit2gtk_sys::{webkit_get_major_version, webkit_get_micro_version, webkit_get_minor_version, webkit_policy_decision_ignore, webkit_policy_decision_use};
 
 use crate::{
-	application::{platform::unix::*, window::Window},
-	webview::{web_context::WebContext, WebViewAttributes},
+	application::{
+		keyboard::{KeyCode, ModifiersState},
+		platform::unix::*,
+		window::Window
+	},
+	webview::{
+		request_media_capture_permission, request_permission, url_origin, web_context::WebContext, CookiePersistence, CssId, InitScriptId,
+		PdfOrientation, PdfSettings, PermissionKind, PermissionResponse, ScrollBarStyle, WebViewAttributes
+	},
 	Error, Result
 };
 
 mod file_drop;
 mod web_context;
+#[cfg(web_extension)]
+mod web_extension;
+
+/// Styles the scroll bars like GTK's classic scroll bars, which makes WebKitGTK draw them even when overlay scrolling
+/// is enabled. Pages can still restyle them.
+const ALWAYS_VISIBLE_SCROLL_BARS: &str = "::-webkit-scrollbar { width: 12px; height: 12px; background-color: transparent; }
+::-webkit-scrollbar-thumb { border: 3px solid transparent; border-radius: 6px; background-clip: content-box; background-color: rgba(128, 128, 128, 0.6); }
+::-webkit-scrollbar-thumb:hover { background-color: rgba(128, 128, 128, 0.8); }";
 
 pub struct InnerWebView {
 	pub(crate) webview: Rc<WebView>,
+	scripts: Rc<RefCell<Vec<(Option<InitScriptId>, UserScript)>>>,
+	style_sheets: Rc<RefCell<Vec<(Option<CssId>, UserStyleSheet)>>>,
+	/// Whether the webview was muted by [`InnerWebView::suspend`], so resuming doesn't unmute a page muted by the app.
+	muted_by_suspend: Cell<bool>,
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	is_inspector_open: Arc<AtomicBool>
 }
@@ -71,9 +94,26 @@ impl InnerWebView {
 			webview = webview.user_content_manager(web_context.manager());
 			webview = webview.web_context(web_context.context());
 			webview = webview.is_controlled_by_automation(web_context.allows_automation());
+			webview = webview.is_ephemeral(attributes.cookie_persistence == CookiePersistence::Session);
 			webview.build()
 		};
 
+		if let Some((filters, interceptor)) = attributes.request_interceptor.take() {
+			#[cfg(web_extension)]
+			web_extension::register_interceptor(webview.page_id(), filters, interceptor);
+			#[cfg(not(web_extension))]
+			{
+				let _ = (filters, interceptor);
+				log::warn!("Request interceptors are unsupported; Millennium Webview was built without the WebKitGTK web extension headers");
+			}
+		}
+
+		if attributes.block_third_party_cookies {
+			if let Some(cookie_manager) = webview.website_data_manager().and_then(|manager| manager.cookie_manager()) {
+				cookie_manager.set_accept_policy(CookieAcceptPolicy::NoThirdParty);
+			}
+		}
+
 		web_context.register_automation(webview.clone());
 
 		// Message handler
@@ -82,6 +122,13 @@ impl InnerWebView {
 		let ipc_handler = attributes.ipc_handler.take();
 		let manager = web_context.manager();
 
+		// WebKitGTK draws overlay scroll bars unless they're styled, so style them to keep them visible
+		if attributes.scroll_bar_style == ScrollBarStyle::AlwaysVisible {
+			let style_sheet = UserStyleSheet::new(ALWAYS_VISIBLE_SCROLL_BARS, UserContentInjectedFrames::AllFrames, UserStyleLevel::User, &[], &[]);
+			manager.add_style_sheet(&style_sheet);
+			web_context.style_sheets().borrow_mut().push((None, style_sheet));
+		}
+
 		// Use the window hash as the script handler name to prevent from conflict when
 		// sharing same web context.
 		let window_hash = {
@@ -91,7 +138,7 @@ impl InnerWebView {
 		};
 
 		// Connect before registering as recommended by the docs
-		manager.connect_script_message_received(None, move |_m, msg| {
+		manager.connect_script_message_received(Some(&window_hash), move |_m, msg| {
 			if let Some(js) = msg.js_value() {
 				if let Some(ipc_handler) = &ipc_handler {
 					ipc_handler(&w, js.to_string());
@@ -102,6 +149,21 @@ impl InnerWebView {
 		// Register the handler we just connected
 		manager.register_script_message_handler(&window_hash);
 
+		// WebKitGTK script messages can't carry binary data, so binary messages are sent as strings
+		// with one character per byte. NUL is sent as U+0100 since JSC strings are NUL-terminated.
+		let binary_ipc_handler = attributes.binary_ipc_handler.take();
+		let has_binary_ipc = binary_ipc_handler.is_some();
+		let binary_window_hash = format!("{}-binary", window_hash);
+		if let Some(binary_ipc_handler) = binary_ipc_handler {
+			let w = window_rc.clone();
+			manager.connect_script_message_received(Some(&binary_window_hash), move |_m, msg| {
+				if let Some(js) = msg.js_value() {
+					binary_ipc_handler(&w, js.to_string().chars().map(|c| c as u32 as u8).collect());
+				}
+			});
+			manager.register_script_message_handler(&binary_window_hash);
+		}
+
 		// Allow the webview to close it's own window
 		let close_window = window_rc.clone();
 		webview.connect_close(move |_| {
@@ -154,16 +216,22 @@ impl InnerWebView {
 					if let Some(window) = widget.parent() {
 						// Safe to unwrap unless this is not from Millennium Core
 						let window: gtk::Window = window.downcast().unwrap();
-						if !window.is_decorated() && window.is_resizable() {
-							if let Some(window) = window.window() {
-								// Safe to unwrap since it's a valide GtkWindow
-								let result = hit_test(&window, cx, cy);
-
-								// we ignore the `__Unknown` variant so the webview receives the click correctly
-								// if it is not on the edges.
-								match result {
-									WindowEdge::__Unknown(_) => (),
-									_ => window.begin_resize_drag(result, 1, cx as i32, cy as i32, event.time())
+						let edge = match window.window() {
+							Some(gdk_window) if !window.is_decorated() && window.is_resizable() => hit_test(&gdk_window, cx, cy),
+							_ => WindowEdge::__Unknown(8)
+						};
+
+						// we ignore the `__Unknown` variant so the webview receives the click correctly
+						// if it is not on the edges.
+						match edge {
+							WindowEdge::__Unknown(_) => {
+								if drag_region_hit_test(&window, cx, cy) {
+									window.begin_move_drag(1, cx as i32, cy as i32, event.time());
+								}
+							}
+							_ => {
+								if let Some(window) = window.window() {
+									window.begin_resize_drag(edge, 1, cx as i32, cy as i32, event.time())
 								}
 							}
 						}
@@ -200,6 +268,52 @@ impl InnerWebView {
 			Inhibit(false)
 		});
 
+		if !attributes.default_context_menu_enabled {
+			webview.connect_context_menu(|_, _, _, _| true);
+		}
+		if let Some(key_press_handler) = attributes.key_press_handler.take() {
+			webview.connect_key_press_event(move |_, event| {
+				let state = event.state();
+				let mut modifiers = ModifiersState::empty();
+				modifiers.set(ModifiersState::SHIFT, state.contains(ModifierType::SHIFT_MASK));
+				modifiers.set(ModifiersState::CONTROL, state.contains(ModifierType::CONTROL_MASK));
+				modifiers.set(ModifiersState::ALT, state.contains(ModifierType::MOD1_MASK));
+				modifiers.set(ModifiersState::SUPER, state.intersects(ModifierType::SUPER_MASK | ModifierType::META_MASK));
+				// the hardware keycode is what `KeyCode` calls the scancode on Linux
+				Inhibit(key_press_handler(modifiers, KeyCode::from_scancode(event.hardware_keycode() as u32)))
+			});
+		}
+		if !attributes.devtools_shortcuts_enabled {
+			webview.connect_key_press_event(|_, event| {
+				let key = event.keyval().to_lower();
+				let ctrl_shift = event.state().contains(ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK);
+				Inhibit(key == keys::F12 || (ctrl_shift && (key == keys::i || key == keys::c)))
+			});
+		}
+
+		// Permission requests are denied unless the handler allows them
+		let permission_handler = attributes.permission_handler.take();
+		webview.connect_permission_request(move |webview, request| {
+			let handler = permission_handler.as_deref();
+			let origin = webview.uri().map(|uri| url_origin(&uri)).unwrap_or_else(|| String::from("null"));
+			let response = if let Some(media) = request.dynamic_cast_ref::<UserMediaPermissionRequest>() {
+				request_media_capture_permission(handler, &origin, media.is_for_video_device(), media.is_for_audio_device())
+			} else if request.is::<GeolocationPermissionRequest>() {
+				request_permission(handler, &origin, PermissionKind::Geolocation)
+			} else if request.is::<NotificationPermissionRequest>() {
+				request_permission(handler, &origin, PermissionKind::Notifications)
+			} else {
+				request_permission(handler, &origin, PermissionKind::Other)
+			};
+
+			match response {
+				PermissionResponse::Allow => request.allow(),
+				PermissionResponse::Deny => request.deny(),
+				PermissionResponse::Prompt => return false
+			}
+			true
+		});
+
 		if attributes.navigation_handler.is_some() || attributes.new_window_handler.is_some() {
 			webview.connect_decide_policy(move |_webview, policy_decision, policy_type| {
 				let handler = match policy_type {
@@ -240,13 +354,19 @@ impl InnerWebView {
 		if let Some(settings) = WebViewExt::settings(&*webview) {
 			settings.set_enable_webgl(true);
 			settings.set_enable_webaudio(true);
-			settings.set_enable_accelerated_2d_canvas(true);
+			settings.set_enable_accelerated_2d_canvas(attributes.hardware_acceleration);
+			if !attributes.hardware_acceleration {
+				settings.set_hardware_acceleration_policy(HardwareAccelerationPolicy::Never);
+			}
 
 			// Enable clipboard
 			if attributes.clipboard {
 				settings.set_javascript_can_access_clipboard(true);
 			}
 
+			// WebKitGTK allows autoplay by default, unlike browsers
+			settings.set_media_playback_requires_user_gesture(!attributes.autoplay);
+
 			// Enable App cache
 			settings.set_enable_offline_web_application_cache(true);
 			settings.set_enable_page_cache(true);
@@ -257,6 +377,13 @@ impl InnerWebView {
 			if attributes.devtools {
 				settings.set_enable_developer_extras(true);
 			}
+
+			// The setting only exists since WebKitGTK 2.24
+			if let Some(swipe_navigation) = attributes.swipe_navigation {
+				if settings.find_property("enable-back-forward-navigation-gestures").is_some() {
+					settings.set_property("enable-back-forward-navigation-gestures", swipe_navigation);
+				}
+			}
 		}
 
 		// Transparent
@@ -271,6 +398,12 @@ impl InnerWebView {
 
 		if window.get_visible() {
 			window.show_all();
+		} else if attributes.headless {
+			// Give the webview a size to render at without ever mapping the window.
+			window.realize();
+			webview.show();
+			let (width, height) = window.size();
+			webview.size_allocate(&gtk::Allocation::new(0, 0, width, height));
 		}
 
 		#[cfg(any(debug_assertions, feature = "devtools"))]
@@ -292,6 +425,9 @@ impl InnerWebView {
 
 		let w = Self {
 			webview,
+			scripts: web_context.scripts().clone(),
+			style_sheets: web_context.style_sheets().clone(),
+			muted_by_suspend: Cell::new(false),
 			#[cfg(any(debug_assertions, feature = "devtools"))]
 			is_inspector_open
 		};
@@ -300,12 +436,20 @@ impl InnerWebView {
 		let mut init = String::with_capacity(115 + 20 + 22);
 		init.push_str("Object.defineProperty(window, 'ipc', {value: Object.freeze({postMessage:function(x){window.webkit.messageHandlers[\"");
 		init.push_str(&window_hash);
-		init.push_str("\"].postMessage(x)}})})");
-		w.init(&init)?;
+		init.push_str("\"].postMessage(x)}");
+		if has_binary_ipc {
+			init.push_str(",postBinaryMessage:function(d){var b=ArrayBuffer.isView(d)?new Uint8Array(d.buffer,d.byteOffset,d.byteLength):new Uint8Array(d),s='';");
+			init.push_str("for(var i=0;i<b.length;i+=32768){var c=new Uint16Array(b.subarray(i,i+32768));for(var j=0;j<c.length;j++)if(!c[j])c[j]=256;");
+			init.push_str("s+=String.fromCharCode.apply(null,c)}window.webkit.messageHandlers[\"");
+			init.push_str(&binary_window_hash);
+			init.push_str("\"].postMessage(s);return Promise.resolve()}");
+		}
+		init.push_str("})})");
+		w.init(None, &init)?;
 
 		// Initialize scripts
 		for js in attributes.initialization_scripts {
-			w.init(&js)?;
+			w.init(None, &js)?;
 		}
 
 		for (name, handler) in attributes.custom_protocols {
@@ -333,13 +477,64 @@ impl InnerWebView {
 		let _ = self.eval("window.print()");
 	}
 
+	pub fn print_to_pdf(&self, path: PathBuf, settings: PdfSettings, callback: Box<dyn FnOnce(Result<PathBuf>)>) -> Result<()> {
+		let print_settings = gtk::PrintSettings::new();
+		print_settings.set_printer("Print to File");
+		print_settings.set(&gtk::PRINT_SETTINGS_OUTPUT_FILE_FORMAT, Some("pdf"));
+		print_settings.set(&gtk::PRINT_SETTINGS_OUTPUT_URI, Some(&glib::filename_to_uri(&path, None)?));
+
+		let page_setup = gtk::PageSetup::new();
+		page_setup.set_paper_size(&gtk::PaperSize::new_custom("millennium", "Custom", settings.page_width, settings.page_height, gtk::Unit::Inch));
+		page_setup.set_orientation(match settings.orientation {
+			PdfOrientation::Portrait => gtk::PageOrientation::Portrait,
+			PdfOrientation::Landscape => gtk::PageOrientation::Landscape
+		});
+		page_setup.set_top_margin(settings.margin_top, gtk::Unit::Inch);
+		page_setup.set_bottom_margin(settings.margin_bottom, gtk::Unit::Inch);
+		page_setup.set_left_margin(settings.margin_left, gtk::Unit::Inch);
+		page_setup.set_right_margin(settings.margin_right, gtk::Unit::Inch);
+
+		let operation = PrintOperation::new(&*self.webview);
+		operation.set_print_settings(&print_settings);
+		operation.set_page_setup(&page_setup);
+
+		// `failed` is followed by `finished`, so whichever fires first gets the callback
+		let callback = Rc::new(RefCell::new(Some(callback)));
+		let failed_callback = callback.clone();
+		operation.connect_failed(move |_, error| {
+			if let Some(callback) = failed_callback.borrow_mut().take() {
+				callback(Err(Error::PrintToPdf(error.to_string())));
+			}
+		});
+		operation.connect_finished(move |_| {
+			if let Some(callback) = callback.borrow_mut().take() {
+				callback(Ok(path.clone()));
+			}
+		});
+		operation.print();
+		Ok(())
+	}
+
+	pub fn take_snapshot(&self, callback: Box<dyn FnOnce(Result<Vec<u8>>)>) -> Result<()> {
+		let snapshot = self.webview.snapshot_future(SnapshotRegion::Visible, SnapshotOptions::NONE);
+		glib::MainContext::default().spawn_local(async move {
+			callback(snapshot.await.map_err(Error::from).and_then(|surface| {
+				let surface = cairo::ImageSurface::try_from(surface).map_err(|_| Error::Snapshot("not an image surface".into()))?;
+				let mut png = Vec::new();
+				surface.write_to_png(&mut png).map_err(|e| Error::Snapshot(e.to_string()))?;
+				Ok(png)
+			}));
+		});
+		Ok(())
+	}
+
 	pub fn eval(&self, js: &str) -> Result<()> {
 		let cancellable: Option<&Cancellable> = None;
 		self.webview.run_javascript(js, cancellable, |_| ());
 		Ok(())
 	}
 
-	fn init(&self, js: &str) -> Result<()> {
+	fn init(&self, id: Option<InitScriptId>, js: &str) -> Result<()> {
 		if let Some(manager) = self.webview.user_content_manager() {
 			let script = UserScript::new(
 				js,
@@ -352,20 +547,118 @@ impl InnerWebView {
 				&[]
 			);
 			manager.add_script(&script);
+			self.scripts.borrow_mut().push((id, script));
 		} else {
 			return Err(Error::InitScriptError);
 		}
 		Ok(())
 	}
 
+	pub fn add_init_script(&self, id: InitScriptId, js: &str) -> Result<()> {
+		self.init(Some(id), js)
+	}
+
+	pub fn remove_init_script(&self, id: InitScriptId) -> Result<()> {
+		let manager = self.webview.user_content_manager().ok_or(Error::InitScriptError)?;
+		let mut scripts = self.scripts.borrow_mut();
+		let len = scripts.len();
+		scripts.retain(|(script_id, _)| *script_id != Some(id));
+		if scripts.len() != len {
+			// removing a single script needs WebKitGTK 2.32, so re-add the remaining ones instead
+			manager.remove_all_scripts();
+			for (_, script) in scripts.iter() {
+				manager.add_script(script);
+			}
+		}
+		Ok(())
+	}
+
+	pub fn insert_css(&self, id: CssId, css: &str) -> Result<()> {
+		let manager = self.webview.user_content_manager().ok_or(Error::InitScriptError)?;
+		let style_sheet = UserStyleSheet::new(css, UserContentInjectedFrames::TopFrame, UserStyleLevel::Author, &[], &[]);
+		manager.add_style_sheet(&style_sheet);
+		self.style_sheets.borrow_mut().push((Some(id), style_sheet));
+		Ok(())
+	}
+
+	pub fn remove_css(&self, id: CssId) -> Result<()> {
+		let manager = self.webview.user_content_manager().ok_or(Error::InitScriptError)?;
+		let mut style_sheets = self.style_sheets.borrow_mut();
+		let len = style_sheets.len();
+		style_sheets.retain(|(style_sheet_id, _)| *style_sheet_id != Some(id));
+		if style_sheets.len() != len {
+			// removing a single stylesheet needs WebKitGTK 2.32, so re-add the remaining ones instead
+			manager.remove_all_style_sheets();
+			for (_, style_sheet) in style_sheets.iter() {
+				manager.add_style_sheet(style_sheet);
+			}
+		}
+		Ok(())
+	}
+
+	pub fn suspend(&self) -> Result<()> {
+		// a hidden page stops rendering and WebKit throttles its timers
+		self.webview.hide();
+		// `is-muted` needs WebKitGTK 2.30, so look it up at runtime
+		if self.webview.find_property("is-muted").is_some() && !self.webview.property::<bool>("is-muted") {
+			self.webview.set_property("is-muted", true);
+			self.muted_by_suspend.set(true);
+		}
+		Ok(())
+	}
+
+	pub fn resume(&self) -> Result<()> {
+		if self.muted_by_suspend.replace(false) {
+			self.webview.set_property("is-muted", false);
+		}
+		self.webview.show();
+		Ok(())
+	}
+
+	pub fn memory_usage(&self) -> Option<u64> {
+		// WebKitGTK doesn't expose the web process ID, so look for web processes among our children
+		let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+		if page_size <= 0 {
+			return None;
+		}
+		let parent = std::process::id().to_string();
+		let mut resident_pages = None;
+		for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+			let path = entry.path();
+			let stat = match std::fs::read_to_string(path.join("stat")) {
+				Ok(stat) => stat,
+				Err(_) => continue
+			};
+			// `pid (comm) state ppid ...`, where `comm` is truncated to 15 bytes and may contain spaces
+			let (comm, rest) = match (stat.find('('), stat.rfind(')')) {
+				(Some(start), Some(end)) if start < end => (&stat[start + 1..end], &stat[end + 1..]),
+				_ => continue
+			};
+			if !comm.starts_with("WebKitWebProces") || rest.split_whitespace().nth(1) != Some(parent.as_str()) {
+				continue;
+			}
+			if let Some(pages) = std::fs::read_to_string(path.join("statm"))
+				.ok()
+				.and_then(|statm| statm.split_whitespace().nth(1)?.parse::<u64>().ok())
+			{
+				*resident_pages.get_or_insert(0) += pages;
+			}
+		}
+		resident_pages.map(|pages| pages * page_size as u64)
+	}
+
 	pub fn focus(&self) {
 		self.webview.grab_focus();
 	}
 
 	#[cfg(any(debug_assertions, feature = "devtools"))]
-	pub fn open_devtools(&self) {
+	pub fn open_devtools(&self, position: crate::webview::DevtoolsPosition) {
 		if let Some(inspector) = WebViewExt::inspector(&*self.webview) {
 			inspector.show();
+			match position {
+				crate::webview::DevtoolsPosition::Detached => inspector.detach(),
+				_ => inspector.attach()
+			}
 			// `bring-to-front` is not received in this case, so we have to manually set
 			// is_inspector_open
 			self.is_inspector_open.store(true, Ordering::Relaxed);
@@ -387,6 +680,19 @@ impl InnerWebView {
 	pub fn zoom(&self, scale_factor: f64) {
 		WebViewExt::set_zoom_level(&*self.webview, scale_factor);
 	}
+
+	pub fn set_clipboard(&self, enabled: bool) {
+		if let Some(settings) = WebViewExt::settings(&*self.webview) {
+			settings.set_javascript_can_access_clipboard(enabled);
+		}
+	}
+}
+
+#[cfg(web_extension)]
+impl Drop for InnerWebView {
+	fn drop(&mut self) {
+		web_extension::unregister_interceptor(self.webview.page_id());
+	}
 }
 
 pub fn platform_webview_version() -> Result<String> {
@@ -17,6 +17,7 @@
 //! Unix platform extensions for [`WebContext`](super::WebContext).
 
 use std::{
+	cell::RefCell,
 	collections::{HashSet, VecDeque},
 	rc::Rc,
 	sync::{
@@ -25,16 +26,20 @@ use std::{
 	}
 };
 
-use glib::FileError;
+use glib::{
+	thread_guard::{thread_id, ThreadGuard},
+	FileError
+};
 use url::Url;
 // use webkit2gtk_sys::webkit_uri_request_get_http_headers;
 use webkit2gtk::{
-	traits::*, ApplicationInfo, CookiePersistentStorage, LoadEvent, UserContentManager, WebContext, WebContextBuilder, WebView, WebsiteDataManagerBuilder
+	traits::*, ApplicationInfo, CookiePersistentStorage, LoadEvent, UserContentManager, UserScript, UserStyleSheet, WebContext, WebContextBuilder, WebView,
+	WebsiteDataManagerBuilder
 };
 
 use crate::{
-	http::{Request as HttpRequest, RequestBuilder as HttpRequestBuilder, Response as HttpResponse},
-	webview::web_context::WebContextData,
+	http::{Request as HttpRequest, RequestBuilder as HttpRequestBuilder},
+	webview::{web_context::WebContextData, CssId, InitScriptId, RequestAsyncResponder},
 	Error
 };
 
@@ -42,6 +47,8 @@ use crate::{
 pub struct WebContextImpl {
 	context: WebContext,
 	manager: UserContentManager,
+	scripts: Rc<RefCell<Vec<(Option<InitScriptId>, UserScript)>>>,
+	style_sheets: Rc<RefCell<Vec<(Option<CssId>, UserStyleSheet)>>>,
 	webview_uri_loader: Rc<WebviewUriLoader>,
 	registered_protocols: HashSet<String>,
 	automation: bool,
@@ -65,6 +72,8 @@ impl WebContextImpl {
 		}
 
 		let context = context_builder.build();
+		#[cfg(web_extension)]
+		super::web_extension::load(&context);
 
 		let automation = false;
 		context.set_automation_allowed(automation);
@@ -82,6 +91,8 @@ impl WebContextImpl {
 			context,
 			automation,
 			manager: UserContentManager::new(),
+			scripts: Rc::default(),
+			style_sheets: Rc::default(),
 			registered_protocols: Default::default(),
 			webview_uri_loader: Rc::default(),
 			app_info: Some(app_info)
@@ -103,6 +114,14 @@ pub trait WebContextExt {
 	/// The GTK [`UserContentManager`] of all webviews in the context.
 	fn manager(&self) -> &UserContentManager;
 
+	/// The scripts added to the context's [`UserContentManager`], in insertion order. Scripts added when creating a
+	/// webview have no ID.
+	fn scripts(&self) -> &Rc<RefCell<Vec<(Option<InitScriptId>, UserScript)>>>;
+
+	/// The stylesheets inserted into the context's [`UserContentManager`], in insertion order. Stylesheets added when
+	/// creating a webview have no ID.
+	fn style_sheets(&self) -> &Rc<RefCell<Vec<(Option<CssId>, UserStyleSheet)>>>;
+
 	/// Register a custom protocol to the web context.
 	///
 	/// When duplicate schemes are registered, the duplicate handler will still
@@ -111,7 +130,7 @@ pub trait WebContextExt {
 	/// implementation to properly handle duplicated scheme handlers.
 	fn register_uri_scheme<F>(&mut self, name: &str, handler: F) -> crate::Result<()>
 	where
-		F: Fn(&HttpRequest) -> crate::Result<HttpResponse> + 'static;
+		F: Fn(HttpRequest, RequestAsyncResponder) + 'static;
 
 	/// Register a custom protocol to the web context, only if it is not a
 	/// duplicate scheme.
@@ -121,7 +140,7 @@ pub trait WebContextExt {
 	/// `Err(Error::DuplicateCustomProtocol)`.
 	fn try_register_uri_scheme<F>(&mut self, name: &str, handler: F) -> crate::Result<()>
 	where
-		F: Fn(&HttpRequest) -> crate::Result<HttpResponse> + 'static;
+		F: Fn(HttpRequest, RequestAsyncResponder) + 'static;
 
 	/// Add a [`WebView`] to the queue waiting to be opened.
 	///
@@ -150,9 +169,17 @@ impl WebContextExt for super::WebContext {
 		&self.os.manager
 	}
 
+	fn scripts(&self) -> &Rc<RefCell<Vec<(Option<InitScriptId>, UserScript)>>> {
+		&self.os.scripts
+	}
+
+	fn style_sheets(&self) -> &Rc<RefCell<Vec<(Option<CssId>, UserStyleSheet)>>> {
+		&self.os.style_sheets
+	}
+
 	fn register_uri_scheme<F>(&mut self, name: &str, handler: F) -> crate::Result<()>
 	where
-		F: Fn(&HttpRequest) -> crate::Result<HttpResponse> + 'static
+		F: Fn(HttpRequest, RequestAsyncResponder) + 'static
 	{
 		actually_register_uri_scheme(self, name, handler)?;
 		if self.os.registered_protocols.insert(name.to_string()) {
@@ -164,7 +191,7 @@ impl WebContextExt for super::WebContext {
 
 	fn try_register_uri_scheme<F>(&mut self, name: &str, handler: F) -> crate::Result<()>
 	where
-		F: Fn(&HttpRequest) -> crate::Result<HttpResponse> + 'static
+		F: Fn(HttpRequest, RequestAsyncResponder) + 'static
 	{
 		if self.os.registered_protocols.insert(name.to_string()) {
 			actually_register_uri_scheme(self, name, handler)
@@ -208,7 +235,7 @@ impl WebContextExt for super::WebContext {
 
 fn actually_register_uri_scheme<F>(context: &mut super::WebContext, name: &str, handler: F) -> crate::Result<()>
 where
-	F: Fn(&HttpRequest) -> crate::Result<HttpResponse> + 'static
+	F: Fn(HttpRequest, RequestAsyncResponder) + 'static
 {
 	use webkit2gtk::traits::*;
 	let context = &context.os.context;
@@ -231,18 +258,32 @@ where
 			// FIXME: Read the body (forms post)
 			let http_request = HttpRequestBuilder::new().uri(uri).method("GET").body(Vec::new()).unwrap();
 
-			match handler(&http_request) {
-				Ok(http_response) => {
-					let buffer = http_response.body();
-
-					// FIXME: Set status code
-					// FIXME: Set sent headers
-
-					let input = gio::MemoryInputStream::from_bytes(&glib::Bytes::from(buffer));
-					request.finish(&input, buffer.len() as i64, http_response.mimetype())
+			// the request can only be finished on the main thread, the guard makes sure it never leaves it
+			let main_thread = thread_id();
+			let request = ThreadGuard::new(request.clone());
+			let responder = RequestAsyncResponder::new(move |http_response| {
+				let finish = move || {
+					let request = request.into_inner();
+					match http_response {
+						Some(http_response) => {
+							let buffer = http_response.body();
+
+							// FIXME: Set status code
+							// FIXME: Set sent headers
+
+							let input = gio::MemoryInputStream::from_bytes(&glib::Bytes::from(buffer));
+							request.finish(&input, buffer.len() as i64, http_response.mimetype())
+						}
+						None => request.finish_error(&mut glib::Error::new(FileError::Exist, "Could not get requested file."))
+					}
+				};
+				if thread_id() == main_thread {
+					finish();
+				} else {
+					glib::idle_add_once(finish);
 				}
-				Err(_) => request.finish_error(&mut glib::Error::new(FileError::Exist, "Could not get requested file."))
-			}
+			});
+			handler(http_request, responder);
 		} else {
 			request.finish_error(&mut glib::Error::new(FileError::Exist, "Could not get uri."));
 		}
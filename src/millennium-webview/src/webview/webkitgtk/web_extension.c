@@ -0,0 +1,141 @@
+/*
+ * Copyright 2022 pyke.io
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*
+ * WebKitGTK web process extension implementing request interceptors, since WebKitGTK only lets web process extensions
+ * modify outgoing requests. It asks the UI process (see `web_extension.rs`) for the filters of each page, and for the
+ * new headers of the requests matching them, over the private D-Bus connection whose address it's initialized with.
+ */
+
+#include <webkit2/webkit-web-extension.h>
+
+#define INTERFACE "io.pyke.Millennium.RequestInterceptor"
+#define OBJECT_PATH "/io/pyke/Millennium/RequestInterceptor"
+#define FILTERS_KEY "millennium-request-interceptor-filters"
+/* Requests wait for the UI process, so don't let a busy main loop stall them for the default 25 seconds. */
+#define CALL_TIMEOUT_MS 1000
+
+static GDBusConnection *connection = NULL;
+
+/* Whether `url` matches `filter`, where `*` matches any run of characters, like `url_matches_filter`. */
+static gboolean url_matches_filter(const char *filter, const char *url) {
+	for (; *filter != '*'; filter++, url++) {
+		if (*filter != *url)
+			return FALSE;
+		if (!*filter)
+			return TRUE;
+	}
+
+	for (filter++;; url++) {
+		if (url_matches_filter(filter, url))
+			return TRUE;
+		if (!*url)
+			return FALSE;
+	}
+}
+
+/* The filters of `page`, fetched from the UI process on its first request. */
+static const char *const *page_filters(WebKitWebPage *page) {
+	char **filters = g_object_get_data(G_OBJECT(page), FILTERS_KEY);
+	GVariant *reply;
+	GError *error = NULL;
+
+	if (filters)
+		return (const char *const *)filters;
+
+	reply = g_dbus_connection_call_sync(
+		connection, NULL, OBJECT_PATH, INTERFACE, "Filters", g_variant_new("(t)", webkit_web_page_get_id(page)), G_VARIANT_TYPE("(as)"),
+		G_DBUS_CALL_FLAGS_NONE, CALL_TIMEOUT_MS, NULL, &error
+	);
+	if (reply) {
+		g_variant_get(reply, "(^as)", &filters);
+		g_variant_unref(reply);
+	} else {
+		g_warning("Failed to get the request interceptor filters: %s", error->message);
+		g_error_free(error);
+		filters = g_new0(char *, 1);
+	}
+
+	g_object_set_data_full(G_OBJECT(page), FILTERS_KEY, filters, (GDestroyNotify)g_strfreev);
+	return (const char *const *)filters;
+}
+
+static gboolean send_request(WebKitWebPage *page, WebKitURIRequest *request, WebKitURIResponse *redirected_response, gpointer user_data) {
+	const char *uri = webkit_uri_request_get_uri(request);
+	SoupMessageHeaders *headers = webkit_uri_request_get_http_headers(request);
+	const char *const *filter;
+	SoupMessageHeadersIter headers_iter;
+	GVariantBuilder builder;
+	GVariantIter *removed_iter, *added_iter;
+	GVariant *reply;
+	GError *error = NULL;
+	const char *name, *value;
+
+	if (!headers)
+		return FALSE;
+	for (filter = page_filters(page); *filter && !url_matches_filter(*filter, uri); filter++)
+		;
+	if (!*filter)
+		return FALSE;
+
+	g_variant_builder_init(&builder, G_VARIANT_TYPE("a(ss)"));
+	soup_message_headers_iter_init(&headers_iter, headers);
+	while (soup_message_headers_iter_next(&headers_iter, &name, &value))
+		g_variant_builder_add(&builder, "(ss)", name, value);
+
+	reply = g_dbus_connection_call_sync(
+		connection, NULL, OBJECT_PATH, INTERFACE, "Intercept", g_variant_new("(tsa(ss))", webkit_web_page_get_id(page), uri, &builder),
+		G_VARIANT_TYPE("(asa(ss))"), G_DBUS_CALL_FLAGS_NONE, CALL_TIMEOUT_MS, NULL, &error
+	);
+	if (!reply) {
+		g_warning("Failed to intercept %s: %s", uri, error->message);
+		g_error_free(error);
+		return FALSE;
+	}
+
+	/* only the headers the interceptor changed are replaced */
+	g_variant_get(reply, "(asa(ss))", &removed_iter, &added_iter);
+	while (g_variant_iter_loop(removed_iter, "&s", &name))
+		soup_message_headers_remove(headers, name);
+	while (g_variant_iter_loop(added_iter, "(&s&s)", &name, &value))
+		soup_message_headers_append(headers, name, value);
+	g_variant_iter_free(removed_iter);
+	g_variant_iter_free(added_iter);
+	g_variant_unref(reply);
+	return FALSE;
+}
+
+static void page_created(WebKitWebExtension *extension, WebKitWebPage *page, gpointer user_data) {
+	g_signal_connect(page, "send-request", G_CALLBACK(send_request), NULL);
+}
+
+G_MODULE_EXPORT void webkit_web_extension_initialize_with_user_data(WebKitWebExtension *extension, GVariant *user_data) {
+	GError *error = NULL;
+
+	if (!g_variant_is_of_type(user_data, G_VARIANT_TYPE_STRING))
+		return;
+
+	connection = g_dbus_connection_new_for_address_sync(
+		g_variant_get_string(user_data, NULL), G_DBUS_CONNECTION_FLAGS_AUTHENTICATION_CLIENT, NULL, NULL, &error
+	);
+	if (!connection) {
+		g_warning("Failed to connect to the request interceptor: %s", error->message);
+		g_error_free(error);
+		return;
+	}
+
+	g_signal_connect(extension, "page-created", G_CALLBACK(page_created), NULL);
+}
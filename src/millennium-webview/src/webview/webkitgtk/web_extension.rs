@@ -0,0 +1,236 @@
+// Copyright 2022 pyke.io
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Request interceptors, implemented by a web process extension (`web_extension.c`) since WebKitGTK only lets web
+//! process extensions modify outgoing requests. The extension asks the UI process for the filters of each page, and
+//! for the new headers of the requests matching them, over a private D-Bus connection.
+
+use std::{
+	cell::RefCell,
+	collections::{hash_map::DefaultHasher, HashMap},
+	fs,
+	hash::{Hash, Hasher},
+	io,
+	path::PathBuf
+};
+
+use gio::{DBusAuthObserver, DBusConnection, DBusMethodInvocation, DBusNodeInfo, DBusServer, DBusServerFlags};
+use glib::{ToVariant, Variant};
+use webkit2gtk::{traits::WebContextExt, WebContext};
+
+use crate::http::header::{HeaderMap, HeaderName, HeaderValue};
+
+const EXTENSION: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/libmillennium_web_extension.so"));
+
+const INTERFACE_NAME: &str = "io.pyke.Millennium.RequestInterceptor";
+const OBJECT_PATH: &str = "/io/pyke/Millennium/RequestInterceptor";
+const INTERFACE: &str = r#"<node>
+	<interface name="io.pyke.Millennium.RequestInterceptor">
+		<method name="Filters">
+			<arg type="t" name="page" direction="in"/>
+			<arg type="as" name="filters" direction="out"/>
+		</method>
+		<method name="Intercept">
+			<arg type="t" name="page" direction="in"/>
+			<arg type="s" name="uri" direction="in"/>
+			<arg type="a(ss)" name="headers" direction="in"/>
+			<arg type="as" name="removed" direction="out"/>
+			<arg type="a(ss)" name="added" direction="out"/>
+		</method>
+	</interface>
+</node>"#;
+
+type RequestInterceptor = (Vec<String>, Box<dyn Fn(&str, &mut HeaderMap)>);
+
+thread_local! {
+	/// The request interceptors of the webviews, by page ID.
+	static INTERCEPTORS: RefCell<HashMap<u64, RequestInterceptor>> = RefCell::new(HashMap::new());
+	/// The server the web processes connect to, and their connections, which must be kept alive.
+	static SERVER: RefCell<Option<(DBusServer, Vec<DBusConnection>)>> = RefCell::new(None);
+}
+
+/// Loads the extension into the web processes of `context`. Must be called before `context` loads anything.
+pub(crate) fn load(context: &WebContext) {
+	if let Err(e) = try_load(context) {
+		log::warn!("Failed to load the request interceptor web extension: {}", e);
+	}
+}
+
+fn try_load(context: &WebContext) -> Result<(), Box<dyn std::error::Error>> {
+	let address = SERVER.with(|server| -> Result<_, glib::Error> {
+		let mut server = server.borrow_mut();
+		if server.is_none() {
+			*server = Some((start_server()?, Vec::new()));
+		}
+		Ok(server.as_ref().unwrap().0.client_address())
+	})?;
+
+	context.set_web_extensions_directory(&extension_directory()?.to_string_lossy());
+	context.set_web_extensions_initialization_user_data(&address.to_variant());
+	Ok(())
+}
+
+/// Writes the extension to the user's runtime directory, in a directory named after its hash so that apps built with
+/// different versions don't load each other's extension.
+fn extension_directory() -> io::Result<PathBuf> {
+	let mut hasher = DefaultHasher::new();
+	EXTENSION.hash(&mut hasher);
+	let directory = glib::user_runtime_dir().join(format!("millennium-web-extension-{:x}", hasher.finish()));
+	let path = directory.join("libmillennium_web_extension.so");
+	if !path.exists() {
+		fs::create_dir_all(&directory)?;
+		// write to a temporary file first so a web process never loads a partially written extension
+		let temporary = directory.join(format!(".{}.tmp", std::process::id()));
+		fs::write(&temporary, EXTENSION)?;
+		fs::rename(&temporary, &path)?;
+	}
+	Ok(directory)
+}
+
+fn start_server() -> Result<DBusServer, glib::Error> {
+	let socket = glib::user_runtime_dir().join(format!("millennium-request-interceptor-{}", std::process::id()));
+	let _ = fs::remove_file(&socket);
+
+	// only accept the web processes, which run as the same user
+	let observer = DBusAuthObserver::new();
+	observer.connect_authorize_authenticated_peer(|_, _, credentials| {
+		credentials.and_then(|credentials| credentials.unix_user().ok()) == Some(unsafe { libc::getuid() })
+	});
+
+	let server = DBusServer::new_sync(
+		&format!("unix:path={}", socket.display()),
+		DBusServerFlags::NONE,
+		&gio::dbus_generate_guid(),
+		Some(&observer),
+		None::<&gio::Cancellable>
+	)?;
+	server.connect_new_connection(|_, connection| match register(connection) {
+		Ok(()) => {
+			SERVER.with(|server| {
+				if let Some((_, connections)) = server.borrow_mut().as_mut() {
+					connections.retain(|connection| !connection.is_closed());
+					connections.push(connection.clone());
+				}
+			});
+			true
+		}
+		Err(e) => {
+			log::warn!("Failed to register the request interceptor: {}", e);
+			false
+		}
+	});
+	server.start();
+	Ok(server)
+}
+
+fn register(connection: &DBusConnection) -> Result<(), glib::Error> {
+	let node = DBusNodeInfo::for_xml(INTERFACE)?;
+	let interface = node.lookup_interface(INTERFACE_NAME).expect("missing request interceptor interface");
+	connection.register_object(
+		OBJECT_PATH,
+		&interface,
+		|_, _, _, _, method, parameters, invocation| handle_method_call(method, parameters, invocation),
+		// the interface has no properties
+		|_, _, _, _, _| ().to_variant(),
+		|_, _, _, _, _, _| false
+	)?;
+	Ok(())
+}
+
+fn handle_method_call(method: &str, parameters: Variant, invocation: DBusMethodInvocation) {
+	match method {
+		"Filters" => {
+			let page = parameters.get::<(u64,)>().map(|(page,)| page).unwrap_or_default();
+			let filters = INTERCEPTORS.with(|interceptors| interceptors.borrow().get(&page).map(|(filters, _)| filters.clone()).unwrap_or_default());
+			invocation.return_value(Some(&(filters,).to_variant()));
+		}
+		"Intercept" => {
+			let (page, uri, headers) = match parameters.get::<(u64, String, Vec<(String, String)>)>() {
+				Some(parameters) => parameters,
+				None => return invocation.return_dbus_error("org.freedesktop.DBus.Error.InvalidArgs", "Invalid arguments")
+			};
+			// headers that can't be represented in a `HeaderMap` are left out, and so are left untouched
+			let mut original = HeaderMap::new();
+			for (name, value) in &headers {
+				if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_bytes(value.as_bytes())) {
+					original.append(name, value);
+				}
+			}
+
+			let mut header_map = original.clone();
+			INTERCEPTORS.with(|interceptors| {
+				if let Some((_, interceptor)) = interceptors.borrow().get(&page) {
+					interceptor(&uri, &mut header_map);
+				}
+			});
+			invocation.return_value(Some(&changed_headers(&original, &header_map).to_variant()));
+		}
+		_ => invocation.return_dbus_error("org.freedesktop.DBus.Error.UnknownMethod", method)
+	}
+}
+
+/// Returns the names of the headers to remove from the request, and the headers to add back in their place, to turn
+/// `original` into `modified`. Only the headers whose values changed are removed.
+fn changed_headers(original: &HeaderMap, modified: &HeaderMap) -> (Vec<String>, Vec<(String, String)>) {
+	let removed = original
+		.keys()
+		.chain(modified.keys().filter(|name| !original.contains_key(*name)))
+		.filter(|name| !original.get_all(*name).iter().eq(modified.get_all(*name).iter()))
+		.map(|name| name.as_str().to_string())
+		.collect::<Vec<_>>();
+	let added = removed
+		.iter()
+		.flat_map(|name| modified.get_all(name.as_str()).iter().map(move |value| (name.clone(), String::from_utf8_lossy(value.as_bytes()).into_owned())))
+		.collect();
+	(removed, added)
+}
+
+/// Passes the requests of the webview with the page ID `page` matching `filters` to `interceptor`.
+pub(crate) fn register_interceptor(page: u64, filters: Vec<String>, interceptor: Box<dyn Fn(&str, &mut HeaderMap)>) {
+	INTERCEPTORS.with(|interceptors| interceptors.borrow_mut().insert(page, (filters, interceptor)));
+}
+
+pub(crate) fn unregister_interceptor(page: u64) {
+	INTERCEPTORS.with(|interceptors| interceptors.borrow_mut().remove(&page));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::changed_headers;
+	use crate::http::header::{HeaderMap, HeaderValue};
+
+	#[test]
+	fn only_returns_changed_headers() {
+		let mut original = HeaderMap::new();
+		original.insert("accept", HeaderValue::from_static("text/html"));
+		original.insert("user-agent", HeaderValue::from_static("Millennium"));
+		original.insert("x-removed", HeaderValue::from_static("1"));
+
+		let mut modified = original.clone();
+		modified.insert("user-agent", HeaderValue::from_static("Custom"));
+		modified.remove("x-removed");
+		modified.append("x-added", HeaderValue::from_static("a"));
+		modified.append("x-added", HeaderValue::from_static("b"));
+
+		let (removed, added) = changed_headers(&original, &modified);
+		assert_eq!(removed, vec!["user-agent", "x-removed", "x-added"]);
+		assert_eq!(added, vec![
+			("user-agent".to_string(), "Custom".to_string()),
+			("x-added".to_string(), "a".to_string()),
+			("x-added".to_string(), "b".to_string())
+		]);
+
+		assert_eq!(changed_headers(&original, &original), (Vec::new(), Vec::new()));
+	}
+}
@@ -16,22 +16,34 @@
 
 mod file_drop;
 
-use std::{collections::HashSet, fmt::Write, mem::MaybeUninit, rc::Rc, sync::mpsc};
+use std::{
+	cell::{Cell, RefCell},
+	collections::{HashMap, HashSet},
+	fmt::Write,
+	mem::MaybeUninit,
+	path::PathBuf,
+	rc::Rc,
+	sync::mpsc,
+	thread
+};
 
 use file_drop::FileDropController;
-use once_cell::unsync::OnceCell;
+use once_cell::{sync::Lazy, unsync::OnceCell};
 use webview2_com::{Microsoft::Web::WebView2::Win32::*, *};
 use windows::{
 	core::{Interface, PCWSTR, PWSTR},
 	Win32::{
-		Foundation::{BOOL, E_FAIL, E_POINTER, FARPROC, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
+		Foundation::{CloseHandle, BOOL, E_FAIL, E_POINTER, FARPROC, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
 		System::{
-			Com::{IStream, StructuredStorage::CreateStreamOnHGlobal},
+			Com::{IStream, StructuredStorage::CreateStreamOnHGlobal, STREAM_SEEK_SET},
 			LibraryLoader::{GetProcAddress, LoadLibraryA},
+			ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
 			SystemInformation::OSVERSIONINFOW,
+			Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION},
 			WinRT::EventRegistrationToken
 		},
 		UI::{
+			Input::KeyboardAndMouse::{GetKeyState, VK_CONTROL, VK_F12, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT},
 			Shell::{DefSubclassProc, SetWindowSubclass},
 			WindowsAndMessaging as win32wm
 		}
@@ -39,11 +51,24 @@ use windows::{
 };
 
 use crate::{
-	application::{platform::windows::WindowExtWindows, window::Window},
-	http::RequestBuilder as HttpRequestBuilder
+	application::{
+		accelerator::Accelerator,
+		dpi::PhysicalPosition,
+		keyboard::{KeyCode, ModifiersState},
+		platform::windows::WindowExtWindows,
+		window::Window
+	},
+	http::{
+		header::{HeaderMap, HeaderName, HeaderValue},
+		RequestBuilder as HttpRequestBuilder, Response as HttpResponse
+	}
 };
 use crate::{
-	webview::{WebContext, WebViewAttributes},
+	webview::{
+		binary_ipc_protocol, insert_css_script, remove_css_script, request_permission, url_matches_filter, url_origin, CookiePersistence, CssId, InitScriptId,
+		PdfOrientation, PdfSettings, PermissionKind, PermissionResponse, RequestAsyncResponder, ScrollBarStyle, WebContext, WebViewAttributes,
+		BINARY_IPC_PROTOCOL
+	},
 	Error, Result
 };
 
@@ -59,7 +84,13 @@ pub struct InnerWebView {
 	// Store FileDropController in here to make sure it gets dropped when
 	// the webview gets dropped, otherwise we'll have a memory leak
 	#[allow(dead_code)]
-	file_drop_controller: Rc<OnceCell<FileDropController>>
+	file_drop_controller: Rc<OnceCell<FileDropController>>,
+	// `None` until clipboard access is explicitly set, leaving the decision to WebView2, which prompts the user
+	clipboard: Rc<Cell<Option<bool>>>,
+	// Maps to the IDs WebView2 gave the document-created scripts
+	init_scripts: RefCell<HashMap<InitScriptId, String>>,
+	// WebView2 has no user stylesheets, so inserted CSS is a document-created script; maps to the script's ID
+	css_scripts: RefCell<HashMap<CssId, String>>
 }
 
 impl InnerWebView {
@@ -69,9 +100,16 @@ impl InnerWebView {
 		let file_drop_handler = attributes.file_drop_handler.take();
 		let file_drop_window = window.clone();
 
-		let env = Self::create_environment(&web_context)?;
-		let controller = Self::create_controller(hwnd, &env)?;
-		let webview = Self::init_webview(window, hwnd, attributes, &env, &controller)?;
+		let env = Self::create_environment(
+			&web_context,
+			attributes.autoplay,
+			attributes.scroll_bar_style,
+			attributes.hardware_acceleration,
+			attributes.scroll_bounce
+		)?;
+		let controller = Self::create_controller(hwnd, &env, attributes.cookie_persistence == CookiePersistence::Session)?;
+		let clipboard = Rc::new(Cell::new(if attributes.clipboard { Some(true) } else { None }));
+		let webview = Self::init_webview(window, hwnd, attributes, clipboard.clone(), &env, &controller)?;
 
 		if let Some(file_drop_handler) = file_drop_handler {
 			let mut controller = FileDropController::new();
@@ -82,11 +120,20 @@ impl InnerWebView {
 		Ok(Self {
 			controller,
 			webview,
-			file_drop_controller
+			file_drop_controller,
+			clipboard,
+			init_scripts: Default::default(),
+			css_scripts: Default::default()
 		})
 	}
 
-	fn create_environment(web_context: &Option<&mut WebContext>) -> webview2_com::Result<ICoreWebView2Environment> {
+	fn create_environment(
+		web_context: &Option<&mut WebContext>,
+		autoplay: bool,
+		scroll_bar_style: ScrollBarStyle,
+		hardware_acceleration: bool,
+		scroll_bounce: bool
+	) -> webview2_com::Result<ICoreWebView2Environment> {
 		let (tx, rx) = mpsc::channel();
 
 		let data_directory = web_context
@@ -99,8 +146,22 @@ impl InnerWebView {
 			Box::new(move |environmentcreatedhandler| unsafe {
 				let options: ICoreWebView2EnvironmentOptions = CoreWebView2EnvironmentOptions::default().into();
 
-				// remove "mini menu"
-				let _ = options.SetAdditionalBrowserArguments("--disable-features=msWebOOUI,msPdfOOUI");
+				// remove "mini menu"; Chromium only reads the last `--disable-features`, so every feature goes in one list
+				let mut disabled_features = String::from("msWebOOUI,msPdfOOUI");
+				if !scroll_bounce {
+					disabled_features.push_str(",ElasticOverscroll");
+				}
+				let mut browser_args = format!("--disable-features={}", disabled_features);
+				if autoplay {
+					browser_args.push_str(" --autoplay-policy=no-user-gesture-required");
+				}
+				if scroll_bar_style == ScrollBarStyle::Overlay {
+					browser_args.push_str(" --enable-features=OverlayScrollbar,msOverlayScrollbarWinStyle,msOverlayScrollbarWinStyleAnimation");
+				}
+				if !hardware_acceleration {
+					browser_args.push_str(" --disable-gpu");
+				}
+				let _ = options.SetAdditionalBrowserArguments(browser_args.as_str());
 
 				if let Some(data_directory) = data_directory {
 					CreateCoreWebView2EnvironmentWithOptions(PCWSTR::default(), data_directory, options, environmentcreatedhandler)
@@ -122,12 +183,33 @@ impl InnerWebView {
 			.map_err(webview2_com::Error::WindowsError)
 	}
 
-	fn create_controller(hwnd: HWND, env: &ICoreWebView2Environment) -> webview2_com::Result<ICoreWebView2Controller> {
+	/// Creates the controller, InPrivate if `in_private` is set so that cookies and other browsing data are only kept
+	/// in memory, without touching the data directory the other webviews use.
+	fn create_controller(hwnd: HWND, env: &ICoreWebView2Environment, in_private: bool) -> webview2_com::Result<ICoreWebView2Controller> {
 		let (tx, rx) = mpsc::channel();
 		let env = env.clone();
+		let env10 = if in_private {
+			let env10 = env.cast::<ICoreWebView2Environment10>().ok();
+			if env10.is_none() {
+				log::warn!("Session cookie persistence requires WebView2 Runtime 101 or newer; cookies will be persisted");
+			}
+			env10
+		} else {
+			None
+		};
 
 		CreateCoreWebView2ControllerCompletedHandler::wait_for_async_operation(
-			Box::new(move |handler| unsafe { env.CreateCoreWebView2Controller(hwnd, handler).map_err(webview2_com::Error::WindowsError) }),
+			Box::new(move |handler| unsafe {
+				match env10 {
+					Some(env10) => {
+						let options = env10.CreateCoreWebView2ControllerOptions()?;
+						options.SetIsInPrivateModeEnabled(true)?;
+						env10.CreateCoreWebView2ControllerWithOptions(hwnd, options, handler)
+					}
+					None => env.CreateCoreWebView2Controller(hwnd, handler)
+				}
+				.map_err(webview2_com::Error::WindowsError)
+			}),
 			Box::new(move |error_code, controller| {
 				error_code?;
 				tx.send(controller.ok_or_else(|| windows::core::Error::from(E_POINTER)))
@@ -145,6 +227,7 @@ impl InnerWebView {
 		window: Rc<Window>,
 		hwnd: HWND,
 		mut attributes: WebViewAttributes,
+		clipboard: Rc<Cell<Option<bool>>>,
 		env: &ICoreWebView2Environment,
 		controller: &ICoreWebView2Controller
 	) -> webview2_com::Result<ICoreWebView2> {
@@ -180,7 +263,7 @@ impl InnerWebView {
 			let settings = webview.Settings().map_err(webview2_com::Error::WindowsError)?;
 			settings.SetIsStatusBarEnabled(false).map_err(webview2_com::Error::WindowsError)?;
 			settings
-				.SetAreDefaultContextMenusEnabled(true)
+				.SetAreDefaultContextMenusEnabled(attributes.default_context_menu_enabled)
 				.map_err(webview2_com::Error::WindowsError)?;
 			settings
 				.SetIsZoomControlEnabled(attributes.zoom_hotkeys_enabled)
@@ -191,26 +274,133 @@ impl InnerWebView {
 			}
 
 			let settings5 = settings.cast::<ICoreWebView2Settings5>()?;
-			let _ = settings5.SetIsPinchZoomEnabled(attributes.zoom_hotkeys_enabled);
+			let _ = settings5.SetIsPinchZoomEnabled(attributes.pinch_zoom.unwrap_or(attributes.zoom_hotkeys_enabled));
+			if let Some(swipe_navigation) = attributes.swipe_navigation {
+				if let Ok(settings6) = settings.cast::<ICoreWebView2Settings6>() {
+					let _ = settings6.SetIsSwipeNavigationEnabled(swipe_navigation);
+				}
+			}
+
+			// WebView2 swallows the key presses it handles, so the ones that should reach the window are posted to it
+			// instead, where the menu accelerators and the default window procedure get them
+			if !attributes.passthrough_accelerators.is_empty() {
+				let passthrough_accelerators = std::mem::take(&mut attributes.passthrough_accelerators);
+				controller
+					.add_AcceleratorKeyPressed(
+						AcceleratorKeyPressedEventHandler::create(Box::new(move |_, args| {
+							if let Some(args) = args {
+								let mut kind = COREWEBVIEW2_KEY_EVENT_KIND::default();
+								args.KeyEventKind(&mut kind)?;
+								if kind == COREWEBVIEW2_KEY_EVENT_KIND_KEY_DOWN || kind == COREWEBVIEW2_KEY_EVENT_KIND_SYSTEM_KEY_DOWN {
+									let mut status = COREWEBVIEW2_PHYSICAL_KEY_STATUS::default();
+									args.PhysicalKeyStatus(&mut status)?;
+									let (modifiers, key) = (current_modifiers(), physical_key_code(&status));
+									if passthrough_accelerators.iter().any(|accelerator| accelerator.matches(modifiers, key)) {
+										let mut virtual_key = 0;
+										args.VirtualKey(&mut virtual_key)?;
+										let msg = if kind == COREWEBVIEW2_KEY_EVENT_KIND_SYSTEM_KEY_DOWN {
+											win32wm::WM_SYSKEYDOWN
+										} else {
+											win32wm::WM_KEYDOWN
+										};
+										let mut lparam = status.RepeatCount | (status.ScanCode << 16);
+										if status.IsExtendedKey.as_bool() {
+											lparam |= 1 << 24;
+										}
+										if status.IsMenuKeyDown.as_bool() {
+											lparam |= 1 << 29;
+										}
+										win32wm::PostMessageW(hwnd, msg, WPARAM(virtual_key as usize), LPARAM(lparam as isize));
+										args.SetHandled(true)?;
+									}
+								}
+							}
+							Ok(())
+						})),
+						&mut token
+					)
+					.map_err(webview2_com::Error::WindowsError)?;
+			}
+
+			// Runs before the devtools shortcuts handler so the app gets the first look at every key press
+			if let Some(key_press_handler) = attributes.key_press_handler.take() {
+				controller
+					.add_AcceleratorKeyPressed(
+						AcceleratorKeyPressedEventHandler::create(Box::new(move |_, args| {
+							if let Some(args) = args {
+								let mut kind = COREWEBVIEW2_KEY_EVENT_KIND::default();
+								args.KeyEventKind(&mut kind)?;
+								// Key presses with Alt held are reported as system keys
+								if kind == COREWEBVIEW2_KEY_EVENT_KIND_KEY_DOWN || kind == COREWEBVIEW2_KEY_EVENT_KIND_SYSTEM_KEY_DOWN {
+									let mut status = COREWEBVIEW2_PHYSICAL_KEY_STATUS::default();
+									args.PhysicalKeyStatus(&mut status)?;
+									if key_press_handler(current_modifiers(), physical_key_code(&status)) {
+										args.SetHandled(true)?;
+									}
+								}
+							}
+							Ok(())
+						})),
+						&mut token
+					)
+					.map_err(webview2_com::Error::WindowsError)?;
+			}
+
+			if !attributes.devtools_shortcuts_enabled {
+				controller
+					.add_AcceleratorKeyPressed(
+						AcceleratorKeyPressedEventHandler::create(Box::new(|_, args| {
+							if let Some(args) = args {
+								let mut kind = COREWEBVIEW2_KEY_EVENT_KIND::default();
+								args.KeyEventKind(&mut kind)?;
+								if kind == COREWEBVIEW2_KEY_EVENT_KIND_KEY_DOWN {
+									let mut key = 0;
+									args.VirtualKey(&mut key)?;
+									let ctrl_shift = GetKeyState(VK_CONTROL.0 as i32) < 0 && GetKeyState(VK_SHIFT.0 as i32) < 0;
+									// F12, and Ctrl+Shift+I/J/C open the devtools on their respective panels
+									if key == VK_F12.0 as u32 || (ctrl_shift && matches!(char::from_u32(key), Some('I' | 'J' | 'C'))) {
+										args.SetHandled(true)?;
+									}
+								}
+							}
+							Ok(())
+						})),
+						&mut token
+					)
+					.map_err(webview2_com::Error::WindowsError)?;
+			}
 
 			let mut rect = RECT::default();
 			win32wm::GetClientRect(hwnd, &mut rect);
 			controller.SetBounds(rect).map_err(webview2_com::Error::WindowsError)?;
 		}
 
+		// Binary messages are posted as request bodies to a custom protocol
+		let post_binary_message = match attributes.binary_ipc_handler.take() {
+			Some(handler) => {
+				attributes.custom_protocols.push(binary_ipc_protocol(window.clone(), handler));
+				format!(
+					", postBinaryMessage: data => fetch('https://{}.localhost/', {{ method: 'POST', body: data }}).then(() => {{}})",
+					BINARY_IPC_PROTOCOL
+				)
+			}
+			None => String::new()
+		};
+
 		// Initialize scripts
 		Self::add_script_to_execute_on_document_created(
 			&webview,
-			String::from(
-				r#"Object.defineProperty(window, 'ipc', {
-					value: Object.freeze({ postMessage: s => window.chrome.webview.postMessage(s) })
-				});
+			format!(
+				r#"Object.defineProperty(window, 'ipc', {{
+					value: Object.freeze({{ postMessage: s => window.chrome.webview.postMessage(s){} }})
+				}});
 
-				window.addEventListener('mousedown', e => {
+				window.addEventListener('mousedown', e => {{
 					if (e.buttons === 1)
 						window.chrome.webview.postMessage('__WEBVIEW_LEFT_MOUSE_DOWN__');
-				});
-				window.addEventListener('mousemove', e => window.chrome.webview.postMessage('__WEBVIEW_MOUSE_MOVE__'));"#
+				}});
+				window.addEventListener('mousemove', e => window.chrome.webview.postMessage('__WEBVIEW_MOUSE_MOVE__'));"#,
+				post_binary_message
 			)
 		)?;
 		for js in attributes.initialization_scripts {
@@ -256,6 +446,18 @@ impl InnerWebView {
 									// `millennium_core::window::drag_window`.
 									if result.0 as u32 != win32wm::HTCLIENT {
 										window.begin_resize_drag(result.0, win32wm::WM_NCLBUTTONDOWN, point.x, point.y);
+										return Ok(());
+									}
+								}
+							}
+							// the webview covers the client area, so the `WM_NCHITTEST` of the window doesn't see
+							// clicks on the drag regions
+							if js == "__WEBVIEW_LEFT_MOUSE_DOWN__" {
+								if let Ok(origin) = window.inner_position() {
+									let mut point = POINT::default();
+									win32wm::GetCursorPos(&mut point);
+									if window.is_drag_region(PhysicalPosition::new(point.x - origin.x, point.y - origin.y)) {
+										let _ = window.drag_window();
 									}
 								}
 							}
@@ -389,49 +591,29 @@ impl InnerWebView {
 									let path = uri.replace(&format!("https://{}.", custom_protocol.0), &format!("{}://", custom_protocol.0));
 									let final_request = request.uri(&path).method(request_method.as_str()).body(body_sent).unwrap();
 
-									return match (custom_protocol.1)(&final_request) {
-										Ok(sent_response) => {
-											let content = sent_response.body();
-											let status_code = sent_response.status().as_u16() as i32;
-
-											let mut headers_map = String::new();
-
-											// set mime type if provided
-											if let Some(mime) = sent_response.mimetype() {
-												writeln!(headers_map, "Content-Type: {}", mime).unwrap();
-											}
-
-											// build headers
-											for (name, value) in sent_response.headers().iter() {
-												let header_key = name.to_string();
-												if let Ok(value) = value.to_str() {
-													writeln!(headers_map, "{}: {}", header_key, value).unwrap();
-												}
-											}
-
-											let mut body_sent = None;
-											if !content.is_empty() {
-												let stream = CreateStreamOnHGlobal(0, true)?;
-												stream.SetSize(content.len() as u64)?;
-												let mut cb_write = MaybeUninit::uninit();
-												if stream
-													.Write(content.as_ptr() as *const _, content.len() as u32, cb_write.as_mut_ptr())
-													.is_ok() && cb_write.assume_init() as usize == content.len()
-												{
-													body_sent = Some(stream);
-												}
+									// the handler may respond from another thread, but the request can only be completed on this one
+									let completion = UiThreadBound((env.clone(), args, args.GetDeferral()?));
+									let ui_thread = thread::current().id();
+									let responder = RequestAsyncResponder::new(move |sent_response| {
+										let complete = move || {
+											let (env, args, deferral) = completion.into_inner();
+											let response = match sent_response {
+												Some(sent_response) => web_resource_response(&env, &sent_response),
+												None => env.CreateWebResourceResponse(None::<IStream>, 500, "Internal Server Error", "")
+											};
+											if let Err(e) = response.and_then(|response| args.SetResponse(response)) {
+												log::warn!("Failed to respond to a custom protocol request: {}", e);
 											}
-
-											// FIXME: Set http response version
-
-											let body_sent = body_sent.map(|content| content.cast().unwrap());
-											let response = env.CreateWebResourceResponse(body_sent, status_code, "OK", headers_map)?;
-
-											args.SetResponse(response)?;
-											Ok(())
+											let _ = deferral.Complete();
+										};
+										if thread::current().id() == ui_thread {
+											complete();
+										} else {
+											dispatch_to_window(hwnd, complete);
 										}
-										Err(_) => Err(E_FAIL.into())
-									};
+									});
+									(custom_protocol.1)(final_request, responder);
+									return Ok(());
 								}
 							}
 
@@ -443,19 +625,47 @@ impl InnerWebView {
 			}
 		}
 
-		// Enable clipboard
-		if attributes.clipboard {
+		// The filters are shared with the custom protocols, so the handler checks the URL against its own filters again
+		if let Some((filters, interceptor)) = attributes.request_interceptor.take() {
+			for filter in &filters {
+				unsafe { webview.AddWebResourceRequestedFilter(filter.as_str(), COREWEBVIEW2_WEB_RESOURCE_CONTEXT_ALL) }
+					.map_err(webview2_com::Error::WindowsError)?;
+			}
+
+			let custom_protocol_names = custom_protocol_names.clone();
 			unsafe {
 				webview
-					.add_PermissionRequested(
-						PermissionRequestedEventHandler::create(Box::new(|_, args| {
+					.add_WebResourceRequested(
+						WebResourceRequestedEventHandler::create(Box::new(move |_, args| {
 							if let Some(args) = args {
-								let mut kind = COREWEBVIEW2_PERMISSION_KIND_UNKNOWN_PERMISSION;
-								args.PermissionKind(&mut kind)?;
-								if kind == COREWEBVIEW2_PERMISSION_KIND_CLIPBOARD_READ {
-									args.SetState(COREWEBVIEW2_PERMISSION_STATE_ALLOW)?;
+								let webview_request = args.Request()?;
+								let mut uri = PWSTR::default();
+								webview_request.Uri(&mut uri)?;
+								let uri = take_pwstr(uri);
+								if custom_protocol_names.iter().any(|name| uri.starts_with(&format!("https://{}.", name)))
+									|| !filters.iter().any(|filter| url_matches_filter(filter, &uri))
+								{
+									return Ok(());
+								}
+
+								let request_headers = webview_request.Headers()?;
+								let original = read_request_headers(&request_headers)?;
+								let mut headers = original.clone();
+								interceptor(&uri, &mut headers);
+
+								for name in original.keys() {
+									if !headers.contains_key(name) {
+										request_headers.RemoveHeader(name.as_str())?;
+									}
+								}
+								for name in headers.keys() {
+									if headers.get_all(name).iter().ne(original.get_all(name).iter()) {
+										let value = headers.get_all(name).iter().filter_map(|value| value.to_str().ok()).collect::<Vec<_>>().join(", ");
+										request_headers.SetHeader(name.as_str(), value)?;
+									}
 								}
 							}
+
 							Ok(())
 						})),
 						&mut token
@@ -464,6 +674,60 @@ impl InnerWebView {
 			}
 		}
 
+		// Clipboard access can be changed later, so the handler is always registered
+		unsafe {
+			webview
+				.add_PermissionRequested(
+					PermissionRequestedEventHandler::create(Box::new(move |_, args| {
+						if let (Some(args), Some(allowed)) = (args, clipboard.get()) {
+							let mut kind = COREWEBVIEW2_PERMISSION_KIND_UNKNOWN_PERMISSION;
+							args.PermissionKind(&mut kind)?;
+							if kind == COREWEBVIEW2_PERMISSION_KIND_CLIPBOARD_READ {
+								args.SetState(if allowed { COREWEBVIEW2_PERMISSION_STATE_ALLOW } else { COREWEBVIEW2_PERMISSION_STATE_DENY })?;
+							}
+						}
+						Ok(())
+					})),
+					&mut token
+				)
+				.map_err(webview2_com::Error::WindowsError)?;
+		}
+
+		// Permission requests are denied unless the handler allows them
+		let permission_handler = attributes.permission_handler.take();
+		unsafe {
+			webview
+				.add_PermissionRequested(
+					PermissionRequestedEventHandler::create(Box::new(move |_, args| {
+						if let Some(args) = args {
+							let mut kind = COREWEBVIEW2_PERMISSION_KIND_UNKNOWN_PERMISSION;
+							args.PermissionKind(&mut kind)?;
+							let kind = match kind {
+								// handled by the clipboard setting
+								COREWEBVIEW2_PERMISSION_KIND_CLIPBOARD_READ => return Ok(()),
+								COREWEBVIEW2_PERMISSION_KIND_CAMERA => PermissionKind::Camera,
+								COREWEBVIEW2_PERMISSION_KIND_MICROPHONE => PermissionKind::Microphone,
+								COREWEBVIEW2_PERMISSION_KIND_GEOLOCATION => PermissionKind::Geolocation,
+								COREWEBVIEW2_PERMISSION_KIND_NOTIFICATIONS => PermissionKind::Notifications,
+								_ => PermissionKind::Other
+							};
+
+							let mut uri = PWSTR::default();
+							args.Uri(&mut uri)?;
+							let origin = url_origin(&take_pwstr(uri));
+							match request_permission(permission_handler.as_deref(), &origin, kind) {
+								PermissionResponse::Allow => args.SetState(COREWEBVIEW2_PERMISSION_STATE_ALLOW)?,
+								PermissionResponse::Deny => args.SetState(COREWEBVIEW2_PERMISSION_STATE_DENY)?,
+								PermissionResponse::Prompt => {}
+							}
+						}
+						Ok(())
+					})),
+					&mut token
+				)
+				.map_err(webview2_com::Error::WindowsError)?;
+		}
+
 		// Set user agent
 		if let Some(user_agent) = attributes.user_agent {
 			unsafe {
@@ -502,6 +766,12 @@ impl InnerWebView {
 
 		unsafe {
 			unsafe extern "system" fn subclass_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM, _uidsubclass: usize, dwrefdata: usize) -> LRESULT {
+				if msg == *EXEC_MSG_ID {
+					let function = Box::from_raw(wparam.0 as *mut Box<dyn FnOnce()>);
+					function();
+					return LRESULT(0);
+				}
+
 				if msg == win32wm::WM_SIZE {
 					let controller = dwrefdata as *mut ICoreWebView2Controller;
 					let mut client_rect = RECT::default();
@@ -533,7 +803,8 @@ impl InnerWebView {
 		Ok(webview)
 	}
 
-	fn add_script_to_execute_on_document_created(webview: &ICoreWebView2, js: String) -> webview2_com::Result<()> {
+	fn add_script_to_execute_on_document_created(webview: &ICoreWebView2, js: String) -> webview2_com::Result<String> {
+		let (tx, rx) = mpsc::channel();
 		let handler_webview = webview.clone();
 		AddScriptToExecuteOnDocumentCreatedCompletedHandler::wait_for_async_operation(
 			Box::new(move |handler| unsafe {
@@ -541,8 +812,14 @@ impl InnerWebView {
 					.AddScriptToExecuteOnDocumentCreated(js, handler)
 					.map_err(webview2_com::Error::WindowsError)
 			}),
-			Box::new(|_, _| Ok(()))
-		)
+			Box::new(move |error_code, id| {
+				error_code?;
+				tx.send(id).expect("send over mpsc channel");
+				Ok(())
+			})
+		)?;
+
+		rx.recv().map_err(|_| webview2_com::Error::SendError)
 	}
 
 	fn execute_script(webview: &ICoreWebView2, js: String) -> windows::core::Result<()> {
@@ -553,16 +830,135 @@ impl InnerWebView {
 		let _ = self.eval("window.print()");
 	}
 
+	fn create_print_settings(webview: &ICoreWebView2, settings: &PdfSettings) -> windows::core::Result<ICoreWebView2PrintSettings> {
+		unsafe {
+			let env: ICoreWebView2Environment6 = webview.cast::<ICoreWebView2_2>()?.Environment()?.cast()?;
+			let print_settings = env.CreatePrintSettings()?;
+			print_settings.SetOrientation(match settings.orientation {
+				PdfOrientation::Portrait => COREWEBVIEW2_PRINT_ORIENTATION_PORTRAIT,
+				PdfOrientation::Landscape => COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE
+			})?;
+			print_settings.SetPageWidth(settings.page_width)?;
+			print_settings.SetPageHeight(settings.page_height)?;
+			print_settings.SetMarginTop(settings.margin_top)?;
+			print_settings.SetMarginBottom(settings.margin_bottom)?;
+			print_settings.SetMarginLeft(settings.margin_left)?;
+			print_settings.SetMarginRight(settings.margin_right)?;
+			print_settings.SetShouldPrintBackgrounds(true)?;
+			Ok(print_settings)
+		}
+	}
+
+	pub fn print_to_pdf(&self, path: PathBuf, settings: PdfSettings, callback: Box<dyn FnOnce(Result<PathBuf>)>) -> Result<()> {
+		let print_settings = Self::create_print_settings(&self.webview, &settings).map_err(webview2_com::Error::WindowsError)?;
+		let webview: ICoreWebView2_7 = self.webview.cast().map_err(webview2_com::Error::WindowsError)?;
+		let file_path = path.to_string_lossy().into_owned();
+		let handler = PrintToPdfCompletedHandler::create(Box::new(move |error_code, is_successful| {
+			callback(match error_code {
+				Ok(()) if is_successful => Ok(path),
+				Ok(()) => Err(Error::PrintToPdf("the page could not be printed".into())),
+				Err(err) => Err(Error::WebView2Error(webview2_com::Error::WindowsError(err)))
+			});
+			Ok(())
+		}));
+		unsafe { webview.PrintToPdf(file_path, print_settings, handler) }.map_err(webview2_com::Error::WindowsError)?;
+		Ok(())
+	}
+
+	pub fn take_snapshot(&self, callback: Box<dyn FnOnce(Result<Vec<u8>>)>) -> Result<()> {
+		unsafe {
+			let stream = CreateStreamOnHGlobal(0, true).map_err(webview2_com::Error::WindowsError)?;
+			let image = stream.clone();
+			let handler = CapturePreviewCompletedHandler::create(Box::new(move |error_code| {
+				callback(
+					error_code
+						.and_then(|_| read_stream(&image))
+						.map_err(|err| Error::WebView2Error(webview2_com::Error::WindowsError(err)))
+				);
+				Ok(())
+			}));
+			self.webview
+				.CapturePreview(COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG, stream, handler)
+				.map_err(webview2_com::Error::WindowsError)?;
+		}
+		Ok(())
+	}
+
 	pub fn eval(&self, js: &str) -> Result<()> {
 		Self::execute_script(&self.webview, js.to_string()).map_err(|err| Error::WebView2Error(webview2_com::Error::WindowsError(err)))
 	}
 
+	pub fn add_init_script(&self, id: InitScriptId, js: &str) -> Result<()> {
+		let script_id = Self::add_script_to_execute_on_document_created(&self.webview, js.to_string())?;
+		self.init_scripts.borrow_mut().insert(id, script_id);
+		Ok(())
+	}
+
+	pub fn remove_init_script(&self, id: InitScriptId) -> Result<()> {
+		if let Some(script_id) = self.init_scripts.borrow_mut().remove(&id) {
+			unsafe { self.webview.RemoveScriptToExecuteOnDocumentCreated(script_id) }.map_err(webview2_com::Error::WindowsError)?;
+		}
+		Ok(())
+	}
+
+	pub fn insert_css(&self, id: CssId, css: &str) -> Result<()> {
+		let js = insert_css_script(id, css);
+		let script_id = Self::add_script_to_execute_on_document_created(&self.webview, js.clone())?;
+		self.css_scripts.borrow_mut().insert(id, script_id);
+		// document-created scripts only run on the next navigation
+		self.eval(&js)
+	}
+
+	pub fn remove_css(&self, id: CssId) -> Result<()> {
+		if let Some(script_id) = self.css_scripts.borrow_mut().remove(&id) {
+			unsafe { self.webview.RemoveScriptToExecuteOnDocumentCreated(script_id) }.map_err(webview2_com::Error::WindowsError)?;
+		}
+		self.eval(&remove_css_script(id))
+	}
+
+	pub fn memory_usage(&self) -> Option<u64> {
+		unsafe {
+			let process_id = self.webview.BrowserProcessId().ok()?;
+			let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, BOOL(0), process_id).ok()?;
+			let mut counters = PROCESS_MEMORY_COUNTERS::default();
+			let success = K32GetProcessMemoryInfo(process, &mut counters, std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32);
+			CloseHandle(process);
+			if success.as_bool() {
+				Some(counters.WorkingSetSize as u64)
+			} else {
+				None
+			}
+		}
+	}
+
+	pub fn suspend(&self) -> Result<()> {
+		unsafe {
+			// WebView2 refuses to suspend a visible webview, so this blanks the window until it is resumed
+			self.controller.SetIsVisible(false).map_err(webview2_com::Error::WindowsError)?;
+			let webview: ICoreWebView2_3 = self.webview.cast().map_err(webview2_com::Error::WindowsError)?;
+			webview
+				.TrySuspend(TrySuspendCompletedHandler::create(Box::new(|_, _| Ok(()))))
+				.map_err(webview2_com::Error::WindowsError)?;
+		}
+		Ok(())
+	}
+
+	pub fn resume(&self) -> Result<()> {
+		unsafe {
+			let webview: ICoreWebView2_3 = self.webview.cast().map_err(webview2_com::Error::WindowsError)?;
+			webview.Resume().map_err(webview2_com::Error::WindowsError)?;
+			self.controller.SetIsVisible(true).map_err(webview2_com::Error::WindowsError)?;
+		}
+		Ok(())
+	}
+
 	pub fn focus(&self) {
 		let _ = unsafe { self.controller.MoveFocus(COREWEBVIEW2_MOVE_FOCUS_REASON_PROGRAMMATIC) };
 	}
 
 	#[cfg(any(debug_assertions, feature = "devtools"))]
-	pub fn open_devtools(&self) {
+	pub fn open_devtools(&self, _position: crate::webview::DevtoolsPosition) {
+		// the devtools can't be docked in WebView2
 		let _ = unsafe { self.webview.OpenDevToolsWindow() };
 	}
 
@@ -579,6 +975,72 @@ impl InnerWebView {
 	pub fn zoom(&self, scale_factor: f64) {
 		let _ = unsafe { self.controller.SetZoomFactor(scale_factor) };
 	}
+
+	pub fn set_clipboard(&self, enabled: bool) {
+		self.clipboard.set(Some(enabled));
+	}
+}
+
+/// Registered message that runs the boxed closure in its `WPARAM` on the window's thread.
+static EXEC_MSG_ID: Lazy<u32> = Lazy::new(|| unsafe { win32wm::RegisterWindowMessageA("Millennium::ExecMsg") });
+
+/// Runs `function` on the thread of the webview's window `hwnd`.
+fn dispatch_to_window<F: FnOnce() + Send + 'static>(hwnd: HWND, function: F) {
+	let function: Box<Box<dyn FnOnce()>> = Box::new(Box::new(function));
+	let function = Box::into_raw(function);
+	// if the window is already gone, the closure is leaked rather than dropped on the wrong thread
+	unsafe { win32wm::PostMessageW(hwnd, *EXEC_MSG_ID, WPARAM(function as usize), LPARAM(0)) };
+}
+
+/// COM objects that are only ever used on the webview's thread, but need to be moved through another thread to get
+/// back to it.
+struct UiThreadBound<T>(T);
+
+// SAFETY: the value is only moved, never used, while on another thread
+unsafe impl<T> Send for UiThreadBound<T> {}
+
+impl<T> UiThreadBound<T> {
+	fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+unsafe fn web_resource_response(env: &ICoreWebView2Environment, sent_response: &HttpResponse) -> windows::core::Result<ICoreWebView2WebResourceResponse> {
+	let content = sent_response.body();
+	let status_code = sent_response.status().as_u16() as i32;
+
+	let mut headers_map = String::new();
+
+	// set mime type if provided
+	if let Some(mime) = sent_response.mimetype() {
+		writeln!(headers_map, "Content-Type: {}", mime).unwrap();
+	}
+
+	// build headers
+	for (name, value) in sent_response.headers().iter() {
+		let header_key = name.to_string();
+		if let Ok(value) = value.to_str() {
+			writeln!(headers_map, "{}: {}", header_key, value).unwrap();
+		}
+	}
+
+	let mut body_sent = None;
+	if !content.is_empty() {
+		let stream = CreateStreamOnHGlobal(0, true)?;
+		stream.SetSize(content.len() as u64)?;
+		let mut cb_write = MaybeUninit::uninit();
+		if stream
+			.Write(content.as_ptr() as *const _, content.len() as u32, cb_write.as_mut_ptr())
+			.is_ok() && cb_write.assume_init() as usize == content.len()
+		{
+			body_sent = Some(stream);
+		}
+	}
+
+	// FIXME: Set http response version
+
+	let body_sent = body_sent.map(|content| content.cast().unwrap());
+	env.CreateWebResourceResponse(body_sent, status_code, "OK", headers_map)
 }
 
 pub fn platform_webview_version() -> Result<String> {
@@ -587,6 +1049,54 @@ pub fn platform_webview_version() -> Result<String> {
 	Ok(take_pwstr(versioninfo))
 }
 
+unsafe fn current_modifiers() -> ModifiersState {
+	let mut modifiers = ModifiersState::empty();
+	modifiers.set(ModifiersState::SHIFT, GetKeyState(VK_SHIFT.0 as i32) < 0);
+	modifiers.set(ModifiersState::CONTROL, GetKeyState(VK_CONTROL.0 as i32) < 0);
+	modifiers.set(ModifiersState::ALT, GetKeyState(VK_MENU.0 as i32) < 0);
+	modifiers.set(ModifiersState::SUPER, GetKeyState(VK_LWIN.0 as i32) < 0 || GetKeyState(VK_RWIN.0 as i32) < 0);
+	modifiers
+}
+
+fn physical_key_code(status: &COREWEBVIEW2_PHYSICAL_KEY_STATUS) -> KeyCode {
+	// `KeyCode::from_scancode` expects extended keys to carry the 0xE0 prefix
+	KeyCode::from_scancode(if status.IsExtendedKey.as_bool() { 0xE000 | status.ScanCode } else { status.ScanCode })
+}
+
+unsafe fn read_stream(stream: &IStream) -> windows::core::Result<Vec<u8>> {
+	stream.Seek(0, STREAM_SEEK_SET)?;
+	let mut bytes = Vec::new();
+	let mut buffer: [u8; 4096] = [0; 4096];
+	loop {
+		let mut cb_read = 0;
+		stream.Read(buffer.as_mut_ptr() as *mut _, buffer.len() as u32, &mut cb_read).ok()?;
+		if cb_read == 0 {
+			break;
+		}
+		bytes.extend_from_slice(&buffer[..(cb_read as usize)]);
+	}
+	Ok(bytes)
+}
+
+/// Collects the headers of a request, skipping those that aren't valid HTTP headers.
+unsafe fn read_request_headers(headers: &ICoreWebView2HttpRequestHeaders) -> windows::core::Result<HeaderMap> {
+	let mut map = HeaderMap::new();
+	let iterator = headers.GetIterator()?;
+	let mut has_current = BOOL::default();
+	iterator.HasCurrentHeader(&mut has_current)?;
+	while has_current.as_bool() {
+		let mut key = PWSTR::default();
+		let mut value = PWSTR::default();
+		iterator.GetCurrentHeader(&mut key, &mut value)?;
+		let (key, value) = (take_pwstr(key), take_pwstr(value));
+		if let (Ok(key), Ok(value)) = (HeaderName::from_bytes(key.as_bytes()), HeaderValue::from_str(&value)) {
+			map.append(key, value);
+		}
+		iterator.MoveNext(&mut has_current)?;
+	}
+	Ok(map)
+}
+
 fn is_windows_7() -> bool {
 	if let Some(v) = get_windows_ver() {
 		// windows 7 is 6.1
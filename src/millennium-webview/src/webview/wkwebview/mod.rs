@@ -19,20 +19,25 @@ mod file_drop;
 mod web_context;
 
 use std::{
+	cell::RefCell,
+	collections::{HashMap, HashSet},
 	ffi::{c_void, CStr},
 	os::raw::c_char,
+	path::PathBuf,
 	ptr::{null, null_mut},
 	rc::Rc,
-	slice, str
+	slice, str,
+	sync::Mutex
 };
 
 #[cfg(target_os = "macos")]
 use cocoa::appkit::{NSView, NSViewHeightSizable, NSViewWidthSizable};
 use cocoa::{
 	base::{id, nil, NO, YES},
-	foundation::{NSDictionary, NSFastEnumeration, NSInteger}
+	foundation::{NSDictionary, NSFastEnumeration, NSInteger, NSUInteger}
 };
 use core_graphics::geometry::{CGPoint, CGRect, CGSize};
+use dispatch::Queue;
 #[cfg(target_os = "macos")]
 use file_drop::{add_file_drop_methods, set_file_drop_handler};
 use objc::{
@@ -40,20 +45,28 @@ use objc::{
 	runtime::{Class, Object, Sel, BOOL}
 };
 use objc_id::Id;
+use once_cell::sync::Lazy;
 pub use web_context::WebContextImpl;
 
 #[cfg(target_os = "ios")]
 use crate::application::platform::ios::WindowExtIOS;
 #[cfg(target_os = "macos")]
-use crate::application::platform::macos::WindowExtMacOS;
+use crate::application::{
+	accelerator::Accelerator,
+	keyboard::{KeyCode, ModifiersState},
+	platform::macos::WindowExtMacOS
+};
 use crate::http::{Request as HttpRequest, RequestBuilder as HttpRequestBuilder, Response as HttpResponse};
 use crate::{
 	application::{
 		dpi::{LogicalSize, PhysicalSize},
 		window::Window
 	},
-	webview::{FileDropEvent, WebContext, WebViewAttributes},
-	Result
+	webview::{
+		binary_ipc_protocol, insert_css_script, remove_css_script, request_media_capture_permission, CookiePersistence, CssId, FileDropEvent,
+		InitScriptId, PdfSettings, PermissionKind, PermissionResponse, RequestAsyncResponder, WebContext, WebViewAttributes, BINARY_IPC_PROTOCOL
+	},
+	Error, Result
 };
 
 pub struct InnerWebView {
@@ -65,13 +78,21 @@ pub struct InnerWebView {
 	// all fucntions pointer declarations in objc callbacks below all need to get updated.
 	ipc_handler_ptr: *mut (Box<dyn Fn(&Window, String)>, Rc<Window>),
 	nav_decide_policy_ptr: *mut Box<dyn Fn(String, bool) -> bool>,
+	permission_handler_ptr: *mut Box<dyn Fn(&str, PermissionKind) -> PermissionResponse>,
+	#[cfg(target_os = "macos")]
+	key_press_handler_ptr: *mut Box<dyn Fn(ModifiersState, KeyCode) -> bool>,
+	#[cfg(target_os = "macos")]
+	passthrough_accelerators_ptr: *mut Vec<Accelerator>,
 	#[cfg(target_os = "macos")]
 	file_drop_ptr: *mut (Box<dyn Fn(&Window, FileDropEvent) -> bool>, Rc<Window>),
-	protocol_ptrs: Vec<*mut Box<dyn Fn(&HttpRequest) -> Result<HttpResponse>>>
+	protocol_ptrs: Vec<*mut Box<dyn Fn(HttpRequest, RequestAsyncResponder)>>,
+	// The `WKUserScript`s added after creation, retained so they can be told apart when removing one
+	init_scripts: RefCell<HashMap<InitScriptId, id>>,
+	css_scripts: RefCell<HashMap<CssId, id>>
 }
 
 impl InnerWebView {
-	pub fn new(window: Rc<Window>, attributes: WebViewAttributes, mut web_context: Option<&mut WebContext>) -> Result<Self> {
+	pub fn new(window: Rc<Window>, mut attributes: WebViewAttributes, mut web_context: Option<&mut WebContext>) -> Result<Self> {
 		// Function for ipc handler
 		extern "C" fn did_receive(this: &Object, _: Sel, _: id, msg: id) {
 			// Safety: objc runtime calls are unsafe
@@ -95,7 +116,7 @@ impl InnerWebView {
 			unsafe {
 				let function = this.get_ivar::<*mut c_void>("function");
 				if !function.is_null() {
-					let function = &mut *(*function as *mut Box<dyn for<'s> Fn(&'s HttpRequest) -> Result<HttpResponse>>);
+					let function = &mut *(*function as *mut Box<dyn Fn(HttpRequest, RequestAsyncResponder)>);
 
 					// Get url request
 					let request: id = msg_send![task, request];
@@ -148,55 +169,48 @@ impl InnerWebView {
 						http_request = http_request.header(header_field.to_str(), header_value.to_str());
 					}
 
-					// send response
+					// the handler may respond from another thread, the task is retained until it does
 					let final_request = http_request.body(sent_form_body).unwrap();
-					if let Ok(sent_response) = function(&final_request) {
-						let content = sent_response.body();
-						// default: application/octet-stream, but should be provided by the client
-						let wanted_mime = sent_response.mimetype();
-						// default to 200
-						let wanted_status_code = sent_response.status().as_u16() as i32;
-						// default to HTTP/1.1
-						let wanted_version = format!("{:#?}", sent_response.version());
-
-						let dictionary: id = msg_send![class!(NSMutableDictionary), alloc];
-						let headers: id = msg_send![dictionary, initWithCapacity:1];
-						if let Some(mime) = wanted_mime {
-							let () = msg_send![headers, setObject:NSString::new(mime) forKey: NSString::new("content-type")];
-						}
-						let () = msg_send![headers, setObject:NSString::new(&content.len().to_string()) forKey: NSString::new("content-length")];
-
-						// add headers
-						for (name, value) in sent_response.headers().iter() {
-							let header_key = name.to_string();
-							if let Ok(value) = value.to_str() {
-								let () = msg_send![headers, setObject:NSString::new(value) forKey: NSString::new(&header_key)];
+					let task: id = msg_send![task, retain];
+					RUNNING_TASKS.lock().unwrap().insert(task as usize);
+					let task = task as usize;
+					let responder = RequestAsyncResponder::new(move |sent_response| {
+						let respond = move || {
+							let task = task as id;
+							// WebKit throws if a task it has stopped is responded to
+							if RUNNING_TASKS.lock().unwrap().remove(&(task as usize)) {
+								respond_to_task(task, sent_response);
 							}
+							let () = msg_send![task, release];
+						};
+						let is_main_thread: BOOL = msg_send![class!(NSThread), isMainThread];
+						if is_main_thread == YES {
+							respond();
+						} else {
+							Queue::main().exec_async(respond);
 						}
-
-						let urlresponse: id = msg_send![class!(NSHTTPURLResponse), alloc];
-						let response: id = msg_send![urlresponse, initWithURL:url statusCode: wanted_status_code HTTPVersion:NSString::new(&wanted_version) headerFields:headers];
-						let () = msg_send![task, didReceiveResponse: response];
-
-						// Send data
-						let bytes = content.as_ptr() as *mut c_void;
-						let data: id = msg_send![class!(NSData), alloc];
-						let data: id = msg_send![data, initWithBytesNoCopy:bytes length:content.len() freeWhenDone: if content.len() == 0 { NO } else { YES }];
-						let () = msg_send![task, didReceiveData: data];
-					} else {
-						let urlresponse: id = msg_send![class!(NSHTTPURLResponse), alloc];
-						let response: id =
-							msg_send![urlresponse, initWithURL:url statusCode:404 HTTPVersion:NSString::new("HTTP/1.1") headerFields:null::<c_void>()];
-						let () = msg_send![task, didReceiveResponse: response];
-					}
-					// Finish
-					let () = msg_send![task, didFinish];
+					});
+					function(final_request, responder);
 				} else {
 					log::warn!("Either WebView or WebContext instance is dropped! This handler shouldn't be called.");
 				}
 			}
 		}
-		extern "C" fn stop_task(_: &Object, _: Sel, _webview: id, _task: id) {}
+		extern "C" fn stop_task(_: &Object, _: Sel, _webview: id, task: id) {
+			RUNNING_TASKS.lock().unwrap().remove(&(task as usize));
+		}
+
+		// Binary messages are posted as request bodies to a custom protocol
+		let post_binary_message = match attributes.binary_ipc_handler.take() {
+			Some(handler) => {
+				attributes.custom_protocols.push(binary_ipc_protocol(window.clone(), handler));
+				format!(
+					", postBinaryMessage: function(data) {{return fetch('{}://localhost/', {{method: 'POST', body: data}}).then(function() {{}});}}",
+					BINARY_IPC_PROTOCOL
+				)
+			}
+			None => String::new()
+		};
 
 		// Safety: objc runtime calls are unsafe
 		unsafe {
@@ -233,7 +247,17 @@ impl InnerWebView {
 				#[allow(unused_mut)]
 				Some(mut decl) => {
 					#[cfg(target_os = "macos")]
-					add_file_drop_methods(&mut decl);
+					{
+						add_file_drop_methods(&mut decl);
+						decl.add_ivar::<BOOL>("ContextMenuEnabled");
+						decl.add_ivar::<BOOL>("AcceptsFirstMouse");
+						decl.add_method(sel!(willOpenMenu:withEvent:), will_open_menu as extern "C" fn(&Object, Sel, id, id));
+						decl.add_method(sel!(acceptsFirstMouse:), accepts_first_mouse as extern "C" fn(&Object, Sel, id) -> BOOL);
+						decl.add_ivar::<*mut c_void>("KeyPressHandler");
+						decl.add_ivar::<*mut c_void>("PassthroughAccelerators");
+						decl.add_method(sel!(keyDown:), key_down as extern "C" fn(&Object, Sel, id));
+						decl.add_method(sel!(performKeyEquivalent:), perform_key_equivalent as extern "C" fn(&Object, Sel, id) -> BOOL);
+					}
 					decl.register()
 				}
 				_ => class!(MillenniumWebView)
@@ -253,6 +277,63 @@ impl InnerWebView {
 			#[cfg(target_os = "macos")]
 			let _: id = msg_send![_preference, setValue:_yes forKey:NSString::new("tabFocusesLinks")];
 
+			// WebKit has no public switch for GPU rendering, so fall back to the private preferences when they exist.
+			// Equivalent Obj-C:
+			// [[config preferences] _setAcceleratedDrawingEnabled:NO];
+			// [[config preferences] _setCanvasUsesAcceleratedDrawing:NO];
+			#[cfg(target_os = "macos")]
+			if !attributes.hardware_acceleration {
+				let responds: BOOL = msg_send![_preference, respondsToSelector: sel!(_setAcceleratedDrawingEnabled:)];
+				if responds == YES {
+					let () = msg_send![_preference, _setAcceleratedDrawingEnabled: NO];
+				}
+				let responds: BOOL = msg_send![_preference, respondsToSelector: sel!(_setCanvasUsesAcceleratedDrawing:)];
+				if responds == YES {
+					let () = msg_send![_preference, _setCanvasUsesAcceleratedDrawing: NO];
+				}
+			}
+
+			// Equivalent Obj-C:
+			// [config setMediaTypesRequiringUserActionForPlayback:WKAudiovisualMediaTypeNone or WKAudiovisualMediaTypeAll];
+			let media_types: NSUInteger = if attributes.autoplay { 0 } else { NSUInteger::MAX };
+			let _: () = msg_send![config, setMediaTypesRequiringUserActionForPlayback: media_types];
+
+			// WebKit follows the "Show scroll bars" preference, which an app can override in the volatile argument domain.
+			// Unlike the app's own domain, it isn't written to the app's preferences on disk.
+			// Equivalent Obj-C:
+			// NSMutableDictionary *arguments = [[defaults volatileDomainForName:NSArgumentDomain] mutableCopy];
+			// arguments[@"AppleShowScrollBars"] = @"WhenScrolling";
+			// [defaults setVolatileDomain:arguments forName:NSArgumentDomain];
+			#[cfg(target_os = "macos")]
+			{
+				use crate::webview::ScrollBarStyle;
+
+				let defaults: id = msg_send![class!(NSUserDefaults), standardUserDefaults];
+				let domain_name = NSString::new("NSArgumentDomain").0;
+				let arguments: id = msg_send![defaults, volatileDomainForName: domain_name];
+				let arguments: id = if arguments == nil {
+					msg_send![class!(NSMutableDictionary), new]
+				} else {
+					msg_send![arguments, mutableCopy]
+				};
+				let key = NSString::new("AppleShowScrollBars");
+				match attributes.scroll_bar_style {
+					ScrollBarStyle::Overlay => {
+						let () = msg_send![arguments, setObject:NSString::new("WhenScrolling") forKey:key];
+					}
+					ScrollBarStyle::AlwaysVisible => {
+						let () = msg_send![arguments, setObject:NSString::new("Always") forKey:key];
+					}
+					_ => {
+						let () = msg_send![arguments, removeObjectForKey: key];
+					}
+				}
+				// a volatile domain can't be replaced while it exists
+				let () = msg_send![defaults, removeVolatileDomainForName: domain_name];
+				let () = msg_send![defaults, setVolatileDomain:arguments forName:domain_name];
+				let () = msg_send![arguments, release];
+			}
+
 			#[cfg(feature = "transparent")]
 			if attributes.transparent {
 				let no: id = msg_send![class!(NSNumber), numberWithBool:0];
@@ -266,9 +347,46 @@ impl InnerWebView {
 			// [preference setValue:@YES forKey:@"fullScreenEnabled"];
 			let _: id = msg_send![_preference, setValue:_yes forKey:NSString::new("fullScreenEnabled")];
 
+			// Equivalent Obj-C:
+			// [config setWebsiteDataStore:[WKWebsiteDataStore nonPersistentDataStore]];
+			if attributes.cookie_persistence == CookiePersistence::Session {
+				let data_store: id = msg_send![class!(WKWebsiteDataStore), nonPersistentDataStore];
+				let () = msg_send![config, setWebsiteDataStore: data_store];
+			}
+
+			// WKHTTPCookieStore only exposes the accept policy through a private API.
+			// Equivalent Obj-C:
+			// [[[config websiteDataStore] httpCookieStore] _setCookieAcceptPolicy:NSHTTPCookieAcceptPolicyOnlyFromMainDocumentDomain completionHandler:^{}];
+			if attributes.block_third_party_cookies {
+				let data_store: id = msg_send![config, websiteDataStore];
+				let cookie_store: id = msg_send![data_store, httpCookieStore];
+				let responds: BOOL = msg_send![cookie_store, respondsToSelector: sel!(_setCookieAcceptPolicy:completionHandler:)];
+				if responds == YES {
+					// NSHTTPCookieAcceptPolicyOnlyFromMainDocumentDomain
+					let policy: NSUInteger = 2;
+					let handler = block::ConcreteBlock::new(|| {}).copy();
+					let () = msg_send![cookie_store, _setCookieAcceptPolicy: policy completionHandler: &*handler];
+				}
+			}
+
 			// Initialize webview with zero point
 			let zero = CGRect::new(&CGPoint::new(0., 0.), &CGSize::new(0., 0.));
 			let _: () = msg_send![webview, initWithFrame:zero configuration:config];
+			#[cfg(target_os = "macos")]
+			(*webview).set_ivar::<BOOL>("ContextMenuEnabled", if attributes.default_context_menu_enabled { YES } else { NO });
+			#[cfg(target_os = "macos")]
+			(*webview).set_ivar::<BOOL>("AcceptsFirstMouse", if attributes.accept_first_mouse { YES } else { NO });
+			#[cfg(target_os = "macos")]
+			let key_press_handler_ptr = match attributes.key_press_handler.take() {
+				Some(key_press_handler) => Box::into_raw(Box::new(key_press_handler)),
+				None => null_mut()
+			};
+			#[cfg(target_os = "macos")]
+			(*webview).set_ivar("KeyPressHandler", key_press_handler_ptr as *mut c_void);
+			#[cfg(target_os = "macos")]
+			let passthrough_accelerators_ptr = Box::into_raw(Box::new(std::mem::take(&mut attributes.passthrough_accelerators)));
+			#[cfg(target_os = "macos")]
+			(*webview).set_ivar("PassthroughAccelerators", passthrough_accelerators_ptr as *mut c_void);
 
 			// Auto-resize on macOS
 			#[cfg(target_os = "macos")]
@@ -276,6 +394,39 @@ impl InnerWebView {
 				webview.setAutoresizingMask_(NSViewHeightSizable | NSViewWidthSizable);
 			}
 
+			// Only a private API controls rubber-banding on macOS; the setter takes the edges that may bounce.
+			// Equivalent Obj-C:
+			// [webview _setRubberBandingEnabled:_WKRectEdgeNone];
+			#[cfg(target_os = "macos")]
+			if !attributes.scroll_bounce {
+				let responds: BOOL = msg_send![webview, respondsToSelector: sel!(_setRubberBandingEnabled:)];
+				if responds == YES {
+					let () = msg_send![webview, _setRubberBandingEnabled: 0 as NSUInteger];
+				}
+			}
+
+			// Equivalent Obj-C:
+			// [webview setAllowsMagnification:YES];
+			#[cfg(target_os = "macos")]
+			if let Some(pinch_zoom) = attributes.pinch_zoom {
+				let () = msg_send![webview, setAllowsMagnification: if pinch_zoom { YES } else { NO }];
+			}
+
+			// Equivalent Obj-C:
+			// [webview setAllowsBackForwardNavigationGestures:YES];
+			#[cfg(target_os = "macos")]
+			if let Some(swipe_navigation) = attributes.swipe_navigation {
+				let () = msg_send![webview, setAllowsBackForwardNavigationGestures: if swipe_navigation { YES } else { NO }];
+			}
+
+			// Equivalent Obj-C:
+			// [[webview scrollView] setBounces:NO];
+			#[cfg(target_os = "ios")]
+			if !attributes.scroll_bounce {
+				let scroll_view: id = msg_send![webview, scrollView];
+				let () = msg_send![scroll_view, setBounces: NO];
+			}
+
 			// Message handler
 			let ipc_handler_ptr = if let Some(ipc_handler) = attributes.ipc_handler {
 				let cls = ClassDecl::new("WebViewDelegate", class!(NSObject));
@@ -379,17 +530,65 @@ impl InnerWebView {
 				}
 			}
 
+			// Media capture permission handler, macOS 12+ / iOS 15+
+			extern "C" fn media_capture_permission(this: &Object, _: Sel, _webview: id, origin: id, _frame: id, capture_type: NSInteger, decision_handler: id) {
+				unsafe {
+					let decision_handler = decision_handler as *mut block::Block<(NSInteger,), c_void>;
+
+					let protocol: id = msg_send![origin, protocol];
+					let host: id = msg_send![origin, host];
+					let port: NSInteger = msg_send![origin, port];
+					let (protocol, host) = (NSString(protocol), NSString(host));
+					let origin = match port {
+						0 => format!("{}://{}", protocol.to_str(), host.to_str()),
+						port => format!("{}://{}:{}", protocol.to_str(), host.to_str(), port)
+					};
+
+					let function = this.get_ivar::<*mut c_void>("permission_function");
+					let handler = if function.is_null() {
+						None
+					} else {
+						Some(&**(*function as *mut Box<dyn Fn(&str, PermissionKind) -> PermissionResponse>))
+					};
+
+					// WKMediaCaptureTypeCamera = 0, WKMediaCaptureTypeMicrophone = 1, WKMediaCaptureTypeCameraAndMicrophone = 2
+					let (camera, microphone) = match capture_type {
+						0 => (true, false),
+						1 => (false, true),
+						_ => (true, true)
+					};
+					// WKPermissionDecisionPrompt = 0, WKPermissionDecisionGrant = 1, WKPermissionDecisionDeny = 2
+					let decision = match request_media_capture_permission(handler, &origin, camera, microphone) {
+						PermissionResponse::Prompt => 0,
+						PermissionResponse::Allow => 1,
+						PermissionResponse::Deny => 2
+					};
+					(*decision_handler).call((decision,));
+				}
+			}
+
 			let ui_delegate = match ClassDecl::new("WebViewUIDelegate", class!(NSObject)) {
 				Some(mut ctl) => {
+					ctl.add_ivar::<*mut c_void>("permission_function");
 					ctl.add_method(
 						sel!(webView:runOpenPanelWithParameters:initiatedByFrame:completionHandler:),
 						run_file_upload_panel as extern "C" fn(&Object, Sel, id, id, id, id)
 					);
+					ctl.add_method(
+						sel!(webView:requestMediaCapturePermissionForOrigin:initiatedByFrame:type:decisionHandler:),
+						media_capture_permission as extern "C" fn(&Object, Sel, id, id, id, NSInteger, id)
+					);
 					ctl.register()
 				}
 				None => class!(WebViewUIDelegate)
 			};
 			let ui_delegate: id = msg_send![ui_delegate, new];
+			// requests are denied when there is no handler
+			let permission_handler_ptr = match attributes.permission_handler {
+				Some(permission_handler) => Box::into_raw(Box::new(permission_handler)),
+				None => null_mut()
+			};
+			(*ui_delegate).set_ivar("permission_function", permission_handler_ptr as *mut c_void);
 			let _: () = msg_send![webview, setUIDelegate: ui_delegate];
 
 			// File drop handling
@@ -422,17 +621,25 @@ impl InnerWebView {
 				manager,
 				ipc_handler_ptr,
 				nav_decide_policy_ptr,
+				permission_handler_ptr,
+				#[cfg(target_os = "macos")]
+				key_press_handler_ptr,
+				#[cfg(target_os = "macos")]
+				passthrough_accelerators_ptr,
 				#[cfg(target_os = "macos")]
 				file_drop_ptr,
-				protocol_ptrs
+				protocol_ptrs,
+				init_scripts: Default::default(),
+				css_scripts: Default::default()
 			};
 
 			// Initialize scripts
-			w.init(
-				r#"Object.defineProperty(window, 'ipc', {
-					value: Object.freeze({postMessage: function(s) {window.webkit.messageHandlers.ipc.postMessage(s);}})
-				});"#
-			);
+			w.init(&format!(
+				r#"Object.defineProperty(window, 'ipc', {{
+					value: Object.freeze({{postMessage: function(s) {{window.webkit.messageHandlers.ipc.postMessage(s);}}{}}})
+				}});"#,
+				post_binary_message
+			));
 			for js in attributes.initialization_scripts {
 				w.init(&js);
 			}
@@ -507,6 +714,115 @@ impl InnerWebView {
 		}
 	}
 
+	pub fn add_init_script(&self, script_id: InitScriptId, js: &str) -> Result<()> {
+		let script = self.add_user_script(js, false);
+		self.init_scripts.borrow_mut().insert(script_id, script);
+		Ok(())
+	}
+
+	pub fn remove_init_script(&self, script_id: InitScriptId) -> Result<()> {
+		if let Some(script) = self.init_scripts.borrow_mut().remove(&script_id) {
+			self.remove_user_script(script);
+		}
+		Ok(())
+	}
+
+	pub fn insert_css(&self, css_id: CssId, css: &str) -> Result<()> {
+		let js = insert_css_script(css_id, css);
+		let script = self.add_user_script(&js, true);
+		self.css_scripts.borrow_mut().insert(css_id, script);
+		// user scripts only run on the next navigation
+		self.eval(&js)
+	}
+
+	pub fn remove_css(&self, css_id: CssId) -> Result<()> {
+		if let Some(script) = self.css_scripts.borrow_mut().remove(&css_id) {
+			self.remove_user_script(script);
+		}
+		self.eval(&remove_css_script(css_id))
+	}
+
+	/// Adds a document-start user script, returning it retained.
+	fn add_user_script(&self, js: &str, main_frame_only: bool) -> id {
+		// Safety: objc runtime calls are unsafe
+		unsafe {
+			let main_frame_only: BOOL = if main_frame_only { YES } else { NO };
+			let userscript: id = msg_send![class!(WKUserScript), alloc];
+			let script: id = msg_send![userscript, initWithSource:NSString::new(js) injectionTime:0 forMainFrameOnly:main_frame_only];
+			let _: () = msg_send![self.manager, addUserScript: script];
+			script
+		}
+	}
+
+	/// Removes and releases a script returned by `add_user_script`.
+	fn remove_user_script(&self, removed: id) {
+		// Safety: objc runtime calls are unsafe
+		unsafe {
+			// WKUserContentController can only remove all user scripts at once, so add back the ones we keep
+			let scripts: id = msg_send![self.manager, userScripts];
+			let scripts: id = msg_send![scripts, copy];
+			let _: () = msg_send![self.manager, removeAllUserScripts];
+			let count: NSUInteger = msg_send![scripts, count];
+			for i in 0..count {
+				let script: id = msg_send![scripts, objectAtIndex: i];
+				if script != removed {
+					let _: () = msg_send![self.manager, addUserScript: script];
+				}
+			}
+			let _: () = msg_send![scripts, release];
+			let _: () = msg_send![removed, release];
+		}
+	}
+
+	pub fn memory_usage(&self) -> Option<u64> {
+		#[cfg(target_os = "macos")]
+		// Safety: objc runtime calls are unsafe
+		unsafe {
+			// the web content process is only exposed through a private property
+			let responds: BOOL = msg_send![self.webview, respondsToSelector: sel!(_webProcessIdentifier)];
+			if responds == NO {
+				return None;
+			}
+			let process_id: libc::pid_t = msg_send![self.webview, _webProcessIdentifier];
+			if process_id <= 0 {
+				return None;
+			}
+			let mut info: libc::proc_taskinfo = std::mem::zeroed();
+			let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+			if libc::proc_pidinfo(process_id, libc::PROC_PIDTASKINFO, 0, &mut info as *mut _ as *mut c_void, size) == size {
+				Some(info.pti_resident_size)
+			} else {
+				None
+			}
+		}
+		#[cfg(target_os = "ios")]
+		None
+	}
+
+	pub fn suspend(&self) -> Result<()> {
+		self.set_suspended(true);
+		Ok(())
+	}
+
+	pub fn resume(&self) -> Result<()> {
+		self.set_suspended(false);
+		Ok(())
+	}
+
+	fn set_suspended(&self, suspended: bool) {
+		// Safety: objc runtime calls are unsafe
+		unsafe {
+			// a hidden page stops rendering and WebKit throttles its timers
+			let _: () = msg_send![self.webview, setHidden: if suspended { YES } else { NO }];
+			// `setAllMediaPlaybackSuspended:completionHandler:` needs macOS 12 / iOS 15
+			let selector = sel!(setAllMediaPlaybackSuspended:completionHandler:);
+			let responds: BOOL = msg_send![self.webview, respondsToSelector: selector];
+			if responds == YES {
+				let _: () = msg_send![self.webview, setAllMediaPlaybackSuspended: if suspended { YES } else { NO } completionHandler: nil];
+			}
+		}
+	}
+
 	fn navigate(&self, url: &str) {
 		// Safety: objc runtime calls are unsafe
 		unsafe {
@@ -549,15 +865,92 @@ impl InnerWebView {
 		}
 	}
 
+	pub fn print_to_pdf(&self, path: PathBuf, _settings: PdfSettings, callback: Box<dyn FnOnce(Result<PathBuf>)>) -> Result<()> {
+		// Safety: objc runtime calls are unsafe
+		unsafe {
+			let can_create_pdf: BOOL = msg_send![self.webview, respondsToSelector: sel!(createPDFWithConfiguration:completionHandler:)];
+			if can_create_pdf != YES {
+				return Err(Error::PrintToPdf("printing to PDF requires macOS 11+ or iOS 14+".into()));
+			}
+
+			// the block is only called once, but `ConcreteBlock` wants an `Fn`
+			let callback = RefCell::new(Some(callback));
+			let handler = block::ConcreteBlock::new(move |data: id, error: id| {
+				let result = if error != nil {
+					let description: id = msg_send![error, localizedDescription];
+					Err(Error::PrintToPdf(NSString(description).to_str().to_string()))
+				} else {
+					let written: BOOL = msg_send![data, writeToFile: NSString::new(&path.to_string_lossy()) atomically: YES];
+					if written == YES {
+						Ok(path.clone())
+					} else {
+						Err(Error::PrintToPdf(format!("failed to write {}", path.display())))
+					}
+				};
+				if let Some(callback) = callback.borrow_mut().take() {
+					callback(result);
+				}
+			});
+			let handler = handler.copy();
+
+			let configuration: id = msg_send![class!(WKPDFConfiguration), new];
+			let () = msg_send![self.webview, createPDFWithConfiguration: configuration completionHandler: &*handler];
+			let () = msg_send![configuration, release];
+		}
+		Ok(())
+	}
+
+	pub fn take_snapshot(&self, callback: Box<dyn FnOnce(Result<Vec<u8>>)>) -> Result<()> {
+		// Safety: objc runtime calls are unsafe
+		unsafe {
+			let callback = RefCell::new(Some(callback));
+			let handler = block::ConcreteBlock::new(move |image: id, error: id| {
+				let result = if error != nil {
+					let description: id = msg_send![error, localizedDescription];
+					Err(Error::Snapshot(NSString(description).to_str().to_string()))
+				} else {
+					let png = png_representation(image);
+					if png != nil {
+						let length: NSUInteger = msg_send![png, length];
+						let bytes: *const u8 = msg_send![png, bytes];
+						Ok(slice::from_raw_parts(bytes, length as usize).to_vec())
+					} else {
+						Err(Error::Snapshot("failed to encode the snapshot".into()))
+					}
+				};
+				if let Some(callback) = callback.borrow_mut().take() {
+					callback(result);
+				}
+			});
+			let handler = handler.copy();
+			let () = msg_send![self.webview, takeSnapshotWithConfiguration: nil completionHandler: &*handler];
+		}
+		Ok(())
+	}
+
 	pub fn focus(&self) {}
 
 	#[cfg(any(debug_assertions, feature = "devtools"))]
-	pub fn open_devtools(&self) {
+	pub fn open_devtools(&self, _position: crate::webview::DevtoolsPosition) {
 		#[cfg(target_os = "macos")]
 		unsafe {
 			// taken from <https://github.com/WebKit/WebKit/blob/784f93cb80a386c29186c510bba910b67ce3adc1/Source/WebKit/UIProcess/API/Cocoa/WKWebView.mm#L1939>
 			let tool: id = msg_send![self.webview, _inspector];
 			let _: id = msg_send![tool, show];
+
+			// Equivalent Obj-C:
+			// [[webview _inspector] detach];
+			let responds: BOOL = msg_send![tool, respondsToSelector: sel!(detach)];
+			if responds == YES {
+				match _position {
+					crate::webview::DevtoolsPosition::Detached => {
+						let _: () = msg_send![tool, detach];
+					}
+					_ => {
+						let _: () = msg_send![tool, attach];
+					}
+				}
+			}
 		}
 	}
 
@@ -596,6 +989,148 @@ impl InnerWebView {
 			let _: () = msg_send![self.webview, setPageZoom: scale_factor];
 		}
 	}
+
+	pub fn set_clipboard(&self, _enabled: bool) {}
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn png_representation(image: id) -> id {
+	let tiff: id = msg_send![image, TIFFRepresentation];
+	let bitmap: id = msg_send![class!(NSBitmapImageRep), imageRepWithData: tiff];
+	let properties: id = msg_send![class!(NSDictionary), dictionary];
+	// NSBitmapImageFileTypePNG
+	msg_send![bitmap, representationUsingType: 4 as NSUInteger properties: properties]
+}
+
+#[cfg(target_os = "ios")]
+unsafe fn png_representation(image: id) -> id {
+	#[link(name = "UIKit", kind = "framework")]
+	extern "C" {
+		fn UIImagePNGRepresentation(image: id) -> id;
+	}
+	UIImagePNGRepresentation(image)
+}
+
+// An empty menu isn't shown, which is how the default context menu is disabled.
+#[cfg(target_os = "macos")]
+extern "C" fn will_open_menu(this: &Object, _: Sel, menu: id, _event: id) {
+	unsafe {
+		if *this.get_ivar::<BOOL>("ContextMenuEnabled") == NO {
+			let _: () = msg_send![menu, removeAllItems];
+		}
+	}
+}
+
+// Returning YES makes the click that activates the window also reach the page.
+#[cfg(target_os = "macos")]
+extern "C" fn accepts_first_mouse(this: &Object, _: Sel, _event: id) -> BOOL {
+	unsafe { *this.get_ivar::<BOOL>("AcceptsFirstMouse") }
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn key_event_info(event: id) -> (ModifiersState, KeyCode) {
+	let flags: NSUInteger = msg_send![event, modifierFlags];
+	let mut modifiers = ModifiersState::empty();
+	modifiers.set(ModifiersState::SHIFT, flags & (1 << 17) != 0);
+	modifiers.set(ModifiersState::CONTROL, flags & (1 << 18) != 0);
+	modifiers.set(ModifiersState::ALT, flags & (1 << 19) != 0);
+	modifiers.set(ModifiersState::SUPER, flags & (1 << 20) != 0);
+	let key_code: u16 = msg_send![event, keyCode];
+	(modifiers, KeyCode::from_scancode(key_code as u32))
+}
+
+// Offers the key press to the app's handler, returning whether it was consumed.
+#[cfg(target_os = "macos")]
+unsafe fn handle_key_press(this: &Object, event: id) -> bool {
+	let handler = *this.get_ivar::<*mut c_void>("KeyPressHandler");
+	if handler.is_null() {
+		return false;
+	}
+	let handler = &*(handler as *mut Box<dyn Fn(ModifiersState, KeyCode) -> bool>);
+	let (modifiers, key) = key_event_info(event);
+	handler(modifiers, key)
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn is_passthrough(this: &Object, event: id) -> bool {
+	let accelerators = *this.get_ivar::<*mut c_void>("PassthroughAccelerators");
+	if accelerators.is_null() {
+		return false;
+	}
+	let accelerators = &*(accelerators as *mut Vec<Accelerator>);
+	let (modifiers, key) = key_event_info(event);
+	accelerators.iter().any(|accelerator| accelerator.matches(modifiers, key))
+}
+
+#[cfg(target_os = "macos")]
+extern "C" fn key_down(this: &Object, _: Sel, event: id) {
+	unsafe {
+		if !handle_key_press(this, event) {
+			let () = msg_send![super(this, class!(WKWebView)), keyDown: event];
+		}
+	}
+}
+
+// Shortcuts with Command held go through `performKeyEquivalent:` instead of `keyDown:`. Declining a passthrough
+// shortcut here lets AppKit offer it to the main menu next.
+#[cfg(target_os = "macos")]
+extern "C" fn perform_key_equivalent(this: &Object, _: Sel, event: id) -> BOOL {
+	unsafe {
+		if is_passthrough(this, event) {
+			NO
+		} else if handle_key_press(this, event) {
+			YES
+		} else {
+			msg_send![super(this, class!(WKWebView)), performKeyEquivalent: event]
+		}
+	}
+}
+
+/// The custom protocol tasks that have been started and not yet stopped by WebKit.
+static RUNNING_TASKS: Lazy<Mutex<HashSet<usize>>> = Lazy::new(Default::default);
+
+/// Completes a custom protocol task with the handler's response, or a 404 if the handler failed.
+unsafe fn respond_to_task(task: id, sent_response: Option<HttpResponse>) {
+	let request: id = msg_send![task, request];
+	let url: id = msg_send![request, URL];
+	if let Some(sent_response) = sent_response {
+		let content = sent_response.body();
+		// default: application/octet-stream, but should be provided by the client
+		let wanted_mime = sent_response.mimetype();
+		// default to 200
+		let wanted_status_code = sent_response.status().as_u16() as i32;
+		// default to HTTP/1.1
+		let wanted_version = format!("{:#?}", sent_response.version());
+
+		let dictionary: id = msg_send![class!(NSMutableDictionary), alloc];
+		let headers: id = msg_send![dictionary, initWithCapacity:1];
+		if let Some(mime) = wanted_mime {
+			let () = msg_send![headers, setObject:NSString::new(mime) forKey: NSString::new("content-type")];
+		}
+		let () = msg_send![headers, setObject:NSString::new(&content.len().to_string()) forKey: NSString::new("content-length")];
+
+		// add headers
+		for (name, value) in sent_response.headers().iter() {
+			let header_key = name.to_string();
+			if let Ok(value) = value.to_str() {
+				let () = msg_send![headers, setObject:NSString::new(value) forKey: NSString::new(&header_key)];
+			}
+		}
+
+		let urlresponse: id = msg_send![class!(NSHTTPURLResponse), alloc];
+		let response: id = msg_send![urlresponse, initWithURL:url statusCode: wanted_status_code HTTPVersion:NSString::new(&wanted_version) headerFields:headers];
+		let () = msg_send![task, didReceiveResponse: response];
+
+		// Send data; copied, since the response is dropped once this returns
+		let data: id = msg_send![class!(NSData), dataWithBytes:content.as_ptr() as *const c_void length:content.len()];
+		let () = msg_send![task, didReceiveData: data];
+	} else {
+		let urlresponse: id = msg_send![class!(NSHTTPURLResponse), alloc];
+		let response: id = msg_send![urlresponse, initWithURL:url statusCode:404 HTTPVersion:NSString::new("HTTP/1.1") headerFields:null::<c_void>()];
+		let () = msg_send![task, didReceiveResponse: response];
+	}
+	// Finish
+	let () = msg_send![task, didFinish];
 }
 
 pub fn platform_webview_version() -> Result<String> {
@@ -621,6 +1156,20 @@ impl Drop for InnerWebView {
 				let _ = Box::from_raw(self.nav_decide_policy_ptr);
 			}
 
+			if !self.permission_handler_ptr.is_null() {
+				let _ = Box::from_raw(self.permission_handler_ptr);
+			}
+
+			#[cfg(target_os = "macos")]
+			if !self.key_press_handler_ptr.is_null() {
+				let _ = Box::from_raw(self.key_press_handler_ptr);
+			}
+
+			#[cfg(target_os = "macos")]
+			if !self.passthrough_accelerators_ptr.is_null() {
+				let _ = Box::from_raw(self.passthrough_accelerators_ptr);
+			}
+
 			#[cfg(target_os = "macos")]
 			if !self.file_drop_ptr.is_null() {
 				let _ = Box::from_raw(self.file_drop_ptr);
@@ -632,6 +1181,10 @@ impl Drop for InnerWebView {
 				}
 			}
 
+			for script in self.init_scripts.borrow().values().chain(self.css_scripts.borrow().values()) {
+				let _: () = msg_send![*script, release];
+			}
+
 			let _: Id<_> = Id::from_retained_ptr(self.webview);
 			let _: Id<_> = Id::from_retained_ptr(self.manager);
 		}
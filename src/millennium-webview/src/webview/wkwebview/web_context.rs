@@ -1,12 +1,11 @@
-use crate::Result;
 use crate::{
-	http::{Request, Response},
-	webview::web_context::WebContextData
+	http::Request,
+	webview::{web_context::WebContextData, RequestAsyncResponder}
 };
 
 #[derive(Debug)]
 pub struct WebContextImpl {
-	protocols: Vec<*mut Box<dyn Fn(&Request) -> Result<Response>>>
+	protocols: Vec<*mut Box<dyn Fn(Request, RequestAsyncResponder)>>
 }
 
 impl WebContextImpl {
@@ -16,7 +15,7 @@ impl WebContextImpl {
 
 	pub fn set_allows_automation(&mut self, _flag: bool) {}
 
-	pub fn registered_protocols(&mut self, handler: *mut Box<dyn Fn(&Request) -> Result<Response>>) {
+	pub fn registered_protocols(&mut self, handler: *mut Box<dyn Fn(Request, RequestAsyncResponder)>) {
 		self.protocols.push(handler);
 	}
 }
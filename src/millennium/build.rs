@@ -48,7 +48,7 @@ fn main() {
 	let api_all = has_feature("api-all");
 	alias("api_all", api_all);
 
-	alias_module("fs", &["read-file", "write-file", "read-dir", "copy-file", "create-dir", "remove-dir", "remove-file", "rename", "exists"], api_all);
+	alias_module("fs", &["read-file", "write-file", "read-dir", "copy-file", "create-dir", "remove-dir", "remove-file", "rename", "exists", "watch"], api_all);
 
 	alias_module(
 		"window",
@@ -80,6 +80,7 @@ fn main() {
 			"set-cursor-icon",
 			"set-cursor-position",
 			"start-dragging",
+			"start-drag",
 			"print"
 		],
 		api_all
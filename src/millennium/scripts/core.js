@@ -137,14 +137,15 @@
 			// Prevents a text cursor from appearing when dragging
 			e.preventDefault();
 
-			// Start dragging if the element has an `app-drag-region` data attribute and maximize on double-clicking it
+			// Start dragging if the element has an `app-drag-region` data attribute, and run the system's title bar
+			// double-click action (usually maximize) on double-clicking it
 			window.__MILLENNIUM_INVOKE__('millennium', {
 				__millenniumModule: 'Window',
 				message: {
 					cmd: 'manage',
 					data: {
 						cmd: {
-							type: e.detail === 2 ? '__toggleMaximize' : 'startDragging'
+							type: e.detail === 2 ? '__titlebarDoubleClick' : 'startDragging'
 						}
 					}
 				}
@@ -263,6 +264,14 @@
 		});
 	};
 
+	// the per-load CSP nonce, from the meta tag injected with the CSP
+	Object.defineProperty(window, '__MILLENNIUM_CSP_NONCE__', {
+		get: function () {
+			const meta = document.querySelector('meta[property="csp-nonce"]');
+			return meta ? meta.nonce : undefined;
+		}
+	});
+
 	// window.print works on Linux/Windows; need to use the API on macOS
 	if (/macintosh/i.test(navigator.userAgentData ? navigator.userAgentData.platform : navigator.userAgent))
 		window.print = function() {
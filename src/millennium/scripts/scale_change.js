@@ -0,0 +1,30 @@
+/**
+ * Copyright 2022 pyke.io
+ *           2019-2021 Tauri Programme within The Commons Conservancy
+ *                     [https://tauri.studio/]
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Evaluated with `scaleFactor` in scope whenever the window's scale factor changes.
+// Images with a `srcset` are already re-selected by the browser. Other images served by a custom protocol are
+// re-requested with the new scale in the `scale` query parameter, so the protocol handler can pick the matching asset.
+// On Windows, custom protocols are served from `https://<scheme>.localhost`.
+for (const image of document.querySelectorAll('img[src]:not([srcset])')) {
+	const url = new URL(image.src, window.location.href);
+	const custom = !['http:', 'https:', 'data:', 'blob:'].includes(url.protocol) || url.hostname.endsWith('.localhost');
+	if (custom) {
+		url.searchParams.set('scale', String(scaleFactor));
+		image.src = url.href;
+	}
+}
@@ -71,6 +71,8 @@ declare global {
 		__MILLENNIUM_PATTERN__: {
 			pattern: MillenniumPattern;
 		};
+
+		__MILLENNIUM_CSP_NONCE__?: string;
 	}
 
 	interface Navigator {
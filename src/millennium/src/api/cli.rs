@@ -25,6 +25,7 @@ use serde::Serialize;
 use serde_json::Value;
 
 use crate::{
+	single_instance::JUMP_LIST_ITEM_ARG_PREFIX,
 	utils::config::{CliArg, CliConfig},
 	PackageInfo
 };
@@ -115,7 +116,9 @@ pub fn get_matches(cli: &CliConfig, package_info: &PackageInfo) -> crate::api::R
 	let about = cli.description().unwrap_or(&package_info.description.to_string()).to_string();
 	let version = &*package_info.version.to_string();
 	let app = get_app(package_info, version, &package_info.name, Some(&about), cli);
-	match app.try_get_matches() {
+	// a Windows jump list item launches the app with an extra argument, which isn't part of the CLI definition
+	let args = std::env::args_os().filter(|arg| !arg.to_string_lossy().starts_with(JUMP_LIST_ITEM_ARG_PREFIX));
+	match app.try_get_matches_from(args) {
 		Ok(matches) => Ok(get_matches_internal(cli, &matches)),
 		Err(e) => match ErrorExt::kind(&e) {
 			ErrorKind::DisplayHelp => {
@@ -79,6 +79,9 @@ pub enum Error {
 	#[cfg(feature = "fs-extract-api")]
 	#[error("Failed to extract: {0}")]
 	Extract(String),
+	/// Failed to move an item to the trash.
+	#[error("failed to move to trash: {0}")]
+	Trash(String),
 	/// Notification error.
 	#[cfg(notification_all)]
 	#[error(transparent)]
@@ -19,15 +19,18 @@
 #[cfg(feature = "fs-extract-api")]
 mod extract;
 mod file_move;
+mod trash;
 
 use std::{
 	fs,
+	io::Write,
 	path::{Display, Path}
 };
 
 #[cfg(feature = "fs-extract-api")]
 pub use extract::*;
 pub use file_move::*;
+pub use trash::*;
 use serde::{de::Error as DeError, Deserialize, Deserializer};
 
 #[derive(Clone, Debug)]
@@ -79,6 +82,34 @@ pub fn read_binary<P: AsRef<Path>>(file: P) -> crate::api::Result<Vec<u8>> {
 	fs::read(file).map_err(Into::into)
 }
 
+/// Writes the contents to a file atomically, replacing the file if it exists.
+///
+/// The contents are first written to a temporary file in the same directory, which is then renamed over the target.
+/// If the process dies midway, the file keeps either its previous or its new contents, never a mix of both.
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> crate::api::Result<()> {
+	let path = path.as_ref();
+	let dir = match path.parent() {
+		Some(dir) if !dir.as_os_str().is_empty() => dir,
+		_ => Path::new(".")
+	};
+
+	let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+	temp.write_all(contents.as_ref())?;
+	// the temporary file is created private, so keep the permissions of the file it replaces
+	if let Ok(metadata) = fs::metadata(path) {
+		temp.as_file().set_permissions(metadata.permissions())?;
+	}
+	temp.as_file().sync_all()?;
+	// on Windows this is a `MoveFileEx` with `MOVEFILE_REPLACE_EXISTING`, since a plain rename fails if the target exists
+	temp.persist(path).map_err(|e| e.error)?;
+
+	// the rename itself is only durable once the directory entry is flushed
+	#[cfg(unix)]
+	fs::File::open(dir)?.sync_all()?;
+
+	Ok(())
+}
+
 #[cfg(test)]
 mod test {
 	use std::path::PathBuf;
@@ -99,6 +130,31 @@ mod test {
 		}
 	}
 
+	#[test]
+	fn check_write_atomic() {
+		let dir = tempfile::tempdir().unwrap();
+		let file = dir.path().join("state.json");
+
+		write_atomic(&file, "{}").unwrap();
+		assert_eq!(read_string(&file).unwrap(), "{}");
+
+		write_atomic(&file, r#"{"count":1}"#).unwrap();
+		assert_eq!(read_string(&file).unwrap(), r#"{"count":1}"#);
+
+		// only the target is left behind
+		assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+	}
+
+	#[test]
+	fn check_move_to_trash_missing_file() {
+		let dir = tempfile::tempdir().unwrap();
+
+		match move_to_trash(dir.path().join("missing.txt")) {
+			Err(Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+			res => panic!("unexpected result: {:?}", res)
+		}
+	}
+
 	#[test]
 	fn check_read_string() {
 		let file = String::from("test/api/test.txt");
@@ -0,0 +1,128 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::{Path, PathBuf};
+
+/// Moves a file or directory to the trash (the recycle bin on Windows) instead of deleting it permanently.
+///
+/// Returns the location of the item inside the trash if the platform reports it.
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Uses `NSFileManager`'s `trashItemAtURL`, the trashed location is always returned.
+/// - **Linux:** Uses GIO, which implements the freedesktop.org trash specification. The trashed location is not returned.
+/// - **Windows:** Uses `SHFileOperation`. The trashed location is not returned. If the item can't be recycled, for
+///   instance because it is on a network drive, the user is asked whether to delete it permanently instead.
+pub fn move_to_trash<P: AsRef<Path>>(path: P) -> crate::api::Result<Option<PathBuf>> {
+	let path = path.as_ref();
+	if !path.exists() {
+		return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("{} does not exist", path.display())).into());
+	}
+	imp::move_to_trash(path)
+}
+
+#[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "openbsd", target_os = "netbsd"))]
+mod imp {
+	use std::path::{Path, PathBuf};
+
+	use gtk::gio::{self, prelude::FileExt};
+
+	pub fn move_to_trash(path: &Path) -> crate::api::Result<Option<PathBuf>> {
+		gio::File::for_path(path)
+			.trash(None::<&gio::Cancellable>)
+			.map_err(|e| crate::api::Error::Trash(e.to_string()))?;
+		Ok(None)
+	}
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+	use std::{
+		ffi::CStr,
+		os::raw::c_char,
+		path::{Path, PathBuf}
+	};
+
+	use cocoa::{
+		base::{id, nil, BOOL, NO},
+		foundation::NSString
+	};
+	use objc::*;
+
+	pub fn move_to_trash(path: &Path) -> crate::api::Result<Option<PathBuf>> {
+		unsafe {
+			let path = NSString::alloc(nil).init_str(&path.to_string_lossy());
+			let url: id = msg_send![class!(NSURL), fileURLWithPath: path];
+			let _: () = msg_send![path, release];
+
+			let file_manager: id = msg_send![class!(NSFileManager), defaultManager];
+			let mut trashed_url: id = nil;
+			let mut error: id = nil;
+			let success: BOOL = msg_send![file_manager, trashItemAtURL: url resultingItemURL: &mut trashed_url error: &mut error];
+			if success == NO {
+				let description: id = msg_send![error, localizedDescription];
+				return Err(crate::api::Error::Trash(ns_string_to_string(description)));
+			}
+
+			if trashed_url == nil {
+				Ok(None)
+			} else {
+				let trashed_path: id = msg_send![trashed_url, path];
+				Ok(Some(PathBuf::from(ns_string_to_string(trashed_path))))
+			}
+		}
+	}
+
+	unsafe fn ns_string_to_string(string: id) -> String {
+		let bytes: *const c_char = msg_send![string, UTF8String];
+		CStr::from_ptr(bytes).to_string_lossy().into_owned()
+	}
+}
+
+#[cfg(windows)]
+mod imp {
+	use std::{
+		os::windows::ffi::OsStrExt,
+		path::{Path, PathBuf}
+	};
+
+	use windows::{
+		core::PCWSTR,
+		Win32::UI::Shell::{SHFileOperationW, FOF_ALLOWUNDO, FOF_NOCONFIRMATION, FOF_NOERRORUI, FOF_SILENT, FOF_WANTNUKEWARNING, FO_DELETE, SHFILEOPSTRUCTW}
+	};
+
+	pub fn move_to_trash(path: &Path) -> crate::api::Result<Option<PathBuf>> {
+		// the recycle bin is only used for absolute paths
+		let path = if path.is_absolute() { path.to_path_buf() } else { std::env::current_dir()?.join(path) };
+		// `pFrom` is a list of paths, terminated by an additional null
+		let from: Vec<u16> = path.as_os_str().encode_wide().chain([0, 0]).collect();
+
+		let mut operation = SHFILEOPSTRUCTW {
+			wFunc: FO_DELETE,
+			pFrom: PCWSTR(from.as_ptr()),
+			fFlags: (FOF_ALLOWUNDO | FOF_NOCONFIRMATION | FOF_NOERRORUI | FOF_SILENT | FOF_WANTNUKEWARNING) as u16,
+			..Default::default()
+		};
+		let result = unsafe { SHFileOperationW(&mut operation) };
+		if result != 0 {
+			Err(crate::api::Error::Trash(format!("SHFileOperation failed with code {:#x}", result)))
+		} else if operation.fAnyOperationsAborted.as_bool() {
+			Err(crate::api::Error::Trash("the operation was cancelled".into()))
+		} else {
+			Ok(None)
+		}
+	}
+}
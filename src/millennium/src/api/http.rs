@@ -683,9 +683,13 @@ pub struct ResponseData {
 
 #[cfg(test)]
 mod test {
+	use std::io::Write;
+
+	use mockito::mock;
 	use quickcheck::{Arbitrary, Gen};
 
-	use super::ClientBuilder;
+	use super::{ClientBuilder, HttpRequestBuilder, ResponseType};
+	use crate::async_runtime::block_on;
 
 	impl Arbitrary for ClientBuilder {
 		fn arbitrary(g: &mut Gen) -> Self {
@@ -695,4 +699,46 @@ mod test {
 			}
 		}
 	}
+
+	fn url(path: &str) -> String {
+		format!("{}{}", mockito::server_url(), path)
+	}
+
+	#[test]
+	fn reads_chunked_response() {
+		// a body written by a function is sent with `Transfer-Encoding: chunked`
+		let _data = mock("GET", "/data").with_body_from_fn(|w| w.write_all(b"chunked response body")).create();
+
+		let client = ClientBuilder::new().build().unwrap();
+		let request = HttpRequestBuilder::new("GET", url("/data")).unwrap().response_type(ResponseType::Binary);
+		let response = block_on(async { client.send(request).await?.bytes().await }).unwrap();
+		assert_eq!(response.status, 200);
+		assert_eq!(response.data, b"chunked response body");
+	}
+
+	#[test]
+	fn reads_json_response() {
+		let _data = mock("GET", "/data")
+			.with_header("Content-Type", "application/json")
+			.with_body(serde_json::json!({ "version": "1.0.0" }).to_string())
+			.create();
+
+		let client = ClientBuilder::new().build().unwrap();
+		let request = HttpRequestBuilder::new("GET", url("/data")).unwrap();
+		let response = block_on(async { client.send(request).await?.read().await }).unwrap();
+		assert_eq!(response.status, 200);
+		assert_eq!(response.data, serde_json::json!({ "version": "1.0.0" }));
+		assert_eq!(response.headers.get("content-type").map(String::as_str), Some("application/json"));
+	}
+
+	#[test]
+	fn reads_error_response() {
+		let missing = mock("GET", "/missing").with_status(404).expect(1).create();
+
+		let client = ClientBuilder::new().build().unwrap();
+		let request = HttpRequestBuilder::new("GET", url("/missing")).unwrap().response_type(ResponseType::Text);
+		let response = block_on(async { client.send(request).await?.read().await }).unwrap();
+		assert_eq!(response.status, 404);
+		missing.assert();
+	}
 }
@@ -291,7 +291,7 @@ pub fn resolve_path<P: AsRef<Path>>(
 
 /// Returns the path to the user's audio directory.
 pub fn audio_dir() -> Option<PathBuf> {
-	dirs_next::audio_dir()
+	special_dir(dirs_next::audio_dir, NS_MUSIC_DIRECTORY, "Music")
 }
 
 /// Returns the path to the user's cache directory.
@@ -316,17 +316,17 @@ pub fn local_data_dir() -> Option<PathBuf> {
 
 /// Returns the path to the user's desktop directory.
 pub fn desktop_dir() -> Option<PathBuf> {
-	dirs_next::desktop_dir()
+	special_dir(dirs_next::desktop_dir, NS_DESKTOP_DIRECTORY, "Desktop")
 }
 
 /// Returns the path to the user's document directory.
 pub fn document_dir() -> Option<PathBuf> {
-	dirs_next::document_dir()
+	special_dir(dirs_next::document_dir, NS_DOCUMENT_DIRECTORY, "Documents")
 }
 
 /// Returns the path to the user's download directory.
 pub fn download_dir() -> Option<PathBuf> {
-	dirs_next::download_dir()
+	special_dir(dirs_next::download_dir, NS_DOWNLOADS_DIRECTORY, "Downloads")
 }
 
 /// Returns the path to the user's executable directory.
@@ -346,27 +346,84 @@ pub fn home_dir() -> Option<PathBuf> {
 
 /// Returns the path to the user's picture directory.
 pub fn picture_dir() -> Option<PathBuf> {
-	dirs_next::picture_dir()
+	special_dir(dirs_next::picture_dir, NS_PICTURES_DIRECTORY, "Pictures")
 }
 
 /// Returns the path to the user's public directory.
 pub fn public_dir() -> Option<PathBuf> {
-	dirs_next::public_dir()
+	special_dir(dirs_next::public_dir, NS_SHARED_PUBLIC_DIRECTORY, "Public")
 }
 
 /// Returns the path to the user's runtime directory.
+///
+/// This is `XDG_RUNTIME_DIR` on Linux. Returns `None` on macOS and Windows, which have no runtime directory, and on
+/// Linux sessions without `XDG_RUNTIME_DIR`.
 pub fn runtime_dir() -> Option<PathBuf> {
 	dirs_next::runtime_dir()
 }
 
 /// Returns the path to the user's template directory.
 pub fn template_dir() -> Option<PathBuf> {
-	dirs_next::template_dir()
+	dirs_next::template_dir().or_else(|| xdg_user_dir_fallback("Templates"))
 }
 
 /// Returns the path to the user's video dir
 pub fn video_dir() -> Option<PathBuf> {
-	dirs_next::video_dir()
+	special_dir(dirs_next::video_dir, NS_MOVIES_DIRECTORY, "Videos")
+}
+
+// `NSSearchPathDirectory` values of the special directories
+const NS_DOCUMENT_DIRECTORY: usize = 9;
+const NS_DESKTOP_DIRECTORY: usize = 12;
+const NS_DOWNLOADS_DIRECTORY: usize = 15;
+const NS_MOVIES_DIRECTORY: usize = 17;
+const NS_MUSIC_DIRECTORY: usize = 18;
+const NS_PICTURES_DIRECTORY: usize = 19;
+const NS_SHARED_PUBLIC_DIRECTORY: usize = 21;
+
+/// Resolves a special directory with `NSSearchPathForDirectoriesInDomains` on macOS, and with `resolve` elsewhere,
+/// which uses `SHGetKnownFolderPath` on Windows and xdg-user-dirs on Linux. `xdg_default` is the name of the directory
+/// in the home directory, used when xdg-user-dirs doesn't define it.
+#[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+fn special_dir(resolve: fn() -> Option<PathBuf>, search_path_directory: usize, xdg_default: &str) -> Option<PathBuf> {
+	#[cfg(target_os = "macos")]
+	let dir = macos::search_path_dir(search_path_directory).or_else(resolve);
+	#[cfg(not(target_os = "macos"))]
+	let dir = resolve();
+	dir.or_else(|| xdg_user_dir_fallback(xdg_default))
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+	use std::{ffi::CStr, os::raw::c_char, path::PathBuf};
+
+	use cocoa::base::{id, nil, BOOL, YES};
+	use objc::*;
+
+	const NS_USER_DOMAIN_MASK: usize = 1;
+
+	#[link(name = "Foundation", kind = "framework")]
+	extern "C" {
+		fn NSSearchPathForDirectoriesInDomains(directory: usize, domain_mask: usize, expand_tilde: BOOL) -> id;
+	}
+
+	pub fn search_path_dir(directory: usize) -> Option<PathBuf> {
+		unsafe {
+			let paths = NSSearchPathForDirectoriesInDomains(directory, NS_USER_DOMAIN_MASK, YES);
+			let path: id = msg_send![paths, firstObject];
+			if path == nil {
+				return None;
+			}
+			let bytes: *const c_char = msg_send![path, UTF8String];
+			Some(PathBuf::from(CStr::from_ptr(bytes).to_string_lossy().into_owned()))
+		}
+	}
+}
+
+// xdg-user-dirs leaves a directory undefined when `user-dirs.dirs` doesn't list it, in which case it's expected at its
+// default location in the home directory. Windows and macOS resolve their special directories from the system instead.
+fn xdg_user_dir_fallback(name: &str) -> Option<PathBuf> {
+	if cfg!(target_os = "linux") { home_dir().map(|dir| dir.join(name)) } else { None }
 }
 
 /// Returns the path to the resource directory of this app.
@@ -389,3 +446,55 @@ pub fn log_dir(config: &Config) -> Option<PathBuf> {
 
 	path
 }
+
+/// Returns the path to the default webview data directory of this app, where the webview stores its cookies, local
+/// storage and caches.
+///
+/// Windows and Linux use this directory unless the window sets its own with
+/// [`WindowBuilder::data_directory`](crate::window::WindowBuilder::data_directory); macOS keeps the webview data in
+/// the app's sandbox.
+pub fn webview_data_dir(config: &Config) -> Option<PathBuf> {
+	local_data_dir().map(|dir| dir.join(&config.millennium.bundle.identifier))
+}
+
+/// Moves the webview data directory of a previous bundle identifier to the one of the current identifier, so users
+/// stay logged in after the app is renamed.
+///
+/// Nothing is moved if the current directory already exists or the previous one doesn't. Returns whether the data
+/// was moved. This must run before any window is created, since the webview locks its data directory.
+pub fn migrate_webview_data_dir(config: &Config, old_identifier: &str) -> crate::api::Result<bool> {
+	let local_data_dir = local_data_dir().ok_or_else(|| crate::api::Error::Path("unable to determine the local data directory".to_string()))?;
+	migrate_dir(&local_data_dir.join(old_identifier), &local_data_dir.join(&config.millennium.bundle.identifier))
+}
+
+fn migrate_dir(from: &Path, to: &Path) -> crate::api::Result<bool> {
+	if from == to || to.exists() || !from.is_dir() {
+		return Ok(false);
+	}
+	std::fs::rename(from, to)?;
+	Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+	use super::migrate_dir;
+
+	#[test]
+	fn migrates_only_into_missing_dir() {
+		let root = tempfile::tempdir().unwrap();
+		let old = root.path().join("com.example.old");
+		let new = root.path().join("com.example.new");
+
+		assert!(!migrate_dir(&old, &new).unwrap());
+
+		std::fs::create_dir(&old).unwrap();
+		std::fs::write(old.join("Cookies"), "session").unwrap();
+		assert!(migrate_dir(&old, &new).unwrap());
+		assert!(!old.exists());
+		assert_eq!(std::fs::read_to_string(new.join("Cookies")).unwrap(), "session");
+
+		std::fs::create_dir(&old).unwrap();
+		assert!(!migrate_dir(&old, &new).unwrap());
+		assert!(old.exists());
+	}
+}
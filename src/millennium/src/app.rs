@@ -40,29 +40,27 @@ use crate::runtime::SystemTrayEvent as RuntimeSystemTrayEvent;
 use crate::scope::ShellScope;
 #[cfg(updater)]
 use crate::updater;
-#[cfg(target_os = "macos")]
-use crate::ActivationPolicy;
 use crate::{
 	api::ipc::CallbackFn,
 	command::{CommandArg, CommandItem},
-	hooks::{window_invoke_responder, InvokeHandler, InvokeResponder, OnPageLoad, PageLoadPayload, SetupHook},
-	manager::{Asset, CustomProtocol, WindowManager},
+	hooks::{window_invoke_responder, CspPayload, InvokeHandler, InvokeResponder, OnBinaryMessage, OnCsp, OnPageLoad, PageLoadPayload, SetupHook},
+	manager::{Asset, CustomProtocol, WindowManager, WindowManagerHooks},
 	plugin::{Plugin, PluginStore},
 	runtime::{
 		http::{Request as HttpRequest, Response as HttpResponse},
 		webview::WebviewAttributes,
 		window::{PendingWindow, WindowEvent as RuntimeWindowEvent},
-		ExitRequestedEventAction, RunEvent as RuntimeRunEvent
+		EventLoopProxy, ExitRequestedEventAction, JumpListItem, RunEvent as RuntimeRunEvent
 	},
 	scope::FsScope,
 	sealed::{ManagerBase, RuntimeOrDispatch},
 	utils::{
 		assets::Assets,
-		config::{Config, WebviewInstallMode},
+		config::{Config, CspDirectiveSources, WebviewInstallMode},
 		resources::resource_relpath,
 		Env
 	},
-	Context, EventLoopMessage, Invoke, InvokeError, InvokeResponse, Manager, Runtime, Scopes, StateManager, Theme, Window
+	AccessibilityPreferences, Context, EventLoopMessage, Icon, Invoke, InvokeError, InvokeResponse, Manager, Runtime, Scopes, StateManager, Theme, Window
 };
 
 pub(crate) type GlobalMenuEventListener<R> = Box<dyn Fn(WindowMenuEvent<R>) + Send + Sync>;
@@ -137,7 +135,13 @@ pub enum WindowEvent {
 	///
 	/// - **macOS / Linux**: Not supported.
 	/// - **Windows**: Only delivered if the window [`theme`](`crate::window::WindowBuilder#method.theme`) is `None`.
-	ThemeChanged(Theme)
+	ThemeChanged(Theme),
+	/// The window's webview has been suspended or resumed, either with [`Window::suspend`] and [`Window::resume`] or
+	/// automatically if the window was built with
+	/// [`suspend_when_unfocused`](`crate::window::WindowBuilder#method.suspend_when_unfocused`).
+	///
+	/// The parameter is true if the webview has been suspended, and false if it has been resumed.
+	Suspended(bool)
 }
 
 impl From<RuntimeWindowEvent> for WindowEvent {
@@ -150,7 +154,8 @@ impl From<RuntimeWindowEvent> for WindowEvent {
 			RuntimeWindowEvent::Focused(flag) => Self::Focused(flag),
 			RuntimeWindowEvent::ScaleFactorChanged { scale_factor, new_inner_size } => Self::ScaleFactorChanged { scale_factor, new_inner_size },
 			RuntimeWindowEvent::FileDrop(event) => Self::FileDrop(event),
-			RuntimeWindowEvent::ThemeChanged(theme) => Self::ThemeChanged(theme)
+			RuntimeWindowEvent::ThemeChanged(theme) => Self::ThemeChanged(theme),
+			RuntimeWindowEvent::Suspended(suspended) => Self::Suspended(suspended)
 		}
 	}
 }
@@ -187,6 +192,76 @@ pub enum RunEvent {
 	/// stuff (updating state, performing calculations, etc) that happens as the
 	/// “main body” of your event loop.
 	MainEventsCleared,
+	/// The OS asked the application to open files or URLs, e.g. because the user opened a file associated with the
+	/// application or followed a link using one of its custom URL schemes. Files are given as `file://` URLs.
+	///
+	/// The file types and URL schemes the application handles are registered in its `Info.plist`. The bundler merges
+	/// an `Info.plist` file placed next to your Millennium config file into the generated one, for example:
+	///
+	/// ```xml
+	/// <?xml version="1.0" encoding="UTF-8"?>
+	/// <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+	/// <plist version="1.0">
+	/// <dict>
+	/// 	<key>CFBundleURLTypes</key>
+	/// 	<array>
+	/// 		<dict>
+	/// 			<key>CFBundleURLName</key>
+	/// 			<string>com.example.app</string>
+	/// 			<key>CFBundleURLSchemes</key>
+	/// 			<array>
+	/// 				<string>example</string>
+	/// 			</array>
+	/// 		</dict>
+	/// 	</array>
+	/// 	<key>CFBundleDocumentTypes</key>
+	/// 	<array>
+	/// 		<dict>
+	/// 			<key>CFBundleTypeExtensions</key>
+	/// 			<array>
+	/// 				<string>example</string>
+	/// 			</array>
+	/// 			<key>CFBundleTypeRole</key>
+	/// 			<string>Editor</string>
+	/// 		</dict>
+	/// 	</array>
+	/// </dict>
+	/// </plist>
+	/// ```
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux:** Unsupported. Files and URLs are passed to a new process as command line arguments.
+	#[non_exhaustive]
+	Opened {
+		/// The URLs to open.
+		urls: Vec<url::Url>
+	},
+	/// The system theme changed, e.g. the user switched the OS to dark mode.
+	///
+	/// Unlike [`WindowEvent::ThemeChanged`], this is emitted once for the whole application.
+	ThemeChanged(Theme),
+	/// The system accent color changed. Carries the new color as RGBA.
+	///
+	/// See [`Window::accent_color`].
+	AccentColorChanged((u8, u8, u8, u8)),
+	/// The display preferences in the system's accessibility settings changed.
+	///
+	/// See [`Window::accessibility_preferences`].
+	AccessibilityPreferencesChanged(AccessibilityPreferences),
+	/// An item of the jump list set with [`AppHandle::set_jump_list`] was clicked.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Clicking an item launches a new instance of the app with a `--jump-list-item=<id>` argument, which
+	///   emits this event once it starts. When the app keeps a single instance with
+	///   [`request_single_instance_lock`](crate::request_single_instance_lock), the running instance emits it instead.
+	/// - **Linux:** Unsupported.
+	#[non_exhaustive]
+	JumpListItemClicked {
+		/// The id of the clicked item.
+		id: String
+	},
 	/// Updater event.
 	#[cfg(updater)]
 	#[cfg_attr(doc_cfg, doc(cfg(feature = "updater")))]
@@ -196,6 +271,7 @@ pub enum RunEvent {
 impl From<EventLoopMessage> for RunEvent {
 	fn from(event: EventLoopMessage) -> Self {
 		match event {
+			EventLoopMessage::JumpListItemClicked(id) => RunEvent::JumpListItemClicked { id },
 			#[cfg(updater)]
 			EventLoopMessage::Updater(event) => RunEvent::Updater(event)
 		}
@@ -341,8 +417,6 @@ pub struct AppHandle<R: Runtime> {
 }
 
 impl<R: Runtime> AppHandle<R> {
-	// currently only used on the updater
-	#[allow(dead_code)]
 	pub(crate) fn create_proxy(&self) -> R::EventLoopProxy {
 		self.runtime_handle.create_proxy()
 	}
@@ -370,6 +444,58 @@ impl AppHandle<crate::MillenniumWebview> {
 			.send_event(millennium_runtime_webview::Message::Window(self.runtime_handle.window_id(window_id), message))
 			.map_err(Into::into)
 	}
+
+	/// Runs the closure on the main thread and returns its result, e.g. to read state that is only accessible from the
+	/// main thread. When called from another thread, this blocks until the event loop has run the closure.
+	pub fn run_on_main_thread_returning<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(&self, f: F) -> crate::Result<T> {
+		self.runtime_handle.run_on_main_thread_returning(f).map_err(Into::into)
+	}
+
+	/// Evaluates the script on every webview window with a single message to the event loop, rather than one per
+	/// window.
+	pub fn eval_script_all<S: Into<String>>(&self, script: S) -> crate::Result<()> {
+		self.runtime_handle.eval_script_all(script).map_err(Into::into)
+	}
+
+	/// Emits an event to the JavaScript listeners of every window, like [`Manager::emit_all`], but with a single
+	/// message to the event loop rather than one per window.
+	///
+	/// Unlike [`Manager::emit_all`], this doesn't trigger the Rust listeners of the event.
+	pub fn emit_to_all<S: serde::Serialize>(&self, event: &str, payload: S) -> crate::Result<()> {
+		crate::event::assert_event_name_is_valid(event);
+		self.eval_script_all(self.manager.event_emit_script(event, None, payload)?)
+	}
+
+	/// Changes the activation policy of the application while it is running, unlike [`App::set_activation_policy`], which
+	/// must be called before the app runs. Agent and menu bar apps can use this to show their dock icon only while one
+	/// of their windows is open.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux / iOS / Android:** Unsupported; does nothing.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// fn on_window_opened(app: &millennium::AppHandle<millennium::MillenniumWebview>) {
+	/// 	app.set_activation_policy(millennium::ActivationPolicy::Regular).unwrap();
+	/// }
+	/// ```
+	pub fn set_activation_policy(&self, activation_policy: crate::ActivationPolicy) -> crate::Result<()> {
+		self.runtime_handle.set_activation_policy(activation_policy).map_err(Into::into)
+	}
+
+	/// Returns the features the runtime supports on the current platform, e.g. to hide options that would be no-ops.
+	pub fn capabilities(&self) -> crate::RuntimeCapabilities {
+		self.runtime_handle.capabilities()
+	}
+
+	/// Returns the version of the platform webview: WebView2 on Windows, WebKit on macOS and Linux.
+	///
+	/// Use this to gracefully degrade features that need a newer webview than the one installed.
+	pub fn webview_version(&self) -> crate::Result<String> {
+		self.runtime_handle.webview_version().map_err(Into::into)
+	}
 }
 
 impl<R: Runtime> Clone for AppHandle<R> {
@@ -403,6 +529,54 @@ impl<R: Runtime> AppHandle<R> {
 		self.runtime_handle.run_on_main_thread(f).map_err(Into::into)
 	}
 
+	/// Registers a shortcut that is handled by the app before the key press reaches the webview.
+	///
+	/// The handler only fires while one of the app's windows is focused; use the global shortcut manager for shortcuts
+	/// that should work in the background.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// millennium::Builder::default().setup(|app| {
+	/// 	app.handle().register_app_shortcut("CmdOrCtrl+K", || println!("palette"))?;
+	/// 	Ok(())
+	/// });
+	/// ```
+	pub fn register_app_shortcut<F: Fn() + Send + Sync + 'static>(&self, accelerator: &str, handler: F) -> crate::Result<()> {
+		self.runtime_handle.register_app_shortcut(accelerator, handler).map_err(Into::into)
+	}
+
+	/// Unregisters an app-level shortcut registered with [`Self::register_app_shortcut`].
+	pub fn unregister_app_shortcut(&self, accelerator: &str) -> crate::Result<()> {
+		self.runtime_handle.unregister_app_shortcut(accelerator).map_err(Into::into)
+	}
+
+	/// Sets the items shown in the app's taskbar jump list on Windows, or in its dock menu on macOS, replacing the
+	/// previous ones. An empty list removes them.
+	///
+	/// Clicking an item emits [`RunEvent::JumpListItemClicked`] with the item's id.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use millennium::JumpListItem;
+	///
+	/// millennium::Builder::default().setup(|app| {
+	/// 	app.handle().set_jump_list(vec![JumpListItem {
+	/// 		id: "new-document".into(),
+	/// 		title: "New Document".into()
+	/// 	}])?;
+	/// 	Ok(())
+	/// });
+	/// ```
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Unsupported.
+	pub fn set_jump_list(&self, items: Vec<JumpListItem>) -> crate::Result<()> {
+		self.runtime_handle.set_jump_list(items).map_err(Into::into)
+	}
+
 	/// Removes the system tray.
 	#[cfg(all(windows, feature = "system-tray"))]
 	#[cfg_attr(doc_cfg, doc(cfg(all(windows, feature = "system-tray"))))]
@@ -648,22 +822,43 @@ impl<R: Runtime> App<R> {
 	/// Sets the activation policy for the application. It is set to
 	/// `NSApplicationActivationPolicyRegular` by default.
 	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux / iOS / Android:** Unsupported; does nothing.
+	///
 	/// # Examples
 	/// ```rust,no_run
 	/// let mut app = millennium::Builder::default()
 	/// 	// on an actual app, remove the string argument
 	/// 	.build(millennium::generate_context!("test/fixture/.millenniumrc"))
 	/// 	.expect("error while building Millennium application");
-	/// #[cfg(target_os = "macos")]
 	/// app.set_activation_policy(millennium::ActivationPolicy::Accessory);
 	/// app.run(|_app_handle, _event| {});
 	/// ```
-	#[cfg(target_os = "macos")]
-	#[cfg_attr(doc_cfg, doc(cfg(target_os = "macos")))]
-	pub fn set_activation_policy(&mut self, activation_policy: ActivationPolicy) {
+	pub fn set_activation_policy(&mut self, activation_policy: crate::ActivationPolicy) {
 		self.runtime.as_mut().unwrap().set_activation_policy(activation_policy);
 	}
 
+	/// Caps how many times per second a window can be redrawn, e.g. to 60 frames per second, so that apps redrawing for
+	/// every animation frame don't keep a core busy. Pass `None` or `0` to remove the cap; redraws are uncapped by
+	/// default.
+	///
+	/// Redraws requested sooner than one frame after the previous one are deferred until the next frame rather than
+	/// dropped, so the last requested frame is always drawn.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// let mut app = millennium::Builder::default()
+	/// 	// on an actual app, remove the string argument
+	/// 	.build(millennium::generate_context!("test/fixture/.millenniumrc"))
+	/// 	.expect("error while building Millennium application");
+	/// app.set_max_frame_rate(Some(60));
+	/// app.run(|_app_handle, _event| {});
+	/// ```
+	pub fn set_max_frame_rate(&mut self, max_frame_rate: Option<u32>) {
+		self.runtime.as_mut().unwrap().set_max_frame_rate(max_frame_rate);
+	}
+
 	/// Gets the argument matches of the CLI definition configured in `.millenniumrc`.
 	///
 	/// # Examples
@@ -701,15 +896,23 @@ impl<R: Runtime> App<R> {
 	pub fn run<F: FnMut(&AppHandle<R>, RunEvent) + 'static>(mut self, mut callback: F) {
 		let app_handle = self.handle();
 		let manager = self.manager.clone();
-		self.runtime.take().unwrap().run(move |event| match event {
-			RuntimeRunEvent::Exit => {
-				on_event_loop_event(&app_handle, RuntimeRunEvent::Exit, &manager, Some(&mut callback));
-				app_handle.cleanup_before_exit();
-			}
-			_ => {
-				on_event_loop_event(&app_handle, event, &manager, Some(&mut callback));
-			}
-		});
+		let runtime = self.runtime.take().unwrap();
+		let run = move || {
+			runtime.run(move |event| match event {
+				RuntimeRunEvent::Exit => {
+					on_event_loop_event(&app_handle, RuntimeRunEvent::Exit, &manager, Some(&mut callback));
+					app_handle.cleanup_before_exit();
+				}
+				_ => {
+					on_event_loop_event(&app_handle, event, &manager, Some(&mut callback));
+				}
+			})
+		};
+
+		#[cfg(feature = "panic-dialog")]
+		crate::panic_dialog::run(run);
+		#[cfg(not(feature = "panic-dialog"))]
+		run();
 	}
 
 	/// Runs a iteration of the runtime event loop and immediately return.
@@ -794,6 +997,10 @@ pub struct Builder<R: Runtime> {
 	#[cfg(any(windows, target_os = "linux"))]
 	runtime_any_thread: bool,
 
+	/// Whether panics should be reported with a native error dialog.
+	#[cfg(feature = "panic-dialog")]
+	panic_dialog: bool,
+
 	/// The JS message handler.
 	invoke_handler: Box<InvokeHandler<R>>,
 
@@ -810,6 +1017,12 @@ pub struct Builder<R: Runtime> {
 	/// Page load hook.
 	on_page_load: Box<OnPageLoad<R>>,
 
+	/// Binary message handler.
+	on_binary_message: Option<Box<OnBinaryMessage<R>>>,
+
+	/// CSP hook.
+	on_csp: Option<Box<OnCsp>>,
+
 	/// windows to create when starting up.
 	pending_windows: Vec<PendingWindow<EventLoopMessage, R>>,
 
@@ -825,6 +1038,9 @@ pub struct Builder<R: Runtime> {
 	/// The menu set to all windows.
 	menu: Option<Menu>,
 
+	/// The icon set to all windows that don't define their own.
+	default_window_icon: Option<Icon>,
+
 	/// Enable macOS default menu creation.
 	#[allow(unused)]
 	enable_macos_default_menu: bool,
@@ -854,17 +1070,22 @@ impl<R: Runtime> Builder<R> {
 		Self {
 			#[cfg(any(windows, target_os = "linux"))]
 			runtime_any_thread: false,
+			#[cfg(feature = "panic-dialog")]
+			panic_dialog: false,
 			setup: Box::new(|_| Ok(())),
 			invoke_handler: Box::new(|_| ()),
 			invoke_responder: Arc::new(window_invoke_responder),
 			invoke_initialization_script:
 				"Object.defineProperty(window, '__MILLENNIUM_POST_MESSAGE__', { value: message => window.ipc.postMessage(JSON.stringify(message)) })".into(),
 			on_page_load: Box::new(|_, _| ()),
+			on_binary_message: None,
+			on_csp: None,
 			pending_windows: Default::default(),
 			plugins: PluginStore::default(),
 			uri_scheme_protocols: Default::default(),
 			state: StateManager::new(),
 			menu: None,
+			default_window_icon: None,
 			enable_macos_default_menu: true,
 			menu_event_listeners: Vec::new(),
 			window_event_listeners: Vec::new(),
@@ -891,6 +1112,27 @@ impl<R: Runtime> Builder<R> {
 		self
 	}
 
+	/// Installs a panic hook that shows a native error dialog with the panic
+	/// message and a button to copy the details to the clipboard when a panic
+	/// unwinds out of the event loop and is about to take down the application.
+	///
+	/// The previously installed hook still runs for every panic. Panics that are
+	/// caught, or that only take down a background thread, don't show the dialog.
+	/// The dialog is shown from the event loop thread after the panic has
+	/// unwound, and the panic resumes unwinding once the dialog is closed.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// millennium::Builder::default().panic_dialog();
+	/// ```
+	#[cfg(feature = "panic-dialog")]
+	#[cfg_attr(doc_cfg, doc(cfg(feature = "panic-dialog")))]
+	#[must_use]
+	pub fn panic_dialog(mut self) -> Self {
+		self.panic_dialog = true;
+		self
+	}
+
 	/// Defines the JS message handler callback.
 	///
 	/// # Examples
@@ -965,6 +1207,56 @@ impl<R: Runtime> Builder<R> {
 		self
 	}
 
+	/// Defines the handler for binary messages sent from the webview with
+	/// `window.ipc.postBinaryMessage(data)`.
+	///
+	/// Binary messages skip the JSON serialization used by commands, so they are better suited for large
+	/// payloads like files or images. They are not available when using the isolation pattern.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// millennium::Builder::default()
+	/// 	.on_binary_message(|window, data| {
+	/// 		println!("window {} sent {} bytes", window.label(), data.len());
+	/// 	})
+	/// 	.run(millennium::generate_context!("test/fixture/.millenniumrc"))
+	/// 	.expect("failed to run app");
+	/// ```
+	#[must_use]
+	pub fn on_binary_message<F>(mut self, on_binary_message: F) -> Self
+	where
+		F: Fn(Window<R>, Vec<u8>) + Send + Sync + 'static
+	{
+		self.on_binary_message = Some(Box::new(on_binary_message));
+		self
+	}
+
+	/// Defines a hook to modify the Content-Security-Policy of each HTML page served by the `millennium`
+	/// protocol, after Millennium has added its own sources. It only runs when a CSP is configured in
+	/// `.millenniumrc > millennium > security > csp`.
+	///
+	/// A new nonce is generated every time a page is loaded. It replaces `__MILLENNIUM_CSP_NONCE_TOKEN__` anywhere in
+	/// the HTML and is exposed to scripts as `window.__MILLENNIUM_CSP_NONCE__`, but it is only allowed by the CSP if the
+	/// hook adds it. This allows a strict CSP while still creating inline scripts at runtime.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// millennium::Builder::default()
+	/// 	.on_csp(|payload, csp| {
+	/// 		csp.entry("script-src".into()).or_default().push(payload.nonce_source());
+	/// 	})
+	/// 	.run(millennium::generate_context!("test/fixture/.millenniumrc"))
+	/// 	.expect("failed to run app");
+	/// ```
+	#[must_use]
+	pub fn on_csp<F>(mut self, on_csp: F) -> Self
+	where
+		F: Fn(&CspPayload, &mut HashMap<String, CspDirectiveSources>) + Send + Sync + 'static
+	{
+		self.on_csp = Some(Box::new(on_csp));
+		self
+	}
+
 	/// Adds a plugin to the runtime.
 	///
 	/// # Examples
@@ -1134,6 +1426,23 @@ impl<R: Runtime> Builder<R> {
 		self
 	}
 
+	/// Sets the icon used by every window that doesn't set its own icon,
+	/// overriding the `default_window_icon` from the [`Context`].
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// millennium::Builder::default().default_window_icon(millennium::Icon::Rgba {
+	/// 	rgba: vec![255; 32 * 32 * 4],
+	/// 	width: 32,
+	/// 	height: 32
+	/// });
+	/// ```
+	#[must_use]
+	pub fn default_window_icon(mut self, icon: Icon) -> Self {
+		self.default_window_icon.replace(icon);
+		self
+	}
+
 	/// Registers a menu event handler for all windows.
 	///
 	/// # Examples
@@ -1224,6 +1533,24 @@ impl<R: Runtime> Builder<R> {
 	/// * `uri_scheme` The URI scheme to register, such as `example`.
 	/// * `protocol` the protocol associated with the given URI scheme. It's a function that takes an URL such as
 	///   `example://localhost/asset.css`.
+	///
+	/// # WebSockets
+	///
+	/// Custom protocols only answer request/response style loads; none of the webviews let a `ws://` or `wss://`
+	/// connection be handled by the app:
+	///
+	/// - **Windows:** WebView2 doesn't raise `WebResourceRequested` for WebSocket connections.
+	/// - **macOS:** `WKURLSchemeHandler` can't be registered for `ws`/`wss`, and WebKit never routes WebSocket
+	///   handshakes through it.
+	/// - **Linux:** WebKitGTK's URI scheme handlers receive a single request and return a single response, with no way to
+	///   upgrade the connection.
+	///
+	/// For a bidirectional channel, use commands and events, or [`Self::on_binary_message`] for large payloads, which
+	/// stay in-process. If a real WebSocket is needed (e.g. to reuse a client library), run a server on
+	/// `127.0.0.1` bound to port `0` so the OS picks a free port, and pass the port and a random token to the page
+	/// (e.g. with an initialization script). Any local process can connect to that port, so reject handshakes that don't
+	/// carry the token or whose `Origin` isn't the app's. Use bounded channels between the socket and the rest of the
+	/// app so a page that stops reading can't make it buffer messages indefinitely.
 	#[must_use]
 	pub fn register_uri_scheme_protocol<
 		N: Into<String>,
@@ -1238,6 +1565,20 @@ impl<R: Runtime> Builder<R> {
 		self
 	}
 
+	/// Registers a URI scheme protocol that serves the files under `root`, e.g. `dev://localhost/index.html` serves
+	/// `root/index.html`.
+	///
+	/// Files are read from disk on every request, so edits show up on reload without rebuilding the app. Directories
+	/// are served with their `index.html`, or a listing of their contents if they don't have one. Requests for paths
+	/// outside of `root` are rejected.
+	///
+	/// This is a development convenience; production apps should serve their embedded assets instead.
+	#[must_use]
+	pub fn register_directory_protocol<N: Into<String>, P: Into<PathBuf>>(self, uri_scheme: N, root: P) -> Self {
+		let protocol = millennium_runtime::http::directory_protocol(root);
+		self.register_uri_scheme_protocol(uri_scheme, move |_, request| protocol(request))
+	}
+
 	/// Sets the current platform's target name for the updater.
 	///
 	/// See [`UpdateBuilder::target`](crate::updater::UpdateBuilder#method.target) for more information.
@@ -1274,7 +1615,16 @@ impl<R: Runtime> Builder<R> {
 
 	/// Builds the application.
 	#[allow(clippy::type_complexity)]
-	pub fn build<A: Assets>(mut self, context: Context<A>) -> crate::Result<App<R>> {
+	pub fn build<A: Assets>(mut self, mut context: Context<A>) -> crate::Result<App<R>> {
+		if let Some(icon) = self.default_window_icon.take() {
+			context.default_window_icon_mut().replace(icon);
+		}
+
+		#[cfg(feature = "panic-dialog")]
+		if self.panic_dialog {
+			crate::panic_dialog::install(context.package_info().name.clone());
+		}
+
 		#[cfg(target_os = "macos")]
 		if self.menu.is_none() && self.enable_macos_default_menu {
 			self.menu = Some(Menu::os_default(&context.package_info().name));
@@ -1298,15 +1648,24 @@ impl<R: Runtime> Builder<R> {
 		let manager = WindowManager::with_handlers(
 			context,
 			self.plugins,
-			self.invoke_handler,
-			self.on_page_load,
+			WindowManagerHooks {
+				invoke_handler: self.invoke_handler,
+				invoke_responder: self.invoke_responder,
+				invoke_initialization_script: self.invoke_initialization_script,
+				on_page_load: self.on_page_load,
+				on_binary_message: self.on_binary_message,
+				on_csp: self.on_csp
+			},
 			self.uri_scheme_protocols,
 			self.state,
 			self.window_event_listeners,
-			(self.menu, self.menu_event_listeners),
-			(self.invoke_responder, self.invoke_initialization_script)
+			(self.menu, self.menu_event_listeners)
 		);
 
+		if let Some(port) = manager.localhost_port() {
+			manager.set_asset_server(crate::asset_server::start(manager.clone(), port)?);
+		}
+
 		// set up all the windows defined in the config
 		for config in manager.config().millennium.windows.clone() {
 			let url = config.url.clone();
@@ -1322,9 +1681,13 @@ impl<R: Runtime> Builder<R> {
 		}
 
 		#[cfg(any(windows, target_os = "linux"))]
-		let runtime = if self.runtime_any_thread { R::new_any_thread()? } else { R::new()? };
+		let mut runtime = if self.runtime_any_thread { R::new_any_thread()? } else { R::new()? };
 		#[cfg(not(any(windows, target_os = "linux")))]
-		let runtime = R::new()?;
+		let mut runtime = R::new()?;
+
+		if let Some(icon) = manager.inner.default_window_icon.clone() {
+			runtime.set_default_window_icon(Some(icon.try_into()?));
+		}
 
 		let runtime_handle = runtime.handle();
 		#[cfg(feature = "global-shortcut")]
@@ -1434,6 +1797,11 @@ impl<R: Runtime> Builder<R> {
 
 		app.manager.initialize_plugins(&app.handle())?;
 
+		let proxy = app.handle.create_proxy();
+		crate::single_instance::forward_jump_list_clicks(move |id| {
+			let _ = proxy.send_event(EventLoopMessage::JumpListItemClicked(id));
+		});
+
 		let window_labels = self.pending_windows.iter().map(|p| p.label.clone()).collect::<Vec<_>>();
 
 		for pending in self.pending_windows {
@@ -1444,6 +1812,9 @@ impl<R: Runtime> Builder<R> {
 
 		(self.setup)(&mut app).map_err(|e| crate::Error::Setup(e.into()))?;
 
+		#[cfg(dev)]
+		crate::dev_reload::listen(app.handle());
+
 		#[cfg(updater)]
 		app.run_updater();
 
@@ -1496,6 +1867,11 @@ fn on_event_loop_event<R: Runtime, F: FnMut(&AppHandle<R>, RunEvent) + 'static>(
 		}
 		RuntimeRunEvent::Resumed => RunEvent::Resumed,
 		RuntimeRunEvent::MainEventsCleared => RunEvent::MainEventsCleared,
+		RuntimeRunEvent::Opened { urls } => RunEvent::Opened { urls },
+		RuntimeRunEvent::ThemeChanged(theme) => RunEvent::ThemeChanged(theme),
+		RuntimeRunEvent::AccentColorChanged(color) => RunEvent::AccentColorChanged(color),
+		RuntimeRunEvent::AccessibilityPreferencesChanged(preferences) => RunEvent::AccessibilityPreferencesChanged(preferences),
+		RuntimeRunEvent::JumpListItemClicked { id } => RunEvent::JumpListItemClicked { id },
 		RuntimeRunEvent::UserEvent(t) => t.into(),
 		_ => unimplemented!()
 	};
@@ -23,7 +23,7 @@ use millennium_macros::default_runtime;
 
 pub use crate::{
 	runtime::{
-		menu::{MenuHash, MenuId, MenuIdRef, MenuUpdate, SystemTrayMenu, SystemTrayMenuEntry, TrayHandle},
+		menu::{CustomMenuItem, MenuHash, MenuId, MenuIdRef, MenuUpdate, SystemTrayMenu, SystemTrayMenuEntry, TrayHandle},
 		window::dpi::{PhysicalPosition, PhysicalSize},
 		SystemTray
 	},
@@ -155,6 +155,25 @@ impl<R: Runtime> SystemTrayHandle<R> {
 		Ok(())
 	}
 
+	/// Inserts a custom item into the tray menu without rebuilding it from the app side.
+	///
+	/// The item is inserted into the submenu assigned the `parent` id with
+	/// [`SystemTraySubmenu::id`](crate::SystemTraySubmenu::id), or into the top level of the tray menu if `parent` is
+	/// `None`. It is placed at `index`, or appended if `index` is `None` or past the end of the menu.
+	pub fn insert_item(&self, parent: Option<MenuIdRef<'_>>, index: Option<usize>, item: CustomMenuItem) -> crate::Result<()> {
+		let (hash, id) = (item.id, item.id_str.clone());
+		self.inner.insert_item(parent.map(Into::into), index, item)?;
+		self.ids.lock().unwrap().insert(hash, id);
+		Ok(())
+	}
+
+	/// Removes the custom item with the given `id` from the tray menu.
+	pub fn remove_item(&self, id: MenuIdRef<'_>) -> crate::Result<()> {
+		self.inner.remove_item(id.into())?;
+		self.ids.lock().unwrap().retain(|_, item_id| item_id != id);
+		Ok(())
+	}
+
 	/// Support [macOS tray icon template](https://developer.apple.com/documentation/appkit/nsimage/1520017-template?language=objc) to adjust automatically based on taskbar color.
 	#[cfg(target_os = "macos")]
 	pub fn set_icon_as_template(&self, is_template: bool) -> crate::Result<()> {
@@ -0,0 +1,221 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Serves the embedded assets from `http://localhost:<port>`.
+//!
+//! WebView2, WKWebView and WebKitGTK only allow service workers to be registered from a secure HTTP(S) origin, never
+//! from a custom protocol. `http://localhost` counts as a secure context, so serving the same assets the custom
+//! protocol would serve from a loopback socket is enough to make service workers work on every platform.
+//!
+//! Any local process can connect to the socket, so requests have to carry a token generated on every launch. The
+//! webviews can't attach headers to their own requests, so windows are first pointed at a URL with the token in its
+//! query string, and the server answers with a cookie holding the token and a redirect to the same URL without it.
+
+use std::{
+	io::{self, BufRead, BufReader, Write},
+	net::{Ipv4Addr, Ipv6Addr, TcpListener, TcpStream}
+};
+
+use url::{form_urlencoded, Url};
+
+use crate::{manager::WindowManager, Runtime};
+
+/// Query parameter used to hand the token to a window when it's created.
+const TOKEN_PARAM: &str = "__millennium_token";
+/// Cookie the token is stored in once the window has been handed the token.
+const TOKEN_COOKIE: &str = "__millennium_token";
+
+/// A running asset server.
+#[derive(Debug, Clone)]
+pub(crate) struct AssetServer {
+	/// The port the server is bound to.
+	pub(crate) port: u16,
+	/// The token requests must carry, generated on every launch.
+	token: String
+}
+
+impl AssetServer {
+	/// Adds the token to a URL the webview will load, so the server can hand it to the webview as a cookie.
+	pub(crate) fn authorize(&self, url: &mut Url) {
+		url.query_pairs_mut().append_pair(TOKEN_PARAM, &self.token);
+	}
+}
+
+/// Binds the asset server to the loopback interface and serves requests on a background thread.
+///
+/// If `port` is `0`, a free port is picked. `localhost` may resolve to either `127.0.0.1` or `::1`, so both are
+/// bound; IPv6 is best-effort since it may be disabled on the machine.
+pub(crate) fn start<R: Runtime>(manager: WindowManager<R>, port: u16) -> crate::Result<AssetServer> {
+	let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+	let server = AssetServer {
+		port: listener.local_addr()?.port(),
+		token: format!("{:032x}", rand::random::<u128>())
+	};
+	serve(listener, manager.clone(), server.clone());
+	if let Ok(listener) = TcpListener::bind((Ipv6Addr::LOCALHOST, server.port)) {
+		serve(listener, manager, server.clone());
+	}
+	Ok(server)
+}
+
+fn serve<R: Runtime>(listener: TcpListener, manager: WindowManager<R>, server: AssetServer) {
+	std::thread::spawn(move || {
+		for stream in listener.incoming().flatten() {
+			let manager = manager.clone();
+			let server = server.clone();
+			std::thread::spawn(move || {
+				let _ = handle(&manager, &server, stream);
+			});
+		}
+	});
+}
+
+fn handle<R: Runtime>(manager: &WindowManager<R>, server: &AssetServer, mut stream: TcpStream) -> io::Result<()> {
+	let mut reader = BufReader::new(stream.try_clone()?);
+
+	let mut request_line = String::new();
+	reader.read_line(&mut request_line)?;
+	let mut parts = request_line.split_whitespace();
+	let (method, target) = match (parts.next(), parts.next()) {
+		(Some(method), Some(target)) => (method.to_string(), target.to_string()),
+		_ => return respond(&mut stream, "400 Bad Request", &[], b"", false)
+	};
+
+	let mut host = None;
+	let mut cookie_token = None;
+	loop {
+		let mut line = String::new();
+		if reader.read_line(&mut line)? == 0 {
+			break;
+		}
+		let line = line.trim_end();
+		if line.is_empty() {
+			break;
+		}
+		if let Some((name, value)) = line.split_once(':') {
+			if name.eq_ignore_ascii_case("host") {
+				host = Some(value.trim().to_string());
+			} else if name.eq_ignore_ascii_case("cookie") {
+				cookie_token = value
+					.split(';')
+					.filter_map(|cookie| cookie.trim().split_once('='))
+					.find(|(name, _)| *name == TOKEN_COOKIE)
+					.map(|(_, value)| value.to_string())
+					.or(cookie_token);
+			}
+		}
+	}
+
+	// only answer requests addressed to us, so a page on another origin can't read the assets through DNS rebinding
+	let host_allowed = host.map_or(false, |host| {
+		["localhost", "127.0.0.1", "[::1]"]
+			.iter()
+			.any(|name| host == format!("{}:{}", name, server.port))
+	});
+	if !host_allowed {
+		return respond(&mut stream, "403 Forbidden", &[], b"", false);
+	}
+
+	let head = match method.as_str() {
+		"GET" => false,
+		"HEAD" => true,
+		_ => return respond(&mut stream, "405 Method Not Allowed", &[("Allow", "GET, HEAD")], b"", false)
+	};
+
+	// ignore the fragment
+	let target = target.split('#').next().unwrap();
+	let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+	// a window being created hands us the token; store it in a cookie and drop it from the URL the page sees
+	let query_token = form_urlencoded::parse(query.as_bytes()).find(|(name, _)| name == TOKEN_PARAM);
+	if let Some((_, token)) = query_token {
+		if token != server.token {
+			return respond(&mut stream, "403 Forbidden", &[], b"", false);
+		}
+		let query = form_urlencoded::Serializer::new(String::new())
+			.extend_pairs(form_urlencoded::parse(query.as_bytes()).filter(|(name, _)| name != TOKEN_PARAM))
+			.finish();
+		let location = if query.is_empty() { path.to_string() } else { format!("{}?{}", path, query) };
+		let cookie = format!("{}={}; Path=/; HttpOnly; SameSite=Strict", TOKEN_COOKIE, server.token);
+		return respond(&mut stream, "303 See Other", &[("Location", &location), ("Set-Cookie", &cookie)], b"", head);
+	}
+
+	if cookie_token.as_deref() != Some(server.token.as_str()) {
+		return respond(&mut stream, "403 Forbidden", &[], b"", false);
+	}
+
+	match manager.get_asset(path.to_string()) {
+		Ok(asset) => {
+			let mut headers = vec![("Content-Type", asset.mime_type.as_str()), ("Cache-Control", "no-cache")];
+			if let Some(csp) = &asset.csp_header {
+				headers.push(("Content-Security-Policy", csp));
+			}
+			respond(&mut stream, "200 OK", &headers, &asset.bytes, head)
+		}
+		Err(_) => respond(&mut stream, "404 Not Found", &[], b"", head)
+	}
+}
+
+fn respond(stream: &mut TcpStream, status: &str, headers: &[(&str, &str)], body: &[u8], head: bool) -> io::Result<()> {
+	let mut response = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n", status, body.len());
+	for (name, value) in headers {
+		response.push_str(&format!("{}: {}\r\n", name, value));
+	}
+	response.push_str("\r\n");
+	stream.write_all(response.as_bytes())?;
+	if !head {
+		stream.write_all(body)?;
+	}
+	stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Read;
+
+	use super::*;
+	use crate::{sealed::ManagerBase, test::mock_app};
+
+	fn request(port: u16, headers: &str, target: &str) -> String {
+		let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+		write!(stream, "GET {} HTTP/1.1\r\n{}\r\n\r\n", target, headers).unwrap();
+		let mut response = String::new();
+		stream.read_to_string(&mut response).unwrap();
+		response
+	}
+
+	#[test]
+	fn requires_launch_token() {
+		let app = mock_app();
+		let server = start(app.manager().clone(), 0).unwrap();
+		let host = format!("Host: localhost:{}", server.port);
+		let cookie = format!("Cookie: theme=dark; {}={}", TOKEN_COOKIE, server.token);
+
+		assert!(request(server.port, &host, "/").starts_with("HTTP/1.1 403"));
+		assert!(request(server.port, &host, &format!("/?{}=0", TOKEN_PARAM)).starts_with("HTTP/1.1 403"));
+
+		let mut url = Url::parse(&format!("http://localhost:{}/about?page=1", server.port)).unwrap();
+		server.authorize(&mut url);
+		let response = request(server.port, &host, &url[url::Position::BeforePath..]);
+		assert!(response.starts_with("HTTP/1.1 303"));
+		assert!(response.contains("Location: /about?page=1\r\n"));
+		assert!(response.contains(&format!("Set-Cookie: {}={};", TOKEN_COOKIE, server.token)));
+
+		// the mock app has no assets, so getting a 404 means the token was accepted
+		assert!(request(server.port, &format!("{}\r\n{}", host, cookie), "/").starts_with("HTTP/1.1 404"));
+		assert!(request(server.port, &format!("Host: example.com\r\n{}", cookie), "/").starts_with("HTTP/1.1 403"));
+	}
+}
@@ -0,0 +1,121 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Live reload of a static `devPath` during `millennium dev`.
+//!
+//! When the frontend isn't served by a dev server, the CLI watches the `devPath` directory and writes a `reload` line
+//! to a TCP socket whenever an asset changes. The app serves its assets from that directory instead of the ones
+//! embedded at compile time, so reloading the windows is enough to pick up the change.
+
+use std::{
+	ffi::OsStr,
+	io::{BufRead, BufReader},
+	net::TcpStream,
+	path::{Component, Path, PathBuf}
+};
+
+use millennium_utils::{config::DisabledCspModificationKind, html};
+
+use crate::{Manager, Runtime};
+
+/// Address of the CLI's reload socket. Must match `millennium-cli`.
+const RELOAD_ADDR_ENV: &str = "MILLENNIUM_DEV_RELOAD_ADDR";
+/// Directory to serve the assets from.
+const ASSETS_DIR_ENV: &str = "MILLENNIUM_DEV_ASSETS_DIR";
+
+/// Connects to the CLI's reload socket, if the app was started by `millennium dev`, and reloads every window when asked
+/// to.
+pub(crate) fn listen<R: Runtime>(app_handle: crate::AppHandle<R>) {
+	let stream = match std::env::var(RELOAD_ADDR_ENV).ok().and_then(|addr| TcpStream::connect(addr).ok()) {
+		Some(stream) => stream,
+		None => return
+	};
+
+	std::thread::spawn(move || {
+		for line in BufReader::new(stream).lines() {
+			match line.as_deref() {
+				Ok("reload") => {
+					for window in app_handle.windows().values() {
+						let _ = window.eval("window.location.reload()");
+					}
+				}
+				Ok(_) => {}
+				Err(_) => break
+			}
+		}
+	});
+}
+
+/// Reads an asset from the `devPath` on disk, if the app was started by `millennium dev`.
+///
+/// With `csp` set, HTML files get the CSP and nonce tokens that the codegen injects into embedded HTML, so they go
+/// through the same CSP pipeline.
+pub(crate) fn asset(key: &str, csp: bool, dangerous_disable_asset_csp_modification: &DisabledCspModificationKind) -> Option<Vec<u8>> {
+	let dir = PathBuf::from(std::env::var_os(ASSETS_DIR_ENV)?);
+	let path = Path::new(key.trim_start_matches('/'));
+	// never serve files from outside the `devPath`
+	if !path.components().all(|component| matches!(component, Component::Normal(_))) {
+		return None;
+	}
+	let asset = std::fs::read(dir.join(path)).ok()?;
+	if csp && path.extension() == Some(OsStr::new("html")) {
+		Some(inject_csp_tokens(&asset, dangerous_disable_asset_csp_modification))
+	} else {
+		Some(asset)
+	}
+}
+
+fn inject_csp_tokens(asset: &[u8], dangerous_disable_asset_csp_modification: &DisabledCspModificationKind) -> Vec<u8> {
+	let mut document = html::parse(String::from_utf8_lossy(asset).into_owned());
+
+	#[cfg(target_os = "linux")]
+	html::inject_csp_token(&mut document);
+
+	html::inject_nonce_token(&mut document, dangerous_disable_asset_csp_modification);
+	// the codegen allows inline scripts by their hash, which would be stale as soon as the file changes
+	html::inject_inline_script_nonce_token(&mut document, dangerous_disable_asset_csp_modification);
+	html::inject_csp_nonce_token(&mut document);
+
+	document.to_string().into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+	use millennium_utils::{config::DisabledCspModificationKind, html};
+
+	use super::inject_csp_tokens;
+
+	#[test]
+	fn injects_csp_tokens_into_disk_html() {
+		let html = r#"<html><head><style>body {}</style></head><body><script>console.log(1)</script><script src="main.js"></script></body></html>"#;
+		let html = String::from_utf8(inject_csp_tokens(html.as_bytes(), &DisabledCspModificationKind::Flag(false))).unwrap();
+
+		assert!(html.contains(&format!(r#"<script nonce="{}">console.log(1)</script>"#, html::SCRIPT_NONCE_TOKEN)));
+		assert!(html.contains(r#"<script src="main.js"></script>"#));
+		assert!(html.contains(&format!(r#"<style nonce="{}">"#, html::STYLE_NONCE_TOKEN)));
+		assert!(html.contains(html::CSP_NONCE_TOKEN));
+		#[cfg(target_os = "linux")]
+		assert!(html.contains(html::CSP_TOKEN));
+	}
+
+	#[test]
+	fn respects_disabled_csp_modification() {
+		let html = "<html><head></head><body><script>console.log(1)</script></body></html>";
+		let html = String::from_utf8(inject_csp_tokens(html.as_bytes(), &DisabledCspModificationKind::Flag(true))).unwrap();
+
+		assert!(!html.contains(html::SCRIPT_NONCE_TOKEN));
+	}
+}
@@ -37,6 +37,7 @@ use super::InvokeContext;
 use crate::{
 	api::{
 		dir,
+		ipc::CallbackFn,
 		file::{self, SafePathBuf},
 		path::BaseDirectory
 	},
@@ -66,6 +67,80 @@ pub struct FileOperationOptions {
 	pub dir: Option<BaseDirectory>
 }
 
+type WatcherId = u32;
+#[cfg(fs_watch)]
+type WatcherStore = std::sync::Mutex<std::collections::HashMap<WatcherId, FileWatcher>>;
+
+#[cfg(fs_watch)]
+struct FileWatcher {
+	window_label: String,
+	listener: uuid::Uuid,
+	// dropping the watcher closes its event channel, which ends the forwarding thread
+	_watcher: notify::RecommendedWatcher
+}
+
+#[cfg(fs_watch)]
+fn file_watchers() -> &'static WatcherStore {
+	use once_cell::sync::Lazy;
+	static STORE: Lazy<WatcherStore> = Lazy::new(Default::default);
+	&STORE
+}
+
+/// The options for the watch function on the file system API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchOptions {
+	/// Whether changes in subdirectories should be reported as well.
+	#[serde(default)]
+	pub recursive: bool,
+	/// Time in milliseconds to wait for further changes before emitting a batch of events.
+	/// Events are emitted as soon as they happen if not set.
+	pub debounce: Option<u64>,
+	/// The base directory of the operation.
+	/// The directory path of the BaseDirectory will be the prefix of the
+	/// defined path.
+	pub dir: Option<BaseDirectory>
+}
+
+/// A change reported by the watch API.
+#[cfg(fs_watch)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct WatchEvent {
+	kind: WatchEventKind,
+	#[serde(serialize_with = "serialize_paths_lossy")]
+	paths: Vec<std::path::PathBuf>
+}
+
+/// Serializes `paths` as strings, replacing invalid UTF-8 instead of failing like `PathBuf`'s implementation.
+#[cfg(fs_watch)]
+fn serialize_paths_lossy<S: serde::Serializer>(paths: &[std::path::PathBuf], serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.collect_seq(paths.iter().map(|path| path.to_string_lossy()))
+}
+
+#[cfg(fs_watch)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum WatchEventKind {
+	Created,
+	Modified,
+	Removed,
+	Renamed
+}
+
+#[cfg(fs_watch)]
+impl WatchEvent {
+	fn from_notify(event: notify::Event) -> Option<Self> {
+		use notify::event::{EventKind, ModifyKind};
+		let kind = match event.kind {
+			EventKind::Create(_) => WatchEventKind::Created,
+			EventKind::Modify(ModifyKind::Name(_)) => WatchEventKind::Renamed,
+			EventKind::Modify(_) => WatchEventKind::Modified,
+			EventKind::Remove(_) => WatchEventKind::Removed,
+			_ => return None
+		};
+		Some(Self { kind, paths: event.paths })
+	}
+}
+
 /// The API descriptor.
 #[command_enum]
 #[derive(Deserialize, CommandModule)]
@@ -84,6 +159,13 @@ pub(crate) enum Cmd {
 		contents: Vec<u8>,
 		options: Option<FileOperationOptions>
 	},
+	/// The atomic write file API.
+	#[cmd(fs_write_file, "fs > writeFile")]
+	WriteFileAtomic {
+		path: SafePathBuf,
+		contents: Vec<u8>,
+		options: Option<FileOperationOptions>
+	},
 	/// The read dir API.
 	#[cmd(fs_read_dir, "fs > readDir")]
 	ReadDir { path: SafePathBuf, options: Option<DirOperationOptions> },
@@ -103,6 +185,9 @@ pub(crate) enum Cmd {
 	/// The remove file API.
 	#[cmd(fs_remove_file, "fs > removeFile")]
 	RemoveFile { path: SafePathBuf, options: Option<FileOperationOptions> },
+	/// The move to trash API.
+	#[cmd(fs_remove_file, "fs > removeFile")]
+	MoveToTrash { path: SafePathBuf, options: Option<FileOperationOptions> },
 	/// The rename API.
 	#[cmd(fs_rename, "fs > rename")]
 	#[serde(rename_all = "camelCase")]
@@ -113,7 +198,18 @@ pub(crate) enum Cmd {
 	},
 	/// The file exists API.
 	#[cmd(fs_exists, "fs > exists")]
-	Exists { path: SafePathBuf, options: Option<FileOperationOptions> }
+	Exists { path: SafePathBuf, options: Option<FileOperationOptions> },
+	/// The watch API.
+	#[cmd(fs_watch, "fs > watch")]
+	#[serde(rename_all = "camelCase")]
+	Watch {
+		path: SafePathBuf,
+		options: Option<WatchOptions>,
+		on_event_fn: CallbackFn
+	},
+	/// The unwatch API.
+	#[cmd(fs_watch, "fs > watch")]
+	Unwatch { id: WatcherId }
 }
 
 impl Cmd {
@@ -142,6 +238,19 @@ impl Cmd {
 			.and_then(|mut f| f.write_all(&contents).map_err(|err| err.into()))
 	}
 
+	#[module_command_handler(fs_write_file)]
+	fn write_file_atomic<R: Runtime>(
+		context: InvokeContext<R>,
+		path: SafePathBuf,
+		contents: Vec<u8>,
+		options: Option<FileOperationOptions>
+	) -> super::Result<()> {
+		let resolved_path = resolve_path(&context.config, &context.package_info, &context.window, path, options.and_then(|o| o.dir))?;
+		file::write_atomic(&resolved_path, &contents)
+			.with_context(|| format!("path: {}", resolved_path.display()))
+			.map_err(Into::into)
+	}
+
 	#[module_command_handler(fs_read_dir)]
 	fn read_dir<R: Runtime>(context: InvokeContext<R>, path: SafePathBuf, options: Option<DirOperationOptions>) -> super::Result<Vec<dir::DiskEntry>> {
 		let (recursive, dir) = if let Some(options_value) = options {
@@ -214,6 +323,18 @@ impl Cmd {
 		Ok(())
 	}
 
+	#[module_command_handler(fs_remove_file)]
+	fn move_to_trash<R: Runtime>(
+		context: InvokeContext<R>,
+		path: SafePathBuf,
+		options: Option<FileOperationOptions>
+	) -> super::Result<Option<std::path::PathBuf>> {
+		let resolved_path = resolve_path(&context.config, &context.package_info, &context.window, path, options.and_then(|o| o.dir))?;
+		file::move_to_trash(&resolved_path)
+			.with_context(|| format!("path: {}", resolved_path.display()))
+			.map_err(Into::into)
+	}
+
 	#[module_command_handler(fs_rename)]
 	fn rename<R: Runtime>(context: InvokeContext<R>, old_path: SafePathBuf, new_path: SafePathBuf, options: Option<FileOperationOptions>) -> super::Result<()> {
 		let (old, new) = match options.and_then(|o| o.dir) {
@@ -233,6 +354,98 @@ impl Cmd {
 		let resolved_path = resolve_path(&context.config, &context.package_info, &context.window, path, options.and_then(|o| o.dir))?;
 		Ok(fs::metadata(&resolved_path).is_ok())
 	}
+
+	#[module_command_handler(fs_watch)]
+	fn watch<R: Runtime>(context: InvokeContext<R>, path: SafePathBuf, options: Option<WatchOptions>, on_event_fn: CallbackFn) -> super::Result<WatcherId> {
+		use std::{
+			sync::{
+				atomic::{AtomicU32, Ordering},
+				mpsc::{channel, RecvTimeoutError}
+			},
+			time::{Duration, Instant}
+		};
+
+		use notify::{RecursiveMode, Watcher};
+
+		static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+		let (recursive, debounce, dir) = if let Some(options_value) = options {
+			(options_value.recursive, options_value.debounce, options_value.dir)
+		} else {
+			(false, None, None)
+		};
+		let resolved_path = resolve_path(&context.config, &context.package_info, &context.window, path, dir)?;
+
+		let (tx, rx) = channel();
+		let mut watcher = notify::recommended_watcher(tx).with_context(|| format!("path: {}", resolved_path.display()))?;
+		let mode = if recursive { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+		watcher.watch(resolved_path.as_ref(), mode).with_context(|| format!("path: {}", resolved_path.display()))?;
+
+		let window = context.window.clone();
+		std::thread::spawn(move || {
+			while let Ok(event) = rx.recv() {
+				let mut events = Vec::new();
+				let mut push = |event: notify::Result<notify::Event>| {
+					if let Some(mut event) = event.ok().and_then(WatchEvent::from_notify) {
+						// the scope may have changed since the watch started, and recursive watches report paths below
+						// the watched one, so every path is checked again
+						let scope = &window.state::<Scopes>().fs;
+						event.paths.retain(|p| scope.is_allowed(p));
+						if !event.paths.is_empty() && !events.contains(&event) {
+							events.push(event);
+						}
+					}
+				};
+				push(event);
+
+				if let Some(debounce) = debounce {
+					let deadline = Instant::now() + Duration::from_millis(debounce);
+					loop {
+						match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+							Ok(event) => push(event),
+							Err(RecvTimeoutError::Timeout) => break,
+							Err(RecvTimeoutError::Disconnected) => return
+						}
+					}
+				}
+
+				if !events.is_empty() {
+					let js = crate::api::ipc::format_callback(on_event_fn, &events).expect("unable to serialize WatchEvent");
+					let _ = window.eval(js.as_str());
+				}
+			}
+		});
+
+		let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+		let listener = context.window.on_window_event(move |event| {
+			if let crate::WindowEvent::Destroyed = event {
+				file_watchers().lock().unwrap().remove(&id);
+			}
+		});
+		file_watchers().lock().unwrap().insert(
+			id,
+			FileWatcher {
+				window_label: context.window.label().to_string(),
+				listener,
+				_watcher: watcher
+			}
+		);
+
+		Ok(id)
+	}
+
+	#[module_command_handler(fs_watch)]
+	fn unwatch<R: Runtime>(context: InvokeContext<R>, id: WatcherId) -> super::Result<()> {
+		let mut watchers = file_watchers().lock().unwrap();
+		// a window can only stop its own watchers
+		if matches!(watchers.get(&id), Some(watcher) if watcher.window_label == context.window.label()) {
+			if let Some(watcher) = watchers.remove(&id) {
+				drop(watchers);
+				context.window.remove_window_event_listener(watcher.listener);
+			}
+		}
+		Ok(())
+	}
 }
 
 #[allow(dead_code)]
@@ -263,7 +476,8 @@ fn resolve_path<R: Runtime>(
 mod tests {
 	use quickcheck::{Arbitrary, Gen};
 
-	use super::{BaseDirectory, DirOperationOptions, FileOperationOptions, SafePathBuf};
+	use super::{BaseDirectory, DirOperationOptions, FileOperationOptions, SafePathBuf, WatchOptions, WatcherId};
+	use crate::api::ipc::CallbackFn;
 
 	impl Arbitrary for BaseDirectory {
 		fn arbitrary(g: &mut Gen) -> Self {
@@ -286,6 +500,16 @@ mod tests {
 		}
 	}
 
+	impl Arbitrary for WatchOptions {
+		fn arbitrary(g: &mut Gen) -> Self {
+			Self {
+				recursive: bool::arbitrary(g),
+				debounce: Option::arbitrary(g),
+				dir: Option::arbitrary(g)
+			}
+		}
+	}
+
 	#[millennium_macros::module_command_test(fs_read_file, "fs > readFile")]
 	#[quickcheck_macros::quickcheck]
 	fn read_file(path: SafePathBuf, options: Option<FileOperationOptions>) {
@@ -300,6 +524,13 @@ mod tests {
 		crate::test_utils::assert_not_allowlist_error(res);
 	}
 
+	#[millennium_macros::module_command_test(fs_write_file, "fs > writeFile")]
+	#[quickcheck_macros::quickcheck]
+	fn write_file_atomic(path: SafePathBuf, contents: Vec<u8>, options: Option<FileOperationOptions>) {
+		let res = super::Cmd::write_file_atomic(crate::test::mock_invoke_context(), path, contents, options);
+		crate::test_utils::assert_not_allowlist_error(res);
+	}
+
 	#[millennium_macros::module_command_test(fs_read_dir, "fs > readDir")]
 	#[quickcheck_macros::quickcheck]
 	fn read_dir(path: SafePathBuf, options: Option<DirOperationOptions>) {
@@ -335,6 +566,13 @@ mod tests {
 		crate::test_utils::assert_not_allowlist_error(res);
 	}
 
+	#[millennium_macros::module_command_test(fs_remove_file, "fs > removeFile")]
+	#[quickcheck_macros::quickcheck]
+	fn move_to_trash(path: SafePathBuf, options: Option<FileOperationOptions>) {
+		let res = super::Cmd::move_to_trash(crate::test::mock_invoke_context(), path, options);
+		crate::test_utils::assert_not_allowlist_error(res);
+	}
+
 	#[millennium_macros::module_command_test(fs_rename, "fs > rename")]
 	#[quickcheck_macros::quickcheck]
 	fn rename(old_path: SafePathBuf, new_path: SafePathBuf, options: Option<FileOperationOptions>) {
@@ -348,4 +586,41 @@ mod tests {
 		let res = super::Cmd::exists(crate::test::mock_invoke_context(), path, options);
 		crate::test_utils::assert_not_allowlist_error(res);
 	}
+
+	#[millennium_macros::module_command_test(fs_watch, "fs > watch")]
+	#[quickcheck_macros::quickcheck]
+	fn watch(path: SafePathBuf, options: Option<WatchOptions>, on_event_fn: CallbackFn) {
+		let res = super::Cmd::watch(crate::test::mock_invoke_context(), path, options, on_event_fn);
+		crate::test_utils::assert_not_allowlist_error(res);
+	}
+
+	#[millennium_macros::module_command_test(fs_watch, "fs > watch")]
+	#[quickcheck_macros::quickcheck]
+	fn unwatch(id: WatcherId) {
+		let res = super::Cmd::unwatch(crate::test::mock_invoke_context(), id);
+		crate::test_utils::assert_not_allowlist_error(res);
+	}
+
+	#[cfg(fs_watch)]
+	#[test]
+	fn watch_rejects_paths_outside_of_the_scope() {
+		// the mock app's scope is empty, so any existing directory is out of it
+		let directory = tempfile::tempdir().unwrap();
+		let path = SafePathBuf::new(directory.path().to_path_buf()).unwrap();
+		let err = super::Cmd::watch(crate::test::mock_invoke_context(), path, None, CallbackFn(0)).unwrap_err();
+		assert!(err.to_string().contains("path not allowed on the configured scope"));
+	}
+
+	#[cfg(all(fs_watch, unix))]
+	#[test]
+	fn serializes_non_utf8_watch_paths() {
+		use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::PathBuf};
+
+		let event = super::WatchEvent {
+			kind: super::WatchEventKind::Created,
+			paths: vec![PathBuf::from(OsStr::from_bytes(b"/tmp/invalid-\xff"))]
+		};
+		let js = crate::api::ipc::format_callback(CallbackFn(0), &[event]).unwrap();
+		assert!(js.contains("/tmp/invalid-\u{fffd}"));
+	}
 }
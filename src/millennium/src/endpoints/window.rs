@@ -28,7 +28,7 @@ use crate::{
 		UserAttentionType
 	},
 	utils::config::WindowConfig,
-	CursorIcon, Icon, Manager, Runtime
+	CursorIcon, Icon, Manager, ResizeDirection, Runtime
 };
 
 #[derive(Deserialize)]
@@ -76,6 +76,9 @@ pub enum WindowManagerCmd {
 	PrimaryMonitor,
 	AvailableMonitors,
 	Theme,
+	AccentColor,
+	AccessibilityPreferences,
+	MemoryUsage,
 	// Setters
 	#[cfg(window_center)]
 	Center,
@@ -134,12 +137,19 @@ pub enum WindowManagerCmd {
 	SetCursorPosition(Position),
 	#[cfg(window_start_dragging)]
 	StartDragging,
+	#[cfg(window_start_dragging)]
+	StartResizeDragging(ResizeDirection),
+	#[cfg(window_start_drag)]
+	StartDrag {
+		paths: Vec<std::path::PathBuf>,
+		icon: Option<IconDto>
+	},
 	#[cfg(window_print)]
 	Print,
 	// internals
 	#[cfg(all(window_maximize, window_unmaximize))]
-	#[serde(rename = "__toggleMaximize")]
-	InternalToggleMaximize,
+	#[serde(rename = "__titlebarDoubleClick")]
+	InternalTitlebarDoubleClick,
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	#[serde(rename = "__toggleDevtools")]
 	InternalToggleDevtools
@@ -172,9 +182,10 @@ pub fn into_allowlist_error(variant: &str) -> crate::Error {
 		"setCursorVisible" => crate::Error::ApiNotAllowlisted("window > setCursorVisible".to_string()),
 		"setCursorIcon" => crate::Error::ApiNotAllowlisted("window > setCursorIcon".to_string()),
 		"setCursorPosition" => crate::Error::ApiNotAllowlisted("window > setCursorPosition".to_string()),
-		"startDragging" => crate::Error::ApiNotAllowlisted("window > startDragging".to_string()),
+		"startDragging" | "startResizeDragging" => crate::Error::ApiNotAllowlisted("window > startDragging".to_string()),
+		"startDrag" => crate::Error::ApiNotAllowlisted("window > startDrag".to_string()),
 		"print" => crate::Error::ApiNotAllowlisted("window > print".to_string()),
-		"internalToggleMaximize" => crate::Error::ApiNotAllowlisted("window > maximize and window > unmaximize".to_string()),
+		"internalTitlebarDoubleClick" => crate::Error::ApiNotAllowlisted("window > maximize and window > unmaximize".to_string()),
 		_ => crate::Error::ApiNotAllowlisted("window".to_string())
 	}
 }
@@ -238,6 +249,9 @@ impl Cmd {
 			WindowManagerCmd::PrimaryMonitor => return Ok(window.primary_monitor()?.into()),
 			WindowManagerCmd::AvailableMonitors => return Ok(window.available_monitors()?.into()),
 			WindowManagerCmd::Theme => return Ok(window.theme()?.into()),
+			WindowManagerCmd::AccentColor => return Ok(window.accent_color()?.into()),
+			WindowManagerCmd::AccessibilityPreferences => return Ok(window.accessibility_preferences()?.into()),
+			WindowManagerCmd::MemoryUsage => return Ok(window.memory_usage()?.into()),
 			// Setters
 			#[cfg(window_center)]
 			WindowManagerCmd::Center => window.center()?,
@@ -296,18 +310,21 @@ impl Cmd {
 			WindowManagerCmd::SetCursorPosition(position) => window.set_cursor_position(position)?,
 			#[cfg(window_start_dragging)]
 			WindowManagerCmd::StartDragging => window.start_dragging()?,
+			#[cfg(window_start_dragging)]
+			WindowManagerCmd::StartResizeDragging(direction) => window.start_resize_dragging(direction)?,
+			#[cfg(window_start_drag)]
+			WindowManagerCmd::StartDrag { paths, icon } => {
+				// only files the webview could read through the `fs` API may be dragged out
+				if let Some(path) = paths.iter().find(|path| !window.state::<crate::scope::Scopes>().fs.is_allowed(path)) {
+					return Err(crate::Error::PathNotAllowed(path.clone()));
+				}
+				window.start_drag(paths, icon.map(Into::into))?
+			}
 			#[cfg(window_print)]
 			WindowManagerCmd::Print => window.print()?,
 			// internals
 			#[cfg(all(window_maximize, window_unmaximize))]
-			WindowManagerCmd::InternalToggleMaximize => {
-				if window.is_resizable()? {
-					match window.is_maximized()? {
-						true => window.unmaximize()?,
-						false => window.maximize()?
-					}
-				}
-			}
+			WindowManagerCmd::InternalTitlebarDoubleClick => window.titlebar_double_click()?,
 			#[cfg(any(debug_assertions, feature = "devtools"))]
 			WindowManagerCmd::InternalToggleDevtools => {
 				if window.is_devtools_open() {
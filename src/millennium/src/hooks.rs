@@ -14,7 +14,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{future::Future, sync::Arc};
+use std::{collections::HashMap, future::Future, sync::Arc};
 
 use millennium_macros::default_runtime;
 use serde::{Deserialize, Serialize};
@@ -24,6 +24,7 @@ use serialize_to_javascript::{default_template, Template};
 use crate::{
 	api::ipc::{format_callback, format_callback_result, CallbackFn},
 	app::App,
+	utils::config::CspDirectiveSources,
 	Runtime, StateManager, Window
 };
 
@@ -40,6 +41,14 @@ pub type InvokeResponder<R> = dyn Fn(Window<R>, InvokeResponse, CallbackFn, Call
 /// A closure that is run once every time a window is created and loaded.
 pub type OnPageLoad<R> = dyn Fn(Window<R>, PageLoadPayload) + Send + Sync + 'static;
 
+/// A closure that is run every time a window sends a binary message with
+/// `window.ipc.postBinaryMessage`.
+pub type OnBinaryMessage<R> = dyn Fn(Window<R>, Vec<u8>) + Send + Sync + 'static;
+
+/// A closure that is run every time an HTML page is served by the `millennium` protocol, to modify its
+/// Content-Security-Policy.
+pub type OnCsp = dyn Fn(&CspPayload, &mut HashMap<String, CspDirectiveSources>) + Send + Sync + 'static;
+
 // todo: why is this derive broken but the output works manually?
 #[derive(Template)]
 #[default_template("../scripts/ipc.js")]
@@ -69,6 +78,31 @@ impl PageLoadPayload {
 	}
 }
 
+/// The payload for the [`OnCsp`] hook.
+#[derive(Debug, Clone)]
+pub struct CspPayload {
+	pub(crate) path: String,
+	pub(crate) nonce: String
+}
+
+impl CspPayload {
+	/// The path of the HTML asset being served, e.g. `/index.html`.
+	pub fn path(&self) -> &str {
+		&self.path
+	}
+
+	/// The nonce generated for this page load. It replaces `__MILLENNIUM_CSP_NONCE_TOKEN__` in the HTML and is
+	/// available to scripts as `window.__MILLENNIUM_CSP_NONCE__`.
+	pub fn nonce(&self) -> &str {
+		&self.nonce
+	}
+
+	/// The nonce as a CSP source expression, `'nonce-<nonce>'`.
+	pub fn nonce_source(&self) -> String {
+		format!("'nonce-{}'", self.nonce)
+	}
+}
+
 /// The payload used on the IPC invoke.
 #[derive(Debug, Deserialize)]
 pub struct InvokePayload {
@@ -51,6 +51,7 @@
 //! - **process-relaunch-dangerous-allow-symlink-macos**: Allows the [`api::process::current_binary`] function to allow
 //!   symlinks on macOS. **This is dangerous**, see the Security section in the function's documentation.
 //! - **dialog**: Enables the [`api::dialog`] module.
+//! - **panic-dialog**: Enables [`Builder::panic_dialog`], which reports panics to the user with a native error dialog.
 //! - **notification**: Enables the [`api::notification`] module.
 //! - **fs-extract-api**: Enables the [`api::file::Extract`] API.
 //! - **cli**: Enables usage of `clap` for CLI argument parsing. Enabled by default if the `cli` config is defined on
@@ -101,6 +102,7 @@
 //! - **fs-remove-dir**: Enables the [`removeDir` API](https://tauri.studio/en/docs/api/js/modules/fs#removedir).
 //! - **fs-remove-file**: Enables the [`removeFile` API](https://tauri.studio/en/docs/api/js/modules/fs#removefile).
 //! - **fs-rename-file**: Enables the [`renameFile` API](https://tauri.studio/en/docs/api/js/modules/fs#renamefile).
+//! - **fs-watch**: Enables the [`watch` API](https://tauri.studio/en/docs/api/js/modules/fs#watch).
 //! - **fs-write-file**: Enables the [`writeFile` API](https://tauri.studio/en/docs/api/js/modules/fs#writefile) and the
 //!   [`writeBinaryFile` API](https://tauri.studio/en/docs/api/js/modules/fs#writebinaryfile).
 //!
@@ -172,7 +174,9 @@
 //! - **window-set-cursor-visible**: Enables the [`setCursorVisible` API](https://tauri.studio/en/docs/api/js/classes/window.WebviewWindow#setcursorvisible).
 //! - **window-set-cursor-icon**: Enables the [`setCursorIcon` API](https://tauri.studio/en/docs/api/js/classes/window.WebviewWindow#setcursoricon).
 //! - **window-set-cursor-position**: Enables the [`setCursorPosition` API](https://tauri.studio/en/docs/api/js/classes/window.WebviewWindow#setcursorposition).
-//! - **window-start-dragging**: Enables the [`startDragging` API](https://tauri.studio/en/docs/api/js/classes/window.WebviewWindow#startdragging).
+//! - **window-start-dragging**: Enables the [`startDragging` API](https://tauri.studio/en/docs/api/js/classes/window.WebviewWindow#startdragging) and the
+//!   `startResizeDragging` API.
+//! - **window-start-drag**: Enables the `startDrag` API, which drags files out of the window.
 //! - **window-print**: Enables the [`print` API](https://tauri.studio/en/docs/api/js/classes/window.WebviewWindow#print).
 
 #![warn(missing_docs, rust_2018_idioms)]
@@ -190,16 +194,22 @@ pub use regex;
 
 pub mod api;
 pub(crate) mod app;
+mod asset_server;
 pub mod async_runtime;
 pub mod command;
+#[cfg(dev)]
+mod dev_reload;
 /// The Millennium API endpoints.
 mod endpoints;
 mod error;
 mod event;
 mod hooks;
 mod manager;
+#[cfg(feature = "panic-dialog")]
+mod panic_dialog;
 mod pattern;
 pub mod plugin;
+mod single_instance;
 pub mod window;
 use millennium_runtime as runtime;
 /// The allowlist scopes.
@@ -221,9 +231,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// A task to run on the main thread.
 pub type SyncTask = Box<dyn FnOnce() + Send>;
 
-use std::{collections::HashMap, fmt, path::PathBuf, sync::Arc};
+use std::{collections::HashMap, fmt, sync::Arc};
 
-use interprocess::local_socket::*;
 /// Reads the config file at compile time and generates a [`Context`] based on
 /// its content.
 ///
@@ -247,7 +256,8 @@ pub use millennium_macros::generate_context;
 pub use runtime::http;
 #[cfg(target_os = "macos")]
 #[cfg_attr(doc_cfg, doc(cfg(target_os = "macos")))]
-pub use runtime::{menu::NativeImage, ActivationPolicy};
+pub use runtime::menu::NativeImage;
+pub use runtime::ActivationPolicy;
 use serde::Serialize;
 #[cfg(feature = "system-tray")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "system-tray")))]
@@ -261,29 +271,33 @@ pub use {
 pub use {
 	self::app::WindowMenuEvent,
 	self::event::{Event, EventHandler},
-	self::runtime::menu::{AboutMetadata, CustomMenuItem, Menu, MenuEntry, MenuItem, Submenu},
+	self::runtime::menu::{AboutMetadata, CustomMenuItem, Menu, MenuEntry, MenuItem, MenuItemKind, Submenu},
 	self::window::menu::MenuEvent
 };
 pub use {
 	self::app::{App, AppHandle, AssetResolver, Builder, CloseRequestApi, GlobalWindowEvent, PathResolver, RunEvent, WindowEvent},
 	self::hooks::{
-		Invoke, InvokeError, InvokeHandler, InvokeMessage, InvokePayload, InvokeResolver, InvokeResponder, InvokeResponse, OnPageLoad, PageLoadPayload,
-		SetupHook
+		CspPayload, Invoke, InvokeError, InvokeHandler, InvokeMessage, InvokePayload, InvokeResolver, InvokeResponder, InvokeResponse, OnBinaryMessage, OnCsp, OnPageLoad,
+		PageLoadPayload, SetupHook
 	},
 	self::manager::Asset,
+	self::single_instance::request_single_instance_lock,
 	self::runtime::{
-		webview::WebviewAttributes,
+		webview::{
+			CookiePersistence, CssId, DevtoolsPosition, InitScriptId, PdfOrientation, PdfSettings, PermissionKind, PermissionResponse, ScrollBarStyle,
+			WebviewAttributes
+		},
 		window::{
 			dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Pixel, Position, Size},
-			CursorIcon, FileDropEvent
+			AccessibilityPreferences, CursorIcon, FileDropEvent, Rect, ResizeDirection
 		},
-		RunIteration, UserAttentionType
+		JumpListItem, RunIteration, RuntimeCapabilities, UserAttentionType
 	},
 	self::state::{State, StateManager},
 	self::utils::{
 		assets::Assets,
 		config::{Config, WindowUrl},
-		Env, PackageInfo, Theme
+		Env, LayoutDirection, PackageInfo, Theme
 	},
 	self::window::{Monitor, Window, WindowBuilder},
 	scope::*
@@ -346,6 +360,8 @@ impl UpdaterEvent {
 /// The user event type.
 #[derive(Debug, Clone)]
 pub enum EventLoopMessage {
+	/// An item of the jump list was clicked in a second instance of the app.
+	JumpListItemClicked(String),
 	/// Updater event.
 	#[cfg(updater)]
 	#[cfg_attr(doc_cfg, doc(cfg(feature = "updater")))]
@@ -812,50 +828,6 @@ pub(crate) mod sealed {
 	}
 }
 
-/// Requests a single instance lock for the application. This can be used to ensure that only one instance of your app
-/// is open at a time.
-///
-/// Returns `Ok(true)` if the lock was acquired (this is the first instance of the app), and `Ok(false)` if the lock was
-/// not acquired (this is the second instance of the app).
-///
-/// `on_second_instance` will be called when a second instance of the app is launched with the instance's command line
-/// arguments, current working directory, and process ID.
-pub fn request_single_instance_lock<F>(id: &str, on_second_instance: F) -> Result<bool>
-where
-	F: Fn(Vec<&str>, PathBuf, u32) + Send + 'static
-{
-	use std::io::{prelude::*, BufReader};
-
-	#[cfg(target_os = "windows")]
-	let path: String = id.into();
-	#[cfg(not(target_os = "windows"))]
-	let path = format!("/tmp/{}.lock", id);
-
-	let _path = path.clone();
-	if let Ok(listener) = LocalSocketListener::bind(_path) {
-		std::thread::spawn(move || {
-			for conn in listener.incoming().flatten() {
-				let mut conn = BufReader::new(conn);
-				let mut buffer = String::new();
-				conn.read_line(&mut buffer).unwrap();
-
-				let mut args = buffer.split('\r').collect::<Vec<_>>();
-				let pid: u32 = args.pop().unwrap().trim_end().parse().unwrap();
-				let cwd = args.pop().unwrap();
-				on_second_instance(args, PathBuf::from(cwd), pid);
-			}
-		});
-		Ok(true)
-	} else {
-		let mut conn = LocalSocketStream::connect(path)?;
-		let args = std::env::args().collect::<Vec<_>>();
-		let cwd = std::env::current_dir()?;
-		let pid = std::process::id();
-		conn.write_all(format!("{}\r{}\r{}\n", args.join("\r"), cwd.to_str().unwrap(), pid).as_bytes())?;
-		Ok(false)
-	}
-}
-
 /// Returns `true` if the application is running under ARM64 translation.
 ///
 /// # Platform-specific
@@ -28,7 +28,7 @@ use millennium_utils::pattern::isolation::RawIsolationPayload;
 use millennium_utils::{
 	assets::{AssetKey, CspHash},
 	config::{Csp, CspDirectiveSources},
-	html::{SCRIPT_NONCE_TOKEN, STYLE_NONCE_TOKEN}
+	html::{CSP_NONCE_TOKEN, SCRIPT_NONCE_TOKEN, STYLE_NONCE_TOKEN}
 };
 use serde::Serialize;
 use serde_json::Value as JsonValue;
@@ -36,19 +36,20 @@ use serialize_to_javascript::{default_template, DefaultTemplate, Template};
 use url::Url;
 
 #[cfg(any(target_os = "linux", target_os = "windows"))]
-use crate::api::path::{resolve_path, BaseDirectory};
+use crate::api::path::webview_data_dir;
 #[cfg(feature = "isolation")]
 use crate::hooks::IsolationJavascript;
 use crate::{
 	app::{AppHandle, GlobalMenuEventListener, GlobalWindowEvent, GlobalWindowEventListener, WindowMenuEvent},
+	asset_server::AssetServer,
 	event::{assert_event_name_is_valid, Event, EventHandler, Listeners},
-	hooks::{InvokeHandler, InvokePayload, InvokeResponder, IpcJavascript, OnPageLoad, PageLoadPayload},
+	hooks::{CspPayload, InvokeHandler, InvokePayload, InvokeResponder, IpcJavascript, OnBinaryMessage, OnCsp, OnPageLoad, PageLoadPayload},
 	pattern::{format_real_schema, PatternJavascript},
 	plugin::PluginStore,
 	runtime::{
 		http::{MimeType, Request as HttpRequest, Response as HttpResponse, ResponseBuilder as HttpResponseBuilder},
 		menu::Menu,
-		webview::{WebviewIpcHandler, WindowBuilder},
+		webview::{WebviewBinaryIpcHandler, WebviewIpcHandler, WindowBuilder},
 		window::{dpi::PhysicalSize, DetachedWindow, FileDropEvent, PendingWindow}
 	},
 	utils::{
@@ -68,6 +69,8 @@ const WINDOW_FOCUS_EVENT: &str = "millennium://focus";
 const WINDOW_BLUR_EVENT: &str = "millennium://blur";
 const WINDOW_SCALE_FACTOR_CHANGED_EVENT: &str = "millennium://scale-change";
 const WINDOW_THEME_CHANGED: &str = "millennium://theme-changed";
+const WINDOW_SUSPEND_EVENT: &str = "millennium://suspend";
+const WINDOW_RESUME_EVENT: &str = "millennium://resume";
 const MENU_EVENT: &str = "millennium://menu";
 
 #[derive(Default)]
@@ -77,6 +80,18 @@ struct CspHashStrings {
 	style: Vec<String>
 }
 
+/// Resolves the mime type of a file served by the asset protocol, sniffing the
+/// file contents when the path has no extension to guess from.
+#[cfg(protocol_asset)]
+fn asset_mime_type(data: &[u8], path: &str) -> String {
+	if std::path::Path::new(path).extension().is_none() {
+		if let Some(mime_type) = MimeType::parse_from_bytes(data) {
+			return mime_type.to_string();
+		}
+	}
+	MimeType::parse(data, path)
+}
+
 /// Sets the CSP value to the asset HTML if needed (on Linux).
 /// Returns the CSP string for access on the response header (on Windows and
 /// macOS).
@@ -113,6 +128,22 @@ fn set_csp<R: Runtime>(asset: &mut String, assets: Arc<dyn Assets>, asset_path:
 		default_src.push(format_real_schema(schema));
 	}
 
+	// binary IPC messages are sent with `fetch` on platforms other than Linux
+	if manager.inner.on_binary_message.is_some() {
+		let schema = format_real_schema("millennium-ipc");
+		if let Some(connect_src) = csp.get_mut("connect-src") {
+			connect_src.push(&schema);
+		}
+		csp.entry("default-src".into()).or_insert_with(Default::default).push(schema);
+	}
+
+	// one nonce per page load, which scripts read from the `csp-nonce` meta tag
+	let nonce = rand::random::<usize>().to_string();
+	*asset = asset.replace(CSP_NONCE_TOKEN, &nonce);
+	if let Some(on_csp) = &manager.inner.on_csp {
+		on_csp(&CspPayload { path: asset_path.as_ref().to_string(), nonce }, &mut csp);
+	}
+
 	Csp::DirectiveMap(csp).to_string()
 }
 
@@ -173,6 +204,12 @@ pub struct InnerWindowManager<R: Runtime> {
 	/// The page load hook, invoked when the webview performs a navigation.
 	on_page_load: Box<OnPageLoad<R>>,
 
+	/// The binary message handler.
+	on_binary_message: Option<Box<OnBinaryMessage<R>>>,
+
+	/// The hook to modify the CSP of served HTML pages.
+	on_csp: Option<Box<OnCsp>>,
+
 	config: Arc<Config>,
 	assets: Arc<dyn Assets>,
 	pub(crate) default_window_icon: Option<Icon>,
@@ -192,7 +229,9 @@ pub struct InnerWindowManager<R: Runtime> {
 	/// The script that initializes the invoke system.
 	invoke_initialization_script: String,
 	/// Application pattern.
-	pattern: Pattern
+	pattern: Pattern,
+	/// The server the embedded assets are served from, if they're not served through the custom protocol.
+	asset_server: Mutex<Option<AssetServer>>
 }
 
 impl<R: Runtime> fmt::Debug for InnerWindowManager<R> {
@@ -227,6 +266,22 @@ pub struct CustomProtocol<R: Runtime> {
 	pub protocol: Box<dyn Fn(&AppHandle<R>, &HttpRequest) -> Result<HttpResponse, Box<dyn std::error::Error>> + Send + Sync>
 }
 
+/// The app hooks the [`WindowManager`] calls into.
+pub(crate) struct WindowManagerHooks<R: Runtime> {
+	/// The JS message handler.
+	pub(crate) invoke_handler: Box<InvokeHandler<R>>,
+	/// Responder for invoke calls.
+	pub(crate) invoke_responder: Arc<InvokeResponder<R>>,
+	/// The script that initializes the invoke system.
+	pub(crate) invoke_initialization_script: String,
+	/// The page load hook, invoked when the webview performs a navigation.
+	pub(crate) on_page_load: Box<OnPageLoad<R>>,
+	/// The binary message handler.
+	pub(crate) on_binary_message: Option<Box<OnBinaryMessage<R>>>,
+	/// The hook to modify the CSP of served HTML pages.
+	pub(crate) on_csp: Option<Box<OnCsp>>
+}
+
 #[default_runtime(crate::MillenniumWebview, millennium_webview)]
 #[derive(Debug)]
 pub struct WindowManager<R: Runtime> {
@@ -240,17 +295,14 @@ impl<R: Runtime> Clone for WindowManager<R> {
 }
 
 impl<R: Runtime> WindowManager<R> {
-	#[allow(clippy::too_many_arguments)]
 	pub(crate) fn with_handlers(
 		#[allow(unused_mut)] mut context: Context<impl Assets>,
 		plugins: PluginStore<R>,
-		invoke_handler: Box<InvokeHandler<R>>,
-		on_page_load: Box<OnPageLoad<R>>,
+		hooks: WindowManagerHooks<R>,
 		uri_scheme_protocols: HashMap<String, Arc<CustomProtocol<R>>>,
 		state: StateManager,
 		window_event_listeners: Vec<GlobalWindowEventListener<R>>,
-		(menu, menu_event_listeners): (Option<Menu>, Vec<GlobalMenuEventListener<R>>),
-		(invoke_responder, invoke_initialization_script): (Arc<InvokeResponder<R>>, String)
+		(menu, menu_event_listeners): (Option<Menu>, Vec<GlobalMenuEventListener<R>>)
 	) -> Self {
 		// generate a random isolation key at runtime
 		#[cfg(feature = "isolation")]
@@ -264,8 +316,10 @@ impl<R: Runtime> WindowManager<R> {
 				plugins: Mutex::new(plugins),
 				listeners: Listeners::default(),
 				state: Arc::new(state),
-				invoke_handler,
-				on_page_load,
+				invoke_handler: hooks.invoke_handler,
+				on_page_load: hooks.on_page_load,
+				on_binary_message: hooks.on_binary_message,
+				on_csp: hooks.on_csp,
 				config: Arc::new(context.config),
 				assets: context.assets,
 				default_window_icon: context.default_window_icon,
@@ -276,8 +330,9 @@ impl<R: Runtime> WindowManager<R> {
 				menu,
 				menu_event_listeners: Arc::new(menu_event_listeners),
 				window_event_listeners: Arc::new(window_event_listeners),
-				invoke_responder,
-				invoke_initialization_script
+				invoke_responder: hooks.invoke_responder,
+				invoke_initialization_script: hooks.invoke_initialization_script,
+				asset_server: Mutex::default()
 			})
 		}
 	}
@@ -317,11 +372,15 @@ impl<R: Runtime> WindowManager<R> {
 
 	/// Get the base URL to use for webview requests.
 	///
-	/// In dev mode, this will be based on the `devPath` configuration value.
+	/// In dev mode, this will be based on the `devPath` configuration value. If the embedded assets are served from
+	/// localhost, this is the localhost URL.
 	fn get_url(&self) -> Cow<'_, Url> {
 		match self.base_path() {
 			AppUrl::Url(WindowUrl::External(url)) => Cow::Borrowed(url),
-			_ => Cow::Owned(Url::parse("millennium://localhost").unwrap())
+			_ => match self.asset_server() {
+				Some(server) => Cow::Owned(Url::parse(&format!("http://localhost:{}", server.port)).unwrap()),
+				None => Cow::Owned(Url::parse("millennium://localhost").unwrap())
+			}
 		}
 	}
 
@@ -329,10 +388,31 @@ impl<R: Runtime> WindowManager<R> {
 	fn get_browser_origin(&self) -> String {
 		match self.base_path() {
 			AppUrl::Url(WindowUrl::External(url)) => url.origin().ascii_serialization(),
-			_ => format_real_schema("millennium")
+			_ => match self.asset_server() {
+				Some(server) => format!("http://localhost:{}", server.port),
+				None => format_real_schema("millennium")
+			}
+		}
+	}
+
+	/// The configured port to serve the embedded assets from, if they're served from localhost instead of the custom
+	/// protocol.
+	pub(crate) fn localhost_port(&self) -> Option<u16> {
+		match self.base_path() {
+			AppUrl::Url(WindowUrl::External(_)) => None,
+			_ => self.inner.config.millennium.embedded_assets.localhost_port
 		}
 	}
 
+	/// The server the embedded assets are served from, once it has been started.
+	fn asset_server(&self) -> Option<AssetServer> {
+		self.inner.asset_server.lock().expect("poisoned asset server").clone()
+	}
+
+	pub(crate) fn set_asset_server(&self, server: AssetServer) {
+		self.inner.asset_server.lock().expect("poisoned asset server").replace(server);
+	}
+
 	fn csp(&self) -> Option<Csp> {
 		if cfg!(feature = "custom-protocol") {
 			self.inner.config.millennium.security.csp.clone()
@@ -549,12 +629,12 @@ impl<R: Runtime> WindowManager<R> {
 						response = response.header(k, v);
 					}
 
-					let mime_type = MimeType::parse(&data, &path);
+					let mime_type = asset_mime_type(&data, &path);
 					response.mimetype(&mime_type).status(status_code).body(data)
 				} else {
 					match crate::async_runtime::safe_block_on(async move { tokio::fs::read(path_).await }) {
 						Ok(data) => {
-							let mime_type = MimeType::parse(&data, &path);
+							let mime_type = asset_mime_type(&data, &path);
 							response.mimetype(&mime_type).body(data)
 						}
 						Err(e) => {
@@ -625,6 +705,23 @@ impl<R: Runtime> WindowManager<R> {
 		})
 	}
 
+	fn prepare_binary_ipc_handler(&self, app_handle: AppHandle<R>) -> Option<WebviewBinaryIpcHandler<EventLoopMessage, R>> {
+		self.inner.on_binary_message.as_ref()?;
+		// binary messages would bypass the isolation application's validation
+		#[cfg(feature = "isolation")]
+		if let Pattern::Isolation { .. } = self.pattern() {
+			return None;
+		}
+
+		let manager = self.clone();
+		Some(Box::new(move |window, data| {
+			let window = Window::new(manager.clone(), window, app_handle.clone());
+			if let Some(on_binary_message) = &manager.inner.on_binary_message {
+				on_binary_message(window, data);
+			}
+		}))
+	}
+
 	pub fn get_asset(&self, mut path: String) -> Result<Asset, Box<dyn std::error::Error>> {
 		let assets = &self.inner.assets;
 		if path.ends_with('/') {
@@ -641,13 +738,23 @@ impl<R: Runtime> WindowManager<R> {
 
 		let mut asset_path = AssetKey::from(path.as_str());
 
-		let asset_response = assets
-			.get(&path.as_str().into())
+		// in dev mode, `millennium dev` may ask us to serve a static `devPath` from disk so changes show up on reload
+		#[cfg(dev)]
+		let csp = self.csp().is_some();
+		let get_asset = |key: &AssetKey| {
+			#[cfg(dev)]
+			if let Some(asset) = crate::dev_reload::asset(key.as_ref(), csp, &self.config().millennium.security.dangerous_disable_asset_csp_modification) {
+				return Some(Cow::Owned(asset));
+			}
+			assets.get(key)
+		};
+
+		let asset_response = get_asset(&path.as_str().into())
 			.or_else(|| {
 				#[cfg(debug_assertions)]
 				eprintln!("Asset `{}` not found; fallback to {}.html", path, path);
 				let fallback = format!("{}.html", path.as_str()).into();
-				let asset = assets.get(&fallback);
+				let asset = get_asset(&fallback);
 				asset_path = fallback;
 				asset
 			})
@@ -655,7 +762,7 @@ impl<R: Runtime> WindowManager<R> {
 				#[cfg(debug_assertions)]
 				eprintln!("Asset `{}` not found; fallback to {}/index.html", path, path);
 				let fallback = format!("{}/index.html", path.as_str()).into();
-				let asset = assets.get(&fallback);
+				let asset = get_asset(&fallback);
 				asset_path = fallback;
 				asset
 			})
@@ -663,7 +770,7 @@ impl<R: Runtime> WindowManager<R> {
 				#[cfg(debug_assertions)]
 				eprintln!("Asset `{}` not found; fallback to index.html", path);
 				let fallback = AssetKey::from("index.html");
-				let asset = assets.get(&fallback);
+				let asset = get_asset(&fallback);
 				asset_path = fallback;
 				asset
 			})
@@ -863,19 +970,28 @@ mod test {
 	use super::*;
 	use crate::{generate_context, plugin::PluginStore, MillenniumWebview, StateManager};
 
+	fn hooks() -> WindowManagerHooks<MillenniumWebview> {
+		WindowManagerHooks {
+			invoke_handler: Box::new(|_| ()),
+			invoke_responder: std::sync::Arc::new(|_, _, _, _| ()),
+			invoke_initialization_script: "".into(),
+			on_page_load: Box::new(|_, _| ()),
+			on_binary_message: None,
+			on_csp: None
+		}
+	}
+
 	#[test]
 	fn check_get_url() {
 		let context = generate_context!("test/fixture/.millenniumrc", crate);
 		let manager: WindowManager<MillenniumWebview> = WindowManager::with_handlers(
 			context,
 			PluginStore::default(),
-			Box::new(|_| ()),
-			Box::new(|_, _| ()),
+			hooks(),
 			Default::default(),
 			StateManager::new(),
 			Default::default(),
-			Default::default(),
-			(std::sync::Arc::new(|_, _, _, _| ()), "".into())
+			Default::default()
 		);
 
 		#[cfg(custom_protocol)]
@@ -884,6 +1000,53 @@ mod test {
 		#[cfg(dev)]
 		assert_eq!(manager.get_url().to_string(), "http://localhost:4000/");
 	}
+
+	#[test]
+	fn csp_hook_receives_page_nonce() {
+		let context = generate_context!("test/fixture/.millenniumrc", crate);
+		let csp = context.config.millennium.security.csp.clone().unwrap();
+		let manager: WindowManager<MillenniumWebview> = WindowManager::with_handlers(
+			context,
+			PluginStore::default(),
+			WindowManagerHooks {
+				on_csp: Some(Box::new(|payload: &CspPayload, csp: &mut HashMap<String, CspDirectiveSources>| {
+					assert_eq!(payload.path(), "/index.html");
+					csp.entry("script-src".into()).or_default().push(payload.nonce_source());
+				})),
+				..hooks()
+			},
+			Default::default(),
+			StateManager::new(),
+			Default::default(),
+			Default::default()
+		);
+
+		let mut html = format!(r#"<script nonce="{}"></script>"#, CSP_NONCE_TOKEN);
+		let csp = set_csp(&mut html, manager.inner.assets.clone(), &AssetKey::from("index.html"), &manager, csp);
+		let nonce = html.trim_start_matches(r#"<script nonce=""#).trim_end_matches(r#""></script>"#);
+		assert!(nonce.parse::<usize>().is_ok());
+		assert!(csp.contains(&format!("script-src 'nonce-{}'", nonce)));
+	}
+
+	#[test]
+	fn csp_nonce_global_is_not_replaced() {
+		let context = generate_context!("test/fixture/.millenniumrc", crate);
+		let csp = context.config.millennium.security.csp.clone().unwrap();
+		let manager: WindowManager<MillenniumWebview> = WindowManager::with_handlers(
+			context,
+			PluginStore::default(),
+			hooks(),
+			Default::default(),
+			StateManager::new(),
+			Default::default(),
+			Default::default()
+		);
+
+		let script = "<script>console.log(window.__MILLENNIUM_CSP_NONCE__);</script>";
+		let mut html = script.to_string();
+		set_csp(&mut html, manager.inner.assets.clone(), &AssetKey::from("index.html"), &manager, csp);
+		assert_eq!(html, script);
+	}
 }
 
 impl<R: Runtime> WindowManager<R> {
@@ -922,18 +1085,19 @@ impl<R: Runtime> WindowManager<R> {
 		let (is_local, mut url) = match &pending.webview_attributes.url {
 			WindowUrl::App(path) => {
 				let url = self.get_url();
-				(
-					true,
-					// ignore "index.html" just to simplify the url
-					if path.to_str() != Some("index.html") {
-						url.join(&path.to_string_lossy())
-							.map_err(crate::Error::InvalidUrl)
-							// this will never fail
-							.unwrap()
-					} else {
-						url.into_owned()
-					}
-				)
+				// ignore "index.html" just to simplify the url
+				let mut url = if path.to_str() != Some("index.html") {
+					url.join(&path.to_string_lossy())
+						.map_err(crate::Error::InvalidUrl)
+						// this will never fail
+						.unwrap()
+				} else {
+					url.into_owned()
+				};
+				if let Some(server) = self.asset_server() {
+					server.authorize(&mut url);
+				}
+				(true, url)
 			}
 			WindowUrl::External(url) => {
 				let config_url = self.get_url();
@@ -965,12 +1129,6 @@ impl<R: Runtime> WindowManager<R> {
 
 		pending.url = url.to_string();
 
-		if !pending.window_builder.has_icon() {
-			if let Some(default_window_icon) = self.inner.default_window_icon.clone() {
-				pending.window_builder = pending.window_builder.icon(default_window_icon.try_into()?)?;
-			}
-		}
-
 		if pending.window_builder.get_menu().is_none() {
 			if let Some(menu) = &self.inner.menu {
 				pending = pending.set_menu(menu.clone());
@@ -980,6 +1138,7 @@ impl<R: Runtime> WindowManager<R> {
 		if is_local {
 			let label = pending.label.clone();
 			pending = self.prepare_pending_window(pending, &label, window_labels, app_handle.clone(), web_resource_request_handler)?;
+			pending.binary_ipc_handler = self.prepare_binary_ipc_handler(app_handle.clone());
 			pending.ipc_handler = Some(self.prepare_ipc_handler(app_handle));
 		}
 
@@ -987,16 +1146,7 @@ impl<R: Runtime> WindowManager<R> {
 		// but we do respect user-specification
 		#[cfg(any(target_os = "linux", target_os = "windows"))]
 		if pending.webview_attributes.data_directory.is_none() {
-			let local_app_data = resolve_path(
-				&self.inner.config,
-				&self.inner.package_info,
-				self.inner.state.get::<crate::Env>().inner(),
-				&self.inner.config.millennium.bundle.identifier,
-				Some(BaseDirectory::LocalData)
-			);
-			if let Ok(user_data_dir) = local_app_data {
-				pending.webview_attributes.data_directory = Some(user_data_dir);
-			}
+			pending.webview_attributes.data_directory = webview_data_dir(&self.inner.config);
 		}
 
 		// make sure the directory is created and available to prevent a panic
@@ -1109,6 +1259,17 @@ impl<R: Runtime> WindowManager<R> {
 		self.inner.listeners.function_name()
 	}
 
+	/// The script that delivers an event to the JavaScript listeners of a window.
+	pub fn event_emit_script<S: Serialize>(&self, event: &str, source_window_label: Option<&str>, payload: S) -> crate::Result<String> {
+		Ok(format!(
+			"window['{}']({{event: {}, windowLabel: {}, payload: {}}})",
+			self.event_emit_function_name(),
+			serde_json::to_string(event)?,
+			serde_json::to_string(&source_window_label)?,
+			serde_json::to_value(payload)?,
+		))
+	}
+
 	pub fn get_window(&self, label: &str) -> Option<Window<R>> {
 		self.windows_lock().get(label).cloned()
 	}
@@ -1164,7 +1325,8 @@ fn on_window_event<R: Runtime>(window: &Window<R>, manager: &WindowManager<R>, e
 			FileDropEvent::Cancelled => window.emit("millennium://file-drop-cancelled", ())?,
 			_ => unimplemented!()
 		},
-		WindowEvent::ThemeChanged(theme) => window.emit(WINDOW_THEME_CHANGED, theme.to_string())?
+		WindowEvent::ThemeChanged(theme) => window.emit(WINDOW_THEME_CHANGED, theme.to_string())?,
+		WindowEvent::Suspended(suspended) => window.emit(if *suspended { WINDOW_SUSPEND_EVENT } else { WINDOW_RESUME_EVENT }, ())?
 	}
 	Ok(())
 }
@@ -0,0 +1,105 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A panic hook that reports crashes to the user with a native error dialog.
+
+use std::{
+	panic::{self, AssertUnwindSafe, PanicInfo},
+	sync::Mutex,
+	thread::{self, ThreadId}
+};
+
+use once_cell::sync::{Lazy, OnceCell};
+
+use millennium_runtime_webview::millennium_webview::application::clipboard::Clipboard;
+
+/// The name of the app, set once the hook is installed.
+static APP_NAME: OnceCell<String> = OnceCell::new();
+
+/// The details of the last panic, along with the thread it was raised on.
+static LAST_PANIC: Lazy<Mutex<Option<(ThreadId, String)>>> = Lazy::new(Default::default);
+
+/// Installs the panic hook. The previous hook still runs first, so the panic
+/// is printed to stderr as usual.
+///
+/// The hook itself only records the panic details; the dialog is shown by
+/// [`run`] once the panic has unwound out of the event loop, so no runtime lock
+/// (e.g. the windows mutex) is held while it is open and panics that are caught
+/// or only take down a worker thread are never reported.
+pub(crate) fn install(app_name: String) {
+	if APP_NAME.set(app_name).is_err() {
+		return;
+	}
+
+	let previous_hook = panic::take_hook();
+	panic::set_hook(Box::new(move |info| {
+		previous_hook(info);
+		*lock(&LAST_PANIC) = Some((thread::current().id(), details(info)));
+	}));
+}
+
+/// Runs the event loop on the main thread. If a panic unwinds out of it, the
+/// dialog is shown before the panic resumes unwinding and the process exits.
+pub(crate) fn run<F: FnOnce()>(event_loop: F) {
+	if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(event_loop)) {
+		let last_panic = lock(&LAST_PANIC).take();
+		if let (Some(app_name), Some((thread, details))) = (APP_NAME.get(), last_panic) {
+			if thread == thread::current().id() {
+				show(app_name, &details);
+			}
+		}
+		panic::resume_unwind(payload);
+	}
+}
+
+fn show(app_name: &str, details: &str) {
+	// the dialog is shown again after copying so the process, which owns the
+	// clipboard contents on Linux, stays alive until the user pastes them
+	loop {
+		let copy = rfd::MessageDialog::new()
+			.set_level(rfd::MessageLevel::Error)
+			.set_title(&format!("{} crashed", app_name))
+			.set_description(&format!("{} ran into an unexpected error and needs to close.\n\n{}", app_name, details))
+			.set_buttons(rfd::MessageButtons::OkCancelCustom("Copy details".into(), "Close".into()))
+			.show();
+		if !copy {
+			break;
+		}
+		Clipboard::new().write_text(details);
+	}
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+	mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn details(info: &PanicInfo<'_>) -> String {
+	let payload = info.payload();
+	let message = if let Some(message) = payload.downcast_ref::<&str>() {
+		message
+	} else if let Some(message) = payload.downcast_ref::<String>() {
+		message.as_str()
+	} else {
+		"Box<dyn Any>"
+	};
+	let thread = std::thread::current();
+	let thread = thread.name().unwrap_or("<unnamed>");
+
+	match info.location() {
+		Some(location) => format!("thread '{}' panicked at '{}', {}", thread, message, location),
+		None => format!("thread '{}' panicked at '{}'", thread, message)
+	}
+}
@@ -0,0 +1,234 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Single instance support, so that launching the application again forwards the new launch to the running instance.
+
+use std::{
+	ffi::OsString,
+	io::{self, BufReader},
+	path::PathBuf,
+	sync::Mutex
+};
+
+use interprocess::local_socket::{LocalSocketListener, LocalSocketStream};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// The argument a Windows jump list item launches the app with, followed by the item's id. Matches the one in
+/// `millennium-core`.
+pub(crate) const JUMP_LIST_ITEM_ARG_PREFIX: &str = "--jump-list-item=";
+
+/// The jump list items clicked in second instances, and where to pass them once the app is built.
+#[derive(Default)]
+struct JumpListClicks {
+	pending: Vec<String>,
+	forward: Option<Box<dyn Fn(String) + Send>>
+}
+
+impl JumpListClicks {
+	fn clicked(&mut self, id: String) {
+		if let Some(forward) = &self.forward {
+			forward(id);
+		} else {
+			self.pending.push(id);
+		}
+	}
+
+	fn forward_to(&mut self, forward: Box<dyn Fn(String) + Send>) {
+		self.pending.drain(..).for_each(&forward);
+		self.forward = Some(forward);
+	}
+}
+
+static JUMP_LIST_CLICKS: Lazy<Mutex<JumpListClicks>> = Lazy::new(Default::default);
+
+/// Requests a single instance lock for the application. This can be used to ensure that only one instance of your app
+/// is open at a time.
+///
+/// Returns `Ok(true)` if the lock was acquired (this is the first instance of the app), and `Ok(false)` if the lock was
+/// not acquired (this is the second instance of the app). In the latter case, the launch has been forwarded to the
+/// first instance and the process should exit.
+///
+/// `on_second_instance` will be called when a second instance of the app is launched with the instance's command line
+/// arguments, current working directory, and process ID. When the app is registered as the handler of a custom URL
+/// scheme on Windows or Linux, the URL is one of the arguments.
+///
+/// The lock is a named pipe on Windows and a Unix domain socket in the user's runtime directory on Linux and macOS.
+/// A socket left behind by an instance that crashed is detected and replaced.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** Clicking an item of the [jump list](crate::AppHandle::set_jump_list) launches a second instance. Its
+///   `--jump-list-item=<id>` argument is removed from the arguments passed to `on_second_instance`, and the first
+///   instance emits [`RunEvent::JumpListItemClicked`](crate::RunEvent::JumpListItemClicked) instead.
+/// - **macOS:** Launch Services already keeps a single instance of an app bundle, and routes files and URLs opened with
+///   the app to it as [`RunEvent::Opened`](crate::RunEvent::Opened). The lock only matters when the binary is run
+///   directly, e.g. from a terminal.
+pub fn request_single_instance_lock<F>(id: &str, on_second_instance: F) -> Result<bool>
+where
+	F: Fn(Vec<OsString>, PathBuf, u32) + Send + 'static
+{
+	let name = socket_name(id);
+	let listener = match LocalSocketListener::bind(name.as_str()) {
+		Ok(listener) => listener,
+		Err(_) => match LocalSocketStream::connect(name.as_str()) {
+			Ok(conn) => {
+				forward_launch(conn)?;
+				return Ok(false);
+			}
+			// the socket file exists, but nobody is listening on it; the previous instance crashed before removing it
+			#[cfg(unix)]
+			Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => {
+				std::fs::remove_file(&name)?;
+				LocalSocketListener::bind(name.as_str())?
+			}
+			Err(e) => return Err(e.into())
+		}
+	};
+
+	std::thread::spawn(move || {
+		for conn in listener.incoming().flatten() {
+			if let Ok(Launch { mut args, cwd, pid }) = serde_json::from_reader(BufReader::new(conn)) {
+				if let Some(id) = take_jump_list_item(&mut args) {
+					jump_list_item_clicked(id);
+				}
+				on_second_instance(args, PathBuf::from(cwd), pid);
+			}
+		}
+	});
+	Ok(true)
+}
+
+#[cfg(windows)]
+fn socket_name(id: &str) -> String {
+	// interprocess places this under `\\.\pipe\`
+	id.into()
+}
+
+#[cfg(not(windows))]
+fn socket_name(id: &str) -> String {
+	dirs_next::runtime_dir()
+		.unwrap_or_else(std::env::temp_dir)
+		.join(format!("{}.lock", id))
+		.to_string_lossy()
+		.into_owned()
+}
+
+/// A second instance's launch, forwarded to the first instance as JSON. The arguments and working directory are sent
+/// as `OsString`s in their raw platform encoding, so they arrive unchanged even if they aren't valid UTF-8.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Launch {
+	args: Vec<OsString>,
+	cwd: OsString,
+	pid: u32
+}
+
+/// Sends this process' arguments, working directory and ID to the first instance.
+fn forward_launch(conn: LocalSocketStream) -> io::Result<()> {
+	let launch = Launch {
+		args: std::env::args_os().collect(),
+		cwd: std::env::current_dir()?.into_os_string(),
+		pid: std::process::id()
+	};
+	serde_json::to_writer(conn, &launch).map_err(io::Error::from)
+}
+
+/// Removes the `--jump-list-item=<id>` argument a Windows jump list item launches the app with from `args`, and
+/// returns the id.
+fn take_jump_list_item(args: &mut Vec<OsString>) -> Option<String> {
+	let index = args
+		.iter()
+		.position(|arg| arg.to_str().map_or(false, |arg| arg.starts_with(JUMP_LIST_ITEM_ARG_PREFIX)))?;
+	let arg = args.remove(index).into_string().ok()?;
+	Some(arg[JUMP_LIST_ITEM_ARG_PREFIX.len()..].to_string())
+}
+
+fn jump_list_item_clicked(id: String) {
+	JUMP_LIST_CLICKS.lock().expect("poisoned jump list clicks").clicked(id);
+}
+
+/// Passes the ids of the jump list items clicked in second instances to `forward`, starting with the ones clicked
+/// before the app was built.
+pub(crate) fn forward_jump_list_clicks<F: Fn(String) + Send + 'static>(forward: F) {
+	JUMP_LIST_CLICKS.lock().expect("poisoned jump list clicks").forward_to(Box::new(forward));
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		ffi::OsString,
+		sync::{Arc, Mutex}
+	};
+
+	use super::{take_jump_list_item, JumpListClicks, Launch};
+
+	#[test]
+	fn forwards_launch_unchanged() {
+		let launch = Launch {
+			args: vec!["/usr/bin/app".into(), "--flag\rwith\nbreaks".into(), "example://open".into()],
+			cwd: "/home/user\r".into(),
+			pid: 1234
+		};
+		let message = serde_json::to_vec(&launch).unwrap();
+		assert_eq!(serde_json::from_slice::<Launch>(&message).unwrap(), launch);
+
+		#[cfg(unix)]
+		{
+			use std::os::unix::ffi::OsStringExt;
+
+			let launch = Launch {
+				args: vec![OsString::from_vec(b"/usr/bin/app".to_vec()), OsString::from_vec(b"caf\xe9".to_vec())],
+				cwd: OsString::from_vec(b"/home/\xff".to_vec()),
+				pid: 1234
+			};
+			let message = serde_json::to_vec(&launch).unwrap();
+			assert_eq!(serde_json::from_slice::<Launch>(&message).unwrap(), launch);
+		}
+	}
+
+	#[test]
+	fn forwards_jump_list_items() {
+		let mut args: Vec<OsString> = vec!["C:\\app.exe".into(), "--jump-list-item=open recent".into(), "--flag".into()];
+		assert_eq!(take_jump_list_item(&mut args).as_deref(), Some("open recent"));
+		assert_eq!(args, vec![OsString::from("C:\\app.exe"), OsString::from("--flag")]);
+		assert!(take_jump_list_item(&mut args).is_none());
+
+		// clicks received before the app is built are delivered once it starts forwarding them
+		let mut clicks = JumpListClicks::default();
+		clicks.clicked("first".into());
+		let clicked = Arc::new(Mutex::new(Vec::new()));
+		let clicked_ = clicked.clone();
+		clicks.forward_to(Box::new(move |id| clicked_.lock().unwrap().push(id)));
+		clicks.clicked("second".into());
+		assert_eq!(*clicked.lock().unwrap(), vec!["first".to_string(), "second".to_string()]);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn replaces_stale_socket() {
+		let id = format!("millennium-single-instance-test-{}", std::process::id());
+		let name = super::socket_name(&id);
+		// simulate a crashed instance: the socket file exists, but nothing is listening on it
+		drop(std::os::unix::net::UnixListener::bind(&name).unwrap());
+		assert!(std::path::Path::new(&name).exists());
+
+		assert!(super::request_single_instance_lock(&id, |_, _, _| {}).unwrap());
+		assert!(!super::request_single_instance_lock(&id, |_, _, _| {}).unwrap());
+		let _ = std::fs::remove_file(&name);
+	}
+}
@@ -19,25 +19,26 @@
 use std::{
 	collections::HashMap,
 	fmt,
+	path::PathBuf,
 	sync::{Arc, Mutex}
 };
 
 use millennium_runtime::{
-	menu::{Menu, MenuUpdate},
+	menu::{CustomMenuItem, Menu, MenuId, MenuUpdate},
 	monitor::Monitor,
-	webview::{WindowBuilder, WindowBuilderBase},
+	webview::{CssId, InitScriptId, PdfSettings, WindowBuilder, WindowBuilderBase},
 	window::{
 		dpi::{PhysicalPosition, PhysicalSize, Position, Size},
-		CursorIcon, DetachedWindow, MenuEvent, PendingWindow, WindowEvent
+		AccessibilityPreferences, CursorIcon, DetachedWindow, MenuEvent, PendingWindow, Rect, ResizeDirection, WindowEvent
 	},
-	Dispatch, EventLoopProxy, Icon, Result, RunEvent, Runtime, RuntimeHandle, UserAttentionType, UserEvent
+	Dispatch, EventLoopProxy, Icon, JumpListItem, Result, RunEvent, Runtime, RuntimeHandle, UserAttentionType, UserEvent
 };
 #[cfg(feature = "system-tray")]
 use millennium_runtime::{
 	menu::{SystemTrayMenu, TrayHandle},
 	SystemTray, SystemTrayEvent
 };
-use millennium_utils::{config::WindowConfig, Theme};
+use millennium_utils::{config::WindowConfig, LayoutDirection, Theme};
 use uuid::Uuid;
 #[cfg(windows)]
 use windows::Win32::Foundation::HWND;
@@ -65,11 +66,14 @@ impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
 	type Runtime = MockRuntime;
 
 	fn create_proxy(&self) -> EventProxy {
-		unimplemented!()
+		EventProxy {}
 	}
 
 	/// Create a new webview window.
 	fn create_window(&self, pending: PendingWindow<T, Self::Runtime>) -> Result<DetachedWindow<T, Self::Runtime>> {
+		if let Some(on_created) = pending.on_created {
+			on_created(Ok(()));
+		}
 		Ok(DetachedWindow {
 			label: pending.label,
 			dispatcher: MockDispatcher { context: self.context.clone() },
@@ -83,6 +87,18 @@ impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
 		unimplemented!()
 	}
 
+	fn register_app_shortcut<F: Fn() + Send + Sync + 'static>(&self, accelerator: &str, handler: F) -> Result<()> {
+		Ok(())
+	}
+
+	fn unregister_app_shortcut(&self, accelerator: &str) -> Result<()> {
+		Ok(())
+	}
+
+	fn set_jump_list(&self, items: Vec<JumpListItem>) -> Result<()> {
+		Ok(())
+	}
+
 	#[cfg(all(windows, feature = "system-tray"))]
 	#[cfg_attr(doc_cfg, doc(cfg(all(windows, feature = "system-tray"))))]
 	fn remove_system_tray(&self) -> Result<()> {
@@ -163,6 +179,10 @@ impl WindowBuilder for MockWindowBuilder {
 		self
 	}
 
+	fn center_on_monitor(self, monitor: Monitor) -> Self {
+		self
+	}
+
 	fn position(self, x: f64, y: f64) -> Self {
 		self
 	}
@@ -183,6 +203,18 @@ impl WindowBuilder for MockWindowBuilder {
 		self
 	}
 
+	fn minimizable(self, minimizable: bool) -> Self {
+		self
+	}
+
+	fn maximizable(self, maximizable: bool) -> Self {
+		self
+	}
+
+	fn closable(self, closable: bool) -> Self {
+		self
+	}
+
 	fn title<S: Into<String>>(self, title: S) -> Self {
 		self
 	}
@@ -227,6 +259,10 @@ impl WindowBuilder for MockWindowBuilder {
 		Ok(self)
 	}
 
+	fn taskbar_icon(self, icon: Icon) -> Result<Self> {
+		Ok(self)
+	}
+
 	fn skip_taskbar(self, skip: bool) -> Self {
 		self
 	}
@@ -250,6 +286,14 @@ impl WindowBuilder for MockWindowBuilder {
 		self
 	}
 
+	fn layout_direction(self, direction: Option<LayoutDirection>) -> Self {
+		self
+	}
+
+	fn title_bar_color(self, color: Option<(u8, u8, u8, u8)>) -> Self {
+		self
+	}
+
 	fn has_icon(&self) -> bool {
 		false
 	}
@@ -276,9 +320,16 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 		Uuid::new_v4()
 	}
 
+	fn remove_window_event_listener(&self, id: Uuid) {}
+
+	fn remove_menu_event_listener(&self, id: Uuid) {}
+
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	fn open_devtools(&self) {}
 
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	fn open_devtools_detached(&self) {}
+
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	fn close_devtools(&self) {}
 
@@ -307,6 +358,14 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 		Ok(PhysicalSize { width: 0, height: 0 })
 	}
 
+	fn min_size(&self) -> Result<Option<PhysicalSize<u32>>> {
+		Ok(None)
+	}
+
+	fn max_size(&self) -> Result<Option<PhysicalSize<u32>>> {
+		Ok(None)
+	}
+
 	fn is_fullscreen(&self) -> Result<bool> {
 		Ok(false)
 	}
@@ -323,6 +382,22 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 		Ok(false)
 	}
 
+	fn is_minimizable(&self) -> Result<bool> {
+		Ok(true)
+	}
+
+	fn is_maximizable(&self) -> Result<bool> {
+		Ok(true)
+	}
+
+	fn is_closable(&self) -> Result<bool> {
+		Ok(true)
+	}
+
+	fn is_visible_on_all_workspaces(&self) -> Result<bool> {
+		Ok(false)
+	}
+
 	fn is_visible(&self) -> Result<bool> {
 		Ok(true)
 	}
@@ -347,6 +422,14 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 		Ok(Theme::Light)
 	}
 
+	fn accent_color(&self) -> Result<Option<(u8, u8, u8, u8)>> {
+		Ok(None)
+	}
+
+	fn accessibility_preferences(&self) -> Result<AccessibilityPreferences> {
+		Ok(AccessibilityPreferences::default())
+	}
+
 	#[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
 	fn gtk_window(&self) -> Result<gtk::ApplicationWindow> {
 		unimplemented!()
@@ -360,10 +443,54 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 		Ok(())
 	}
 
+	fn center_on_monitor(&self, monitor: Monitor) -> Result<()> {
+		Ok(())
+	}
+
 	fn print(&self) -> Result<()> {
 		Ok(())
 	}
 
+	fn print_to_pdf(&self, path: PathBuf, settings: PdfSettings) -> Result<PathBuf> {
+		Ok(path)
+	}
+
+	fn take_snapshot(&self) -> Result<Vec<u8>> {
+		Ok(Vec::new())
+	}
+
+	fn set_clipboard_access(&self, enabled: bool) -> Result<()> {
+		Ok(())
+	}
+
+	fn insert_css(&self, css: &str) -> Result<CssId> {
+		Ok(CssId(0))
+	}
+
+	fn remove_css(&self, id: CssId) -> Result<()> {
+		Ok(())
+	}
+
+	fn add_init_script(&self, js: &str) -> Result<InitScriptId> {
+		Ok(InitScriptId(0))
+	}
+
+	fn remove_init_script(&self, id: InitScriptId) -> Result<()> {
+		Ok(())
+	}
+
+	fn memory_usage(&self) -> Result<Option<u64>> {
+		Ok(None)
+	}
+
+	fn suspend(&self) -> Result<()> {
+		Ok(())
+	}
+
+	fn resume(&self) -> Result<()> {
+		Ok(())
+	}
+
 	fn request_user_attention(&self, request_type: Option<UserAttentionType>) -> Result<()> {
 		Ok(())
 	}
@@ -376,6 +503,18 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 		Ok(())
 	}
 
+	fn set_minimizable(&self, minimizable: bool) -> Result<()> {
+		Ok(())
+	}
+
+	fn set_maximizable(&self, maximizable: bool) -> Result<()> {
+		Ok(())
+	}
+
+	fn set_closable(&self, closable: bool) -> Result<()> {
+		Ok(())
+	}
+
 	fn set_title<S: Into<String>>(&self, title: S) -> Result<()> {
 		Ok(())
 	}
@@ -424,6 +563,14 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 		Ok(())
 	}
 
+	fn set_visible_on_all_workspaces(&self, visible_on_all_workspaces: bool) -> Result<()> {
+		Ok(())
+	}
+
+	fn set_opacity(&self, opacity: f64) -> Result<()> {
+		Ok(())
+	}
+
 	fn set_size(&self, size: Size) -> Result<()> {
 		Ok(())
 	}
@@ -436,6 +583,10 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 		Ok(())
 	}
 
+	fn set_aspect_ratio(&self, aspect_ratio: Option<f64>) -> Result<()> {
+		Ok(())
+	}
+
 	fn set_position(&self, position: Position) -> Result<()> {
 		Ok(())
 	}
@@ -452,10 +603,22 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 		Ok(())
 	}
 
+	fn set_taskbar_icon(&self, icon: Icon) -> Result<()> {
+		Ok(())
+	}
+
 	fn set_skip_taskbar(&self, skip: bool) -> Result<()> {
 		Ok(())
 	}
 
+	fn set_represented_filename(&self, path: PathBuf) -> Result<()> {
+		Ok(())
+	}
+
+	fn set_title_bar_colors(&self, caption: Option<(u8, u8, u8, u8)>, text: Option<(u8, u8, u8, u8)>, border: Option<(u8, u8, u8, u8)>) -> Result<()> {
+		Ok(())
+	}
+
 	fn set_cursor_grab(&self, grab: bool) -> Result<()> {
 		Ok(())
 	}
@@ -476,6 +639,22 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 		Ok(())
 	}
 
+	fn start_resize_dragging(&self, direction: ResizeDirection) -> Result<()> {
+		Ok(())
+	}
+
+	fn titlebar_double_click(&self) -> Result<()> {
+		Ok(())
+	}
+
+	fn set_drag_regions(&self, drag_regions: Vec<Rect>) -> Result<()> {
+		Ok(())
+	}
+
+	fn start_drag(&self, paths: Vec<PathBuf>, icon: Option<Icon>) -> Result<()> {
+		Ok(())
+	}
+
 	fn eval_script<S: Into<String>>(&self, script: S) -> Result<()> {
 		Ok(())
 	}
@@ -483,6 +662,14 @@ impl<T: UserEvent> Dispatch<T> for MockDispatcher {
 	fn update_menu_item(&self, id: u16, update: MenuUpdate) -> Result<()> {
 		Ok(())
 	}
+
+	fn insert_menu_item(&self, parent: Option<MenuId>, index: Option<usize>, item: CustomMenuItem) -> Result<()> {
+		Ok(())
+	}
+
+	fn remove_menu_item(&self, id: MenuId) -> Result<()> {
+		Ok(())
+	}
 }
 
 #[cfg(feature = "system-tray")]
@@ -502,6 +689,12 @@ impl TrayHandle for MockTrayHandler {
 	fn update_item(&self, id: u16, update: MenuUpdate) -> Result<()> {
 		Ok(())
 	}
+	fn insert_item(&self, parent: Option<MenuId>, index: Option<usize>, item: CustomMenuItem) -> Result<()> {
+		Ok(())
+	}
+	fn remove_item(&self, id: MenuId) -> Result<()> {
+		Ok(())
+	}
 	#[cfg(target_os = "macos")]
 	fn set_icon_as_template(&self, is_template: bool) -> Result<()> {
 		Ok(())
@@ -567,7 +760,7 @@ impl<T: UserEvent> Runtime<T> for MockRuntime {
 	}
 
 	fn create_proxy(&self) -> EventProxy {
-		unimplemented!()
+		EventProxy {}
 	}
 
 	fn handle(&self) -> Self::Handle {
@@ -585,6 +778,9 @@ impl<T: UserEvent> Runtime<T> for MockRuntime {
 	}
 
 	fn create_window(&self, pending: PendingWindow<T, Self>) -> Result<DetachedWindow<T, Self>> {
+		if let Some(on_created) = pending.on_created {
+			on_created(Ok(()));
+		}
 		Ok(DetachedWindow {
 			label: pending.label,
 			dispatcher: MockDispatcher { context: self.context.clone() },
@@ -605,8 +801,10 @@ impl<T: UserEvent> Runtime<T> for MockRuntime {
 		Uuid::new_v4()
 	}
 
-	#[cfg(target_os = "macos")]
-	#[cfg_attr(doc_cfg, doc(cfg(target_os = "macos")))]
+	fn set_default_window_icon(&mut self, icon: Option<Icon>) {}
+
+	fn set_max_frame_rate(&mut self, max_frame_rate: Option<u32>) {}
+
 	fn set_activation_policy(&mut self, activation_policy: millennium_runtime::ActivationPolicy) {}
 
 	fn run_iteration<F: Fn(RunEvent<T>) + 'static>(&mut self, callback: F) -> millennium_runtime::RunIteration {
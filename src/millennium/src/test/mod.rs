@@ -70,7 +70,8 @@ pub fn mock_context<A: Assets>(assets: A) -> crate::Context<A> {
 				security: Default::default(),
 				updater: Default::default(),
 				system_tray: None,
-				macos_private_api: false
+				macos_private_api: false,
+				embedded_assets: Default::default()
 			},
 			build: Default::default(),
 			plugins: Default::default()
@@ -370,15 +370,17 @@ impl<R: Runtime> UpdateBuilder<R> {
 			// If we got a success, we stop the loop
 			// and we set our remote_release variable
 			if let Ok(res) = resp {
-				let res = res.read().await?;
+				let status = res.status().as_u16();
 				// got status code 2XX
-				if StatusCode::from_u16(res.status).map_err(|e| Error::Builder(e.to_string()))?.is_success() {
+				if StatusCode::from_u16(status).map_err(|e| Error::Builder(e.to_string()))?.is_success() {
 					// if we got 204
-					if StatusCode::NO_CONTENT.as_u16() == res.status {
+					// checked before reading the response, since the empty body isn't valid JSON
+					if StatusCode::NO_CONTENT.as_u16() == status {
 						// return with `UpToDate` error
 						// we should catch on the client
 						return Err(Error::UpToDate);
 					};
+					let res = res.read().await?;
 					// Convert the remote result to our local struct
 					let built_release = serde_json::from_value(res.data).map_err(Into::into);
 					// make sure all went well and the remote data is compatible
@@ -506,6 +508,34 @@ impl<R: Runtime> Update<R> {
 			return Err(Error::UnsupportedLinuxPackage);
 		}
 
+		let buffer = self.download(on_chunk, on_download_finish).await?;
+
+		// create memory buffer from our archive (Seek + Read)
+		let mut archive_buffer = Cursor::new(buffer);
+
+		// we make sure the archive is valid and signed with the private key linked with
+		// the publickey
+		verify_signature(&mut archive_buffer, &self.signature, &pub_key)?;
+
+		// we copy the files depending of the operating system
+		// we run the setup, appimage re-install or overwrite the
+		// macos .app
+		#[cfg(target_os = "windows")]
+		copy_files_and_run(
+			archive_buffer,
+			&self.extract_path,
+			self.with_elevated_task,
+			self.app.config().millennium.updater.windows.install_mode.clone().msiexec_args()
+		)?;
+		#[cfg(not(target_os = "windows"))]
+		copy_files_and_run(archive_buffer, &self.extract_path)?;
+
+		// We are done!
+		Ok(())
+	}
+
+	// Download the update archive into memory
+	async fn download<C: Fn(usize, Option<u64>), D: FnOnce()>(&self, on_chunk: C, on_download_finish: D) -> Result<Vec<u8>> {
 		// set our headers
 		let mut headers = self.headers.clone();
 		headers.insert("Accept", HeaderValue::from_str("application/octet-stream").unwrap());
@@ -542,28 +572,7 @@ impl<R: Runtime> Update<R> {
 
 		on_download_finish();
 
-		// create memory buffer from our archive (Seek + Read)
-		let mut archive_buffer = Cursor::new(buffer);
-
-		// we make sure the archive is valid and signed with the private key linked with
-		// the publickey
-		verify_signature(&mut archive_buffer, &self.signature, &pub_key)?;
-
-		// we copy the files depending of the operating system
-		// we run the setup, appimage re-install or overwrite the
-		// macos .app
-		#[cfg(target_os = "windows")]
-		copy_files_and_run(
-			archive_buffer,
-			&self.extract_path,
-			self.with_elevated_task,
-			self.app.config().millennium.updater.windows.install_mode.clone().msiexec_args()
-		)?;
-		#[cfg(not(target_os = "windows"))]
-		copy_files_and_run(archive_buffer, &self.extract_path)?;
-
-		// We are done!
-		Ok(())
+		Ok(buffer)
 	}
 }
 
@@ -883,3 +892,102 @@ where
 	public_key.verify(&data, &signature, true)?;
 	Ok(true)
 }
+
+#[cfg(test)]
+mod test {
+	use std::{cell::Cell, io::Write};
+
+	use mockito::mock;
+
+	use super::*;
+	use crate::{async_runtime::block_on, test::mock_app};
+
+	// A minisign key pair generated for these tests. `SIGNATURE` signs `ARCHIVE`; both keys are base64 encoded like the
+	// `pubkey` setting and the `.sig` files generated by the bundler.
+	const PUB_KEY: &str = concat!(
+		"dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IDRCQTc0OUI1RjI3NkJBRDAKUldUUXVuYnl0VW1uU3dMVnhISWx4ejNIWl",
+		"ZGTm1lWlFmSFc0OUdwZllmYVR6UGpyQXBQcTZjd2MK"
+	);
+	const SIGNATURE: &str = concat!(
+		"dW50cnVzdGVkIGNvbW1lbnQ6IHNpZ25hdHVyZSBmcm9tIG1pbmlzaWduIHNlY3JldCBrZXkKUldUUXVuYnl0VW1uUzRKb0dOSEVXbGtOMWJtSz",
+		"lXTkE4V0lkakZHa21LY1AxMTJHYWdubi9HaXZ2N25VcDRyNzRvWmJ5NEN5RDhPZ0QvMWlpU2lGTFE0T2huN1lRU2NhbWdBPQp0cnVzdGVkIGNv",
+		"bW1lbnQ6IHRpbWVzdGFtcDoxNjU5MzEyMDAwCWZpbGU6dXBkYXRlLnRhci5negpnY1Q2bDBFZ25qNWIxSTNrOG9YcXFvK1FoV1FJRE9zallSMV",
+		"VUR2lEUzJVK1duR0doZC90SjBiYmQ3WVpSVlNKMWpvbjhQcndkTnRhVkVYMzZoZ2NCZz09Cg=="
+	);
+	const ARCHIVE: &[u8] = b"millennium update archive";
+
+	fn url(path: &str) -> String {
+		format!("{}{}", mockito::server_url(), path)
+	}
+
+	fn release() -> String {
+		serde_json::json!({
+			"version": "2.0.0",
+			"notes": "Test release",
+			"pub_date": "2022-08-01T00:00:00Z",
+			"url": url("/archive"),
+			"signature": SIGNATURE
+		})
+		.to_string()
+	}
+
+	fn build_update(urls: &[String]) -> Result<Update<crate::test::MockRuntime>> {
+		let app = mock_app();
+		block_on(builder(app.handle()).current_version(Version::new(1, 0, 0)).urls(urls).build())
+	}
+
+	#[test]
+	fn up_to_date_on_no_content() {
+		let _update = mock("GET", "/update").with_status(204).create();
+
+		assert!(matches!(build_update(&[url("/update")]), Err(Error::UpToDate)));
+	}
+
+	#[test]
+	fn falls_back_to_next_url() {
+		let broken = mock("GET", "/broken").with_status(500).expect(1).create();
+		let release = mock("GET", "/update")
+			.with_header("Content-Type", "application/json")
+			.with_body(release())
+			.expect(1)
+			.create();
+
+		let update = build_update(&[url("/broken"), url("/update")]).unwrap();
+		broken.assert();
+		release.assert();
+		assert!(update.should_update);
+		assert_eq!(update.version, "2.0.0");
+		assert_eq!(update.body.as_deref(), Some("Test release"));
+	}
+
+	#[test]
+	fn downloads_chunked_signed_archive() {
+		let _release = mock("GET", "/update").with_header("Content-Type", "application/json").with_body(release()).create();
+		// a body written by a function is sent with `Transfer-Encoding: chunked`, so the response has no `Content-Length`
+		let _archive = mock("GET", "/archive").with_body_from_fn(|w| w.write_all(ARCHIVE)).create();
+
+		let update = build_update(&[url("/update")]).unwrap();
+		let downloaded = Cell::new(0);
+		let archive = block_on(update.download(
+			|chunk_length, content_length| {
+				assert_eq!(content_length, None);
+				downloaded.set(downloaded.get() + chunk_length);
+			},
+			|| {}
+		))
+		.unwrap();
+		assert_eq!(archive, ARCHIVE);
+		assert_eq!(downloaded.get(), ARCHIVE.len());
+		assert!(verify_signature(&mut Cursor::new(archive), &update.signature, PUB_KEY).unwrap());
+	}
+
+	#[test]
+	fn rejects_tampered_archive() {
+		let _release = mock("GET", "/update").with_header("Content-Type", "application/json").with_body(release()).create();
+		let _archive = mock("GET", "/archive").with_body("tampered update archive").create();
+
+		let update = build_update(&[url("/update")]).unwrap();
+		let archive = block_on(update.download(|_, _| {}, || {})).unwrap();
+		assert!(matches!(verify_signature(&mut Cursor::new(archive), &update.signature, PUB_KEY), Err(Error::Minisign(_))));
+	}
+}
@@ -22,11 +22,14 @@ pub(crate) mod menu;
 
 use std::{
 	fmt,
+	future::Future,
 	hash::{Hash, Hasher},
 	path::PathBuf,
-	sync::Arc
+	sync::Arc,
+	time::Duration
 };
 
+use futures::channel::oneshot;
 pub use menu::{MenuEvent, MenuHandle};
 use millennium_macros::default_runtime;
 use serde::Serialize;
@@ -40,20 +43,23 @@ use crate::{
 	hooks::{InvokePayload, InvokeResponder},
 	manager::WindowManager,
 	runtime::{
-		http::{Request as HttpRequest, Response as HttpResponse},
+		http::{header::HeaderMap, Request as HttpRequest, Response as HttpResponse},
 		menu::Menu,
 		monitor::Monitor as RuntimeMonitor,
-		webview::{WebviewAttributes, WindowBuilder as _},
+		webview::{
+			CookiePersistence, CssId, DevtoolsPosition, InitScriptId, PdfSettings, PermissionKind, PermissionResponse, ScrollBarStyle, WebviewAttributes,
+			WebviewPermissionHandler, WebviewRequestInterceptor, WindowBuilder as _
+		},
 		window::{
 			dpi::{PhysicalPosition, PhysicalSize, Position, Size},
-			DetachedWindow, JsEventListenerKey, PendingWindow
+			AccessibilityPreferences, DetachedWindow, JsEventListenerKey, PendingWindow, Rect, ResizeDirection, WindowCreatedHandler
 		},
 		Dispatch, RuntimeHandle, UserAttentionType
 	},
 	sealed::ManagerBase,
 	sealed::RuntimeOrDispatch,
 	utils::config::WindowUrl,
-	CursorIcon, EventLoopMessage, Icon, Invoke, InvokeError, InvokeMessage, InvokeResolver, Manager, PageLoadPayload, Runtime, Theme, WindowEvent
+	CursorIcon, EventLoopMessage, Icon, Invoke, InvokeError, InvokeMessage, InvokeResolver, LayoutDirection, Manager, PageLoadPayload, Runtime, Theme, WindowEvent
 };
 
 pub(crate) type WebResourceRequestHandler = dyn Fn(&HttpRequest, &mut HttpResponse) + Send + Sync;
@@ -84,6 +90,17 @@ impl From<RuntimeMonitor> for Monitor {
 	}
 }
 
+impl From<Monitor> for RuntimeMonitor {
+	fn from(monitor: Monitor) -> Self {
+		Self {
+			name: monitor.name,
+			size: monitor.size,
+			position: monitor.position,
+			scale_factor: monitor.scale_factor
+		}
+	}
+}
+
 impl Monitor {
 	/// Returns a human-readable name of the monitor.
 	/// Returns None if the monitor doesn't exist anymore.
@@ -118,7 +135,9 @@ pub struct WindowBuilder<'a, R: Runtime> {
 	label: String,
 	pub(crate) window_builder: <R::Dispatcher as Dispatch<EventLoopMessage>>::WindowBuilder,
 	pub(crate) webview_attributes: WebviewAttributes,
-	web_resource_request_handler: Option<Box<WebResourceRequestHandler>>
+	web_resource_request_handler: Option<Box<WebResourceRequestHandler>>,
+	permission_handler: Option<WebviewPermissionHandler>,
+	request_interceptor: Option<(Vec<String>, WebviewRequestInterceptor)>
 }
 
 impl<'a, R: Runtime> fmt::Debug for WindowBuilder<'a, R> {
@@ -146,7 +165,9 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
 			label: label.into(),
 			window_builder: <R::Dispatcher as Dispatch<EventLoopMessage>>::WindowBuilder::new(),
 			webview_attributes: WebviewAttributes::new(url),
-			web_resource_request_handler: None
+			web_resource_request_handler: None,
+			permission_handler: None,
+			request_interceptor: None
 		}
 	}
 
@@ -198,10 +219,122 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
 		self
 	}
 
+	/// Defines a closure that decides whether the page may use the camera, microphone, geolocation and other
+	/// capabilities it asks for. The closure receives the origin of the page making the request, e.g.
+	/// `millennium://localhost` or `https://example.com`.
+	///
+	/// Without a handler, every request is denied.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Clipboard reads are controlled by [`Self::enable_clipboard_access`] instead.
+	/// - **Linux:** [`PermissionResponse::Prompt`] denies the request.
+	/// - **macOS / iOS:** Only camera and microphone requests are handled, on macOS 12+ / iOS 15+.
+	/// - **Android:** Unsupported.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use millennium::{utils::config::WindowUrl, window::WindowBuilder, PermissionKind, PermissionResponse};
+	/// millennium::Builder::default().setup(|app| {
+	/// 	WindowBuilder::new(app, "call", WindowUrl::App("call.html".into()))
+	/// 		.permission_handler(|origin, kind| match kind {
+	/// 			PermissionKind::Camera | PermissionKind::Microphone if origin == "millennium://localhost" => PermissionResponse::Allow,
+	/// 			_ => PermissionResponse::Deny
+	/// 		})
+	/// 		.build()?;
+	/// 	Ok(())
+	/// });
+	/// ```
+	#[must_use]
+	pub fn permission_handler<F: Fn(&str, PermissionKind) -> PermissionResponse + Send + 'static>(mut self, handler: F) -> Self {
+		self.permission_handler.replace(Box::new(handler));
+		self
+	}
+
+	/// Defines a closure that rewrites the headers of the requests the page sends, e.g. to add an `Authorization`
+	/// header to the `fetch` and XHR calls made to your API without exposing the token to the page.
+	///
+	/// Only requests whose URL matches one of `filters` reach the closure, so the other requests don't pay for the
+	/// interception. In a filter, `*` matches any run of characters, e.g. `https://api.example.com/*`.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Requests to custom protocols aren't intercepted; use [`Self::on_web_resource_request`] for
+	///   those.
+	/// - **Linux:** Needs the WebKitGTK web extension headers (`webkit2gtk-web-extension-4.0`) at build time;
+	///   unsupported without them.
+	/// - **macOS / iOS:** Unsupported; WebKit has no API to modify outgoing requests, and `WKURLSchemeHandler` can only
+	///   answer requests to custom protocols.
+	/// - **Android:** Unsupported.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use millennium::{http::header::HeaderValue, utils::config::WindowUrl, window::WindowBuilder};
+	/// millennium::Builder::default().setup(|app| {
+	/// 	WindowBuilder::new(app, "main", WindowUrl::App("index.html".into()))
+	/// 		.request_interceptor(vec!["https://api.example.com/*".into()], |_url, headers| {
+	/// 			headers.insert("Authorization", HeaderValue::from_static("Bearer <token>"));
+	/// 		})
+	/// 		.build()?;
+	/// 	Ok(())
+	/// });
+	/// ```
+	#[must_use]
+	pub fn request_interceptor<F: Fn(&str, &mut HeaderMap) + Send + 'static>(mut self, filters: Vec<String>, interceptor: F) -> Self {
+		self.request_interceptor.replace((filters, Box::new(interceptor)));
+		self
+	}
+
 	/// Creates a new webview window.
-	pub fn build(mut self) -> crate::Result<Window<R>> {
+	///
+	/// This returns as soon as the window is queued on the event loop, so it can be called from any thread, including
+	/// the main thread. Errors raised while the event loop creates the webview are only logged; use
+	/// [`Self::build_async`] to be notified of them.
+	pub fn build(self) -> crate::Result<Window<R>> {
+		self.build_with(None)
+	}
+
+	/// Creates a new webview window, returning a future that resolves once the event loop has created its webview.
+	///
+	/// The future can be awaited from any thread, but don't block the main thread on it (e.g. with
+	/// [`crate::async_runtime::block_on`]), since the webview is created by the event loop running there.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use millennium::{utils::config::WindowUrl, window::WindowBuilder};
+	/// #[millennium::command]
+	/// async fn open_settings(app: millennium::AppHandle) -> Result<(), String> {
+	/// 	let window = WindowBuilder::new(&app, "settings", WindowUrl::App("settings.html".into()))
+	/// 		.build_async()
+	/// 		.await
+	/// 		.map_err(|e| e.to_string())?;
+	/// 	window.set_focus().map_err(|e| e.to_string())
+	/// }
+	/// ```
+	pub fn build_async(self) -> impl Future<Output = crate::Result<Window<R>>> {
+		let (tx, rx) = oneshot::channel();
+		let window = self.build_with(Some(Box::new(move |result| {
+			let _ = tx.send(result);
+		})));
+		async move {
+			let window = window?;
+			match rx.await {
+				Ok(result) => result.map(|()| window).map_err(Into::into),
+				// the runtime dropped the handler without creating the webview
+				Err(_) => Err(crate::Error::CreateWindow)
+			}
+		}
+	}
+
+	fn build_with(mut self, on_created: Option<WindowCreatedHandler>) -> crate::Result<Window<R>> {
 		let web_resource_request_handler = self.web_resource_request_handler.take();
-		let pending = PendingWindow::new(self.window_builder.clone(), self.webview_attributes.clone(), self.label.clone())?;
+		let mut pending = PendingWindow::new(self.window_builder.clone(), self.webview_attributes.clone(), self.label.clone())?;
+		pending.permission_handler = self.permission_handler.take();
+		pending.request_interceptor = self.request_interceptor.take();
+		pending.on_created = on_created;
 		let labels = self.manager.labels().into_iter().collect::<Vec<_>>();
 		let pending = self
 			.manager
@@ -233,6 +366,14 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
 		self
 	}
 
+	/// Show window in the center of the given monitor instead of the one it's created on, e.g. the monitor under the
+	/// cursor or the one with the focused window.
+	#[must_use]
+	pub fn center_on_monitor(mut self, monitor: &Monitor) -> Self {
+		self.window_builder = self.window_builder.center_on_monitor(monitor.clone().into());
+		self
+	}
+
 	/// The initial position of the window's.
 	#[must_use]
 	pub fn position(mut self, x: f64, y: f64) -> Self {
@@ -268,6 +409,27 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
 		self
 	}
 
+	/// Whether the window's minimize button is enabled.
+	#[must_use]
+	pub fn minimizable(mut self, minimizable: bool) -> Self {
+		self.window_builder = self.window_builder.minimizable(minimizable);
+		self
+	}
+
+	/// Whether the window's maximize button is enabled.
+	#[must_use]
+	pub fn maximizable(mut self, maximizable: bool) -> Self {
+		self.window_builder = self.window_builder.maximizable(maximizable);
+		self
+	}
+
+	/// Whether the window's close button is enabled.
+	#[must_use]
+	pub fn closable(mut self, closable: bool) -> Self {
+		self.window_builder = self.window_builder.closable(closable);
+		self
+	}
+
 	/// The title of the window in the title bar.
 	#[must_use]
 	pub fn title<S: Into<String>>(mut self, title: S) -> Self {
@@ -315,6 +477,32 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
 		self
 	}
 
+	/// Forces the layout direction of the window's menu bar and controls, or follows the system locale if None was
+	/// provided. Use [`LayoutDirection::RightToLeft`] for e.g. Arabic or Hebrew UIs.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux**: Only the window and its menu bar are mirrored.
+	#[must_use]
+	pub fn layout_direction(mut self, direction: Option<LayoutDirection>) -> Self {
+		self.window_builder = self.window_builder.layout_direction(direction);
+		self
+	}
+
+	/// Forces the background color of the window's title bar as RGBA, e.g. so a dark-only app gets a dark title bar
+	/// regardless of the system theme. If None was provided, the title bar follows the system theme.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS**: The title bar is made transparent and the color is used as the window's background color.
+	/// - **Windows**: Requires Windows 11. The alpha channel is ignored.
+	/// - **Linux**: The window uses a client-side header bar drawn with the color.
+	#[must_use]
+	pub fn title_bar_color(mut self, color: Option<(u8, u8, u8, u8)>) -> Self {
+		self.window_builder = self.window_builder.title_bar_color(color);
+		self
+	}
+
 	/// Whether the the window should be transparent. If this is true, writing
 	/// colors with alpha values different than `1.0` will produce a transparent
 	/// window.
@@ -354,6 +542,17 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
 		Ok(self)
 	}
 
+	/// Sets the taskbar icon, e.g. a high resolution version of a simpler window icon shown in the title bar.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** A good ceiling here is 256x256.
+	/// - **macOS / Linux:** Unsupported.
+	pub fn taskbar_icon(mut self, icon: Icon) -> crate::Result<Self> {
+		self.window_builder = self.window_builder.taskbar_icon(icon.try_into()?)?;
+		Ok(self)
+	}
+
 	/// Sets whether or not the window icon should be added to the taskbar.
 	#[must_use]
 	pub fn skip_taskbar(mut self, skip: bool) -> Self {
@@ -424,11 +623,278 @@ impl<'a, R: Runtime> WindowBuilder<'a, R> {
 	///
 	/// **macOS** doesn't provide such method and is always enabled by default,
 	/// but you still need to add menu item accelerators to use shortcuts.
+	///
+	/// Access can be changed later with [`Window::set_clipboard_access`].
 	#[must_use]
 	pub fn enable_clipboard_access(mut self) -> Self {
 		self.webview_attributes.clipboard = true;
 		self
 	}
+
+	/// Runs the custom protocol handlers of this window on their own threads with
+	/// the given timeout. Requests that take longer than `timeout` receive a
+	/// `504 Gateway Timeout` response.
+	///
+	/// Handlers are run synchronously on the webview thread by default, so a slow
+	/// handler (e.g. reading a huge file) freezes the window until it returns.
+	/// With a timeout, requests are completed asynchronously without blocking the
+	/// window, at the cost of spawning a thread per request.
+	#[must_use]
+	pub fn custom_protocol_timeout(mut self, timeout: Duration) -> Self {
+		self.webview_attributes.custom_protocol_timeout.replace(timeout);
+		self
+	}
+
+	/// Caches the responses of this window's custom protocol handlers in memory, keeping at most `max_size` bytes of
+	/// response bodies and evicting the least recently used responses first.
+	///
+	/// Custom protocols don't go through the webview's HTTP cache, so without this every navigation runs the handlers
+	/// again, e.g. an SPA reload re-requests all of its JS chunks. Handlers opt in per response:
+	///
+	/// - `Cache-Control: max-age=<seconds>` responses are served from the cache until they expire.
+	/// - Responses with an `ETag` are revalidated; the handler receives the cached tag in `If-None-Match` and can respond
+	///   with `304 Not Modified` to serve the cached response.
+	/// - `Cache-Control: no-store` responses are never cached.
+	#[must_use]
+	pub fn custom_protocol_cache(mut self, max_size: usize) -> Self {
+		self.webview_attributes.custom_protocol_cache_size.replace(max_size);
+		self
+	}
+
+	/// Allows media on the page to play automatically, without the user interacting with it first. Autoplay is blocked
+	/// by default, as it is in browsers.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** All windows sharing a data directory must use the same setting.
+	#[must_use]
+	pub fn autoplay(mut self, autoplay: bool) -> Self {
+		self.webview_attributes.autoplay = autoplay;
+		self
+	}
+
+	/// Disables the webview's built-in context menu, which includes "Inspect Element" when the devtools are enabled.
+	#[must_use]
+	pub fn disable_default_context_menu(mut self) -> Self {
+		self.webview_attributes.default_context_menu_enabled = false;
+		self
+	}
+
+	/// Disables the keyboard shortcuts that open the devtools, such as F12 and Ctrl+Shift+I.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS:** Unsupported; the devtools have no keyboard shortcuts.
+	#[must_use]
+	pub fn disable_devtools_shortcuts(mut self) -> Self {
+		self.webview_attributes.devtools_shortcuts_enabled = false;
+		self
+	}
+
+	/// Renders the webview offscreen: the window is created hidden and is never shown, even if
+	/// [`Window::show`] is called. Use [`Window::take_snapshot`] to capture its contents, e.g. for server-side
+	/// rendering or screenshot generation. Combined with [`Builder::any_thread`](crate::Builder#method.any_thread), the
+	/// app can run on a background thread.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** True offscreen rendering. The window is never mapped; WebKit lays out and paints the page
+	///   offscreen at the window's size.
+	/// - **Windows / macOS:** The window is hidden but the webview is still composited into it, so a desktop session
+	///   is required. On macOS, the app must also stay on the main thread.
+	#[must_use]
+	pub fn headless(mut self, headless: bool) -> Self {
+		self.webview_attributes.headless = headless;
+		self
+	}
+
+	/// Forces overlay or always-visible scroll bars in the webview instead of following the system preference.
+	///
+	/// To style scroll bars per page or per element instead, use the `::-webkit-scrollbar` CSS pseudo-elements, which
+	/// every webview supports.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Overlay scroll bars are the default, unless the user disabled them. Always-visible scroll bars are
+	///   drawn by a user stylesheet, which pages can override.
+	/// - **macOS:** Applies to the whole app while it runs, so all windows should use the same style.
+	/// - **Windows:** Scroll bars are always visible by default. All windows sharing a data directory must use the same
+	///   setting.
+	#[must_use]
+	pub fn scroll_bar_style(mut self, style: ScrollBarStyle) -> Self {
+		self.webview_attributes.scroll_bar_style = style;
+		self
+	}
+
+	/// Where [`Window::open_devtools`] opens the devtools. Defaults to [`DevtoolsPosition::Docked`].
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Unsupported; the devtools always open in a separate window.
+	/// - **Android / iOS:** Unsupported.
+	#[must_use]
+	pub fn devtools_position(mut self, position: DevtoolsPosition) -> Self {
+		self.webview_attributes.devtools_position = position;
+		self
+	}
+
+	/// Suspends the webview whenever the window loses focus while it is hidden or minimized, and resumes it when the
+	/// window regains focus. Visible windows are never suspended, since suspending hides the webview. Disabled by
+	/// default. See [`Window::suspend`].
+	#[must_use]
+	pub fn suspend_when_unfocused(mut self, suspend: bool) -> Self {
+		self.webview_attributes.suspend_when_unfocused = suspend;
+		self
+	}
+
+	/// Adds a script that is evaluated whenever the window's scale factor changes, e.g. when it is dragged to a
+	/// monitor with a different DPI. The new scale factor is available to the script as `scaleFactor`. Use it to swap
+	/// raster assets that don't follow `devicePixelRatio` on their own.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use millennium::{utils::config::WindowUrl, window::WindowBuilder};
+	/// millennium::Builder::default().setup(|app| {
+	/// 	WindowBuilder::new(app, "main", WindowUrl::App("index.html".into()))
+	/// 		.on_scale_change("document.documentElement.dataset.scale = scaleFactor;")
+	/// 		.build()?;
+	/// 	Ok(())
+	/// });
+	/// ```
+	#[must_use]
+	pub fn on_scale_change(mut self, script: &str) -> Self {
+		self.webview_attributes = self.webview_attributes.scale_change_script(script);
+		self
+	}
+
+	/// Re-requests the images served by custom protocols whenever the window's scale factor changes, adding the new
+	/// scale factor to their URL as the `scale` query parameter so the protocol handler can serve the matching asset.
+	/// Images with a `srcset` are left to the webview, which already re-selects them.
+	///
+	/// This is a ready-made [`WindowBuilder::on_scale_change`] script and can be combined with others.
+	#[must_use]
+	pub fn reload_assets_on_scale_change(self) -> Self {
+		self.on_scale_change(include_str!("../scripts/scale_change.js"))
+	}
+
+	/// Whether the webview composites and draws with the GPU. Enabled by default.
+	///
+	/// This is a troubleshooting knob: if users report a blank window, flickering or other rendering glitches,
+	/// typically in virtual machines, over remote desktop or with broken GPU drivers, disabling it forces software
+	/// rendering at the cost of performance.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** All windows sharing a data directory must use the same setting.
+	/// - **macOS:** Best effort; relies on private WebKit preferences.
+	/// - **iOS / Android:** Unsupported.
+	#[must_use]
+	pub fn hardware_acceleration(mut self, enabled: bool) -> Self {
+		self.webview_attributes.hardware_acceleration = enabled;
+		self
+	}
+
+	/// Whether the page bounces ("rubber-bands") when scrolled past its edges. Follows the platform by default; disable
+	/// it for fixed-layout app UIs where the bounce looks out of place.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS:** Relies on a private WebKit API, so it may be ignored by some versions of macOS.
+	/// - **Windows:** Disables the elastic overscroll of precision touchpads. All windows sharing a data directory must
+	///   use the same setting.
+	/// - **Linux / Android:** Unsupported; their webviews don't bounce.
+	#[must_use]
+	pub fn scroll_bounce(mut self, enabled: bool) -> Self {
+		self.webview_attributes.scroll_bounce = enabled;
+		self
+	}
+
+	/// Whether clicking into the window while it is unfocused also clicks the element under the cursor, rather than only
+	/// focusing the window. Useful for panels and tool windows. Disabled by default.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux / Android / iOS:** Unsupported; the first click is already delivered to the page.
+	#[must_use]
+	pub fn accept_first_mouse(mut self, accept_first_mouse: bool) -> Self {
+		self.webview_attributes.accept_first_mouse = accept_first_mouse;
+		self
+	}
+
+	/// Whether pinching on a trackpad or touch screen zooms the page. Set it to `false` if your app handles pinch
+	/// gestures itself. Follows the platform if not set.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS / Windows:** Disabled by default.
+	/// - **Linux / Android / iOS:** Unsupported.
+	#[must_use]
+	pub fn pinch_zoom(mut self, enabled: bool) -> Self {
+		self.webview_attributes = self.webview_attributes.pinch_zoom(enabled);
+		self
+	}
+
+	/// Whether swiping with two fingers navigates back and forward through the page history. Disable it if your app
+	/// uses horizontal swipes itself. Follows the platform if not set.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS / Linux:** Disabled by default.
+	/// - **Linux:** Requires WebKitGTK 2.24 or newer.
+	/// - **Android / iOS:** Unsupported.
+	#[must_use]
+	pub fn swipe_navigation(mut self, enabled: bool) -> Self {
+		self.webview_attributes = self.webview_attributes.swipe_navigation(enabled);
+		self
+	}
+
+	/// Whether the window's cookies are kept across runs of the app. Defaults to [`CookiePersistence::Persistent`],
+	/// which keeps users logged in; use [`CookiePersistence::Session`] to forget every cookie once the app exits.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux / macOS / iOS / Windows:** [`CookiePersistence::Session`] also keeps local storage and caches off the
+	///   disk.
+	/// - **Windows:** [`CookiePersistence::Session`] requires WebView2 Runtime 101 or newer; cookies are persisted on
+	///   older runtimes.
+	/// - **Android:** Unsupported.
+	#[must_use]
+	pub fn cookie_persistence(mut self, persistence: CookiePersistence) -> Self {
+		self.webview_attributes.cookie_persistence = persistence;
+		self
+	}
+
+	/// Whether cookies set by sites other than the page's own are rejected. Disabled by default, which keeps the
+	/// platform's own policy.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Applies to every window sharing the same data directory, unless it uses
+	///   [`CookiePersistence::Session`].
+	/// - **macOS / iOS:** Relies on a private WebKit API, so it may be ignored by some versions of macOS.
+	/// - **Windows / Android:** Unsupported.
+	#[must_use]
+	pub fn block_third_party_cookies(mut self, block: bool) -> Self {
+		self.webview_attributes.block_third_party_cookies = block;
+		self
+	}
+
+	/// Adds a shortcut that always reaches the window's menu and the OS, even when the webview has focus and would
+	/// otherwise consume it. Use this for shortcuts such as `Cmd+Q` and `Cmd+H`, which the webview swallows while a
+	/// text input is focused. Creating the window fails if `accelerator` can't be parsed.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** The key press is posted to the window, so it triggers its menu accelerators and system shortcuts
+	///   such as `Alt+F4`.
+	/// - **Linux:** Unsupported; the accelerators of the window's menu already take precedence over the webview.
+	/// - **Android / iOS:** Unsupported.
+	#[must_use]
+	pub fn passthrough_shortcut(mut self, accelerator: &str) -> Self {
+		self.webview_attributes.passthrough_shortcuts.push(accelerator.to_string());
+		self
+	}
 }
 
 // TODO: expand these docs since this is a pretty important type
@@ -608,6 +1074,13 @@ impl Window<crate::MillenniumWebview> {
 	pub fn with_webview<F: FnOnce(PlatformWebview) + Send + 'static>(&self, f: F) -> crate::Result<()> {
 		self.window.dispatcher.with_webview(|w| f(PlatformWebview(w))).map_err(Into::into)
 	}
+
+	/// Runs the closure on the main thread and returns its result, e.g. to read state that is only accessible from the
+	/// main thread. When called from another thread, this blocks until the event loop has run the closure.
+	#[cfg_attr(doc_cfg, doc(cfg(feature = "millennium_webview")))]
+	pub fn run_on_main_thread_returning<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(&self, f: F) -> crate::Result<T> {
+		self.window.dispatcher.run_on_main_thread_returning(f).map_err(Into::into)
+	}
 }
 
 /// Base window functions.
@@ -649,12 +1122,12 @@ impl<R: Runtime> Window<R> {
 		&self.window.label
 	}
 
-	/// Registers a window event listener.
-	pub fn on_window_event<F: Fn(&WindowEvent) + Send + 'static>(&self, f: F) {
-		self.window.dispatcher.on_window_event(move |event| f(&event.clone().into()));
+	/// Registers a window event listener. The returned id can be passed to [`Self::remove_window_event_listener`].
+	pub fn on_window_event<F: Fn(&WindowEvent) + Send + 'static>(&self, f: F) -> uuid::Uuid {
+		self.window.dispatcher.on_window_event(move |event| f(&event.clone().into()))
 	}
 
-	/// Registers a menu event listener.
+	/// Registers a menu event listener. The returned id can be passed to [`Self::remove_menu_event_listener`].
 	pub fn on_menu_event<F: Fn(MenuEvent) + Send + 'static>(&self, f: F) -> uuid::Uuid {
 		let menu_ids = self.window.menu_ids.clone();
 		self.window.dispatcher.on_menu_event(move |event| {
@@ -663,6 +1136,20 @@ impl<R: Runtime> Window<R> {
 			})
 		})
 	}
+
+	/// Removes a listener registered with [`Self::on_window_event`].
+	///
+	/// Listeners are dropped along with the window, so this is only needed to unsubscribe from a long-lived window.
+	pub fn remove_window_event_listener(&self, id: uuid::Uuid) {
+		self.window.dispatcher.remove_window_event_listener(id);
+	}
+
+	/// Removes a listener registered with [`Self::on_menu_event`].
+	///
+	/// Listeners are dropped along with the window, so this is only needed to unsubscribe from a long-lived window.
+	pub fn remove_menu_event_listener(&self, id: uuid::Uuid) {
+		self.window.dispatcher.remove_menu_event_listener(id);
+	}
 }
 
 /// Window getters.
@@ -709,6 +1196,16 @@ impl<R: Runtime> Window<R> {
 		self.window.dispatcher.outer_size().map_err(Into::into)
 	}
 
+	/// Returns the minimum inner size set with [`WindowBuilder::min_inner_size`] or [`Self::set_min_size`], if any.
+	pub fn min_size(&self) -> crate::Result<Option<PhysicalSize<u32>>> {
+		self.window.dispatcher.min_size().map_err(Into::into)
+	}
+
+	/// Returns the maximum inner size set with [`WindowBuilder::max_inner_size`] or [`Self::set_max_size`], if any.
+	pub fn max_size(&self) -> crate::Result<Option<PhysicalSize<u32>>> {
+		self.window.dispatcher.max_size().map_err(Into::into)
+	}
+
 	/// Gets the window's current fullscreen state.
 	pub fn is_fullscreen(&self) -> crate::Result<bool> {
 		self.window.dispatcher.is_fullscreen().map_err(Into::into)
@@ -729,6 +1226,26 @@ impl<R: Runtime> Window<R> {
 		self.window.dispatcher.is_resizable().map_err(Into::into)
 	}
 
+	/// Gets whether the window's minimize button is enabled.
+	pub fn is_minimizable(&self) -> crate::Result<bool> {
+		self.window.dispatcher.is_minimizable().map_err(Into::into)
+	}
+
+	/// Gets whether the window's maximize button is enabled.
+	pub fn is_maximizable(&self) -> crate::Result<bool> {
+		self.window.dispatcher.is_maximizable().map_err(Into::into)
+	}
+
+	/// Gets whether the window's close button is enabled.
+	pub fn is_closable(&self) -> crate::Result<bool> {
+		self.window.dispatcher.is_closable().map_err(Into::into)
+	}
+
+	/// Gets whether the window is shown on every virtual desktop. See [`Window::set_visible_on_all_workspaces`].
+	pub fn is_visible_on_all_workspaces(&self) -> crate::Result<bool> {
+		self.window.dispatcher.is_visible_on_all_workspaces().map_err(Into::into)
+	}
+
 	/// Gets the window's current vibility state.
 	pub fn is_visible(&self) -> crate::Result<bool> {
 		self.window.dispatcher.is_visible().map_err(Into::into)
@@ -795,19 +1312,52 @@ impl<R: Runtime> Window<R> {
 	/// ## Platform-specific
 	///
 	/// - **macOS**: Supported on macOS 10.14+.
-	/// - **Linux**: Not implemented, always returns [`Theme::Light`].
+	/// - **Linux**: Reads the `color-scheme` setting of the XDG desktop portal, falling back to the GTK theme.
 	pub fn theme(&self) -> crate::Result<Theme> {
 		self.window.dispatcher.theme().map_err(Into::into)
 	}
+
+	/// Returns the accent color the user picked for the system, as RGBA.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows**: The DWM colorization color.
+	/// - **macOS**: Supported on macOS 10.14+.
+	/// - **Linux**: Reads the `accent-color` setting of the XDG desktop portal, falling back to the GTK theme's selection color.
+	/// - **iOS / Android**: Unsupported, always returns `None`.
+	pub fn accent_color(&self) -> crate::Result<Option<(u8, u8, u8, u8)>> {
+		self.window.dispatcher.accent_color().map_err(Into::into)
+	}
+
+	/// Returns the display preferences set in the system's accessibility settings, e.g. to skip animations or
+	/// vibrancy effects the user asked to reduce.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS**: `reduce_motion` is supported on macOS 10.12+.
+	/// - **Linux**: Reads the XDG desktop portal, falling back to the GTK settings. `reduce_transparency` is always
+	///   `false`.
+	/// - **iOS / Android**: Unsupported, always returns the default preferences.
+	pub fn accessibility_preferences(&self) -> crate::Result<AccessibilityPreferences> {
+		self.window.dispatcher.accessibility_preferences().map_err(Into::into)
+	}
 }
 
 /// Window setters and actions.
 impl<R: Runtime> Window<R> {
 	/// Centers the window.
+	///
+	/// Falls back to the primary monitor if the window's monitor isn't known. If no monitor is known at all (e.g. on
+	/// Wayland before the window is mapped), the window is centered the next time it's resized or moved.
 	pub fn center(&self) -> crate::Result<()> {
 		self.window.dispatcher.center().map_err(Into::into)
 	}
 
+	/// Centers the window on the given monitor, moving it there if it's on another one.
+	pub fn center_on_monitor(&self, monitor: &Monitor) -> crate::Result<()> {
+		self.window.dispatcher.center_on_monitor(monitor.clone().into()).map_err(Into::into)
+	}
+
 	/// Requests user attention to the window, this has no effect if the
 	/// application is already focused. How requesting for user attention
 	/// manifests is platform dependent, see `UserAttentionType` for details.
@@ -831,11 +1381,154 @@ impl<R: Runtime> Window<R> {
 		self.window.dispatcher.print().map_err(Into::into)
 	}
 
+	/// Prints the contents of the webview to a PDF file at `path` without opening the print dialog, and returns the
+	/// path of the written file once it has been saved.
+	///
+	/// Blocks until printing has finished, so it must not be called on the main thread, usually on the
+	/// [`run`](crate::App#method.run) closure. Use an async command or [`std::thread::spawn`] instead.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** `path` must be absolute.
+	/// - **macOS:** The page size, margins and orientation are ignored; the content is rendered onto a single page.
+	///   Requires macOS 11+.
+	pub fn print_to_pdf<P: Into<PathBuf>>(&self, path: P, settings: PdfSettings) -> crate::Result<PathBuf> {
+		self.window.dispatcher.print_to_pdf(path.into(), settings).map_err(Into::into)
+	}
+
+	/// Captures the webview as a PNG image. Works on [headless](WindowBuilder::headless) windows too.
+	///
+	/// Blocks until the image is ready, so it must not be called on the main thread.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Android:** Unsupported.
+	pub fn take_snapshot(&self) -> crate::Result<Vec<u8>> {
+		self.window.dispatcher.take_snapshot().map_err(Into::into)
+	}
+
+	/// Enables or disables clipboard access for the page, e.g. after asking the user for consent.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Only reading the clipboard is gated; disabling denies the permission request instead of
+	///   letting WebView2 prompt the user.
+	/// - **macOS / iOS:** Unsupported. Clipboard access is always enabled.
+	/// - **Android:** Unsupported.
+	pub fn set_clipboard_access(&self, enabled: bool) -> crate::Result<()> {
+		self.window.dispatcher.set_clipboard_access(enabled).map_err(Into::into)
+	}
+
+	/// Inserts CSS into the webview as a user stylesheet. Unlike evaluating a script that adds a `<style>` element,
+	/// the CSS stays applied after navigating to another page.
+	///
+	/// Blocks until the CSS has been inserted, so it must not be called on the main thread.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** The CSS applies to every window sharing the same data directory.
+	/// - **Android:** The CSS is lost on navigation.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// use millennium::Manager;
+	///
+	/// millennium::Builder::default().setup(|app| {
+	/// 	let window = app.get_window("main").unwrap();
+	/// 	std::thread::spawn(move || {
+	/// 		let id = window.insert_css("body { background: black; }").unwrap();
+	/// 		// ...
+	/// 		window.remove_css(id).unwrap();
+	/// 	});
+	/// 	Ok(())
+	/// });
+	/// ```
+	pub fn insert_css(&self, css: &str) -> crate::Result<CssId> {
+		self.window.dispatcher.insert_css(css).map_err(Into::into)
+	}
+
+	/// Removes CSS inserted with [`Window::insert_css`].
+	pub fn remove_css(&self, id: CssId) -> crate::Result<()> {
+		self.window.dispatcher.remove_css(id).map_err(Into::into)
+	}
+
+	/// Adds a script that runs before any other script on every page loaded afterwards, like
+	/// [`WindowBuilder::initialization_script`]. The current page is not affected.
+	///
+	/// Blocks until the script has been added, so it must not be called on the main thread.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** The script runs in every window sharing the same data directory.
+	/// - **Android:** Unsupported; returns an error.
+	pub fn add_init_script(&self, js: &str) -> crate::Result<InitScriptId> {
+		self.window.dispatcher.add_init_script(js).map_err(Into::into)
+	}
+
+	/// Removes a script added with [`Window::add_init_script`], so it no longer runs on pages loaded afterwards.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Android:** Unsupported.
+	pub fn remove_init_script(&self, id: InitScriptId) -> crate::Result<()> {
+		self.window.dispatcher.remove_init_script(id).map_err(Into::into)
+	}
+
+	/// Gets the resident memory in bytes of the process rendering the webview, for diagnostics. Returns `None` if it
+	/// can't be determined.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Reports the WebView2 browser process, which is shared by all windows.
+	/// - **Linux:** Reports the combined memory of all WebKit web processes of the application.
+	/// - **iOS / Android:** Unsupported, always returns `None`.
+	pub fn memory_usage(&self) -> crate::Result<Option<u64>> {
+		self.window.dispatcher.memory_usage().map_err(Into::into)
+	}
+
+	/// Suspends the webview to reduce its CPU usage while the window isn't being used, e.g. while it's minimized or
+	/// hidden in the tray. Emits [`WindowEvent::Suspended`](crate::WindowEvent::Suspended) and the
+	/// `millennium://suspend` event once the webview is suspended.
+	///
+	/// The webview is hidden while it is suspended, so the window shows no content until it is resumed.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** WebView2 freezes the page's timers, scripts and rendering until it is resumed.
+	/// - **Linux / macOS / iOS:** WebKit treats the hidden page like a background tab: it stops rendering and throttles
+	///   its timers. Media is muted on WebKitGTK 2.30+ and paused on macOS 12+ / iOS 15+.
+	/// - **Android:** Unsupported.
+	pub fn suspend(&self) -> crate::Result<()> {
+		self.window.dispatcher.suspend().map_err(Into::into)
+	}
+
+	/// Resumes a webview suspended with [`Window::suspend`]. Emits
+	/// [`WindowEvent::Suspended`](crate::WindowEvent::Suspended) and the `millennium://resume` event once the webview is
+	/// resumed.
+	pub fn resume(&self) -> crate::Result<()> {
+		self.window.dispatcher.resume().map_err(Into::into)
+	}
+
 	/// Determines if this window should be resizable.
 	pub fn set_resizable(&self, resizable: bool) -> crate::Result<()> {
 		self.window.dispatcher.set_resizable(resizable).map_err(Into::into)
 	}
 
+	/// Enables or disables this window's minimize button.
+	pub fn set_minimizable(&self, minimizable: bool) -> crate::Result<()> {
+		self.window.dispatcher.set_minimizable(minimizable).map_err(Into::into)
+	}
+
+	/// Enables or disables this window's maximize button.
+	pub fn set_maximizable(&self, maximizable: bool) -> crate::Result<()> {
+		self.window.dispatcher.set_maximizable(maximizable).map_err(Into::into)
+	}
+
+	/// Enables or disables this window's close button.
+	pub fn set_closable(&self, closable: bool) -> crate::Result<()> {
+		self.window.dispatcher.set_closable(closable).map_err(Into::into)
+	}
+
 	/// Set this window's title.
 	pub fn set_title(&self, title: &str) -> crate::Result<()> {
 		self.window.dispatcher.set_title(title.to_string()).map_err(Into::into)
@@ -897,6 +1590,31 @@ impl<R: Runtime> Window<R> {
 		self.window.dispatcher.set_always_on_top(always_on_top).map_err(Into::into)
 	}
 
+	/// Shows this window on every virtual desktop instead of only the one it was opened on, e.g. for a quick
+	/// launcher that should pop up wherever the user is.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS:** The window joins all spaces.
+	/// - **Linux:** Sets the `_NET_WM_STATE_STICKY` hint, which some window managers ignore.
+	/// - **Windows / iOS / Android:** Unsupported.
+	pub fn set_visible_on_all_workspaces(&self, visible_on_all_workspaces: bool) -> crate::Result<()> {
+		self.window.dispatcher.set_visible_on_all_workspaces(visible_on_all_workspaces).map_err(Into::into)
+	}
+
+	/// Sets the opacity of this whole window, decorations included, from `0.0` (fully transparent) to `1.0` (fully
+	/// opaque). Values outside that range are clamped.
+	///
+	/// This is not the same as [`WindowBuilder::transparent`], which only makes the window background
+	/// transparent so the page can draw with per-pixel alpha; opacity fades everything, page content included.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Requires a compositing window manager.
+	pub fn set_opacity(&self, opacity: f64) -> crate::Result<()> {
+		self.window.dispatcher.set_opacity(opacity).map_err(Into::into)
+	}
+
 	/// Resizes this window.
 	pub fn set_size<S: Into<Size>>(&self, size: S) -> crate::Result<()> {
 		self.window.dispatcher.set_size(size.into()).map_err(Into::into)
@@ -912,6 +1630,16 @@ impl<R: Runtime> Window<R> {
 		self.window.dispatcher.set_max_size(size.map(|s| s.into())).map_err(Into::into)
 	}
 
+	/// Keeps this window's width-to-height ratio fixed while the user resizes it, e.g. `Some(16.0 / 9.0)` for a video
+	/// player. Pass `None` to allow free resizing again.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux:** Replaces the constraints set by [`Self::set_min_size`] and [`Self::set_max_size`].
+	pub fn set_aspect_ratio(&self, aspect_ratio: Option<f64>) -> crate::Result<()> {
+		self.window.dispatcher.set_aspect_ratio(aspect_ratio).map_err(Into::into)
+	}
+
 	/// Sets this window's position.
 	pub fn set_position<Pos: Into<Position>>(&self, position: Pos) -> crate::Result<()> {
 		self.window.dispatcher.set_position(position.into()).map_err(Into::into)
@@ -932,11 +1660,47 @@ impl<R: Runtime> Window<R> {
 		self.window.dispatcher.set_icon(icon.try_into()?).map_err(Into::into)
 	}
 
+	/// Sets this window's taskbar icon, separately from the window icon shown in the title bar.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** A good ceiling here is 256x256.
+	/// - **macOS / Linux:** Unsupported.
+	pub fn set_taskbar_icon(&self, icon: Icon) -> crate::Result<()> {
+		self.window.dispatcher.set_taskbar_icon(icon.try_into()?).map_err(Into::into)
+	}
+
 	/// Whether to show the window icon in the task bar or not.
 	pub fn set_skip_taskbar(&self, skip: bool) -> crate::Result<()> {
 		self.window.dispatcher.set_skip_taskbar(skip).map_err(Into::into)
 	}
 
+	/// Sets the file this window represents. On macOS, its icon is shown in the title bar and can be dragged to move
+	/// the file or Cmd-clicked to reveal its path, like in native document editors. Pass an empty path to remove it.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux:** Unsupported.
+	pub fn set_represented_filename<P: Into<PathBuf>>(&self, path: P) -> crate::Result<()> {
+		self.window.dispatcher.set_represented_filename(path.into()).map_err(Into::into)
+	}
+
+	/// Sets the colors of the title bar background, the title text, and the window border as RGBA, so branded apps
+	/// can match their UI while keeping native decorations. `None` restores the system's color.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Requires Windows 11; does nothing on older versions. The alpha channel is ignored.
+	/// - **macOS / Linux:** Unsupported.
+	pub fn set_title_bar_colors(
+		&self,
+		caption: Option<(u8, u8, u8, u8)>,
+		text: Option<(u8, u8, u8, u8)>,
+		border: Option<(u8, u8, u8, u8)>
+	) -> crate::Result<()> {
+		self.window.dispatcher.set_title_bar_colors(caption, text, border).map_err(Into::into)
+	}
+
 	/// Grabs the cursor, preventing it from leaving the window.
 	///
 	/// There's no guarantee that the cursor will be hidden. You should
@@ -978,6 +1742,48 @@ impl<R: Runtime> Window<R> {
 	pub fn start_dragging(&self) -> crate::Result<()> {
 		self.window.dispatcher.start_dragging().map_err(Into::into)
 	}
+
+	/// Starts resizing the window from the given border or corner, for custom resize handles on undecorated windows.
+	/// Call this in response to a mousedown in the webview.
+	pub fn start_resize_dragging(&self, direction: ResizeDirection) -> crate::Result<()> {
+		self.window.dispatcher.start_resize_dragging(direction).map_err(Into::into)
+	}
+
+	/// Performs the action the system runs when a native title bar is double-clicked, so custom title bars can
+	/// behave the same way. This usually maximizes or restores the window.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS:** Respects the "Double-click a window's title bar to" preference, which can also minimize the window
+	///   or do nothing.
+	/// - **Linux:** Respects the `gtk-titlebar-double-click` setting.
+	pub fn titlebar_double_click(&self) -> crate::Result<()> {
+		self.window.dispatcher.titlebar_double_click().map_err(Into::into)
+	}
+
+	/// Sets the regions of the window that move it when dragged, for custom window decorations. Pressing the left mouse
+	/// button inside one of them moves the window like [`Self::start_dragging`], while clicks elsewhere reach the
+	/// webview as usual. Each call replaces the previous regions; pass an empty list to remove them.
+	///
+	/// The rectangles are relative to the top-left corner of the window's client area. The regions only move the
+	/// window; they don't get the other title bar behaviors such as maximizing on double click, see
+	/// [`Self::titlebar_double_click`].
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / Linux:** The resize borders of undecorated windows take precedence over the regions.
+	/// - **iOS / Android:** Unsupported.
+	pub fn set_drag_regions(&self, drag_regions: Vec<Rect>) -> crate::Result<()> {
+		self.window.dispatcher.set_drag_regions(drag_regions).map_err(Into::into)
+	}
+
+	/// Starts dragging the given files out of the window, e.g. into the system
+	/// file manager. Call this in response to a mousedown in the webview.
+	///
+	/// If `icon` is `None`, the platform's file icon is used as the drag image.
+	pub fn start_drag(&self, paths: Vec<PathBuf>, icon: Option<Icon>) -> crate::Result<()> {
+		self.window.dispatcher.start_drag(paths, icon.map(TryInto::try_into).transpose()?).map_err(Into::into)
+	}
 }
 
 /// Webview APIs.
@@ -1078,6 +1884,20 @@ impl<R: Runtime> Window<R> {
 		self.window.dispatcher.open_devtools();
 	}
 
+	/// Opens the developer tools window (Web Inspector) detached from the window, regardless of
+	/// [`WindowBuilder::devtools_position`].
+	/// The devtools is only enabled on debug builds or with the `devtools` feature flag.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS:** This is a private API on macOS, so you cannot use this if your application will be published on the
+	///   App Store.
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	#[cfg_attr(doc_cfg, doc(cfg(any(debug_assertions, feature = "devtools"))))]
+	pub fn open_devtools_detached(&self) {
+		self.window.dispatcher.open_devtools_detached();
+	}
+
 	/// Closes the developer tools window (Web Inspector).
 	/// The devtools is only enabled on debug builds or with the `devtools` feature flag.
 	///
@@ -1150,13 +1970,7 @@ impl<R: Runtime> Window<R> {
 	}
 
 	pub(crate) fn emit_internal<S: Serialize>(&self, event: &str, source_window_label: Option<&str>, payload: S) -> crate::Result<()> {
-		self.eval(&format!(
-			"window['{}']({{event: {}, windowLabel: {}, payload: {}}})",
-			self.manager.event_emit_function_name(),
-			serde_json::to_string(event)?,
-			serde_json::to_string(&source_window_label)?,
-			serde_json::to_value(payload)?,
-		))?;
+		self.eval(&self.manager.event_emit_script(event, source_window_label, payload)?)?;
 		Ok(())
 	}
 
@@ -23,7 +23,7 @@ use millennium_macros::default_runtime;
 
 use crate::{
 	runtime::{
-		menu::{MenuHash, MenuId, MenuIdRef, MenuUpdate},
+		menu::{CustomMenuItem, MenuHash, MenuId, MenuIdRef, MenuUpdate},
 		Dispatch
 	},
 	Runtime
@@ -92,6 +92,25 @@ impl<R: Runtime> MenuHandle<R> {
 		panic!("item id not found")
 	}
 
+	/// Inserts a custom item into the menu without rebuilding it from the app side.
+	///
+	/// The item is inserted into the submenu assigned the `parent` id with [`Submenu::id`](crate::Submenu::id), or
+	/// into the menu bar itself if `parent` is `None`. It is placed at `index`, or appended if `index` is `None` or past
+	/// the end of the menu.
+	pub fn insert_item(&self, parent: Option<MenuIdRef<'_>>, index: Option<usize>, item: CustomMenuItem) -> crate::Result<()> {
+		let (hash, id) = (item.id, item.id_str.clone());
+		self.dispatcher.insert_menu_item(parent.map(Into::into), index, item)?;
+		self.ids.lock().unwrap().insert(hash, id);
+		Ok(())
+	}
+
+	/// Removes the custom item with the given `id` from the menu.
+	pub fn remove_item(&self, id: MenuIdRef<'_>) -> crate::Result<()> {
+		self.dispatcher.remove_menu_item(id.into())?;
+		self.ids.lock().unwrap().retain(|_, item_id| item_id != id);
+		Ok(())
+	}
+
 	/// Shows the menu.
 	pub fn show(&self) -> crate::Result<()> {
 		self.dispatcher.show_menu().map_err(Into::into)
@@ -16,8 +16,8 @@
 
 use std::{
 	collections::BTreeMap,
-	fs::{remove_dir_all, write},
-	path::PathBuf,
+	fs::{read_to_string, remove_dir_all, rename, write},
+	path::{Path, PathBuf},
 	process::{Command, Stdio}
 };
 
@@ -29,7 +29,7 @@ use super::{
 	super::{common::CommandExt, path_utils},
 	debian
 };
-use crate::Settings;
+use crate::{Settings, UpdaterSettings};
 /// Bundles the project.
 /// Returns a vector of PathBuf that shows where the AppImage was created.
 pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
@@ -42,8 +42,9 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 	let package_dir = settings.project_out_directory().join("bundle/appimage_deb");
 
 	// generate deb_folder structure
-	let (_, icons) = debian::generate_data(settings, &package_dir)?;
+	let (data_dir, icons) = debian::generate_data(settings, &package_dir)?;
 	let icons: Vec<debian::DebIcon> = icons.into_iter().collect();
+	integrate_desktop_file(settings, &data_dir)?;
 
 	let output_path = settings.project_out_directory().join("bundle/appimage");
 	if output_path.exists() {
@@ -56,10 +57,14 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 	path_utils::create(app_dir_path, true)?;
 
 	let upcase_app_name = settings.main_binary_name().to_uppercase();
+	let target_arch = settings.target().split('-').next().unwrap();
+	// unversioned, so that the update information of every release points to the latest one
+	let zsync_filename = format!("{}_{}.AppImage.zsync", settings.main_binary_name(), arch);
+	let update_information = settings.updater().and_then(|updater| update_information(updater, target_arch, &zsync_filename));
 
 	// setup data to insert into shell script
 	let mut sh_map = BTreeMap::new();
-	sh_map.insert("arch", settings.target().split('-').next().unwrap());
+	sh_map.insert("arch", target_arch);
 	sh_map.insert("app_name", settings.main_binary_name());
 	sh_map.insert("app_name_uppercase", &upcase_app_name);
 	sh_map.insert("appimage_filename", &appimage_filename);
@@ -85,6 +90,9 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 		.to_string_lossy()
 		.to_string();
 	sh_map.insert("icon_path", &larger_icon_path);
+	if let Some(update_information) = &update_information {
+		sh_map.insert("update_information", update_information);
+	}
 
 	// initialize shell script template.
 	let mut handlebars = Handlebars::new();
@@ -117,5 +125,137 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 		.context("error running appimage.sh")?;
 
 	remove_dir_all(&package_dir)?;
-	Ok(vec![appimage_path])
+
+	if update_information.is_some() {
+		// written by linuxdeploy next to the AppImage; rename it to the file the update information points to
+		let zsync_path = appimage_path.with_file_name(zsync_filename);
+		rename(appimage_path.with_file_name(format!("{}.zsync", appimage_filename)), &zsync_path)?;
+		Ok(vec![appimage_path, zsync_path])
+	} else {
+		Ok(vec![appimage_path])
+	}
+}
+
+/// The update information to embed in the AppImage when the updater is active: the `zsync_filename` in the directory
+/// of the first updater endpoint, with its `{{target}}` and `{{arch}}` variables replaced.
+fn update_information(updater: &UpdaterSettings, arch: &str, zsync_filename: &str) -> Option<String> {
+	if !updater.active {
+		return None;
+	}
+	let endpoint = updater.endpoints.as_ref()?.first()?.replace("{{target}}", "linux").replace("{{arch}}", arch);
+	let endpoint = endpoint.split(&['?', '#'][..]).next().unwrap_or_default();
+	let directory = &endpoint[..endpoint.rfind('/')? + 1];
+	Some(format!("zsync|{}{}", directory, zsync_filename))
+}
+
+/// Adjusts the desktop file generated for the Debian package to what AppImage desktop integration tools expect.
+fn integrate_desktop_file(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
+	let desktop_file_path = data_dir.join("usr/share/applications").join(format!("{}.desktop", settings.main_binary_name()));
+	let entry = read_to_string(&desktop_file_path)?;
+	write(&desktop_file_path, appimage_desktop_entry(&entry, settings.version_string()))?;
+	Ok(())
+}
+
+/// appimagetool rejects desktop files without a category, and integration tools like appimaged show
+/// `X-AppImage-Version` to tell apart several versions of the same app.
+fn appimage_desktop_entry(entry: &str, version: &str) -> String {
+	let mut output = String::with_capacity(entry.len());
+	for line in entry.lines() {
+		if line == "Categories=" {
+			output.push_str("Categories=Utility;");
+		} else {
+			output.push_str(line);
+		}
+		output.push('\n');
+	}
+	output.push_str(&format!("X-AppImage-Version={}\n", version));
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{appimage_desktop_entry, update_information};
+	use crate::UpdaterSettings;
+
+	#[test]
+	fn desktop_entry_has_category_and_version() {
+		let entry = "[Desktop Entry]\nCategories=\nExec=app\nIcon=app\nName=App\nTerminal=false\nType=Application\n";
+		assert_eq!(
+			appimage_desktop_entry(entry, "1.2.3"),
+			"[Desktop Entry]\nCategories=Utility;\nExec=app\nIcon=app\nName=App\nTerminal=false\nType=Application\nX-AppImage-Version=1.2.3\n"
+		);
+
+		let entry = "[Desktop Entry]\nCategories=Development;\nExec=app\n";
+		assert_eq!(appimage_desktop_entry(entry, "1.2.3"), "[Desktop Entry]\nCategories=Development;\nExec=app\nX-AppImage-Version=1.2.3\n");
+	}
+
+	#[test]
+	fn update_information_points_next_to_endpoint() {
+		let updater = |endpoint: &str| UpdaterSettings {
+			active: true,
+			endpoints: Some(vec![endpoint.into(), "https://fallback.app.dev/latest.json".into()]),
+			..Default::default()
+		};
+		let information = |endpoint: &str| update_information(&updater(endpoint), "x86_64", "app_amd64.AppImage.zsync");
+
+		assert_eq!(information("https://my.cdn.com/latest.json").as_deref(), Some("zsync|https://my.cdn.com/app_amd64.AppImage.zsync"));
+		assert_eq!(
+			information("https://updates.app.dev/{{target}}?version={{current_version}}&arch={{arch}}").as_deref(),
+			Some("zsync|https://updates.app.dev/app_amd64.AppImage.zsync")
+		);
+		assert_eq!(
+			information("https://releases.myapp.com/update/{{target}}/{{arch}}/{{current_version}}").as_deref(),
+			Some("zsync|https://releases.myapp.com/update/linux/x86_64/app_amd64.AppImage.zsync")
+		);
+
+		assert!(update_information(&UpdaterSettings { active: false, ..updater("https://my.cdn.com/latest.json") }, "x86_64", "app.zsync").is_none());
+		assert!(update_information(&UpdaterSettings { active: true, ..Default::default() }, "x86_64", "app.zsync").is_none());
+	}
+
+	/// Embeds the update information like `linuxdeploy-plugin-appimage` does, by passing it to `appimagetool`, and
+	/// checks that it ends up in the `.upd_info` section. Needs `appimagetool` and `readelf` on the `PATH`.
+	#[test]
+	#[ignore = "needs appimagetool"]
+	fn appimagetool_embeds_update_information() {
+		use std::{fs, os::unix::fs::PermissionsExt, process::Command};
+
+		let information = update_information(
+			&UpdaterSettings {
+				active: true,
+				endpoints: Some(vec!["https://my.cdn.com/latest.json".into()]),
+				..Default::default()
+			},
+			"x86_64",
+			"app_amd64.AppImage.zsync"
+		)
+		.unwrap();
+
+		let tmp = tempfile::tempdir().unwrap();
+		let app_dir = tmp.path().join("app.AppDir");
+		fs::create_dir(&app_dir).unwrap();
+		fs::write(app_dir.join("AppRun"), "#!/bin/sh\n").unwrap();
+		fs::set_permissions(app_dir.join("AppRun"), fs::Permissions::from_mode(0o755)).unwrap();
+		fs::write(app_dir.join("app.desktop"), "[Desktop Entry]\nCategories=Utility;\nExec=app\nIcon=app\nName=App\nType=Application\n").unwrap();
+		image::RgbaImage::new(1, 1).save(app_dir.join("app.png")).unwrap();
+
+		let appimage = tmp.path().join("app_amd64.AppImage");
+		let status = Command::new("appimagetool")
+			.env("ARCH", "x86_64")
+			.arg("--updateinformation")
+			.arg(&information)
+			.arg(&app_dir)
+			.arg(&appimage)
+			.current_dir(tmp.path())
+			.status()
+			.unwrap();
+		assert!(status.success());
+
+		let list = Command::new("appimagetool").arg("--list").arg(&appimage).output().unwrap();
+		assert!(list.status.success());
+		assert!(String::from_utf8_lossy(&list.stdout).contains("app.desktop"));
+
+		let section = Command::new("readelf").arg("--string-dump=.upd_info").arg(&appimage).output().unwrap();
+		assert!(String::from_utf8_lossy(&section.stdout).contains(&information));
+		assert!(tmp.path().join("app_amd64.AppImage.zsync").exists());
+	}
 }
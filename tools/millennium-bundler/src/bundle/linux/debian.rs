@@ -21,8 +21,10 @@
 //     control.tar.gz          # Contains files controlling the installation:
 //         control                  # Basic package metadata
 //         md5sums                  # Checksums for files in data.tar.gz below
+//         preinst                  # Pre-installation script (optional)
 //         postinst                 # Post-installation script (optional)
 //         prerm                    # Pre-uninstallation script (optional)
+//         postrm                   # Post-uninstallation script (optional)
 //     data.tar.gz             # Contains files to be installed:
 //         usr/bin/foobar                            # Binary executable file
 //         usr/share/applications/foobar.desktop     # Desktop file (for apps)
@@ -31,14 +33,15 @@
 //
 // For cargo-bundle, we put bundle resource files under /usr/lib/package_name/,
 // and then generate the desktop file and control file from the bundle
-// metadata, as well as generating the md5sums file.  Currently we do not
-// generate postinst or prerm files.
+// metadata, as well as generating the md5sums file. The maintainer scripts are
+// copied from the paths given in the `deb` settings, if any.
 
 use std::{
 	collections::BTreeSet,
 	ffi::OsStr,
 	fs::{self, File},
-	io::{self, Write},
+	io::{self, Read, Write},
+	os::unix::fs::PermissionsExt,
 	path::{Path, PathBuf}
 };
 
@@ -88,6 +91,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 	// Generate control files.
 	let control_dir = package_dir.join("control");
 	generate_control_file(settings, arch, &control_dir, &data_dir).with_context(|| "Failed to create control file")?;
+	copy_maintainer_scripts(settings, &control_dir).with_context(|| "Failed to copy maintainer scripts")?;
 	generate_md5sums(&control_dir, &data_dir).with_context(|| "Failed to create md5sums file")?;
 
 	// Generate `debian-binary` file; see
@@ -217,6 +221,38 @@ fn generate_md5sums(control_dir: &Path, data_dir: &Path) -> crate::Result<()> {
 	Ok(())
 }
 
+/// Copies the maintainer scripts (`preinst`, `postinst`, `prerm` and `postrm`) into the `control_dir`.
+/// dpkg runs them as programs, so they must be executable and start with a `#!` line.
+fn copy_maintainer_scripts(settings: &Settings, control_dir: &Path) -> crate::Result<()> {
+	let deb = settings.deb();
+	for (name, script) in [("preinst", &deb.preinst), ("postinst", &deb.postinst), ("prerm", &deb.prerm), ("postrm", &deb.postrm)] {
+		let script = match script {
+			Some(script) => script,
+			None => continue
+		};
+
+		let mut shebang = [0; 2];
+		File::open(script)
+			.and_then(|mut file| file.read_exact(&mut shebang))
+			.with_context(|| format!("Failed to read {} script {}", name, script.display()))?;
+		if &shebang != b"#!" {
+			return Err(crate::Error::GenericError(format!("{} script {} must start with a `#!` line", name, script.display())));
+		}
+		if fs::metadata(script)?.permissions().mode() & 0o111 == 0 {
+			return Err(crate::Error::GenericError(format!(
+				"{} script {} is not executable; run `chmod +x {}`",
+				name,
+				script.display(),
+				script.display()
+			)));
+		}
+
+		// `fs::copy` keeps the permissions, which the control archive preserves
+		common::copy_file(script, control_dir.join(name))?;
+	}
+	Ok(())
+}
+
 /// Copy the bundle's resource files into an appropriate directory under the
 /// `data_dir`.
 fn copy_resource_files(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
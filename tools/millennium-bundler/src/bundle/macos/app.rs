@@ -85,14 +85,16 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 		// sign application
 		sign(app_bundle_path.clone(), identity, settings, true)?;
 		// notarization is required for distribution
-		match notarize_auth_args() {
+		match notarize_auth_args(settings) {
 			Ok(args) => {
-				notarize(app_bundle_path.clone(), args, settings)?;
+				notarize(app_bundle_path.clone(), args)?;
 			}
 			Err(e) => {
-				warn!("skipping app notarization, {}", e.to_string());
+				warn!("Skipping app notarization: {}", e);
 			}
 		}
+	} else {
+		info!("Skipping code signing: no signing identity set with `macOS.signingIdentity` or APPLE_SIGNING_IDENTITY");
 	}
 
 	Ok(vec![app_bundle_path])
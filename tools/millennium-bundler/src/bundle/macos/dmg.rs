@@ -137,6 +137,10 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
 	// Sign DMG if needed
 	if let Some(identity) = &settings.macos().signing_identity {
 		super::sign::sign(dmg_path.clone(), identity, settings, false)?;
+		// the app inside was notarized on its own, but the DMG needs its own ticket to pass Gatekeeper when downloaded
+		if let Ok(args) = super::sign::notarize_auth_args(settings) {
+			super::sign::notarize(dmg_path.clone(), args)?;
+		}
 	}
 	Ok(vec![dmg_path])
 }
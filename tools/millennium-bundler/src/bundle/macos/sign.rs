@@ -14,11 +14,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{ffi::OsString, fs::File, io::prelude::*, path::PathBuf, process::Command};
+use std::{
+	ffi::OsString,
+	fs::File,
+	io::prelude::*,
+	path::{Path, PathBuf},
+	process::Command
+};
 
 use anyhow::Context;
 use log::info;
-use regex::Regex;
 
 use crate::{bundle::common::CommandExt, Settings};
 
@@ -157,10 +162,16 @@ fn try_sign(path_to_sign: PathBuf, identity: &str, settings: &Settings, is_an_ex
 	}
 
 	if is_an_executable {
+		// notarization requires the hardened runtime
 		args.push("--options");
 		args.push("runtime");
 	}
 
+	// notarization requires a secure timestamp, which ad-hoc signatures can't have
+	if identity != "-" {
+		args.push("--timestamp");
+	}
+
 	if path_to_sign.is_dir() {
 		args.push("--deep");
 	}
@@ -174,137 +185,98 @@ fn try_sign(path_to_sign: PathBuf, identity: &str, settings: &Settings, is_an_ex
 	Ok(())
 }
 
-pub fn notarize(app_bundle_path: PathBuf, auth_args: Vec<String>, settings: &Settings) -> crate::Result<()> {
-	let identifier = settings.bundle_identifier();
-
-	let bundle_stem = app_bundle_path.file_stem().expect("failed to get bundle filename");
-
+/// Submits the app bundle or DMG to Apple's notary service with `notarytool`, waits for the result and staples the
+/// ticket to it, so Gatekeeper accepts it on other machines even when they are offline.
+pub fn notarize(path: PathBuf, auth_args: Vec<String>) -> crate::Result<()> {
 	let tmp_dir = tempfile::tempdir()?;
-	let zip_path = tmp_dir.path().join(format!("{}.zip", bundle_stem.to_string_lossy()));
-	let zip_args = vec![
-		"-c",
-		"-k",
-		"--keepParent",
-		"--sequesterRsrc",
-		app_bundle_path.to_str().expect("failed to convert bundle_path to string"),
-		zip_path.to_str().expect("failed to convert zip_path to string"),
-	];
-
-	// use ditto to create a PKZip almost identical to Finder
-	// this remove almost 99% of false alarm in notarization
-	Command::new("ditto").args(zip_args).output_ok().context("failed to zip app with ditto")?;
-
-	// sign the zip file
-	if let Some(identity) = &settings.macos().signing_identity {
-		sign(zip_path.clone(), identity, settings, false)?;
+	// notarytool takes DMGs as is, but app bundles have to be zipped
+	let submission_path = if path.is_dir() {
+		let bundle_stem = path.file_stem().expect("failed to get bundle filename");
+		let zip_path = tmp_dir.path().join(format!("{}.zip", bundle_stem.to_string_lossy()));
+		// use ditto to create a PKZip almost identical to Finder
+		// this remove almost 99% of false alarm in notarization
+		Command::new("ditto")
+			.args(["-c", "-k", "--keepParent", "--sequesterRsrc"])
+			.arg(&path)
+			.arg(&zip_path)
+			.output_ok()
+			.context("failed to zip app with ditto")?;
+		zip_path
+	} else {
+		path.clone()
 	};
 
-	let mut notarize_args = vec![
-		"altool",
-		"--notarize-app",
-		"-f",
-		zip_path.to_str().expect("failed to convert zip_path to string"),
-		"--primary-bundle-id",
-		identifier,
-	];
-
-	if let Some(provider_short_name) = &settings.macos().provider_short_name {
-		notarize_args.push("--asc-provider");
-		notarize_args.push(provider_short_name);
-	}
-
-	info!(action = "Notarizing"; "{}", app_bundle_path.display());
+	info!(action = "Notarizing"; "{} (this may take a few minutes)", path.display());
 
 	let output = Command::new("xcrun")
-		.args(notarize_args)
-		.args(auth_args.clone())
+		.args(["notarytool", "submit", "--wait", "--output-format", "json"])
+		.arg(&submission_path)
+		.args(auth_args)
 		.output_ok()
-		.context("failed to upload app to Apple notarization servers")?;
-
-	if !output.status.success() {
-		return Err(anyhow::anyhow!(format!("failed to upload app to Apple's notarization servers. {}", std::str::from_utf8(&output.stdout)?)).into());
-	}
-
-	let mut notarize_response = std::str::from_utf8(&output.stdout)?.to_string();
-	notarize_response.push('\n');
-	notarize_response.push_str(std::str::from_utf8(&output.stderr)?);
-	notarize_response.push('\n');
-	if let Some(uuid) = Regex::new(r"\nRequestUUID = (.+?)\n")?.captures_iter(&notarize_response).next() {
-		info!("notarization started; waiting for Apple response...");
-		let uuid = uuid[1].to_string();
-		get_notarization_status(uuid, auth_args, settings)?;
-		staple_app(app_bundle_path.clone())?;
-	} else {
-		return Err(anyhow::anyhow!("failed to parse RequestUUID from upload output. {}", notarize_response).into());
+		.context("failed to upload app to Apple's notarization servers")?;
+
+	let response: serde_json::Value = serde_json::from_slice(&output.stdout)
+		.with_context(|| format!("failed to parse notarytool output: {}", String::from_utf8_lossy(&output.stdout)))?;
+	let status = response["status"].as_str().unwrap_or_default();
+	if status != "Accepted" {
+		let id = response["id"].as_str().unwrap_or_default();
+		return Err(anyhow::anyhow!(
+			"Apple failed to notarize {} (status: {}). Run `xcrun notarytool log {}` with the same credentials to see why.",
+			path.display(),
+			status,
+			id
+		)
+		.into());
 	}
 
-	Ok(())
+	staple(&path)
 }
 
-fn staple_app(mut app_bundle_path: PathBuf) -> crate::Result<()> {
-	let app_bundle_path_clone = app_bundle_path.clone();
-	let filename = app_bundle_path_clone
-		.file_name()
-		.expect("failed to get bundle filename")
-		.to_str()
-		.expect("failed to convert bundle filename to string");
-
-	app_bundle_path.pop();
-
+fn staple(path: &Path) -> crate::Result<()> {
 	Command::new("xcrun")
-		.args(vec!["stapler", "staple", "-v", filename])
-		.current_dir(app_bundle_path)
+		.args(["stapler", "staple", "-v"])
+		.arg(path.file_name().expect("failed to get bundle filename"))
+		.current_dir(path.parent().expect("failed to get bundle directory"))
 		.output_ok()
-		.context("failed to staple app")?;
+		.context("failed to staple notarization ticket")?;
 
 	Ok(())
 }
 
-fn get_notarization_status(uuid: String, auth_args: Vec<String>, settings: &Settings) -> crate::Result<()> {
-	std::thread::sleep(std::time::Duration::from_secs(10));
-	let result = Command::new("xcrun")
-		.args(vec!["altool", "--notarization-info", &uuid])
-		.args(auth_args.clone())
-		.output_ok();
-
-	if let Ok(output) = result {
-		let mut notarize_status = std::str::from_utf8(&output.stdout)?.to_string();
-		notarize_status.push('\n');
-		notarize_status.push_str(std::str::from_utf8(&output.stderr)?);
-		notarize_status.push('\n');
-		if let Some(status) = Regex::new(r"\n *Status: (.+?)\n")?.captures_iter(&notarize_status).next() {
-			let status = status[1].to_string();
-			if status == "in progress" {
-				get_notarization_status(uuid, auth_args, settings)
-			} else if status == "invalid" {
-				Err(anyhow::anyhow!(format!("Apple failed to notarize your app. {}", notarize_status)).into())
-			} else if status != "success" {
-				Err(anyhow::anyhow!(format!("Unknown notarize status {}. {}", status, notarize_status)).into())
-			} else {
-				Ok(())
-			}
-		} else {
-			get_notarization_status(uuid, auth_args, settings)
-		}
-	} else {
-		get_notarization_status(uuid, auth_args, settings)
+/// Gets the `notarytool` arguments authenticating with Apple, in order of preference from:
+///
+/// - a keychain profile created with `xcrun notarytool store-credentials`, set with `notarizationProfile` or
+///   `APPLE_NOTARIZATION_PROFILE`;
+/// - an Apple ID: `APPLE_ID`, an app-specific password in `APPLE_PASSWORD` and the team ID in `APPLE_TEAM_ID` (or
+///   `providerShortName`);
+/// - an App Store Connect API key: `APPLE_API_KEY` (the key ID), `APPLE_API_ISSUER` and the path to the `.p8` file in
+///   `APPLE_API_KEY_PATH`, which defaults to `~/.appstoreconnect/private_keys/AuthKey_<APPLE_API_KEY>.p8`.
+pub fn notarize_auth_args(settings: &Settings) -> crate::Result<Vec<String>> {
+	let env = |name: &str| std::env::var_os(name).map(|value| value.to_string_lossy().into_owned());
+
+	if let Some(profile) = &settings.macos().notarization_profile {
+		return Ok(vec!["--keychain-profile".into(), profile.clone()]);
 	}
-}
 
-pub fn notarize_auth_args() -> crate::Result<Vec<String>> {
-	match (std::env::var_os("APPLE_ID"), std::env::var_os("APPLE_PASSWORD")) {
-		(Some(apple_id), Some(apple_password)) => {
-			let apple_id = apple_id.to_str().expect("failed to convert APPLE_ID to string").to_string();
-			let apple_password = apple_password.to_str().expect("failed to convert APPLE_PASSWORD to string").to_string();
-			Ok(vec!["-u".to_string(), apple_id, "-p".to_string(), apple_password])
-		}
-		_ => match (std::env::var_os("APPLE_API_KEY"), std::env::var_os("APPLE_API_ISSUER")) {
-			(Some(api_key), Some(api_issuer)) => {
-				let api_key = api_key.to_str().expect("failed to convert APPLE_API_KEY to string").to_string();
-				let api_issuer = api_issuer.to_str().expect("failed to convert APPLE_API_ISSUER to string").to_string();
-				Ok(vec!["--apiKey".to_string(), api_key, "--apiIssuer".to_string(), api_issuer])
-			}
-			_ => Err(anyhow::anyhow!("no APPLE_ID & APPLE_PASSWORD or APPLE_API_KEY & APPLE_API_ISSUER environment variables found").into())
-		}
+	if let (Some(apple_id), Some(password)) = (env("APPLE_ID"), env("APPLE_PASSWORD")) {
+		let team_id = env("APPLE_TEAM_ID")
+			.or_else(|| settings.macos().provider_short_name.clone())
+			.ok_or_else(|| anyhow::anyhow!("notarizing with an Apple ID requires the APPLE_TEAM_ID environment variable"))?;
+		return Ok(vec!["--apple-id".into(), apple_id, "--password".into(), password, "--team-id".into(), team_id]);
 	}
+
+	if let (Some(key_id), Some(issuer)) = (env("APPLE_API_KEY"), env("APPLE_API_ISSUER")) {
+		let key_path = env("APPLE_API_KEY_PATH").map(PathBuf::from).unwrap_or_else(|| {
+			dirs_next::home_dir()
+				.unwrap_or_default()
+				.join(".appstoreconnect/private_keys")
+				.join(format!("AuthKey_{}.p8", key_id))
+		});
+		return Ok(vec!["--key".into(), key_path.to_string_lossy().into_owned(), "--key-id".into(), key_id, "--issuer".into(), issuer]);
+	}
+
+	Err(anyhow::anyhow!(
+		"no notarization credentials found; set `notarizationProfile`, APPLE_ID & APPLE_PASSWORD & APPLE_TEAM_ID, or APPLE_API_KEY & APPLE_API_ISSUER"
+	)
+	.into())
 }
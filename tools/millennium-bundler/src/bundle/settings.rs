@@ -161,7 +161,15 @@ pub struct DebianSettings {
 	/// List of custom files to add to the deb package.
 	/// Maps the path on the debian package to the path of the file to include (relative to the current working
 	/// directory).
-	pub files: HashMap<PathBuf, PathBuf>
+	pub files: HashMap<PathBuf, PathBuf>,
+	/// Path to the `preinst` maintainer script, run before the package is unpacked.
+	pub preinst: Option<PathBuf>,
+	/// Path to the `postinst` maintainer script, run after the package is unpacked.
+	pub postinst: Option<PathBuf>,
+	/// Path to the `prerm` maintainer script, run before the package is removed.
+	pub prerm: Option<PathBuf>,
+	/// Path to the `postrm` maintainer script, run after the package is removed.
+	pub postrm: Option<PathBuf>
 }
 
 /// The macOS bundle settings.
@@ -198,6 +206,8 @@ pub struct MacOsSettings {
 	pub provider_short_name: Option<String>,
 	/// Path to the entitlements.plist file.
 	pub entitlements: Option<String>,
+	/// Keychain profile storing the notarization credentials.
+	pub notarization_profile: Option<String>,
 	/// Path to the Info.plist file for the bundle.
 	pub info_plist_path: Option<PathBuf>
 }
@@ -267,6 +277,10 @@ pub struct WindowsSettings {
 	pub digest_algorithm: Option<String>,
 	/// The SHA1 hash of the signing certificate.
 	pub certificate_thumbprint: Option<String>,
+	/// Path to a PFX certificate file to sign with, used when [`Self::certificate_thumbprint`] isn't set.
+	pub certificate_path: Option<PathBuf>,
+	/// Password of the PFX certificate file.
+	pub certificate_password: Option<String>,
 	/// Server to use during timestamping.
 	pub timestamp_url: Option<String>,
 	/// Whether to use Time-Stamp Protocol (TSP, a.k.a. RFC 3161) for the timestamp server. Your code signing provider
@@ -295,6 +309,8 @@ impl Default for WindowsSettings {
 		Self {
 			digest_algorithm: None,
 			certificate_thumbprint: None,
+			certificate_path: None,
+			certificate_password: None,
 			timestamp_url: None,
 			tsp: false,
 			wix: None,
@@ -32,7 +32,7 @@ use sha2::Digest;
 use uuid::Uuid;
 use zip::ZipArchive;
 
-use super::super::sign::{sign, SignParams};
+use super::super::sign::try_sign;
 use crate::bundle::{
 	common::CommandExt,
 	path_utils::{copy_file, FileOpts},
@@ -371,29 +371,10 @@ pub fn build_wix_app_installer(settings: &Settings, wix_toolset_path: &Path, upd
 		.find(|bin| bin.main())
 		.ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
 	let app_exe_source = settings.binary_path(main_binary);
-	let try_sign = |file_path: &PathBuf| -> crate::Result<()> {
-		if let Some(certificate_thumbprint) = &settings.windows().certificate_thumbprint {
-			info!(action = "Signing"; "{}", file_path.display());
-			sign(
-				&file_path,
-				&SignParams {
-					product_name: settings.product_name().into(),
-					digest_algorithm: settings
-						.windows()
-						.digest_algorithm
-						.as_ref()
-						.map(|algorithm| algorithm.to_string())
-						.unwrap_or_else(|| "sha256".to_string()),
-					certificate_thumbprint: certificate_thumbprint.to_string(),
-					timestamp_url: settings.windows().timestamp_url.as_ref().map(|url| url.to_string()),
-					tsp: settings.windows().tsp
-				}
-			)?;
-		}
-		Ok(())
-	};
 
-	try_sign(&app_exe_source)?;
+	for bin in settings.binaries() {
+		try_sign(settings.binary_path(bin), settings)?;
+	}
 
 	let output_path = settings.project_out_directory().join("wix").join(arch);
 	if output_path.exists() {
@@ -703,7 +684,8 @@ pub fn build_wix_app_installer(settings: &Settings, wix_toolset_path: &Path, upd
 
 		run_light(wix_toolset_path, &output_path, arguments, &msi_output_path)?;
 		rename(&msi_output_path, &msi_path)?;
-		try_sign(&msi_path)?;
+		// this also signs the MSI packed into the updater `.msi.zip`, so its elevated install isn't blocked
+		try_sign(&msi_path, settings)?;
 		output_paths.push(msi_path);
 	}
 
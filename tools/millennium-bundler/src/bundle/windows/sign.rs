@@ -26,16 +26,46 @@ use winreg::{
 	RegKey
 };
 
-use crate::bundle::common::CommandExt;
+use crate::{bundle::common::CommandExt, Settings};
+
+/// The certificate to sign with.
+pub enum SignCertificate {
+	/// SHA1 hash of a certificate in the Windows certificate store.
+	Thumbprint(String),
+	/// A PFX certificate file and its password.
+	File { path: PathBuf, password: Option<String> }
+}
 
 pub struct SignParams {
 	pub product_name: String,
 	pub digest_algorithm: String,
-	pub certificate_thumbprint: String,
+	pub certificate: SignCertificate,
 	pub timestamp_url: Option<String>,
 	pub tsp: bool
 }
 
+impl SignParams {
+	/// Gets the signing parameters from the bundle settings, or `None` if no certificate is configured.
+	pub fn from_settings(settings: &Settings) -> Option<Self> {
+		let windows = settings.windows();
+		let certificate = match (&windows.certificate_thumbprint, &windows.certificate_path) {
+			(Some(thumbprint), _) => SignCertificate::Thumbprint(thumbprint.clone()),
+			(None, Some(path)) => SignCertificate::File {
+				path: path.clone(),
+				password: windows.certificate_password.clone()
+			},
+			(None, None) => return None
+		};
+		Some(Self {
+			product_name: settings.product_name().into(),
+			digest_algorithm: windows.digest_algorithm.clone().unwrap_or_else(|| "sha256".to_string()),
+			certificate,
+			timestamp_url: windows.timestamp_url.clone(),
+			tsp: windows.tsp
+		})
+	}
+}
+
 // sign code forked from https://github.com/forbjok/rust-codesign
 fn locate_signtool() -> crate::Result<PathBuf> {
 	const INSTALLED_ROOTS_REGKEY_PATH: &str = r"SOFTWARE\Microsoft\Windows Kits\Installed Roots";
@@ -103,7 +133,10 @@ pub fn sign<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
 	// Convert path to string reference, as we need to pass it as a commandline parameter to signtool
 	let path_str = path.as_ref().to_str().unwrap();
 
-	info!(action = "Signing"; "{} with identity \"{}\"", path_str, params.certificate_thumbprint);
+	match &params.certificate {
+		SignCertificate::Thumbprint(thumbprint) => info!(action = "Signing"; "{} with identity \"{}\"", path_str, thumbprint),
+		SignCertificate::File { path, .. } => info!(action = "Signing"; "{} with certificate {}", path_str, path.display())
+	}
 
 	// Construct SignTool command
 	let signtool = locate_signtool()?;
@@ -111,7 +144,17 @@ pub fn sign<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
 	let mut cmd = Command::new(signtool);
 	cmd.arg("sign");
 	cmd.args(&["/fd", &params.digest_algorithm]);
-	cmd.args(&["/sha1", &params.certificate_thumbprint]);
+	match &params.certificate {
+		SignCertificate::Thumbprint(thumbprint) => {
+			cmd.args(&["/sha1", thumbprint]);
+		}
+		SignCertificate::File { path, password } => {
+			cmd.arg("/f").arg(path);
+			if let Some(password) = password {
+				cmd.args(&["/p", password]);
+			}
+		}
+	}
 	cmd.args(&["/d", &params.product_name]);
 
 	if let Some(ref timestamp_url) = params.timestamp_url {
@@ -133,3 +176,11 @@ pub fn sign<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
 
 	Ok(())
 }
+
+/// Signs the file if a certificate is configured, otherwise does nothing.
+pub fn try_sign<P: AsRef<Path>>(path: P, settings: &Settings) -> crate::Result<()> {
+	match SignParams::from_settings(settings) {
+		Some(params) => sign(path, &params),
+		None => Ok(())
+	}
+}
@@ -3,5 +3,5 @@
 
 /* auto-generated by NAPI-RS */
 
-export function run(args: Array<string>, binName: string | undefined | null, callback: (...args: any[]) => any): void
+export function run(args: Array<string>, binName: string | undefined | null, callback: (...args: any[]) => any, onProgress?: (...args: any[]) => any | undefined | null): void
 export function logError(error: string): void
@@ -1,5 +1,12 @@
 /* tslint:disable */
 /* eslint-disable */
 
-export function run(args: Array<string>, binName: string | undefined | null): Promise<void>;
+export type ProgressEvent =
+	| { event: 'compile-started'; target: string | null }
+	| { event: 'compile-progress'; crate: string; version: string }
+	| { event: 'bundling'; formats: string[] }
+	| { event: 'artifact-produced'; path: string }
+	| { event: 'error'; message: string };
+
+export function run(args: Array<string>, binName: string | undefined | null, onProgress?: (event: ProgressEvent) => void): Promise<void>;
 export function logError(error: string): void;
@@ -1,13 +1,13 @@
 const { run, logError } = require('./index')
 
-module.exports.run = (args, binName) => {
+module.exports.run = (args, binName, onProgress) => {
 	return new Promise((resolve, reject) => {
 		run(args, binName, res => {
 			if (res instanceof Error)
 				reject(res);
 			else
 				resolve(res);
-		});
+		}, onProgress ? event => onProgress(JSON.parse(event)) : undefined);
 	});
 };
 
@@ -20,10 +20,19 @@ use napi::{
 };
 
 #[napi_derive::napi]
-pub fn run(args: Vec<String>, bin_name: Option<String>, callback: JsFunction) -> Result<()> {
+pub fn run(args: Vec<String>, bin_name: Option<String>, callback: JsFunction, on_progress: Option<JsFunction>) -> Result<()> {
 	let function: ThreadsafeFunction<bool, ErrorStrategy::CalleeHandled> =
 		callback.create_threadsafe_function(0, |ctx| ctx.env.get_boolean(ctx.value).map(|v| vec![v]))?;
 
+	if let Some(on_progress) = on_progress {
+		// events are passed to JS as JSON, the same format `--message-format json` prints
+		let on_progress: ThreadsafeFunction<String, ErrorStrategy::Fatal> =
+			on_progress.create_threadsafe_function(0, |ctx| ctx.env.create_string_from_std(ctx.value).map(|v| vec![v]))?;
+		millennium_cli::progress::set_listener(move |event| {
+			on_progress.call(event.to_json(), ThreadsafeFunctionCallMode::NonBlocking);
+		});
+	}
+
 	std::thread::spawn(move || match millennium_cli::try_run(args, bin_name) {
 		Ok(_) => function.call(Ok(true), ThreadsafeFunctionCallMode::Blocking),
 		Err(e) => function.call(Err(Error::new(Status::GenericFailure, format!("{:#}", e))), ThreadsafeFunctionCallMode::Blocking)
@@ -25,10 +25,11 @@ use crate::{
 	helpers::{
 		app_paths::{app_dir, millennium_dir},
 		command_env,
-		config::{get as get_config, AppUrl, WindowUrl, MERGE_CONFIG_EXTENSION_NAME},
+		config::{get as get_config, AppUrl, Config, WindowUrl, MERGE_CONFIG_EXTENSION_NAME},
 		updater_signature::sign_file_from_env_variables
 	},
 	interface::{AppInterface, AppSettings, Interface},
+	progress::{self, MessageFormat, ProgressEvent},
 	CommandExt, Result
 };
 
@@ -41,11 +42,16 @@ pub struct Options {
 	/// Builds with debug information. By default, `millennium build` performs a release build.
 	#[clap(short, long)]
 	pub debug: bool,
-	/// Target triple to build against.
+	/// Builds with the given Cargo profile, e.g. a custom `release-lto` profile defined in your `Cargo.toml`.
+	#[clap(long, conflicts_with = "debug")]
+	pub profile: Option<String>,
+	/// Target triple to build against. Pass a comma-separated list to build and bundle several targets in one go.
 	///
 	/// Must be one of the values outputted by `$rustc --print target-list` or `universal-apple-darwin` for a
 	/// universal macOS application. Note that compiling a universal macOS application requires both the
 	/// `aarch64-apple-darwin` and `x86_64-apple-darwin` toolchains to be installed.
+	///
+	/// The artifacts of each target are written to `target/<triple>/<profile>/bundle`.
 	#[clap(short, long)]
 	pub target: Option<String>,
 	/// Space or comma-separated list of Cargo features to activate.
@@ -61,11 +67,16 @@ pub struct Options {
 	/// JSON string or path to JSON file to merge with .millenniumrc
 	#[clap(short, long)]
 	pub config: Option<String>,
+	/// Format of the build progress. `json` also prints a JSON object per line on stdout, for tools wrapping the CLI.
+	#[clap(long, arg_enum, default_value = "human")]
+	pub message_format: MessageFormat,
 	/// Command line arguments passed to the runner
 	pub args: Vec<String>
 }
 
 pub fn command(mut options: Options) -> Result<()> {
+	progress::set_format(options.message_format);
+
 	let (merge_config, merge_config_path) = if let Some(config) = &options.config {
 		if config.starts_with('{') {
 			(Some(config.to_string()), None)
@@ -177,7 +188,33 @@ pub fn command(mut options: Options) -> Result<()> {
 		list.extend(config_.build.features.clone().unwrap_or_default());
 	}
 
+	let targets: Vec<Option<String>> = match &options.target {
+		Some(target) => target.split(',').map(str::trim).filter(|t| !t.is_empty()).map(|t| Some(t.to_string())).collect(),
+		None => vec![None]
+	};
+	if targets.is_empty() {
+		bail!("`--target` must name at least one target triple");
+	}
+
 	let mut interface = AppInterface::new(config_)?;
+	// check every target up front so a missing toolchain doesn't fail the build halfway through the list
+	interface.validate_targets(targets.iter().flatten())?;
+
+	for target in targets {
+		let mut options = options.clone();
+		options.target = target;
+		build_target(&mut interface, config_, options)?;
+	}
+
+	Ok(())
+}
+
+/// Builds and bundles the app for the single target in `options.target`, or the host if it's `None`.
+fn build_target(interface: &mut AppInterface, config_: &Config, options: Options) -> Result<()> {
+	if let Some(target) = &options.target {
+		info!(action = "Building"; "target {}", target);
+	}
+
 	let app_settings = interface.app_settings();
 	let interface_options = options.clone().into();
 
@@ -255,7 +292,13 @@ pub fn command(mut options: Options) -> Result<()> {
 			}
 		}
 
+		progress::emit(ProgressEvent::Bundling {
+			formats: settings.package_types()?.iter().map(|package_type| package_type.short_name().into()).collect()
+		});
 		let bundles = bundle_project(settings).with_context(|| "failed to bundle project")?;
+		for path in bundles.iter().flat_map(|bundle| &bundle.bundle_paths) {
+			progress::emit(ProgressEvent::ArtifactProduced { path: path.clone() });
+		}
 
 		// If updater is active
 		if config_.millennium.updater.active {
@@ -267,6 +310,7 @@ pub fn command(mut options: Options) -> Result<()> {
 				for path in elem.bundle_paths.iter() {
 					// sign our path from environment variables
 					let (signature_path, _signature) = sign_file_from_env_variables(path)?;
+					progress::emit(ProgressEvent::ArtifactProduced { path: signature_path.clone() });
 					signed_paths.append(&mut vec![signature_path]);
 				}
 			}
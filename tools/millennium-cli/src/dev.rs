@@ -16,16 +16,23 @@
 
 use std::{
 	env::set_current_dir,
+	ffi::OsStr,
+	io::Write,
+	net::{TcpListener, TcpStream},
+	path::{Path, PathBuf},
 	process::{exit, Command, ExitStatus, Stdio},
 	sync::{
 		atomic::{AtomicBool, Ordering},
+		mpsc::{channel, RecvTimeoutError},
 		Arc, Mutex
-	}
+	},
+	time::Duration
 };
 
 use anyhow::Context;
 use clap::Parser;
 use log::{error, info, warn};
+use notify::{watcher, DebouncedEvent, RecursiveMode, Watcher};
 use once_cell::sync::OnceCell;
 use shared_child::SharedChild;
 
@@ -36,6 +43,7 @@ use crate::{
 		config::{get as get_config, AppUrl, WindowUrl}
 	},
 	interface::{AppInterface, ExitReason, Interface},
+	progress::{self, MessageFormat},
 	Result
 };
 
@@ -47,6 +55,14 @@ const KILL_CHILDREN_SCRIPT: &[u8] = include_bytes!("../scripts/kill-children.sh"
 
 pub const DEV_WATCHER_GITIGNORE: &[u8] = include_bytes!("../millennium-dev-watcher.gitignore");
 
+/// Address of the reload channel, read by the app in dev mode. Must match `millennium::dev_reload`.
+const DEV_RELOAD_ADDR_ENV: &str = "MILLENNIUM_DEV_RELOAD_ADDR";
+/// Directory the app serves its assets from in dev mode, instead of the ones embedded at compile time.
+const DEV_ASSETS_DIR_ENV: &str = "MILLENNIUM_DEV_ASSETS_DIR";
+/// How long the `devPath` has to be quiet before the app is reloaded, so that saving several files at once (or a
+/// bundler writing its output) only reloads once.
+const ASSET_RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Start Millennium in development mode", trailing_var_arg(true))]
 pub struct Options {
@@ -68,6 +84,9 @@ pub struct Options {
 	/// Run the code in release mode
 	#[clap(long = "release")]
 	pub release_mode: bool,
+	/// Format of the build progress. `json` also prints a JSON object per line on stdout, for tools wrapping the CLI.
+	#[clap(long, arg_enum, default_value = "human")]
+	pub message_format: MessageFormat,
 	/// Command line arguments passed to the runner
 	pub args: Vec<String>
 }
@@ -83,6 +102,8 @@ pub fn command(options: Options) -> Result<()> {
 }
 
 fn command_internal(mut options: Options) -> Result<()> {
+	progress::set_format(options.message_format);
+
 	let millennium_path = millennium_dir();
 	options.config = if let Some(config) = &options.config {
 		Some(if config.starts_with('{') {
@@ -114,7 +135,7 @@ fn command_internal(mut options: Options) -> Result<()> {
 				command
 			};
 			command.stdin(Stdio::piped());
-			command.stdout(os_pipe::dup_stdout()?);
+			command.stdout(progress::stdout()?);
 			command.stderr(os_pipe::dup_stderr()?);
 
 			let child = SharedChild::spawn(&mut command).unwrap_or_else(|_| panic!("failed to run `{}`", before_dev));
@@ -196,12 +217,90 @@ fn command_internal(mut options: Options) -> Result<()> {
 		}
 	}
 
+	if let AppUrl::Url(WindowUrl::App(path)) = config.lock().unwrap().as_ref().unwrap().build.dev_path.clone() {
+		watch_assets(millennium_path.join(path))?;
+	}
+
 	let mut interface = AppInterface::new(config.lock().unwrap().as_ref().unwrap())?;
 
 	let exit_on_panic = options.exit_on_panic;
 	interface.dev(options.into(), move |status, reason| on_dev_exit(status, reason, exit_on_panic))
 }
 
+/// Watches a static `devPath` and tells the running app to reload its windows whenever an asset changes.
+///
+/// The app connects to a TCP socket opened here (see [`DEV_RELOAD_ADDR_ENV`]); each change writes a `reload` line to
+/// every connected app. The app process inherits the environment variables set here.
+fn watch_assets(dir: PathBuf) -> Result<()> {
+	let dir = dir.canonicalize().with_context(|| format!("failed to resolve devPath {}", dir.display()))?;
+
+	let listener = TcpListener::bind(("127.0.0.1", 0)).with_context(|| "failed to open the dev reload channel")?;
+	std::env::set_var(DEV_RELOAD_ADDR_ENV, listener.local_addr()?.to_string());
+	std::env::set_var(DEV_ASSETS_DIR_ENV, &dir);
+
+	// the app reconnects each time it's restarted after a Rust change
+	let clients = Arc::new(Mutex::new(Vec::<TcpStream>::new()));
+	let clients_ = clients.clone();
+	std::thread::spawn(move || {
+		for stream in listener.incoming().flatten() {
+			clients_.lock().unwrap().push(stream);
+		}
+	});
+
+	let (tx, rx) = channel();
+	let mut watcher = watcher(tx, ASSET_RELOAD_DEBOUNCE).with_context(|| "failed to create the devPath watcher")?;
+	watcher
+		.watch(&dir, RecursiveMode::Recursive)
+		.with_context(|| format!("failed to watch {}", dir.display()))?;
+	info!(action = "Watching"; "{} for changes...", dir.display());
+
+	std::thread::spawn(move || {
+		// keep the watcher alive for as long as we're receiving its events
+		let _watcher = watcher;
+		while let Ok(event) = rx.recv() {
+			if !is_asset_change(&event) {
+				continue;
+			}
+			// wait for the burst of changes to settle
+			loop {
+				match rx.recv_timeout(ASSET_RELOAD_DEBOUNCE) {
+					Ok(_) => continue,
+					Err(RecvTimeoutError::Timeout) => break,
+					Err(RecvTimeoutError::Disconnected) => return
+				}
+			}
+
+			info!(action = "Reloading"; "app after changes in {}", dir.display());
+			clients.lock().unwrap().retain(|client| {
+				let mut client: &TcpStream = client;
+				client.write_all(b"reload\n").is_ok()
+			});
+		}
+	});
+	Ok(())
+}
+
+fn is_asset_change(event: &DebouncedEvent) -> bool {
+	match event {
+		DebouncedEvent::Create(path) | DebouncedEvent::Write(path) | DebouncedEvent::Remove(path) | DebouncedEvent::Rename(_, path) => !is_swap_file(path),
+		_ => false
+	}
+}
+
+/// Whether the file is a temporary file written by an editor while saving, e.g. Vim's `.index.html.swp` and `4913`,
+/// Emacs' `#index.html#` and `.#index.html`, or a `index.html~` backup.
+fn is_swap_file(path: &Path) -> bool {
+	let name = match path.file_name().and_then(OsStr::to_str) {
+		Some(name) => name,
+		None => return false
+	};
+	name == "4913"
+		|| name.ends_with('~')
+		|| name.starts_with(".#")
+		|| (name.starts_with('#') && name.ends_with('#'))
+		|| [".swp", ".swo", ".swx", ".tmp"].iter().any(|extension| name.ends_with(extension))
+}
+
 fn on_dev_exit(status: ExitStatus, reason: ExitReason, exit_on_panic: bool) {
 	if !matches!(reason, ExitReason::TriggeredKill) && (exit_on_panic || matches!(reason, ExitReason::NormalExit)) {
 		kill_before_dev_process();
@@ -220,7 +319,7 @@ fn check_for_updates() -> Result<()> {
 		let upstream_version = crate::info::cli_upstream_version()?;
 		let upstream = semver::Version::parse(&upstream_version)?;
 		if current < upstream {
-			println!("🚀 A new version of Millennium CLI is avaliable! [{}]", upstream.to_string());
+			writeln!(progress::stdout()?, "🚀 A new version of Millennium CLI is avaliable! [{}]", upstream.to_string())?;
 		}
 	}
 	Ok(())
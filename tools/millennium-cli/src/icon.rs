@@ -0,0 +1,166 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+	fs::{create_dir_all, File},
+	io::BufWriter,
+	path::{Path, PathBuf}
+};
+
+use anyhow::{bail, Context};
+use clap::Parser;
+use image::{
+	codecs::ico::{IcoEncoder, IcoFrame},
+	imageops::{self, FilterType},
+	ColorType, GenericImageView, ImageFormat, RgbaImage
+};
+use log::{info, warn};
+
+use crate::{helpers::app_paths::millennium_dir, Result};
+
+/// Sizes packed into `icon.ico`. Windows picks the closest one for the taskbar, title bar, Explorer, etc.
+const ICO_SIZES: [u32; 6] = [16, 24, 32, 48, 64, 256];
+
+/// Sizes packed into `icon.icns` as `(size, density)`, i.e. a 64x64 image is the retina version of 32x32.
+const ICNS_SIZES: [(u32, u32); 9] = [(16, 1), (32, 1), (32, 2), (128, 1), (128, 2), (256, 1), (256, 2), (512, 1), (512, 2)];
+
+/// Standalone PNGs, used by the Linux bundles and referenced by the default `.millenniumrc`.
+const PNG_SIZES: [(&str, u32); 5] = [("32x32.png", 32), ("128x128.png", 128), ("128x128@2x.png", 256), ("256x256.png", 256), ("icon.png", 512)];
+
+#[derive(Debug, Parser)]
+#[clap(about = "Generate the app icons for all platforms from a single source image")]
+pub struct Options {
+	/// Path to the source icon. Should be a square PNG of at least 1024x1024 pixels with transparency.
+	#[clap(default_value = "./app-icon.png")]
+	input: PathBuf,
+	/// Directory to write the icons to. Defaults to the `icons` folder next to your `.millenniumrc`.
+	#[clap(short, long)]
+	output: Option<PathBuf>,
+	/// Transparent padding around the icon, as a percentage of the icon size.
+	#[clap(short, long, default_value_t = 0)]
+	padding: u32,
+	/// Round the corners of the icon with the given radius, as a percentage of the icon size. 50 makes the icon a circle.
+	#[clap(short, long)]
+	rounded: Option<u32>
+}
+
+pub fn command(options: Options) -> Result<()> {
+	if options.padding >= 50 {
+		bail!("padding must be less than 50%, got {}%", options.padding);
+	}
+	if let Some(radius) = options.rounded {
+		if radius > 50 {
+			bail!("corner radius must be at most 50%, got {}%", radius);
+		}
+	}
+
+	let source = image::open(&options.input).with_context(|| format!("failed to read source icon {}", options.input.display()))?;
+	let (width, height) = source.dimensions();
+	if width != height {
+		bail!("source icon must be square, got {}x{}", width, height);
+	}
+	if width < 1024 {
+		warn!("Source icon is only {}x{}, the larger icons will be upscaled. Use an image of at least 1024x1024 pixels.", width, height);
+	}
+
+	let mut master = source.to_rgba8();
+	if let Some(radius) = options.rounded {
+		round_corners(&mut master, radius);
+	}
+	if options.padding > 0 {
+		master = pad(&master, options.padding);
+	}
+
+	let out_dir = options.output.unwrap_or_else(|| millennium_dir().join("icons"));
+	create_dir_all(&out_dir).with_context(|| format!("failed to create icon directory {}", out_dir.display()))?;
+
+	write_pngs(&master, &out_dir)?;
+	write_ico(&master, &out_dir.join("icon.ico"))?;
+	write_icns(&master, &out_dir.join("icon.icns"))?;
+
+	info!(action = "Generated"; "icons in {}", out_dir.display());
+	Ok(())
+}
+
+fn resize(master: &RgbaImage, size: u32) -> RgbaImage {
+	imageops::resize(master, size, size, FilterType::Lanczos3)
+}
+
+/// Shrinks the icon and centers it on a transparent canvas of the original size.
+fn pad(master: &RgbaImage, padding: u32) -> RgbaImage {
+	let size = master.width();
+	let inner_size = size * (100 - 2 * padding) / 100;
+	let offset = (size - inner_size) / 2;
+	let mut canvas = RgbaImage::new(size, size);
+	imageops::overlay(&mut canvas, &resize(master, inner_size), offset as i64, offset as i64);
+	canvas
+}
+
+/// Masks out the corners of the icon, antialiasing the edge of each arc.
+fn round_corners(master: &mut RgbaImage, radius: u32) {
+	let size = master.width() as f64;
+	let radius = size * radius as f64 / 100.0;
+	if radius <= 0.0 {
+		return;
+	}
+
+	for (x, y, pixel) in master.enumerate_pixels_mut() {
+		// distance from the pixel center to the center of the nearest corner's arc, if the pixel is inside a corner
+		let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+		let cx = px.clamp(radius, size - radius);
+		let cy = py.clamp(radius, size - radius);
+		let distance = ((px - cx).powi(2) + (py - cy).powi(2)).sqrt();
+		let coverage = (radius - distance + 0.5).clamp(0.0, 1.0);
+		pixel[3] = (pixel[3] as f64 * coverage).round() as u8;
+	}
+}
+
+fn write_pngs(master: &RgbaImage, out_dir: &Path) -> Result<()> {
+	for (name, size) in PNG_SIZES {
+		let path = out_dir.join(name);
+		resize(master, size)
+			.save_with_format(&path, ImageFormat::Png)
+			.with_context(|| format!("failed to write {}", path.display()))?;
+	}
+	Ok(())
+}
+
+fn write_ico(master: &RgbaImage, path: &Path) -> Result<()> {
+	let images = ICO_SIZES.iter().map(|&size| resize(master, size)).collect::<Vec<_>>();
+	let frames = images
+		.iter()
+		.map(|image| IcoFrame::as_png(image.as_raw(), image.width(), image.height(), ColorType::Rgba8))
+		.collect::<image::ImageResult<Vec<_>>>()?;
+
+	let file = File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+	IcoEncoder::new(BufWriter::new(file))
+		.encode_images(&frames)
+		.with_context(|| format!("failed to write {}", path.display()))?;
+	Ok(())
+}
+
+fn write_icns(master: &RgbaImage, path: &Path) -> Result<()> {
+	let mut family = icns::IconFamily::new();
+	for (size, density) in ICNS_SIZES {
+		let icon_type = icns::IconType::from_pixel_size_and_density(size * density, size * density, density).expect("ICNS_SIZES only contains valid icon types");
+		let image = icns::Image::from_data(icns::PixelFormat::RGBA, size * density, size * density, resize(master, size * density).into_raw())?;
+		family.add_icon_with_type(&image, icon_type)?;
+	}
+
+	let file = File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+	family.write(BufWriter::new(file)).with_context(|| format!("failed to write {}", path.display()))?;
+	Ok(())
+}
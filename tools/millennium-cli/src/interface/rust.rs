@@ -44,6 +44,7 @@ use crate::{
 		config::{reload as reload_config, wix_settings, Config}
 	},
 	interface::{AppSettings, ExitReason, Interface},
+	progress::{self, ProgressEvent},
 	CommandExt
 };
 
@@ -54,6 +55,7 @@ use manifest::{rewrite_manifest, Manifest};
 pub struct Options {
 	pub runner: Option<String>,
 	pub debug: bool,
+	pub profile: Option<String>,
 	pub target: Option<String>,
 	pub features: Option<Vec<String>>,
 	pub args: Vec<String>,
@@ -65,6 +67,7 @@ impl From<crate::build::Options> for Options {
 		Self {
 			runner: options.runner,
 			debug: options.debug,
+			profile: options.profile,
 			target: options.target,
 			features: options.features,
 			args: options.args,
@@ -78,6 +81,7 @@ impl From<crate::dev::Options> for Options {
 		Self {
 			runner: options.runner,
 			debug: !options.release_mode,
+			profile: None,
 			target: options.target,
 			features: options.features,
 			args: options.args,
@@ -86,6 +90,20 @@ impl From<crate::dev::Options> for Options {
 	}
 }
 
+impl Options {
+	/// The name of the folder cargo writes the artifacts of the selected profile to. The built-in `dev` and `test` profiles
+	/// share `debug`, and `bench` shares `release`; custom profiles use their own name.
+	pub fn profile_dir(&self) -> &str {
+		match self.profile.as_deref() {
+			Some("dev" | "test") => "debug",
+			Some("release" | "bench") => "release",
+			Some(profile) => profile,
+			None if self.debug => "debug",
+			None => "release"
+		}
+	}
+}
+
 pub struct DevChild {
 	manually_killed_app: Arc<AtomicBool>,
 	build_child: Arc<SharedChild>,
@@ -181,7 +199,7 @@ impl Interface for Rust {
 
 				let triple_out_dir = self
 					.app_settings
-					.out_dir(Some(triple.into()), options.debug)
+					.out_dir(Some(triple.into()), options.profile_dir())
 					.with_context(|| format!("failed to get {} out dir", triple))?;
 				self.build_app_blocking(options)
 					.with_context(|| format!("failed to build {} binary", triple))?;
@@ -197,7 +215,8 @@ impl Interface for Rust {
 			self.build_app_blocking(options).with_context(|| "failed to build app")?;
 		}
 
-		rename_app(&bin_path, self.product_name.as_deref())?;
+		let app_path = rename_app(&bin_path, self.product_name.as_deref())?;
+		progress::emit(ProgressEvent::ArtifactProduced { path: app_path });
 
 		Ok(())
 	}
@@ -278,6 +297,23 @@ impl Rust {
 		Ok(())
 	}
 
+	/// Checks that every target is installed before any of them is built. `universal-apple-darwin` requires both of
+	/// the macOS targets it is made of.
+	pub fn validate_targets<'a>(&mut self, targets: impl IntoIterator<Item = &'a String>) -> crate::Result<()> {
+		if self.available_targets.is_none() {
+			self.fetch_available_targets();
+		}
+		for target in targets {
+			if target == "universal-apple-darwin" {
+				self.validate_target("aarch64-apple-darwin")?;
+				self.validate_target("x86_64-apple-darwin")?;
+			} else {
+				self.validate_target(target)?;
+			}
+		}
+		Ok(())
+	}
+
 	fn run_dev<F: Fn(ExitStatus, ExitReason) + Send + Sync + 'static>(&mut self, mut options: Options, on_exit: F) -> crate::Result<DevChild> {
 		let bin_path = self.app_settings.app_binary_path(&options)?;
 		let product_name = self.product_name.clone();
@@ -328,7 +364,7 @@ impl Rust {
 				let bin_path = rename_app(&bin_path, product_name.as_deref()).expect("failed to rename app");
 
 				let mut app = Command::new(bin_path);
-				app.stdout(os_pipe::dup_stdout().unwrap());
+				app.stdout(progress::stdout().unwrap());
 				app.stderr(os_pipe::dup_stderr().unwrap());
 				app.args(run_args);
 				let app_child = Arc::new(SharedChild::spawn(&mut app).unwrap());
@@ -460,10 +496,14 @@ impl Rust {
 			args.push(features.join(","));
 		}
 
-		if !options.debug {
+		if let Some(profile) = options.profile {
+			args.push("--profile".into());
+			args.push(profile);
+		} else if !options.debug {
 			args.push("--release".into());
 		}
 
+		progress::emit(ProgressEvent::CompileStarted { target: options.target.clone() });
 		if let Some(target) = options.target {
 			args.push("--target".into());
 			args.push(target);
@@ -482,7 +522,7 @@ impl Rust {
 		build_cmd.arg("build").arg("--color").arg("always");
 		build_cmd.args(args);
 
-		build_cmd.stdout(os_pipe::dup_stdout()?);
+		build_cmd.stdout(progress::stdout()?);
 		build_cmd.stderr(Stdio::piped());
 
 		let build_child = match SharedChild::spawn(&mut build_cmd) {
@@ -523,7 +563,9 @@ impl Rust {
 				if !buf.ends_with(&[b'\r']) {
 					let _ = io_stderr.write_all(b"\n");
 				}
-				lines.push(String::from_utf8_lossy(&buf).into_owned());
+				let line = String::from_utf8_lossy(&buf).into_owned();
+				progress::emit_cargo_status(&line);
+				lines.push(line);
 			}
 		});
 
@@ -656,7 +698,7 @@ impl AppSettings for RustAppSettings {
 			.expect("Cargo manifest must have the `package.name` field");
 
 		let out_dir = self
-			.out_dir(options.target.clone(), options.debug)
+			.out_dir(options.target.clone(), options.profile_dir())
 			.with_context(|| "failed to get project output directory")?;
 		let target: String = if let Some(target) = options.target.clone() {
 			target
@@ -775,16 +817,16 @@ impl RustAppSettings {
 		&self.cargo_package_settings
 	}
 
-	pub fn out_dir(&self, target: Option<String>, debug: bool) -> crate::Result<PathBuf> {
+	pub fn out_dir(&self, target: Option<String>, profile_dir: &str) -> crate::Result<PathBuf> {
 		let millennium_dir = millennium_dir();
 		let workspace_dir = get_workspace_dir(&millennium_dir);
-		get_target_dir(&workspace_dir, target, !debug)
+		get_target_dir(&workspace_dir, target, profile_dir)
 	}
 }
 
-/// This function determines where 'target' dir is and suffixes it with 'release' or 'debug'
+/// This function determines where 'target' dir is and suffixes it with the profile directory (e.g. 'release' or 'debug')
 /// to determine where the compiled binary will be located.
-fn get_target_dir(project_root_dir: &Path, target: Option<String>, is_release: bool) -> crate::Result<PathBuf> {
+fn get_target_dir(project_root_dir: &Path, target: Option<String>, profile_dir: &str) -> crate::Result<PathBuf> {
 	let mut path: PathBuf = match std::env::var_os("CARGO_TARGET_DIR") {
 		Some(target_dir) => target_dir.into(),
 		None => {
@@ -820,7 +862,7 @@ fn get_target_dir(project_root_dir: &Path, target: Option<String>, is_release: b
 	if let Some(ref triple) = target {
 		path.push(triple);
 	}
-	path.push(if is_release { "release" } else { "debug" });
+	path.push(profile_dir);
 	Ok(path)
 }
 
@@ -939,6 +981,16 @@ fn millennium_config_to_bundle_settings(
 		None => config.macos.provider_short_name
 	};
 
+	let notarization_profile = match std::env::var_os("APPLE_NOTARIZATION_PROFILE") {
+		Some(notarization_profile) => Some(
+			notarization_profile
+				.to_str()
+				.expect("failed to convert APPLE_NOTARIZATION_PROFILE to string")
+				.to_string()
+		),
+		None => config.macos.notarization_profile
+	};
+
 	Ok(BundleSettings {
 		identifier: Some(config.identifier),
 		icon: Some(config.icon),
@@ -956,7 +1008,11 @@ fn millennium_config_to_bundle_settings(
 		external_bin: config.external_bin,
 		deb: DebianSettings {
 			depends: if depends.is_empty() { None } else { Some(depends) },
-			files: config.deb.files
+			files: config.deb.files,
+			preinst: config.deb.preinst,
+			postinst: config.deb.postinst,
+			prerm: config.deb.prerm,
+			postrm: config.deb.postrm
 		},
 		macos: MacOsSettings {
 			frameworks: config.macos.frameworks,
@@ -966,6 +1022,7 @@ fn millennium_config_to_bundle_settings(
 			signing_identity,
 			provider_short_name,
 			entitlements: config.macos.entitlements,
+			notarization_profile,
 			info_plist_path: {
 				let path = millennium_dir().join("Info.plist");
 				if path.exists() { Some(path) } else { None }
@@ -976,6 +1033,8 @@ fn millennium_config_to_bundle_settings(
 			tsp: config.windows.tsp,
 			digest_algorithm: config.windows.digest_algorithm,
 			certificate_thumbprint: config.windows.certificate_thumbprint,
+			certificate_path: config.windows.certificate_path.map(|path| millennium_dir().join(path)),
+			certificate_password: std::env::var("WINDOWS_CERTIFICATE_PASSWORD").ok(),
 			wix: config.windows.wix.map(|w| {
 				let mut wix = wix_settings(w);
 				wix.license = wix.license.map(|l| millennium_dir().join(l));
@@ -19,10 +19,13 @@ pub use anyhow::Result;
 mod build;
 mod dev;
 mod helpers;
+mod icon;
 mod info;
 mod init;
 mod interface;
+mod migrate;
 mod plugin;
+pub mod progress;
 mod signer;
 
 use std::{
@@ -77,8 +80,10 @@ struct Cli {
 enum Commands {
 	Build(build::Options),
 	Dev(dev::Options),
+	Icon(icon::Options),
 	Info(info::Options),
 	Init(init::Options),
+	Migrate(migrate::Options),
 	Plugin(plugin::Cli),
 	Signer(signer::Cli)
 }
@@ -165,16 +170,21 @@ where
 		eprintln!("Failed to attach logger: {}", err);
 	}
 
-	match cli.command {
-		Commands::Build(options) => build::command(options)?,
-		Commands::Dev(options) => dev::command(options)?,
-		Commands::Info(options) => info::command(options)?,
-		Commands::Init(options) => init::command(options)?,
-		Commands::Plugin(cli) => plugin::command(cli)?,
-		Commands::Signer(cli) => signer::command(cli)?
-	}
+	let result = match cli.command {
+		Commands::Build(options) => build::command(options),
+		Commands::Dev(options) => dev::command(options),
+		Commands::Icon(options) => icon::command(options),
+		Commands::Info(options) => info::command(options),
+		Commands::Init(options) => init::command(options),
+		Commands::Migrate(options) => migrate::command(options),
+		Commands::Plugin(cli) => plugin::command(cli),
+		Commands::Signer(cli) => signer::command(cli)
+	};
 
-	Ok(())
+	if let Err(e) = &result {
+		progress::emit(progress::ProgressEvent::Error { message: format!("{:#}", e) });
+	}
+	result
 }
 
 /// This maps the occurrence of `--verbose` flags to the correct log level
@@ -208,7 +218,7 @@ pub trait CommandExt {
 
 impl CommandExt for Command {
 	fn piped(&mut self) -> std::io::Result<ExitStatus> {
-		self.stdout(os_pipe::dup_stdout()?);
+		self.stdout(progress::stdout()?);
 		self.stderr(os_pipe::dup_stderr()?);
 
 		let program = self.get_program().to_string_lossy().into_owned();
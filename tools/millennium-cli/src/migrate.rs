@@ -0,0 +1,274 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::Path};
+
+use anyhow::Context;
+use clap::Parser;
+use log::{info, warn};
+use serde::Serialize;
+use serde_json::{json, Map, Value as JsonValue};
+
+use crate::{
+	helpers::{app_paths::millennium_dir, config::Config},
+	Result
+};
+
+/// The config files that are migrated, if they exist.
+const CONFIG_FILES: [&str; 4] = [".millenniumrc", ".millenniumrc.linux", ".millenniumrc.macos", ".millenniumrc.windows"];
+
+/// A change to the config schema, and the version of Millennium that introduced it.
+struct Migration {
+	version: &'static str,
+	/// Applies the migration to the config, returning a description of each change it made.
+	apply: fn(&mut JsonValue) -> Vec<String>
+}
+
+/// Every known schema change, oldest first. Migrations must leave an already migrated config untouched.
+const MIGRATIONS: &[Migration] = &[
+	Migration {
+		version: "1.0.0-beta.1",
+		apply: migrate_from_tauri
+	},
+	Migration {
+		version: "1.0.0-beta.4",
+		apply: migrate_webview_install_mode
+	}
+];
+
+#[derive(Debug, Parser)]
+#[clap(about = "Upgrade your .millenniumrc to the latest configuration schema")]
+pub struct Options {
+	/// Print the changes without writing them.
+	#[clap(long)]
+	dry_run: bool
+}
+
+pub fn command(options: Options) -> Result<()> {
+	let millennium_path = millennium_dir();
+	let mut migrated_any = false;
+	for name in CONFIG_FILES {
+		let path = millennium_path.join(name);
+		if path.exists() {
+			migrated_any |= migrate_file(&path, options.dry_run)?;
+		}
+	}
+
+	if !migrated_any {
+		info!("Your configuration is already up to date.");
+	} else if options.dry_run {
+		info!("Run again without `--dry-run` to apply the changes.");
+	}
+	Ok(())
+}
+
+/// Migrates a single config file, returning whether anything changed.
+fn migrate_file(path: &Path, dry_run: bool) -> Result<bool> {
+	let name = path.file_name().unwrap().to_string_lossy();
+	let mut config: JsonValue = millennium_utils::config::parse::parse_value(path.to_path_buf())?;
+
+	let mut changes = Vec::new();
+	for migration in MIGRATIONS {
+		changes.extend((migration.apply)(&mut config).into_iter().map(|change| (migration.version, change)));
+	}
+	if changes.is_empty() {
+		return Ok(false);
+	}
+
+	info!(action = "Migrating"; "{}", name);
+	for (version, change) in &changes {
+		info!("        [{}] {}", version, change);
+	}
+
+	// platform-specific files only contain the fields they override, so only the main config must be complete
+	if name == ".millenniumrc" {
+		if let Err(e) = serde_json::from_value::<Config>(config.clone()) {
+			warn!("{} still doesn't match the configuration schema after migrating: {}", name, e);
+		}
+	}
+
+	if !dry_run {
+		let backup_path = path.with_file_name(format!("{}.bak", name));
+		fs::copy(path, &backup_path).with_context(|| format!("failed to back up {}", name))?;
+
+		let mut contents = Vec::new();
+		let mut serializer = serde_json::Serializer::with_formatter(&mut contents, serde_json::ser::PrettyFormatter::with_indent(b"\t"));
+		config.serialize(&mut serializer)?;
+		contents.push(b'\n');
+		fs::write(path, contents).with_context(|| format!("failed to write {}", name))?;
+
+		info!(action = "Migrated"; "{}; the original was saved to {}", name, backup_path.display());
+		warn!("Comments in {} were not preserved; copy any you need back from the backup.", name);
+	}
+	Ok(true)
+}
+
+/// Configs ported from Tauri use `tauri` where Millennium uses `millennium`.
+fn migrate_from_tauri(config: &mut JsonValue) -> Vec<String> {
+	let mut changes = Vec::new();
+	if let Some(root) = config.as_object_mut() {
+		if rename(root, "tauri", "millennium") {
+			changes.push("renamed `tauri` to `millennium`".into());
+		}
+	}
+	if let Some(build) = config.get_mut("build").and_then(JsonValue::as_object_mut) {
+		if rename(build, "withGlobalTauri", "withGlobalMillennium") {
+			changes.push("renamed `build > withGlobalTauri` to `build > withGlobalMillennium`".into());
+		}
+	}
+	changes
+}
+
+/// `webviewFixedRuntimePath` and `wix > skipWebviewInstall` were replaced by `webviewInstallMode`.
+fn migrate_webview_install_mode(config: &mut JsonValue) -> Vec<String> {
+	let mut changes = Vec::new();
+	let windows = match config.pointer_mut("/millennium/bundle/windows").and_then(JsonValue::as_object_mut) {
+		Some(windows) => windows,
+		None => return changes
+	};
+
+	if let Some(path) = windows.remove("webviewFixedRuntimePath") {
+		let change = if path.is_null() {
+			"removed `millennium > bundle > windows > webviewFixedRuntimePath`, which was unset"
+		} else if windows.contains_key("webviewInstallMode") {
+			"removed `millennium > bundle > windows > webviewFixedRuntimePath`, as `webviewInstallMode` is already set"
+		} else {
+			windows.insert("webviewInstallMode".into(), json!({ "type": "fixedRuntime", "path": path }));
+			"moved `millennium > bundle > windows > webviewFixedRuntimePath` to `webviewInstallMode` with type `fixedRuntime`"
+		};
+		changes.push(change.into());
+	}
+
+	let skip = windows
+		.get_mut("wix")
+		.and_then(JsonValue::as_object_mut)
+		.and_then(|wix| wix.remove("skipWebviewInstall"));
+	if let Some(skip) = skip {
+		// a fixed runtime takes precedence, as it did before the option was removed
+		let change = if skip != JsonValue::Bool(true) {
+			"removed `millennium > bundle > windows > wix > skipWebviewInstall`, which was disabled"
+		} else if windows.contains_key("webviewInstallMode") {
+			"removed `millennium > bundle > windows > wix > skipWebviewInstall`, as `webviewInstallMode` is already set"
+		} else {
+			windows.insert("webviewInstallMode".into(), json!({ "type": "skip" }));
+			"replaced `millennium > bundle > windows > wix > skipWebviewInstall` with `webviewInstallMode` of type `skip`"
+		};
+		changes.push(change.into());
+	}
+
+	changes
+}
+
+/// Renames `from` to `to`, unless the object already has `to`. Returns whether the key was renamed.
+fn rename(object: &mut Map<String, JsonValue>, from: &str, to: &str) -> bool {
+	if object.contains_key(to) {
+		return false;
+	}
+	match object.remove(from) {
+		Some(value) => {
+			object.insert(to.into(), value);
+			true
+		}
+		None => false
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::{migrate_from_tauri, migrate_webview_install_mode, MIGRATIONS};
+
+	#[test]
+	fn migrates_from_tauri() {
+		let mut config = json!({ "build": { "withGlobalTauri": true }, "tauri": { "bundle": {} } });
+		assert_eq!(migrate_from_tauri(&mut config).len(), 2);
+		assert_eq!(config, json!({ "build": { "withGlobalMillennium": true }, "millennium": { "bundle": {} } }));
+		assert!(migrate_from_tauri(&mut config).is_empty());
+
+		// an existing key is never overwritten
+		let mut config = json!({ "tauri": { "a": 1 }, "millennium": { "b": 2 } });
+		assert!(migrate_from_tauri(&mut config).is_empty());
+		assert_eq!(config, json!({ "tauri": { "a": 1 }, "millennium": { "b": 2 } }));
+	}
+
+	#[test]
+	fn migrates_webview_install_mode() {
+		let mut config = json!({ "millennium": { "bundle": { "windows": { "webviewFixedRuntimePath": "./webview" } } } });
+		assert_eq!(
+			migrate_webview_install_mode(&mut config),
+			vec!["moved `millennium > bundle > windows > webviewFixedRuntimePath` to `webviewInstallMode` with type `fixedRuntime`"]
+		);
+		assert_eq!(
+			config,
+			json!({ "millennium": { "bundle": { "windows": { "webviewInstallMode": { "type": "fixedRuntime", "path": "./webview" } } } } })
+		);
+		assert!(migrate_webview_install_mode(&mut config).is_empty());
+
+		let mut config = json!({ "millennium": { "bundle": { "windows": { "wix": { "skipWebviewInstall": true } } } } });
+		assert_eq!(
+			migrate_webview_install_mode(&mut config),
+			vec!["replaced `millennium > bundle > windows > wix > skipWebviewInstall` with `webviewInstallMode` of type `skip`"]
+		);
+		assert_eq!(config, json!({ "millennium": { "bundle": { "windows": { "wix": {}, "webviewInstallMode": { "type": "skip" } } } } }));
+
+		let mut config = json!({ "millennium": { "bundle": { "windows": { "webviewFixedRuntimePath": null, "wix": { "skipWebviewInstall": false } } } } });
+		assert_eq!(
+			migrate_webview_install_mode(&mut config),
+			vec![
+				"removed `millennium > bundle > windows > webviewFixedRuntimePath`, which was unset",
+				"removed `millennium > bundle > windows > wix > skipWebviewInstall`, which was disabled"
+			]
+		);
+		assert_eq!(config, json!({ "millennium": { "bundle": { "windows": { "wix": {} } } } }));
+	}
+
+	#[test]
+	fn fixed_runtime_takes_precedence_over_skip() {
+		let mut config = json!({
+			"millennium": { "bundle": { "windows": { "webviewFixedRuntimePath": "./webview", "wix": { "skipWebviewInstall": true } } } }
+		});
+		assert_eq!(
+			migrate_webview_install_mode(&mut config),
+			vec![
+				"moved `millennium > bundle > windows > webviewFixedRuntimePath` to `webviewInstallMode` with type `fixedRuntime`",
+				"removed `millennium > bundle > windows > wix > skipWebviewInstall`, as `webviewInstallMode` is already set"
+			]
+		);
+		assert_eq!(
+			config,
+			json!({ "millennium": { "bundle": { "windows": { "wix": {}, "webviewInstallMode": { "type": "fixedRuntime", "path": "./webview" } } } } })
+		);
+	}
+
+	#[test]
+	fn migrations_are_ordered_and_idempotent() {
+		let mut config = json!({
+			"build": { "withGlobalTauri": true },
+			"tauri": { "bundle": { "windows": { "webviewFixedRuntimePath": "./webview", "wix": { "skipWebviewInstall": true } } } }
+		});
+		let changes: usize = MIGRATIONS.iter().map(|migration| (migration.apply)(&mut config).len()).sum();
+		assert_eq!(changes, 4);
+		assert_eq!(
+			config,
+			json!({
+				"build": { "withGlobalMillennium": true },
+				"millennium": { "bundle": { "windows": { "wix": {}, "webviewInstallMode": { "type": "fixedRuntime", "path": "./webview" } } } }
+			})
+		);
+		assert!(MIGRATIONS.iter().all(|migration| (migration.apply)(&mut config).is_empty()));
+	}
+}
@@ -19,6 +19,7 @@ use clap::{Parser, Subcommand};
 use crate::Result;
 
 mod init;
+mod new;
 
 #[derive(Parser)]
 #[clap(author, version, about = "Manage Millennium plugins", subcommand_required(true), arg_required_else_help(true))]
@@ -29,12 +30,14 @@ pub struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-	Init(init::Options)
+	Init(init::Options),
+	New(new::Options)
 }
 
 pub fn command(cli: Cli) -> Result<()> {
 	match cli.command {
-		Commands::Init(options) => init::command(options)?
+		Commands::Init(options) => init::command(options)?,
+		Commands::New(options) => new::command(options)?
 	}
 
 	Ok(())
@@ -14,7 +14,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{collections::BTreeMap, env::current_dir, fs::remove_dir_all, path::PathBuf};
+use std::{
+	collections::BTreeMap,
+	env::current_dir,
+	fs::remove_dir_all,
+	path::PathBuf,
+	process::Command
+};
 
 use anyhow::Context;
 use clap::Parser;
@@ -22,6 +28,7 @@ use handlebars::{to_json, Handlebars};
 use heck::{ToKebabCase, ToSnakeCase};
 use include_dir::{include_dir, Dir};
 use log::warn;
+use time::OffsetDateTime;
 
 use crate::Result;
 use crate::{
@@ -29,44 +36,58 @@ use crate::{
 	VersionMetadata
 };
 
-// TODO
-const BACKEND_PLUGIN_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates/basic");
-const API_PLUGIN_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates/basic");
+const BACKEND_PLUGIN_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates/plugin");
+/// Rendered on top of [`BACKEND_PLUGIN_DIR`] when the plugin has a JavaScript API.
+const API_PLUGIN_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates/plugin-api");
 
 #[derive(Debug, Parser)]
 #[clap(about = "Initializes a Millennium plugin project")]
 pub struct Options {
 	/// Name of your Millennium plugin
 	#[clap(short = 'n', long = "name")]
-	plugin_name: String,
+	pub(super) plugin_name: String,
 	/// Initializes a Millennium plugin with TypeScript API
 	#[clap(long)]
-	api: bool,
+	pub(super) api: bool,
 	/// Initializes a Millennium core plugin (internal usage)
 	#[clap(long, hide(true))]
-	pyke: bool,
+	pub(super) pyke: bool,
 	/// Set target directory for init
 	#[clap(short, long)]
 	#[clap(default_value_t = current_dir().expect("failed to read cwd").display().to_string())]
-	directory: String,
+	pub(super) directory: String,
 	/// Path of the Millennium project to use (relative to the cwd)
 	#[clap(short, long)]
-	millennium_path: Option<PathBuf>,
-	/// Author name
+	pub(super) millennium_path: Option<PathBuf>,
+	/// Author name, defaults to `user.name` from your git config
 	#[clap(short, long)]
-	author: Option<String>
+	pub(super) author: Option<String>
 }
 
 impl Options {
-	fn load(&mut self) {
+	fn load(&mut self) -> Result<()> {
 		if self.author.is_none() {
-			self.author.replace(if self.pyke { "pykeio".into() } else { "You".into() });
+			let author = if self.pyke { Some("pykeio".into()) } else { git_user_name() };
+			self.author = Some(author.context("failed to read `user.name` from your git config; pass the author name with `--author`")?);
 		}
+		Ok(())
+	}
+}
+
+/// The `user.name` from the user's git config, if it is set.
+fn git_user_name() -> Option<String> {
+	let output = Command::new("git").args(["config", "user.name"]).output().ok()?;
+	let name = String::from_utf8(output.stdout).ok()?;
+	let name = name.trim();
+	if output.status.success() && !name.is_empty() {
+		Some(name.into())
+	} else {
+		None
 	}
 }
 
 pub fn command(mut options: Options) -> Result<()> {
-	options.load();
+	options.load()?;
 	let template_target_path = PathBuf::from(options.directory).join(&format!("millennium-plugin-{}", options.plugin_name.to_kebab_case()));
 	let metadata = serde_json::from_str::<VersionMetadata>(include_str!("../../metadata.json"))?;
 	if template_target_path.exists() {
@@ -96,6 +117,8 @@ pub fn command(mut options: Options) -> Result<()> {
 		data.insert("millennium_dep", to_json(millennium_dep));
 		data.insert("millennium_example_dep", to_json(millennium_example_dep));
 		data.insert("millennium_build_dep", to_json(millennium_build_dep));
+		data.insert("millennium_version", to_json(&metadata.millennium));
+		data.insert("license_header", to_json(apache_license_header(options.author.as_deref().unwrap_or_default())));
 		data.insert("author", to_json(options.author));
 
 		if options.pyke {
@@ -141,8 +164,32 @@ pub fn command(mut options: Options) -> Result<()> {
 			);
 		}
 
-		template::render(&handlebars, &data, if options.api { &API_PLUGIN_DIR } else { &BACKEND_PLUGIN_DIR }, &template_target_path, "")
-			.with_context(|| "failed to render Millennium> template")?;
+		template::render(&handlebars, &data, &BACKEND_PLUGIN_DIR, &template_target_path, "").with_context(|| "failed to render plugin template")?;
+		if options.api {
+			template::render(&handlebars, &data, &API_PLUGIN_DIR, &template_target_path, "").with_context(|| "failed to render plugin API template")?;
+		}
 	}
 	Ok(())
 }
+
+/// The standard Apache-2.0 header for the source files of a new plugin, copyrighted to `author` this year.
+fn apache_license_header(author: &str) -> String {
+	let year = OffsetDateTime::now_utc().year();
+	format!(
+		"// Copyright {} {}
+		//
+		// Licensed under the Apache License, Version 2.0 (the \"License\");
+		// you may not use this file except in compliance with the License.
+		// You may obtain a copy of the License at
+		//
+		//     http://www.apache.org/licenses/LICENSE-2.0
+		//
+		// Unless required by applicable law or agreed to in writing, software
+		// distributed under the License is distributed on an \"AS IS\" BASIS,
+		// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+		// See the License for the specific language governing permissions and
+		// limitations under the License.\n\n",
+		year, author
+	)
+	.replace('\t', "")
+}
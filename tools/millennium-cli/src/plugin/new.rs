@@ -0,0 +1,59 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{env::current_dir, path::PathBuf};
+
+use clap::Parser;
+
+use super::init;
+use crate::Result;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Creates a new Millennium plugin crate, with a JavaScript API package")]
+pub struct Options {
+	/// Name of your Millennium plugin
+	plugin_name: String,
+	/// Don't create the JavaScript API package
+	#[clap(long)]
+	no_api: bool,
+	/// Directory to create the plugin crate in
+	#[clap(short, long)]
+	#[clap(default_value_t = current_dir().expect("failed to read cwd").display().to_string())]
+	directory: String,
+	/// Path of the Millennium project to use (relative to the cwd)
+	#[clap(short, long)]
+	millennium_path: Option<PathBuf>,
+	/// Author name, defaults to `user.name` from your git config
+	#[clap(short, long)]
+	author: Option<String>
+}
+
+impl From<Options> for init::Options {
+	fn from(options: Options) -> Self {
+		Self {
+			plugin_name: options.plugin_name,
+			api: !options.no_api,
+			pyke: false,
+			directory: options.directory,
+			millennium_path: options.millennium_path,
+			author: options.author
+		}
+	}
+}
+
+pub fn command(options: Options) -> Result<()> {
+	init::command(options.into())
+}
@@ -0,0 +1,139 @@
+// Copyright 2022 pyke.io
+//           2019-2021 Tauri Programme within The Commons Conservancy
+//                     [https://tauri.studio/]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Machine-readable progress events, for IDE integrations and GUIs that wrap the CLI.
+//!
+//! The binary prints them as JSON lines on stdout with `--message-format json`; the Node.js binding forwards them to
+//! a JavaScript callback.
+
+use std::{
+	path::PathBuf,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		RwLock
+	}
+};
+
+use clap::ArgEnum;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+type Listener = Box<dyn Fn(&ProgressEvent) + Send + Sync>;
+
+static LISTENER: Lazy<RwLock<Option<Listener>>> = Lazy::new(Default::default);
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+
+/// How `build` and `dev` report their progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum MessageFormat {
+	/// Human readable logs on stderr.
+	Human,
+	/// A JSON object per line on stdout, in addition to the logs on stderr. Anything else that would be printed to
+	/// stdout, including the output of `beforeDevCommand`, `beforeBuildCommand`, cargo and the app, goes to stderr.
+	Json
+}
+
+/// A step of `build` or `dev`. Serialized with an `event` field naming the variant, e.g.
+/// `{"event":"artifact-produced","path":"..."}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum ProgressEvent {
+	/// Cargo started compiling the app, for the given target triple or the host.
+	CompileStarted { target: Option<String> },
+	/// Cargo started compiling a crate.
+	CompileProgress {
+		#[serde(rename = "crate")]
+		crate_name: String,
+		version: String
+	},
+	/// The compiled app is being packaged into the given bundle formats, e.g. `deb` or `msi`.
+	Bundling { formats: Vec<String> },
+	/// A file was produced: the app binary, a bundle, or an updater signature.
+	ArtifactProduced { path: PathBuf },
+	/// The command failed.
+	Error { message: String }
+}
+
+impl ProgressEvent {
+	/// Serializes the event as a single line of JSON.
+	pub fn to_json(&self) -> String {
+		serde_json::to_string(self).expect("progress events are always serializable")
+	}
+}
+
+/// Sets the function receiving every progress event, replacing the previous one.
+pub fn set_listener<F: Fn(&ProgressEvent) + Send + Sync + 'static>(listener: F) {
+	*LISTENER.write().unwrap() = Some(Box::new(listener));
+}
+
+/// Applies the `--message-format` option of a command.
+pub(crate) fn set_format(format: MessageFormat) {
+	if format == MessageFormat::Json {
+		STDOUT_RESERVED.store(true, Ordering::Relaxed);
+		set_listener(|event| println!("{}", event.to_json()));
+	}
+}
+
+/// Returns the stream that child processes and the CLI itself should print their regular output to: stdout, unless it is
+/// reserved for JSON progress events, in which case it is stderr.
+pub(crate) fn stdout() -> std::io::Result<os_pipe::PipeWriter> {
+	if STDOUT_RESERVED.load(Ordering::Relaxed) {
+		os_pipe::dup_stderr()
+	} else {
+		os_pipe::dup_stdout()
+	}
+}
+
+pub(crate) fn emit(event: ProgressEvent) {
+	if let Some(listener) = &*LISTENER.read().unwrap() {
+		listener(&event);
+	}
+}
+
+/// Emits [`ProgressEvent::CompileProgress`] if `line` is cargo's `Compiling <crate> v<version>` status.
+pub(crate) fn emit_cargo_status(line: &str) {
+	let line = strip_ansi(line);
+	let mut words = line.split_whitespace();
+	if words.next() != Some("Compiling") {
+		return;
+	}
+	if let (Some(crate_name), Some(version)) = (words.next(), words.next()) {
+		emit(ProgressEvent::CompileProgress {
+			crate_name: crate_name.into(),
+			version: version.trim_start_matches('v').into()
+		});
+	}
+}
+
+/// Removes the color codes from cargo's output, which is colored even when piped.
+fn strip_ansi(line: &str) -> String {
+	let mut output = String::with_capacity(line.len());
+	let mut chars = line.chars();
+	while let Some(c) = chars.next() {
+		if c == '\u{1b}' {
+			// skip `ESC [ ... <letter>`
+			for c in chars.by_ref() {
+				if c.is_ascii_alphabetic() {
+					break;
+				}
+			}
+		} else {
+			output.push(c);
+		}
+	}
+	output
+}
@@ -0,0 +1,6 @@
+{{{ license_header }}}import { invoke } from '@pyke/millennium-api/millennium';
+
+/** Echoes `value` back from the `ping` command of the plugin. */
+export async function ping(value: string): Promise<string> {
+	return await invoke('plugin:{{ plugin_name }}|ping', { value });
+}
@@ -0,0 +1,17 @@
+{{{ license_header }}}use millennium::{
+	plugin::{Builder, MillenniumPlugin},
+	Runtime
+};
+
+/// Echoes `value` back to the webview. Called from JavaScript with `invoke('plugin:{{ plugin_name }}|ping', { value })`.
+#[millennium::command]
+fn ping(value: String) -> String {
+	value
+}
+
+/// Initializes the plugin. Register it with `millennium::Builder::plugin(millennium_plugin_{{ plugin_name_snake_case }}::init())`.
+pub fn init<R: Runtime>() -> MillenniumPlugin<R> {
+	Builder::new("{{ plugin_name }}")
+		.invoke_handler(millennium::generate_handler![ping])
+		.build()
+}